@@ -0,0 +1,107 @@
+use super::settings::{account_jira_client, delete_account_token, store_account_token};
+use super::sync::{parse_category_rules, run_exclusive_sync, sync_response, SyncLock};
+use crate::db::{
+    delete_jira_account as delete_account_row, get_jira_account, insert_jira_account,
+    list_jira_accounts as list_account_rows, DbPool,
+};
+use crate::errors::{AppError, DbError};
+use crate::jira::JiraAuthMethod;
+use crate::models::JiraAccount;
+use crate::services::outbox::flush_outbox;
+use crate::services::source_sync::sync_source;
+
+#[tauri::command]
+pub async fn add_jira_account(
+    db: tauri::State<'_, DbPool>,
+    name: String,
+    jira_url: String,
+    email: String,
+    auth_method: Option<JiraAuthMethod>,
+    token: String,
+) -> Result<JiraAccount, AppError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::Config("Account name is required".to_string()));
+    }
+    let base_url = jira_url.trim().trim_end_matches('/').to_string();
+
+    let db_clone = db.0.clone();
+    let account = tauri::async_runtime::spawn_blocking(move || {
+        let conn = db_clone.lock().map_err(|_| DbError::LockFailed)?;
+        insert_jira_account(
+            &conn,
+            &name,
+            &base_url,
+            &email,
+            auth_method.unwrap_or_default(),
+        )
+    })
+    .await
+    .map_err(|_| AppError::Internal("Task join failed".to_string()))??;
+
+    store_account_token(account.id, &token)?;
+    Ok(account)
+}
+
+#[tauri::command]
+pub async fn list_jira_accounts(
+    db: tauri::State<'_, DbPool>,
+) -> Result<Vec<JiraAccount>, AppError> {
+    let db_clone = db.0.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = db_clone.lock().map_err(|_| DbError::LockFailed)?;
+        list_account_rows(&conn)
+    })
+    .await
+    .map_err(|_| AppError::Internal("Task join failed".to_string()))?
+}
+
+#[tauri::command]
+pub async fn remove_jira_account(
+    db: tauri::State<'_, DbPool>,
+    account_id: i64,
+) -> Result<bool, AppError> {
+    let db_clone = db.0.clone();
+    let removed = tauri::async_runtime::spawn_blocking(move || {
+        let conn = db_clone.lock().map_err(|_| DbError::LockFailed)?;
+        delete_account_row(&conn, account_id)
+    })
+    .await
+    .map_err(|_| AppError::Internal("Task join failed".to_string()))??;
+
+    if removed {
+        delete_account_token(account_id)?;
+    }
+    Ok(removed)
+}
+
+#[tauri::command]
+pub async fn trigger_account_sync(
+    lock: tauri::State<'_, SyncLock>,
+    db: tauri::State<'_, DbPool>,
+    account_id: i64,
+    category_rules_json: String,
+    app_handle: tauri::AppHandle,
+) -> Result<serde_json::Value, AppError> {
+    run_exclusive_sync(&lock, &app_handle, async {
+        let db_clone = db.0.clone();
+        let account = tauri::async_runtime::spawn_blocking(move || {
+            let conn = db_clone.lock().map_err(|_| DbError::LockFailed)?;
+            get_jira_account(&conn, account_id)
+        })
+        .await
+        .map_err(|_| AppError::Internal("Task join failed".to_string()))??
+        .ok_or_else(|| AppError::Config(format!("Unknown Jira account: {}", account_id)))?;
+
+        let category_rules = parse_category_rules(&category_rules_json)?;
+        let client = account_jira_client(&app_handle, account).await?;
+
+        // Send the account's offline write-backs first so the sync pulls their results
+        if let Err(e) = flush_outbox(&client, &db, Some(account_id), false).await {
+            log::warn!("Outbox flush failed: {}", e);
+        }
+        let outcome = sync_source(&client, db.0.clone(), &category_rules, &app_handle).await?;
+        Ok(sync_response(&outcome))
+    })
+    .await
+}
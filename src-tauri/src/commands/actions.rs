@@ -0,0 +1,303 @@
+use super::settings::{
+    account_jira_client, conflict_policy, saved_jira_client, ticket_jira_client,
+};
+use crate::db::{
+    self, account_ticket_key, clear_local_change, enqueue_outbox, get_ticket_by_key,
+    jira_issue_key, record_local_change, update_ticket_status, upsert_synced_ticket, DbPool,
+};
+use crate::errors::{AppError, JiraError};
+use crate::jira::JiraClient;
+use crate::models::{
+    CreateIssueOutcome, LocalChange, LocalField, NewIssue, OutboxAction, OutboxFlushSummary,
+    OutboxItem, Ticket, TicketContent, TicketTransition,
+};
+use crate::services::outbox::{self, is_offline_error};
+
+#[tauri::command]
+pub async fn get_ticket_transitions(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+    jira_key: String,
+) -> Result<Vec<TicketTransition>, AppError> {
+    let client = ticket_jira_client(&app_handle, &db, &jira_key).await?;
+    client.get_transitions(jira_issue_key(&jira_key)).await
+}
+
+/// Description and comments of a ticket as Markdown, fetched from Jira on demand.
+#[tauri::command]
+pub async fn get_ticket_content(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+    jira_key: String,
+) -> Result<TicketContent, AppError> {
+    let client = ticket_jira_client(&app_handle, &db, &jira_key).await?;
+    client.get_issue_content(jira_issue_key(&jira_key)).await
+}
+
+/// Moves a ticket through a workflow transition. The local row is updated
+/// before calling Jira so the dashboard reflects the change immediately, and
+/// is restored if Jira rejects the transition. If Jira can't be reached the
+/// transition is queued in the outbox and the edit stays as a pending local
+/// change, protected from syncs by the conflict policy.
+#[tauri::command]
+pub async fn transition_ticket(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+    jira_key: String,
+    transition_id: String,
+) -> Result<Option<Ticket>, AppError> {
+    let client = ticket_jira_client(&app_handle, &db, &jira_key).await?;
+    let issue_key = jira_issue_key(&jira_key).to_string();
+    let transitions = match client.get_transitions(&issue_key).await {
+        // The target status is unknown offline, so only the push is queued
+        Err(e) if is_offline_error(&e) => {
+            return queue_write_back(&db, &jira_key, OutboxAction::Transition { transition_id })
+                .await;
+        }
+        result => result?,
+    };
+    let transition = transitions
+        .into_iter()
+        .find(|t| t.id == transition_id)
+        .ok_or_else(|| {
+            AppError::Config(format!(
+                "Transition {} is not available for {}",
+                transition_id, jira_key
+            ))
+        })?;
+
+    let key = jira_key.clone();
+    let previous = db
+        .run(move |conn| {
+            let previous = get_ticket_by_key(conn, &key)?;
+            if let Some(ticket) = &previous {
+                let resolved_at = if transition.resolves {
+                    Some(
+                        ticket
+                            .resolved_at
+                            .clone()
+                            .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+                    )
+                } else {
+                    None
+                };
+                update_ticket_status(conn, &key, &transition.to_status, resolved_at.as_deref())?;
+                record_local_change(
+                    conn,
+                    &key,
+                    LocalField::Status,
+                    Some(&ticket.status),
+                    &chrono::Utc::now().to_rfc3339(),
+                )?;
+            }
+            Ok(previous)
+        })
+        .await?;
+
+    match client.transition_issue(&issue_key, &transition_id).await {
+        Ok(()) => {}
+        Err(e) if is_offline_error(&e) => {
+            return queue_write_back(&db, &jira_key, OutboxAction::Transition { transition_id })
+                .await;
+        }
+        Err(e) => {
+            if let Some(ticket) = previous {
+                db.run(move |conn| {
+                    update_ticket_status(
+                        conn,
+                        &ticket.jira_key,
+                        &ticket.status,
+                        ticket.resolved_at.as_deref(),
+                    )?;
+                    clear_local_change(conn, &ticket.jira_key, LocalField::Status)
+                })
+                .await?;
+            }
+            return Err(e);
+        }
+    }
+
+    db.run(move |conn| {
+        clear_local_change(conn, &jira_key, LocalField::Status)?;
+        get_ticket_by_key(conn, &jira_key)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn assign_ticket(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+    jira_key: String,
+    assignee: Option<String>,
+) -> Result<Option<Ticket>, AppError> {
+    let client = ticket_jira_client(&app_handle, &db, &jira_key).await?;
+    match client
+        .assign_issue(jira_issue_key(&jira_key), assignee.as_deref())
+        .await
+    {
+        Err(e) if is_offline_error(&e) => {
+            queue_write_back(&db, &jira_key, OutboxAction::Assign { assignee }).await
+        }
+        result => {
+            result?;
+            refresh_ticket(&app_handle, &client, &db, &jira_key).await
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn comment_on_ticket(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+    jira_key: String,
+    body: String,
+) -> Result<Option<Ticket>, AppError> {
+    if body.trim().is_empty() {
+        return Err(AppError::Config("Comment body is empty".to_string()));
+    }
+
+    let client = ticket_jira_client(&app_handle, &db, &jira_key).await?;
+    match client.add_comment(jira_issue_key(&jira_key), &body).await {
+        Err(e) if is_offline_error(&e) => {
+            queue_write_back(&db, &jira_key, OutboxAction::Comment { body }).await
+        }
+        result => {
+            result?;
+            refresh_ticket(&app_handle, &client, &db, &jira_key).await
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn create_issue(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+    issue: NewIssue,
+) -> Result<CreateIssueOutcome, AppError> {
+    let client = saved_jira_client(&app_handle).await?;
+
+    let key = match client.create_issue(&issue).await {
+        Ok(key) => key,
+        Err(AppError::JiraApi(JiraError::Validation(errors))) => {
+            return Ok(CreateIssueOutcome::Rejected { errors });
+        }
+        Err(e) => return Err(e),
+    };
+
+    let ticket = refresh_ticket(&app_handle, &client, &db, &key).await?;
+    Ok(CreateIssueOutcome::Created { ticket })
+}
+
+/// Queues a write-back Jira couldn't be reached for and returns the local
+/// ticket as it stands.
+async fn queue_write_back(
+    db: &DbPool,
+    jira_key: &str,
+    action: OutboxAction,
+) -> Result<Option<Ticket>, AppError> {
+    log::info!("Jira unreachable, queued {:?} for {}", action, jira_key);
+    let key = jira_key.to_string();
+    db.run(move |conn| {
+        enqueue_outbox(conn, &key, &action, chrono::Utc::now())?;
+        get_ticket_by_key(conn, &key)
+    })
+    .await
+}
+
+/// Re-reads one issue from Jira after a write and stores it under its
+/// stored key, keeping the local category and account since a single-issue
+/// fetch doesn't know them.
+async fn refresh_ticket(
+    app_handle: &tauri::AppHandle,
+    client: &JiraClient,
+    db: &DbPool,
+    jira_key: &str,
+) -> Result<Option<Ticket>, AppError> {
+    let issue = client.get_issue(jira_issue_key(jira_key)).await?;
+    let mut ticket = JiraClient::convert_issue_to_ticket(issue, client.field_mapping());
+    let key = jira_key.to_string();
+    let policy = conflict_policy(app_handle)?;
+
+    db.run(move |conn| {
+        if let Some(existing) = get_ticket_by_key(conn, &key)? {
+            ticket.category = existing.category;
+            ticket.category_confidence = existing.category_confidence;
+            if let Some(account_id) = existing.account_id {
+                ticket.jira_key = account_ticket_key(account_id, &ticket.jira_key);
+            }
+            ticket.account_id = existing.account_id;
+        }
+        upsert_synced_ticket(conn, &ticket, policy, None)?;
+        get_ticket_by_key(conn, &key)
+    })
+    .await
+}
+
+/// Local edits Jira hasn't confirmed yet; flagged conflicts come first.
+#[tauri::command]
+pub async fn get_pending_local_changes(
+    db: tauri::State<'_, DbPool>,
+) -> Result<Vec<LocalChange>, AppError> {
+    db.run(db::get_pending_local_changes).await
+}
+
+/// Settles a pending local edit. `keep_local` keeps the app's value over
+/// whatever Jira reported; otherwise the edit is dropped and the ticket is
+/// re-read from Jira.
+#[tauri::command]
+pub async fn resolve_local_change(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+    jira_key: String,
+    field: LocalField,
+    keep_local: bool,
+) -> Result<Option<Ticket>, AppError> {
+    let key = jira_key.clone();
+    if keep_local {
+        return db
+            .run(move |conn| {
+                db::keep_local_change(conn, &key, field)?;
+                get_ticket_by_key(conn, &key)
+            })
+            .await;
+    }
+
+    db.run(move |conn| clear_local_change(conn, &key, field))
+        .await?;
+    let client = ticket_jira_client(&app_handle, &db, &jira_key).await?;
+    refresh_ticket(&app_handle, &client, &db, &jira_key).await
+}
+
+/// Write-backs queued while Jira was unreachable, including ones Jira later
+/// rejected.
+#[tauri::command]
+pub async fn get_outbox(db: tauri::State<'_, DbPool>) -> Result<Vec<OutboxItem>, AppError> {
+    db.run(db::get_outbox).await
+}
+
+/// Sends queued write-backs now, ignoring any retry backoff. Each account's
+/// items go to its own Jira site.
+#[tauri::command]
+pub async fn flush_outbox(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+) -> Result<OutboxFlushSummary, AppError> {
+    let client = saved_jira_client(&app_handle).await?;
+    let mut summary = outbox::flush_outbox(&client, &db, None, true).await?;
+
+    for account in db.run(db::list_jira_accounts).await? {
+        let account_id = account.id;
+        let client = account_jira_client(&app_handle, account).await?;
+        let account_summary = outbox::flush_outbox(&client, &db, Some(account_id), true).await?;
+        summary.sent += account_summary.sent;
+        summary.failed += account_summary.failed;
+        summary.pending += account_summary.pending;
+    }
+    Ok(summary)
+}
+
+#[tauri::command]
+pub async fn discard_outbox_item(db: tauri::State<'_, DbPool>, id: i64) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_outbox_item(conn, id)).await
+}
@@ -0,0 +1,40 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{AlertRule, AlertRuleInput};
+
+#[tauri::command]
+pub async fn list_alert_rules(db: tauri::State<'_, DbPool>) -> Result<Vec<AlertRule>, AppError> {
+    db.run(db::list_alert_rules).await
+}
+
+#[tauri::command]
+pub async fn create_alert_rule(
+    db: tauri::State<'_, DbPool>,
+    rule: AlertRuleInput,
+) -> Result<AlertRule, AppError> {
+    validate(&rule)?;
+    db.run(move |conn| db::create_alert_rule(conn, &rule)).await
+}
+
+#[tauri::command]
+pub async fn update_alert_rule(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+    rule: AlertRuleInput,
+) -> Result<Option<AlertRule>, AppError> {
+    validate(&rule)?;
+    db.run(move |conn| db::update_alert_rule(conn, id, &rule))
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_alert_rule(db: tauri::State<'_, DbPool>, id: i64) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_alert_rule(conn, id)).await
+}
+
+fn validate(rule: &AlertRuleInput) -> Result<(), AppError> {
+    if rule.name.trim().is_empty() {
+        return Err(AppError::Config("Alert rule name is required".to_string()));
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use crate::db::DbPool;
+use crate::errors::AppError;
+use crate::models::AlertSettings;
+use crate::services::alerts;
+use crate::services::notify::digest::build_weekly_digest;
+use crate::services::notify::email::{render_digest_email, DigestEmail, EmailSender};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+#[tauri::command]
+pub async fn save_alert_settings(
+    app_handle: AppHandle,
+    settings: AlertSettings,
+) -> Result<(), AppError> {
+    if !(0.0..=1.0).contains(&settings.sla_warn_ratio) {
+        return Err(AppError::Config(
+            "SLA warning ratio must be between 0 and 1".to_string(),
+        ));
+    }
+
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+
+    let settings_value = serde_json::to_value(&settings)
+        .map_err(|e| AppError::Config(format!("Failed to serialize settings: {}", e)))?;
+
+    store.set("alerts", settings_value);
+
+    store
+        .save()
+        .map_err(|e| AppError::Config(format!("Failed to save settings: {}", e)))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn load_alert_settings(app_handle: AppHandle) -> Result<AlertSettings, AppError> {
+    alerts::load_alert_settings(&app_handle)
+}
+
+/// Posts the weekly digest to Slack now and returns the text that was sent.
+#[tauri::command]
+pub async fn send_slack_digest(
+    db: tauri::State<'_, DbPool>,
+    app_handle: AppHandle,
+) -> Result<String, AppError> {
+    let digest = alerts::send_slack_digest(db.0.clone(), &app_handle).await?;
+    Ok(digest.render_text())
+}
+
+/// Emails the weekly digest and current SLA breaches. With `dry_run` nothing is
+/// sent; either way the rendered email is returned.
+#[tauri::command]
+pub async fn send_digest(
+    db: tauri::State<'_, DbPool>,
+    app_handle: AppHandle,
+    dry_run: Option<bool>,
+) -> Result<DigestEmail, AppError> {
+    let settings = alerts::load_alert_settings(&app_handle)?;
+    let email = db
+        .run(move |conn| {
+            let now = chrono::Utc::now().naive_utc();
+            let digest = build_weekly_digest(conn, now)?;
+            let breaches = alerts::sla_breaches(conn, &settings, now)?;
+            Ok(render_digest_email(&digest, &breaches))
+        })
+        .await?;
+
+    if !dry_run.unwrap_or(false) {
+        let smtp = crate::commands::settings::load_smtp_settings_internal(&app_handle)?;
+        let password = crate::commands::settings::get_smtp_password_internal()?;
+        EmailSender::new(&smtp, password)?.send(&email).await?;
+    }
+
+    Ok(email)
+}
@@ -0,0 +1,47 @@
+use super::settings::ticket_jira_client;
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::Attachment;
+use std::path::{Path, PathBuf};
+
+#[tauri::command]
+pub async fn get_ticket_attachments(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+) -> Result<Vec<Attachment>, AppError> {
+    db.run(move |conn| db::get_attachments(conn, &key)).await
+}
+
+/// Saves an attachment's contents to `dest` and returns the written path.
+/// When `dest` is an existing directory the file keeps its original name.
+#[tauri::command]
+pub async fn download_attachment(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+    id: String,
+    dest: String,
+) -> Result<String, AppError> {
+    let attachment = db
+        .run(move |conn| {
+            db::get_attachment(conn, &id)?
+                .ok_or_else(|| AppError::Config(format!("Unknown attachment: {}", id)))
+        })
+        .await?;
+
+    // Tickets from an added account are downloaded with that account's credentials
+    let client = ticket_jira_client(&app_handle, &db, &attachment.ticket_key).await?;
+    let bytes = client.download_attachment(&attachment.content_url).await?;
+
+    let mut path = PathBuf::from(&dest);
+    if path.is_dir() {
+        // Keep only the final component so a crafted name can't escape `dest`
+        let filename = Path::new(&attachment.filename).file_name().ok_or_else(|| {
+            AppError::Config(format!("Invalid attachment name: {}", attachment.filename))
+        })?;
+        path.push(filename);
+    }
+    std::fs::write(&path, bytes)
+        .map_err(|e| AppError::Config(format!("Failed to write {}: {}", path.display(), e)))?;
+
+    Ok(path.to_string_lossy().into_owned())
+}
@@ -0,0 +1,109 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{
+    Automation, AutomationAction, AutomationInput, AutomationLogEntry, AutomationSettings,
+};
+use crate::services::automations;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+/// Log entries returned when no limit is given.
+const DEFAULT_LOG_LIMIT: u32 = 200;
+
+#[tauri::command]
+pub async fn list_automations(db: tauri::State<'_, DbPool>) -> Result<Vec<Automation>, AppError> {
+    db.run(db::list_automations).await
+}
+
+#[tauri::command]
+pub async fn create_automation(
+    db: tauri::State<'_, DbPool>,
+    automation: AutomationInput,
+) -> Result<Automation, AppError> {
+    validate(&automation)?;
+    db.run(move |conn| db::create_automation(conn, &automation))
+        .await
+}
+
+#[tauri::command]
+pub async fn update_automation(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+    automation: AutomationInput,
+) -> Result<Option<Automation>, AppError> {
+    validate(&automation)?;
+    db.run(move |conn| db::update_automation(conn, id, &automation))
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_automation(db: tauri::State<'_, DbPool>, id: i64) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_automation(conn, id)).await
+}
+
+/// What automations did, newest first.
+#[tauri::command]
+pub async fn get_automation_log(
+    db: tauri::State<'_, DbPool>,
+    limit: Option<u32>,
+) -> Result<Vec<AutomationLogEntry>, AppError> {
+    let limit = limit.unwrap_or(DEFAULT_LOG_LIMIT);
+    db.run(move |conn| db::get_automation_log(conn, limit))
+        .await
+}
+
+#[tauri::command]
+pub async fn save_automation_settings(
+    app_handle: AppHandle,
+    settings: AutomationSettings,
+) -> Result<(), AppError> {
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+
+    let settings_value = serde_json::to_value(&settings)
+        .map_err(|e| AppError::Config(format!("Failed to serialize settings: {}", e)))?;
+
+    store.set("automations", settings_value);
+
+    store
+        .save()
+        .map_err(|e| AppError::Config(format!("Failed to save settings: {}", e)))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn load_automation_settings(
+    app_handle: AppHandle,
+) -> Result<AutomationSettings, AppError> {
+    automations::load_automation_settings(&app_handle)
+}
+
+fn validate(automation: &AutomationInput) -> Result<(), AppError> {
+    if automation.name.trim().is_empty() {
+        return Err(AppError::Config("Automation name is required".to_string()));
+    }
+    if automation.actions.is_empty() {
+        return Err(AppError::Config(
+            "Automation needs at least one action".to_string(),
+        ));
+    }
+    for action in &automation.actions {
+        let missing = match action {
+            AutomationAction::AddTag { tag } => tag.trim().is_empty().then_some("tag"),
+            AutomationAction::Comment { body } => body.trim().is_empty().then_some("comment"),
+            AutomationAction::Transition { transition_id } => {
+                transition_id.trim().is_empty().then_some("transition")
+            }
+            AutomationAction::Watch | AutomationAction::Notify { .. } => None,
+        };
+        if let Some(missing) = missing {
+            return Err(AppError::Config(format!(
+                "Automation action is missing its {}",
+                missing
+            )));
+        }
+    }
+    Ok(())
+}
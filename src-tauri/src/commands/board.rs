@@ -0,0 +1,36 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{BoardColumn, StatusCategory, TicketFilter, WipLimit};
+
+/// `filter`'s tickets as kanban columns by status category, with each
+/// column's WIP limit and whether it is exceeded.
+#[tauri::command]
+pub async fn get_board_columns(
+    db: tauri::State<'_, DbPool>,
+    filter: Option<TicketFilter>,
+) -> Result<Vec<BoardColumn>, AppError> {
+    let filter = filter.unwrap_or_default();
+    db.run(move |conn| db::get_board_columns(conn, &filter))
+        .await
+}
+
+#[tauri::command]
+pub async fn get_wip_limits(db: tauri::State<'_, DbPool>) -> Result<Vec<WipLimit>, AppError> {
+    db.run(db::get_wip_limits).await
+}
+
+/// Sets the WIP limit of a column; a missing `max_tickets` removes it.
+#[tauri::command]
+pub async fn set_wip_limit(
+    db: tauri::State<'_, DbPool>,
+    category: StatusCategory,
+    max_tickets: Option<u32>,
+) -> Result<(), AppError> {
+    if max_tickets == Some(0) {
+        return Err(AppError::Config(
+            "A WIP limit must allow at least one ticket".to_string(),
+        ));
+    }
+    db.run(move |conn| db::set_wip_limit(conn, category, max_tickets))
+        .await
+}
@@ -0,0 +1,81 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{CalendarEntry, ScheduledWork, TicketFilter};
+use crate::services::ics::build_calendar;
+use chrono::{Datelike, NaiveDate};
+
+/// Due dates, sprint starts and ends, and scheduled work in `month`
+/// (`YYYY-MM`), by date.
+#[tauri::command]
+pub async fn get_calendar_entries(
+    db: tauri::State<'_, DbPool>,
+    month: String,
+) -> Result<Vec<CalendarEntry>, AppError> {
+    let invalid = || AppError::Config(format!("Invalid month (expected YYYY-MM): {}", month));
+    let from =
+        NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d").map_err(|_| invalid())?;
+    let to = if from.month() == 12 {
+        NaiveDate::from_ymd_opt(from.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(from.year(), from.month() + 1, 1)
+    }
+    .ok_or_else(invalid)?;
+    db.run(move |conn| db::get_calendar_entries(conn, from, to))
+        .await
+}
+
+/// Plans work on ticket `key` for `date` (`YYYY-MM-DD`), with an optional
+/// note shown on the calendar instead of the ticket's summary.
+#[tauri::command]
+pub async fn schedule_work(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+    date: String,
+    note: Option<String>,
+) -> Result<ScheduledWork, AppError> {
+    let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|_| AppError::Config(format!("Invalid date (expected YYYY-MM-DD): {}", date)))?;
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    let now = chrono::Utc::now().to_rfc3339();
+    db.run(move |conn| db::schedule_work(conn, &key, date, note.as_deref(), &now))
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_scheduled_work(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_scheduled_work(conn, id))
+        .await
+}
+
+/// Writes an iCalendar file to `path` with the due dates of `filter`'s
+/// unresolved tickets and the start and end of their sprints, for import into
+/// a calendar app. Returns how many events it holds.
+#[tauri::command]
+pub async fn export_ics(
+    db: tauri::State<'_, DbPool>,
+    path: String,
+    filter: Option<TicketFilter>,
+) -> Result<usize, AppError> {
+    let filter = filter.unwrap_or_default();
+    let (tickets, sprints) = db
+        .run(move |conn| {
+            let tickets: Vec<_> = db::get_tickets(conn, &filter)?
+                .into_iter()
+                .filter(|t| t.resolved_at.is_none())
+                .collect();
+            let sprints = db::list_sprints(conn)?
+                .into_iter()
+                .filter(|s| tickets.iter().any(|t| t.sprint.as_ref() == Some(&s.name)))
+                .collect::<Vec<_>>();
+            Ok((tickets, sprints))
+        })
+        .await?;
+
+    let (calendar, events) = build_calendar(&tickets, &sprints, chrono::Utc::now().naive_utc());
+    std::fs::write(&path, calendar)
+        .map_err(|e| AppError::Config(format!("Failed to write {}: {}", path, e)))?;
+    Ok(events)
+}
@@ -0,0 +1,37 @@
+use crate::db::DbPool;
+use crate::errors::AppError;
+use crate::models::{CapacitySimulation, TicketFilter};
+use crate::services::capacity;
+
+/// Longest projection `simulate_capacity` accepts.
+const MAX_SIMULATION_WEEKS: u32 = 104;
+/// Largest staffing change `simulate_capacity` accepts, either way.
+const MAX_EXTRA_PEOPLE: u32 = 100;
+
+/// Projects the filtered backlog's burndown over `weeks` weeks with up to
+/// `extra_people` more people (fewer when negative), from recent per-person
+/// throughput.
+#[tauri::command]
+pub async fn simulate_capacity(
+    db: tauri::State<'_, DbPool>,
+    extra_people: i32,
+    weeks: u32,
+    filter: Option<TicketFilter>,
+) -> Result<CapacitySimulation, AppError> {
+    if weeks == 0 || weeks > MAX_SIMULATION_WEEKS {
+        return Err(AppError::Config(format!(
+            "Weeks must be between 1 and {}",
+            MAX_SIMULATION_WEEKS
+        )));
+    }
+    if extra_people.unsigned_abs() > MAX_EXTRA_PEOPLE {
+        return Err(AppError::Config(format!(
+            "Extra people must be between -{0} and {0}",
+            MAX_EXTRA_PEOPLE
+        )));
+    }
+    let filter = filter.unwrap_or_default();
+    let now = chrono::Utc::now().naive_utc();
+    db.run(move |conn| capacity::simulate_capacity(conn, &filter, extra_people, weeks, now))
+        .await
+}
@@ -0,0 +1,77 @@
+use super::settings::http_client;
+use super::sync::{parse_category_rules, run_exclusive_sync, sync_response, SyncLock};
+use crate::connectors::gitlab::{self, GitLabClient};
+use crate::connectors::servicenow::{self, ServiceNowClient, ServiceNowTable};
+use crate::connectors::zendesk::{self, ZendeskClient};
+use crate::db::DbPool;
+use crate::errors::AppError;
+use crate::services::source_sync::sync_source;
+
+#[tauri::command]
+pub async fn trigger_gitlab_sync(
+    lock: tauri::State<'_, SyncLock>,
+    db: tauri::State<'_, DbPool>,
+    gitlab_url: String,
+    project: String,
+    category_rules_json: String,
+    app_handle: tauri::AppHandle,
+) -> Result<serde_json::Value, AppError> {
+    run_exclusive_sync(&lock, &app_handle, async {
+        let token = super::settings::get_source_token_internal(gitlab::SOURCE_ID).await?;
+        let category_rules = parse_category_rules(&category_rules_json)?;
+        let client = GitLabClient::new(&gitlab_url, &project, &token, http_client(&app_handle)?)?;
+
+        let outcome = sync_source(&client, db.0.clone(), &category_rules, &app_handle).await?;
+        Ok(sync_response(&outcome))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn trigger_zendesk_sync(
+    lock: tauri::State<'_, SyncLock>,
+    db: tauri::State<'_, DbPool>,
+    zendesk_url: String,
+    email: String,
+    category_rules_json: String,
+    app_handle: tauri::AppHandle,
+) -> Result<serde_json::Value, AppError> {
+    run_exclusive_sync(&lock, &app_handle, async {
+        let token = super::settings::get_source_token_internal(zendesk::SOURCE_ID).await?;
+        let category_rules = parse_category_rules(&category_rules_json)?;
+        let client = ZendeskClient::new(&zendesk_url, &email, &token, http_client(&app_handle)?)?;
+
+        let outcome = sync_source(&client, db.0.clone(), &category_rules, &app_handle).await?;
+        Ok(sync_response(&outcome))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn trigger_servicenow_sync(
+    lock: tauri::State<'_, SyncLock>,
+    db: tauri::State<'_, DbPool>,
+    instance_url: String,
+    username: String,
+    table: String,
+    category_rules_json: String,
+    app_handle: tauri::AppHandle,
+) -> Result<serde_json::Value, AppError> {
+    let table = ServiceNowTable::parse(&table)?;
+
+    run_exclusive_sync(&lock, &app_handle, async {
+        let password = super::settings::get_source_token_internal(servicenow::SOURCE_ID).await?;
+        let category_rules = parse_category_rules(&category_rules_json)?;
+        let client = ServiceNowClient::new(
+            &instance_url,
+            &username,
+            &password,
+            table,
+            http_client(&app_handle)?,
+        )?;
+
+        let outcome = sync_source(&client, db.0.clone(), &category_rules, &app_handle).await?;
+        Ok(sync_response(&outcome))
+    })
+    .await
+}
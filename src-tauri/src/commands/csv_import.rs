@@ -0,0 +1,50 @@
+use crate::commands::settings::categorizer_backend;
+use crate::commands::sync::parse_category_rules;
+use crate::db::{self, DbPool};
+use crate::errors::{AppError, DbError};
+use crate::models::{CsvImportSummary, CsvMapping};
+use crate::services::categorizer::apply_categories;
+use crate::services::csv_import::parse_csv;
+
+/// Loads tickets exported from another tracker into the tickets table.
+/// Rows that can't be mapped are skipped and reported back.
+#[tauri::command]
+pub async fn import_csv(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+    path: String,
+    mapping: CsvMapping,
+    category_rules_json: Option<String>,
+) -> Result<CsvImportSummary, AppError> {
+    let rules = match category_rules_json {
+        Some(json) => parse_category_rules(&json)?,
+        None => Vec::new(),
+    };
+
+    let file = std::fs::File::open(&path)
+        .map_err(|e| AppError::Config(format!("Failed to open {}: {}", path, e)))?;
+    let parsed = parse_csv(file, &mapping)?;
+
+    let mut tickets = parsed.tickets;
+    apply_categories(
+        categorizer_backend(&app_handle, &rules)?.as_ref(),
+        &mut tickets,
+    )
+    .await;
+    let imported = tickets.len();
+
+    db.run(move |conn| {
+        let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+        for ticket in &tickets {
+            db::upsert_ticket(&tx, ticket)?;
+        }
+        tx.commit().map_err(DbError::from)?;
+        Ok(())
+    })
+    .await?;
+
+    Ok(CsvImportSummary {
+        imported,
+        skipped: parsed.skipped,
+    })
+}
@@ -0,0 +1,72 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::Dashboard;
+
+#[tauri::command]
+pub async fn list_dashboards(db: tauri::State<'_, DbPool>) -> Result<Vec<Dashboard>, AppError> {
+    db.run(db::list_dashboards).await
+}
+
+#[tauri::command]
+pub async fn get_dashboard(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+) -> Result<Option<Dashboard>, AppError> {
+    db.run(move |conn| db::get_dashboard(conn, id)).await
+}
+
+#[tauri::command]
+pub async fn create_dashboard(
+    db: tauri::State<'_, DbPool>,
+    name: String,
+    layout: Option<serde_json::Value>,
+) -> Result<Dashboard, AppError> {
+    validate_name(&name)?;
+    let layout = layout.unwrap_or_else(|| serde_json::Value::Array(Vec::new()));
+    db.run(move |conn| db::create_dashboard(conn, name.trim(), &layout))
+        .await
+}
+
+#[tauri::command]
+pub async fn rename_dashboard(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+    name: String,
+) -> Result<Option<Dashboard>, AppError> {
+    validate_name(&name)?;
+    db.run(move |conn| db::rename_dashboard(conn, id, name.trim()))
+        .await
+}
+
+#[tauri::command]
+pub async fn duplicate_dashboard(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+    name: String,
+) -> Result<Option<Dashboard>, AppError> {
+    validate_name(&name)?;
+    db.run(move |conn| db::duplicate_dashboard(conn, id, name.trim()))
+        .await
+}
+
+#[tauri::command]
+pub async fn save_dashboard_layout(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+    layout: serde_json::Value,
+) -> Result<Option<Dashboard>, AppError> {
+    db.run(move |conn| db::save_dashboard_layout(conn, id, &layout))
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_dashboard(db: tauri::State<'_, DbPool>, id: i64) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_dashboard(conn, id)).await
+}
+
+fn validate_name(name: &str) -> Result<(), AppError> {
+    if name.trim().is_empty() {
+        return Err(AppError::Config("Dashboard name is required".to_string()));
+    }
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{ArchiveSummary, Dataset, DatasetSummary};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+/// Current `Dataset::format_version`.
+const DATASET_FORMAT_VERSION: u32 = 1;
+
+/// Settings store keys carried in a dataset. Tokens and passwords live in the
+/// OS keychain and have to be re-entered on the new machine.
+const DATASET_SETTINGS_KEYS: [&str; 5] = ["jira", "network", "alerts", "smtp", "retention"];
+
+/// Writes tickets, history, rules, views, and settings to a JSON file at `path`.
+#[tauri::command]
+pub async fn export_dataset(
+    db: tauri::State<'_, DbPool>,
+    app_handle: AppHandle,
+    path: String,
+) -> Result<DatasetSummary, AppError> {
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+    let settings: serde_json::Map<String, serde_json::Value> = DATASET_SETTINGS_KEYS
+        .iter()
+        .filter_map(|key| store.get(*key).map(|value| (key.to_string(), value)))
+        .collect();
+
+    let tables = db.run(db::export_tables).await?;
+    let summary = DatasetSummary {
+        tables: tables
+            .iter()
+            .map(|(t, rows)| (t.clone(), rows.len()))
+            .collect(),
+        settings: settings.keys().cloned().collect(),
+    };
+
+    let dataset = Dataset {
+        format_version: DATASET_FORMAT_VERSION,
+        schema_version: db::SCHEMA_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        tables,
+        settings,
+    };
+    let json = serde_json::to_vec_pretty(&dataset)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize dataset: {}", e)))?;
+    std::fs::write(&path, json)
+        .map_err(|e| AppError::Config(format!("Failed to write {}: {}", path, e)))?;
+
+    Ok(summary)
+}
+
+/// Replaces the local data with a file written by `export_dataset`.
+#[tauri::command]
+pub async fn import_dataset(
+    db: tauri::State<'_, DbPool>,
+    app_handle: AppHandle,
+    path: String,
+) -> Result<DatasetSummary, AppError> {
+    let bytes = std::fs::read(&path)
+        .map_err(|e| AppError::Config(format!("Failed to read {}: {}", path, e)))?;
+    let dataset: Dataset = serde_json::from_slice(&bytes)
+        .map_err(|e| AppError::Config(format!("Not a TicketDash dataset: {}", e)))?;
+
+    if dataset.format_version > DATASET_FORMAT_VERSION
+        || dataset.schema_version > db::SCHEMA_VERSION
+    {
+        return Err(AppError::Config(
+            "Dataset was exported by a newer version of TicketDash".to_string(),
+        ));
+    }
+
+    let tables = dataset.tables;
+    let written = db.run(move |conn| db::import_tables(conn, &tables)).await?;
+
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+    let mut imported_settings = Vec::new();
+    for key in DATASET_SETTINGS_KEYS {
+        if let Some(value) = dataset.settings.get(key) {
+            store.set(key, value.clone());
+            imported_settings.push(key.to_string());
+        }
+    }
+    store
+        .save()
+        .map_err(|e| AppError::Config(format!("Failed to save settings: {}", e)))?;
+
+    Ok(DatasetSummary {
+        tables: written,
+        settings: imported_settings,
+    })
+}
+
+/// Moves tickets resolved longer ago than the saved retention period into
+/// the archive. Does nothing when no retention period is set.
+#[tauri::command]
+pub async fn archive_tickets(
+    db: tauri::State<'_, DbPool>,
+    app_handle: AppHandle,
+) -> Result<ArchiveSummary, AppError> {
+    let retention = super::settings::load_retention_settings_internal(&app_handle)?;
+    let cutoff = retention.archive_cutoff(chrono::Utc::now().naive_utc());
+    db.run(move |conn| {
+        let archived = match cutoff {
+            Some(cutoff) => db::archive_resolved_tickets(conn, cutoff)?,
+            None => 0,
+        };
+        Ok(ArchiveSummary {
+            archived: archived as u32,
+            total_archived: db::count_archived_tickets(conn)?,
+        })
+    })
+    .await
+}
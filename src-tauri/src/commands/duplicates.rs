@@ -0,0 +1,49 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{DuplicateCandidate, DuplicateStatus};
+use crate::services::dedupe;
+
+/// Runs the dedupe pass now (it also runs after every sync) and returns how
+/// many new candidates were flagged.
+#[tauri::command]
+pub async fn detect_duplicates(
+    db: tauri::State<'_, DbPool>,
+    threshold: Option<f64>,
+) -> Result<usize, AppError> {
+    let threshold = threshold.unwrap_or(dedupe::DUPLICATE_THRESHOLD);
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(AppError::Config(
+            "Duplicate threshold must be between 0 and 1".to_string(),
+        ));
+    }
+    db.run(move |conn| dedupe::detect_duplicates(conn, threshold, chrono::Utc::now().naive_utc()))
+        .await
+}
+
+#[tauri::command]
+pub async fn list_duplicate_candidates(
+    db: tauri::State<'_, DbPool>,
+    status: Option<DuplicateStatus>,
+) -> Result<Vec<DuplicateCandidate>, AppError> {
+    db.run(move |conn| db::list_duplicate_candidates(conn, status))
+        .await
+}
+
+/// Confirms a candidate and merges the other ticket into `keep`.
+#[tauri::command]
+pub async fn merge_duplicate(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+    keep: String,
+) -> Result<Option<DuplicateCandidate>, AppError> {
+    db.run(move |conn| db::merge_duplicate(conn, id, &keep))
+        .await
+}
+
+#[tauri::command]
+pub async fn dismiss_duplicate(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+) -> Result<Option<DuplicateCandidate>, AppError> {
+    db.run(move |conn| db::dismiss_duplicate(conn, id)).await
+}
@@ -0,0 +1,59 @@
+use super::settings::{delete_database_key, get_database_key_internal, store_database_key};
+use crate::db::{self, DbPool};
+use crate::errors::{AppError, DbError};
+use crate::services::cache::AggregationCache;
+use tauri::{AppHandle, Manager};
+
+/// Whether the database is encrypted at rest.
+#[tauri::command]
+pub async fn get_database_encryption_status() -> Result<bool, AppError> {
+    Ok(get_database_key_internal()?.is_some())
+}
+
+/// Encrypts the existing database with SQLCipher under a new random key
+/// kept in the OS keychain. The app keeps running on the encrypted copy.
+#[tauri::command]
+pub async fn encrypt_database(
+    db: tauri::State<'_, DbPool>,
+    cache: tauri::State<'_, AggregationCache>,
+    app_handle: AppHandle,
+) -> Result<(), AppError> {
+    if get_database_key_internal()?.is_some() {
+        return Err(AppError::Config(
+            "The database is already encrypted".to_string(),
+        ));
+    }
+    let path = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Config(format!("Failed to get app data directory: {}", e)))?
+        .join(db::DATABASE_FILE);
+
+    let key = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    // Stored first: a database encrypted under a lost key can't be opened
+    store_database_key(&key)?;
+
+    let pool = db.0.clone();
+    let file = path.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let mut conn = pool.lock().map_err(|_| DbError::LockFailed)?;
+        db::encrypt_database_file(&mut conn, &file, &key)
+    })
+    .await
+    .map_err(|_| AppError::Internal("Task join failed".to_string()))?;
+    cache.clear();
+
+    // Only forget the key if the plaintext file is still the one in place
+    if result.is_err()
+        && path
+            .to_str()
+            .is_some_and(|p| db::open_database(p, None).is_ok())
+    {
+        delete_database_key()?;
+    }
+    result
+}
@@ -0,0 +1,60 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{GoalProgress, MetricGoal, MetricGoalInput};
+use crate::services::goals;
+use chrono::NaiveDate;
+
+#[tauri::command]
+pub async fn list_metric_goals(db: tauri::State<'_, DbPool>) -> Result<Vec<MetricGoal>, AppError> {
+    db.run(db::list_metric_goals).await
+}
+
+#[tauri::command]
+pub async fn create_metric_goal(
+    db: tauri::State<'_, DbPool>,
+    goal: MetricGoalInput,
+) -> Result<MetricGoal, AppError> {
+    validate(&goal)?;
+    db.run(move |conn| db::create_metric_goal(conn, &goal))
+        .await
+}
+
+#[tauri::command]
+pub async fn update_metric_goal(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+    goal: MetricGoalInput,
+) -> Result<Option<MetricGoal>, AppError> {
+    validate(&goal)?;
+    db.run(move |conn| db::update_metric_goal(conn, id, &goal))
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_metric_goal(db: tauri::State<'_, DbPool>, id: i64) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_metric_goal(conn, id)).await
+}
+
+/// Each goal's current value and whether its trend reaches the target by
+/// the deadline, for the dashboard.
+#[tauri::command]
+pub async fn get_goal_progress(
+    db: tauri::State<'_, DbPool>,
+) -> Result<Vec<GoalProgress>, AppError> {
+    let now = chrono::Utc::now().naive_utc();
+    db.run(move |conn| goals::get_goal_progress(conn, now))
+        .await
+}
+
+fn validate(goal: &MetricGoalInput) -> Result<(), AppError> {
+    if !goal.target.is_finite() {
+        return Err(AppError::Config("Goal target must be a number".to_string()));
+    }
+    NaiveDate::parse_from_str(&goal.deadline, "%Y-%m-%d").map_err(|_| {
+        AppError::Config(format!(
+            "Invalid deadline (expected YYYY-MM-DD): {}",
+            goal.deadline
+        ))
+    })?;
+    Ok(())
+}
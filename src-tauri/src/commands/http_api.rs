@@ -0,0 +1,73 @@
+use super::settings::http_api_token;
+use crate::db::DbPool;
+use crate::errors::AppError;
+use crate::models::HttpApiStatus;
+use crate::services::cache::AggregationCache;
+use crate::services::http_api::HttpApiServer;
+use std::sync::{Mutex, MutexGuard};
+
+const DEFAULT_HTTP_API_PORT: u16 = 8788;
+
+/// The running HTTP API server, if any.
+pub struct HttpApiState(pub Mutex<Option<HttpApiServer>>);
+
+impl HttpApiState {
+    fn lock(&self) -> Result<MutexGuard<'_, Option<HttpApiServer>>, AppError> {
+        self.0
+            .lock()
+            .map_err(|_| AppError::Internal("HTTP API state lock poisoned".to_string()))
+    }
+}
+
+/// Stops `server` on a blocking thread, since dropping it joins its
+/// listener thread.
+async fn shut_down(server: Option<HttpApiServer>) -> Result<(), AppError> {
+    if let Some(server) = server {
+        tauri::async_runtime::spawn_blocking(move || drop(server))
+            .await
+            .map_err(|_| AppError::Internal("Task join failed".to_string()))?;
+    }
+    Ok(())
+}
+
+/// Starts (or restarts) the read-only local HTTP API and returns the bound
+/// port with the token clients must send. The token is kept in the OS
+/// keychain, so it survives restarts unless `regenerate_token` is set. The
+/// new server is bound before the old one stops, so a failed restart on
+/// another port leaves the running API up.
+#[tauri::command]
+pub async fn start_http_api(
+    state: tauri::State<'_, HttpApiState>,
+    db: tauri::State<'_, DbPool>,
+    cache: tauri::State<'_, AggregationCache>,
+    port: Option<u16>,
+    regenerate_token: Option<bool>,
+) -> Result<HttpApiStatus, AppError> {
+    let token = http_api_token(regenerate_token.unwrap_or(false))?;
+    let port = port.unwrap_or(DEFAULT_HTTP_API_PORT);
+
+    let running_port = state.lock()?.as_ref().map(HttpApiServer::port);
+    if running_port == Some(port) {
+        // The old server holds this port, so it has to let go first
+        let old = state.lock()?.take();
+        shut_down(old).await?;
+    }
+
+    let server = HttpApiServer::start(port, token.clone(), db.0.clone(), cache.inner().clone())?;
+    let status = HttpApiStatus {
+        port: server.port(),
+        token,
+    };
+    let old = state.lock()?.replace(server);
+    shut_down(old).await?;
+
+    Ok(status)
+}
+
+#[tauri::command]
+pub async fn stop_http_api(state: tauri::State<'_, HttpApiState>) -> Result<bool, AppError> {
+    let old = state.lock()?.take();
+    let stopped = old.is_some();
+    shut_down(old).await?;
+    Ok(stopped)
+}
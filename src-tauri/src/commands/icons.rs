@@ -0,0 +1,33 @@
+use super::settings::saved_jira_client;
+use crate::db::DbPool;
+use crate::errors::AppError;
+use crate::models::IconImage;
+use crate::services::icons::load_icon;
+use tauri::{AppHandle, Manager};
+
+/// An avatar or issue-type icon as a data URL. Downloads go through the
+/// backend with the saved credentials and are cached on disk, so icons keep
+/// showing offline and the webview never sees the auth header.
+#[tauri::command]
+pub async fn get_icon(
+    app_handle: AppHandle,
+    db: tauri::State<'_, DbPool>,
+    url: String,
+) -> Result<IconImage, AppError> {
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err(AppError::Config(format!("Not an icon URL: {}", url)));
+    }
+    let cache_dir = app_handle
+        .path()
+        .app_cache_dir()
+        .map_err(|e| AppError::Config(format!("Failed to get app cache directory: {}", e)))?
+        .join("icons");
+    let client = match saved_jira_client(&app_handle).await {
+        Ok(client) => Some(client),
+        Err(e) => {
+            log::warn!("Loading icon without a Jira client: {}", e);
+            None
+        }
+    };
+    load_icon(client.as_ref(), &db, &cache_dir, &url).await
+}
@@ -0,0 +1,77 @@
+use super::settings::load_jira_settings_internal;
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{LinkFormat, Ticket};
+use crate::services::deep_links::{browse_url, format_ticket_link};
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+/// The ticket `key` with its Jira page, which lives on the site of the account
+/// it was synced through, or the saved Jira site for the single-account setup.
+async fn ticket_with_url(
+    app_handle: &AppHandle,
+    db: &DbPool,
+    key: String,
+) -> Result<(Ticket, String), AppError> {
+    let lookup_key = key.clone();
+    let ticket = db
+        .run(move |conn| db::get_ticket_by_key(conn, &lookup_key))
+        .await?
+        .ok_or_else(|| AppError::Config(format!("Unknown ticket: {}", key)))?;
+    if ticket.source != "jira" {
+        return Err(AppError::Config(format!(
+            "{} was imported from {} and has no Jira page",
+            ticket.jira_key, ticket.source
+        )));
+    }
+
+    let base_url = match ticket.account_id {
+        Some(account_id) => {
+            db.run(move |conn| db::get_jira_account(conn, account_id))
+                .await?
+                .ok_or_else(|| {
+                    AppError::Config(format!("Jira account {} no longer exists", account_id))
+                })?
+                .base_url
+        }
+        None => {
+            load_jira_settings_internal(app_handle)?
+                .ok_or_else(|| AppError::Config("Jira is not configured".to_string()))?
+                .jira_url
+        }
+    };
+    let url = browse_url(&base_url, db::jira_issue_key(&ticket.jira_key));
+    Ok((ticket, url))
+}
+
+/// Opens the ticket's Jira page in the default browser.
+#[tauri::command]
+pub async fn open_in_jira(
+    app_handle: AppHandle,
+    db: tauri::State<'_, DbPool>,
+    key: String,
+) -> Result<(), AppError> {
+    let (_, url) = ticket_with_url(&app_handle, &db, key).await?;
+    app_handle
+        .opener()
+        .open_url(&url, None::<&str>)
+        .map_err(|e| AppError::Config(format!("Failed to open {}: {}", url, e)))
+}
+
+/// A link to the ticket in `format` (the bare URL by default), for the UI to
+/// put on the clipboard.
+#[tauri::command]
+pub async fn copy_ticket_link(
+    app_handle: AppHandle,
+    db: tauri::State<'_, DbPool>,
+    key: String,
+    format: Option<LinkFormat>,
+) -> Result<String, AppError> {
+    let (ticket, url) = ticket_with_url(&app_handle, &db, key).await?;
+    Ok(format_ticket_link(
+        &url,
+        &ticket.jira_key,
+        &ticket.summary,
+        format.unwrap_or_default(),
+    ))
+}
@@ -0,0 +1,36 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{IncidentFollowUps, LocalLinkKind};
+
+/// Links ticket `a` to `b` as `kind` ("`a` follow-up-of `b`"); the link
+/// shows up in `get_linked_tickets` on both tickets. Returns whether it's new.
+#[tauri::command]
+pub async fn link_tickets(
+    db: tauri::State<'_, DbPool>,
+    a: String,
+    b: String,
+    kind: LocalLinkKind,
+) -> Result<bool, AppError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    db.run(move |conn| db::link_tickets(conn, a.trim(), b.trim(), kind, &now))
+        .await
+}
+
+#[tauri::command]
+pub async fn unlink_tickets(
+    db: tauri::State<'_, DbPool>,
+    a: String,
+    b: String,
+    kind: LocalLinkKind,
+) -> Result<bool, AppError> {
+    db.run(move |conn| db::unlink_tickets(conn, a.trim(), b.trim(), kind))
+        .await
+}
+
+/// Incidents that still have unresolved follow-ups, most first.
+#[tauri::command]
+pub async fn get_open_follow_ups(
+    db: tauri::State<'_, DbPool>,
+) -> Result<Vec<IncidentFollowUps>, AppError> {
+    db.run(db::get_open_follow_ups).await
+}
@@ -1,7 +1,91 @@
+pub mod accounts;
+pub mod actions;
+pub mod alert_rules;
+pub mod alerts;
+pub mod attachments;
+pub mod automations;
+pub mod board;
+pub mod calendar;
+pub mod capacity;
+pub mod connectors;
+pub mod csv_import;
+pub mod dashboards;
+pub mod dataset;
+pub mod duplicates;
+pub mod encryption;
+pub mod goals;
+pub mod http_api;
+pub mod icons;
+pub mod links;
+pub mod local_links;
+pub mod notes;
+pub mod outbound_webhooks;
+pub mod period_comparison;
+pub mod person_report;
+pub mod priorities;
+pub mod query_console;
+pub mod ranking;
+pub mod root_causes;
+pub mod rotations;
 pub mod settings;
+pub mod snapshots;
+pub mod snoozes;
+pub mod standup;
+pub mod status_categories;
+pub mod support_calendars;
 pub mod sync;
+pub mod tags;
+pub mod teams;
 pub mod tickets;
+pub mod time_tracking;
+pub mod users;
+pub mod views;
+pub mod wallboard;
+pub mod watchlist;
+pub mod webhook;
 
+pub use accounts::*;
+pub use actions::*;
+pub use alert_rules::*;
+pub use alerts::*;
+pub use attachments::*;
+pub use automations::*;
+pub use board::*;
+pub use calendar::*;
+pub use capacity::*;
+pub use connectors::*;
+pub use csv_import::*;
+pub use dashboards::*;
+pub use dataset::*;
+pub use duplicates::*;
+pub use encryption::*;
+pub use goals::*;
+pub use http_api::*;
+pub use icons::*;
+pub use links::*;
+pub use local_links::*;
+pub use notes::*;
+pub use outbound_webhooks::*;
+pub use period_comparison::*;
+pub use person_report::*;
+pub use priorities::*;
+pub use query_console::*;
+pub use ranking::*;
+pub use root_causes::*;
+pub use rotations::*;
 pub use settings::*;
+pub use snapshots::*;
+pub use snoozes::*;
+pub use standup::*;
+pub use status_categories::*;
+pub use support_calendars::*;
 pub use sync::*;
+pub use tags::*;
+pub use teams::*;
 pub use tickets::*;
+pub use time_tracking::*;
+pub use users::*;
+pub use views::*;
+pub use wallboard::*;
+pub use watchlist::*;
+pub use webhook::*;
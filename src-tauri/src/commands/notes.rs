@@ -0,0 +1,50 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::TicketNote;
+
+fn validate(body: &str) -> Result<(), AppError> {
+    if body.trim().is_empty() {
+        return Err(AppError::Config("A note needs some text".to_string()));
+    }
+    Ok(())
+}
+
+/// The user's private notes on ticket `key`, oldest first.
+#[tauri::command]
+pub async fn list_ticket_notes(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+) -> Result<Vec<TicketNote>, AppError> {
+    db.run(move |conn| db::list_ticket_notes(conn, &key)).await
+}
+
+/// Adds a markdown note to ticket `key`. Notes stay on this machine and are
+/// never sent to Jira.
+#[tauri::command]
+pub async fn create_ticket_note(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+    body: String,
+) -> Result<TicketNote, AppError> {
+    validate(&body)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    db.run(move |conn| db::create_ticket_note(conn, &key, &body, &now))
+        .await
+}
+
+#[tauri::command]
+pub async fn update_ticket_note(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+    body: String,
+) -> Result<Option<TicketNote>, AppError> {
+    validate(&body)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    db.run(move |conn| db::update_ticket_note(conn, id, &body, &now))
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_ticket_note(db: tauri::State<'_, DbPool>, id: i64) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_ticket_note(conn, id)).await
+}
@@ -0,0 +1,63 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{OutboundWebhook, OutboundWebhookInput};
+
+#[tauri::command]
+pub async fn list_outbound_webhooks(
+    db: tauri::State<'_, DbPool>,
+) -> Result<Vec<OutboundWebhook>, AppError> {
+    db.run(db::list_outbound_webhooks).await
+}
+
+#[tauri::command]
+pub async fn create_outbound_webhook(
+    db: tauri::State<'_, DbPool>,
+    webhook: OutboundWebhookInput,
+) -> Result<OutboundWebhook, AppError> {
+    validate(&webhook)?;
+    db.run(move |conn| db::create_outbound_webhook(conn, &webhook))
+        .await
+}
+
+#[tauri::command]
+pub async fn update_outbound_webhook(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+    webhook: OutboundWebhookInput,
+) -> Result<Option<OutboundWebhook>, AppError> {
+    validate(&webhook)?;
+    db.run(move |conn| db::update_outbound_webhook(conn, id, &webhook))
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_outbound_webhook(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_outbound_webhook(conn, id))
+        .await
+}
+
+/// The secret receivers verify signatures with, created on first use.
+#[tauri::command]
+pub async fn get_webhook_signing_secret(regenerate: Option<bool>) -> Result<String, AppError> {
+    crate::commands::settings::webhook_signing_secret(regenerate.unwrap_or(false))
+}
+
+fn validate(webhook: &OutboundWebhookInput) -> Result<(), AppError> {
+    if webhook.name.trim().is_empty() {
+        return Err(AppError::Config("Webhook name is required".to_string()));
+    }
+    if !webhook.url.starts_with("https://") && !webhook.url.starts_with("http://") {
+        return Err(AppError::Config(
+            "Webhook URL must start with http:// or https://".to_string(),
+        ));
+    }
+    if webhook.events.is_empty() {
+        return Err(AppError::Config(
+            "Webhook must subscribe to at least one event".to_string(),
+        ));
+    }
+    Ok(())
+}
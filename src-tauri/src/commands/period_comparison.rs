@@ -0,0 +1,26 @@
+use crate::db::DbPool;
+use crate::errors::AppError;
+use crate::models::{DurationMode, PeriodComparison, TicketFilter};
+use crate::services::alerts::load_alert_settings;
+use crate::services::period_comparison::{self, parse_period};
+
+/// Compares `filter`'s tickets between `period_a` and `period_b`, each a
+/// year (`2025`), quarter (`2025-Q1`) or month (`2025-01`). SLA compliance
+/// uses the targets from the alert settings.
+#[tauri::command]
+pub async fn compare_periods(
+    db: tauri::State<'_, DbPool>,
+    app_handle: tauri::AppHandle,
+    period_a: String,
+    period_b: String,
+    filter: Option<TicketFilter>,
+    duration_mode: Option<DurationMode>,
+) -> Result<PeriodComparison, AppError> {
+    let a = parse_period(&period_a)?;
+    let b = parse_period(&period_b)?;
+    let filter = filter.unwrap_or_default();
+    let mode = duration_mode.unwrap_or_default();
+    let targets = load_alert_settings(&app_handle)?.sla_targets_hours;
+    db.run(move |conn| period_comparison::compare_periods(conn, a, b, &filter, mode, &targets))
+        .await
+}
@@ -0,0 +1,79 @@
+use crate::db::DbPool;
+use crate::errors::AppError;
+use crate::models::{DateRange, DurationMode, PersonReport, ReportFormat};
+use crate::services::{pdf, person_report};
+use chrono::{Duration, NaiveDate};
+
+/// Days a person report covers when no range is given.
+const DEFAULT_REPORT_DAYS: i64 = 30;
+
+/// Dates bounding `range`: `to` defaults to tomorrow, so today is included,
+/// and `from` to `DEFAULT_REPORT_DAYS` before it.
+fn report_dates(range: Option<DateRange>) -> Result<(NaiveDate, NaiveDate), AppError> {
+    let parse = |date: &str| {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| AppError::Config(format!("Invalid date: {}", date)))
+    };
+    let range = range.unwrap_or_default();
+    let to = match range.to.as_deref() {
+        Some(to) => parse(to)?,
+        None => chrono::Utc::now().date_naive() + Duration::days(1),
+    };
+    let from = match range.from.as_deref() {
+        Some(from) => parse(from)?,
+        None => to - Duration::days(DEFAULT_REPORT_DAYS),
+    };
+    if from >= to {
+        return Err(AppError::Config(
+            "Report range must end after it starts".to_string(),
+        ));
+    }
+    Ok((from, to))
+}
+
+async fn build(
+    db: &DbPool,
+    assignee: String,
+    range: Option<DateRange>,
+    duration_mode: Option<DurationMode>,
+) -> Result<PersonReport, AppError> {
+    if assignee.trim().is_empty() {
+        return Err(AppError::Config("Assignee is required".to_string()));
+    }
+    let (from, to) = report_dates(range)?;
+    let mode = duration_mode.unwrap_or_default();
+    let now = chrono::Utc::now().naive_utc();
+    db.run(move |conn| person_report::build_person_report(conn, &assignee, from, to, mode, now))
+        .await
+}
+
+/// Throughput, current load, aging tickets, and notable resolutions for
+/// `assignee` over `range` (`YYYY-MM-DD` dates, the last 30 days by default).
+#[tauri::command]
+pub async fn generate_person_report(
+    db: tauri::State<'_, DbPool>,
+    assignee: String,
+    range: Option<DateRange>,
+    duration_mode: Option<DurationMode>,
+) -> Result<PersonReport, AppError> {
+    build(&db, assignee, range, duration_mode).await
+}
+
+/// Writes `generate_person_report`'s report to `path` as Markdown or PDF.
+#[tauri::command]
+pub async fn export_person_report(
+    db: tauri::State<'_, DbPool>,
+    assignee: String,
+    range: Option<DateRange>,
+    duration_mode: Option<DurationMode>,
+    path: String,
+    format: Option<ReportFormat>,
+) -> Result<(), AppError> {
+    let report = build(&db, assignee, range, duration_mode).await?;
+    let contents = match format.unwrap_or_default() {
+        ReportFormat::Markdown => report.markdown.into_bytes(),
+        ReportFormat::Pdf => pdf::markdown_to_pdf(&report.markdown),
+    };
+    std::fs::write(&path, contents)
+        .map_err(|e| AppError::Config(format!("Failed to write {}: {}", path, e)))
+}
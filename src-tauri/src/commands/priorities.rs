@@ -0,0 +1,24 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{PriorityLevel, PriorityMapping};
+
+#[tauri::command]
+pub async fn get_priority_mappings(
+    db: tauri::State<'_, DbPool>,
+) -> Result<Vec<PriorityMapping>, AppError> {
+    db.run(db::get_priority_mappings).await
+}
+
+/// Maps `priority` onto a canonical `level` in `project_key` (every project
+/// when null), or removes the mapping when `level` is null, and returns how
+/// many stored tickets changed.
+#[tauri::command]
+pub async fn set_priority_mapping(
+    db: tauri::State<'_, DbPool>,
+    priority: String,
+    project_key: Option<String>,
+    level: Option<PriorityLevel>,
+) -> Result<u32, AppError> {
+    db.run(move |conn| db::set_priority_mapping(conn, &priority, project_key.as_deref(), level))
+        .await
+}
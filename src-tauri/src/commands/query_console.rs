@@ -0,0 +1,38 @@
+use super::settings::get_database_key_internal;
+use crate::db;
+use crate::errors::AppError;
+use crate::models::QueryResult;
+use std::time::Duration;
+use tauri::Manager;
+
+const DEFAULT_MAX_ROWS: usize = 1_000;
+const MAX_ROWS_LIMIT: usize = 10_000;
+const QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs one SELECT-style statement on a separate read-only connection, so
+/// ad-hoc questions can't change data or hold up the app's own queries.
+#[tauri::command]
+pub async fn run_readonly_query(
+    app_handle: tauri::AppHandle,
+    sql: String,
+    max_rows: Option<usize>,
+) -> Result<QueryResult, AppError> {
+    let path = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Config(format!("Failed to get app data directory: {}", e)))?
+        .join(db::DATABASE_FILE);
+    let path = path
+        .to_str()
+        .ok_or_else(|| AppError::Config(format!("Invalid DB path: {:?}", path)))?
+        .to_string();
+    let key = get_database_key_internal()?;
+    let max_rows = max_rows.unwrap_or(DEFAULT_MAX_ROWS).min(MAX_ROWS_LIMIT);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = db::open_readonly_database(&path, key.as_deref())?;
+        db::run_readonly_query(&conn, &sql, max_rows, QUERY_TIMEOUT)
+    })
+    .await
+    .map_err(|_| AppError::Internal("Task join failed".to_string()))?
+}
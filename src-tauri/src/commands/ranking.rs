@@ -0,0 +1,27 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::Ticket;
+
+/// Moves `keys` to the front of the personal queue in the given order,
+/// adding any that weren't queued. The order is kept locally and never
+/// changed by syncs or by Jira's rank.
+#[tauri::command]
+pub async fn reorder_tickets(
+    db: tauri::State<'_, DbPool>,
+    keys: Vec<String>,
+) -> Result<(), AppError> {
+    db.run(move |conn| db::reorder_tickets(conn, &keys)).await
+}
+
+#[tauri::command]
+pub async fn get_ranked_tickets(db: tauri::State<'_, DbPool>) -> Result<Vec<Ticket>, AppError> {
+    db.run(db::get_ranked_tickets).await
+}
+
+#[tauri::command]
+pub async fn remove_ticket_rank(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+) -> Result<bool, AppError> {
+    db.run(move |conn| db::remove_ticket_rank(conn, &key)).await
+}
@@ -0,0 +1,77 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{DurationMode, RootCause, RootCauseBreakdown, RootCauseInput, TicketFilter};
+
+#[tauri::command]
+pub async fn list_root_causes(db: tauri::State<'_, DbPool>) -> Result<Vec<RootCause>, AppError> {
+    db.run(db::list_root_causes).await
+}
+
+#[tauri::command]
+pub async fn create_root_cause(
+    db: tauri::State<'_, DbPool>,
+    root_cause: RootCauseInput,
+) -> Result<RootCause, AppError> {
+    validate(&root_cause)?;
+    db.run(move |conn| db::create_root_cause(conn, &root_cause))
+        .await
+}
+
+#[tauri::command]
+pub async fn update_root_cause(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+    root_cause: RootCauseInput,
+) -> Result<Option<RootCause>, AppError> {
+    validate(&root_cause)?;
+    db.run(move |conn| db::update_root_cause(conn, id, &root_cause))
+        .await
+}
+
+/// Deletes a root cause, clearing it from the tickets it was set on.
+#[tauri::command]
+pub async fn delete_root_cause(db: tauri::State<'_, DbPool>, id: i64) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_root_cause(conn, id)).await
+}
+
+#[tauri::command]
+pub async fn get_ticket_root_cause(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+) -> Result<Option<RootCause>, AppError> {
+    db.run(move |conn| db::get_ticket_root_cause(conn, &key))
+        .await
+}
+
+/// Sets a resolved ticket's root cause, or clears it when `root_cause_id`
+/// is omitted.
+#[tauri::command]
+pub async fn set_ticket_root_cause(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+    root_cause_id: Option<i64>,
+) -> Result<Option<RootCause>, AppError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    db.run(move |conn| db::set_ticket_root_cause(conn, &key, root_cause_id, &now))
+        .await
+}
+
+/// Root causes behind the filtered resolved tickets, most common first.
+#[tauri::command]
+pub async fn get_root_cause_breakdown(
+    db: tauri::State<'_, DbPool>,
+    filter: Option<TicketFilter>,
+    duration_mode: Option<DurationMode>,
+) -> Result<RootCauseBreakdown, AppError> {
+    let filter = filter.unwrap_or_default();
+    let mode = duration_mode.unwrap_or_default();
+    db.run(move |conn| db::get_root_cause_breakdown(conn, &filter, mode))
+        .await
+}
+
+fn validate(root_cause: &RootCauseInput) -> Result<(), AppError> {
+    if root_cause.name.trim().is_empty() {
+        return Err(AppError::Config("Root cause name is required".to_string()));
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{DurationMode, Rotation, RotationInput, RotationStats, TicketFilter};
+use crate::services::time_calc::parse_timestamp;
+
+#[tauri::command]
+pub async fn list_rotations(db: tauri::State<'_, DbPool>) -> Result<Vec<Rotation>, AppError> {
+    db.run(db::list_rotations).await
+}
+
+#[tauri::command]
+pub async fn create_rotation(
+    db: tauri::State<'_, DbPool>,
+    rotation: RotationInput,
+) -> Result<Rotation, AppError> {
+    validate(&rotation)?;
+    db.run(move |conn| db::create_rotation(conn, &rotation))
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_rotation(db: tauri::State<'_, DbPool>, id: i64) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_rotation(conn, id)).await
+}
+
+/// Ticket load per rotation, attributing each ticket to the rotations on duty
+/// when it was created.
+#[tauri::command]
+pub async fn get_triage_stats_by_rotation(
+    db: tauri::State<'_, DbPool>,
+    filter: Option<TicketFilter>,
+    duration_mode: Option<DurationMode>,
+) -> Result<Vec<RotationStats>, AppError> {
+    let filter = filter.unwrap_or_default();
+    let mode = duration_mode.unwrap_or_default();
+    db.run(move |conn| db::get_triage_stats_by_rotation(conn, &filter, mode))
+        .await
+}
+
+fn validate(rotation: &RotationInput) -> Result<(), AppError> {
+    if rotation.person.trim().is_empty() {
+        return Err(AppError::Config("Rotation person is required".to_string()));
+    }
+    let (Some(start), Some(end)) = (
+        parse_timestamp(&rotation.start_at),
+        parse_timestamp(&rotation.end_at),
+    ) else {
+        return Err(AppError::Config(
+            "Rotation start and end must be RFC 3339 timestamps".to_string(),
+        ));
+    };
+    if start >= end {
+        return Err(AppError::Config(
+            "Rotation must end after it starts".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -1,5 +1,16 @@
+use crate::connectors::TicketSource;
+use crate::db::{self, DbPool};
 use crate::errors::AppError;
-use crate::errors::JiraError;
+use crate::errors::{ConnectorError, JiraError};
+use crate::jira::{JiraAuth, JiraAuthMethod, JiraClient, JiraFieldMapping, JiraLimits};
+use crate::models::{
+    CategorizerKind, CategorizerSettings, ConflictPolicy, JiraAccount, NetworkSettings,
+    RetentionSettings, SmtpSettings,
+};
+use crate::services::categorizer::{CategorizerBackend, CategoryRule, RulesCategorizer};
+use crate::services::embeddings::EmbeddingCategorizer;
+use crate::services::http_client::build_http_client;
+use crate::services::notify::email::EmailSender;
 use keyring::Entry;
 use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
@@ -8,10 +19,26 @@ use tauri_plugin_store::StoreExt;
 const SERVICE_NAME: &str = "ticket-dashboard";
 const KEYRING_USER: &str = "jira-api-token";
 
+/// Non-Jira connectors that keep an API token in the OS keychain.
+const TOKEN_SOURCES: [&str; 3] = [
+    crate::connectors::gitlab::SOURCE_ID,
+    crate::connectors::servicenow::SOURCE_ID,
+    crate::connectors::zendesk::SOURCE_ID,
+];
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct JiraSettings {
     pub jira_url: String,
     pub email: String,
+    #[serde(default)]
+    pub auth_method: JiraAuthMethod,
+    #[serde(default)]
+    pub limits: JiraLimits,
+    #[serde(default)]
+    pub fields: JiraFieldMapping,
+    /// What a sync does with status changes not yet confirmed by Jira.
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
 }
 
 #[tauri::command]
@@ -34,6 +61,163 @@ pub(crate) async fn get_jira_token_internal() -> Result<String, AppError> {
     }
 }
 
+fn slack_webhook_entry() -> Result<Entry, AppError> {
+    Entry::new(SERVICE_NAME, "slack-webhook-url").map_err(|e| AppError::Keyring(e.to_string()))
+}
+
+#[tauri::command]
+pub async fn store_slack_webhook(webhook_url: String) -> Result<(), AppError> {
+    // Validates the URL shape before it is stored
+    crate::services::notify::slack::SlackNotifier::new(&webhook_url, reqwest::Client::new())?;
+    slack_webhook_entry()?
+        .set_password(&webhook_url)
+        .map_err(|e| AppError::Keyring(e.to_string()))
+}
+
+#[tauri::command]
+pub async fn delete_slack_webhook() -> Result<(), AppError> {
+    match slack_webhook_entry()?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(AppError::Keyring(e.to_string())),
+    }
+}
+
+/// The stored Slack webhook URL, or `None` when Slack isn't set up.
+pub(crate) fn get_slack_webhook_internal() -> Result<Option<String>, AppError> {
+    match slack_webhook_entry()?.get_password() {
+        Ok(url) => Ok(Some(url)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(AppError::Keyring(e.to_string())),
+    }
+}
+
+fn smtp_password_entry() -> Result<Entry, AppError> {
+    Entry::new(SERVICE_NAME, "smtp-password").map_err(|e| AppError::Keyring(e.to_string()))
+}
+
+#[tauri::command]
+pub async fn store_smtp_password(password: String) -> Result<(), AppError> {
+    smtp_password_entry()?
+        .set_password(&password)
+        .map_err(|e| AppError::Keyring(e.to_string()))
+}
+
+#[tauri::command]
+pub async fn delete_smtp_password() -> Result<(), AppError> {
+    match smtp_password_entry()?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(AppError::Keyring(e.to_string())),
+    }
+}
+
+pub(crate) fn get_smtp_password_internal() -> Result<Option<String>, AppError> {
+    match smtp_password_entry()?.get_password() {
+        Ok(password) => Ok(Some(password)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(AppError::Keyring(e.to_string())),
+    }
+}
+
+fn http_api_token_entry() -> Result<Entry, AppError> {
+    Entry::new(SERVICE_NAME, "http-api-token").map_err(|e| AppError::Keyring(e.to_string()))
+}
+
+/// The random secret stored in `entry`, created on first use. With
+/// `regenerate`, a new secret replaces the old one.
+fn generated_secret(entry: Entry, regenerate: bool) -> Result<String, AppError> {
+    if !regenerate {
+        match entry.get_password() {
+            Ok(secret) => return Ok(secret),
+            Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(AppError::Keyring(e.to_string())),
+        }
+    }
+    let secret = uuid::Uuid::new_v4().simple().to_string();
+    entry
+        .set_password(&secret)
+        .map_err(|e| AppError::Keyring(e.to_string()))?;
+    Ok(secret)
+}
+
+/// The local HTTP API token. A regenerated token replaces the old one,
+/// which stops working.
+pub(crate) fn http_api_token(regenerate: bool) -> Result<String, AppError> {
+    generated_secret(http_api_token_entry()?, regenerate)
+}
+
+fn webhook_signing_secret_entry() -> Result<Entry, AppError> {
+    Entry::new(SERVICE_NAME, "webhook-signing-secret").map_err(|e| AppError::Keyring(e.to_string()))
+}
+
+/// The key outbound webhook payloads are signed with. Receivers have to be
+/// updated when it is regenerated.
+pub(crate) fn webhook_signing_secret(regenerate: bool) -> Result<String, AppError> {
+    generated_secret(webhook_signing_secret_entry()?, regenerate)
+}
+
+fn webhook_listener_secret_entry() -> Result<Entry, AppError> {
+    Entry::new(SERVICE_NAME, "webhook-listener-secret")
+        .map_err(|e| AppError::Keyring(e.to_string()))
+}
+
+/// The secret incoming Jira webhook URLs must carry. The webhook URL in Jira
+/// has to be updated when it is regenerated.
+pub(crate) fn webhook_listener_secret(regenerate: bool) -> Result<String, AppError> {
+    generated_secret(webhook_listener_secret_entry()?, regenerate)
+}
+
+fn database_key_entry() -> Result<Entry, AppError> {
+    Entry::new(SERVICE_NAME, "database-key").map_err(|e| AppError::Keyring(e.to_string()))
+}
+
+pub(crate) fn store_database_key(key: &str) -> Result<(), AppError> {
+    database_key_entry()?
+        .set_password(key)
+        .map_err(|e| AppError::Keyring(e.to_string()))
+}
+
+/// The SQLCipher key, or `None` while the database is unencrypted.
+pub(crate) fn get_database_key_internal() -> Result<Option<String>, AppError> {
+    match database_key_entry()?.get_password() {
+        Ok(key) => Ok(Some(key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(AppError::Keyring(e.to_string())),
+    }
+}
+
+pub(crate) fn delete_database_key() -> Result<(), AppError> {
+    match database_key_entry()?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(AppError::Keyring(e.to_string())),
+    }
+}
+
+fn account_token_entry(account_id: i64) -> Result<Entry, AppError> {
+    Entry::new(SERVICE_NAME, &format!("{}:{}", KEYRING_USER, account_id))
+        .map_err(|e| AppError::Keyring(e.to_string()))
+}
+
+pub(crate) fn store_account_token(account_id: i64, token: &str) -> Result<(), AppError> {
+    account_token_entry(account_id)?
+        .set_password(token)
+        .map_err(|e| AppError::Keyring(e.to_string()))
+}
+
+pub(crate) async fn get_account_token_internal(account_id: i64) -> Result<String, AppError> {
+    match account_token_entry(account_id)?.get_password() {
+        Ok(token) => Ok(token),
+        Err(keyring::Error::NoEntry) => Err(JiraError::NotConfigured.into()),
+        Err(e) => Err(AppError::Keyring(e.to_string())),
+    }
+}
+
+pub(crate) fn delete_account_token(account_id: i64) -> Result<(), AppError> {
+    match account_token_entry(account_id)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(AppError::Keyring(e.to_string())),
+    }
+}
+
 #[tauri::command]
 pub async fn delete_jira_token() -> Result<(), AppError> {
     let entry =
@@ -46,14 +230,16 @@ pub async fn delete_jira_token() -> Result<(), AppError> {
 
 #[tauri::command]
 pub async fn verify_jira_connection(
+    app_handle: AppHandle,
     jira_url: String,
     email: String,
+    auth_method: Option<JiraAuthMethod>,
 ) -> Result<serde_json::Value, AppError> {
     let token = get_jira_token_internal().await?;
-    let client = crate::jira::JiraClient::new(&jira_url, &email, &token)?;
+    let auth = JiraAuth::from_method(auth_method.unwrap_or_default(), &email, &token);
+    let client = jira_client(&app_handle, &jira_url, auth)?;
 
-    // Simple verification: try to fetch 1 ticket
-    let _ = client.fetch_tickets(None).await?;
+    client.authenticate().await?;
 
     let mut response = serde_json::Map::new();
     response.insert("email".to_string(), serde_json::Value::String(email));
@@ -67,8 +253,41 @@ pub async fn save_jira_settings(
     app_handle: AppHandle,
     jira_url: String,
     email: String,
+    auth_method: Option<JiraAuthMethod>,
+    limits: Option<JiraLimits>,
+    fields: Option<JiraFieldMapping>,
+    conflict_policy: Option<ConflictPolicy>,
 ) -> Result<(), AppError> {
-    let settings = JiraSettings { jira_url, email };
+    // Keep previously saved limits, fields, and policy when the caller only edits credentials
+    let saved = load_jira_settings_internal(&app_handle)?;
+    let limits = match limits {
+        Some(limits) => limits,
+        None => saved
+            .as_ref()
+            .map(|settings| settings.limits)
+            .unwrap_or_default(),
+    };
+    let fields = match fields {
+        Some(fields) => fields,
+        None => saved
+            .as_ref()
+            .map(|settings| settings.fields.clone())
+            .unwrap_or_default(),
+    };
+    let conflict_policy = match conflict_policy {
+        Some(policy) => policy,
+        None => saved
+            .map(|settings| settings.conflict_policy)
+            .unwrap_or_default(),
+    };
+    let settings = JiraSettings {
+        jira_url,
+        email,
+        auth_method: auth_method.unwrap_or_default(),
+        limits,
+        fields,
+        conflict_policy,
+    };
 
     let store = app_handle
         .store("settings.json")
@@ -88,6 +307,12 @@ pub async fn save_jira_settings(
 
 #[tauri::command]
 pub async fn load_jira_settings(app_handle: AppHandle) -> Result<Option<JiraSettings>, AppError> {
+    load_jira_settings_internal(&app_handle)
+}
+
+pub(crate) fn load_jira_settings_internal(
+    app_handle: &AppHandle,
+) -> Result<Option<JiraSettings>, AppError> {
     let store = app_handle
         .store("settings.json")
         .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
@@ -102,3 +327,338 @@ pub async fn load_jira_settings(app_handle: AppHandle) -> Result<Option<JiraSett
         Ok(None)
     }
 }
+
+#[tauri::command]
+pub async fn save_network_settings(
+    app_handle: AppHandle,
+    settings: NetworkSettings,
+) -> Result<(), AppError> {
+    // Fail on save rather than on the next sync
+    build_http_client(&settings)?;
+
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+
+    let settings_value = serde_json::to_value(&settings)
+        .map_err(|e| AppError::Config(format!("Failed to serialize settings: {}", e)))?;
+
+    store.set("network", settings_value);
+
+    store
+        .save()
+        .map_err(|e| AppError::Config(format!("Failed to save settings: {}", e)))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn load_network_settings(app_handle: AppHandle) -> Result<NetworkSettings, AppError> {
+    load_network_settings_internal(&app_handle)
+}
+
+fn load_network_settings_internal(app_handle: &AppHandle) -> Result<NetworkSettings, AppError> {
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+
+    match store.get("network") {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|e| AppError::Config(format!("Failed to parse settings: {}", e))),
+        None => Ok(NetworkSettings::default()),
+    }
+}
+
+#[tauri::command]
+pub async fn save_smtp_settings(
+    app_handle: AppHandle,
+    settings: SmtpSettings,
+) -> Result<(), AppError> {
+    // Catches a missing host or malformed addresses before the first send
+    EmailSender::new(&settings, None)?;
+
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+
+    let settings_value = serde_json::to_value(&settings)
+        .map_err(|e| AppError::Config(format!("Failed to serialize settings: {}", e)))?;
+
+    store.set("smtp", settings_value);
+
+    store
+        .save()
+        .map_err(|e| AppError::Config(format!("Failed to save settings: {}", e)))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn load_smtp_settings(app_handle: AppHandle) -> Result<SmtpSettings, AppError> {
+    load_smtp_settings_internal(&app_handle)
+}
+
+pub(crate) fn load_smtp_settings_internal(
+    app_handle: &AppHandle,
+) -> Result<SmtpSettings, AppError> {
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+
+    match store.get("smtp") {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|e| AppError::Config(format!("Failed to parse settings: {}", e))),
+        None => Ok(SmtpSettings::default()),
+    }
+}
+
+#[tauri::command]
+pub async fn save_retention_settings(
+    app_handle: AppHandle,
+    settings: RetentionSettings,
+) -> Result<(), AppError> {
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+
+    let settings_value = serde_json::to_value(&settings)
+        .map_err(|e| AppError::Config(format!("Failed to serialize settings: {}", e)))?;
+
+    store.set("retention", settings_value);
+
+    store
+        .save()
+        .map_err(|e| AppError::Config(format!("Failed to save settings: {}", e)))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn load_retention_settings(app_handle: AppHandle) -> Result<RetentionSettings, AppError> {
+    load_retention_settings_internal(&app_handle)
+}
+
+pub(crate) fn load_retention_settings_internal(
+    app_handle: &AppHandle,
+) -> Result<RetentionSettings, AppError> {
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+
+    match store.get("retention") {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|e| AppError::Config(format!("Failed to parse settings: {}", e))),
+        None => Ok(RetentionSettings::default()),
+    }
+}
+
+#[tauri::command]
+pub async fn save_categorizer_settings(
+    app_handle: AppHandle,
+    settings: CategorizerSettings,
+) -> Result<(), AppError> {
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+
+    let settings_value = serde_json::to_value(&settings)
+        .map_err(|e| AppError::Config(format!("Failed to serialize settings: {}", e)))?;
+
+    store.set("categorizer", settings_value);
+
+    store
+        .save()
+        .map_err(|e| AppError::Config(format!("Failed to save settings: {}", e)))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn load_categorizer_settings(
+    app_handle: AppHandle,
+) -> Result<CategorizerSettings, AppError> {
+    load_categorizer_settings_internal(&app_handle)
+}
+
+fn load_categorizer_settings_internal(
+    app_handle: &AppHandle,
+) -> Result<CategorizerSettings, AppError> {
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+
+    match store.get("categorizer") {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|e| AppError::Config(format!("Failed to parse settings: {}", e))),
+        None => Ok(CategorizerSettings::default()),
+    }
+}
+
+fn categorizer_api_key_entry() -> Result<Entry, AppError> {
+    Entry::new(SERVICE_NAME, "categorizer-api-key").map_err(|e| AppError::Keyring(e.to_string()))
+}
+
+/// API key sent as a bearer token to the embeddings endpoint. Local model
+/// servers usually don't need one.
+#[tauri::command]
+pub async fn store_categorizer_api_key(api_key: String) -> Result<(), AppError> {
+    categorizer_api_key_entry()?
+        .set_password(&api_key)
+        .map_err(|e| AppError::Keyring(e.to_string()))
+}
+
+#[tauri::command]
+pub async fn delete_categorizer_api_key() -> Result<(), AppError> {
+    match categorizer_api_key_entry()?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(AppError::Keyring(e.to_string())),
+    }
+}
+
+fn get_categorizer_api_key_internal() -> Result<Option<String>, AppError> {
+    match categorizer_api_key_entry()?.get_password() {
+        Ok(api_key) => Ok(Some(api_key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(AppError::Keyring(e.to_string())),
+    }
+}
+
+/// Categorizer for a sync or import, as configured in Settings, applying
+/// `rules` first whichever backend is chosen.
+pub(crate) fn categorizer_backend(
+    app_handle: &AppHandle,
+    rules: &[CategoryRule],
+) -> Result<Box<dyn CategorizerBackend>, AppError> {
+    let settings = load_categorizer_settings_internal(app_handle)?;
+    match settings.backend {
+        CategorizerKind::Rules => Ok(Box::new(RulesCategorizer::new(rules.to_vec()))),
+        CategorizerKind::Embedding => {
+            let (Some(endpoint), Some(model)) = (settings.endpoint, settings.model) else {
+                return Err(AppError::Config(
+                    "The embedding categorizer needs an endpoint and a model".to_string(),
+                ));
+            };
+            Ok(Box::new(EmbeddingCategorizer::new(
+                &endpoint,
+                &model,
+                get_categorizer_api_key_internal()?,
+                rules.to_vec(),
+                settings.min_confidence,
+                http_client(app_handle)?,
+            )))
+        }
+    }
+}
+
+/// Jira client using the shared HTTP settings and the saved request limits
+/// and custom field mapping.
+pub(crate) fn jira_client(
+    app_handle: &AppHandle,
+    jira_url: &str,
+    auth: JiraAuth,
+) -> Result<JiraClient, AppError> {
+    let (limits, fields) = load_jira_settings_internal(app_handle)?
+        .map(|settings| (settings.limits, settings.fields))
+        .unwrap_or_default();
+
+    Ok(JiraClient::new(jira_url, auth, http_client(app_handle)?)?
+        .with_limits(limits)
+        .with_field_mapping(fields))
+}
+
+/// Custom field mapping from the saved Jira settings.
+pub(crate) fn jira_field_mapping(app_handle: &AppHandle) -> Result<JiraFieldMapping, AppError> {
+    Ok(load_jira_settings_internal(app_handle)?
+        .map(|settings| settings.fields)
+        .unwrap_or_default())
+}
+
+/// Conflict policy from the saved Jira settings.
+pub(crate) fn conflict_policy(app_handle: &AppHandle) -> Result<ConflictPolicy, AppError> {
+    Ok(load_jira_settings_internal(app_handle)?
+        .map(|settings| settings.conflict_policy)
+        .unwrap_or_default())
+}
+
+/// Jira client for the primary account saved in Settings, used by write-back commands.
+pub(crate) async fn saved_jira_client(app_handle: &AppHandle) -> Result<JiraClient, AppError> {
+    let settings = load_jira_settings_internal(app_handle)?.ok_or(JiraError::NotConfigured)?;
+    let token = get_jira_token_internal().await?;
+    let auth = JiraAuth::from_method(settings.auth_method, &settings.email, &token);
+    jira_client(app_handle, &settings.jira_url, auth)
+}
+
+/// Jira client for an account added next to the primary site, using its own token.
+pub(crate) async fn account_jira_client(
+    app_handle: &AppHandle,
+    account: JiraAccount,
+) -> Result<JiraClient, AppError> {
+    let token = get_account_token_internal(account.id).await?;
+    let auth = JiraAuth::from_method(account.auth_method, &account.email, &token);
+    Ok(jira_client(app_handle, &account.base_url, auth)?.with_account(account.id))
+}
+
+/// Jira client for the site stored ticket `jira_key` was synced from: its
+/// account's, or the primary one for tickets without an account.
+pub(crate) async fn ticket_jira_client(
+    app_handle: &AppHandle,
+    db: &DbPool,
+    jira_key: &str,
+) -> Result<JiraClient, AppError> {
+    let key = jira_key.to_string();
+    let account = db
+        .run(
+            move |conn| match db::get_ticket_by_key(conn, &key)?.and_then(|t| t.account_id) {
+                Some(account_id) => db::get_jira_account(conn, account_id),
+                None => Ok(None),
+            },
+        )
+        .await?;
+    match account {
+        Some(account) => account_jira_client(app_handle, account).await,
+        None => saved_jira_client(app_handle).await,
+    }
+}
+
+/// HTTP client honouring the saved proxy and TLS settings, shared by every connector.
+pub(crate) fn http_client(app_handle: &AppHandle) -> Result<reqwest::Client, AppError> {
+    build_http_client(&load_network_settings_internal(app_handle)?)
+}
+
+fn source_token_entry(source: &str) -> Result<(&'static str, Entry), AppError> {
+    let source_id = TOKEN_SOURCES
+        .iter()
+        .copied()
+        .find(|known| *known == source)
+        .ok_or_else(|| AppError::Config(format!("Unknown ticket source: {}", source)))?;
+    let entry = Entry::new(SERVICE_NAME, &format!("{}-api-token", source_id))
+        .map_err(|e| AppError::Keyring(e.to_string()))?;
+    Ok((source_id, entry))
+}
+
+#[tauri::command]
+pub async fn store_source_token(source: String, token: String) -> Result<(), AppError> {
+    let (_, entry) = source_token_entry(&source)?;
+    entry
+        .set_password(&token)
+        .map_err(|e| AppError::Keyring(e.to_string()))?;
+    Ok(())
+}
+
+pub(crate) async fn get_source_token_internal(source: &str) -> Result<String, AppError> {
+    let (source_id, entry) = source_token_entry(source)?;
+    match entry.get_password() {
+        Ok(token) => Ok(token),
+        Err(keyring::Error::NoEntry) => Err(ConnectorError::NotConfigured(source_id).into()),
+        Err(e) => Err(AppError::Keyring(e.to_string())),
+    }
+}
+
+#[tauri::command]
+pub async fn delete_source_token(source: String) -> Result<(), AppError> {
+    let (_, entry) = source_token_entry(&source)?;
+    entry
+        .delete_credential()
+        .map_err(|e| AppError::Keyring(e.to_string()))?;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{ReportSnapshot, SnapshotDiff};
+use crate::services::snapshots;
+
+#[tauri::command]
+pub async fn list_report_snapshots(
+    db: tauri::State<'_, DbPool>,
+) -> Result<Vec<ReportSnapshot>, AppError> {
+    db.run(db::list_report_snapshots).await
+}
+
+/// Takes a snapshot now, in addition to the automatic weekly one.
+#[tauri::command]
+pub async fn take_report_snapshot(
+    db: tauri::State<'_, DbPool>,
+) -> Result<ReportSnapshot, AppError> {
+    db.run(|conn| snapshots::take_snapshot(conn, chrono::Utc::now().naive_utc()))
+        .await
+}
+
+/// The frozen dashboard data of one snapshot.
+#[tauri::command]
+pub async fn get_report_snapshot(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+) -> Result<Option<serde_json::Value>, AppError> {
+    let Some((_, data)) = db
+        .run(move |conn| db::get_report_snapshot(conn, id))
+        .await?
+    else {
+        return Ok(None);
+    };
+    serde_json::from_str(&data)
+        .map(Some)
+        .map_err(|e| AppError::Internal(format!("Failed to parse snapshot: {}", e)))
+}
+
+#[tauri::command]
+pub async fn get_snapshot_diff(
+    db: tauri::State<'_, DbPool>,
+    a: i64,
+    b: i64,
+) -> Result<Option<SnapshotDiff>, AppError> {
+    db.run(move |conn| snapshots::get_snapshot_diff(conn, a, b))
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_report_snapshot(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_report_snapshot(conn, id))
+        .await
+}
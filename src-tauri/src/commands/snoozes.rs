@@ -0,0 +1,37 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::TicketSnooze;
+use crate::services::time_calc::parse_timestamp;
+
+/// Hides a ticket from default views until `until` (RFC 3339), or until a
+/// sync brings a change to it first.
+#[tauri::command]
+pub async fn snooze_ticket(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+    until: String,
+) -> Result<TicketSnooze, AppError> {
+    let until = parse_timestamp(&until)
+        .ok_or_else(|| AppError::Config(format!("Invalid snooze time: {}", until)))?
+        .and_utc();
+    let now = chrono::Utc::now();
+    if until <= now {
+        return Err(AppError::Config(
+            "Snooze time must be in the future".to_string(),
+        ));
+    }
+    db.run(move |conn| db::snooze_ticket(conn, &key, until, now))
+        .await
+}
+
+#[tauri::command]
+pub async fn unsnooze_ticket(db: tauri::State<'_, DbPool>, key: String) -> Result<bool, AppError> {
+    db.run(move |conn| db::unsnooze_ticket(conn, &key)).await
+}
+
+#[tauri::command]
+pub async fn get_snoozed_tickets(
+    db: tauri::State<'_, DbPool>,
+) -> Result<Vec<TicketSnooze>, AppError> {
+    db.run(db::list_snoozed_tickets).await
+}
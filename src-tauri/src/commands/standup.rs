@@ -0,0 +1,33 @@
+use crate::db::DbPool;
+use crate::errors::AppError;
+use crate::models::Standup;
+use crate::services::{alerts, standup};
+use chrono::NaiveDate;
+
+/// Builds the standup for `date` (`YYYY-MM-DD`, today by default) for
+/// `assignee`, or for the `my_name` alert setting when none is given.
+#[tauri::command]
+pub async fn generate_standup(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+    date: Option<String>,
+    assignee: Option<String>,
+) -> Result<Standup, AppError> {
+    let now = chrono::Utc::now().naive_utc();
+    let date = match date {
+        Some(date) => NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .map_err(|_| AppError::Config(format!("Invalid date: {}", date)))?,
+        None => now.date(),
+    };
+    let assignee = match assignee {
+        Some(assignee) => assignee,
+        None => alerts::load_alert_settings(&app_handle)?
+            .my_name
+            .ok_or_else(|| {
+                AppError::Config("Set my_name in the alert settings first".to_string())
+            })?,
+    };
+
+    db.run(move |conn| standup::build_standup(conn, &assignee, date, now))
+        .await
+}
@@ -0,0 +1,44 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{StatusCategory, StatusCategoryMapping};
+
+#[tauri::command]
+pub async fn get_status_category_mappings(
+    db: tauri::State<'_, DbPool>,
+) -> Result<Vec<StatusCategoryMapping>, AppError> {
+    db.run(db::get_status_category_mappings).await
+}
+
+/// Sets the category for every ticket in `status` (or removes the override
+/// when `category` is null) and returns how many stored tickets changed.
+#[tauri::command]
+pub async fn set_status_category_mapping(
+    db: tauri::State<'_, DbPool>,
+    status: String,
+    category: Option<StatusCategory>,
+) -> Result<u32, AppError> {
+    db.run(move |conn| db::set_status_category_mapping(conn, &status, category))
+        .await
+}
+
+#[tauri::command]
+pub async fn get_resolved_statuses(db: tauri::State<'_, DbPool>) -> Result<Vec<String>, AppError> {
+    db.run(db::get_resolved_statuses).await
+}
+
+/// Replaces the statuses that count as resolved even without a resolution
+/// date, and returns how many stored tickets changed.
+#[tauri::command]
+pub async fn set_resolved_statuses(
+    db: tauri::State<'_, DbPool>,
+    statuses: Vec<String>,
+) -> Result<u32, AppError> {
+    let statuses: Vec<String> = statuses
+        .iter()
+        .map(|status| status.trim())
+        .filter(|status| !status.is_empty())
+        .map(str::to_string)
+        .collect();
+    db.run(move |conn| db::set_resolved_statuses(conn, &statuses))
+        .await
+}
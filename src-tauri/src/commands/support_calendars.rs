@@ -0,0 +1,48 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{SupportCalendar, SupportCalendarInput};
+use crate::services::time_calc::validate_schedule;
+
+#[tauri::command]
+pub async fn list_support_calendars(
+    db: tauri::State<'_, DbPool>,
+) -> Result<Vec<SupportCalendar>, AppError> {
+    db.run(db::list_support_calendars).await
+}
+
+#[tauri::command]
+pub async fn create_support_calendar(
+    db: tauri::State<'_, DbPool>,
+    calendar: SupportCalendarInput,
+) -> Result<SupportCalendar, AppError> {
+    validate(&calendar)?;
+    db.run(move |conn| db::create_support_calendar(conn, &calendar))
+        .await
+}
+
+#[tauri::command]
+pub async fn update_support_calendar(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+    calendar: SupportCalendarInput,
+) -> Result<Option<SupportCalendar>, AppError> {
+    validate(&calendar)?;
+    db.run(move |conn| db::update_support_calendar(conn, id, &calendar))
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_support_calendar(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_support_calendar(conn, id))
+        .await
+}
+
+fn validate(calendar: &SupportCalendarInput) -> Result<(), AppError> {
+    if calendar.name.trim().is_empty() {
+        return Err(AppError::Config("Calendar name is required".to_string()));
+    }
+    validate_schedule(&calendar.schedule)
+}
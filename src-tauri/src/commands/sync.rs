@@ -1,34 +1,44 @@
-use crate::db::{get_sync_metadata, set_sync_metadata, upsert_ticket, DbPool};
+use crate::db::{self, get_sync_metadata, DbPool};
 use crate::errors::{AppError, DbError};
-use crate::jira::JiraClient;
-use crate::services::categorize_ticket;
-use serde::{Deserialize, Serialize};
+use crate::jira::{JiraAuth, JiraAuthMethod, JiraClient};
+use crate::models::SyncRun;
+use crate::services::categorizer::CategoryRule;
+use crate::services::outbox::flush_outbox;
+use crate::services::source_sync::{preview_source, sync_source, SourceSyncOutcome};
+use serde::Deserialize;
+use std::future::Future;
 use tauri::Emitter;
 
 pub struct SyncLock(pub tokio::sync::Mutex<bool>);
 
+/// Runs returned by `get_sync_history` when no limit is given.
+const DEFAULT_SYNC_HISTORY_LIMIT: u32 = 50;
+
 #[derive(Deserialize)]
 pub struct CategoryRulesWrapper {
     #[serde(rename = "categoryRules")]
     pub category_rules: Vec<crate::services::categorizer::CategoryRule>,
 }
 
-#[derive(Serialize, Clone)]
-struct SyncProgress {
-    phase: String,
-    current: usize,
-    total: Option<usize>,
+/// Parses the `{"categoryRules": [...]}` payload the frontend sends with every sync.
+pub(crate) fn parse_category_rules(
+    category_rules_json: &str,
+) -> Result<Vec<CategoryRule>, AppError> {
+    let rules_wrapper: CategoryRulesWrapper = serde_json::from_str(category_rules_json)
+        .map_err(|e| AppError::Config(format!("Failed to parse category rules: {}", e)))?;
+    Ok(rules_wrapper.category_rules)
 }
 
-#[tauri::command]
-pub async fn trigger_sync(
-    lock: tauri::State<'_, SyncLock>,
-    db: tauri::State<'_, DbPool>,
-    jira_url: String,
-    email: String,
-    category_rules_json: String,
-    app_handle: tauri::AppHandle,
-) -> Result<serde_json::Value, AppError> {
+/// Holds the global sync lock for the duration of `sync` and reports the
+/// outcome through the shared sync-* events.
+pub(crate) async fn run_exclusive_sync<F>(
+    lock: &SyncLock,
+    app_handle: &tauri::AppHandle,
+    sync: F,
+) -> Result<serde_json::Value, AppError>
+where
+    F: Future<Output = Result<serde_json::Value, AppError>>,
+{
     let mut is_syncing = lock.0.lock().await;
     if *is_syncing {
         return Err(AppError::SyncAlreadyInProgress);
@@ -39,7 +49,7 @@ pub async fn trigger_sync(
     // Emit sync started
     app_handle.emit("sync-started", ()).ok();
 
-    let result = perform_sync(db, jira_url, email, category_rules_json, app_handle.clone()).await;
+    let result = sync.await;
 
     let mut is_syncing = lock.0.lock().await;
     *is_syncing = false;
@@ -57,125 +67,102 @@ pub async fn trigger_sync(
     result
 }
 
-async fn perform_sync(
+pub(crate) fn sync_response(outcome: &SourceSyncOutcome) -> serde_json::Value {
+    let mut response = serde_json::Map::new();
+    response.insert(
+        "source".to_string(),
+        serde_json::Value::String(outcome.source_id.to_string()),
+    );
+    response.insert(
+        "synced".to_string(),
+        serde_json::Value::from(outcome.synced as u64),
+    );
+    response.insert(
+        "inserted".to_string(),
+        serde_json::Value::from(outcome.upserts.inserted),
+    );
+    response.insert(
+        "updated".to_string(),
+        serde_json::Value::from(outcome.upserts.updated),
+    );
+    response.insert(
+        "unchanged".to_string(),
+        serde_json::Value::from(outcome.upserts.unchanged),
+    );
+    response.insert(
+        "errors".to_string(),
+        serde_json::Value::from(outcome.failures.len() as u64),
+    );
+    response.insert(
+        "last_sync".to_string(),
+        serde_json::Value::String(chrono::Utc::now().to_rfc3339()),
+    );
+
+    serde_json::Value::Object(response)
+}
+
+async fn jira_sync_client(
+    app_handle: &tauri::AppHandle,
+    jira_url: &str,
+    email: &str,
+    auth_method: Option<JiraAuthMethod>,
+    category_rules_json: &str,
+) -> Result<(JiraClient, Vec<CategoryRule>), AppError> {
+    let token = super::settings::get_jira_token_internal().await?;
+    let category_rules = parse_category_rules(category_rules_json)?;
+    let auth = JiraAuth::from_method(auth_method.unwrap_or_default(), email, &token);
+    let client = super::settings::jira_client(app_handle, jira_url, auth)?;
+    Ok((client, category_rules))
+}
+
+/// Syncs Jira into the local database. With `dry_run` the tickets are
+/// fetched and compared with what is stored, and a `SyncPreview` is
+/// returned without writing anything or sending queued write-backs.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn trigger_sync(
+    lock: tauri::State<'_, SyncLock>,
     db: tauri::State<'_, DbPool>,
     jira_url: String,
     email: String,
+    auth_method: Option<JiraAuthMethod>,
     category_rules_json: String,
+    dry_run: Option<bool>,
     app_handle: tauri::AppHandle,
 ) -> Result<serde_json::Value, AppError> {
-    // Get token
-    let token = super::settings::get_jira_token_internal().await?;
-
-    // Parse category rules
-    let rules_wrapper: CategoryRulesWrapper = serde_json::from_str(&category_rules_json)
-        .map_err(|e| AppError::Config(format!("Failed to parse category rules: {}", e)))?;
-    let category_rules = rules_wrapper.category_rules;
-
-    // Create Jira client
-    let client = JiraClient::new(&jira_url, &email, &token)?;
-
-    // Get last sync timestamp
-    let db_clone = db.0.clone();
-    let last_sync_ts = tauri::async_runtime::spawn_blocking(move || {
-        let conn = db_clone.lock().map_err(|_| DbError::LockFailed)?;
-        get_sync_metadata(&conn, "last_sync_at")
-    })
-    .await
-    .map_err(|_| AppError::Internal("Task join failed".to_string()))??;
-
-    // Emit fetching phase
-    app_handle
-        .emit(
-            "sync-progress",
-            SyncProgress {
-                phase: "fetching".to_string(),
-                current: 0,
-                total: None,
-            },
-        )
-        .ok();
-
-    // Fetch tickets from Jira
-    let mut tickets = client.fetch_tickets(last_sync_ts.as_deref()).await?;
-
-    let total_count = tickets.len();
-
-    // Emit categorizing phase
-    app_handle
-        .emit(
-            "sync-progress",
-            SyncProgress {
-                phase: "categorizing".to_string(),
-                current: 0,
-                total: Some(total_count),
-            },
+    if dry_run.unwrap_or(false) {
+        let (client, category_rules) = jira_sync_client(
+            &app_handle,
+            &jira_url,
+            &email,
+            auth_method,
+            &category_rules_json,
         )
-        .ok();
-
-    // Categorize tickets
-    for (idx, ticket) in tickets.iter_mut().enumerate() {
-        ticket.category = categorize_ticket(ticket, &category_rules);
-
-        // Emit progress every 10 tickets
-        if idx % 10 == 0 {
-            app_handle
-                .emit(
-                    "sync-progress",
-                    SyncProgress {
-                        phase: "categorizing".to_string(),
-                        current: idx,
-                        total: Some(total_count),
-                    },
-                )
-                .ok();
-        }
+        .await?;
+        let categorizer = super::settings::categorizer_backend(&app_handle, &category_rules)?;
+        let preview = preview_source(&client, &db, categorizer.as_ref()).await?;
+        return serde_json::to_value(preview)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize sync preview: {}", e)));
     }
 
-    let synced_count = tickets.len();
-
-    // Emit saving phase
-    app_handle
-        .emit(
-            "sync-progress",
-            SyncProgress {
-                phase: "saving".to_string(),
-                current: 0,
-                total: Some(synced_count),
-            },
+    run_exclusive_sync(&lock, &app_handle, async {
+        let (client, category_rules) = jira_sync_client(
+            &app_handle,
+            &jira_url,
+            &email,
+            auth_method,
+            &category_rules_json,
         )
-        .ok();
-
-    // Store in database
-    let db_clone = db.0.clone();
-    tauri::async_runtime::spawn_blocking(move || {
-        let conn = db_clone.lock().map_err(|_| DbError::LockFailed)?;
+        .await?;
 
-        for ticket in &tickets {
-            upsert_ticket(&conn, ticket)?;
+        // Send offline write-backs first so the sync pulls their results
+        if let Err(e) = flush_outbox(&client, &db, None, false).await {
+            log::warn!("Outbox flush failed: {}", e);
         }
-
-        // Update last sync timestamp
-        let now = chrono::Utc::now().to_rfc3339();
-        set_sync_metadata(&conn, "last_sync_at", &now)?;
-
-        Ok::<(), AppError>(())
+        let outcome = sync_source(&client, db.0.clone(), &category_rules, &app_handle).await?;
+        Ok(sync_response(&outcome))
     })
     .await
-    .map_err(|_| AppError::Internal("Task join failed".to_string()))??;
-
-    let mut response = serde_json::Map::new();
-    response.insert(
-        "synced".to_string(),
-        serde_json::Value::from(synced_count as u64),
-    );
-    response.insert("errors".to_string(), serde_json::Value::from(0_u64));
-    response.insert(
-        "last_sync".to_string(),
-        serde_json::Value::String(chrono::Utc::now().to_rfc3339()),
-    );
-
-    Ok(serde_json::Value::Object(response))
 }
 
 #[tauri::command]
@@ -200,3 +187,13 @@ pub async fn get_sync_status(db: tauri::State<'_, DbPool>) -> Result<serde_json:
 
     Ok(serde_json::Value::Object(response))
 }
+
+/// Recent sync runs across all sources, newest first.
+#[tauri::command]
+pub async fn get_sync_history(
+    limit: Option<u32>,
+    db: tauri::State<'_, DbPool>,
+) -> Result<Vec<SyncRun>, AppError> {
+    let limit = limit.unwrap_or(DEFAULT_SYNC_HISTORY_LIMIT);
+    db.run(move |conn| db::get_sync_history(conn, limit)).await
+}
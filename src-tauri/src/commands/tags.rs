@@ -0,0 +1,39 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{CountEntry, TicketFilter};
+
+#[tauri::command]
+pub async fn tag_tickets(
+    db: tauri::State<'_, DbPool>,
+    jira_keys: Vec<String>,
+    tag: String,
+) -> Result<usize, AppError> {
+    db.run(move |conn| db::tag_tickets(conn, &jira_keys, &tag))
+        .await
+}
+
+#[tauri::command]
+pub async fn untag_tickets(
+    db: tauri::State<'_, DbPool>,
+    jira_keys: Vec<String>,
+    tag: String,
+) -> Result<usize, AppError> {
+    db.run(move |conn| db::untag_tickets(conn, &jira_keys, &tag))
+        .await
+}
+
+#[tauri::command]
+pub async fn get_ticket_tags(
+    db: tauri::State<'_, DbPool>,
+    jira_key: String,
+) -> Result<Vec<String>, AppError> {
+    db.run(move |conn| db::get_ticket_tags(conn, &jira_key))
+        .await
+}
+
+/// Every local tag in use with its ticket count, for tag pickers and filters.
+#[tauri::command]
+pub async fn list_local_tags(db: tauri::State<'_, DbPool>) -> Result<Vec<CountEntry>, AppError> {
+    db.run(|conn| db::get_count_by_local_tag(conn, &TicketFilter::default()))
+        .await
+}
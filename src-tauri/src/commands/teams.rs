@@ -0,0 +1,50 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{DurationMode, Team, TeamInput, TeamRollup, TicketFilter};
+
+#[tauri::command]
+pub async fn list_teams(db: tauri::State<'_, DbPool>) -> Result<Vec<Team>, AppError> {
+    db.run(db::list_teams).await
+}
+
+#[tauri::command]
+pub async fn create_team(db: tauri::State<'_, DbPool>, team: TeamInput) -> Result<Team, AppError> {
+    validate(&team)?;
+    db.run(move |conn| db::create_team(conn, &team)).await
+}
+
+#[tauri::command]
+pub async fn update_team(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+    team: TeamInput,
+) -> Result<Option<Team>, AppError> {
+    validate(&team)?;
+    db.run(move |conn| db::update_team(conn, id, &team)).await
+}
+
+#[tauri::command]
+pub async fn delete_team(db: tauri::State<'_, DbPool>, id: i64) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_team(conn, id)).await
+}
+
+/// Open tickets, throughput over the last 4 weeks, and resolution time per
+/// team, for the filtered tickets.
+#[tauri::command]
+pub async fn get_team_rollups(
+    db: tauri::State<'_, DbPool>,
+    filter: Option<TicketFilter>,
+    duration_mode: Option<DurationMode>,
+) -> Result<Vec<TeamRollup>, AppError> {
+    let filter = filter.unwrap_or_default();
+    let mode = duration_mode.unwrap_or_default();
+    db.run(move |conn| db::get_team_rollups(conn, &filter, mode, chrono::Utc::now().naive_utc()))
+        .await
+}
+
+fn validate(team: &TeamInput) -> Result<(), AppError> {
+    if team.name.trim().is_empty() {
+        return Err(AppError::Config("Team name is required".to_string()));
+    }
+    Ok(())
+}
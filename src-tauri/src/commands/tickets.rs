@@ -1,27 +1,461 @@
-use crate::db::{get_aggregations, get_tickets, DbPool};
+use crate::db::{self, get_ticket_by_key, get_tickets, DbPool};
 use crate::errors::{AppError, DbError};
-use crate::models::{AggregationResult, Ticket};
+use crate::models::{
+    AffectedVersionStats, AggregateEntry, AggregationResult, CohortMatrix, CountEntry,
+    CustomerStats, DateRange, Dimension, DurationMode, HistoryRange, InflowAnomaly, LinkedTicket,
+    Metric, PivotTable, PointsEntry, ProjectStats, QueryPlan, QuickSearchResult, RecentlyViewed,
+    ReleaseReadiness, SimilarTicket, StaleTicketGroup, StatsHistoryEntry, StatusDwellViolation,
+    Ticket, TicketChange, TicketChunk, TicketCluster, TicketFilter, TrendingTerm, VelocityEntry,
+};
+use crate::services::alerts::load_alert_settings;
+use crate::services::anomaly::{inflow_anomalies, BASELINE_DAYS, DEFAULT_Z_THRESHOLD};
+use crate::services::cache::AggregationCache;
+use crate::services::clustering;
+use crate::services::similar::similar_tickets;
+use crate::services::status_dwell::status_dwell_violations;
+use crate::services::trending::trending_terms;
+use tauri::ipc::Channel;
 
 #[tauri::command]
 pub async fn get_dashboard_data(
     db: tauri::State<'_, DbPool>,
+    cache: tauri::State<'_, AggregationCache>,
+    filter: Option<TicketFilter>,
+    duration_mode: Option<DurationMode>,
 ) -> Result<AggregationResult, AppError> {
+    let filter = filter.unwrap_or_default();
+    let mode = duration_mode.unwrap_or_default();
     let db_clone = db.0.clone();
+    let cache = cache.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         let conn = db_clone.lock().map_err(|_| DbError::LockFailed)?;
-        get_aggregations(&conn)
+        cache.get_or_compute(&conn, &filter, mode)
     })
     .await
     .map_err(|_| AppError::Internal("Task join failed".to_string()))?
 }
 
+/// `metric` for each value of `dimension` among the filtered tickets, for
+/// charts the fixed dashboard aggregations don't cover.
 #[tauri::command]
-pub async fn get_all_tickets(db: tauri::State<'_, DbPool>) -> Result<Vec<Ticket>, AppError> {
+pub async fn aggregate(
+    db: tauri::State<'_, DbPool>,
+    dimension: Dimension,
+    metric: Metric,
+    filter: Option<TicketFilter>,
+    duration_mode: Option<DurationMode>,
+) -> Result<Vec<AggregateEntry>, AppError> {
+    let filter = filter.unwrap_or_default();
+    let mode = duration_mode.unwrap_or_default();
+    db.run(move |conn| db::aggregate(conn, &dimension, metric, &filter, mode))
+        .await
+}
+
+/// `metric` over every `rows` × `columns` combination of the filtered
+/// tickets, e.g. priority × status counts for a heatmap.
+#[tauri::command]
+pub async fn get_pivot(
+    db: tauri::State<'_, DbPool>,
+    rows: Dimension,
+    columns: Dimension,
+    metric: Metric,
+    filter: Option<TicketFilter>,
+    duration_mode: Option<DurationMode>,
+) -> Result<PivotTable, AppError> {
+    let filter = filter.unwrap_or_default();
+    let mode = duration_mode.unwrap_or_default();
+    db.run(move |conn| db::get_pivot(conn, &rows, &columns, metric, &filter, mode))
+        .await
+}
+
+/// Query plans of the main dashboard queries, for diagnosing slow
+/// dashboards on large databases.
+#[tauri::command]
+pub async fn explain_queries(
+    db: tauri::State<'_, DbPool>,
+    filter: Option<TicketFilter>,
+    duration_mode: Option<DurationMode>,
+) -> Result<Vec<QueryPlan>, AppError> {
+    let filter = filter.unwrap_or_default();
+    let mode = duration_mode.unwrap_or_default();
+    db.run(move |conn| db::explain_dashboard_queries(conn, &filter, mode))
+        .await
+}
+
+#[tauri::command]
+pub async fn get_all_tickets(
+    db: tauri::State<'_, DbPool>,
+    filter: Option<TicketFilter>,
+) -> Result<Vec<Ticket>, AppError> {
+    let filter = filter.unwrap_or_default().without_snoozed();
     let db_clone = db.0.clone();
     tauri::async_runtime::spawn_blocking(move || {
         let conn = db_clone.lock().map_err(|_| DbError::LockFailed)?;
-        get_tickets(&conn)
+        get_tickets(&conn, &filter)
     })
     .await
     .map_err(|_| AppError::Internal("Task join failed".to_string()))?
 }
+
+/// Like `get_all_tickets`, but each ticket carries only the named `fields`,
+/// for list views that don't need whole tickets.
+#[tauri::command]
+pub async fn get_tickets_projection(
+    db: tauri::State<'_, DbPool>,
+    fields: Vec<String>,
+    filter: Option<TicketFilter>,
+) -> Result<Vec<serde_json::Map<String, serde_json::Value>>, AppError> {
+    let filter = filter.unwrap_or_default().without_snoozed();
+    db.run(move |conn| db::get_tickets_projection(conn, &fields, &filter))
+        .await
+}
+
+/// Tickets per `stream_tickets` chunk unless the caller asks otherwise.
+const DEFAULT_TICKET_CHUNK_SIZE: u32 = 500;
+const MAX_TICKET_CHUNK_SIZE: u32 = 5_000;
+
+/// Sends the same tickets as `get_all_tickets` over `on_chunk`, a page at a
+/// time, so large databases don't cross IPC as one huge array. The database
+/// lock is released between chunks. Returns the number of tickets sent.
+#[tauri::command]
+pub async fn stream_tickets(
+    db: tauri::State<'_, DbPool>,
+    filter: Option<TicketFilter>,
+    chunk_size: Option<u32>,
+    on_chunk: Channel<TicketChunk>,
+) -> Result<u32, AppError> {
+    let filter = filter.unwrap_or_default().without_snoozed();
+    let chunk_size = chunk_size
+        .unwrap_or(DEFAULT_TICKET_CHUNK_SIZE)
+        .clamp(1, MAX_TICKET_CHUNK_SIZE);
+
+    let mut offset = 0;
+    loop {
+        let page_filter = filter.clone();
+        let tickets = db
+            .run(move |conn| db::get_tickets_page(conn, &page_filter, chunk_size, offset))
+            .await?;
+        let sent = tickets.len() as u32;
+        let done = sent < chunk_size;
+        on_chunk
+            .send(TicketChunk {
+                offset,
+                tickets,
+                done,
+            })
+            .map_err(|e| AppError::Internal(format!("Failed to send ticket chunk: {}", e)))?;
+        offset += sent;
+        if done {
+            return Ok(offset);
+        }
+    }
+}
+
+/// Open tickets not updated in `days` days (default 7), grouped by assignee.
+#[tauri::command]
+pub async fn get_stale_tickets(
+    db: tauri::State<'_, DbPool>,
+    days: Option<u32>,
+) -> Result<Vec<StaleTicketGroup>, AppError> {
+    let days = days.unwrap_or(db::STALE_TICKET_DAYS);
+    db.run(move |conn| db::get_stale_tickets(conn, days, chrono::Utc::now().naive_utc()))
+        .await
+}
+
+/// Open ticket counts per age bucket (0–1d … 30d+) for the age-distribution chart.
+#[tauri::command]
+pub async fn get_open_ticket_age_histogram(
+    db: tauri::State<'_, DbPool>,
+    priority: Option<String>,
+) -> Result<Vec<CountEntry>, AppError> {
+    db.run(move |conn| {
+        db::get_open_ticket_age_histogram(conn, priority.as_deref(), chrono::Utc::now().naive_utc())
+    })
+    .await
+}
+
+/// Per-project comparison; SLA compliance uses the targets from the alert settings.
+#[tauri::command]
+pub async fn get_stats_by_project(
+    db: tauri::State<'_, DbPool>,
+    app_handle: tauri::AppHandle,
+    filter: Option<TicketFilter>,
+    duration_mode: Option<DurationMode>,
+) -> Result<Vec<ProjectStats>, AppError> {
+    let filter = filter.unwrap_or_default();
+    let mode = duration_mode.unwrap_or_default();
+    let targets = load_alert_settings(&app_handle)?.sla_targets_hours;
+    db.run(move |conn| db::get_stats_by_project(conn, &filter, mode, &targets))
+        .await
+}
+
+/// Times tickets stayed in a status longer than the dwell limits from the
+/// alert settings allow, furthest over first.
+#[tauri::command]
+pub async fn get_status_dwell_violations(
+    db: tauri::State<'_, DbPool>,
+    app_handle: tauri::AppHandle,
+    filter: Option<TicketFilter>,
+) -> Result<Vec<StatusDwellViolation>, AppError> {
+    let filter = filter.unwrap_or_default();
+    let limits = load_alert_settings(&app_handle)?.status_dwell_limits_hours;
+    db.run(move |conn| {
+        status_dwell_violations(conn, &filter, &limits, chrono::Utc::now().naive_utc())
+    })
+    .await
+}
+
+/// Summary stats recorded after each sync, one point per day (default: past year).
+#[tauri::command]
+pub async fn get_stats_history(
+    db: tauri::State<'_, DbPool>,
+    range: Option<HistoryRange>,
+) -> Result<Vec<StatsHistoryEntry>, AppError> {
+    let range = range.unwrap_or_default();
+    db.run(move |conn| db::get_stats_history(conn, range, chrono::Utc::now().naive_utc()))
+        .await
+}
+
+/// Days in `range` (default: past year) on which unusually many tickets
+/// were created, judged against the four weeks before each day.
+#[tauri::command]
+pub async fn get_inflow_anomalies(
+    db: tauri::State<'_, DbPool>,
+    range: Option<HistoryRange>,
+    z_threshold: Option<f64>,
+) -> Result<Vec<InflowAnomaly>, AppError> {
+    let days = range.unwrap_or_default().days() as u32;
+    let z_threshold = z_threshold.unwrap_or(DEFAULT_Z_THRESHOLD);
+    db.run(move |conn| {
+        let tickets = get_tickets(conn, &TicketFilter::default())?;
+        Ok(inflow_anomalies(
+            &tickets,
+            chrono::Utc::now().naive_utc(),
+            days,
+            BASELINE_DAYS,
+            z_threshold,
+        ))
+    })
+    .await
+}
+
+/// Words in the summaries of tickets created in `range` (default: past
+/// month) that are much more common than before it, best first.
+#[tauri::command]
+pub async fn get_trending_terms(
+    db: tauri::State<'_, DbPool>,
+    range: Option<HistoryRange>,
+    limit: Option<u32>,
+) -> Result<Vec<TrendingTerm>, AppError> {
+    let days = range.unwrap_or(HistoryRange::Month).days() as u32;
+    let limit = limit.unwrap_or(20) as usize;
+    db.run(move |conn| {
+        let tickets = get_tickets(conn, &TicketFilter::default())?;
+        Ok(trending_terms(
+            &tickets,
+            chrono::Utc::now().naive_utc(),
+            days,
+            limit,
+        ))
+    })
+    .await
+}
+
+/// Tickets created in `range` (default: past month) grouped into at most
+/// `k` (default 8) clusters of similar summaries, largest first.
+#[tauri::command]
+pub async fn cluster_tickets(
+    db: tauri::State<'_, DbPool>,
+    range: Option<HistoryRange>,
+    k: Option<u32>,
+) -> Result<Vec<TicketCluster>, AppError> {
+    let since =
+        chrono::Utc::now() - chrono::Duration::days(range.unwrap_or(HistoryRange::Month).days());
+    let k = k.unwrap_or(8) as usize;
+    db.run(move |conn| {
+        let filter = TicketFilter {
+            created: Some(DateRange::since(since.format("%Y-%m-%d").to_string())),
+            ..Default::default()
+        };
+        Ok(clustering::cluster_tickets(get_tickets(conn, &filter)?, k))
+    })
+    .await
+}
+
+/// Up to `limit` (default 10) other tickets whose summaries resemble `key`'s,
+/// so prior incidents and their resolutions can be looked up while triaging.
+/// Empty when `key` isn't stored locally.
+#[tauri::command]
+pub async fn find_similar_tickets(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+    limit: Option<u32>,
+) -> Result<Vec<SimilarTicket>, AppError> {
+    let limit = limit.unwrap_or(10) as usize;
+    db.run(move |conn| {
+        let Some(target) = get_ticket_by_key(conn, &key)? else {
+            return Ok(Vec::new());
+        };
+        let candidates = get_tickets(conn, &TicketFilter::default())?;
+        Ok(similar_tickets(&target, candidates, limit))
+    })
+    .await
+}
+
+/// Tickets created within `range` by creation month, with the fraction of
+/// each month resolved within 1, 7 and 30 days.
+#[tauri::command]
+pub async fn get_cohort_resolution(
+    db: tauri::State<'_, DbPool>,
+    range: Option<DateRange>,
+) -> Result<CohortMatrix, AppError> {
+    let range = range.unwrap_or_default();
+    let now = chrono::Utc::now().naive_utc();
+    db.run(move |conn| db::get_cohort_resolution(conn, &range, now))
+        .await
+}
+
+/// Ranked ticket matches for the cmd-K quick switcher: an exact key match,
+/// then full-text matches, or recently updated tickets when `text` is empty.
+#[tauri::command]
+pub async fn quick_search(
+    db: tauri::State<'_, DbPool>,
+    text: String,
+    limit: Option<u32>,
+) -> Result<Vec<QuickSearchResult>, AppError> {
+    let limit = limit.unwrap_or(20);
+    db.run(move |conn| db::quick_search(conn, &text, limit))
+        .await
+}
+
+/// Records that the user opened ticket `key`, for `get_recently_viewed`.
+#[tauri::command]
+pub async fn mark_viewed(db: tauri::State<'_, DbPool>, key: String) -> Result<(), AppError> {
+    let viewed_at = chrono::Utc::now().to_rfc3339();
+    db.run(move |conn| db::mark_viewed(conn, &key, &viewed_at))
+        .await
+}
+
+/// The tickets the user opened most recently, latest first.
+#[tauri::command]
+pub async fn get_recently_viewed(
+    db: tauri::State<'_, DbPool>,
+    limit: Option<u32>,
+) -> Result<Vec<RecentlyViewed>, AppError> {
+    let limit = limit.unwrap_or(10);
+    db.run(move |conn| db::get_recently_viewed(conn, limit))
+        .await
+}
+
+/// Tickets linked to `key` (blocks, duplicates, relates to, ...) in either direction.
+#[tauri::command]
+pub async fn get_linked_tickets(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+) -> Result<Vec<LinkedTicket>, AppError> {
+    db.run(move |conn| db::get_linked_tickets(conn, &key)).await
+}
+
+/// Field changes recorded locally for `key` each time a newer version was
+/// stored, newest first.
+#[tauri::command]
+pub async fn get_ticket_change_log(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+) -> Result<Vec<TicketChange>, AppError> {
+    db.run(move |conn| db::get_ticket_change_log(conn, &key))
+        .await
+}
+
+/// Committed vs. completed story points for recent sprints, oldest first.
+#[tauri::command]
+pub async fn get_velocity_by_sprint(
+    db: tauri::State<'_, DbPool>,
+    filter: Option<TicketFilter>,
+    sprints: Option<usize>,
+) -> Result<Vec<VelocityEntry>, AppError> {
+    let filter = filter.unwrap_or_default();
+    let sprints = sprints.unwrap_or(db::VELOCITY_SPRINTS);
+    db.run(move |conn| db::get_velocity_by_sprint(conn, &filter, sprints))
+        .await
+}
+
+#[tauri::command]
+pub async fn get_points_by_assignee(
+    db: tauri::State<'_, DbPool>,
+    filter: Option<TicketFilter>,
+) -> Result<Vec<PointsEntry>, AppError> {
+    let filter = filter.unwrap_or_default();
+    db.run(move |conn| db::get_points_by_assignee(conn, &filter))
+        .await
+}
+
+/// Volume, open tickets, and average resolution time per customer, busiest
+/// first.
+#[tauri::command]
+pub async fn get_tickets_by_customer(
+    db: tauri::State<'_, DbPool>,
+    filter: Option<TicketFilter>,
+    duration_mode: Option<DurationMode>,
+) -> Result<Vec<CustomerStats>, AppError> {
+    let filter = filter.unwrap_or_default();
+    let mode = duration_mode.unwrap_or_default();
+    db.run(move |conn| db::get_tickets_by_customer(conn, &filter, mode))
+        .await
+}
+
+/// Tickets per affected version, most first, to size the regressions each
+/// release introduced.
+#[tauri::command]
+pub async fn get_affected_version_stats(
+    db: tauri::State<'_, DbPool>,
+    filter: Option<TicketFilter>,
+    duration_mode: Option<DurationMode>,
+) -> Result<Vec<AffectedVersionStats>, AppError> {
+    let filter = filter.unwrap_or_default();
+    let mode = duration_mode.unwrap_or_default();
+    db.run(move |conn| db::get_affected_version_stats(conn, &filter, mode))
+        .await
+}
+
+/// Open tickets past their due date, most overdue first.
+#[tauri::command]
+pub async fn get_overdue_tickets(
+    db: tauri::State<'_, DbPool>,
+    filter: Option<TicketFilter>,
+) -> Result<Vec<Ticket>, AppError> {
+    let filter = filter.unwrap_or_default();
+    db.run(move |conn| db::get_overdue_tickets(conn, &filter, chrono::Utc::now().date_naive()))
+        .await
+}
+
+/// Open tickets due in the next `days` days (default 7), for the deadlines widget.
+#[tauri::command]
+pub async fn get_tickets_due_soon(
+    db: tauri::State<'_, DbPool>,
+    filter: Option<TicketFilter>,
+    days: Option<u32>,
+) -> Result<Vec<Ticket>, AppError> {
+    let filter = filter.unwrap_or_default();
+    let days = days.unwrap_or(db::DUE_SOON_DAYS);
+    db.run(move |conn| {
+        db::get_tickets_due_soon(conn, &filter, days, chrono::Utc::now().date_naive())
+    })
+    .await
+}
+
+/// Fix versions seen on synced tickets, with their ticket counts.
+#[tauri::command]
+pub async fn list_fix_versions(db: tauri::State<'_, DbPool>) -> Result<Vec<CountEntry>, AppError> {
+    db.run(db::list_fix_versions).await
+}
+
+/// Open vs. done counts, blockers and a projected finish date for a fix version.
+#[tauri::command]
+pub async fn get_release_readiness(
+    db: tauri::State<'_, DbPool>,
+    version: String,
+) -> Result<Option<ReleaseReadiness>, AppError> {
+    db.run(move |conn| db::get_release_readiness(conn, &version, chrono::Utc::now().naive_utc()))
+        .await
+}
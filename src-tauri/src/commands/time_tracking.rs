@@ -0,0 +1,144 @@
+use super::settings::ticket_jira_client;
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{DateRange, FocusStats, TimeEntry, TimeReportPeriod, TimeReportRow};
+use crate::services::time_calc::parse_timestamp;
+use chrono::NaiveDate;
+
+/// Length of a focus session when none is given.
+const DEFAULT_FOCUS_MINUTES: u32 = 25;
+
+fn parse_date(value: &str) -> Result<NaiveDate, AppError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| AppError::Config(format!("Invalid date (expected YYYY-MM-DD): {}", value)))
+}
+
+/// Starts timing work on ticket `key`. Starting a timer that already runs
+/// returns it unchanged, so several tickets can be timed at once.
+#[tauri::command]
+pub async fn start_timer(db: tauri::State<'_, DbPool>, key: String) -> Result<TimeEntry, AppError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    db.run(move |conn| db::start_timer(conn, &key, &now)).await
+}
+
+/// Stops the timer on ticket `key`; `None` when it wasn't running.
+#[tauri::command]
+pub async fn stop_timer(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+) -> Result<Option<TimeEntry>, AppError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    db.run(move |conn| db::stop_timer(conn, &key, &now)).await
+}
+
+/// Starts a focus session (25 minutes unless `minutes` says otherwise) on
+/// ticket `key`. Stop it with `stop_timer`; its time counts like any timer's.
+#[tauri::command]
+pub async fn start_focus_session(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+    minutes: Option<u32>,
+) -> Result<TimeEntry, AppError> {
+    let minutes = minutes.unwrap_or(DEFAULT_FOCUS_MINUTES);
+    if minutes == 0 {
+        return Err(AppError::Config(
+            "A focus session needs at least one minute".to_string(),
+        ));
+    }
+    let now = chrono::Utc::now().to_rfc3339();
+    db.run(move |conn| db::start_focus_session(conn, &key, minutes, &now))
+        .await
+}
+
+/// Counts an interruption against the focus session running on ticket
+/// `key`; `None` when no focus session runs.
+#[tauri::command]
+pub async fn record_interruption(
+    db: tauri::State<'_, DbPool>,
+    key: String,
+) -> Result<Option<TimeEntry>, AppError> {
+    db.run(move |conn| db::record_interruption(conn, &key))
+        .await
+}
+
+/// Sessions, completions, interruptions and focused time for the focus
+/// sessions started within `range`, overall and per ticket.
+#[tauri::command]
+pub async fn get_focus_stats(
+    db: tauri::State<'_, DbPool>,
+    range: Option<DateRange>,
+) -> Result<FocusStats, AppError> {
+    let range = range.unwrap_or_default();
+    let now = chrono::Utc::now().naive_utc();
+    db.run(move |conn| db::get_focus_stats(conn, &range, now))
+        .await
+}
+
+#[tauri::command]
+pub async fn get_running_timers(db: tauri::State<'_, DbPool>) -> Result<Vec<TimeEntry>, AppError> {
+    db.run(db::get_running_timers).await
+}
+
+/// Time tracked per ticket and day (or week) between the `from` and `to`
+/// dates (`YYYY-MM-DD`, `to` exclusive), in UTC.
+#[tauri::command]
+pub async fn get_time_report(
+    db: tauri::State<'_, DbPool>,
+    from: String,
+    to: String,
+    period: Option<TimeReportPeriod>,
+) -> Result<Vec<TimeReportRow>, AppError> {
+    let from = parse_date(&from)?;
+    let to = parse_date(&to)?;
+    if from >= to {
+        return Err(AppError::Config("`from` must be before `to`".to_string()));
+    }
+    let period = period.unwrap_or_default();
+    let now = chrono::Utc::now().naive_utc();
+    db.run(move |conn| db::get_time_report(conn, period, from, to, now))
+        .await
+}
+
+/// Pushes a stopped time entry to Jira as a worklog on its ticket. Each
+/// entry is pushed at most once.
+#[tauri::command]
+pub async fn push_time_entry(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+) -> Result<TimeEntry, AppError> {
+    let entry = db
+        .run(move |conn| db::get_time_entry(conn, id))
+        .await?
+        .ok_or_else(|| AppError::Config(format!("Time entry {} not found", id)))?;
+    if entry.worklog_id.is_some() {
+        return Err(AppError::Config(format!(
+            "Time entry {} was already logged to Jira",
+            id
+        )));
+    }
+    let (Some(started), Some(ended)) = (
+        parse_timestamp(&entry.started_at),
+        entry.ended_at.as_deref().and_then(parse_timestamp),
+    ) else {
+        return Err(AppError::Config(format!(
+            "Stop the timer on {} before logging it",
+            entry.ticket_key
+        )));
+    };
+
+    let client = ticket_jira_client(&app_handle, &db, &entry.ticket_key).await?;
+    let worklog_id = client
+        .add_worklog(
+            db::jira_issue_key(&entry.ticket_key),
+            started,
+            (ended - started).num_seconds(),
+        )
+        .await?;
+    db.run(move |conn| {
+        db::set_worklog_id(conn, id, &worklog_id)?;
+        db::get_time_entry(conn, id)
+    })
+    .await?
+    .ok_or_else(|| AppError::Internal(format!("Time entry {} disappeared", id)))
+}
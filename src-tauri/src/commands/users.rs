@@ -0,0 +1,31 @@
+use super::settings::saved_jira_client;
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::User;
+
+/// Fetches the users assignable in every project tickets were synced from
+/// and stores them in the local directory. Returns how many were stored.
+#[tauri::command]
+pub async fn sync_users(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+) -> Result<usize, AppError> {
+    let project_keys = db.run(db::synced_jira_project_keys).await?;
+    let client = saved_jira_client(&app_handle).await?;
+    let users = client.fetch_users(&project_keys).await?;
+    let synced_at = chrono::Utc::now().to_rfc3339();
+    db.run(move |conn| db::upsert_users(conn, &users, &synced_at))
+        .await
+}
+
+/// The synced user directory, e.g. for assignee pickers. Inactive users are
+/// left out unless `include_inactive` is set.
+#[tauri::command]
+pub async fn list_users(
+    db: tauri::State<'_, DbPool>,
+    include_inactive: Option<bool>,
+) -> Result<Vec<User>, AppError> {
+    let include_inactive = include_inactive.unwrap_or(false);
+    db.run(move |conn| db::list_users(conn, include_inactive))
+        .await
+}
@@ -0,0 +1,40 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{SavedView, SavedViewInput};
+
+#[tauri::command]
+pub async fn list_saved_views(db: tauri::State<'_, DbPool>) -> Result<Vec<SavedView>, AppError> {
+    db.run(db::list_saved_views).await
+}
+
+#[tauri::command]
+pub async fn create_saved_view(
+    db: tauri::State<'_, DbPool>,
+    view: SavedViewInput,
+) -> Result<SavedView, AppError> {
+    validate(&view)?;
+    db.run(move |conn| db::create_saved_view(conn, &view)).await
+}
+
+#[tauri::command]
+pub async fn update_saved_view(
+    db: tauri::State<'_, DbPool>,
+    id: i64,
+    view: SavedViewInput,
+) -> Result<Option<SavedView>, AppError> {
+    validate(&view)?;
+    db.run(move |conn| db::update_saved_view(conn, id, &view))
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_saved_view(db: tauri::State<'_, DbPool>, id: i64) -> Result<bool, AppError> {
+    db.run(move |conn| db::delete_saved_view(conn, id)).await
+}
+
+fn validate(view: &SavedViewInput) -> Result<(), AppError> {
+    if view.name.trim().is_empty() {
+        return Err(AppError::Config("View name is required".to_string()));
+    }
+    Ok(())
+}
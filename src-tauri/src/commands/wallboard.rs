@@ -0,0 +1,81 @@
+use crate::db::DbPool;
+use crate::errors::AppError;
+use crate::models::WallboardPayload;
+use crate::services::alerts::load_alert_settings;
+use crate::services::time_calc::validate_utc_offset;
+use crate::services::wallboard::{load_wallboard, WallboardTicker};
+use std::sync::Mutex;
+
+const DEFAULT_WALLBOARD_INTERVAL_SECONDS: u64 = 30;
+
+/// The running wallboard ticker, if any.
+pub struct WallboardState(pub Mutex<Option<WallboardTicker>>);
+
+/// KPIs, SLA countdowns and newest criticals for a TV display, scoped to a
+/// saved view or to all tickets. Today's counts follow the display's
+/// `utc_offset_minutes` (default 0, i.e. UTC).
+#[tauri::command]
+pub async fn get_wallboard_payload(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, DbPool>,
+    view_id: Option<i64>,
+    utc_offset_minutes: Option<i32>,
+) -> Result<WallboardPayload, AppError> {
+    let utc_offset_minutes = utc_offset_minutes.unwrap_or(0);
+    validate_utc_offset(utc_offset_minutes)?;
+    let settings = load_alert_settings(&app_handle)?;
+    let now = chrono::Utc::now().naive_utc();
+    db.run(move |conn| load_wallboard(conn, view_id, &settings, now, utc_offset_minutes))
+        .await
+}
+
+/// Starts (or restarts) pushing `wallboard-updated` events every
+/// `interval_seconds` (5 to 3600, default 30).
+#[tauri::command]
+pub async fn start_wallboard(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, WallboardState>,
+    db: tauri::State<'_, DbPool>,
+    view_id: Option<i64>,
+    utc_offset_minutes: Option<i32>,
+    interval_seconds: Option<u64>,
+) -> Result<(), AppError> {
+    let utc_offset_minutes = utc_offset_minutes.unwrap_or(0);
+    validate_utc_offset(utc_offset_minutes)?;
+    let interval_seconds = interval_seconds.unwrap_or(DEFAULT_WALLBOARD_INTERVAL_SECONDS);
+    if !(5..=3600).contains(&interval_seconds) {
+        return Err(AppError::Config(
+            "Wallboard interval must be between 5 and 3600 seconds".to_string(),
+        ));
+    }
+    if let Some(id) = view_id {
+        db.run(move |conn| {
+            crate::db::get_saved_view(conn, id)?
+                .map(|_| ())
+                .ok_or_else(|| AppError::Config(format!("Unknown saved view: {}", id)))
+        })
+        .await?;
+    }
+
+    let mut current = state
+        .0
+        .lock()
+        .map_err(|_| AppError::Internal("Wallboard state lock poisoned".to_string()))?;
+    *current = Some(WallboardTicker::start(
+        DbPool(db.0.clone()),
+        view_id,
+        utc_offset_minutes,
+        interval_seconds,
+        app_handle,
+    ));
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_wallboard(state: tauri::State<'_, WallboardState>) -> Result<bool, AppError> {
+    let mut current = state
+        .0
+        .lock()
+        .map_err(|_| AppError::Internal("Wallboard state lock poisoned".to_string()))?;
+    Ok(current.take().is_some())
+}
@@ -0,0 +1,38 @@
+use crate::db::{self, DbPool};
+use crate::errors::AppError;
+use crate::models::{Ticket, WatchlistChange};
+
+#[tauri::command]
+pub async fn watch_ticket(db: tauri::State<'_, DbPool>, jira_key: String) -> Result<(), AppError> {
+    db.run(move |conn| db::watch_ticket(conn, &jira_key)).await
+}
+
+#[tauri::command]
+pub async fn unwatch_ticket(
+    db: tauri::State<'_, DbPool>,
+    jira_key: String,
+) -> Result<bool, AppError> {
+    db.run(move |conn| db::unwatch_ticket(conn, &jira_key))
+        .await
+}
+
+#[tauri::command]
+pub async fn get_watchlist(db: tauri::State<'_, DbPool>) -> Result<Vec<Ticket>, AppError> {
+    db.run(db::list_watched_tickets).await
+}
+
+#[tauri::command]
+pub async fn get_watchlist_changes(
+    db: tauri::State<'_, DbPool>,
+) -> Result<Vec<WatchlistChange>, AppError> {
+    db.run(db::get_watchlist_changes).await
+}
+
+#[tauri::command]
+pub async fn acknowledge_watchlist_change(
+    db: tauri::State<'_, DbPool>,
+    jira_key: String,
+) -> Result<(), AppError> {
+    db.run(move |conn| db::acknowledge_watchlist_change(conn, &jira_key))
+        .await
+}
@@ -0,0 +1,85 @@
+use super::settings::{jira_field_mapping, webhook_listener_secret};
+use super::sync::parse_category_rules;
+use crate::db::DbPool;
+use crate::errors::AppError;
+use crate::models::WebhookListenerStatus;
+use crate::services::webhook::WebhookListener;
+use std::sync::{Mutex, MutexGuard};
+
+const DEFAULT_WEBHOOK_PORT: u16 = 8787;
+
+/// The running webhook listener, if any.
+pub struct WebhookState(pub Mutex<Option<WebhookListener>>);
+
+impl WebhookState {
+    fn lock(&self) -> Result<MutexGuard<'_, Option<WebhookListener>>, AppError> {
+        self.0
+            .lock()
+            .map_err(|_| AppError::Internal("Webhook state lock poisoned".to_string()))
+    }
+}
+
+/// Stops `listener` on a blocking thread, since dropping it joins its
+/// listener thread.
+async fn shut_down(listener: Option<WebhookListener>) -> Result<(), AppError> {
+    if let Some(listener) = listener {
+        tauri::async_runtime::spawn_blocking(move || drop(listener))
+            .await
+            .map_err(|_| AppError::Internal("Task join failed".to_string()))?;
+    }
+    Ok(())
+}
+
+/// Starts (or restarts) the local Jira webhook listener and returns the bound
+/// port with the secret the webhook URL must carry. The secret is kept in the
+/// OS keychain, so it survives restarts unless `regenerate_secret` is set. The
+/// new listener is bound before the old one stops, so a failed restart on
+/// another port leaves the running listener up.
+#[tauri::command]
+pub async fn start_webhook_listener(
+    state: tauri::State<'_, WebhookState>,
+    db: tauri::State<'_, DbPool>,
+    port: Option<u16>,
+    regenerate_secret: Option<bool>,
+    category_rules_json: String,
+    app_handle: tauri::AppHandle,
+) -> Result<WebhookListenerStatus, AppError> {
+    let category_rules = parse_category_rules(&category_rules_json)?;
+    let fields = jira_field_mapping(&app_handle)?;
+    let secret = webhook_listener_secret(regenerate_secret.unwrap_or(false))?;
+    let port = port.unwrap_or(DEFAULT_WEBHOOK_PORT);
+
+    let running_port = state.lock()?.as_ref().map(WebhookListener::port);
+    if running_port == Some(port) {
+        // The old listener holds this port, so it has to let go first
+        let old = state.lock()?.take();
+        shut_down(old).await?;
+    }
+
+    let listener = WebhookListener::start(
+        port,
+        secret.clone(),
+        db.0.clone(),
+        category_rules,
+        fields,
+        app_handle,
+    )?;
+    let status = WebhookListenerStatus {
+        port: listener.port(),
+        secret,
+    };
+    let old = state.lock()?.replace(listener);
+    shut_down(old).await?;
+
+    Ok(status)
+}
+
+#[tauri::command]
+pub async fn stop_webhook_listener(
+    state: tauri::State<'_, WebhookState>,
+) -> Result<bool, AppError> {
+    let old = state.lock()?.take();
+    let stopped = old.is_some();
+    shut_down(old).await?;
+    Ok(stopped)
+}
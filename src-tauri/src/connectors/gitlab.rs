@@ -0,0 +1,501 @@
+use crate::connectors::{read_json_response, SourceBatch, TicketSource};
+use crate::errors::{AppError, ConnectorError};
+use crate::models::{Ticket, TicketLink};
+use async_trait::async_trait;
+use chrono::DateTime;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+pub const SOURCE_ID: &str = "gitlab";
+
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+#[derive(Deserialize)]
+pub struct GitLabIssue {
+    pub iid: u64,
+    pub title: String,
+    pub state: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub assignees: Vec<GitLabUser>,
+    pub author: Option<GitLabUser>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub closed_at: Option<String>,
+    pub issue_type: Option<String>,
+    pub references: GitLabReferences,
+    /// Fetched separately after the issue list; `None` when that failed.
+    #[serde(skip)]
+    pub merge_requests: Option<GitLabIssueMergeRequests>,
+}
+
+#[derive(Deserialize)]
+pub struct GitLabUser {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct GitLabReferences {
+    pub full: String,
+}
+
+#[derive(Deserialize)]
+pub struct GitLabMergeRequest {
+    pub references: GitLabReferences,
+}
+
+/// Full references (e.g. `group/app!3`) of the merge requests tied to an
+/// issue. Closing ones close it when merged; the rest only mention it.
+#[derive(Debug, Default)]
+pub struct GitLabIssueMergeRequests {
+    pub closing: Vec<String>,
+    pub related: Vec<String>,
+}
+
+pub struct GitLabClient {
+    base_url: String,
+    project: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+impl GitLabClient {
+    pub fn new(
+        gitlab_url: &str,
+        project: &str,
+        token: &str,
+        http_client: reqwest::Client,
+    ) -> Result<Self, AppError> {
+        let project = project.trim().trim_matches('/');
+        if project.is_empty() {
+            return Err(AppError::Config(
+                "GitLab project path is required".to_string(),
+            ));
+        }
+
+        Ok(GitLabClient {
+            base_url: format!("{}/api/v4", gitlab_url.trim_end_matches('/')),
+            project: project.to_string(),
+            token: token.to_string(),
+            client: http_client,
+        })
+    }
+
+    fn normalize_updated_after(last_sync_ts: Option<&str>) -> Option<String> {
+        let ts = last_sync_ts?;
+        match DateTime::parse_from_rfc3339(ts) {
+            Ok(parsed) => Some(parsed.to_rfc3339()),
+            Err(_) => {
+                log::warn!(
+                    "Invalid GitLab last_sync_at value '{}'; falling back to full sync",
+                    ts
+                );
+                None
+            }
+        }
+    }
+
+    fn project_url(&self) -> String {
+        format!(
+            "{}/projects/{}",
+            self.base_url,
+            self.project.replace('/', "%2F")
+        )
+    }
+
+    /// GETs `url`, waiting out up to `MAX_RATE_LIMIT_RETRIES` 429 responses.
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, AppError> {
+        let mut attempt = 0;
+
+        loop {
+            match self.get_json_once(url).await {
+                Err(AppError::Connector(ConnectorError::RateLimited {
+                    retry_after_secs, ..
+                })) if attempt < MAX_RATE_LIMIT_RETRIES => {
+                    attempt += 1;
+                    log::warn!(
+                        "GitLab rate limited; retrying in {}s (attempt {}/{})",
+                        retry_after_secs,
+                        attempt,
+                        MAX_RATE_LIMIT_RETRIES
+                    );
+                    tokio::time::sleep(tokio::time::Duration::from_secs(retry_after_secs)).await;
+                }
+                other => return other,
+            }
+        }
+    }
+
+    async fn get_json_once<T: DeserializeOwned>(&self, url: &str) -> Result<T, AppError> {
+        let response = self
+            .client
+            .get(url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .query(&[("per_page", "100")])
+            .send()
+            .await
+            .map_err(|e| ConnectorError::Http(SOURCE_ID, e))?;
+        read_json_response(SOURCE_ID, response).await
+    }
+
+    async fn issue_merge_requests(&self, iid: u64) -> Result<GitLabIssueMergeRequests, AppError> {
+        let issue_url = format!("{}/issues/{}", self.project_url(), iid);
+        let closing: Vec<GitLabMergeRequest> =
+            self.get_json(&format!("{}/closed_by", issue_url)).await?;
+        let related: Vec<GitLabMergeRequest> = self
+            .get_json(&format!("{}/related_merge_requests", issue_url))
+            .await?;
+
+        let closing: Vec<String> = closing.into_iter().map(|mr| mr.references.full).collect();
+        let related = related
+            .into_iter()
+            .map(|mr| mr.references.full)
+            .filter(|reference| !closing.contains(reference))
+            .collect();
+        Ok(GitLabIssueMergeRequests { closing, related })
+    }
+
+    async fn list_issues(
+        &self,
+        updated_after: Option<&str>,
+        page: u32,
+    ) -> Result<(Vec<GitLabIssue>, Option<u32>), AppError> {
+        let url = format!("{}/issues", self.project_url());
+
+        let mut query = vec![
+            ("order_by", "updated_at".to_string()),
+            ("sort", "asc".to_string()),
+            ("per_page", "100".to_string()),
+            ("page", page.to_string()),
+        ];
+        if let Some(ts) = updated_after {
+            query.push(("updated_after", ts.to_string()));
+        }
+
+        let response = self
+            .client
+            .get(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| ConnectorError::Http(SOURCE_ID, e))?;
+
+        // GitLab leaves X-Next-Page empty on the last page
+        let next_page = response
+            .headers()
+            .get("X-Next-Page")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u32>().ok());
+
+        let issues: Vec<GitLabIssue> = read_json_response(SOURCE_ID, response).await?;
+        Ok((issues, next_page))
+    }
+
+    fn convert_issue_to_ticket(project: &str, issue: GitLabIssue) -> Ticket {
+        let status = match issue.state.as_str() {
+            "opened" => "Open".to_string(),
+            "closed" => "Closed".to_string(),
+            other => other.to_string(),
+        };
+        let priority = Self::priority_from_labels(&issue.labels);
+
+        Ticket {
+            id: 0, // Will be set by database
+            jira_key: issue.references.full,
+            summary: issue.title,
+            status,
+            status_category: None,
+            priority,
+            priority_level: None, // Set on write from the priority mappings
+            issue_type: match issue.issue_type.as_deref() {
+                Some("incident") => "Incident".to_string(),
+                Some("test_case") => "Test Case".to_string(),
+                _ => "Issue".to_string(),
+            },
+            assignee: issue.assignees.into_iter().next().map(|a| a.name),
+            reporter: issue.author.map(|a| a.name),
+            created_at: issue.created_at,
+            updated_at: issue.updated_at,
+            resolved_at: issue.closed_at,
+            labels: issue.labels.join(","),
+            project_key: project.to_string(),
+            category: None, // Will be set by categorizer
+            category_confidence: None,
+            source: SOURCE_ID.to_string(),
+            custom_fields: String::new(),
+            account_id: None,
+            story_points: None,
+            sprint: None,
+            due_date: None,
+            customer: None,
+            environment: None,
+            escalated: false,
+            components: Vec::new(),
+            fix_versions: Vec::new(),
+            affected_versions: Vec::new(),
+        }
+    }
+
+    /// Links from each merge request to `issue_key`, outward from the merge request.
+    fn convert_merge_request_links(
+        issue_key: &str,
+        merge_requests: &GitLabIssueMergeRequests,
+    ) -> Vec<TicketLink> {
+        let link =
+            |merge_request: &String, link_type: &str, outward: &str, inward: &str| TicketLink {
+                outward_key: merge_request.clone(),
+                inward_key: issue_key.to_string(),
+                link_type: link_type.to_string(),
+                outward_label: outward.to_string(),
+                inward_label: inward.to_string(),
+            };
+
+        merge_requests
+            .closing
+            .iter()
+            .map(|mr| link(mr, "Closes", "closes", "is closed by"))
+            .chain(
+                merge_requests
+                    .related
+                    .iter()
+                    .map(|mr| link(mr, "Relates", "relates to", "relates to")),
+            )
+            .collect()
+    }
+
+    /// GitLab has no priority field; teams conventionally use scoped
+    /// `priority::<level>` labels, which map onto the Jira-style scale.
+    fn priority_from_labels(labels: &[String]) -> String {
+        labels
+            .iter()
+            .find_map(|label| {
+                let (scope, value) = label.split_once("::")?;
+                if !scope.trim().eq_ignore_ascii_case("priority") {
+                    return None;
+                }
+                let priority = match value.trim().to_lowercase().as_str() {
+                    "critical" | "p1" | "urgent" => "Critical",
+                    "high" | "p2" => "High",
+                    "medium" | "p3" => "Medium",
+                    "low" | "p4" => "Low",
+                    _ => return None,
+                };
+                Some(priority.to_string())
+            })
+            .unwrap_or_else(|| "Medium".to_string())
+    }
+}
+
+#[async_trait]
+impl TicketSource for GitLabClient {
+    type Item = GitLabIssue;
+
+    fn source_id(&self) -> &'static str {
+        SOURCE_ID
+    }
+
+    async fn authenticate(&self) -> Result<(), AppError> {
+        let response = self
+            .client
+            .get(format!("{}/user", self.base_url))
+            .header("PRIVATE-TOKEN", &self.token)
+            .send()
+            .await
+            .map_err(|e| ConnectorError::Http(SOURCE_ID, e))?;
+
+        read_json_response::<serde_json::Value>(SOURCE_ID, response).await?;
+        Ok(())
+    }
+
+    async fn fetch_incremental(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<SourceBatch<GitLabIssue>, AppError> {
+        let sync_started_at = chrono::Utc::now().to_rfc3339();
+        let updated_after = Self::normalize_updated_after(cursor);
+        let mut items = Vec::new();
+        let mut page: u32 = 1;
+
+        loop {
+            let (issues, next_page) = self.list_issues(updated_after.as_deref(), page).await?;
+            items.extend(issues);
+
+            match next_page {
+                Some(next) => page = next,
+                None => break,
+            }
+        }
+
+        for issue in &mut items {
+            match self.issue_merge_requests(issue.iid).await {
+                Ok(merge_requests) => issue.merge_requests = Some(merge_requests),
+                Err(AppError::Connector(ConnectorError::RateLimited { .. })) => {
+                    // Still limited after retrying; the rest sync without their links
+                    // rather than hammering the API, and their stored links are kept
+                    log::warn!(
+                        "GitLab rate limit persisted; skipping merge requests from {} on",
+                        issue.references.full
+                    );
+                    break;
+                }
+                Err(e) => {
+                    // The issue still syncs; its stored links are kept
+                    log::warn!(
+                        "Failed to fetch merge requests for {}: {}",
+                        issue.references.full,
+                        e
+                    );
+                }
+            }
+        }
+
+        Ok(SourceBatch {
+            items,
+            failures: Vec::new(),
+            next_cursor: Some(sync_started_at),
+        })
+    }
+
+    fn map_to_ticket(&self, item: GitLabIssue) -> Ticket {
+        Self::convert_issue_to_ticket(&self.project, item)
+    }
+
+    fn map_links(&self, item: &GitLabIssue) -> Option<Vec<TicketLink>> {
+        item.merge_requests.as_ref().map(|merge_requests| {
+            Self::convert_merge_request_links(&item.references.full, merge_requests)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_issue(state: &str, labels: &[&str]) -> GitLabIssue {
+        GitLabIssue {
+            iid: 7,
+            title: "Login page times out".to_string(),
+            state: state.to_string(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            assignees: vec![GitLabUser {
+                name: "Ada".to_string(),
+            }],
+            author: None,
+            created_at: "2025-01-06T09:00:00.000Z".to_string(),
+            updated_at: "2025-01-07T09:00:00.000Z".to_string(),
+            closed_at: None,
+            issue_type: Some("issue".to_string()),
+            references: GitLabReferences {
+                full: "group/app#7".to_string(),
+            },
+            merge_requests: None,
+        }
+    }
+
+    #[test]
+    fn convert_issue_uses_full_reference_as_key() {
+        let ticket = GitLabClient::convert_issue_to_ticket(
+            "group/app",
+            sample_issue("opened", &["priority::high", "backend"]),
+        );
+
+        assert_eq!(ticket.jira_key, "group/app#7");
+        assert_eq!(ticket.status, "Open");
+        assert_eq!(ticket.priority, "High");
+        assert_eq!(ticket.assignee.as_deref(), Some("Ada"));
+        assert_eq!(ticket.labels, "priority::high,backend");
+        assert_eq!(ticket.source, SOURCE_ID);
+    }
+
+    #[test]
+    fn rate_limited_requests_are_retried() {
+        let server = tiny_http::Server::http("127.0.0.1:0").expect("bind fake GitLab");
+        let url = format!("http://{}", server.server_addr());
+        std::thread::spawn(move || {
+            for (attempt, request) in server.incoming_requests().enumerate() {
+                let response = if attempt == 0 {
+                    let retry_after = tiny_http::Header::from_bytes(&b"Retry-After"[..], &b"0"[..])
+                        .expect("header");
+                    tiny_http::Response::from_string("")
+                        .with_status_code(429)
+                        .with_header(retry_after)
+                } else {
+                    tiny_http::Response::from_string(r#"[{"references":{"full":"group/app!3"}}]"#)
+                };
+                let _ = request.respond(response);
+            }
+        });
+        let client =
+            GitLabClient::new(&url, "group/app", "token", reqwest::Client::new()).expect("client");
+
+        let merge_requests: Vec<GitLabMergeRequest> =
+            tauri::async_runtime::block_on(client.get_json(&format!("{}/closed_by", url)))
+                .expect("retried");
+        assert_eq!(merge_requests.len(), 1);
+        assert_eq!(merge_requests[0].references.full, "group/app!3");
+    }
+
+    #[test]
+    fn merge_requests_link_to_the_issue() {
+        let client = GitLabClient::new(
+            "https://gitlab.com",
+            "group/app",
+            "token",
+            reqwest::Client::new(),
+        )
+        .expect("client");
+        let mut issue = sample_issue("closed", &[]);
+        assert_eq!(client.map_links(&issue), None);
+
+        issue.merge_requests = Some(GitLabIssueMergeRequests {
+            closing: vec!["group/app!3".to_string()],
+            related: vec!["group/lib!9".to_string()],
+        });
+        let links = client.map_links(&issue).expect("links");
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(
+            (links[0].outward_key.as_str(), links[0].inward_key.as_str()),
+            ("group/app!3", "group/app#7")
+        );
+        assert_eq!(links[0].outward_label, "closes");
+        assert_eq!(links[1].outward_key, "group/lib!9");
+        assert_eq!(links[1].link_type, "Relates");
+    }
+
+    #[test]
+    fn priority_defaults_to_medium_for_missing_or_unknown_labels() {
+        assert_eq!(GitLabClient::priority_from_labels(&[]), "Medium");
+        assert_eq!(
+            GitLabClient::priority_from_labels(&["priority::someday".to_string()]),
+            "Medium"
+        );
+        assert_eq!(
+            GitLabClient::priority_from_labels(&["severity::high".to_string()]),
+            "Medium"
+        );
+    }
+
+    #[test]
+    fn invalid_last_sync_falls_back_to_full_sync() {
+        assert_eq!(
+            GitLabClient::normalize_updated_after(Some("yesterday")),
+            None
+        );
+        assert_eq!(GitLabClient::normalize_updated_after(None), None);
+        assert_eq!(
+            GitLabClient::normalize_updated_after(Some("2025-01-01T00:00:00Z")).as_deref(),
+            Some("2025-01-01T00:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn new_rejects_empty_project() {
+        assert!(
+            GitLabClient::new("https://gitlab.com", " / ", "token", reqwest::Client::new())
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,117 @@
+pub mod gitlab;
+pub mod servicenow;
+pub mod zendesk;
+
+use crate::db::source_metadata_key;
+use crate::errors::{AppError, ConnectorError};
+use crate::models::{Attachment, Sprint, SyncItemFailure, Ticket, TicketLink};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+
+/// Raw items fetched from a source since the stored cursor, plus the cursor
+/// the next incremental sync should resume from. Items the source returned
+/// but that couldn't be read are reported in `failures` instead of failing
+/// the whole batch.
+pub struct SourceBatch<T> {
+    pub items: Vec<T>,
+    pub failures: Vec<SyncItemFailure>,
+    pub next_cursor: Option<String>,
+}
+
+/// A system tickets can be synced from. The shared orchestration in
+/// `services::source_sync` drives every implementation the same way:
+/// authenticate, fetch everything after the stored cursor, map each item
+/// to a `Ticket`, then persist tickets and the new cursor together.
+#[async_trait]
+pub trait TicketSource: Send + Sync {
+    type Item: Send;
+
+    /// Stable identifier stored in `tickets.source` and used to scope sync metadata.
+    fn source_id(&self) -> &'static str;
+
+    /// Sync metadata key holding this source's incremental cursor.
+    fn cursor_key(&self) -> String {
+        source_metadata_key(self.source_id(), "last_sync_at")
+    }
+
+    /// Sync metadata key recording when this source last synced. Sources
+    /// whose cursor is already the sync time return `cursor_key`.
+    fn last_sync_key(&self) -> String {
+        source_metadata_key(self.source_id(), "last_sync_at")
+    }
+
+    /// Cheap request that fails fast on bad credentials.
+    async fn authenticate(&self) -> Result<(), AppError>;
+
+    async fn fetch_incremental(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<SourceBatch<Self::Item>, AppError>;
+
+    fn map_to_ticket(&self, item: Self::Item) -> Ticket;
+
+    /// Links from this item to other tickets. `None` means the source has no
+    /// notion of links, so stored links are left alone; `Some` replaces them.
+    fn map_links(&self, _item: &Self::Item) -> Option<Vec<TicketLink>> {
+        None
+    }
+
+    /// Attachment metadata for this item, with the same `None`/`Some`
+    /// semantics as `map_links`.
+    fn map_attachments(&self, _item: &Self::Item) -> Option<Vec<Attachment>> {
+        None
+    }
+
+    /// Sprints this item was in, with their dates.
+    fn map_sprints(&self, _item: &Self::Item) -> Vec<Sprint> {
+        Vec::new()
+    }
+}
+
+/// Maps a connector response onto the shared connector error surface,
+/// deserializing the body on success.
+pub(crate) async fn read_json_response<T: DeserializeOwned>(
+    source_id: &'static str,
+    response: reqwest::Response,
+) -> Result<T, AppError> {
+    let status = response.status();
+
+    if status.is_success() {
+        response
+            .json::<T>()
+            .await
+            .map_err(|e| ConnectorError::ParseError(source_id, e.to_string()).into())
+    } else if status.as_u16() == 401 {
+        Err(ConnectorError::Unauthorized(source_id).into())
+    } else if status.as_u16() == 429 {
+        let retry_after = response
+            .headers()
+            .get("Retry-After")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or_else(|| {
+                log::warn!(
+                    "{} rate limited but Retry-After header missing or invalid, defaulting to 60 seconds",
+                    source_id
+                );
+                60
+            });
+
+        Err(ConnectorError::RateLimited {
+            source_id,
+            retry_after_secs: retry_after.min(300),
+        }
+        .into())
+    } else {
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Failed to read error response".to_string());
+        Err(ConnectorError::ApiError {
+            source_id,
+            status: status.as_u16(),
+            body,
+        }
+        .into())
+    }
+}
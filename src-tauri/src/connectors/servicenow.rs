@@ -0,0 +1,437 @@
+use crate::connectors::{read_json_response, SourceBatch, TicketSource};
+use crate::db::source_metadata_key;
+use crate::errors::{AppError, ConnectorError};
+use crate::models::Ticket;
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+
+pub const SOURCE_ID: &str = "servicenow";
+
+const PAGE_SIZE: usize = 100;
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+const SN_DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// ServiceNow tables the connector knows how to map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceNowTable {
+    Incident,
+    ChangeRequest,
+}
+
+impl ServiceNowTable {
+    pub fn parse(name: &str) -> Result<Self, AppError> {
+        match name {
+            "incident" => Ok(ServiceNowTable::Incident),
+            "change_request" => Ok(ServiceNowTable::ChangeRequest),
+            other => Err(AppError::Config(format!(
+                "Unsupported ServiceNow table: {}",
+                other
+            ))),
+        }
+    }
+
+    pub fn table_name(self) -> &'static str {
+        match self {
+            ServiceNowTable::Incident => "incident",
+            ServiceNowTable::ChangeRequest => "change_request",
+        }
+    }
+
+    fn issue_type(self) -> &'static str {
+        match self {
+            ServiceNowTable::Incident => "Incident",
+            ServiceNowTable::ChangeRequest => "Change",
+        }
+    }
+
+    fn project_key(self) -> &'static str {
+        match self {
+            ServiceNowTable::Incident => "INCIDENT",
+            ServiceNowTable::ChangeRequest => "CHANGE",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ServiceNowResponse {
+    pub result: Vec<ServiceNowRecord>,
+}
+
+/// With `sysparm_display_value=all` every field carries both its raw value
+/// and its display value.
+#[derive(Deserialize, Default)]
+pub struct SnField {
+    #[serde(default)]
+    pub value: String,
+    #[serde(default)]
+    pub display_value: String,
+}
+
+#[derive(Deserialize)]
+pub struct ServiceNowRecord {
+    pub sys_id: SnField,
+    pub number: SnField,
+    #[serde(default)]
+    pub short_description: SnField,
+    #[serde(default)]
+    pub state: SnField,
+    #[serde(default)]
+    pub priority: SnField,
+    #[serde(default)]
+    pub assigned_to: SnField,
+    #[serde(default)]
+    pub assignment_group: SnField,
+    #[serde(default)]
+    pub opened_by: SnField,
+    #[serde(default)]
+    pub opened_at: SnField,
+    pub sys_updated_on: SnField,
+    #[serde(default)]
+    pub resolved_at: SnField,
+    #[serde(default)]
+    pub closed_at: SnField,
+}
+
+pub struct ServiceNowClient {
+    base_url: String,
+    auth_header: String,
+    table: ServiceNowTable,
+    client: reqwest::Client,
+}
+
+impl ServiceNowClient {
+    pub fn new(
+        instance_url: &str,
+        username: &str,
+        password: &str,
+        table: ServiceNowTable,
+        http_client: reqwest::Client,
+    ) -> Result<Self, AppError> {
+        let instance_url = instance_url.trim().trim_end_matches('/');
+        let host = instance_url
+            .strip_prefix("https://")
+            .or_else(|| instance_url.strip_prefix("http://"));
+        if host.filter(|host| !host.is_empty()).is_none() {
+            return Err(AppError::Config(format!(
+                "ServiceNow instance URL must start with http:// or https://: {}",
+                instance_url
+            )));
+        }
+
+        let base_url = format!("{}/api/now/table", instance_url);
+        let credentials = format!("{}:{}", username, password);
+        let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+
+        Ok(ServiceNowClient {
+            base_url,
+            auth_header: format!("Basic {}", encoded),
+            table,
+            client: http_client,
+        })
+    }
+
+    fn build_query(cursor: Option<&str>) -> String {
+        match cursor {
+            Some(ts) if NaiveDateTime::parse_from_str(ts, SN_DATETIME_FORMAT).is_ok() => {
+                format!("sys_updated_on>={}^ORDERBYsys_updated_on", ts)
+            }
+            Some(ts) => {
+                log::warn!(
+                    "Invalid ServiceNow cursor '{}'; falling back to full sync query",
+                    ts
+                );
+                "ORDERBYsys_updated_on".to_string()
+            }
+            None => "ORDERBYsys_updated_on".to_string(),
+        }
+    }
+
+    async fn list_records(
+        &self,
+        query: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<ServiceNowRecord>, AppError> {
+        let mut attempt = 0;
+
+        loop {
+            match self.list_records_once(query, offset, limit).await {
+                Err(AppError::Connector(ConnectorError::RateLimited {
+                    retry_after_secs, ..
+                })) if attempt < MAX_RATE_LIMIT_RETRIES => {
+                    attempt += 1;
+                    log::warn!(
+                        "ServiceNow rate limited; retrying in {}s (attempt {}/{})",
+                        retry_after_secs,
+                        attempt,
+                        MAX_RATE_LIMIT_RETRIES
+                    );
+                    tokio::time::sleep(tokio::time::Duration::from_secs(retry_after_secs)).await;
+                }
+                other => return other,
+            }
+        }
+    }
+
+    async fn list_records_once(
+        &self,
+        query: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<ServiceNowRecord>, AppError> {
+        let url = format!("{}/{}", self.base_url, self.table.table_name());
+        let params = [
+            ("sysparm_query", query.to_string()),
+            ("sysparm_display_value", "all".to_string()),
+            ("sysparm_exclude_reference_link", "true".to_string()),
+            ("sysparm_limit", limit.to_string()),
+            ("sysparm_offset", offset.to_string()),
+        ];
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", &self.auth_header)
+            .header("Accept", "application/json")
+            .query(&params)
+            .send()
+            .await
+            .map_err(|e| ConnectorError::Http(SOURCE_ID, e))?;
+
+        let body: ServiceNowResponse = read_json_response(SOURCE_ID, response).await?;
+        Ok(body.result)
+    }
+
+    fn convert_record_to_ticket(table: ServiceNowTable, record: ServiceNowRecord) -> Ticket {
+        let resolved_at =
+            to_rfc3339(&record.resolved_at.value).or_else(|| to_rfc3339(&record.closed_at.value));
+        let updated_at = to_rfc3339(&record.sys_updated_on.value).unwrap_or_default();
+
+        let mut custom_fields = serde_json::Map::new();
+        custom_fields.insert(
+            "sys_id".to_string(),
+            serde_json::Value::String(record.sys_id.value),
+        );
+        if let Some(group) = non_empty(record.assignment_group.display_value) {
+            custom_fields.insert(
+                "assignment_group".to_string(),
+                serde_json::Value::String(group),
+            );
+        }
+
+        Ticket {
+            id: 0, // Will be set by database
+            jira_key: record.number.value,
+            summary: record.short_description.display_value,
+            status: record.state.display_value,
+            status_category: None,
+            priority: Self::map_priority(&record.priority.value),
+            priority_level: None, // Set on write from the priority mappings
+            issue_type: table.issue_type().to_string(),
+            assignee: non_empty(record.assigned_to.display_value),
+            reporter: non_empty(record.opened_by.display_value),
+            created_at: to_rfc3339(&record.opened_at.value).unwrap_or_else(|| updated_at.clone()),
+            updated_at,
+            resolved_at,
+            labels: String::new(),
+            project_key: table.project_key().to_string(),
+            category: None, // Will be set by categorizer
+            category_confidence: None,
+            source: SOURCE_ID.to_string(),
+            custom_fields: serde_json::Value::Object(custom_fields).to_string(),
+            account_id: None,
+            story_points: None,
+            sprint: None,
+            due_date: None,
+            customer: None,
+            environment: None,
+            escalated: false,
+            components: Vec::new(),
+            fix_versions: Vec::new(),
+            affected_versions: Vec::new(),
+        }
+    }
+
+    /// ServiceNow priorities run 1 (Critical) to 5 (Planning).
+    fn map_priority(value: &str) -> String {
+        match value {
+            "1" => "Critical",
+            "2" => "High",
+            "3" => "Medium",
+            "4" | "5" => "Low",
+            _ => "Medium",
+        }
+        .to_string()
+    }
+}
+
+/// ServiceNow returns raw datetimes as UTC "YYYY-MM-DD HH:MM:SS".
+fn to_rfc3339(value: &str) -> Option<String> {
+    NaiveDateTime::parse_from_str(value, SN_DATETIME_FORMAT)
+        .ok()
+        .map(|dt| dt.and_utc().to_rfc3339())
+}
+
+fn non_empty(value: String) -> Option<String> {
+    if value.trim().is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+#[async_trait]
+impl TicketSource for ServiceNowClient {
+    type Item = ServiceNowRecord;
+
+    fn source_id(&self) -> &'static str {
+        SOURCE_ID
+    }
+
+    /// Each table advances independently, keyed by its newest `sys_updated_on`.
+    fn cursor_key(&self) -> String {
+        source_metadata_key(SOURCE_ID, &format!("{}_cursor", self.table.table_name()))
+    }
+
+    async fn authenticate(&self) -> Result<(), AppError> {
+        self.list_records("", 0, 1).await?;
+        Ok(())
+    }
+
+    /// Fetches every record updated at or after `cursor` (a raw
+    /// `sys_updated_on` value); the newest `sys_updated_on` seen becomes the
+    /// cursor for the next sync.
+    async fn fetch_incremental(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<SourceBatch<ServiceNowRecord>, AppError> {
+        let mut items = Vec::new();
+        let mut offset = 0;
+        let query = Self::build_query(cursor);
+
+        loop {
+            let records = self.list_records(&query, offset, PAGE_SIZE).await?;
+            let page_len = records.len();
+            items.extend(records);
+
+            if page_len < PAGE_SIZE {
+                break;
+            }
+            offset += page_len;
+        }
+
+        // Records arrive ordered by sys_updated_on, so the last one wins
+        let next_cursor = items
+            .last()
+            .map(|record| record.sys_updated_on.value.clone())
+            .or_else(|| cursor.map(|c| c.to_string()));
+
+        Ok(SourceBatch {
+            items,
+            failures: Vec::new(),
+            next_cursor,
+        })
+    }
+
+    fn map_to_ticket(&self, item: ServiceNowRecord) -> Ticket {
+        Self::convert_record_to_ticket(self.table, item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(value: &str, display_value: &str) -> SnField {
+        SnField {
+            value: value.to_string(),
+            display_value: display_value.to_string(),
+        }
+    }
+
+    fn sample_record() -> ServiceNowRecord {
+        ServiceNowRecord {
+            sys_id: field("abc123", "abc123"),
+            number: field("INC0010001", "INC0010001"),
+            short_description: field("Email down", "Email down"),
+            state: field("6", "Resolved"),
+            priority: field("1", "1 - Critical"),
+            assigned_to: field("u1", "Beth Anglin"),
+            assignment_group: field("g1", "Service Desk"),
+            opened_by: field("u2", "Abel Tuter"),
+            opened_at: field("2025-01-06 09:00:00", "2025-01-06 01:00:00 AM"),
+            sys_updated_on: field("2025-01-06 12:00:00", "2025-01-06 04:00:00 AM"),
+            resolved_at: field("2025-01-06 11:30:00", "2025-01-06 03:30:00 AM"),
+            closed_at: SnField::default(),
+        }
+    }
+
+    #[test]
+    fn convert_record_maps_raw_values_to_canonical_ticket() {
+        let ticket =
+            ServiceNowClient::convert_record_to_ticket(ServiceNowTable::Incident, sample_record());
+
+        assert_eq!(ticket.jira_key, "INC0010001");
+        assert_eq!(ticket.status, "Resolved");
+        assert_eq!(ticket.priority, "Critical");
+        assert_eq!(ticket.issue_type, "Incident");
+        assert_eq!(ticket.created_at, "2025-01-06T09:00:00+00:00");
+        assert_eq!(
+            ticket.resolved_at.as_deref(),
+            Some("2025-01-06T11:30:00+00:00")
+        );
+        assert_eq!(ticket.assignee.as_deref(), Some("Beth Anglin"));
+
+        let custom: serde_json::Value =
+            serde_json::from_str(&ticket.custom_fields).expect("custom fields json");
+        assert_eq!(custom["assignment_group"], "Service Desk");
+    }
+
+    #[test]
+    fn empty_references_and_unknown_priority_are_handled() {
+        let mut record = sample_record();
+        record.assigned_to = SnField::default();
+        record.resolved_at = SnField::default();
+        record.priority = field("", "");
+
+        let ticket =
+            ServiceNowClient::convert_record_to_ticket(ServiceNowTable::ChangeRequest, record);
+
+        assert_eq!(ticket.assignee, None);
+        assert_eq!(ticket.resolved_at, None);
+        assert_eq!(ticket.priority, "Medium");
+        assert_eq!(ticket.project_key, "CHANGE");
+    }
+
+    #[test]
+    fn build_query_ignores_malformed_cursor() {
+        assert_eq!(
+            ServiceNowClient::build_query(Some("2025-01-06 12:00:00")),
+            "sys_updated_on>=2025-01-06 12:00:00^ORDERBYsys_updated_on"
+        );
+        assert_eq!(
+            ServiceNowClient::build_query(Some("2025-01-06T12:00:00Z")),
+            "ORDERBYsys_updated_on"
+        );
+        assert!(ServiceNowTable::parse("problem").is_err());
+    }
+
+    #[test]
+    fn instance_url_must_be_http() {
+        let client = |url: &str| {
+            ServiceNowClient::new(
+                url,
+                "admin",
+                "secret",
+                ServiceNowTable::Incident,
+                reqwest::Client::new(),
+            )
+        };
+        let valid = client(" https://acme.service-now.com/ ").expect("valid URL");
+        assert_eq!(valid.base_url, "https://acme.service-now.com/api/now/table");
+        assert!(client("acme.service-now.com").is_err());
+        assert!(client("https://").is_err());
+    }
+}
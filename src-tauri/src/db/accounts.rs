@@ -0,0 +1,277 @@
+use crate::errors::{AppError, DbError};
+use crate::jira::JiraAuthMethod;
+use crate::models::JiraAccount;
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+const ACCOUNT_COLUMNS: &str = "id, name, base_url, email, auth_method, created_at";
+
+fn map_account_row(row: &Row) -> rusqlite::Result<JiraAccount> {
+    let auth_method: String = row.get(4)?;
+    Ok(JiraAccount {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        base_url: row.get(2)?,
+        email: row.get(3)?,
+        auth_method: JiraAuthMethod::parse(&auth_method).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                4,
+                Type::Text,
+                format!("Unknown auth method: {}", auth_method).into(),
+            )
+        })?,
+        created_at: row.get(5)?,
+    })
+}
+
+pub fn insert_jira_account(
+    conn: &Connection,
+    name: &str,
+    base_url: &str,
+    email: &str,
+    auth_method: JiraAuthMethod,
+) -> Result<JiraAccount, AppError> {
+    let created_at = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO jira_accounts (name, base_url, email, auth_method, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![name, base_url, email, auth_method.as_str(), created_at],
+    )
+    .map_err(DbError::from)?;
+
+    Ok(JiraAccount {
+        id: conn.last_insert_rowid(),
+        name: name.to_string(),
+        base_url: base_url.to_string(),
+        email: email.to_string(),
+        auth_method,
+        created_at,
+    })
+}
+
+pub fn list_jira_accounts(conn: &Connection) -> Result<Vec<JiraAccount>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM jira_accounts ORDER BY name",
+            ACCOUNT_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+
+    let accounts = stmt
+        .query_map([], map_account_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(accounts)
+}
+
+pub fn get_jira_account(conn: &Connection, id: i64) -> Result<Option<JiraAccount>, AppError> {
+    let account = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM jira_accounts WHERE id = ?1",
+                ACCOUNT_COLUMNS
+            ),
+            params![id],
+            map_account_row,
+        )
+        .optional()
+        .map_err(DbError::from)?;
+    Ok(account)
+}
+
+/// Removes the account, every ticket synced through it, and its sync cursor.
+/// Returns false when no such account existed.
+pub fn delete_jira_account(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+
+    tx.execute("DELETE FROM tickets WHERE account_id = ?1", params![id])
+        .map_err(DbError::from)?;
+    tx.execute(
+        "DELETE FROM sync_metadata WHERE key = ?1",
+        params![account_cursor_key(id)],
+    )
+    .map_err(DbError::from)?;
+    let removed = tx
+        .execute("DELETE FROM jira_accounts WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+
+    tx.commit().map_err(DbError::from)?;
+    Ok(removed > 0)
+}
+
+/// Sync metadata key holding an account's incremental cursor.
+pub fn account_cursor_key(account_id: i64) -> String {
+    format!("jira:account:{}:last_sync_at", account_id)
+}
+
+/// Stored key of issue `issue_key` synced through an account, e.g. `OPS-1@2`,
+/// so two sites can both have an OPS-1.
+pub fn account_ticket_key(account_id: i64, issue_key: &str) -> String {
+    format!("{}@{}", issue_key, account_id)
+}
+
+/// The key Jira knows a stored ticket by, without its account suffix.
+pub fn jira_issue_key(jira_key: &str) -> &str {
+    jira_key
+        .split_once('@')
+        .map_or(jira_key, |(issue_key, _)| issue_key)
+}
+
+/// The account a stored ticket key was synced through, read from its
+/// suffix; `None` for the primary site.
+pub fn ticket_key_account(jira_key: &str) -> Option<i64> {
+    jira_key
+        .split_once('@')
+        .and_then(|(_, account_id)| account_id.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{get_tickets, set_sync_metadata, upsert_ticket};
+    use crate::models::{Ticket, TicketFilter};
+
+    fn account_ticket(key: &str, account_id: Option<i64>, summary: &str) -> Ticket {
+        Ticket {
+            summary: summary.to_string(),
+            account_id,
+            ..test_support::ticket(key)
+        }
+    }
+
+    #[test]
+    fn accounts_scope_tickets_and_cascade_on_delete() {
+        let conn = setup_db();
+        let eu = insert_jira_account(
+            &conn,
+            "EU",
+            "https://eu.example.net",
+            "a@x.io",
+            JiraAuthMethod::Basic,
+        )
+        .expect("insert EU");
+        let us = insert_jira_account(
+            &conn,
+            "US",
+            "https://us.example.net",
+            "a@x.io",
+            JiraAuthMethod::Basic,
+        )
+        .expect("insert US");
+
+        upsert_ticket(&conn, &account_ticket("EU-1", Some(eu.id), "eu")).expect("EU-1");
+        upsert_ticket(&conn, &account_ticket("US-1", Some(us.id), "us")).expect("US-1");
+        set_sync_metadata(&conn, &account_cursor_key(eu.id), "2025-01-01T00:00:00Z")
+            .expect("cursor");
+
+        let eu_only = TicketFilter {
+            account_id: Some(eu.id),
+            ..Default::default()
+        };
+        let tickets = get_tickets(&conn, &eu_only).expect("eu tickets");
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets[0].jira_key, "EU-1");
+
+        assert!(delete_jira_account(&conn, eu.id).expect("delete EU"));
+        assert!(!delete_jira_account(&conn, eu.id).expect("delete EU again"));
+        assert!(get_jira_account(&conn, eu.id).expect("lookup").is_none());
+
+        let remaining = get_tickets(&conn, &TicketFilter::default()).expect("all tickets");
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].jira_key, "US-1");
+    }
+
+    #[test]
+    fn same_key_from_two_accounts_is_stored_twice() {
+        let conn = setup_db();
+        let first = insert_jira_account(
+            &conn,
+            "A",
+            "https://a.example.net",
+            "a@x.io",
+            JiraAuthMethod::Basic,
+        )
+        .expect("insert A");
+        let second = insert_jira_account(
+            &conn,
+            "B",
+            "https://b.example.net",
+            "b@x.io",
+            JiraAuthMethod::Basic,
+        )
+        .expect("insert B");
+
+        for (account, summary) in [(&first, "from A"), (&second, "from B")] {
+            let key = account_ticket_key(account.id, "OPS-1");
+            upsert_ticket(&conn, &account_ticket(&key, Some(account.id), summary)).expect("upsert");
+        }
+        upsert_ticket(&conn, &account_ticket("OPS-1", None, "primary")).expect("upsert");
+
+        let mut tickets: Vec<(String, String)> = get_tickets(&conn, &TicketFilter::default())
+            .expect("tickets")
+            .into_iter()
+            .map(|t| (t.jira_key, t.summary))
+            .collect();
+        tickets.sort();
+        assert_eq!(
+            tickets,
+            vec![
+                ("OPS-1".to_string(), "primary".to_string()),
+                (format!("OPS-1@{}", first.id), "from A".to_string()),
+                (format!("OPS-1@{}", second.id), "from B".to_string()),
+            ]
+        );
+        assert_eq!(
+            jira_issue_key(&account_ticket_key(second.id, "OPS-1")),
+            "OPS-1"
+        );
+        assert_eq!(jira_issue_key("OPS-1"), "OPS-1");
+        assert_eq!(
+            ticket_key_account(&account_ticket_key(second.id, "OPS-1")),
+            Some(second.id)
+        );
+        assert_eq!(ticket_key_account("OPS-1"), None);
+    }
+
+    #[test]
+    fn accounts_keep_their_auth_method() {
+        let conn = setup_db();
+        let dc = insert_jira_account(
+            &conn,
+            "DC",
+            "https://jira.example.net",
+            "",
+            JiraAuthMethod::Pat,
+        )
+        .expect("insert DC");
+
+        let stored = get_jira_account(&conn, dc.id)
+            .expect("lookup")
+            .expect("stored");
+        assert_eq!(stored.auth_method, JiraAuthMethod::Pat);
+    }
+
+    #[test]
+    fn duplicate_account_names_are_rejected() {
+        let conn = setup_db();
+        insert_jira_account(
+            &conn,
+            "Main",
+            "https://a.example.net",
+            "a@x.io",
+            JiraAuthMethod::Basic,
+        )
+        .expect("first");
+        assert!(insert_jira_account(
+            &conn,
+            "Main",
+            "https://b.example.net",
+            "b@x.io",
+            JiraAuthMethod::Basic
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use crate::errors::{AppError, DbError};
+use crate::models::{AlertChannel, AlertRule, AlertRuleInput};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+const RULE_COLUMNS: &str = "id, name, condition, channel, throttle_minutes, enabled, last_fired_at";
+
+fn map_rule_row(row: &Row) -> rusqlite::Result<AlertRule> {
+    let condition: String = row.get(2)?;
+    let channel: String = row.get(3)?;
+
+    Ok(AlertRule {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        condition: serde_json::from_str(&condition)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(2, Type::Text, Box::new(e)))?,
+        channel: AlertChannel::parse(&channel).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                3,
+                Type::Text,
+                format!("Unknown alert channel: {}", channel).into(),
+            )
+        })?,
+        throttle_minutes: row.get(4)?,
+        enabled: row.get(5)?,
+        last_fired_at: row.get(6)?,
+    })
+}
+
+fn condition_json(input: &AlertRuleInput) -> Result<String, AppError> {
+    serde_json::to_string(&input.condition)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize alert condition: {}", e)))
+}
+
+pub fn create_alert_rule(conn: &Connection, input: &AlertRuleInput) -> Result<AlertRule, AppError> {
+    conn.execute(
+        "INSERT INTO alert_rules (name, condition, channel, throttle_minutes, enabled, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            input.name,
+            condition_json(input)?,
+            input.channel.as_str(),
+            input.throttle_minutes,
+            input.enabled,
+            chrono::Utc::now().to_rfc3339(),
+        ],
+    )
+    .map_err(DbError::from)?;
+
+    get_alert_rule(conn, conn.last_insert_rowid())?
+        .ok_or_else(|| AppError::Internal("Inserted alert rule not found".to_string()))
+}
+
+pub fn get_alert_rule(conn: &Connection, id: i64) -> Result<Option<AlertRule>, AppError> {
+    conn.query_row(
+        &format!("SELECT {} FROM alert_rules WHERE id = ?1", RULE_COLUMNS),
+        params![id],
+        map_rule_row,
+    )
+    .optional()
+    .map_err(|e| DbError::from(e).into())
+}
+
+pub fn list_alert_rules(conn: &Connection) -> Result<Vec<AlertRule>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM alert_rules ORDER BY name",
+            RULE_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+
+    let rules = stmt
+        .query_map([], map_rule_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(rules)
+}
+
+pub fn update_alert_rule(
+    conn: &Connection,
+    id: i64,
+    input: &AlertRuleInput,
+) -> Result<Option<AlertRule>, AppError> {
+    conn.execute(
+        "UPDATE alert_rules SET name = ?2, condition = ?3, channel = ?4, throttle_minutes = ?5,
+             enabled = ?6
+         WHERE id = ?1",
+        params![
+            id,
+            input.name,
+            condition_json(input)?,
+            input.channel.as_str(),
+            input.throttle_minutes,
+            input.enabled,
+        ],
+    )
+    .map_err(DbError::from)?;
+
+    get_alert_rule(conn, id)
+}
+
+pub fn delete_alert_rule(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute("DELETE FROM alert_rules WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+pub fn set_alert_rule_fired(conn: &Connection, id: i64, fired_at: &str) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE alert_rules SET last_fired_at = ?2 WHERE id = ?1",
+        params![id, fired_at],
+    )
+    .map_err(DbError::from)?;
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use crate::errors::{AppError, DbError};
+use rusqlite::{params, Connection};
+
+/// Records `alert_key` as fired; returns false if it already had been, so
+/// callers deliver each alert once.
+pub fn record_alert_once(conn: &Connection, alert_key: &str) -> Result<bool, AppError> {
+    let inserted = conn
+        .execute(
+            "INSERT OR IGNORE INTO alert_log (alert_key, fired_at) VALUES (?1, ?2)",
+            params![alert_key, chrono::Utc::now().to_rfc3339()],
+        )
+        .map_err(DbError::from)?;
+    Ok(inserted > 0)
+}
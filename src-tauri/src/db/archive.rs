@@ -0,0 +1,131 @@
+use super::queries::TICKET_TABLE_COLUMNS;
+use crate::errors::{AppError, DbError};
+use crate::services::time_calc::parse_timestamp;
+use chrono::NaiveDateTime;
+use rusqlite::{params, Connection};
+
+/// Moves tickets resolved before `cutoff` from `tickets` to
+/// `tickets_archive`. Their components, links and other per-key rows stay
+/// in place, so including archived tickets in a query brings them back
+/// whole. Returns how many tickets were archived.
+pub fn archive_resolved_tickets(
+    conn: &Connection,
+    cutoff: NaiveDateTime,
+) -> Result<usize, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT jira_key, resolved_at FROM tickets WHERE resolved_at IS NOT NULL")
+        .map_err(DbError::from)?;
+    let keys: Vec<String> = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?
+        .into_iter()
+        .filter(|(_, resolved_at)| parse_timestamp(resolved_at).is_some_and(|at| at < cutoff))
+        .map(|(key, _)| key)
+        .collect();
+
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    for key in &keys {
+        tx.execute(
+            &format!(
+                "INSERT OR REPLACE INTO tickets_archive ({cols}) \
+                 SELECT {cols} FROM tickets WHERE jira_key = ?1",
+                cols = TICKET_TABLE_COLUMNS
+            ),
+            params![key],
+        )
+        .map_err(DbError::from)?;
+        tx.execute("DELETE FROM tickets WHERE jira_key = ?1", params![key])
+            .map_err(DbError::from)?;
+    }
+    tx.commit().map_err(DbError::from)?;
+
+    Ok(keys.len())
+}
+
+pub fn count_archived_tickets(conn: &Connection) -> Result<u32, AppError> {
+    let count = conn
+        .query_row("SELECT COUNT(*) FROM tickets_archive", [], |row| row.get(0))
+        .map_err(DbError::from)?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{get_summary_stats, get_tickets, upsert_ticket};
+    use crate::models::{DurationMode, Ticket, TicketFilter};
+
+    fn ticket(key: &str, resolved_at: Option<&str>) -> Ticket {
+        Ticket {
+            created_at: "2023-01-02T09:00:00Z".to_string(),
+            updated_at: "2023-01-03T09:00:00Z".to_string(),
+            ..test_support::ticket_resolved_at(key, resolved_at)
+        }
+    }
+
+    #[test]
+    fn old_resolved_tickets_move_to_the_archive() {
+        let conn = setup_db();
+        upsert_ticket(
+            &conn,
+            &ticket("OPS-1", Some("2023-01-05T09:00:00.000+0000")),
+        )
+        .expect("old resolved");
+        upsert_ticket(&conn, &ticket("OPS-2", Some("2025-01-05T09:00:00Z")))
+            .expect("recent resolved");
+        upsert_ticket(&conn, &ticket("OPS-3", None)).expect("open");
+
+        let cutoff = NaiveDateTime::parse_from_str("2024-07-01 00:00:00", "%Y-%m-%d %H:%M:%S")
+            .expect("valid cutoff");
+        assert_eq!(
+            archive_resolved_tickets(&conn, cutoff).expect("archived"),
+            1
+        );
+        assert_eq!(count_archived_tickets(&conn).expect("count"), 1);
+
+        let live = get_tickets(&conn, &TicketFilter::default()).expect("tickets");
+        assert_eq!(live.len(), 2);
+        let everything = TicketFilter {
+            include_archived: true,
+            ..Default::default()
+        };
+        let stats =
+            get_summary_stats(&conn, &everything, DurationMode::Calendar).expect("summary stats");
+        assert_eq!((stats.total_tickets, stats.resolved_tickets), (3, 2));
+
+        // Seen again in Jira, the ticket is live once more
+        upsert_ticket(
+            &conn,
+            &ticket("OPS-1", Some("2023-01-05T09:00:00.000+0000")),
+        )
+        .expect("resynced");
+        assert_eq!(count_archived_tickets(&conn).expect("count"), 0);
+        assert_eq!(get_tickets(&conn, &everything).expect("tickets").len(), 3);
+    }
+
+    #[test]
+    fn archive_has_the_same_columns_as_tickets() {
+        let conn = setup_db();
+        // Names only: CREATE TABLE ... AS records affinities rather than the declared types
+        let columns = |table: &str| -> Vec<String> {
+            let mut stmt = conn
+                .prepare(&format!("PRAGMA table_info({})", table))
+                .expect("table info");
+            let names = stmt
+                .query_map([], |row| row.get(1))
+                .expect("columns")
+                .collect::<Result<Vec<_>, _>>()
+                .expect("columns");
+            names
+        };
+
+        let tickets = columns("tickets");
+        assert_eq!(tickets, columns("tickets_archive"));
+        assert_eq!(tickets.join(", "), TICKET_TABLE_COLUMNS);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::errors::{AppError, DbError};
+use crate::models::Attachment;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+const ATTACHMENT_COLUMNS: &str =
+    "id, ticket_key, filename, size_bytes, mime_type, author, created_at, content_url";
+
+fn map_attachment_row(row: &Row) -> rusqlite::Result<Attachment> {
+    Ok(Attachment {
+        id: row.get(0)?,
+        ticket_key: row.get(1)?,
+        filename: row.get(2)?,
+        size_bytes: row.get(3)?,
+        mime_type: row.get(4)?,
+        author: row.get(5)?,
+        created_at: row.get(6)?,
+        content_url: row.get(7)?,
+    })
+}
+
+/// Replaces the stored attachment metadata for `key` with `attachments`, so
+/// files deleted at the source disappear locally too.
+pub fn replace_attachments(
+    conn: &Connection,
+    key: &str,
+    attachments: &[Attachment],
+) -> Result<(), AppError> {
+    conn.execute(
+        "DELETE FROM attachments WHERE ticket_key = ?1",
+        params![key],
+    )
+    .map_err(DbError::from)?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "INSERT OR REPLACE INTO attachments ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            ATTACHMENT_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+    for attachment in attachments {
+        stmt.execute(params![
+            attachment.id,
+            key,
+            attachment.filename,
+            attachment.size_bytes,
+            attachment.mime_type,
+            attachment.author,
+            attachment.created_at,
+            attachment.content_url,
+        ])
+        .map_err(DbError::from)?;
+    }
+    Ok(())
+}
+
+/// Attachments on `key`, oldest first.
+pub fn get_attachments(conn: &Connection, key: &str) -> Result<Vec<Attachment>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM attachments WHERE ticket_key = ?1 ORDER BY created_at, id",
+            ATTACHMENT_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+    let attachments = stmt
+        .query_map(params![key], map_attachment_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(attachments)
+}
+
+pub fn get_attachment(conn: &Connection, id: &str) -> Result<Option<Attachment>, AppError> {
+    let attachment = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM attachments WHERE id = ?1",
+                ATTACHMENT_COLUMNS
+            ),
+            params![id],
+            map_attachment_row,
+        )
+        .optional()
+        .map_err(DbError::from)?;
+
+    Ok(attachment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+
+    fn attachment(id: &str, filename: &str) -> Attachment {
+        Attachment {
+            id: id.to_string(),
+            ticket_key: "OPS-1".to_string(),
+            filename: filename.to_string(),
+            size_bytes: 2048,
+            mime_type: Some("image/png".to_string()),
+            author: Some("Dana".to_string()),
+            created_at: Some(format!("2025-01-0{}T09:00:00Z", id)),
+            content_url: format!("https://jira.example.com/attachment/content/{}", id),
+        }
+    }
+
+    #[test]
+    fn replacing_attachments_drops_removed_files() {
+        let conn = setup_db();
+
+        replace_attachments(
+            &conn,
+            "OPS-1",
+            &[attachment("1", "before.png"), attachment("2", "after.png")],
+        )
+        .expect("attachments stored");
+        replace_attachments(&conn, "OPS-1", &[attachment("2", "after.png")])
+            .expect("attachments replaced");
+
+        let stored = get_attachments(&conn, "OPS-1").expect("attachments");
+        assert_eq!(stored, vec![attachment("2", "after.png")]);
+        assert!(get_attachment(&conn, "1").expect("lookup").is_none());
+        assert_eq!(
+            get_attachment(&conn, "2")
+                .expect("lookup")
+                .map(|a| a.filename),
+            Some("after.png".to_string())
+        );
+    }
+}
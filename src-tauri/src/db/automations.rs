@@ -0,0 +1,242 @@
+use crate::errors::{AppError, DbError};
+use crate::models::{
+    Automation, AutomationAction, AutomationInput, AutomationLogEntry, AutomationOutcome,
+};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::collections::HashSet;
+
+const AUTOMATION_COLUMNS: &str = "id, name, filter, actions, enabled, created_at";
+
+const LOG_COLUMNS: &str =
+    "id, automation_id, automation_name, ticket_key, action, outcome, detail, ran_at";
+
+fn json_column<T: serde::de::DeserializeOwned>(row: &Row, idx: usize) -> rusqlite::Result<T> {
+    let value: String = row.get(idx)?;
+    serde_json::from_str(&value)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, Box::new(e)))
+}
+
+fn map_automation_row(row: &Row) -> rusqlite::Result<Automation> {
+    Ok(Automation {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        filter: json_column(row, 2)?,
+        actions: json_column(row, 3)?,
+        enabled: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+fn map_log_row(row: &Row) -> rusqlite::Result<AutomationLogEntry> {
+    let outcome: String = row.get(5)?;
+
+    Ok(AutomationLogEntry {
+        id: row.get(0)?,
+        automation_id: row.get(1)?,
+        automation_name: row.get(2)?,
+        ticket_key: row.get(3)?,
+        action: json_column(row, 4)?,
+        outcome: AutomationOutcome::parse(&outcome).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                5,
+                Type::Text,
+                format!("Unknown automation outcome: {}", outcome).into(),
+            )
+        })?,
+        detail: row.get(6)?,
+        ran_at: row.get(7)?,
+    })
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, AppError> {
+    serde_json::to_string(value)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize automation: {}", e)))
+}
+
+pub fn create_automation(
+    conn: &Connection,
+    input: &AutomationInput,
+) -> Result<Automation, AppError> {
+    conn.execute(
+        "INSERT INTO automations (name, filter, actions, enabled, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            input.name,
+            to_json(&input.filter)?,
+            to_json(&input.actions)?,
+            input.enabled,
+            chrono::Utc::now().to_rfc3339(),
+        ],
+    )
+    .map_err(DbError::from)?;
+
+    get_automation(conn, conn.last_insert_rowid())?
+        .ok_or_else(|| AppError::Internal("Inserted automation not found".to_string()))
+}
+
+pub fn get_automation(conn: &Connection, id: i64) -> Result<Option<Automation>, AppError> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM automations WHERE id = ?1",
+            AUTOMATION_COLUMNS
+        ),
+        params![id],
+        map_automation_row,
+    )
+    .optional()
+    .map_err(|e| DbError::from(e).into())
+}
+
+pub fn list_automations(conn: &Connection) -> Result<Vec<Automation>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM automations ORDER BY name",
+            AUTOMATION_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+
+    let automations = stmt
+        .query_map([], map_automation_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(automations)
+}
+
+/// Updates an automation. Tickets it already matched are forgotten when the
+/// filter changes, so the new filter acts on everything it matches.
+pub fn update_automation(
+    conn: &Connection,
+    id: i64,
+    input: &AutomationInput,
+) -> Result<Option<Automation>, AppError> {
+    let Some(existing) = get_automation(conn, id)? else {
+        return Ok(None);
+    };
+    let filter = to_json(&input.filter)?;
+    conn.execute(
+        "UPDATE automations SET name = ?2, filter = ?3, actions = ?4, enabled = ?5 WHERE id = ?1",
+        params![
+            id,
+            input.name,
+            filter,
+            to_json(&input.actions)?,
+            input.enabled
+        ],
+    )
+    .map_err(DbError::from)?;
+    if to_json(&existing.filter)? != filter {
+        conn.execute(
+            "DELETE FROM automation_matches WHERE automation_id = ?1",
+            params![id],
+        )
+        .map_err(DbError::from)?;
+    }
+
+    get_automation(conn, id)
+}
+
+/// Deletes an automation. Its log entries are kept for the audit trail.
+pub fn delete_automation(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    conn.execute(
+        "DELETE FROM automation_matches WHERE automation_id = ?1",
+        params![id],
+    )
+    .map_err(DbError::from)?;
+    let deleted = conn
+        .execute("DELETE FROM automations WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+/// Tickets `automation_id` matched when it last ran.
+pub fn get_automation_matches(
+    conn: &Connection,
+    automation_id: i64,
+) -> Result<HashSet<String>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT ticket_key FROM automation_matches WHERE automation_id = ?1")
+        .map_err(DbError::from)?;
+
+    let keys = stmt
+        .query_map(params![automation_id], |row| row.get(0))
+        .map_err(DbError::from)?
+        .collect::<Result<HashSet<String>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(keys)
+}
+
+/// Replaces the tickets `automation_id` matched. A ticket that leaves the
+/// filter is dropped, so it is acted on again if it comes back.
+pub fn set_automation_matches(
+    conn: &Connection,
+    automation_id: i64,
+    ticket_keys: &[String],
+) -> Result<(), AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    tx.execute(
+        "DELETE FROM automation_matches WHERE automation_id = ?1",
+        params![automation_id],
+    )
+    .map_err(DbError::from)?;
+    for key in ticket_keys {
+        tx.execute(
+            "INSERT OR IGNORE INTO automation_matches (automation_id, ticket_key) VALUES (?1, ?2)",
+            params![automation_id, key],
+        )
+        .map_err(DbError::from)?;
+    }
+    tx.commit().map_err(DbError::from)?;
+    Ok(())
+}
+
+pub fn record_automation_run(
+    conn: &Connection,
+    automation: &Automation,
+    ticket_key: &str,
+    action: &AutomationAction,
+    outcome: AutomationOutcome,
+    detail: Option<&str>,
+    ran_at: &str,
+) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT INTO automation_log
+             (automation_id, automation_name, ticket_key, action, outcome, detail, ran_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            automation.id,
+            automation.name,
+            ticket_key,
+            to_json(action)?,
+            outcome.as_str(),
+            detail,
+            ran_at,
+        ],
+    )
+    .map_err(DbError::from)?;
+    Ok(())
+}
+
+/// The most recent `limit` log entries, newest first.
+pub fn get_automation_log(
+    conn: &Connection,
+    limit: u32,
+) -> Result<Vec<AutomationLogEntry>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM automation_log ORDER BY ran_at DESC, id DESC LIMIT ?1",
+            LOG_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+
+    let entries = stmt
+        .query_map(params![limit], map_log_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(entries)
+}
@@ -0,0 +1,187 @@
+use super::queries::get_tickets;
+use super::ticket_rank::ticket_positions;
+use crate::errors::{AppError, DbError};
+use crate::models::{BoardColumn, StatusCategory, Ticket, TicketFilter, WipLimit};
+use rusqlite::{params, Connection};
+
+/// Configured WIP limits in column order.
+pub fn get_wip_limits(conn: &Connection) -> Result<Vec<WipLimit>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT category, max_tickets FROM wip_limits")
+        .map_err(DbError::from)?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    let mut limits: Vec<WipLimit> = rows
+        .into_iter()
+        .filter_map(|(category, max_tickets)| {
+            Some(WipLimit {
+                category: StatusCategory::parse(&category)?,
+                max_tickets,
+            })
+        })
+        .collect();
+    limits.sort_by_key(|l| StatusCategory::ALL.iter().position(|c| *c == l.category));
+    Ok(limits)
+}
+
+/// Sets the WIP limit of `category`'s column, or removes it with `None`.
+pub fn set_wip_limit(
+    conn: &Connection,
+    category: StatusCategory,
+    max_tickets: Option<u32>,
+) -> Result<(), AppError> {
+    match max_tickets {
+        Some(max_tickets) => conn.execute(
+            "INSERT INTO wip_limits (category, max_tickets) VALUES (?1, ?2)
+             ON CONFLICT(category) DO UPDATE SET max_tickets = excluded.max_tickets",
+            params![category.as_str(), max_tickets],
+        ),
+        None => conn.execute(
+            "DELETE FROM wip_limits WHERE category = ?1",
+            params![category.as_str()],
+        ),
+    }
+    .map_err(DbError::from)?;
+    Ok(())
+}
+
+/// The column a ticket sits in. Tickets whose status has no category go by
+/// whether they are resolved.
+fn board_category(ticket: &Ticket) -> StatusCategory {
+    match ticket.status_category {
+        Some(category) => category,
+        None if ticket.resolved_at.is_some() => StatusCategory::Done,
+        None => StatusCategory::Todo,
+    }
+}
+
+/// `filter`'s tickets as kanban columns, one per status category in
+/// workflow order. Each column lists tickets in the personal queue first, in
+/// queue order, then the rest by priority and age (oldest first).
+pub fn get_board_columns(
+    conn: &Connection,
+    filter: &TicketFilter,
+) -> Result<Vec<BoardColumn>, AppError> {
+    let limits = get_wip_limits(conn)?;
+    let positions = ticket_positions(conn)?;
+    let mut tickets = get_tickets(conn, filter)?;
+    tickets.sort_by(|a, b| {
+        let position = |t: &Ticket| positions.get(&t.jira_key).copied().unwrap_or(usize::MAX);
+        let level = |t: &Ticket| t.priority_level.map_or(usize::MAX, |l| l as usize);
+        position(a)
+            .cmp(&position(b))
+            .then_with(|| level(a).cmp(&level(b)))
+            .then_with(|| a.created_at.cmp(&b.created_at))
+            .then_with(|| a.jira_key.cmp(&b.jira_key))
+    });
+
+    Ok(StatusCategory::ALL
+        .into_iter()
+        .map(|category| {
+            let tickets: Vec<Ticket> = tickets
+                .iter()
+                .filter(|t| board_category(t) == category)
+                .cloned()
+                .collect();
+            let wip_limit = limits
+                .iter()
+                .find(|l| l.category == category)
+                .map(|l| l.max_tickets);
+            BoardColumn {
+                category,
+                over_wip_limit: wip_limit.is_some_and(|limit| tickets.len() > limit as usize),
+                tickets,
+                wip_limit,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::upsert_ticket;
+
+    fn ticket(key: &str, status: &str, priority: &str, created_at: &str) -> Ticket {
+        Ticket {
+            status: status.to_string(),
+            status_category: match status {
+                "Open" => Some(StatusCategory::Todo),
+                "In Progress" => Some(StatusCategory::InProgress),
+                _ => None,
+            },
+            priority: priority.to_string(),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            ..test_support::ticket(key)
+        }
+    }
+
+    #[test]
+    fn groups_tickets_into_columns_and_flags_wip_overflow() {
+        let conn = setup_db();
+        for t in [
+            ticket("OPS-1", "In Progress", "Low", "2025-01-06T09:00:00Z"),
+            ticket("OPS-2", "In Progress", "Critical", "2025-01-07T09:00:00Z"),
+            ticket("OPS-3", "In Progress", "Low", "2025-01-05T09:00:00Z"),
+            ticket("OPS-4", "Open", "High", "2025-01-06T09:00:00Z"),
+            // No category and unresolved, so it waits in To Do
+            ticket("OPS-5", "Triage", "High", "2025-01-04T09:00:00Z"),
+        ] {
+            upsert_ticket(&conn, &t).expect("ticket");
+        }
+        set_wip_limit(&conn, StatusCategory::InProgress, Some(2)).expect("limit");
+        set_wip_limit(&conn, StatusCategory::Todo, Some(5)).expect("limit");
+        set_wip_limit(&conn, StatusCategory::Todo, Some(2)).expect("limit");
+
+        let columns = get_board_columns(&conn, &TicketFilter::default()).expect("board");
+        let board: Vec<(StatusCategory, Vec<&str>, Option<u32>, bool)> = columns
+            .iter()
+            .map(|c| {
+                (
+                    c.category,
+                    c.tickets.iter().map(|t| t.jira_key.as_str()).collect(),
+                    c.wip_limit,
+                    c.over_wip_limit,
+                )
+            })
+            .collect();
+        assert_eq!(
+            board,
+            vec![
+                (StatusCategory::Todo, vec!["OPS-5", "OPS-4"], Some(2), false),
+                (
+                    StatusCategory::InProgress,
+                    vec!["OPS-2", "OPS-3", "OPS-1"],
+                    Some(2),
+                    true
+                ),
+                (StatusCategory::Done, vec![], None, false),
+            ]
+        );
+
+        // The personal queue comes before priority
+        crate::db::reorder_tickets(&conn, &["OPS-1".to_string()]).expect("reorder");
+        let columns = get_board_columns(&conn, &TicketFilter::default()).expect("board");
+        let in_progress: Vec<&str> = columns[1]
+            .tickets
+            .iter()
+            .map(|t| t.jira_key.as_str())
+            .collect();
+        assert_eq!(in_progress, vec!["OPS-1", "OPS-2", "OPS-3"]);
+
+        set_wip_limit(&conn, StatusCategory::Todo, None).expect("limit");
+        assert_eq!(
+            get_wip_limits(&conn).expect("limits"),
+            vec![WipLimit {
+                category: StatusCategory::InProgress,
+                max_tickets: 2
+            }]
+        );
+    }
+}
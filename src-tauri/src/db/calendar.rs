@@ -0,0 +1,254 @@
+use super::queries::{map_ticket_row, TICKET_COLUMNS};
+use super::sprints::list_sprints;
+use crate::errors::{AppError, DbError};
+use crate::models::{CalendarEntry, CalendarEntryKind, ScheduledWork};
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+const SCHEDULED_WORK_COLUMNS: &str = "id, ticket_key, date, note, created_at";
+
+fn map_scheduled_work_row(row: &Row) -> rusqlite::Result<ScheduledWork> {
+    Ok(ScheduledWork {
+        id: row.get(0)?,
+        ticket_key: row.get(1)?,
+        date: row.get(2)?,
+        note: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+pub fn schedule_work(
+    conn: &Connection,
+    key: &str,
+    date: NaiveDate,
+    note: Option<&str>,
+    now: &str,
+) -> Result<ScheduledWork, AppError> {
+    conn.execute(
+        "INSERT INTO scheduled_work (ticket_key, date, note, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![key, date.format("%Y-%m-%d").to_string(), note, now],
+    )
+    .map_err(DbError::from)?;
+
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM scheduled_work WHERE id = ?1",
+            SCHEDULED_WORK_COLUMNS
+        ),
+        params![conn.last_insert_rowid()],
+        map_scheduled_work_row,
+    )
+    .optional()
+    .map_err(DbError::from)?
+    .ok_or_else(|| AppError::Internal("Inserted scheduled work not found".to_string()))
+}
+
+pub fn delete_scheduled_work(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute("DELETE FROM scheduled_work WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+/// The calendar day of an ISO 8601 date or timestamp, as written.
+fn calendar_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
+
+/// Everything on the calendar from `from` up to but not including `to`:
+/// due dates of unresolved tickets, sprint starts and ends, and scheduled
+/// work, by date.
+pub fn get_calendar_entries(
+    conn: &Connection,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<CalendarEntry>, AppError> {
+    let from_text = from.format("%Y-%m-%d").to_string();
+    let to_text = to.format("%Y-%m-%d").to_string();
+    let mut entries = Vec::new();
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM tickets
+             WHERE resolved_at IS NULL AND due_date >= ?1 AND due_date < ?2",
+            TICKET_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+    let due = stmt
+        .query_map(params![from_text, to_text], map_ticket_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+    for ticket in due {
+        let Some(date) = ticket.due_date.as_deref().and_then(calendar_date) else {
+            continue;
+        };
+        entries.push(CalendarEntry {
+            date: date.format("%Y-%m-%d").to_string(),
+            kind: CalendarEntryKind::Due,
+            title: format!("{}: {}", ticket.jira_key, ticket.summary),
+            ticket_key: Some(ticket.jira_key),
+            scheduled_work_id: None,
+        });
+    }
+
+    for sprint in list_sprints(conn)? {
+        let boundaries = [
+            (CalendarEntryKind::SprintStart, sprint.start_date.as_deref()),
+            (CalendarEntryKind::SprintEnd, sprint.end_date.as_deref()),
+        ];
+        for (kind, value) in boundaries {
+            let Some(date) = value.and_then(calendar_date) else {
+                continue;
+            };
+            if date >= from && date < to {
+                entries.push(CalendarEntry {
+                    date: date.format("%Y-%m-%d").to_string(),
+                    kind,
+                    title: sprint.name.clone(),
+                    ticket_key: None,
+                    scheduled_work_id: None,
+                });
+            }
+        }
+    }
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT scheduled_work.id, ticket_key, date, note, summary FROM scheduled_work
+             LEFT JOIN tickets ON jira_key = ticket_key
+             WHERE date >= ?1 AND date < ?2",
+        )
+        .map_err(DbError::from)?;
+    let scheduled = stmt
+        .query_map(params![from_text, to_text], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+    for (id, ticket_key, date, note, summary) in scheduled {
+        let title = match note.or(summary) {
+            Some(text) => format!("{}: {}", ticket_key, text),
+            None => ticket_key.clone(),
+        };
+        entries.push(CalendarEntry {
+            date,
+            kind: CalendarEntryKind::Scheduled,
+            title,
+            ticket_key: Some(ticket_key),
+            scheduled_work_id: Some(id),
+        });
+    }
+
+    entries.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then(a.kind.cmp(&b.kind))
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{upsert_sprints, upsert_ticket};
+    use crate::models::{Sprint, Ticket};
+
+    fn ticket(key: &str, due_date: &str, resolved_at: Option<&str>) -> Ticket {
+        Ticket {
+            due_date: Some(due_date.to_string()),
+            ..test_support::ticket_resolved_at(key, resolved_at)
+        }
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).expect("valid date")
+    }
+
+    #[test]
+    fn combines_due_dates_sprints_and_scheduled_work() {
+        let conn = setup_db();
+        for t in [
+            ticket("OPS-1", "2025-02-14", None),
+            ticket("OPS-2", "2025-02-14", Some("2025-02-10T09:00:00Z")),
+            ticket("OPS-3", "2025-03-01", None),
+        ] {
+            upsert_ticket(&conn, &t).expect("ticket");
+        }
+        let sprint = |name: &str, start: &str, end: &str| Sprint {
+            name: name.to_string(),
+            state: Some("active".to_string()),
+            start_date: Some(start.to_string()),
+            end_date: Some(end.to_string()),
+        };
+        upsert_sprints(
+            &conn,
+            &[
+                sprint(
+                    "Ops 1",
+                    "2025-01-27T09:00:00.000Z",
+                    "2025-02-10T17:00:00.000Z",
+                ),
+                sprint(
+                    "Ops 2",
+                    "2025-02-10T09:00:00.000Z",
+                    "2025-02-24T17:00:00.000Z",
+                ),
+            ],
+        )
+        .expect("sprints");
+        schedule_work(&conn, "OPS-3", date(2, 12), None, "2025-02-01T09:00:00Z")
+            .expect("scheduled");
+        let pairing = schedule_work(
+            &conn,
+            "OPS-1",
+            date(2, 12),
+            Some("Pair with Dana"),
+            "2025-02-01T09:00:00Z",
+        )
+        .expect("scheduled");
+
+        let entries = get_calendar_entries(&conn, date(2, 1), date(3, 1)).expect("entries");
+        let rows: Vec<(&str, CalendarEntryKind, &str)> = entries
+            .iter()
+            .map(|e| (e.date.as_str(), e.kind, e.title.as_str()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("2025-02-10", CalendarEntryKind::SprintStart, "Ops 2"),
+                ("2025-02-10", CalendarEntryKind::SprintEnd, "Ops 1"),
+                (
+                    "2025-02-12",
+                    CalendarEntryKind::Scheduled,
+                    "OPS-1: Pair with Dana"
+                ),
+                (
+                    "2025-02-12",
+                    CalendarEntryKind::Scheduled,
+                    "OPS-3: Summary OPS-3"
+                ),
+                ("2025-02-14", CalendarEntryKind::Due, "OPS-1: Summary OPS-1"),
+                ("2025-02-24", CalendarEntryKind::SprintEnd, "Ops 2"),
+            ]
+        );
+        assert_eq!(entries[2].scheduled_work_id, Some(pairing.id));
+
+        assert!(delete_scheduled_work(&conn, pairing.id).expect("delete"));
+        assert_eq!(
+            get_calendar_entries(&conn, date(2, 1), date(3, 1))
+                .expect("entries")
+                .len(),
+            5
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use super::queries::{get_tickets, resolution_hours};
+use crate::errors::AppError;
+use crate::models::{CohortMatrix, CohortRow, DateRange, DurationMode, TicketFilter, WorkSchedule};
+use crate::services::time_calc::parse_timestamp;
+use chrono::NaiveDateTime;
+use rusqlite::Connection;
+use std::collections::BTreeMap;
+
+/// Resolution windows of the cohort matrix, in calendar days.
+const COHORT_WINDOW_DAYS: [u32; 3] = [1, 7, 30];
+
+/// Tickets created within `range`, grouped by creation month, with the
+/// fraction resolved within 1, 7 and 30 days of creation. Each window only
+/// counts tickets created at least that long before `now`, so recent
+/// cohorts aren't penalized for time they haven't had yet.
+pub fn get_cohort_resolution(
+    conn: &Connection,
+    range: &DateRange,
+    now: NaiveDateTime,
+) -> Result<CohortMatrix, AppError> {
+    #[derive(Default)]
+    struct Tally {
+        tickets: u32,
+        eligible: [u32; COHORT_WINDOW_DAYS.len()],
+        resolved: [u32; COHORT_WINDOW_DAYS.len()],
+    }
+
+    let filter = TicketFilter {
+        created: Some(range.clone()),
+        ..Default::default()
+    };
+    let mut tallies: BTreeMap<String, Tally> = BTreeMap::new();
+    for ticket in get_tickets(conn, &filter)? {
+        let Some(created) = parse_timestamp(&ticket.created_at) else {
+            continue;
+        };
+        let hours = ticket.resolved_at.as_deref().and_then(|resolved| {
+            resolution_hours(
+                &ticket.created_at,
+                resolved,
+                DurationMode::Calendar,
+                &WorkSchedule::default(),
+            )
+        });
+        let tally = tallies
+            .entry(created.format("%Y-%m").to_string())
+            .or_default();
+        tally.tickets += 1;
+        for (i, days) in COHORT_WINDOW_DAYS.iter().enumerate() {
+            let window_hours = f64::from(*days) * 24.0;
+            if (now - created).num_seconds() as f64 / 3600.0 < window_hours {
+                continue;
+            }
+            tally.eligible[i] += 1;
+            if hours.is_some_and(|h| h <= window_hours) {
+                tally.resolved[i] += 1;
+            }
+        }
+    }
+
+    Ok(CohortMatrix {
+        window_days: COHORT_WINDOW_DAYS.to_vec(),
+        cohorts: tallies
+            .into_iter()
+            .map(|(cohort, tally)| CohortRow {
+                cohort,
+                tickets: tally.tickets,
+                resolved_within: tally
+                    .eligible
+                    .iter()
+                    .zip(tally.resolved)
+                    .map(|(&eligible, resolved)| {
+                        (eligible > 0).then(|| f64::from(resolved) / f64::from(eligible))
+                    })
+                    .collect(),
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::upsert_ticket;
+    use crate::models::Ticket;
+    use chrono::NaiveDate;
+
+    fn ticket(key: &str, created_at: &str, resolved_at: Option<&str>) -> Ticket {
+        Ticket {
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            ..test_support::ticket_resolved_at(key, resolved_at)
+        }
+    }
+
+    #[test]
+    fn groups_by_creation_month_and_skips_immature_windows() {
+        let conn = setup_db();
+        for t in [
+            // January: one resolved same day, one in 5 days, one in 40, one open
+            ticket(
+                "OPS-1",
+                "2025-01-06T09:00:00Z",
+                Some("2025-01-06T15:00:00Z"),
+            ),
+            ticket(
+                "OPS-2",
+                "2025-01-10T09:00:00Z",
+                Some("2025-01-15T09:00:00Z"),
+            ),
+            ticket(
+                "OPS-3",
+                "2025-01-20T09:00:00Z",
+                Some("2025-03-01T09:00:00Z"),
+            ),
+            ticket("OPS-4", "2025-01-25T09:00:00Z", None),
+            // March: only 3 days old at `now`
+            ticket(
+                "OPS-5",
+                "2025-03-07T09:00:00Z",
+                Some("2025-03-07T10:00:00Z"),
+            ),
+            // Outside the range
+            ticket("OPS-6", "2024-12-30T09:00:00Z", None),
+        ] {
+            upsert_ticket(&conn, &t).expect("ticket");
+        }
+        let now = NaiveDate::from_ymd_opt(2025, 3, 10)
+            .and_then(|d| d.and_hms_opt(9, 0, 0))
+            .expect("valid datetime");
+
+        let matrix =
+            get_cohort_resolution(&conn, &DateRange::since("2025-01-01"), now).expect("cohorts");
+        assert_eq!(matrix.window_days, vec![1, 7, 30]);
+        assert_eq!(
+            matrix.cohorts,
+            vec![
+                CohortRow {
+                    cohort: "2025-01".to_string(),
+                    tickets: 4,
+                    resolved_within: vec![Some(0.25), Some(0.5), Some(0.5)],
+                },
+                CohortRow {
+                    cohort: "2025-03".to_string(),
+                    tickets: 1,
+                    resolved_within: vec![Some(1.0), None, None],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use crate::errors::{AppError, DbError};
+use crate::models::Dashboard;
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+const DASHBOARD_COLUMNS: &str = "id, name, layout, created_at, updated_at";
+
+fn map_dashboard_row(row: &Row) -> rusqlite::Result<Dashboard> {
+    let layout: String = row.get(2)?;
+
+    Ok(Dashboard {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        layout: serde_json::from_str(&layout)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(2, Type::Text, Box::new(e)))?,
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+pub fn create_dashboard(
+    conn: &Connection,
+    name: &str,
+    layout: &serde_json::Value,
+) -> Result<Dashboard, AppError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO dashboards (name, layout, created_at, updated_at) VALUES (?1, ?2, ?3, ?3)",
+        params![name, layout.to_string(), now],
+    )
+    .map_err(DbError::from)?;
+
+    get_dashboard(conn, conn.last_insert_rowid())?
+        .ok_or_else(|| AppError::Internal("Inserted dashboard not found".to_string()))
+}
+
+pub fn get_dashboard(conn: &Connection, id: i64) -> Result<Option<Dashboard>, AppError> {
+    conn.query_row(
+        &format!("SELECT {} FROM dashboards WHERE id = ?1", DASHBOARD_COLUMNS),
+        params![id],
+        map_dashboard_row,
+    )
+    .optional()
+    .map_err(|e| DbError::from(e).into())
+}
+
+pub fn list_dashboards(conn: &Connection) -> Result<Vec<Dashboard>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM dashboards ORDER BY name COLLATE NOCASE",
+            DASHBOARD_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+
+    let dashboards = stmt
+        .query_map([], map_dashboard_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(dashboards)
+}
+
+pub fn rename_dashboard(
+    conn: &Connection,
+    id: i64,
+    name: &str,
+) -> Result<Option<Dashboard>, AppError> {
+    conn.execute(
+        "UPDATE dashboards SET name = ?2, updated_at = ?3 WHERE id = ?1",
+        params![id, name, chrono::Utc::now().to_rfc3339()],
+    )
+    .map_err(DbError::from)?;
+
+    get_dashboard(conn, id)
+}
+
+pub fn save_dashboard_layout(
+    conn: &Connection,
+    id: i64,
+    layout: &serde_json::Value,
+) -> Result<Option<Dashboard>, AppError> {
+    conn.execute(
+        "UPDATE dashboards SET layout = ?2, updated_at = ?3 WHERE id = ?1",
+        params![id, layout.to_string(), chrono::Utc::now().to_rfc3339()],
+    )
+    .map_err(DbError::from)?;
+
+    get_dashboard(conn, id)
+}
+
+/// Copies a dashboard's layout under a new name; `None` if the source is gone.
+pub fn duplicate_dashboard(
+    conn: &Connection,
+    id: i64,
+    name: &str,
+) -> Result<Option<Dashboard>, AppError> {
+    match get_dashboard(conn, id)? {
+        Some(source) => create_dashboard(conn, name, &source.layout).map(Some),
+        None => Ok(None),
+    }
+}
+
+pub fn delete_dashboard(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute("DELETE FROM dashboards WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+    use serde_json::json;
+
+    #[test]
+    fn duplicate_copies_layout_under_new_name() {
+        let conn = setup_db();
+
+        let layout = json!([{ "widget": "summary", "x": 0, "y": 0, "w": 12, "h": 2 }]);
+        let ic = create_dashboard(&conn, "IC", &layout).expect("create");
+        let manager = duplicate_dashboard(&conn, ic.id, "Manager")
+            .expect("duplicate")
+            .expect("source exists");
+
+        assert_ne!(manager.id, ic.id);
+        assert_eq!(manager.layout, layout);
+
+        let renamed = rename_dashboard(&conn, manager.id, "Team Lead")
+            .expect("rename")
+            .expect("dashboard exists");
+        assert_eq!(renamed.name, "Team Lead");
+
+        let names: Vec<String> = list_dashboards(&conn)
+            .expect("list")
+            .into_iter()
+            .map(|d| d.name)
+            .collect();
+        assert_eq!(names, vec!["IC".to_string(), "Team Lead".to_string()]);
+        assert!(duplicate_dashboard(&conn, 404, "Nope")
+            .expect("duplicate")
+            .is_none());
+    }
+}
@@ -0,0 +1,268 @@
+use super::queries::{refresh_resolution_hours, RefreshScope};
+use super::support_calendars::project_schedules;
+use crate::errors::{AppError, DbError};
+use crate::models::TableRows;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection};
+use std::collections::BTreeMap;
+
+/// Tables carried by `export_dataset`; new tables need adding here (a test
+/// checks the schema against this list).
+pub const DATASET_TABLES: [&str; 47] = [
+    "tickets",
+    "sync_metadata",
+    "jira_accounts",
+    "local_tags",
+    "watched_tickets",
+    "alert_log",
+    "alert_rules",
+    "saved_views",
+    "dashboards",
+    "report_snapshots",
+    "stats_history",
+    "duplicate_candidates",
+    "ticket_links",
+    "ticket_components",
+    "ticket_fix_versions",
+    "ticket_affected_versions",
+    "attachments",
+    "local_changes",
+    "outbox",
+    "sync_runs",
+    "ticket_changes",
+    "tickets_archive",
+    "ticket_notes",
+    "time_entries",
+    "ticket_rank",
+    "scheduled_work",
+    "status_category_mappings",
+    "priority_mappings",
+    "resolved_statuses",
+    "rotations",
+    "teams",
+    "team_members",
+    "users",
+    "ticket_views",
+    "wip_limits",
+    "sprints",
+    "calendars",
+    "project_calendars",
+    "outbound_webhooks",
+    "automations",
+    "automation_matches",
+    "automation_log",
+    "ticket_snoozes",
+    "metric_goals",
+    "root_causes",
+    "ticket_root_causes",
+    "local_links",
+];
+
+/// Every row of every dataset table that exists in this database.
+pub fn export_tables(conn: &Connection) -> Result<BTreeMap<String, TableRows>, AppError> {
+    let mut tables = BTreeMap::new();
+    for table in DATASET_TABLES {
+        if table_columns(conn, table)?.is_empty() {
+            continue;
+        }
+        tables.insert(table.to_string(), export_table(conn, table)?);
+    }
+    Ok(tables)
+}
+
+fn export_table(conn: &Connection, table: &str) -> Result<TableRows, AppError> {
+    // Table names only ever come from DATASET_TABLES
+    let mut stmt = conn
+        .prepare(&format!("SELECT * FROM {}", table))
+        .map_err(DbError::from)?;
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+
+    let rows = stmt
+        .query_map([], |row| {
+            let mut object = serde_json::Map::new();
+            for (idx, column) in columns.iter().enumerate() {
+                object.insert(column.clone(), sql_to_json(row.get(idx)?));
+            }
+            Ok(object)
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(rows)
+}
+
+/// Replaces the contents of every dataset table present in `tables`, in one
+/// transaction. Columns this schema doesn't know are ignored. Returns the
+/// number of rows written per table.
+pub fn import_tables(
+    conn: &Connection,
+    tables: &BTreeMap<String, TableRows>,
+) -> Result<BTreeMap<String, usize>, AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    let mut written = BTreeMap::new();
+
+    for table in DATASET_TABLES {
+        let Some(rows) = tables.get(table) else {
+            continue;
+        };
+        let known = table_columns(&tx, table)?;
+        if known.is_empty() {
+            continue;
+        }
+
+        tx.execute(&format!("DELETE FROM {}", table), [])
+            .map_err(DbError::from)?;
+        for row in rows {
+            let (columns, values): (Vec<&str>, Vec<Value>) = row
+                .iter()
+                .filter(|(column, _)| known.iter().any(|k| k == *column))
+                .map(|(column, value)| (column.as_str(), json_to_sql(value)))
+                .unzip();
+            if columns.is_empty() {
+                continue;
+            }
+            let placeholders = (1..=columns.len())
+                .map(|i| format!("?{}", i))
+                .collect::<Vec<_>>()
+                .join(", ");
+            tx.execute(
+                &format!(
+                    "INSERT INTO {} ({}) VALUES ({})",
+                    table,
+                    columns.join(", "),
+                    placeholders
+                ),
+                params_from_iter(values),
+            )
+            .map_err(DbError::from)?;
+        }
+        written.insert(table.to_string(), rows.len());
+    }
+    // Exports from before resolution hours were stored don't carry them
+    let schedules = project_schedules(&tx)?;
+    for table in ["tickets", "tickets_archive"] {
+        refresh_resolution_hours(&tx, table, RefreshScope::Missing, &schedules)?;
+    }
+
+    tx.commit().map_err(DbError::from)?;
+    Ok(written)
+}
+
+/// Column names of `table`, empty if it doesn't exist.
+fn table_columns(conn: &Connection, table: &str) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT name FROM pragma_table_info(?1)")
+        .map_err(DbError::from)?;
+    let columns = stmt
+        .query_map([table], |row| row.get(0))
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(DbError::from)?;
+    Ok(columns)
+}
+
+pub(crate) fn sql_to_json(value: Value) -> serde_json::Value {
+    match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Integer(i) => i.into(),
+        Value::Real(f) => f.into(),
+        Value::Text(s) => s.into(),
+        // No table stores blobs; keep the bytes rather than dropping them
+        Value::Blob(bytes) => bytes.into(),
+    }
+}
+
+fn json_to_sql(value: &serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Integer(i64::from(*b)),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Real(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) => Value::Text(s.clone()),
+        other => Value::Text(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{create_alert_rule, get_tickets, list_alert_rules, tag_tickets, upsert_ticket};
+    use crate::models::{
+        AlertChannel, AlertCondition, AlertRuleInput, Ticket, TicketFilter, TicketMatch,
+    };
+
+    #[test]
+    fn dataset_round_trips_into_an_empty_database() {
+        let source = setup_db();
+        upsert_ticket(
+            &source,
+            &Ticket {
+                summary: "Printer on fire".to_string(),
+                ..test_support::ticket("OPS-1")
+            },
+        )
+        .expect("ticket");
+        tag_tickets(&source, &["OPS-1".to_string()], "follow-up").expect("tag");
+        create_alert_rule(
+            &source,
+            &AlertRuleInput {
+                name: "Idle".to_string(),
+                condition: AlertCondition::Idle {
+                    tickets: TicketMatch::default(),
+                    days: 7.0,
+                },
+                channel: AlertChannel::Desktop,
+                throttle_minutes: 60,
+                enabled: true,
+            },
+        )
+        .expect("rule");
+
+        let tables = export_tables(&source).expect("export");
+        // Through JSON text, as the file would be
+        let json = serde_json::to_string(&tables).expect("serialize");
+        let tables: BTreeMap<String, TableRows> = serde_json::from_str(&json).expect("parse");
+
+        let target = setup_db();
+        let written = import_tables(&target, &tables).expect("import");
+        assert_eq!(written.get("tickets"), Some(&1));
+
+        let tickets = get_tickets(
+            &target,
+            &TicketFilter {
+                tags: vec!["follow-up".to_string()],
+                ..Default::default()
+            },
+        )
+        .expect("tickets");
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets[0].summary, "Printer on fire");
+        assert_eq!(list_alert_rules(&target).expect("rules").len(), 1);
+    }
+
+    #[test]
+    fn every_table_is_exported_except_caches_and_search_indexes() {
+        // Rebuilt from the exported tables, or refetched
+        const NOT_EXPORTED: [&str; 3] = ["icon_cache", "tickets_fts", "ticket_notes_fts"];
+        let conn = setup_db();
+        let mut stmt = conn
+            .prepare(
+                "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+            )
+            .expect("prepare");
+        let missing: Vec<String> = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .expect("tables")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("names")
+            .into_iter()
+            .filter(|table| !DATASET_TABLES.contains(&table.as_str()))
+            .filter(|table| !NOT_EXPORTED.iter().any(|prefix| table.starts_with(prefix)))
+            .collect();
+        assert!(missing.is_empty(), "not in DATASET_TABLES: {:?}", missing);
+    }
+}
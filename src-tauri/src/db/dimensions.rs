@@ -0,0 +1,469 @@
+use super::queries::{average, percentile, resolution_column, tickets_table, LIST_SEPARATOR};
+use super::root_causes::ROOT_CAUSE_EXPR;
+use crate::errors::{AppError, DbError};
+use crate::models::{
+    AggregateEntry, CountEntry, Dimension, DurationMode, Metric, PivotTable, TicketFilter,
+};
+use crate::services::time_calc::parse_timestamp;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// How one dimension's values are read from a `tickets` row.
+pub(crate) struct DimensionColumn {
+    /// SQL expression giving the value as text, or NULL.
+    pub expr: &'static str,
+    /// Parameters bound by `expr`, ahead of any others in the query.
+    pub params: Vec<Value>,
+    /// Separates the values of multi-valued columns.
+    pub separator: Option<char>,
+    /// Maps a raw value to its group, e.g. a timestamp to its week.
+    pub bucket: Option<fn(&str) -> Option<String>>,
+    /// Group for tickets without a value.
+    pub missing: &'static str,
+}
+
+impl DimensionColumn {
+    /// The groups a ticket with this raw column value belongs to.
+    pub fn values(&self, raw: Option<String>) -> Vec<String> {
+        let mut values: Vec<String> = match (raw, self.separator) {
+            (Some(raw), Some(separator)) => raw
+                .split(separator)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .collect(),
+            (Some(raw), None) if !raw.trim().is_empty() => vec![raw],
+            _ => Vec::new(),
+        };
+        if let Some(bucket) = self.bucket {
+            values = values.iter().filter_map(|v| bucket(v)).collect();
+        }
+        values.sort();
+        values.dedup();
+        if values.is_empty() {
+            values.push(self.missing.to_string());
+        }
+        values
+    }
+}
+
+/// The dimension registry: every groupable ticket attribute and how to
+/// read it. Custom field names are bound as parameters, never spliced.
+pub(crate) fn dimension_column(dimension: &Dimension) -> Result<DimensionColumn, AppError> {
+    let column = |expr, separator, missing| DimensionColumn {
+        expr,
+        params: Vec::new(),
+        separator,
+        bucket: None,
+        missing,
+    };
+    let week = |expr, missing| DimensionColumn {
+        bucket: Some(iso_week),
+        ..column(expr, None, missing)
+    };
+
+    Ok(match dimension {
+        Dimension::Status => column("status", None, "None"),
+        Dimension::Priority => column("priority", None, "None"),
+        Dimension::Assignee => column("assignee", None, "Unassigned"),
+        Dimension::Project => column("project_key", None, "No project"),
+        Dimension::Label => column("labels", Some(','), "No label"),
+        Dimension::Component => column(
+            "(SELECT group_concat(component, char(31)) FROM ticket_components \
+             WHERE ticket_key = tickets.jira_key)",
+            Some(LIST_SEPARATOR),
+            "No component",
+        ),
+        Dimension::Category => column("category", None, "Uncategorized"),
+        Dimension::Team => column(
+            "(SELECT group_concat(t.name, char(31)) FROM team_members m \
+             JOIN teams t ON t.id = m.team_id WHERE m.assignee = tickets.assignee)",
+            Some(LIST_SEPARATOR),
+            "No team",
+        ),
+        Dimension::Customer => column("customer", None, "No customer"),
+        Dimension::AffectedVersion => column(
+            "(SELECT group_concat(version, char(31)) FROM ticket_affected_versions \
+             WHERE ticket_key = tickets.jira_key)",
+            Some(LIST_SEPARATOR),
+            "No affected version",
+        ),
+        Dimension::Environment => column("environment", None, "No environment"),
+        Dimension::RootCause => column(ROOT_CAUSE_EXPR, None, "No root cause"),
+        Dimension::CreatedWeek => week("created_at", "Unknown"),
+        Dimension::ResolvedWeek => week("resolved_at", "Unresolved"),
+        Dimension::CustomField(name) => {
+            if name.trim().is_empty() {
+                return Err(AppError::Config(
+                    "Custom field name cannot be empty".to_string(),
+                ));
+            }
+            DimensionColumn {
+                expr: "CASE WHEN json_valid(custom_fields) \
+                       THEN CAST(json_extract(custom_fields, '$.' || json_quote(?)) AS TEXT) END",
+                params: vec![Value::Text(name.clone())],
+                separator: None,
+                bucket: None,
+                missing: "None",
+            }
+        }
+    })
+}
+
+/// ISO week (`2025-W02`) of a stored timestamp.
+fn iso_week(timestamp: &str) -> Option<String> {
+    parse_timestamp(timestamp).map(|at| at.format("%G-W%V").to_string())
+}
+
+/// `metric` over a group of `count` tickets whose resolved ones took
+/// `sorted_hours`; `None` when a resolution metric has no samples.
+pub(crate) fn metric_value(metric: Metric, count: u32, sorted_hours: &[f64]) -> Option<f64> {
+    match metric {
+        Metric::Count => Some(f64::from(count)),
+        _ if sorted_hours.is_empty() => None,
+        Metric::AvgResolution => Some(average(sorted_hours)),
+        Metric::P90Resolution => Some(percentile(sorted_hours, 90.0)),
+    }
+}
+
+/// `metric` for each value of `dimension` among `filter`'s tickets. Counts
+/// come largest first; resolution times fastest first, and groups without
+/// resolved tickets are left out.
+pub fn aggregate(
+    conn: &Connection,
+    dimension: &Dimension,
+    metric: Metric,
+    filter: &TicketFilter,
+    mode: DurationMode,
+) -> Result<Vec<AggregateEntry>, AppError> {
+    let column = dimension_column(dimension)?;
+    let (clause, filter_values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let hours = resolution_column(mode);
+    let values = column.params.iter().cloned().chain(filter_values);
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {}, {hours} FROM {table} WHERE {clause} ORDER BY {hours}",
+            column.expr
+        ))
+        .map_err(DbError::from)?;
+    let rows = stmt
+        .query_map(params_from_iter(values), |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, Option<f64>>(1)?,
+            ))
+        })
+        .map_err(DbError::from)?;
+
+    // Rows arrive sorted by duration (NULLs first), so each group's are too
+    let mut groups: BTreeMap<String, (u32, Vec<f64>)> = BTreeMap::new();
+    for row in rows {
+        let (raw, hours) = row.map_err(DbError::from)?;
+        for name in column.values(raw) {
+            let group = groups.entry(name).or_default();
+            group.0 += 1;
+            group.1.extend(hours);
+        }
+    }
+
+    let mut entries: Vec<AggregateEntry> = groups
+        .into_iter()
+        .filter_map(|(name, (count, hours))| {
+            let value = metric_value(metric, count, &hours)?;
+            let count = match metric {
+                Metric::Count => count,
+                _ => hours.len() as u32,
+            };
+            Some(AggregateEntry { name, value, count })
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        let by_value = match metric {
+            Metric::Count => b.value.total_cmp(&a.value),
+            _ => a.value.total_cmp(&b.value),
+        };
+        by_value.then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(entries)
+}
+
+/// `metric` for every combination of a `rows` value and a `columns` value
+/// among `filter`'s tickets, for heatmaps. Labels are sorted by name (so
+/// weeks run in order); cells without tickets, or without resolved tickets
+/// for a resolution metric, are `None`.
+pub fn get_pivot(
+    conn: &Connection,
+    rows: &Dimension,
+    columns: &Dimension,
+    metric: Metric,
+    filter: &TicketFilter,
+    mode: DurationMode,
+) -> Result<PivotTable, AppError> {
+    let row_column = dimension_column(rows)?;
+    let col_column = dimension_column(columns)?;
+    let (clause, filter_values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let hours = resolution_column(mode);
+    let values = row_column
+        .params
+        .iter()
+        .chain(&col_column.params)
+        .cloned()
+        .chain(filter_values);
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {}, {}, {hours} FROM {table} WHERE {clause} ORDER BY {hours}",
+            row_column.expr, col_column.expr
+        ))
+        .map_err(DbError::from)?;
+    let ticket_rows = stmt
+        .query_map(params_from_iter(values), |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<f64>>(2)?,
+            ))
+        })
+        .map_err(DbError::from)?;
+
+    let mut row_labels = BTreeSet::new();
+    let mut column_labels = BTreeSet::new();
+    let mut cells: BTreeMap<(String, String), (u32, Vec<f64>)> = BTreeMap::new();
+    for ticket_row in ticket_rows {
+        let (raw_row, raw_col, hours) = ticket_row.map_err(DbError::from)?;
+        let col_values = col_column.values(raw_col);
+        for row_value in row_column.values(raw_row) {
+            for col_value in &col_values {
+                let cell = cells
+                    .entry((row_value.clone(), col_value.clone()))
+                    .or_default();
+                cell.0 += 1;
+                cell.1.extend(hours);
+                column_labels.insert(col_value.clone());
+            }
+            row_labels.insert(row_value);
+        }
+    }
+
+    let row_labels: Vec<String> = row_labels.into_iter().collect();
+    let column_labels: Vec<String> = column_labels.into_iter().collect();
+    let values = row_labels
+        .iter()
+        .map(|row| {
+            column_labels
+                .iter()
+                .map(|col| {
+                    cells
+                        .get(&(row.clone(), col.clone()))
+                        .and_then(|(count, hours)| metric_value(metric, *count, hours))
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(PivotTable {
+        rows: row_labels,
+        columns: column_labels,
+        values,
+    })
+}
+
+/// Ticket count per value of `dimension`, largest first.
+pub fn get_count_by(
+    conn: &Connection,
+    dimension: &Dimension,
+    filter: &TicketFilter,
+) -> Result<Vec<CountEntry>, AppError> {
+    Ok(aggregate(
+        conn,
+        dimension,
+        Metric::Count,
+        filter,
+        DurationMode::default(),
+    )?
+    .into_iter()
+    .map(|entry| CountEntry {
+        name: entry.name,
+        count: entry.count,
+    })
+    .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support;
+    use crate::db::upsert_ticket;
+    use crate::models::Ticket;
+
+    fn seeded_db() -> Connection {
+        let conn = test_support::setup_db();
+        for (key, assignee, labels, resolved_at, custom_fields) in [
+            (
+                "OPS-1",
+                Some("ana"),
+                "vpn,network",
+                Some("2025-01-06T11:00:00Z"),
+                r#"{"region":"EU"}"#,
+            ),
+            (
+                "OPS-2",
+                Some("ana"),
+                "vpn",
+                Some("2025-01-06T17:00:00Z"),
+                r#"{"region":"US"}"#,
+            ),
+            ("OPS-3", None, "", None, r#"{"region":"EU"}"#),
+        ] {
+            upsert_ticket(
+                &conn,
+                &Ticket {
+                    jira_key: key.to_string(),
+                    summary: format!("Summary {}", key),
+                    status: "Open".to_string(),
+                    priority: "High".to_string(),
+                    assignee: assignee.map(str::to_string),
+                    labels: labels.to_string(),
+                    created_at: "2025-01-06T09:00:00Z".to_string(),
+                    updated_at: "2025-01-06T09:00:00Z".to_string(),
+                    resolved_at: resolved_at.map(str::to_string),
+                    project_key: "OPS".to_string(),
+                    custom_fields: custom_fields.to_string(),
+                    ..Default::default()
+                },
+            )
+            .expect("insert");
+        }
+        conn
+    }
+
+    fn summarize(entries: Vec<AggregateEntry>) -> Vec<(String, f64, u32)> {
+        entries
+            .into_iter()
+            .map(|e| (e.name, e.value, e.count))
+            .collect()
+    }
+
+    #[test]
+    fn counts_split_multi_valued_dimensions() {
+        let conn = seeded_db();
+        let filter = TicketFilter::default();
+        let count = |dimension: Dimension| {
+            summarize(
+                aggregate(
+                    &conn,
+                    &dimension,
+                    Metric::Count,
+                    &filter,
+                    DurationMode::Business,
+                )
+                .expect("aggregate"),
+            )
+        };
+
+        assert_eq!(
+            count(Dimension::Label),
+            vec![
+                ("vpn".to_string(), 2.0, 2),
+                ("No label".to_string(), 1.0, 1),
+                ("network".to_string(), 1.0, 1),
+            ]
+        );
+        assert_eq!(
+            count(Dimension::Assignee),
+            vec![
+                ("ana".to_string(), 2.0, 2),
+                ("Unassigned".to_string(), 1.0, 1),
+            ]
+        );
+        assert_eq!(
+            count(Dimension::CustomField("region".to_string())),
+            vec![("EU".to_string(), 2.0, 2), ("US".to_string(), 1.0, 1)]
+        );
+        assert!(aggregate(
+            &conn,
+            &Dimension::CustomField(" ".to_string()),
+            Metric::Count,
+            &filter,
+            DurationMode::Business
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn pivot_fills_a_matrix_of_both_dimensions() {
+        let conn = seeded_db();
+        let filter = TicketFilter::default();
+
+        let pivot = get_pivot(
+            &conn,
+            &Dimension::Label,
+            &Dimension::Assignee,
+            Metric::Count,
+            &filter,
+            DurationMode::Business,
+        )
+        .expect("pivot");
+        assert_eq!(pivot.rows, vec!["No label", "network", "vpn"]);
+        assert_eq!(pivot.columns, vec!["Unassigned", "ana"]);
+        assert_eq!(
+            pivot.values,
+            vec![
+                vec![Some(1.0), None],
+                vec![None, Some(1.0)],
+                vec![None, Some(2.0)],
+            ]
+        );
+
+        let throughput = get_pivot(
+            &conn,
+            &Dimension::Assignee,
+            &Dimension::ResolvedWeek,
+            Metric::AvgResolution,
+            &filter,
+            DurationMode::Business,
+        )
+        .expect("pivot");
+        assert_eq!(throughput.rows, vec!["Unassigned", "ana"]);
+        assert_eq!(throughput.columns, vec!["2025-W02", "Unresolved"]);
+        assert_eq!(
+            throughput.values,
+            vec![vec![None, None], vec![Some(5.0), None]]
+        );
+    }
+
+    #[test]
+    fn resolution_metrics_only_use_resolved_tickets() {
+        let conn = seeded_db();
+        let filter = TicketFilter::default();
+
+        let avg = aggregate(
+            &conn,
+            &Dimension::Label,
+            Metric::AvgResolution,
+            &filter,
+            DurationMode::Business,
+        )
+        .expect("aggregate");
+        assert_eq!(
+            summarize(avg),
+            vec![("network".to_string(), 2.0, 1), ("vpn".to_string(), 5.0, 2),]
+        );
+
+        let p90 = aggregate(
+            &conn,
+            &Dimension::Project,
+            Metric::P90Resolution,
+            &filter,
+            DurationMode::Business,
+        )
+        .expect("aggregate");
+        assert_eq!(p90.len(), 1);
+        assert_eq!((p90[0].name.as_str(), p90[0].count), ("OPS", 2));
+        assert!((p90[0].value - 7.4).abs() < 1e-9);
+    }
+}
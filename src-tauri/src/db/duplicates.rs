@@ -0,0 +1,200 @@
+use crate::errors::{AppError, DbError};
+use crate::models::{DuplicateCandidate, DuplicateStatus};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+const CANDIDATE_QUERY: &str = "SELECT d.id, d.ticket_a, a.summary, a.source, d.ticket_b, \
+     b.summary, b.source, d.similarity, d.status, d.detected_at, d.resolved_at \
+     FROM duplicate_candidates d \
+     LEFT JOIN tickets a ON a.jira_key = d.ticket_a \
+     LEFT JOIN tickets b ON b.jira_key = d.ticket_b";
+
+fn map_candidate_row(row: &Row) -> rusqlite::Result<DuplicateCandidate> {
+    let status: String = row.get(8)?;
+
+    Ok(DuplicateCandidate {
+        id: row.get(0)?,
+        ticket_a: row.get(1)?,
+        summary_a: row.get(2)?,
+        source_a: row.get(3)?,
+        ticket_b: row.get(4)?,
+        summary_b: row.get(5)?,
+        source_b: row.get(6)?,
+        similarity: row.get(7)?,
+        status: DuplicateStatus::parse(&status).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                8,
+                Type::Text,
+                format!("Unknown duplicate status: {}", status).into(),
+            )
+        })?,
+        detected_at: row.get(9)?,
+        resolved_at: row.get(10)?,
+    })
+}
+
+/// A ticket considered by the dedupe pass.
+pub struct DedupeTicket {
+    pub jira_key: String,
+    pub summary: String,
+    pub source: String,
+    pub created_at: String,
+}
+
+/// Tickets not already merged into another, oldest first.
+pub fn get_dedupe_tickets(conn: &Connection) -> Result<Vec<DedupeTicket>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT jira_key, summary, source, created_at FROM tickets
+             WHERE duplicate_of IS NULL ORDER BY created_at",
+        )
+        .map_err(DbError::from)?;
+    let tickets = stmt
+        .query_map([], |row| {
+            Ok(DedupeTicket {
+                jira_key: row.get(0)?,
+                summary: row.get(1)?,
+                source: row.get(2)?,
+                created_at: row.get(3)?,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(tickets)
+}
+
+/// Records a candidate pair unless it was already flagged (in either order),
+/// so dismissed pairs stay dismissed. Returns whether a row was added.
+pub fn insert_duplicate_candidate(
+    conn: &Connection,
+    ticket_a: &str,
+    ticket_b: &str,
+    similarity: f64,
+    detected_at: &str,
+) -> Result<bool, AppError> {
+    let (first, second) = if ticket_a <= ticket_b {
+        (ticket_a, ticket_b)
+    } else {
+        (ticket_b, ticket_a)
+    };
+    let inserted = conn
+        .execute(
+            "INSERT OR IGNORE INTO duplicate_candidates
+                 (ticket_a, ticket_b, similarity, status, detected_at)
+             VALUES (?1, ?2, ?3, 'pending', ?4)",
+            params![first, second, similarity, detected_at],
+        )
+        .map_err(DbError::from)?;
+
+    Ok(inserted > 0)
+}
+
+pub fn get_duplicate_candidate(
+    conn: &Connection,
+    id: i64,
+) -> Result<Option<DuplicateCandidate>, AppError> {
+    conn.query_row(
+        &format!("{} WHERE d.id = ?1", CANDIDATE_QUERY),
+        params![id],
+        map_candidate_row,
+    )
+    .optional()
+    .map_err(|e| DbError::from(e).into())
+}
+
+/// Candidates with `status`, or all of them, most similar first.
+pub fn list_duplicate_candidates(
+    conn: &Connection,
+    status: Option<DuplicateStatus>,
+) -> Result<Vec<DuplicateCandidate>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE (?1 IS NULL OR d.status = ?1) ORDER BY d.similarity DESC, d.id",
+            CANDIDATE_QUERY
+        ))
+        .map_err(DbError::from)?;
+    let candidates = stmt
+        .query_map(
+            params![status.map(DuplicateStatus::as_str)],
+            map_candidate_row,
+        )
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(candidates)
+}
+
+/// Folds the other ticket of a pending candidate into `keep`: it's marked
+/// `duplicate_of = keep` (which drops it from every aggregation) and its
+/// local tags move over. Returns `None` for an unknown or already-resolved id.
+pub fn merge_duplicate(
+    conn: &Connection,
+    id: i64,
+    keep: &str,
+) -> Result<Option<DuplicateCandidate>, AppError> {
+    let Some(candidate) = get_duplicate_candidate(conn, id)? else {
+        return Ok(None);
+    };
+    if candidate.status != DuplicateStatus::Pending {
+        return Ok(None);
+    }
+    let duplicate = if keep == candidate.ticket_a {
+        candidate.ticket_b.as_str()
+    } else if keep == candidate.ticket_b {
+        candidate.ticket_a.as_str()
+    } else {
+        return Err(AppError::Config(format!(
+            "{} is not part of duplicate candidate {}",
+            keep, id
+        )));
+    };
+
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    tx.execute(
+        "UPDATE tickets SET duplicate_of = ?2 WHERE jira_key = ?1",
+        params![duplicate, keep],
+    )
+    .map_err(DbError::from)?;
+    tx.execute(
+        "INSERT OR IGNORE INTO local_tags (ticket_key, tag, created_at)
+         SELECT ?2, tag, created_at FROM local_tags WHERE ticket_key = ?1",
+        params![duplicate, keep],
+    )
+    .map_err(DbError::from)?;
+    tx.execute(
+        "DELETE FROM local_tags WHERE ticket_key = ?1",
+        params![duplicate],
+    )
+    .map_err(DbError::from)?;
+    set_status(&tx, id, DuplicateStatus::Merged)?;
+    tx.commit().map_err(DbError::from)?;
+
+    get_duplicate_candidate(conn, id)
+}
+
+/// Marks a pending candidate as not a duplicate. Returns `None` for an
+/// unknown or already-resolved id.
+pub fn dismiss_duplicate(
+    conn: &Connection,
+    id: i64,
+) -> Result<Option<DuplicateCandidate>, AppError> {
+    match get_duplicate_candidate(conn, id)? {
+        Some(candidate) if candidate.status == DuplicateStatus::Pending => {
+            set_status(conn, id, DuplicateStatus::Dismissed)?;
+            get_duplicate_candidate(conn, id)
+        }
+        _ => Ok(None),
+    }
+}
+
+fn set_status(conn: &Connection, id: i64, status: DuplicateStatus) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE duplicate_candidates SET status = ?2, resolved_at = ?3 WHERE id = ?1",
+        params![id, status.as_str(), chrono::Utc::now().to_rfc3339()],
+    )
+    .map_err(DbError::from)?;
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use crate::errors::{AppError, DbError};
+use rusqlite::{params, Connection, OpenFlags};
+use std::path::Path;
+
+/// Opens the database at `path`, unlocking it with `key` when it is
+/// encrypted. SQLCipher only checks the key on first read, so the schema
+/// is read here to fail fast on a wrong or missing key.
+pub fn open_database(path: &str, key: Option<&str>) -> Result<Connection, AppError> {
+    unlock(Connection::open(path).map_err(DbError::from)?, key)
+}
+
+/// Opens the database at `path` without write access, for running SQL
+/// typed by the user. Writes fail even if a statement slips through.
+pub fn open_readonly_database(path: &str, key: Option<&str>) -> Result<Connection, AppError> {
+    let conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(DbError::from)?;
+    let conn = unlock(conn, key)?;
+    conn.pragma_update(None, "query_only", true)
+        .map_err(DbError::from)?;
+    Ok(conn)
+}
+
+fn unlock(conn: Connection, key: Option<&str>) -> Result<Connection, AppError> {
+    if let Some(key) = key {
+        conn.pragma_update(None, "key", key)
+            .map_err(DbError::from)?;
+    }
+    conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| {
+        row.get::<_, i64>(0)
+    })
+    .map_err(|e| match key {
+        Some(_) => AppError::Config(format!("Failed to unlock encrypted database: {}", e)),
+        None => AppError::Config(format!(
+            "Failed to read database (is it encrypted without a stored key?): {}",
+            e
+        )),
+    })?;
+    Ok(conn)
+}
+
+/// Replaces the plaintext database behind `conn` with an encrypted copy
+/// keyed by `key`, and reopens `conn` on it. The copy is written next to
+/// the original and only swapped in once complete; on failure the original
+/// file is kept and reopened.
+pub fn encrypt_database_file(
+    conn: &mut Connection,
+    path: &Path,
+    key: &str,
+) -> Result<(), AppError> {
+    let encrypted_path = path.with_extension("db.encrypting");
+    let encrypted_str = encrypted_path
+        .to_str()
+        .ok_or_else(|| AppError::Internal(format!("Invalid DB path: {:?}", encrypted_path)))?;
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| AppError::Internal(format!("Invalid DB path: {:?}", path)))?;
+    // A leftover from an interrupted attempt would be appended to
+    if encrypted_path.exists() {
+        std::fs::remove_file(&encrypted_path)
+            .map_err(|e| AppError::Internal(format!("Failed to remove stale copy: {}", e)))?;
+    }
+
+    conn.execute(
+        "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+        params![encrypted_str, key],
+    )
+    .map_err(DbError::from)?;
+    let exported = conn
+        .query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()))
+        .and_then(|()| {
+            let version: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+            conn.pragma_update(Some("encrypted"), "user_version", version)
+        });
+    conn.execute("DETACH DATABASE encrypted", [])
+        .map_err(DbError::from)?;
+    if let Err(e) = exported {
+        std::fs::remove_file(&encrypted_path).ok();
+        return Err(DbError::from(e).into());
+    }
+
+    // Close the plaintext connection so its file can be replaced
+    let plaintext = std::mem::replace(conn, Connection::open_in_memory().map_err(DbError::from)?);
+    plaintext.close().map_err(|(_, e)| DbError::from(e))?;
+
+    if let Err(e) = std::fs::rename(&encrypted_path, path) {
+        *conn = open_database(path_str, None)?;
+        return Err(AppError::Internal(format!(
+            "Failed to replace database with encrypted copy: {}",
+            e
+        )));
+    }
+    *conn = open_database(path_str, Some(key))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_tickets, initialize_database, upsert_ticket};
+    use crate::models::{Ticket, TicketFilter};
+
+    #[test]
+    fn encrypted_copy_needs_the_key() {
+        let dir = std::env::temp_dir().join(format!("ticketdash-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let path = dir.join("tickets.db");
+        let path_str = path.to_str().expect("utf-8 path");
+
+        let mut conn = open_database(path_str, None).expect("plaintext db");
+        initialize_database(&conn).expect("schema initialized");
+        upsert_ticket(
+            &conn,
+            &Ticket {
+                jira_key: "OPS-1".to_string(),
+                summary: "Payroll export leaks salaries".to_string(),
+                ..Default::default()
+            },
+        )
+        .expect("ticket");
+
+        encrypt_database_file(&mut conn, &path, "correct horse").expect("encrypted");
+        assert_eq!(
+            get_tickets(&conn, &TicketFilter::default())
+                .expect("tickets")
+                .len(),
+            1
+        );
+        drop(conn);
+
+        assert!(open_database(path_str, None).is_err());
+        assert!(open_database(path_str, Some("wrong key")).is_err());
+        let reopened = open_database(path_str, Some("correct horse")).expect("unlocked");
+        initialize_database(&reopened).expect("schema already current");
+        drop(reopened);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,160 @@
+use crate::errors::{AppError, DbError};
+use crate::models::{Comparison, GoalMetric, MetricGoal, MetricGoalInput};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+const GOAL_COLUMNS: &str = "id, metric, comparison, target, deadline, created_at";
+
+fn unknown(idx: usize, kind: &str, value: &str) -> rusqlite::Error {
+    rusqlite::Error::FromSqlConversionFailure(
+        idx,
+        Type::Text,
+        format!("Unknown goal {}: {}", kind, value).into(),
+    )
+}
+
+fn map_goal_row(row: &Row) -> rusqlite::Result<MetricGoal> {
+    let metric: String = row.get(1)?;
+    let comparison: String = row.get(2)?;
+
+    Ok(MetricGoal {
+        id: row.get(0)?,
+        metric: GoalMetric::parse(&metric).ok_or_else(|| unknown(1, "metric", &metric))?,
+        op: Comparison::from_symbol(&comparison)
+            .ok_or_else(|| unknown(2, "comparison", &comparison))?,
+        target: row.get(3)?,
+        deadline: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+pub fn create_metric_goal(
+    conn: &Connection,
+    input: &MetricGoalInput,
+) -> Result<MetricGoal, AppError> {
+    conn.execute(
+        "INSERT INTO metric_goals (metric, comparison, target, deadline, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            input.metric.as_str(),
+            input.op.symbol(),
+            input.target,
+            input.deadline,
+            chrono::Utc::now().to_rfc3339(),
+        ],
+    )
+    .map_err(DbError::from)?;
+
+    get_metric_goal(conn, conn.last_insert_rowid())?
+        .ok_or_else(|| AppError::Internal("Inserted goal not found".to_string()))
+}
+
+pub fn get_metric_goal(conn: &Connection, id: i64) -> Result<Option<MetricGoal>, AppError> {
+    conn.query_row(
+        &format!("SELECT {} FROM metric_goals WHERE id = ?1", GOAL_COLUMNS),
+        params![id],
+        map_goal_row,
+    )
+    .optional()
+    .map_err(|e| DbError::from(e).into())
+}
+
+/// Every goal, nearest deadline first.
+pub fn list_metric_goals(conn: &Connection) -> Result<Vec<MetricGoal>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM metric_goals ORDER BY deadline, id",
+            GOAL_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+
+    let goals = stmt
+        .query_map([], map_goal_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(goals)
+}
+
+pub fn update_metric_goal(
+    conn: &Connection,
+    id: i64,
+    input: &MetricGoalInput,
+) -> Result<Option<MetricGoal>, AppError> {
+    conn.execute(
+        "UPDATE metric_goals SET metric = ?2, comparison = ?3, target = ?4, deadline = ?5
+         WHERE id = ?1",
+        params![
+            id,
+            input.metric.as_str(),
+            input.op.symbol(),
+            input.target,
+            input.deadline
+        ],
+    )
+    .map_err(DbError::from)?;
+
+    get_metric_goal(conn, id)
+}
+
+pub fn delete_metric_goal(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute("DELETE FROM metric_goals WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+
+    #[test]
+    fn goals_round_trip_nearest_deadline_first() {
+        let conn = setup_db();
+        let input = |metric, op, deadline: &str| MetricGoalInput {
+            metric,
+            op,
+            target: 8.0,
+            deadline: deadline.to_string(),
+        };
+
+        let later = create_metric_goal(
+            &conn,
+            &input(
+                GoalMetric::MedianResolutionHours,
+                Comparison::Lt,
+                "2025-09-30",
+            ),
+        )
+        .expect("goal");
+        let sooner = create_metric_goal(
+            &conn,
+            &input(GoalMetric::OpenTickets, Comparison::Lte, "2025-06-30"),
+        )
+        .expect("goal");
+        assert_eq!(later.metric, GoalMetric::MedianResolutionHours);
+        assert_eq!(later.op, Comparison::Lt);
+
+        let updated = update_metric_goal(
+            &conn,
+            sooner.id,
+            &input(GoalMetric::StaleTickets, Comparison::Eq, "2025-12-31"),
+        )
+        .expect("update")
+        .expect("goal exists");
+        assert_eq!(updated.metric, GoalMetric::StaleTickets);
+        assert_eq!(updated.op, Comparison::Eq);
+
+        let ids: Vec<i64> = list_metric_goals(&conn)
+            .expect("goals")
+            .iter()
+            .map(|goal| goal.id)
+            .collect();
+        assert_eq!(ids, vec![later.id, sooner.id]);
+
+        assert!(delete_metric_goal(&conn, later.id).expect("delete"));
+        assert!(!delete_metric_goal(&conn, later.id).expect("delete"));
+    }
+}
@@ -0,0 +1,67 @@
+use crate::errors::{AppError, DbError};
+use crate::models::CachedIcon;
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub fn get_cached_icon(conn: &Connection, url: &str) -> Result<Option<CachedIcon>, AppError> {
+    conn.query_row(
+        "SELECT url, file_name, content_type, etag, fetched_at FROM icon_cache WHERE url = ?1",
+        params![url],
+        |row| {
+            Ok(CachedIcon {
+                url: row.get(0)?,
+                file_name: row.get(1)?,
+                content_type: row.get(2)?,
+                etag: row.get(3)?,
+                fetched_at: row.get(4)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| DbError::from(e).into())
+}
+
+pub fn save_cached_icon(conn: &Connection, icon: &CachedIcon) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT INTO icon_cache (url, file_name, content_type, etag, fetched_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(url) DO UPDATE SET
+             file_name = excluded.file_name,
+             content_type = excluded.content_type,
+             etag = excluded.etag,
+             fetched_at = excluded.fetched_at",
+        params![
+            icon.url,
+            icon.file_name,
+            icon.content_type,
+            icon.etag,
+            icon.fetched_at
+        ],
+    )
+    .map_err(DbError::from)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+
+    #[test]
+    fn refetched_icons_replace_their_cache_entry() {
+        let conn = setup_db();
+        let url = "https://example.atlassian.net/avatar/1";
+        let mut icon = CachedIcon {
+            url: url.to_string(),
+            file_name: "0123.png".to_string(),
+            content_type: "image/png".to_string(),
+            etag: Some("\"v1\"".to_string()),
+            fetched_at: "2025-01-06T09:00:00Z".to_string(),
+        };
+        save_cached_icon(&conn, &icon).expect("save");
+        icon.etag = Some("\"v2\"".to_string());
+        save_cached_icon(&conn, &icon).expect("save");
+
+        assert_eq!(get_cached_icon(&conn, url).expect("icon"), Some(icon));
+        assert_eq!(get_cached_icon(&conn, "https://other").expect("icon"), None);
+    }
+}
@@ -0,0 +1,271 @@
+use super::queries::{get_ticket_by_key, upsert_ticket, UpsertOutcome};
+use super::ticket_changes::record_ticket_changes;
+use crate::errors::{AppError, DbError};
+use crate::models::{ConflictPolicy, LocalChange, LocalField, Ticket};
+use rusqlite::{params, Connection};
+
+/// Marks `field` on `key` as edited locally. `base_value` is Jira's value
+/// before the edit; a ticket already dirty keeps its original base.
+pub fn record_local_change(
+    conn: &Connection,
+    key: &str,
+    field: LocalField,
+    base_value: Option<&str>,
+    changed_at: &str,
+) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT INTO local_changes (ticket_key, field, base_value, changed_at)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(ticket_key, field) DO UPDATE SET changed_at = excluded.changed_at",
+        params![key, field.as_str(), base_value, changed_at],
+    )
+    .map_err(DbError::from)?;
+    Ok(())
+}
+
+pub fn clear_local_change(conn: &Connection, key: &str, field: LocalField) -> Result<(), AppError> {
+    conn.execute(
+        "DELETE FROM local_changes WHERE ticket_key = ?1 AND field = ?2",
+        params![key, field.as_str()],
+    )
+    .map_err(DbError::from)?;
+    Ok(())
+}
+
+/// Keeps a flagged local edit over Jira's conflicting value; later syncs
+/// treat that value as the new base.
+pub fn keep_local_change(conn: &Connection, key: &str, field: LocalField) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE local_changes SET base_value = conflicting_value, conflicting_value = NULL
+         WHERE ticket_key = ?1 AND field = ?2 AND conflicting_value IS NOT NULL",
+        params![key, field.as_str()],
+    )
+    .map_err(DbError::from)?;
+    Ok(())
+}
+
+/// Every local edit Jira hasn't confirmed, conflicts first, then oldest first.
+pub fn get_pending_local_changes(conn: &Connection) -> Result<Vec<LocalChange>, AppError> {
+    query_local_changes(conn, None)
+}
+
+fn query_local_changes(conn: &Connection, key: Option<&str>) -> Result<Vec<LocalChange>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT c.ticket_key, c.field, c.base_value, c.conflicting_value, c.changed_at,
+                    t.status
+             FROM local_changes c
+             LEFT JOIN tickets t ON t.jira_key = c.ticket_key
+             WHERE ?1 IS NULL OR c.ticket_key = ?1
+             ORDER BY c.conflicting_value IS NULL, c.changed_at, c.ticket_key",
+        )
+        .map_err(DbError::from)?;
+    let rows = stmt
+        .query_map(params![key], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get::<_, Option<String>>(5)?,
+            ))
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    // Rows for fields this build doesn't know are left alone
+    Ok(rows
+        .into_iter()
+        .filter_map(
+            |(ticket_key, field, base_value, conflicting_value, changed_at, status)| {
+                let field = LocalField::parse(&field)?;
+                let local_value = match field {
+                    LocalField::Status => status,
+                };
+                Some(LocalChange {
+                    ticket_key,
+                    field,
+                    local_value,
+                    base_value,
+                    conflicting_value,
+                    changed_at,
+                })
+            },
+        )
+        .collect())
+}
+
+/// Stores a ticket fetched from the source, applying `policy` to fields
+/// with unconfirmed local edits. An edit Jira now agrees with is cleared
+/// under every policy. Fields an update changes are journaled in
+/// `ticket_changes` against `sync_run_id`.
+pub fn upsert_synced_ticket(
+    conn: &Connection,
+    ticket: &Ticket,
+    policy: ConflictPolicy,
+    sync_run_id: Option<i64>,
+) -> Result<UpsertOutcome, AppError> {
+    let Some(existing) = get_ticket_by_key(conn, &ticket.jira_key)? else {
+        return upsert_ticket(conn, ticket);
+    };
+    let merged = merge_local_changes(conn, &existing, ticket, policy)?;
+
+    let outcome = upsert_ticket(conn, &merged)?;
+    if outcome == UpsertOutcome::Updated {
+        if let Some(stored) = get_ticket_by_key(conn, &ticket.jira_key)? {
+            let now = chrono::Utc::now().to_rfc3339();
+            record_ticket_changes(conn, &existing, &stored, sync_run_id, &now)?;
+        }
+    }
+    Ok(outcome)
+}
+
+/// `ticket` with `policy` applied to the fields edited locally on `existing`.
+fn merge_local_changes(
+    conn: &Connection,
+    existing: &Ticket,
+    ticket: &Ticket,
+    policy: ConflictPolicy,
+) -> Result<Ticket, AppError> {
+    let changes = query_local_changes(conn, Some(&ticket.jira_key))?;
+    let mut merged = ticket.clone();
+    for change in changes {
+        let server_value = change.field.value(ticket);
+        if server_value == change.local_value.as_deref() || policy == ConflictPolicy::ServerWins {
+            clear_local_change(conn, &ticket.jira_key, change.field)?;
+            continue;
+        }
+
+        if policy == ConflictPolicy::Prompt && server_value != change.base_value.as_deref() {
+            conn.execute(
+                "UPDATE local_changes SET conflicting_value = ?3
+                 WHERE ticket_key = ?1 AND field = ?2",
+                params![ticket.jira_key, change.field.as_str(), server_value],
+            )
+            .map_err(DbError::from)?;
+        }
+        change.field.apply(existing, &mut merged);
+    }
+
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{get_ticket_change_log, update_ticket_status};
+
+    fn setup() -> Connection {
+        let conn = setup_db();
+        upsert_ticket(&conn, &server_ticket("Open")).expect("ticket inserted");
+        // Resolved locally while Jira still says Open
+        update_ticket_status(&conn, "OPS-1", "Done", Some("2025-01-02T09:00:00Z"))
+            .expect("local edit");
+        record_local_change(
+            &conn,
+            "OPS-1",
+            LocalField::Status,
+            Some("Open"),
+            "2025-01-02T09:00:00Z",
+        )
+        .expect("change recorded");
+        conn
+    }
+
+    fn server_ticket(status: &str) -> Ticket {
+        Ticket {
+            status: status.to_string(),
+            created_at: "2025-01-01T09:00:00Z".to_string(),
+            updated_at: "2025-01-03T09:00:00Z".to_string(),
+            ..test_support::ticket("OPS-1")
+        }
+    }
+
+    fn stored_status(conn: &Connection) -> String {
+        get_ticket_by_key(conn, "OPS-1")
+            .expect("lookup")
+            .expect("ticket exists")
+            .status
+    }
+
+    #[test]
+    fn server_wins_drops_the_local_edit() {
+        let conn = setup();
+
+        upsert_synced_ticket(
+            &conn,
+            &server_ticket("Open"),
+            ConflictPolicy::ServerWins,
+            None,
+        )
+        .expect("synced");
+
+        assert_eq!(stored_status(&conn), "Open");
+        assert!(get_pending_local_changes(&conn)
+            .expect("changes")
+            .is_empty());
+        let log = get_ticket_change_log(&conn, "OPS-1").expect("change log");
+        let fields: Vec<&str> = log.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["status", "resolved_at"]);
+        assert_eq!(log[0].old_value, serde_json::json!("Done"));
+    }
+
+    #[test]
+    fn local_wins_keeps_the_edit_until_jira_agrees() {
+        let conn = setup();
+
+        upsert_synced_ticket(
+            &conn,
+            &server_ticket("Open"),
+            ConflictPolicy::LocalWins,
+            None,
+        )
+        .expect("synced");
+        assert_eq!(stored_status(&conn), "Done");
+        let pending = get_pending_local_changes(&conn).expect("changes");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].local_value.as_deref(), Some("Done"));
+        assert!(pending[0].conflicting_value.is_none());
+
+        upsert_synced_ticket(
+            &conn,
+            &server_ticket("Done"),
+            ConflictPolicy::LocalWins,
+            None,
+        )
+        .expect("synced");
+        assert!(get_pending_local_changes(&conn)
+            .expect("changes")
+            .is_empty());
+    }
+
+    #[test]
+    fn prompt_flags_fields_jira_changed_since_the_edit() {
+        let conn = setup();
+
+        upsert_synced_ticket(&conn, &server_ticket("Open"), ConflictPolicy::Prompt, None)
+            .expect("synced");
+        assert!(get_pending_local_changes(&conn).expect("changes")[0]
+            .conflicting_value
+            .is_none());
+
+        upsert_synced_ticket(
+            &conn,
+            &server_ticket("Blocked"),
+            ConflictPolicy::Prompt,
+            None,
+        )
+        .expect("synced");
+        assert_eq!(stored_status(&conn), "Done");
+        let pending = get_pending_local_changes(&conn).expect("changes");
+        assert_eq!(pending[0].conflicting_value.as_deref(), Some("Blocked"));
+
+        keep_local_change(&conn, "OPS-1", LocalField::Status).expect("kept");
+        let pending = get_pending_local_changes(&conn).expect("changes");
+        assert_eq!(pending[0].base_value.as_deref(), Some("Blocked"));
+        assert!(pending[0].conflicting_value.is_none());
+    }
+}
@@ -0,0 +1,248 @@
+use crate::errors::{AppError, DbError};
+use crate::models::{FollowUp, IncidentFollowUps, LinkedTicket, LocalLinkKind};
+use rusqlite::{params, Connection};
+use std::collections::BTreeMap;
+
+/// Links `source` to `target` as `kind`, e.g. a task as a follow-up of an
+/// incident. Both tickets must be stored. Returns whether the link is new.
+pub fn link_tickets(
+    conn: &Connection,
+    source: &str,
+    target: &str,
+    kind: LocalLinkKind,
+    now: &str,
+) -> Result<bool, AppError> {
+    if source == target {
+        return Err(AppError::Config(format!(
+            "Cannot link {} to itself",
+            source
+        )));
+    }
+    for key in [source, target] {
+        let stored: bool = conn
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM tickets WHERE jira_key = ?1)",
+                params![key],
+                |row| row.get(0),
+            )
+            .map_err(DbError::from)?;
+        if !stored {
+            return Err(AppError::Config(format!("Unknown ticket: {}", key)));
+        }
+    }
+
+    let added = conn
+        .execute(
+            "INSERT OR IGNORE INTO local_links (source_key, target_key, kind, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![source, target, kind.as_str(), now],
+        )
+        .map_err(DbError::from)?;
+    Ok(added > 0)
+}
+
+pub fn unlink_tickets(
+    conn: &Connection,
+    source: &str,
+    target: &str,
+    kind: LocalLinkKind,
+) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute(
+            "DELETE FROM local_links WHERE source_key = ?1 AND target_key = ?2 AND kind = ?3",
+            params![source, target, kind.as_str()],
+        )
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+/// Tickets locally linked to `key` in either direction, with the relation
+/// read from `key`'s side.
+pub(crate) fn get_locally_linked_tickets(
+    conn: &Connection,
+    key: &str,
+) -> Result<Vec<LinkedTicket>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT other_key, kind, outward, t.summary, t.status, t.resolved_at IS NOT NULL
+             FROM (
+                 SELECT target_key AS other_key, kind, 1 AS outward
+                 FROM local_links WHERE source_key = ?1
+                 UNION
+                 SELECT source_key, kind, 0 FROM local_links WHERE target_key = ?1
+             )
+             LEFT JOIN tickets t ON t.jira_key = other_key",
+        )
+        .map_err(DbError::from)?;
+    let rows = stmt
+        .query_map(params![key], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, bool>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, bool>(5)?,
+            ))
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(jira_key, kind, outward, summary, status, resolved)| {
+            let kind = LocalLinkKind::parse(&kind)?;
+            Some(LinkedTicket {
+                jira_key,
+                link_type: kind.as_str().to_string(),
+                relation: if outward {
+                    kind.outward_label()
+                } else {
+                    kind.inward_label()
+                }
+                .to_string(),
+                summary,
+                status,
+                resolved,
+                local: true,
+            })
+        })
+        .collect())
+}
+
+/// Incidents with at least one unresolved follow-up, most open follow-ups
+/// first. Follow-ups that are no longer stored aren't counted.
+pub fn get_open_follow_ups(conn: &Connection) -> Result<Vec<IncidentFollowUps>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT l.target_key, i.summary, i.resolved_at IS NOT NULL,
+                    f.jira_key, f.summary, f.status, f.assignee, f.resolved_at IS NULL
+             FROM local_links l
+             JOIN tickets f ON f.jira_key = l.source_key
+             LEFT JOIN tickets i ON i.jira_key = l.target_key
+             WHERE l.kind = ?1
+             ORDER BY l.target_key, f.created_at, f.jira_key",
+        )
+        .map_err(DbError::from)?;
+    let rows = stmt
+        .query_map(params![LocalLinkKind::FollowUpOf.as_str()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, bool>(2)?,
+                FollowUp {
+                    jira_key: row.get(3)?,
+                    summary: row.get(4)?,
+                    status: row.get(5)?,
+                    assignee: row.get(6)?,
+                },
+                row.get::<_, bool>(7)?,
+            ))
+        })
+        .map_err(DbError::from)?;
+
+    let mut incidents: BTreeMap<String, IncidentFollowUps> = BTreeMap::new();
+    for row in rows {
+        let (incident_key, incident_summary, incident_resolved, follow_up, open) =
+            row.map_err(DbError::from)?;
+        let incident = incidents
+            .entry(incident_key.clone())
+            .or_insert_with(|| IncidentFollowUps {
+                incident_key,
+                incident_summary,
+                incident_resolved,
+                total_follow_ups: 0,
+                open_follow_ups: Vec::new(),
+            });
+        incident.total_follow_ups += 1;
+        if open {
+            incident.open_follow_ups.push(follow_up);
+        }
+    }
+
+    let mut incidents: Vec<IncidentFollowUps> = incidents
+        .into_values()
+        .filter(|incident| !incident.open_follow_ups.is_empty())
+        .collect();
+    incidents.sort_by(|a, b| {
+        b.open_follow_ups
+            .len()
+            .cmp(&a.open_follow_ups.len())
+            .then_with(|| a.incident_key.cmp(&b.incident_key))
+    });
+    Ok(incidents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{get_linked_tickets, upsert_ticket};
+    use crate::models::Ticket;
+
+    const NOW: &str = "2025-01-08T09:00:00+00:00";
+
+    fn ticket(key: &str, resolved_at: Option<&str>) -> Ticket {
+        Ticket {
+            created_at: "2025-01-06T09:00:00+00:00".to_string(),
+            updated_at: "2025-01-06T09:00:00+00:00".to_string(),
+            ..test_support::ticket_resolved_at(key, resolved_at)
+        }
+    }
+
+    #[test]
+    fn incidents_list_their_open_follow_ups() {
+        let conn = setup_db();
+        let done = Some("2025-01-07T09:00:00+00:00");
+        for (key, resolved_at) in [
+            ("INC-1", done),
+            ("INC-2", done),
+            ("OPS-1", None),
+            ("OPS-2", done),
+            ("OPS-3", done),
+            ("CHG-1", done),
+        ] {
+            upsert_ticket(&conn, &ticket(key, resolved_at)).expect("ticket");
+        }
+
+        let follow_up = LocalLinkKind::FollowUpOf;
+        assert!(link_tickets(&conn, "OPS-1", "INC-1", follow_up, NOW).expect("link"));
+        assert!(!link_tickets(&conn, "OPS-1", "INC-1", follow_up, NOW).expect("link"));
+        link_tickets(&conn, "OPS-2", "INC-1", follow_up, NOW).expect("link");
+        link_tickets(&conn, "OPS-3", "INC-2", follow_up, NOW).expect("link");
+        link_tickets(&conn, "INC-1", "CHG-1", LocalLinkKind::CausedBy, NOW).expect("link");
+        assert!(link_tickets(&conn, "OPS-1", "OPS-1", follow_up, NOW).is_err());
+        assert!(link_tickets(&conn, "OPS-1", "INC-9", follow_up, NOW).is_err());
+
+        let open = get_open_follow_ups(&conn).expect("follow-ups");
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].incident_key, "INC-1");
+        assert!(open[0].incident_resolved);
+        assert_eq!(open[0].total_follow_ups, 2);
+        let keys: Vec<&str> = open[0]
+            .open_follow_ups
+            .iter()
+            .map(|f| f.jira_key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["OPS-1"]);
+
+        let linked = get_linked_tickets(&conn, "INC-1").expect("linked");
+        let relations: Vec<(&str, &str)> = linked
+            .iter()
+            .map(|l| (l.jira_key.as_str(), l.relation.as_str()))
+            .collect();
+        assert_eq!(
+            relations,
+            vec![
+                ("CHG-1", "was caused by"),
+                ("OPS-1", "has follow-up"),
+                ("OPS-2", "has follow-up"),
+            ]
+        );
+        assert!(linked.iter().all(|l| l.local));
+
+        assert!(unlink_tickets(&conn, "OPS-1", "INC-1", follow_up).expect("unlink"));
+        assert!(get_open_follow_ups(&conn).expect("follow-ups").is_empty());
+    }
+}
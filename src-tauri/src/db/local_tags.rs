@@ -0,0 +1,162 @@
+use super::queries::tickets_table;
+use crate::errors::{AppError, DbError};
+use crate::models::{CountEntry, TicketFilter};
+use rusqlite::{params, params_from_iter, Connection};
+
+/// Adds `tag` to every ticket in `jira_keys`; returns how many were newly tagged.
+pub fn tag_tickets(conn: &Connection, jira_keys: &[String], tag: &str) -> Result<usize, AppError> {
+    let tag = normalize_tag(tag)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+
+    let mut added = 0;
+    for key in jira_keys {
+        added += tx
+            .execute(
+                "INSERT OR IGNORE INTO local_tags (ticket_key, tag, created_at) VALUES (?1, ?2, ?3)",
+                params![key, tag, now],
+            )
+            .map_err(DbError::from)?;
+    }
+
+    tx.commit().map_err(DbError::from)?;
+    Ok(added)
+}
+
+/// Removes `tag` from every ticket in `jira_keys`; returns how many were untagged.
+pub fn untag_tickets(
+    conn: &Connection,
+    jira_keys: &[String],
+    tag: &str,
+) -> Result<usize, AppError> {
+    let tag = normalize_tag(tag)?;
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+
+    let mut removed = 0;
+    for key in jira_keys {
+        removed += tx
+            .execute(
+                "DELETE FROM local_tags WHERE ticket_key = ?1 AND tag = ?2",
+                params![key, tag],
+            )
+            .map_err(DbError::from)?;
+    }
+
+    tx.commit().map_err(DbError::from)?;
+    Ok(removed)
+}
+
+pub fn get_ticket_tags(conn: &Connection, jira_key: &str) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT tag FROM local_tags WHERE ticket_key = ?1 ORDER BY tag")
+        .map_err(DbError::from)?;
+
+    let tags = stmt
+        .query_map(params![jira_key], |row| row.get(0))
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(tags)
+}
+
+/// Ticket count per local tag among tickets matching `filter`. Tags on
+/// tickets that are no longer stored locally are not counted.
+pub fn get_count_by_local_tag(
+    conn: &Connection,
+    filter: &TicketFilter,
+) -> Result<Vec<CountEntry>, AppError> {
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let query = format!(
+        "SELECT lt.tag, COUNT(*) as count FROM local_tags lt \
+         JOIN {table} ON tickets.jira_key = lt.ticket_key \
+         WHERE {} GROUP BY lt.tag ORDER BY count DESC, lt.tag",
+        clause
+    );
+
+    let mut stmt = conn.prepare(&query).map_err(DbError::from)?;
+    let entries = stmt
+        .query_map(params_from_iter(values), |row| {
+            Ok(CountEntry {
+                name: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(entries)
+}
+
+fn normalize_tag(tag: &str) -> Result<&str, AppError> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err(AppError::Config("Tag cannot be empty".to_string()));
+    }
+    Ok(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support;
+    use crate::db::{get_tickets, upsert_ticket};
+
+    fn seeded_db() -> Connection {
+        let conn = test_support::setup_db();
+        for key in ["OPS-1", "OPS-2", "OPS-3"] {
+            upsert_ticket(&conn, &test_support::ticket(key)).expect("insert ticket");
+        }
+        conn
+    }
+
+    fn keys(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|k| k.to_string()).collect()
+    }
+
+    #[test]
+    fn bulk_tagging_is_idempotent() {
+        let conn = seeded_db();
+        assert_eq!(
+            tag_tickets(&conn, &keys(&["OPS-1", "OPS-2"]), " follow-up ").expect("tag"),
+            2
+        );
+        assert_eq!(
+            tag_tickets(&conn, &keys(&["OPS-2", "OPS-3"]), "follow-up").expect("tag"),
+            1
+        );
+        assert_eq!(
+            get_ticket_tags(&conn, "OPS-2").expect("tags"),
+            vec!["follow-up"]
+        );
+
+        assert_eq!(
+            untag_tickets(&conn, &keys(&["OPS-1", "OPS-9"]), "follow-up").expect("untag"),
+            1
+        );
+        assert!(tag_tickets(&conn, &keys(&["OPS-1"]), "  ").is_err());
+    }
+
+    #[test]
+    fn tags_filter_and_aggregate() {
+        let conn = seeded_db();
+        tag_tickets(&conn, &keys(&["OPS-1", "OPS-2"]), "follow-up").expect("tag");
+        tag_tickets(&conn, &keys(&["OPS-2"]), "blocked-on-vendor").expect("tag");
+
+        let filter = TicketFilter {
+            tags: vec!["blocked-on-vendor".to_string()],
+            ..Default::default()
+        };
+        let tickets = get_tickets(&conn, &filter).expect("tickets");
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets[0].jira_key, "OPS-2");
+
+        let counts = get_count_by_local_tag(&conn, &TicketFilter::default()).expect("counts");
+        assert_eq!(counts[0].name, "follow-up");
+        assert_eq!(counts[0].count, 2);
+        assert_eq!(counts[1].name, "blocked-on-vendor");
+        assert_eq!(counts[1].count, 1);
+    }
+}
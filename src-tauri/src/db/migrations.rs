@@ -1,16 +1,23 @@
+use super::queries::{refresh_resolution_hours, RefreshScope};
+use super::support_calendars::ProjectSchedules;
 use crate::errors::{AppError, DbError};
 use rusqlite::Connection;
 
-const SCHEMA_VERSION: i32 = 1;
+pub(crate) const SCHEMA_VERSION: i32 = 54;
 
 pub fn initialize_database(conn: &Connection) -> Result<(), AppError> {
-    let current_version = get_schema_version(conn)?;
+    let mut current_version = get_schema_version(conn)?;
 
     if current_version == 0 {
-        create_schema_v1(conn)?;
-        set_schema_version(conn, SCHEMA_VERSION)?;
-    } else if current_version < SCHEMA_VERSION {
-        migrate_schema(conn, current_version)?;
+        let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+        create_schema_v1(&tx)?;
+        set_schema_version(&tx, 1)?;
+        tx.commit().map_err(DbError::from)?;
+        current_version = 1;
+    }
+
+    if current_version < SCHEMA_VERSION {
+        migrate_schema(conn, current_version, SCHEMA_VERSION)?;
     }
 
     Ok(())
@@ -66,7 +73,1154 @@ fn create_schema_v1(conn: &Connection) -> Result<(), AppError> {
     Ok(())
 }
 
-fn migrate_schema(_conn: &Connection, _from_version: i32) -> Result<(), AppError> {
-    // Future migrations will go here
+type Migration = fn(&Connection) -> Result<(), AppError>;
+
+/// Every migration in order; entry `i` brings the schema to version `i + 2`.
+const MIGRATIONS: [Migration; SCHEMA_VERSION as usize - 1] = [
+    migrate_to_v2,
+    migrate_to_v3,
+    migrate_to_v4,
+    migrate_to_v5,
+    migrate_to_v6,
+    migrate_to_v7,
+    migrate_to_v8,
+    migrate_to_v9,
+    migrate_to_v10,
+    migrate_to_v11,
+    migrate_to_v12,
+    migrate_to_v13,
+    migrate_to_v14,
+    migrate_to_v15,
+    migrate_to_v16,
+    migrate_to_v17,
+    migrate_to_v18,
+    migrate_to_v19,
+    migrate_to_v20,
+    migrate_to_v21,
+    migrate_to_v22,
+    migrate_to_v23,
+    migrate_to_v24,
+    migrate_to_v25,
+    migrate_to_v26,
+    migrate_to_v27,
+    migrate_to_v28,
+    migrate_to_v29,
+    migrate_to_v30,
+    migrate_to_v31,
+    migrate_to_v32,
+    migrate_to_v33,
+    migrate_to_v34,
+    migrate_to_v35,
+    migrate_to_v36,
+    migrate_to_v37,
+    migrate_to_v38,
+    migrate_to_v39,
+    migrate_to_v40,
+    migrate_to_v41,
+    migrate_to_v42,
+    migrate_to_v43,
+    migrate_to_v44,
+    migrate_to_v45,
+    migrate_to_v46,
+    migrate_to_v47,
+    migrate_to_v48,
+    migrate_to_v49,
+    migrate_to_v50,
+    migrate_to_v51,
+    migrate_to_v52,
+    migrate_to_v53,
+    migrate_to_v54,
+];
+
+/// Migrates from `from_version` up to `to_version`. Each step commits
+/// together with its `user_version`, so a failed step leaves the database
+/// at the last version that completed.
+fn migrate_schema(conn: &Connection, from_version: i32, to_version: i32) -> Result<(), AppError> {
+    for version in from_version + 1..=to_version {
+        let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+        MIGRATIONS[version as usize - 2](&tx)?;
+        set_schema_version(&tx, version)?;
+        tx.commit().map_err(DbError::from)?;
+    }
+
+    Ok(())
+}
+
+/// v2: tickets remember which connector they came from.
+fn migrate_to_v2(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tickets ADD COLUMN source TEXT NOT NULL DEFAULT 'jira';
+
+        CREATE INDEX IF NOT EXISTS idx_tickets_source ON tickets(source);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v2: {}", e)))?;
+
+    Ok(())
+}
+
+/// v3: free-form per-source fields (JSON object) that have no canonical column.
+fn migrate_to_v3(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tickets ADD COLUMN custom_fields TEXT NOT NULL DEFAULT '{}';
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v3: {}", e)))?;
+
+    Ok(())
+}
+
+/// v4: multiple Jira accounts, with tickets tagged by the account they came from.
+fn migrate_to_v4(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS jira_accounts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            base_url TEXT NOT NULL,
+            email TEXT NOT NULL,
+            -- 'basic' (email and API token) or 'pat' (Data Center personal access token)
+            auth_method TEXT NOT NULL DEFAULT 'basic',
+            created_at TEXT NOT NULL
+        );
+
+        ALTER TABLE tickets ADD COLUMN account_id INTEGER;
+
+        CREATE INDEX IF NOT EXISTS idx_tickets_account ON tickets(account_id);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v4: {}", e)))?;
+
+    Ok(())
+}
+
+/// v5: local-only ticket tags, independent of Jira labels.
+fn migrate_to_v5(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS local_tags (
+            ticket_key TEXT NOT NULL,
+            tag TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (ticket_key, tag)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_local_tags_tag ON local_tags(tag);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v5: {}", e)))?;
+
+    Ok(())
+}
+
+/// v6: pinned tickets with the field values last acknowledged by the user.
+fn migrate_to_v6(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS watched_tickets (
+            ticket_key TEXT PRIMARY KEY,
+            pinned_at TEXT NOT NULL,
+            seen_status TEXT,
+            seen_assignee TEXT,
+            seen_priority TEXT,
+            changed_at TEXT
+        );
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v6: {}", e)))?;
+
+    Ok(())
+}
+
+/// v7: record of fired alerts so each one is only delivered once.
+fn migrate_to_v7(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS alert_log (
+            alert_key TEXT PRIMARY KEY,
+            fired_at TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v7: {}", e)))?;
+
+    Ok(())
+}
+
+/// v8: user-defined alert rules evaluated after each sync.
+fn migrate_to_v8(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS alert_rules (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            condition TEXT NOT NULL,
+            channel TEXT NOT NULL DEFAULT 'desktop',
+            throttle_minutes INTEGER NOT NULL DEFAULT 60,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            last_fired_at TEXT,
+            created_at TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v8: {}", e)))?;
+
+    Ok(())
+}
+
+/// v9: named dashboard views (filter, sort, granularity)
+fn migrate_to_v9(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS saved_views (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            filter TEXT NOT NULL,
+            sort_column TEXT,
+            sort_descending INTEGER NOT NULL DEFAULT 0,
+            granularity TEXT NOT NULL DEFAULT 'month',
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v9: {}", e)))?;
+
+    Ok(())
+}
+
+/// v10: named dashboards with their widget layout
+fn migrate_to_v10(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS dashboards (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            layout TEXT NOT NULL DEFAULT '[]',
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v10: {}", e)))?;
+
+    Ok(())
+}
+
+/// v11: frozen weekly dashboard snapshots
+fn migrate_to_v11(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS report_snapshots (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            period TEXT NOT NULL,
+            taken_at TEXT NOT NULL,
+            data TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_report_snapshots_period ON report_snapshots(period);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v11: {}", e)))?;
+
+    Ok(())
+}
+
+/// v12: summary stats recorded after each sync
+fn migrate_to_v12(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS stats_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            recorded_at TEXT NOT NULL,
+            total_tickets INTEGER NOT NULL,
+            open_tickets INTEGER NOT NULL,
+            resolved_tickets INTEGER NOT NULL,
+            stale_tickets INTEGER NOT NULL,
+            avg_resolution_hours REAL NOT NULL,
+            median_resolution_hours REAL NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_stats_history_recorded_at ON stats_history(recorded_at);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v12: {}", e)))?;
+
+    Ok(())
+}
+
+/// v13: tickets.duplicate_of and the duplicate_candidates table
+fn migrate_to_v13(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tickets ADD COLUMN duplicate_of TEXT;
+
+        CREATE TABLE IF NOT EXISTS duplicate_candidates (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            ticket_a TEXT NOT NULL,
+            ticket_b TEXT NOT NULL,
+            similarity REAL NOT NULL,
+            status TEXT NOT NULL DEFAULT 'pending',
+            detected_at TEXT NOT NULL,
+            resolved_at TEXT,
+            UNIQUE(ticket_a, ticket_b)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_duplicate_candidates_status ON duplicate_candidates(status);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v13: {}", e)))?;
+
+    Ok(())
+}
+
+/// v14: ticket_links table
+fn migrate_to_v14(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS ticket_links (
+            outward_key TEXT NOT NULL,
+            inward_key TEXT NOT NULL,
+            link_type TEXT NOT NULL,
+            outward_label TEXT NOT NULL,
+            inward_label TEXT NOT NULL,
+            PRIMARY KEY (outward_key, inward_key, link_type)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_ticket_links_inward_key ON ticket_links(inward_key);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v14: {}", e)))?;
+
+    Ok(())
+}
+
+/// v15: story points and sprint columns on tickets
+fn migrate_to_v15(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tickets ADD COLUMN story_points REAL;
+        ALTER TABLE tickets ADD COLUMN sprint TEXT;
+
+        CREATE INDEX IF NOT EXISTS idx_tickets_sprint ON tickets(sprint);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v15: {}", e)))?;
+
+    Ok(())
+}
+
+/// v16: due_date column on tickets
+fn migrate_to_v16(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tickets ADD COLUMN due_date TEXT;
+
+        CREATE INDEX IF NOT EXISTS idx_tickets_due_date ON tickets(due_date);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v16: {}", e)))?;
+
+    Ok(())
+}
+
+/// v17: ticket_components table
+fn migrate_to_v17(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS ticket_components (
+            ticket_key TEXT NOT NULL,
+            component TEXT NOT NULL,
+            PRIMARY KEY (ticket_key, component)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_ticket_components_component ON ticket_components(component);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v17: {}", e)))?;
+
+    Ok(())
+}
+
+/// v18: ticket_fix_versions table
+fn migrate_to_v18(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS ticket_fix_versions (
+            ticket_key TEXT NOT NULL,
+            version TEXT NOT NULL,
+            PRIMARY KEY (ticket_key, version)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_ticket_fix_versions_version ON ticket_fix_versions(version);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v18: {}", e)))?;
+
+    Ok(())
+}
+
+/// v19: attachments table
+fn migrate_to_v19(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS attachments (
+            id TEXT PRIMARY KEY,
+            ticket_key TEXT NOT NULL,
+            filename TEXT NOT NULL,
+            size_bytes INTEGER NOT NULL DEFAULT 0,
+            mime_type TEXT,
+            author TEXT,
+            created_at TEXT,
+            content_url TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_attachments_ticket_key ON attachments(ticket_key);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v19: {}", e)))?;
+
+    Ok(())
+}
+
+/// v20: local_changes table
+fn migrate_to_v20(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS local_changes (
+            ticket_key TEXT NOT NULL,
+            field TEXT NOT NULL,
+            base_value TEXT,
+            conflicting_value TEXT,
+            changed_at TEXT NOT NULL,
+            PRIMARY KEY (ticket_key, field)
+        );
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v20: {}", e)))?;
+
+    Ok(())
+}
+
+/// v21: outbox table
+fn migrate_to_v21(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS outbox (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            ticket_key TEXT NOT NULL,
+            action TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'pending',
+            attempts INTEGER NOT NULL DEFAULT 0,
+            last_error TEXT,
+            created_at TEXT NOT NULL,
+            last_attempt_at TEXT,
+            next_attempt_at TEXT
+        );
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v21: {}", e)))?;
+
+    Ok(())
+}
+
+/// v22: sync_runs table
+fn migrate_to_v22(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS sync_runs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            source TEXT NOT NULL,
+            started_at TEXT NOT NULL,
+            finished_at TEXT,
+            status TEXT NOT NULL DEFAULT 'running',
+            tickets_fetched INTEGER NOT NULL DEFAULT 0,
+            tickets_updated INTEGER NOT NULL DEFAULT 0,
+            tickets_failed INTEGER NOT NULL DEFAULT 0,
+            error TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_sync_runs_started_at ON sync_runs(started_at);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v22: {}", e)))?;
+
+    Ok(())
+}
+
+/// v23: per-item failures skipped by a sync run (JSON array)
+fn migrate_to_v23(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE sync_runs ADD COLUMN failures TEXT NOT NULL DEFAULT '[]';
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v23: {}", e)))?;
+
+    Ok(())
+}
+
+/// v24: content hash of the last synced ticket, to skip no-op upserts
+fn migrate_to_v24(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tickets ADD COLUMN content_hash TEXT;
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v24: {}", e)))?;
+
+    Ok(())
+}
+
+/// v25: ticket_changes journal of field updates
+fn migrate_to_v25(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS ticket_changes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            ticket_key TEXT NOT NULL,
+            field TEXT NOT NULL,
+            old_value TEXT NOT NULL,
+            new_value TEXT NOT NULL,
+            changed_at TEXT NOT NULL,
+            sync_run_id INTEGER
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_ticket_changes_ticket ON ticket_changes(ticket_key);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v25: {}", e)))?;
+
+    Ok(())
+}
+
+/// v26: tickets_archive for resolved tickets past the retention period
+fn migrate_to_v26(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- Same columns as tickets: migrations adding a ticket column must add it here too
+        CREATE TABLE IF NOT EXISTS tickets_archive AS SELECT * FROM tickets WHERE 0;
+
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_tickets_archive_key ON tickets_archive(jira_key);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v26: {}", e)))?;
+
     Ok(())
 }
+
+/// v27: stored resolution hours so aggregations stay in SQL
+fn migrate_to_v27(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tickets ADD COLUMN resolution_hours REAL;
+        ALTER TABLE tickets ADD COLUMN calendar_resolution_hours REAL;
+        ALTER TABLE tickets_archive ADD COLUMN resolution_hours REAL;
+        ALTER TABLE tickets_archive ADD COLUMN calendar_resolution_hours REAL;
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v27: {}", e)))?;
+
+    for table in ["tickets", "tickets_archive"] {
+        // Calendars come later, so every project has the default schedule
+        refresh_resolution_hours(
+            conn,
+            table,
+            RefreshScope::Missing,
+            &ProjectSchedules::default(),
+        )?;
+    }
+    Ok(())
+}
+
+/// v28: indexes on the columns aggregations filter and group by
+fn migrate_to_v28(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_tickets_status ON tickets(status);
+        CREATE INDEX IF NOT EXISTS idx_tickets_priority ON tickets(priority);
+        CREATE INDEX IF NOT EXISTS idx_tickets_assignee ON tickets(assignee);
+        CREATE INDEX IF NOT EXISTS idx_tickets_project ON tickets(project_key);
+        CREATE INDEX IF NOT EXISTS idx_tickets_created ON tickets(created_at);
+        CREATE INDEX IF NOT EXISTS idx_tickets_resolved ON tickets(resolved_at);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v28: {}", e)))?;
+
+    Ok(())
+}
+
+/// v29: how sure the categorizer was of each ticket's category.
+fn migrate_to_v29(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tickets ADD COLUMN category_confidence REAL;
+        ALTER TABLE tickets_archive ADD COLUMN category_confidence REAL;
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v29: {}", e)))?;
+
+    Ok(())
+}
+
+/// v30: a workflow-independent status category per ticket, with local overrides by status name.
+fn migrate_to_v30(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tickets ADD COLUMN status_category TEXT;
+        ALTER TABLE tickets_archive ADD COLUMN status_category TEXT;
+
+        CREATE TABLE IF NOT EXISTS status_category_mappings (
+            status TEXT PRIMARY KEY,
+            category TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v30: {}", e)))?;
+
+    Ok(())
+}
+
+/// v31: priorities normalized onto a canonical scale through local mappings.
+fn migrate_to_v31(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tickets ADD COLUMN priority_level TEXT;
+        ALTER TABLE tickets_archive ADD COLUMN priority_level TEXT;
+
+        -- project_key '' applies to every project
+        CREATE TABLE IF NOT EXISTS priority_mappings (
+            priority TEXT NOT NULL,
+            project_key TEXT NOT NULL DEFAULT '',
+            level TEXT NOT NULL,
+            PRIMARY KEY (priority, project_key)
+        );
+
+        UPDATE tickets SET priority_level = CASE lower(priority)
+            WHEN 'critical' THEN 'Critical'
+            WHEN 'high' THEN 'High'
+            WHEN 'medium' THEN 'Medium'
+            WHEN 'low' THEN 'Low'
+        END;
+        UPDATE tickets_archive SET priority_level = CASE lower(priority)
+            WHEN 'critical' THEN 'Critical'
+            WHEN 'high' THEN 'High'
+            WHEN 'medium' THEN 'Medium'
+            WHEN 'low' THEN 'Low'
+        END;
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v31: {}", e)))?;
+
+    Ok(())
+}
+
+/// v32: on-call / triage rotation windows
+fn migrate_to_v32(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS rotations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            person TEXT NOT NULL,
+            start_at TEXT NOT NULL,
+            end_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_rotations_start ON rotations(start_at);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v32: {}", e)))?;
+
+    Ok(())
+}
+
+/// v33: teams of assignees, for team-level rollups
+fn migrate_to_v33(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS teams (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE
+        );
+        -- An assignee may belong to several teams
+        CREATE TABLE IF NOT EXISTS team_members (
+            team_id INTEGER NOT NULL,
+            assignee TEXT NOT NULL,
+            PRIMARY KEY (team_id, assignee)
+        );
+        CREATE INDEX IF NOT EXISTS idx_team_members_assignee ON team_members(assignee);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v33: {}", e)))?;
+
+    Ok(())
+}
+
+/// v34: Jira user directory
+fn migrate_to_v34(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS users (
+            account_id TEXT PRIMARY KEY,
+            display_name TEXT NOT NULL,
+            avatar_url TEXT,
+            active INTEGER NOT NULL DEFAULT 1,
+            synced_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_users_display_name ON users(display_name);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v34: {}", e)))?;
+
+    Ok(())
+}
+
+/// v35: icon image cache
+fn migrate_to_v35(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- Avatar and issue-type icon images cached on disk, by source URL
+        CREATE TABLE IF NOT EXISTS icon_cache (
+            url TEXT PRIMARY KEY,
+            file_name TEXT NOT NULL,
+            content_type TEXT NOT NULL,
+            etag TEXT,
+            fetched_at TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v35: {}", e)))?;
+
+    Ok(())
+}
+
+/// v36: full-text index for the quick switcher
+fn migrate_to_v36(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- Full-text index over ticket keys, summaries and labels, kept in step
+        -- with the tickets table by triggers
+        CREATE VIRTUAL TABLE IF NOT EXISTS tickets_fts USING fts5(
+            jira_key, summary, labels,
+            content='tickets', content_rowid='id'
+        );
+        CREATE TRIGGER IF NOT EXISTS tickets_fts_insert AFTER INSERT ON tickets BEGIN
+            INSERT INTO tickets_fts (rowid, jira_key, summary, labels)
+            VALUES (new.id, new.jira_key, new.summary, new.labels);
+        END;
+        CREATE TRIGGER IF NOT EXISTS tickets_fts_delete AFTER DELETE ON tickets BEGIN
+            INSERT INTO tickets_fts (tickets_fts, rowid, jira_key, summary, labels)
+            VALUES ('delete', old.id, old.jira_key, old.summary, old.labels);
+        END;
+        CREATE TRIGGER IF NOT EXISTS tickets_fts_update
+        AFTER UPDATE OF jira_key, summary, labels ON tickets BEGIN
+            INSERT INTO tickets_fts (tickets_fts, rowid, jira_key, summary, labels)
+            VALUES ('delete', old.id, old.jira_key, old.summary, old.labels);
+            INSERT INTO tickets_fts (rowid, jira_key, summary, labels)
+            VALUES (new.id, new.jira_key, new.summary, new.labels);
+        END;
+        INSERT INTO tickets_fts (tickets_fts) VALUES ('rebuild');
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v36: {}", e)))?;
+
+    Ok(())
+}
+
+/// v37: recently viewed tickets
+fn migrate_to_v37(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- When each ticket was last opened in the app
+        CREATE TABLE IF NOT EXISTS ticket_views (
+            ticket_key TEXT PRIMARY KEY,
+            viewed_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_ticket_views_viewed_at ON ticket_views(viewed_at);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v37: {}", e)))?;
+
+    Ok(())
+}
+
+/// v38: personal ticket notes
+fn migrate_to_v38(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- Private notes on tickets; never sent to Jira
+        CREATE TABLE IF NOT EXISTS ticket_notes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            ticket_key TEXT NOT NULL,
+            body TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_ticket_notes_ticket_key ON ticket_notes(ticket_key);
+        CREATE VIRTUAL TABLE IF NOT EXISTS ticket_notes_fts USING fts5(
+            body,
+            content='ticket_notes', content_rowid='id'
+        );
+        CREATE TRIGGER IF NOT EXISTS ticket_notes_fts_insert AFTER INSERT ON ticket_notes BEGIN
+            INSERT INTO ticket_notes_fts (rowid, body) VALUES (new.id, new.body);
+        END;
+        CREATE TRIGGER IF NOT EXISTS ticket_notes_fts_delete AFTER DELETE ON ticket_notes BEGIN
+            INSERT INTO ticket_notes_fts (ticket_notes_fts, rowid, body)
+            VALUES ('delete', old.id, old.body);
+        END;
+        CREATE TRIGGER IF NOT EXISTS ticket_notes_fts_update AFTER UPDATE OF body ON ticket_notes BEGIN
+            INSERT INTO ticket_notes_fts (ticket_notes_fts, rowid, body)
+            VALUES ('delete', old.id, old.body);
+            INSERT INTO ticket_notes_fts (rowid, body) VALUES (new.id, new.body);
+        END;
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v38: {}", e)))?;
+
+    Ok(())
+}
+
+/// v39: local time tracking
+fn migrate_to_v39(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- Time spent on tickets, one row per timer run; ended_at is NULL while the
+        -- timer runs and worklog_id is set once pushed to Jira
+        CREATE TABLE IF NOT EXISTS time_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            ticket_key TEXT NOT NULL,
+            started_at TEXT NOT NULL,
+            ended_at TEXT,
+            worklog_id TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_time_entries_started_at ON time_entries(started_at);
+        -- At most one running timer per ticket
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_time_entries_running
+            ON time_entries(ticket_key) WHERE ended_at IS NULL;
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v39: {}", e)))?;
+
+    Ok(())
+}
+
+/// v40: focus sessions
+fn migrate_to_v40(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- Focus sessions are timer runs with a planned length
+        ALTER TABLE time_entries ADD COLUMN focus_minutes INTEGER;
+        ALTER TABLE time_entries ADD COLUMN interruptions INTEGER NOT NULL DEFAULT 0;
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v40: {}", e)))?;
+
+    Ok(())
+}
+
+/// v41: kanban WIP limits
+fn migrate_to_v41(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- Work-in-progress limits per kanban column (status category)
+        CREATE TABLE IF NOT EXISTS wip_limits (
+            category TEXT PRIMARY KEY,
+            max_tickets INTEGER NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v41: {}", e)))?;
+
+    Ok(())
+}
+
+/// v42: personal ticket ranking
+fn migrate_to_v42(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- Personal queue order, independent of Jira's rank and untouched by syncs
+        CREATE TABLE IF NOT EXISTS ticket_rank (
+            ticket_key TEXT PRIMARY KEY,
+            position INTEGER NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v42: {}", e)))?;
+
+    Ok(())
+}
+
+/// v43: sprint dates and scheduled work for the calendar
+fn migrate_to_v43(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- Sprints seen on synced issues, for their start and end dates
+        CREATE TABLE IF NOT EXISTS sprints (
+            name TEXT PRIMARY KEY,
+            state TEXT,
+            start_date TEXT,
+            end_date TEXT
+        );
+        -- Locally planned "work on this ticket on that day" entries
+        CREATE TABLE IF NOT EXISTS scheduled_work (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            ticket_key TEXT NOT NULL,
+            date TEXT NOT NULL,
+            note TEXT,
+            created_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_scheduled_work_date ON scheduled_work(date);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v43: {}", e)))?;
+
+    Ok(())
+}
+
+/// v44: statuses configured to count as resolved
+fn migrate_to_v44(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- Statuses that count as resolved whatever their category or resolution date
+        CREATE TABLE IF NOT EXISTS resolved_statuses (
+            status TEXT PRIMARY KEY
+        );
+
+        -- Set when resolved_at was derived locally from a status rather than sent by
+        -- the source, so only those dates are cleared when the status stops counting
+        ALTER TABLE tickets ADD COLUMN resolution_derived INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE tickets_archive ADD COLUMN resolution_derived INTEGER NOT NULL DEFAULT 0;
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v44: {}", e)))?;
+
+    Ok(())
+}
+
+/// v45: support calendars and their projects
+fn migrate_to_v45(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- Named working-hour schedules (a serialized WorkSchedule)
+        CREATE TABLE IF NOT EXISTS calendars (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            schedule TEXT NOT NULL
+        );
+        -- Each project follows at most one calendar
+        CREATE TABLE IF NOT EXISTS project_calendars (
+            project_key TEXT PRIMARY KEY,
+            calendar_id INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_project_calendars_calendar ON project_calendars(calendar_id);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v45: {}", e)))?;
+
+    Ok(())
+}
+
+/// v46: outbound webhooks posted on alerts and syncs
+fn migrate_to_v46(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- events is a JSON array of WebhookEvent names
+        CREATE TABLE IF NOT EXISTS outbound_webhooks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            url TEXT NOT NULL,
+            events TEXT NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v46: {}", e)))?;
+
+    Ok(())
+}
+
+/// v47: rule-based automations run after each sync
+fn migrate_to_v47(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- filter is a serialized TicketFilter, actions a JSON array of AutomationAction
+        CREATE TABLE IF NOT EXISTS automations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            filter TEXT NOT NULL,
+            actions TEXT NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL
+        );
+        -- Tickets each automation matched after the last sync; only new ones are acted on
+        CREATE TABLE IF NOT EXISTS automation_matches (
+            automation_id INTEGER NOT NULL,
+            ticket_key TEXT NOT NULL,
+            PRIMARY KEY (automation_id, ticket_key)
+        );
+        CREATE TABLE IF NOT EXISTS automation_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            automation_id INTEGER NOT NULL,
+            automation_name TEXT NOT NULL,
+            ticket_key TEXT NOT NULL,
+            action TEXT NOT NULL,
+            outcome TEXT NOT NULL,
+            detail TEXT,
+            ran_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_automation_log_ran_at ON automation_log(ran_at);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v47: {}", e)))?;
+
+    Ok(())
+}
+
+/// v48: local ticket snoozes
+fn migrate_to_v48(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- until is fixed-width UTC (YYYY-MM-DDTHH:MM:SSZ) so it compares as text;
+        -- seen_updated_at is the ticket's updated_at when it was snoozed
+        CREATE TABLE IF NOT EXISTS ticket_snoozes (
+            ticket_key TEXT PRIMARY KEY,
+            until TEXT NOT NULL,
+            snoozed_at TEXT NOT NULL,
+            seen_updated_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_ticket_snoozes_until ON ticket_snoozes(until);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v48: {}", e)))?;
+
+    Ok(())
+}
+
+/// v49: metric goals with a target and deadline
+fn migrate_to_v49(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS metric_goals (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            metric TEXT NOT NULL,
+            comparison TEXT NOT NULL,
+            target REAL NOT NULL,
+            deadline TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v49: {}", e)))?;
+
+    Ok(())
+}
+
+/// v50: escalated flag on tickets
+fn migrate_to_v50(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tickets ADD COLUMN escalated INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE tickets_archive ADD COLUMN escalated INTEGER NOT NULL DEFAULT 0;
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v50: {}", e)))?;
+
+    Ok(())
+}
+
+/// v51: customer column on tickets
+fn migrate_to_v51(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tickets ADD COLUMN customer TEXT;
+        ALTER TABLE tickets_archive ADD COLUMN customer TEXT;
+        CREATE INDEX IF NOT EXISTS idx_tickets_customer ON tickets(customer);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v51: {}", e)))?;
+
+    Ok(())
+}
+
+/// v52: environment column and ticket_affected_versions table
+fn migrate_to_v52(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tickets ADD COLUMN environment TEXT;
+        ALTER TABLE tickets_archive ADD COLUMN environment TEXT;
+
+        CREATE TABLE IF NOT EXISTS ticket_affected_versions (
+            ticket_key TEXT NOT NULL,
+            version TEXT NOT NULL,
+            PRIMARY KEY (ticket_key, version)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_ticket_affected_versions_version
+            ON ticket_affected_versions(version);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v52: {}", e)))?;
+
+    Ok(())
+}
+
+/// v53: root-cause taxonomy and per-ticket root causes
+fn migrate_to_v53(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS root_causes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            description TEXT
+        );
+        -- At most one root cause per ticket, set once it's resolved
+        CREATE TABLE IF NOT EXISTS ticket_root_causes (
+            ticket_key TEXT PRIMARY KEY,
+            root_cause_id INTEGER NOT NULL,
+            set_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_ticket_root_causes_root_cause
+            ON ticket_root_causes(root_cause_id);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v53: {}", e)))?;
+
+    Ok(())
+}
+
+/// v54: local links between tickets
+fn migrate_to_v54(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        -- Links made in the app, kept apart from the Jira links that each sync replaces
+        CREATE TABLE IF NOT EXISTS local_links (
+            source_key TEXT NOT NULL,
+            target_key TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (source_key, target_key, kind)
+        );
+        CREATE INDEX IF NOT EXISTS idx_local_links_target ON local_links(target_key, kind);
+        "#,
+    )
+    .map_err(|e| DbError::Migration(format!("Failed to migrate schema to v54: {}", e)))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_database_ends_at_the_current_version() {
+        let conn = Connection::open_in_memory().expect("in-memory db");
+        initialize_database(&conn).expect("initialized");
+        assert_eq!(get_schema_version(&conn).expect("version"), SCHEMA_VERSION);
+        // Re-running is a no-op
+        initialize_database(&conn).expect("initialized again");
+        assert_eq!(get_schema_version(&conn).expect("version"), SCHEMA_VERSION);
+    }
+}
@@ -1,8 +1,100 @@
+pub mod accounts;
+pub mod alert_rules;
+pub mod alerts;
+pub mod archive;
+pub mod attachments;
+pub mod automations;
+pub mod board;
+pub mod calendar;
+pub mod cohorts;
+pub mod dashboards;
+pub mod dataset;
+pub mod dimensions;
+pub mod duplicates;
+pub mod encryption;
+pub mod goals;
+pub mod icon_cache;
+pub mod local_changes;
+pub mod local_links;
+pub mod local_tags;
 pub mod migrations;
+pub mod outbound_webhooks;
+pub mod outbox;
+pub mod priorities;
 pub mod queries;
+pub mod query_console;
+pub mod query_plans;
+pub mod quick_search;
+pub mod releases;
+pub mod root_causes;
+pub mod rotations;
+pub mod saved_views;
+pub mod snapshots;
+pub mod snoozes;
+pub mod sprints;
+pub mod stats_history;
+pub mod status_categories;
+pub mod support_calendars;
+pub mod sync_runs;
+pub mod teams;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod ticket_changes;
+pub mod ticket_links;
+pub mod ticket_notes;
+pub mod ticket_rank;
+pub mod ticket_views;
+pub mod time_entries;
+pub mod users;
+pub mod watchlist;
 
+pub use accounts::*;
+pub use alert_rules::*;
+pub use alerts::*;
+pub use archive::*;
+pub use attachments::*;
+pub use automations::*;
+pub use board::*;
+pub use calendar::*;
+pub use cohorts::*;
+pub use dashboards::*;
+pub use dataset::*;
+pub use dimensions::*;
+pub use duplicates::*;
+pub use encryption::*;
+pub use goals::*;
+pub use icon_cache::*;
+pub use local_changes::*;
+pub use local_links::*;
+pub use local_tags::*;
 pub use migrations::*;
+pub use outbound_webhooks::*;
+pub use outbox::*;
+pub use priorities::*;
 pub use queries::*;
+pub use query_console::*;
+pub use query_plans::*;
+pub use quick_search::*;
+pub use releases::*;
+pub use root_causes::*;
+pub use rotations::*;
+pub use saved_views::*;
+pub use snapshots::*;
+pub use snoozes::*;
+pub use sprints::*;
+pub use stats_history::*;
+pub use status_categories::*;
+pub use support_calendars::*;
+pub use sync_runs::*;
+pub use teams::*;
+pub use ticket_changes::*;
+pub use ticket_links::*;
+pub use ticket_notes::*;
+pub use ticket_rank::*;
+pub use ticket_views::*;
+pub use time_entries::*;
+pub use users::*;
+pub use watchlist::*;
 
 use crate::errors::{AppError, DbError};
 use rusqlite::Connection;
@@ -10,10 +102,30 @@ use std::sync::{Arc, Mutex};
 
 pub struct DbPool(pub Arc<Mutex<Connection>>);
 
+/// File name of the database inside the app data directory.
+pub const DATABASE_FILE: &str = "tickets.db";
+
 impl DbPool {
-    pub fn new(db_path: &str) -> Result<Self, AppError> {
-        let conn = Connection::open(db_path).map_err(DbError::from)?;
+    /// Opens (and migrates) the database, unlocking it with `key` when it
+    /// has been encrypted.
+    pub fn new(db_path: &str, key: Option<&str>) -> Result<Self, AppError> {
+        let conn = open_database(db_path, key)?;
         initialize_database(&conn)?;
         Ok(DbPool(Arc::new(Mutex::new(conn))))
     }
+
+    /// Runs `f` against the connection on the blocking thread pool.
+    pub async fn run<T, F>(&self, f: F) -> Result<T, AppError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T, AppError> + Send + 'static,
+    {
+        let db_clone = self.0.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let conn = db_clone.lock().map_err(|_| DbError::LockFailed)?;
+            f(&conn)
+        })
+        .await
+        .map_err(|_| AppError::Internal("Task join failed".to_string()))?
+    }
 }
@@ -0,0 +1,171 @@
+use crate::errors::{AppError, DbError};
+use crate::models::{OutboundWebhook, OutboundWebhookInput, WebhookEvent};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+const WEBHOOK_COLUMNS: &str = "id, name, url, events, enabled, created_at";
+
+fn map_webhook_row(row: &Row) -> rusqlite::Result<OutboundWebhook> {
+    let events: String = row.get(3)?;
+
+    Ok(OutboundWebhook {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        url: row.get(2)?,
+        events: serde_json::from_str(&events)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, Type::Text, Box::new(e)))?,
+        enabled: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+fn events_json(input: &OutboundWebhookInput) -> Result<String, AppError> {
+    serde_json::to_string(&input.events)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize webhook events: {}", e)))
+}
+
+pub fn create_outbound_webhook(
+    conn: &Connection,
+    input: &OutboundWebhookInput,
+) -> Result<OutboundWebhook, AppError> {
+    conn.execute(
+        "INSERT INTO outbound_webhooks (name, url, events, enabled, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            input.name,
+            input.url,
+            events_json(input)?,
+            input.enabled,
+            chrono::Utc::now().to_rfc3339(),
+        ],
+    )
+    .map_err(DbError::from)?;
+
+    get_outbound_webhook(conn, conn.last_insert_rowid())?
+        .ok_or_else(|| AppError::Internal("Inserted webhook not found".to_string()))
+}
+
+pub fn get_outbound_webhook(
+    conn: &Connection,
+    id: i64,
+) -> Result<Option<OutboundWebhook>, AppError> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM outbound_webhooks WHERE id = ?1",
+            WEBHOOK_COLUMNS
+        ),
+        params![id],
+        map_webhook_row,
+    )
+    .optional()
+    .map_err(|e| DbError::from(e).into())
+}
+
+pub fn list_outbound_webhooks(conn: &Connection) -> Result<Vec<OutboundWebhook>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM outbound_webhooks ORDER BY name",
+            WEBHOOK_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+
+    let webhooks = stmt
+        .query_map([], map_webhook_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(webhooks)
+}
+
+/// Enabled webhooks subscribed to `event`.
+pub fn outbound_webhooks_for(
+    conn: &Connection,
+    event: WebhookEvent,
+) -> Result<Vec<OutboundWebhook>, AppError> {
+    Ok(list_outbound_webhooks(conn)?
+        .into_iter()
+        .filter(|webhook| webhook.enabled && webhook.events.contains(&event))
+        .collect())
+}
+
+pub fn update_outbound_webhook(
+    conn: &Connection,
+    id: i64,
+    input: &OutboundWebhookInput,
+) -> Result<Option<OutboundWebhook>, AppError> {
+    conn.execute(
+        "UPDATE outbound_webhooks SET name = ?2, url = ?3, events = ?4, enabled = ?5
+         WHERE id = ?1",
+        params![
+            id,
+            input.name,
+            input.url,
+            events_json(input)?,
+            input.enabled
+        ],
+    )
+    .map_err(DbError::from)?;
+
+    get_outbound_webhook(conn, id)
+}
+
+pub fn delete_outbound_webhook(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute("DELETE FROM outbound_webhooks WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+
+    fn input(name: &str, events: Vec<WebhookEvent>) -> OutboundWebhookInput {
+        OutboundWebhookInput {
+            name: name.to_string(),
+            url: format!("https://automations.example.com/{}", name),
+            events,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn webhooks_are_selected_by_event() {
+        let conn = setup_db();
+
+        let pager = create_outbound_webhook(&conn, &input("pager", vec![WebhookEvent::AlertFired]))
+            .expect("create");
+        let chatops = create_outbound_webhook(
+            &conn,
+            &input(
+                "chatops",
+                vec![WebhookEvent::AlertFired, WebhookEvent::SyncCompleted],
+            ),
+        )
+        .expect("create");
+
+        let names = |event| {
+            outbound_webhooks_for(&conn, event)
+                .expect("webhooks")
+                .into_iter()
+                .map(|webhook| webhook.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(WebhookEvent::AlertFired), vec!["chatops", "pager"]);
+        assert_eq!(names(WebhookEvent::SyncCompleted), vec!["chatops"]);
+
+        let mut paused = input("chatops", chatops.events.clone());
+        paused.enabled = false;
+        let updated = update_outbound_webhook(&conn, chatops.id, &paused)
+            .expect("update")
+            .expect("webhook exists");
+        assert!(!updated.enabled);
+        assert_eq!(names(WebhookEvent::AlertFired), vec!["pager"]);
+
+        assert!(delete_outbound_webhook(&conn, pager.id).expect("delete"));
+        assert!(!delete_outbound_webhook(&conn, pager.id).expect("delete"));
+        assert!(names(WebhookEvent::AlertFired).is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::errors::{AppError, DbError};
+use crate::models::{OutboxAction, OutboxItem, OutboxStatus};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, Row};
+
+const OUTBOX_COLUMNS: &str = "id, ticket_key, action, status, attempts, last_error, created_at, \
+     last_attempt_at, next_attempt_at";
+
+/// Fixed-width UTC timestamps, so `next_attempt_at` compares correctly as text.
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn map_outbox_row(row: &Row) -> rusqlite::Result<OutboxItem> {
+    let action: String = row.get(2)?;
+    let status: String = row.get(3)?;
+
+    Ok(OutboxItem {
+        id: row.get(0)?,
+        ticket_key: row.get(1)?,
+        action: serde_json::from_str(&action)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(2, Type::Text, Box::new(e)))?,
+        status: OutboxStatus::parse(&status).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                3,
+                Type::Text,
+                format!("Unknown outbox status: {}", status).into(),
+            )
+        })?,
+        attempts: row.get(4)?,
+        last_error: row.get(5)?,
+        created_at: row.get(6)?,
+        last_attempt_at: row.get(7)?,
+        next_attempt_at: row.get(8)?,
+    })
+}
+
+pub fn enqueue_outbox(
+    conn: &Connection,
+    ticket_key: &str,
+    action: &OutboxAction,
+    now: DateTime<Utc>,
+) -> Result<i64, AppError> {
+    let action = serde_json::to_string(action)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize outbox action: {}", e)))?;
+    conn.execute(
+        "INSERT INTO outbox (ticket_key, action, status, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            ticket_key,
+            action,
+            OutboxStatus::Pending.as_str(),
+            timestamp(now)
+        ],
+    )
+    .map_err(DbError::from)?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Every queued write-back, in the order it was made.
+pub fn get_outbox(conn: &Connection) -> Result<Vec<OutboxItem>, AppError> {
+    query_outbox(conn, None)
+}
+
+/// Items still waiting to be sent, oldest first.
+pub fn get_pending_outbox(conn: &Connection) -> Result<Vec<OutboxItem>, AppError> {
+    query_outbox(conn, Some(OutboxStatus::Pending))
+}
+
+fn query_outbox(
+    conn: &Connection,
+    status: Option<OutboxStatus>,
+) -> Result<Vec<OutboxItem>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM outbox WHERE ?1 IS NULL OR status = ?1 ORDER BY id",
+            OUTBOX_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+    let items = stmt
+        .query_map(params![status.map(OutboxStatus::as_str)], map_outbox_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(items)
+}
+
+/// Whether an automatic flush should try `item` at `now`.
+pub fn outbox_item_due(item: &OutboxItem, now: DateTime<Utc>) -> bool {
+    item.next_attempt_at
+        .as_deref()
+        .is_none_or(|next| next <= timestamp(now).as_str())
+}
+
+/// Removes an item once sent, or when the user discards it.
+pub fn delete_outbox_item(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute("DELETE FROM outbox WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+/// Records a failed attempt. Pending items are retried from `next_attempt_at`;
+/// failed items stay until discarded.
+pub fn record_outbox_attempt(
+    conn: &Connection,
+    id: i64,
+    status: OutboxStatus,
+    error: &str,
+    now: DateTime<Utc>,
+    next_attempt_at: Option<DateTime<Utc>>,
+) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE outbox SET status = ?2, attempts = attempts + 1, last_error = ?3,
+             last_attempt_at = ?4, next_attempt_at = ?5
+         WHERE id = ?1",
+        params![
+            id,
+            status.as_str(),
+            error,
+            timestamp(now),
+            next_attempt_at.map(timestamp)
+        ],
+    )
+    .map_err(DbError::from)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+    use chrono::TimeZone;
+
+    #[test]
+    fn retry_state_is_tracked_per_item() {
+        let conn = setup_db();
+        let now = Utc
+            .with_ymd_and_hms(2025, 1, 6, 9, 0, 0)
+            .single()
+            .expect("valid datetime");
+
+        let comment = enqueue_outbox(
+            &conn,
+            "OPS-1",
+            &OutboxAction::Comment {
+                body: "On it".to_string(),
+            },
+            now,
+        )
+        .expect("queued");
+        let assign = enqueue_outbox(
+            &conn,
+            "OPS-2",
+            &OutboxAction::Assign { assignee: None },
+            now,
+        )
+        .expect("queued");
+
+        record_outbox_attempt(
+            &conn,
+            comment,
+            OutboxStatus::Pending,
+            "connection refused",
+            now,
+            Some(now + chrono::Duration::minutes(2)),
+        )
+        .expect("attempt recorded");
+        record_outbox_attempt(
+            &conn,
+            assign,
+            OutboxStatus::Failed,
+            "no such user",
+            now,
+            None,
+        )
+        .expect("attempt recorded");
+
+        let pending = get_pending_outbox(&conn).expect("pending");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].attempts, 1);
+        assert_eq!(pending[0].last_error.as_deref(), Some("connection refused"));
+        assert!(!outbox_item_due(
+            &pending[0],
+            now + chrono::Duration::minutes(1)
+        ));
+        assert!(outbox_item_due(
+            &pending[0],
+            now + chrono::Duration::minutes(2)
+        ));
+
+        let all = get_outbox(&conn).expect("outbox");
+        assert_eq!(all[1].status, OutboxStatus::Failed);
+        assert_eq!(all[1].action, OutboxAction::Assign { assignee: None });
+
+        assert!(delete_outbox_item(&conn, comment).expect("deleted"));
+        assert!(get_pending_outbox(&conn).expect("pending").is_empty());
+    }
+}
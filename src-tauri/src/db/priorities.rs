@@ -0,0 +1,153 @@
+use super::queries::{get_tickets, upsert_ticket, UpsertOutcome};
+use crate::errors::{AppError, DbError};
+use crate::models::{PriorityLevel, PriorityMapping, Ticket, TicketFilter};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub fn get_priority_mappings(conn: &Connection) -> Result<Vec<PriorityMapping>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT priority, project_key, level FROM priority_mappings
+             ORDER BY project_key, priority",
+        )
+        .map_err(DbError::from)?;
+    let mappings = stmt
+        .query_map([], |row| {
+            let project_key: String = row.get(1)?;
+            let level: String = row.get(2)?;
+            Ok(PriorityMapping {
+                priority: row.get(0)?,
+                project_key: (!project_key.is_empty()).then_some(project_key),
+                level: PriorityLevel::parse(&level).ok_or_else(|| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        2,
+                        Type::Text,
+                        format!("Unknown priority level: {}", level).into(),
+                    )
+                })?,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+    Ok(mappings)
+}
+
+/// Sets `ticket.priority_level`: a mapping for its project wins over one for
+/// every project, and a priority already named after a level needs none.
+pub(crate) fn apply_priority_level(conn: &Connection, ticket: &mut Ticket) -> Result<(), AppError> {
+    let mapped: Option<String> = conn
+        .prepare_cached(
+            "SELECT level FROM priority_mappings
+             WHERE priority = ?1 AND project_key IN (?2, '')
+             ORDER BY project_key DESC LIMIT 1",
+        )
+        .and_then(|mut stmt| {
+            stmt.query_row(params![ticket.priority, ticket.project_key], |row| {
+                row.get(0)
+            })
+            .optional()
+        })
+        .map_err(DbError::from)?;
+    ticket.priority_level = mapped
+        .as_deref()
+        .and_then(PriorityLevel::parse)
+        .or_else(|| PriorityLevel::parse(&ticket.priority));
+    Ok(())
+}
+
+/// Maps `priority` (in `project_key`, or everywhere) onto `level`, or removes
+/// the mapping when `level` is `None`, and re-levels the stored tickets it
+/// covers. Returns how many tickets changed.
+pub fn set_priority_mapping(
+    conn: &Connection,
+    priority: &str,
+    project_key: Option<&str>,
+    level: Option<PriorityLevel>,
+) -> Result<u32, AppError> {
+    let project_key = project_key.unwrap_or_default();
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    match level {
+        Some(level) => tx.execute(
+            "INSERT INTO priority_mappings (priority, project_key, level) VALUES (?1, ?2, ?3)
+             ON CONFLICT(priority, project_key) DO UPDATE SET level = excluded.level",
+            params![priority, project_key, level.as_str()],
+        ),
+        None => tx.execute(
+            "DELETE FROM priority_mappings WHERE priority = ?1 AND project_key = ?2",
+            params![priority, project_key],
+        ),
+    }
+    .map_err(DbError::from)?;
+
+    let filter = TicketFilter {
+        priorities: vec![priority.to_string()],
+        project_keys: if project_key.is_empty() {
+            Vec::new()
+        } else {
+            vec![project_key.to_string()]
+        },
+        ..Default::default()
+    }
+    .with_duplicates();
+    let mut changed = 0;
+    for ticket in get_tickets(&tx, &filter)? {
+        if upsert_ticket(&tx, &ticket)? == UpsertOutcome::Updated {
+            changed += 1;
+        }
+    }
+    tx.commit().map_err(DbError::from)?;
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::get_ticket_by_key;
+    use crate::db::test_support::{self, setup_db};
+
+    fn ticket(key: &str, project_key: &str, priority: &str) -> Ticket {
+        Ticket {
+            priority: priority.to_string(),
+            project_key: project_key.to_string(),
+            ..test_support::ticket(key)
+        }
+    }
+
+    fn level(conn: &Connection, key: &str) -> Option<PriorityLevel> {
+        get_ticket_by_key(conn, key)
+            .expect("query")
+            .expect("ticket")
+            .priority_level
+    }
+
+    #[test]
+    fn project_mappings_override_global_ones() {
+        let conn = setup_db();
+        for (key, project, priority) in [
+            ("OPS-1", "OPS", "high"),
+            ("OPS-2", "OPS", "P1"),
+            ("WEB-1", "WEB", "P1"),
+            ("WEB-2", "WEB", "Blocker"),
+        ] {
+            upsert_ticket(&conn, &ticket(key, project, priority)).expect("ticket");
+        }
+        assert_eq!(level(&conn, "OPS-1"), Some(PriorityLevel::High));
+        assert_eq!(level(&conn, "OPS-2"), None);
+
+        let changed =
+            set_priority_mapping(&conn, "P1", None, Some(PriorityLevel::Critical)).expect("map");
+        assert_eq!(changed, 2);
+        set_priority_mapping(&conn, "P1", Some("WEB"), Some(PriorityLevel::High)).expect("map");
+        set_priority_mapping(&conn, "Blocker", None, Some(PriorityLevel::Critical)).expect("map");
+
+        assert_eq!(level(&conn, "OPS-2"), Some(PriorityLevel::Critical));
+        assert_eq!(level(&conn, "WEB-1"), Some(PriorityLevel::High));
+        assert_eq!(level(&conn, "WEB-2"), Some(PriorityLevel::Critical));
+        assert_eq!(get_priority_mappings(&conn).expect("mappings").len(), 3);
+
+        // Removing the project override falls back to the global mapping
+        set_priority_mapping(&conn, "P1", Some("WEB"), None).expect("unmap");
+        assert_eq!(level(&conn, "WEB-1"), Some(PriorityLevel::Critical));
+    }
+}
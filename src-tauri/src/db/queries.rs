@@ -1,30 +1,216 @@
+use super::attachments::replace_attachments;
+use super::dataset::sql_to_json;
+use super::dimensions::get_count_by;
+use super::local_tags::get_count_by_local_tag;
+use super::priorities::apply_priority_level;
+use super::status_categories::apply_status_category;
+use super::support_calendars::{project_schedule, project_schedules, ProjectSchedules};
+use super::ticket_links::BLOCKS_LINK_CONDITION;
 use crate::errors::{AppError, DbError};
 use crate::models::{
-    AggregationResult, AvgEntry, CountEntry, SummaryStats, Ticket, TimeSeriesEntry,
+    AffectedVersionStats, AggregationResult, AvgEntry, CountEntry, CustomerStats, Dimension,
+    DurationMode, PointsEntry, PriorityLevel, RatioEntry, StaleTicketGroup, StatusCategory,
+    SummaryStats, Ticket, TicketFilter, TimeSeriesEntry, VelocityEntry, WorkSchedule,
 };
-use crate::services::time_calc::business_hours_between;
-use chrono::DateTime;
-use rusqlite::{params, Connection, OptionalExtension};
-use std::collections::HashMap;
+use crate::services::hashing::fnv1a;
+use crate::services::time_calc::{business_hours_between, parse_timestamp};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row};
+use std::collections::{BTreeMap, HashMap};
 
-pub fn upsert_ticket(conn: &Connection, ticket: &Ticket) -> Result<(), AppError> {
+/// Open tickets untouched for this many days count as stale in `SummaryStats`.
+pub const STALE_TICKET_DAYS: u32 = 7;
+
+/// Column list matching `map_ticket_row`; keep the two in sync.
+pub(crate) const TICKET_COLUMNS: &str = "id, jira_key, summary, status, priority, issue_type, \
+     assignee, reporter, created_at, updated_at, resolved_at, labels, project_key, category, \
+     source, custom_fields, account_id, story_points, sprint, due_date, category_confidence, \
+     status_category, priority_level, escalated, customer, environment, \
+     (SELECT group_concat(component, char(31)) FROM ticket_components \
+      WHERE ticket_key = tickets.jira_key), \
+     (SELECT group_concat(version, char(31)) FROM ticket_fix_versions \
+      WHERE ticket_key = tickets.jira_key), \
+     (SELECT group_concat(version, char(31)) FROM ticket_affected_versions \
+      WHERE ticket_key = tickets.jira_key)";
+
+/// Separates values in the `group_concat` columns of `TICKET_COLUMNS`.
+pub(crate) const LIST_SEPARATOR: char = '\u{1f}';
+
+fn split_list(joined: Option<String>) -> Vec<String> {
+    let mut values: Vec<String> = joined
+        .map(|j| j.split(LIST_SEPARATOR).map(str::to_string).collect())
+        .unwrap_or_default();
+    values.sort();
+    values
+}
+
+pub(crate) fn map_ticket_row(row: &Row) -> rusqlite::Result<Ticket> {
+    Ok(Ticket {
+        id: row.get(0)?,
+        jira_key: row.get(1)?,
+        summary: row.get(2)?,
+        status: row.get(3)?,
+        priority: row.get(4)?,
+        issue_type: row.get(5)?,
+        assignee: row.get(6)?,
+        reporter: row.get(7)?,
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+        resolved_at: row.get(10)?,
+        labels: row.get(11)?,
+        project_key: row.get(12)?,
+        category: row.get(13)?,
+        source: row.get(14)?,
+        custom_fields: row.get(15)?,
+        account_id: row.get(16)?,
+        story_points: row.get(17)?,
+        sprint: row.get(18)?,
+        due_date: row.get(19)?,
+        category_confidence: row.get(20)?,
+        status_category: row
+            .get::<_, Option<String>>(21)?
+            .as_deref()
+            .and_then(StatusCategory::parse),
+        priority_level: row
+            .get::<_, Option<String>>(22)?
+            .as_deref()
+            .and_then(PriorityLevel::parse),
+        escalated: row.get(23)?,
+        customer: row.get(24)?,
+        environment: row.get(25)?,
+        components: split_list(row.get(26)?),
+        fix_versions: split_list(row.get(27)?),
+        affected_versions: split_list(row.get(28)?),
+    })
+}
+
+/// Every column of `tickets`, which `tickets_archive` mirrors. The archive
+/// is copied to and unioned with by name, so a column a migration appends to
+/// the two tables in a different order can't shift values.
+pub(crate) const TICKET_TABLE_COLUMNS: &str = "id, jira_key, summary, status, priority, \
+     issue_type, assignee, reporter, created_at, updated_at, resolved_at, labels, project_key, \
+     category, source, custom_fields, account_id, duplicate_of, story_points, sprint, due_date, \
+     content_hash, resolution_hours, calendar_resolution_hours, category_confidence, \
+     status_category, priority_level, resolution_derived, escalated, customer, environment";
+
+/// Table expression to select `filter`'s tickets from. Archived tickets are
+/// unioned in under the `tickets` name, so column references still resolve.
+pub(crate) fn tickets_table(filter: &TicketFilter) -> String {
+    if filter.include_archived {
+        format!(
+            "(SELECT {cols} FROM tickets UNION ALL SELECT {cols} FROM tickets_archive) AS tickets",
+            cols = TICKET_TABLE_COLUMNS
+        )
+    } else {
+        "tickets".to_string()
+    }
+}
+
+/// What `upsert_ticket` did with a ticket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertOutcome {
+    Inserted,
+    Updated,
+    /// Identical to the stored row (its content hash matched), so nothing was written.
+    Unchanged,
+}
+
+/// Tally of `UpsertOutcome`s over a batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UpsertCounts {
+    pub inserted: u32,
+    pub updated: u32,
+    pub unchanged: u32,
+}
+
+impl UpsertCounts {
+    pub fn record(&mut self, outcome: UpsertOutcome) {
+        match outcome {
+            UpsertOutcome::Inserted => self.inserted += 1,
+            UpsertOutcome::Updated => self.updated += 1,
+            UpsertOutcome::Unchanged => self.unchanged += 1,
+        }
+    }
+}
+
+/// Stable FNV-1a hash of everything an upsert writes, so unchanged tickets
+/// can be skipped without comparing columns one by one.
+fn ticket_content_hash(ticket: &Ticket) -> String {
+    let mut content = ticket.clone();
+    content.id = 0;
+    let bytes = serde_json::to_vec(&content).unwrap_or_default();
+    format!("{:016x}", fnv1a(&bytes))
+}
+
+/// Inserts or updates a ticket. Rows whose content hash matches are left
+/// untouched, so repeat syncs don't rewrite unchanged tickets. The status
+/// category decides whether the ticket is resolved (see `apply_status_category`),
+/// and the priority mappings its `priority_level` (see `apply_priority_level`).
+pub fn upsert_ticket(conn: &Connection, ticket: &Ticket) -> Result<UpsertOutcome, AppError> {
+    let mut ticket = ticket.clone();
+    let resolution_derived = apply_status_category(conn, &mut ticket)?;
+    apply_priority_level(conn, &mut ticket)?;
+    let ticket = &ticket;
+    let content_hash = ticket_content_hash(ticket);
+    let stored_hash: Option<Option<String>> = conn
+        .query_row(
+            "SELECT content_hash FROM tickets WHERE jira_key = ?1",
+            params![ticket.jira_key],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(DbError::from)?;
+    if stored_hash
+        .as_ref()
+        .is_some_and(|stored| stored.as_deref() == Some(content_hash.as_str()))
+    {
+        return Ok(UpsertOutcome::Unchanged);
+    }
+
+    let (business_hours, calendar_hours) = stored_resolution_hours(
+        &ticket.created_at,
+        ticket.resolved_at.as_deref(),
+        &project_schedule(conn, &ticket.project_key)?,
+    );
     conn.execute(
         r#"
         INSERT INTO tickets (
             jira_key, summary, status, priority, issue_type, assignee, reporter,
-            created_at, updated_at, resolved_at, labels, project_key, category
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+            created_at, updated_at, resolved_at, labels, project_key, category, source,
+            custom_fields, account_id, story_points, sprint, due_date, content_hash,
+            resolution_hours, calendar_resolution_hours, category_confidence, status_category,
+            priority_level, escalated, customer, environment, resolution_derived
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18,
+            ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29)
         ON CONFLICT(jira_key) DO UPDATE SET
             summary = excluded.summary,
             status = excluded.status,
+            status_category = excluded.status_category,
             priority = excluded.priority,
+            priority_level = excluded.priority_level,
             issue_type = excluded.issue_type,
             assignee = excluded.assignee,
             reporter = excluded.reporter,
             updated_at = excluded.updated_at,
             resolved_at = excluded.resolved_at,
             labels = excluded.labels,
-            category = excluded.category
+            category = excluded.category,
+            category_confidence = excluded.category_confidence,
+            custom_fields = excluded.custom_fields,
+            story_points = excluded.story_points,
+            sprint = excluded.sprint,
+            due_date = excluded.due_date,
+            escalated = excluded.escalated,
+            customer = excluded.customer,
+            environment = excluded.environment,
+            content_hash = excluded.content_hash,
+            resolution_hours = excluded.resolution_hours,
+            calendar_resolution_hours = excluded.calendar_resolution_hours,
+            account_id = COALESCE(excluded.account_id, tickets.account_id),
+            -- A rewrite that keeps the date keeps where it came from
+            resolution_derived = CASE WHEN excluded.resolved_at IS tickets.resolved_at
+                THEN tickets.resolution_derived ELSE excluded.resolution_derived END
         "#,
         params![
             ticket.jira_key,
@@ -40,41 +226,172 @@ pub fn upsert_ticket(conn: &Connection, ticket: &Ticket) -> Result<(), AppError>
             ticket.labels,
             ticket.project_key,
             ticket.category,
+            ticket.source,
+            custom_fields_json(&ticket.custom_fields),
+            ticket.account_id,
+            ticket.story_points,
+            ticket.sprint,
+            ticket.due_date,
+            content_hash,
+            business_hours,
+            calendar_hours,
+            ticket.category_confidence,
+            ticket.status_category.map(StatusCategory::as_str),
+            ticket.priority_level.map(PriorityLevel::as_str),
+            ticket.escalated,
+            ticket.customer,
+            ticket.environment,
+            resolution_derived,
         ],
     )
     .map_err(DbError::from)?;
 
+    if stored_hash.is_none() {
+        // An archived ticket that shows up again is live once more
+        conn.execute(
+            "DELETE FROM tickets_archive WHERE jira_key = ?1",
+            params![ticket.jira_key],
+        )
+        .map_err(DbError::from)?;
+    }
+    replace_ticket_list(
+        conn,
+        TicketList::Components,
+        &ticket.jira_key,
+        &ticket.components,
+    )?;
+    replace_ticket_list(
+        conn,
+        TicketList::FixVersions,
+        &ticket.jira_key,
+        &ticket.fix_versions,
+    )?;
+    replace_ticket_list(
+        conn,
+        TicketList::AffectedVersions,
+        &ticket.jira_key,
+        &ticket.affected_versions,
+    )?;
+
+    Ok(match stored_hash {
+        Some(_) => UpsertOutcome::Updated,
+        None => UpsertOutcome::Inserted,
+    })
+}
+
+/// Multi-valued ticket fields kept in their own `(ticket_key, value)` tables.
+#[derive(Clone, Copy)]
+enum TicketList {
+    Components,
+    FixVersions,
+    AffectedVersions,
+}
+
+impl TicketList {
+    const ALL: [TicketList; 3] = [
+        TicketList::Components,
+        TicketList::FixVersions,
+        TicketList::AffectedVersions,
+    ];
+
+    fn table_and_column(self) -> (&'static str, &'static str) {
+        match self {
+            TicketList::Components => ("ticket_components", "component"),
+            TicketList::FixVersions => ("ticket_fix_versions", "version"),
+            TicketList::AffectedVersions => ("ticket_affected_versions", "version"),
+        }
+    }
+}
+
+fn replace_ticket_list(
+    conn: &Connection,
+    list: TicketList,
+    key: &str,
+    values: &[String],
+) -> Result<(), AppError> {
+    let (table, column) = list.table_and_column();
+    conn.execute(
+        &format!("DELETE FROM {} WHERE ticket_key = ?1", table),
+        params![key],
+    )
+    .map_err(DbError::from)?;
+    for value in values {
+        conn.execute(
+            &format!(
+                "INSERT OR IGNORE INTO {} (ticket_key, {}) VALUES (?1, ?2)",
+                table, column
+            ),
+            params![key, value],
+        )
+        .map_err(DbError::from)?;
+    }
     Ok(())
 }
 
-pub fn get_tickets(conn: &Connection) -> Result<Vec<Ticket>, AppError> {
-    let mut stmt = conn
-        .prepare(
-            "SELECT id, jira_key, summary, status, priority, issue_type, assignee, reporter, \
-             created_at, updated_at, resolved_at, labels, project_key, category \
-             FROM tickets ORDER BY created_at DESC",
+pub fn get_ticket_by_key(conn: &Connection, jira_key: &str) -> Result<Option<Ticket>, AppError> {
+    conn.query_row(
+        &format!("SELECT {} FROM tickets WHERE jira_key = ?1", TICKET_COLUMNS),
+        params![jira_key],
+        map_ticket_row,
+    )
+    .optional()
+    .map_err(|e| DbError::from(e).into())
+}
+
+/// Writes a status change made from the app ahead of the next sync. The
+/// content hash is cleared so the next sync compares against Jira afresh.
+pub fn update_ticket_status(
+    conn: &Connection,
+    jira_key: &str,
+    status: &str,
+    resolved_at: Option<&str>,
+) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE tickets SET status = ?2, resolved_at = ?3, status_category = NULL,
+             content_hash = NULL
+         WHERE jira_key = ?1",
+        params![jira_key, status, resolved_at],
+    )
+    .map_err(DbError::from)?;
+    refresh_resolution_hours(
+        conn,
+        "tickets",
+        RefreshScope::Ticket(jira_key),
+        &project_schedules(conn)?,
+    )
+}
+
+/// Removes a ticket by key; returns whether a row existed.
+pub fn delete_ticket(conn: &Connection, jira_key: &str) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute("DELETE FROM tickets WHERE jira_key = ?1", params![jira_key])
+        .map_err(DbError::from)?;
+    for list in TicketList::ALL {
+        replace_ticket_list(conn, list, jira_key, &[])?;
+    }
+    replace_attachments(conn, jira_key, &[])?;
+    for table in ["local_changes", "ticket_changes"] {
+        conn.execute(
+            &format!("DELETE FROM {} WHERE ticket_key = ?1", table),
+            params![jira_key],
         )
         .map_err(DbError::from)?;
+    }
+    Ok(deleted > 0)
+}
+
+pub fn get_tickets(conn: &Connection, filter: &TicketFilter) -> Result<Vec<Ticket>, AppError> {
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM {table} WHERE {} ORDER BY created_at DESC",
+            TICKET_COLUMNS, clause
+        ))
+        .map_err(DbError::from)?;
 
     let tickets = stmt
-        .query_map([], |row| {
-            Ok(Ticket {
-                id: row.get(0)?,
-                jira_key: row.get(1)?,
-                summary: row.get(2)?,
-                status: row.get(3)?,
-                priority: row.get(4)?,
-                issue_type: row.get(5)?,
-                assignee: row.get(6)?,
-                reporter: row.get(7)?,
-                created_at: row.get(8)?,
-                updated_at: row.get(9)?,
-                resolved_at: row.get(10)?,
-                labels: row.get(11)?,
-                project_key: row.get(12)?,
-                category: row.get(13)?,
-            })
-        })
+        .query_map(params_from_iter(values), map_ticket_row)
         .map_err(DbError::from)?
         .collect::<Result<Vec<_>, _>>()
         .map_err(DbError::from)?;
@@ -82,68 +399,219 @@ pub fn get_tickets(conn: &Connection) -> Result<Vec<Ticket>, AppError> {
     Ok(tickets)
 }
 
-pub fn get_aggregations(conn: &Connection) -> Result<AggregationResult, AppError> {
-    let tickets_by_status = get_count_by_field(conn, "status")?;
-    let tickets_by_priority = get_count_by_field(conn, "priority")?;
-    let tickets_by_category = get_count_by_field(conn, "category")?;
-    let tickets_over_time = get_tickets_over_time(conn)?;
-    let resolution_time_by_priority = get_resolution_time_by_priority(conn)?;
-    let summary = get_summary_stats(conn)?;
-
-    Ok(AggregationResult {
-        tickets_by_status,
-        tickets_by_priority,
-        tickets_by_category,
-        tickets_over_time,
-        resolution_time_by_priority,
-        summary,
-    })
+/// Number of tickets matching `filter`.
+pub fn count_tickets(conn: &Connection, filter: &TicketFilter) -> Result<u32, AppError> {
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let count = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM {table} WHERE {}", clause),
+            params_from_iter(values),
+            |row| row.get(0),
+        )
+        .map_err(DbError::from)?;
+    Ok(count)
 }
 
-fn get_count_by_field(conn: &Connection, field: &str) -> Result<Vec<CountEntry>, AppError> {
-    // Whitelist of allowed field names to prevent SQL injection
-    let allowed_fields = ["status", "priority", "category"];
-    if !allowed_fields.contains(&field) {
-        return Err(AppError::Internal(format!("Invalid field name: {}", field)));
+/// Ticket fields `get_tickets_projection` can select, with the SQL for each;
+/// keep in step with `TICKET_COLUMNS`.
+const PROJECTABLE_FIELDS: [(&str, &str); 25] = [
+    ("id", "id"),
+    ("jira_key", "jira_key"),
+    ("summary", "summary"),
+    ("status", "status"),
+    ("priority", "priority"),
+    ("issue_type", "issue_type"),
+    ("assignee", "assignee"),
+    ("reporter", "reporter"),
+    ("created_at", "created_at"),
+    ("updated_at", "updated_at"),
+    ("resolved_at", "resolved_at"),
+    ("labels", "labels"),
+    ("project_key", "project_key"),
+    ("category", "category"),
+    ("source", "source"),
+    ("custom_fields", "custom_fields"),
+    ("account_id", "account_id"),
+    ("story_points", "story_points"),
+    ("sprint", "sprint"),
+    ("due_date", "due_date"),
+    ("category_confidence", "category_confidence"),
+    ("status_category", "status_category"),
+    ("priority_level", "priority_level"),
+    ("escalated", "escalated"),
+    ("customer", "customer"),
+    ("environment", "environment"),
+    (
+        "components",
+        "(SELECT group_concat(component, char(31)) FROM ticket_components \
+         WHERE ticket_key = tickets.jira_key)",
+    ),
+    (
+        "fix_versions",
+        "(SELECT group_concat(version, char(31)) FROM ticket_fix_versions \
+         WHERE ticket_key = tickets.jira_key)",
+    ),
+    (
+        "affected_versions",
+        "(SELECT group_concat(version, char(31)) FROM ticket_affected_versions \
+         WHERE ticket_key = tickets.jira_key)",
+    ),
+];
+
+/// `filter`'s tickets in `get_tickets` order, with only the named `fields`
+/// of each. Values have the same JSON shape as in a full `Ticket`.
+pub fn get_tickets_projection(
+    conn: &Connection,
+    fields: &[String],
+    filter: &TicketFilter,
+) -> Result<Vec<serde_json::Map<String, serde_json::Value>>, AppError> {
+    if fields.is_empty() {
+        return Err(AppError::Config("No ticket fields requested".to_string()));
     }
+    let columns = fields
+        .iter()
+        .map(|field| {
+            PROJECTABLE_FIELDS
+                .iter()
+                .find(|(name, _)| name == field)
+                .map(|(_, column)| *column)
+                .ok_or_else(|| AppError::Config(format!("Unknown ticket field: {}", field)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
-    // Safe to use now that field is validated
-    let query = format!(
-        "SELECT COALESCE({}, 'Uncategorized') as name, COUNT(*) as count FROM tickets GROUP BY {} ORDER BY count DESC",
-        field, field
-    );
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM {table} WHERE {} ORDER BY created_at DESC",
+            columns.join(", "),
+            clause
+        ))
+        .map_err(DbError::from)?;
 
-    let mut stmt = conn.prepare(&query).map_err(DbError::from)?;
-    let entries = stmt
-        .query_map([], |row| {
-            Ok(CountEntry {
-                name: row.get(0)?,
-                count: row.get(1)?,
-            })
+    let rows = stmt
+        .query_map(params_from_iter(values), |row| {
+            fields
+                .iter()
+                .enumerate()
+                .map(|(idx, field)| {
+                    let value = match field.as_str() {
+                        "components" | "fix_versions" | "affected_versions" => {
+                            split_list(row.get(idx)?).into()
+                        }
+                        "escalated" => row.get::<_, bool>(idx)?.into(),
+                        _ => sql_to_json(row.get::<_, Value>(idx)?),
+                    };
+                    Ok((field.clone(), value))
+                })
+                .collect::<rusqlite::Result<serde_json::Map<_, _>>>()
         })
         .map_err(DbError::from)?
         .collect::<Result<Vec<_>, _>>()
         .map_err(DbError::from)?;
 
-    Ok(entries)
+    Ok(rows)
+}
+
+/// One page of `filter`'s tickets in `get_tickets` order, for streaming
+/// large result sets. Ties on `created_at` are broken by key so pages never
+/// overlap.
+pub fn get_tickets_page(
+    conn: &Connection,
+    filter: &TicketFilter,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<Ticket>, AppError> {
+    let (clause, mut values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    values.push(Value::Integer(i64::from(limit)));
+    values.push(Value::Integer(i64::from(offset)));
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM {table} WHERE {} ORDER BY created_at DESC, jira_key LIMIT ? OFFSET ?",
+            TICKET_COLUMNS, clause
+        ))
+        .map_err(DbError::from)?;
+
+    let tickets = stmt
+        .query_map(params_from_iter(values), map_ticket_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(tickets)
+}
+
+pub fn get_aggregations(
+    conn: &Connection,
+    filter: &TicketFilter,
+    mode: DurationMode,
+) -> Result<AggregationResult, AppError> {
+    let tickets_by_status = get_count_by(conn, &Dimension::Status, filter)?;
+    let tickets_by_priority = get_count_by(conn, &Dimension::Priority, filter)?;
+    let tickets_by_category = get_count_by(conn, &Dimension::Category, filter)?;
+    let tickets_by_local_tag = get_count_by_local_tag(conn, filter)?;
+    let tickets_by_component = get_count_by(conn, &Dimension::Component, filter)?;
+    let tickets_over_time = get_tickets_over_time(conn, filter)?;
+    let resolution_time_by_priority = get_resolution_time_by_priority(conn, filter, mode)?;
+    let resolution_time_by_assignee =
+        get_resolution_time_by_assignee(conn, filter, mode, MIN_ASSIGNEE_SAMPLES)?;
+    let resolution_time_by_component = get_resolution_time_by_component(conn, filter, mode)?;
+    let today = chrono::Utc::now().date_naive();
+    let created_resolved_ratio = rolling_created_resolved(
+        &get_weekly_created_resolved(conn, filter, today, ratio_weeks(RATIO_TREND_WEEKS))?,
+        today,
+        RATIO_TREND_WEEKS,
+    );
+    let velocity_by_sprint = get_velocity_by_sprint(conn, filter, VELOCITY_SPRINTS)?;
+    let points_by_assignee = get_points_by_assignee(conn, filter)?;
+    let tickets_by_customer = get_tickets_by_customer(conn, filter, mode)?;
+    let tickets_by_affected_version = get_affected_version_stats(conn, filter, mode)?;
+    let summary = get_summary_stats(conn, filter, mode)?;
+
+    Ok(AggregationResult {
+        tickets_by_status,
+        tickets_by_priority,
+        tickets_by_category,
+        tickets_by_local_tag,
+        tickets_by_component,
+        tickets_over_time,
+        resolution_time_by_priority,
+        resolution_time_by_assignee,
+        resolution_time_by_component,
+        created_resolved_ratio,
+        velocity_by_sprint,
+        points_by_assignee,
+        tickets_by_customer,
+        tickets_by_affected_version,
+        summary,
+        // Filled in by `AggregationCache`, which holds the registry
+        custom_metrics: Vec::new(),
+    })
 }
 
-fn get_tickets_over_time(conn: &Connection) -> Result<Vec<TimeSeriesEntry>, AppError> {
+fn get_tickets_over_time(
+    conn: &Connection,
+    filter: &TicketFilter,
+) -> Result<Vec<TimeSeriesEntry>, AppError> {
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(filter);
     // Group created/resolved independently by month, then merge.
     // This avoids undercounting resolved issues that were created in a different month.
     let mut stmt = conn
-        .prepare(
+        .prepare(&format!(
             r#"
         WITH created AS (
             SELECT strftime('%Y-%m', created_at) AS month, COUNT(*) AS created_count
-            FROM tickets
-            WHERE created_at IS NOT NULL
+            FROM {table}
+            WHERE created_at IS NOT NULL AND {clause}
             GROUP BY month
         ),
         resolved AS (
             SELECT strftime('%Y-%m', resolved_at) AS month, COUNT(*) AS resolved_count
-            FROM tickets
-            WHERE resolved_at IS NOT NULL
+            FROM {table}
+            WHERE resolved_at IS NOT NULL AND {clause}
             GROUP BY month
         ),
         months AS (
@@ -165,16 +633,488 @@ fn get_tickets_over_time(conn: &Connection) -> Result<Vec<TimeSeriesEntry>, AppE
         SELECT month, created_count, resolved_count
         FROM combined
         ORDER BY month ASC
-        "#,
+        "#
+        ))
+        .map_err(DbError::from)?;
+
+    // The predicate appears once per CTE, so its parameters are bound twice
+    let entries = stmt
+        .query_map(
+            params_from_iter(values.iter().chain(values.iter())),
+            |row| {
+                Ok(TimeSeriesEntry {
+                    date: row.get(0)?,
+                    created: row.get(1)?,
+                    resolved: row.get(2)?,
+                })
+            },
         )
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
         .map_err(DbError::from)?;
 
+    Ok(entries)
+}
+
+/// Assignees with fewer resolved tickets are left out of the dashboard's
+/// per-assignee report, since a couple of tickets says little.
+pub const MIN_ASSIGNEE_SAMPLES: u32 = 3;
+
+/// Weeks of history in the dashboard's created/resolved ratio trend.
+const RATIO_TREND_WEEKS: usize = 12;
+/// Each ratio point covers this many weeks ending on it.
+const RATIO_WINDOW_WEEKS: usize = 4;
+
+/// Tickets created and resolved per week before a given day. Index `w`
+/// counts the days `7w` to `7w + 6` back, by each timestamp's calendar date.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WeeklyCounts {
+    pub created: Vec<u32>,
+    pub resolved: Vec<u32>,
+}
+
+/// Weeks of counts a `points`-long rolling ratio trend reads.
+pub fn ratio_weeks(points: usize) -> usize {
+    points + RATIO_WINDOW_WEEKS - 1
+}
+
+/// `WeeklyCounts` of `filter`'s tickets over the `weeks` weeks up to `today`.
+pub fn get_weekly_created_resolved(
+    conn: &Connection,
+    filter: &TicketFilter,
+    today: NaiveDate,
+    weeks: usize,
+) -> Result<WeeklyCounts, AppError> {
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let today_text = today.format("%Y-%m-%d").to_string();
+    let first_day = (today - chrono::Duration::days(weeks as i64 * 7 - 1))
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let weekly = |column: &str| -> Result<Vec<u32>, AppError> {
+        // Only the date part is compared, since Jira offsets like +0100 aren't SQLite times
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT CAST((julianday(?1) - julianday(substr({column}, 1, 10))) / 7 AS INTEGER)
+                     AS weeks_back, COUNT(*)
+                 FROM {table}
+                 WHERE substr({column}, 1, 10) BETWEEN ?2 AND ?1 AND {clause}
+                 GROUP BY weeks_back"
+            ))
+            .map_err(DbError::from)?;
+        let params = [
+            Value::Text(today_text.clone()),
+            Value::Text(first_day.clone()),
+        ];
+        let mut series = vec![0; weeks];
+        for row in stmt
+            .query_map(
+                params_from_iter(params.iter().chain(values.iter())),
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, u32>(1)?)),
+            )
+            .map_err(DbError::from)?
+        {
+            let (week, count) = row.map_err(DbError::from)?;
+            if let Some(slot) = series.get_mut(week as usize) {
+                *slot = count;
+            }
+        }
+        Ok(series)
+    };
+
+    Ok(WeeklyCounts {
+        created: weekly("created_at")?,
+        resolved: weekly("resolved_at")?,
+    })
+}
+
+/// Rolling 4-week created/resolved ratio, one point per week for the `points`
+/// weeks ending on `today`, oldest first. A ratio above 1 means the backlog
+/// grew. `counts` needs `ratio_weeks(points)` weeks for the oldest point to
+/// see a full window.
+pub fn rolling_created_resolved(
+    counts: &WeeklyCounts,
+    today: NaiveDate,
+    points: usize,
+) -> Vec<RatioEntry> {
+    let window_sum = |series: &[u32], weeks_back: usize| -> u32 {
+        series
+            .iter()
+            .skip(weeks_back)
+            .take(RATIO_WINDOW_WEEKS)
+            .sum()
+    };
+
+    (0..points)
+        .rev()
+        .map(|weeks_back| {
+            let end = today - chrono::Duration::weeks(weeks_back as i64);
+            let created = window_sum(&counts.created, weeks_back);
+            let resolved = window_sum(&counts.resolved, weeks_back);
+            RatioEntry {
+                week: end.format("%G-W%V").to_string(),
+                created,
+                resolved,
+                ratio: (resolved > 0).then(|| f64::from(created) / f64::from(resolved)),
+            }
+        })
+        .collect()
+}
+
+fn get_resolution_time_by_priority(
+    conn: &Connection,
+    filter: &TicketFilter,
+    mode: DurationMode,
+) -> Result<Vec<AvgEntry>, AppError> {
+    let mut entries = get_resolution_time_by_field(conn, "priority", filter, mode)?;
+
+    // Canonical levels first, most urgent first; unmapped priorities after
+    entries.sort_by(|a, b| {
+        let rank = |name: &str| PriorityLevel::parse(name).map_or(usize::MAX, |l| l as usize);
+        rank(&a.name)
+            .cmp(&rank(&b.name))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(entries)
+}
+
+/// Assignees who resolved at least `min_samples` tickets, fastest median first.
+/// Unassigned tickets are left out.
+pub fn get_resolution_time_by_assignee(
+    conn: &Connection,
+    filter: &TicketFilter,
+    mode: DurationMode,
+    min_samples: u32,
+) -> Result<Vec<AvgEntry>, AppError> {
+    let mut entries: Vec<AvgEntry> = get_resolution_time_by_field(conn, "assignee", filter, mode)?
+        .into_iter()
+        .filter(|e| e.count >= min_samples)
+        .collect();
+    entries.sort_by(|a, b| {
+        a.median_hours
+            .total_cmp(&b.median_hours)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(entries)
+}
+
+/// Resolution time per Jira component, fastest median first.
+pub fn get_resolution_time_by_component(
+    conn: &Connection,
+    filter: &TicketFilter,
+    mode: DurationMode,
+) -> Result<Vec<AvgEntry>, AppError> {
+    let mut entries = get_resolution_time_by_field(conn, "component", filter, mode)?;
+    entries.sort_by(|a, b| {
+        a.median_hours
+            .total_cmp(&b.median_hours)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(entries)
+}
+
+fn get_resolution_time_by_field(
+    conn: &Connection,
+    field: &str,
+    filter: &TicketFilter,
+    mode: DurationMode,
+) -> Result<Vec<AvgEntry>, AppError> {
+    // Whitelist of allowed field names to prevent SQL injection
+    let allowed_fields = ["priority", "assignee", "component"];
+    if !allowed_fields.contains(&field) {
+        return Err(AppError::Internal(format!("Invalid field name: {}", field)));
+    }
+
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let hours = resolution_column(mode);
+    let query = match field {
+        // Group on the canonical level, so "P1" and "Blocker" land together
+        "priority" => format!(
+            "SELECT COALESCE(priority_level, priority), {hours} FROM {table} \
+             WHERE {hours} IS NOT NULL AND priority IS NOT NULL AND {clause} ORDER BY {hours}"
+        ),
+        "assignee" => format!(
+            "SELECT {field}, {hours} FROM {table} \
+             WHERE {hours} IS NOT NULL AND {field} IS NOT NULL AND {clause} ORDER BY {hours}"
+        ),
+        // A ticket counts once for each of its components
+        _ => format!(
+            "SELECT c.component, {hours} FROM {table} \
+             JOIN ticket_components c ON c.ticket_key = tickets.jira_key \
+             WHERE {hours} IS NOT NULL AND {clause} ORDER BY {hours}"
+        ),
+    };
+    let mut stmt = conn.prepare(&query).map_err(DbError::from)?;
+
+    let rows = stmt
+        .query_map(params_from_iter(values), |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+        })
+        .map_err(DbError::from)?;
+
+    // Rows arrive sorted, so each group's durations are too
+    let mut durations_by_name: HashMap<String, Vec<f64>> = HashMap::new();
+    for row in rows {
+        let (name, hours) = row.map_err(DbError::from)?;
+        durations_by_name.entry(name).or_default().push(hours);
+    }
+
+    Ok(durations_by_name
+        .into_iter()
+        .map(|(name, durations)| AvgEntry {
+            name,
+            avg_hours: average(&durations),
+            median_hours: median(&durations),
+            p75_hours: percentile(&durations, 75.0),
+            p90_hours: percentile(&durations, 90.0),
+            p95_hours: percentile(&durations, 95.0),
+            count: durations.len() as u32,
+        })
+        .collect())
+}
+
+pub fn get_summary_stats(
+    conn: &Connection,
+    filter: &TicketFilter,
+    mode: DurationMode,
+) -> Result<SummaryStats, AppError> {
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let total_tickets: u32 = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM {table} WHERE {}", clause),
+            params_from_iter(values.iter()),
+            |row| row.get(0),
+        )
+        .map_err(DbError::from)?;
+
+    let open_tickets: u32 = conn
+        .query_row(
+            &format!(
+                "SELECT COUNT(*) FROM {table} WHERE resolved_at IS NULL AND {}",
+                clause
+            ),
+            params_from_iter(values.iter()),
+            |row| row.get(0),
+        )
+        .map_err(DbError::from)?;
+
+    let resolved_tickets = total_tickets - open_tickets;
+
+    // Blockers that aren't stored locally count as unresolved
+    let blocked_tickets: u32 = conn
+        .query_row(
+            &format!(
+                "SELECT COUNT(*) FROM {table} WHERE resolved_at IS NULL AND {} \
+                 AND jira_key IN ( \
+                     SELECT l.inward_key FROM ticket_links l \
+                     LEFT JOIN tickets blocker ON blocker.jira_key = l.outward_key \
+                     WHERE {} AND blocker.resolved_at IS NULL)",
+                clause, BLOCKS_LINK_CONDITION
+            ),
+            params_from_iter(values.iter()),
+            |row| row.get(0),
+        )
+        .map_err(DbError::from)?;
+
+    let today = chrono::Utc::now()
+        .date_naive()
+        .format("%Y-%m-%d")
+        .to_string();
+    let overdue_tickets: u32 = conn
+        .query_row(
+            &format!(
+                "SELECT COUNT(*) FROM {table} \
+                 WHERE resolved_at IS NULL AND due_date < ? AND {}",
+                clause
+            ),
+            params_from_iter(std::iter::once(Value::Text(today)).chain(values.iter().cloned())),
+            |row| row.get(0),
+        )
+        .map_err(DbError::from)?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT updated_at FROM {table} WHERE resolved_at IS NULL AND {}",
+            clause
+        ))
+        .map_err(DbError::from)?;
+    let cutoff = stale_cutoff(STALE_TICKET_DAYS, chrono::Utc::now().naive_utc());
+    let mut stale_tickets = 0;
+    for updated_at in stmt
+        .query_map(params_from_iter(values.iter()), |row| {
+            row.get::<_, String>(0)
+        })
+        .map_err(DbError::from)?
+    {
+        if is_stale(&updated_at.map_err(DbError::from)?, cutoff) {
+            stale_tickets += 1;
+        }
+    }
+
+    let hours = resolution_column(mode);
+    let sorted_hours = |condition: &str| -> Result<Vec<f64>, AppError> {
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {hours} FROM {table} WHERE {hours} IS NOT NULL AND {condition} \
+                 AND {clause} ORDER BY {hours}"
+            ))
+            .map_err(DbError::from)?;
+        let durations = stmt
+            .query_map(params_from_iter(values.iter()), |row| row.get::<_, f64>(0))
+            .map_err(DbError::from)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(DbError::from)?;
+        Ok(durations)
+    };
+    let resolution_hours = sorted_hours("1")?;
+
+    let avg_resolution_hours = average(&resolution_hours);
+    let median_resolution_hours = median(&resolution_hours);
+    let p75_resolution_hours = percentile(&resolution_hours, 75.0);
+    let p90_resolution_hours = percentile(&resolution_hours, 90.0);
+    let p95_resolution_hours = percentile(&resolution_hours, 95.0);
+
+    let escalated_tickets: u32 = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM {table} WHERE escalated = 1 AND {clause}"),
+            params_from_iter(values.iter()),
+            |row| row.get(0),
+        )
+        .map_err(DbError::from)?;
+    let escalation_rate = if total_tickets == 0 {
+        0.0
+    } else {
+        f64::from(escalated_tickets) / f64::from(total_tickets)
+    };
+    let escalated_hours = sorted_hours("escalated = 1")?;
+
+    Ok(SummaryStats {
+        total_tickets,
+        open_tickets,
+        resolved_tickets,
+        stale_tickets,
+        blocked_tickets,
+        overdue_tickets,
+        avg_resolution_hours,
+        median_resolution_hours,
+        p75_resolution_hours,
+        p90_resolution_hours,
+        p95_resolution_hours,
+        escalated_tickets,
+        escalation_rate,
+        escalated_avg_resolution_hours: average(&escalated_hours),
+        escalated_median_resolution_hours: median(&escalated_hours),
+    })
+}
+
+/// Sprints shown in the dashboard's velocity chart.
+pub const VELOCITY_SPRINTS: usize = 10;
+
+/// Committed and completed story points for the `sprints` most recently
+/// active sprints, oldest first. A sprint's activity is the latest
+/// resolution (or update, for open tickets) among its tickets.
+pub fn get_velocity_by_sprint(
+    conn: &Connection,
+    filter: &TicketFilter,
+    sprints: usize,
+) -> Result<Vec<VelocityEntry>, AppError> {
+    let (clause, mut values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    values.push(Value::Integer(sprints as i64));
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT sprint,
+                    SUM(COALESCE(story_points, 0)),
+                    SUM(CASE WHEN resolved_at IS NOT NULL THEN COALESCE(story_points, 0) ELSE 0 END),
+                    COUNT(*),
+                    MAX(COALESCE(resolved_at, updated_at)) AS last_activity
+             FROM {table} WHERE sprint IS NOT NULL AND {}
+             GROUP BY sprint ORDER BY last_activity DESC LIMIT ?",
+            clause
+        ))
+        .map_err(DbError::from)?;
+    let mut entries = stmt
+        .query_map(params_from_iter(values), |row| {
+            Ok(VelocityEntry {
+                sprint: row.get(0)?,
+                committed_points: row.get(1)?,
+                completed_points: row.get(2)?,
+                tickets: row.get(3)?,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    entries.reverse();
+    Ok(entries)
+}
+
+/// Open and completed story points per assignee among estimated tickets,
+/// most open points first.
+pub fn get_points_by_assignee(
+    conn: &Connection,
+    filter: &TicketFilter,
+) -> Result<Vec<PointsEntry>, AppError> {
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT COALESCE(assignee, 'Unassigned') AS name,
+                    SUM(CASE WHEN resolved_at IS NULL THEN story_points ELSE 0 END) AS open_points,
+                    SUM(CASE WHEN resolved_at IS NOT NULL THEN story_points ELSE 0 END),
+                    COUNT(*)
+             FROM {table} WHERE story_points IS NOT NULL AND {}
+             GROUP BY name ORDER BY open_points DESC, name",
+            clause
+        ))
+        .map_err(DbError::from)?;
+    let entries = stmt
+        .query_map(params_from_iter(values), |row| {
+            Ok(PointsEntry {
+                assignee: row.get(0)?,
+                open_points: row.get(1)?,
+                completed_points: row.get(2)?,
+                tickets: row.get(3)?,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(entries)
+}
+
+/// Ticket volume, open tickets, and average resolution time per customer,
+/// busiest first. Tickets without a customer are left out.
+pub fn get_tickets_by_customer(
+    conn: &Connection,
+    filter: &TicketFilter,
+    mode: DurationMode,
+) -> Result<Vec<CustomerStats>, AppError> {
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let hours = resolution_column(mode);
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT customer, COUNT(*) AS total,
+                    SUM(CASE WHEN resolved_at IS NULL THEN 1 ELSE 0 END),
+                    COALESCE(AVG({hours}), 0)
+             FROM {table} WHERE customer IS NOT NULL AND customer != '' AND {clause}
+             GROUP BY customer ORDER BY total DESC, customer"
+        ))
+        .map_err(DbError::from)?;
     let entries = stmt
-        .query_map([], |row| {
-            Ok(TimeSeriesEntry {
-                date: row.get(0)?,
-                created: row.get(1)?,
-                resolved: row.get(2)?,
+        .query_map(params_from_iter(values), |row| {
+            Ok(CustomerStats {
+                customer: row.get(0)?,
+                total_tickets: row.get(1)?,
+                open_tickets: row.get(2)?,
+                avg_resolution_hours: row.get(3)?,
             })
         })
         .map_err(DbError::from)?
@@ -184,103 +1124,286 @@ fn get_tickets_over_time(conn: &Connection) -> Result<Vec<TimeSeriesEntry>, AppE
     Ok(entries)
 }
 
-fn get_resolution_time_by_priority(conn: &Connection) -> Result<Vec<AvgEntry>, AppError> {
+/// Tickets per affected version, with how many are open and their average
+/// resolution time, most tickets first. A ticket counts once for each
+/// version it affects.
+pub fn get_affected_version_stats(
+    conn: &Connection,
+    filter: &TicketFilter,
+    mode: DurationMode,
+) -> Result<Vec<AffectedVersionStats>, AppError> {
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let hours = resolution_column(mode);
     let mut stmt = conn
-        .prepare(
-            "SELECT priority, created_at, resolved_at FROM tickets WHERE resolved_at IS NOT NULL",
+        .prepare(&format!(
+            "SELECT v.version, COUNT(*) AS total,
+                    SUM(CASE WHEN resolved_at IS NULL THEN 1 ELSE 0 END),
+                    COALESCE(AVG({hours}), 0)
+             FROM {table} JOIN ticket_affected_versions v ON v.ticket_key = tickets.jira_key
+             WHERE {clause}
+             GROUP BY v.version ORDER BY total DESC, v.version"
+        ))
+        .map_err(DbError::from)?;
+    let entries = stmt
+        .query_map(params_from_iter(values), |row| {
+            Ok(AffectedVersionStats {
+                version: row.get(0)?,
+                total_tickets: row.get(1)?,
+                open_tickets: row.get(2)?,
+                avg_resolution_hours: row.get(3)?,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(entries)
+}
+
+/// Per-project totals for side-by-side comparison, sorted by project key,
+/// from the stored resolution hours. SLA compliance is the share of resolved
+/// tickets with a target (by priority level, in business hours) that were
+/// resolved within it; `None` when no resolved ticket in the project has a
+/// target.
+pub fn get_stats_by_project(
+    conn: &Connection,
+    filter: &TicketFilter,
+    mode: DurationMode,
+    sla_targets_hours: &BTreeMap<String, f64>,
+) -> Result<Vec<ProjectStats>, AppError> {
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let hours = resolution_column(mode);
+    let targets = if sla_targets_hours.is_empty() {
+        "SELECT NULL, NULL WHERE 0".to_string()
+    } else {
+        format!(
+            "VALUES {}",
+            vec!["(?, ?)"; sla_targets_hours.len()].join(", ")
         )
+    };
+    let target_values = sla_targets_hours
+        .iter()
+        .flat_map(|(priority, target)| [Value::Text(priority.clone()), Value::Real(*target)]);
+
+    let mut stmt = conn
+        .prepare(&format!(
+            r#"
+        WITH sla_targets(sla_priority, sla_hours) AS ({targets})
+        SELECT
+            project_key,
+            COUNT(*),
+            COUNT(CASE WHEN resolved_at IS NULL THEN 1 END),
+            COALESCE(AVG({hours}), 0),
+            COUNT(CASE WHEN sla_hours IS NOT NULL AND resolution_hours IS NOT NULL THEN 1 END),
+            COUNT(CASE WHEN resolution_hours <= sla_hours THEN 1 END)
+        FROM {table}
+        LEFT JOIN sla_targets ON sla_priority = COALESCE(priority_level, priority)
+        WHERE {clause}
+        GROUP BY project_key
+        ORDER BY project_key
+        "#
+        ))
         .map_err(DbError::from)?;
 
-    let rows = stmt
-        .query_map([], |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-            ))
+    let stats = stmt
+        .query_map(params_from_iter(target_values.chain(values)), |row| {
+            let total: u32 = row.get(1)?;
+            let open: u32 = row.get(2)?;
+            let sla_checked: u32 = row.get(4)?;
+            let sla_met: u32 = row.get(5)?;
+            Ok(ProjectStats {
+                project_key: row.get(0)?,
+                total_tickets: total,
+                open_tickets: open,
+                resolved_tickets: total - open,
+                avg_resolution_hours: row.get(3)?,
+                sla_compliance: (sla_checked > 0)
+                    .then(|| f64::from(sla_met) / f64::from(sla_checked)),
+            })
         })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
         .map_err(DbError::from)?;
 
-    let mut durations_by_priority: HashMap<String, Vec<f64>> = HashMap::new();
+    Ok(stats)
+}
 
-    for row in rows {
-        let (priority, created_at, resolved_at) = row.map_err(DbError::from)?;
-        if let Some(hours) = calculate_business_resolution_hours(&created_at, &resolved_at) {
-            durations_by_priority
-                .entry(priority)
-                .or_default()
-                .push(hours);
+/// Open tickets not updated in `days` days as of `now`, grouped by assignee
+/// (largest group first, unassigned tickets under "Unassigned").
+pub fn get_stale_tickets(
+    conn: &Connection,
+    days: u32,
+    now: NaiveDateTime,
+) -> Result<Vec<StaleTicketGroup>, AppError> {
+    let cutoff = stale_cutoff(days, now);
+    let mut groups: Vec<StaleTicketGroup> = Vec::new();
+
+    for ticket in get_tickets(conn, &TicketFilter::default())? {
+        if ticket.resolved_at.is_some() || !is_stale(&ticket.updated_at, cutoff) {
+            continue;
+        }
+        let assignee = ticket
+            .assignee
+            .clone()
+            .unwrap_or_else(|| "Unassigned".to_string());
+        match groups.iter_mut().find(|g| g.assignee == assignee) {
+            Some(group) => group.tickets.push(ticket),
+            None => groups.push(StaleTicketGroup {
+                assignee,
+                tickets: vec![ticket],
+            }),
         }
     }
-
-    let mut entries = durations_by_priority
-        .into_iter()
-        .map(|(priority, mut durations)| {
-            durations.sort_by(|a, b| a.total_cmp(b));
-            AvgEntry {
-                name: priority,
-                avg_hours: average(&durations),
-                median_hours: median(&durations),
-                count: durations.len() as u32,
-            }
-        })
-        .collect::<Vec<_>>();
-
-    // Sort by priority order
-    entries.sort_by_key(|e| match e.name.as_str() {
-        "Critical" => 1,
-        "High" => 2,
-        "Medium" => 3,
-        "Low" => 4,
-        _ => 5,
+
+    for group in &mut groups {
+        group
+            .tickets
+            .sort_by(|a, b| a.updated_at.cmp(&b.updated_at));
+    }
+    groups.sort_by(|a, b| {
+        b.tickets
+            .len()
+            .cmp(&a.tickets.len())
+            .then_with(|| a.assignee.cmp(&b.assignee))
     });
+    Ok(groups)
+}
 
-    Ok(entries)
+/// Days ahead covered by the deadlines widget.
+pub const DUE_SOON_DAYS: u32 = 7;
+
+/// Open tickets whose due date is before `today`, most overdue first.
+pub fn get_overdue_tickets(
+    conn: &Connection,
+    filter: &TicketFilter,
+    today: NaiveDate,
+) -> Result<Vec<Ticket>, AppError> {
+    query_due_tickets(conn, filter, "due_date < ?", &[today])
 }
 
-fn get_summary_stats(conn: &Connection) -> Result<SummaryStats, AppError> {
-    let total_tickets: u32 = conn
-        .query_row("SELECT COUNT(*) FROM tickets", [], |row| row.get(0))
-        .map_err(DbError::from)?;
+/// Open tickets due between `today` and `days` days from it (inclusive),
+/// soonest first.
+pub fn get_tickets_due_soon(
+    conn: &Connection,
+    filter: &TicketFilter,
+    days: u32,
+    today: NaiveDate,
+) -> Result<Vec<Ticket>, AppError> {
+    let until = today + chrono::Duration::days(i64::from(days));
+    query_due_tickets(conn, filter, "due_date BETWEEN ? AND ?", &[today, until])
+}
 
-    let open_tickets: u32 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM tickets WHERE resolved_at IS NULL",
-            [],
-            |row| row.get(0),
-        )
+fn query_due_tickets(
+    conn: &Connection,
+    filter: &TicketFilter,
+    due_condition: &str,
+    dates: &[NaiveDate],
+) -> Result<Vec<Ticket>, AppError> {
+    let (clause, filter_values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let values = dates
+        .iter()
+        .map(|date| Value::Text(date.format("%Y-%m-%d").to_string()))
+        .chain(filter_values);
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM {table}
+             WHERE resolved_at IS NULL AND due_date IS NOT NULL AND {} AND {}
+             ORDER BY due_date, jira_key",
+            TICKET_COLUMNS, due_condition, clause
+        ))
+        .map_err(DbError::from)?;
+    let tickets = stmt
+        .query_map(params_from_iter(values), map_ticket_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
         .map_err(DbError::from)?;
 
-    let resolved_tickets = total_tickets - open_tickets;
+    Ok(tickets)
+}
+
+/// Age buckets for `get_open_ticket_age_histogram`: label and upper bound in days.
+const AGE_BUCKETS: [(&str, Option<i64>); 5] = [
+    ("0–1d", Some(1)),
+    ("1–3d", Some(3)),
+    ("3–7d", Some(7)),
+    ("7–30d", Some(30)),
+    ("30d+", None),
+];
 
+/// Open tickets bucketed by age since creation, optionally for one priority.
+/// Every bucket is returned, including empty ones, in age order. Merged
+/// duplicates are left out, as in every other ticket count.
+pub fn get_open_ticket_age_histogram(
+    conn: &Connection,
+    priority: Option<&str>,
+    now: NaiveDateTime,
+) -> Result<Vec<CountEntry>, AppError> {
+    let filter = TicketFilter {
+        priorities: priority.into_iter().map(str::to_string).collect(),
+        ..Default::default()
+    };
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(&filter);
     let mut stmt = conn
-        .prepare("SELECT created_at, resolved_at FROM tickets WHERE resolved_at IS NOT NULL")
+        .prepare(&format!(
+            "SELECT created_at FROM {table} WHERE resolved_at IS NULL AND {}",
+            clause
+        ))
         .map_err(DbError::from)?;
-    let rows = stmt
-        .query_map([], |row| {
-            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
-        })
+    let created = stmt
+        .query_map(params_from_iter(values), |row| row.get::<_, String>(0))
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
         .map_err(DbError::from)?;
 
-    let mut resolution_hours = Vec::new();
-    for row in rows {
-        let (created_at, resolved_at) = row.map_err(DbError::from)?;
-        if let Some(hours) = calculate_business_resolution_hours(&created_at, &resolved_at) {
-            resolution_hours.push(hours);
-        }
+    let mut counts = [0u32; AGE_BUCKETS.len()];
+    for created_at in created {
+        let Some(created_at) = parse_timestamp(&created_at) else {
+            continue;
+        };
+        let age = now - created_at;
+        let bucket = AGE_BUCKETS
+            .iter()
+            .position(|(_, max_days)| match max_days {
+                Some(days) => age < chrono::Duration::days(*days),
+                None => true,
+            })
+            .unwrap_or(AGE_BUCKETS.len() - 1);
+        counts[bucket] += 1;
     }
-    resolution_hours.sort_by(|a, b| a.total_cmp(b));
 
-    let avg_resolution_hours = average(&resolution_hours);
-    let median_resolution_hours = median(&resolution_hours);
+    Ok(AGE_BUCKETS
+        .iter()
+        .zip(counts)
+        .map(|((name, _), count)| CountEntry {
+            name: name.to_string(),
+            count,
+        })
+        .collect())
+}
 
-    Ok(SummaryStats {
-        total_tickets,
-        open_tickets,
-        resolved_tickets,
-        avg_resolution_hours,
-        median_resolution_hours,
-    })
+fn stale_cutoff(days: u32, now: NaiveDateTime) -> NaiveDateTime {
+    now - chrono::Duration::days(i64::from(days))
+}
+
+fn is_stale(updated_at: &str, cutoff: NaiveDateTime) -> bool {
+    match parse_timestamp(updated_at) {
+        Some(updated) => updated < cutoff,
+        None => false,
+    }
+}
+
+/// Sync metadata key scoped to a ticket source. Jira keeps the unprefixed
+/// keys it has always used so existing databases stay incremental.
+pub fn source_metadata_key(source: &str, key: &str) -> String {
+    if source == "jira" {
+        key.to_string()
+    } else {
+        format!("{}:{}", source, key)
+    }
 }
 
 pub fn get_sync_metadata(conn: &Connection, key: &str) -> Result<Option<String>, AppError> {
@@ -304,13 +1427,115 @@ pub fn set_sync_metadata(conn: &Connection, key: &str, value: &str) -> Result<()
     Ok(())
 }
 
-fn calculate_business_resolution_hours(created_at: &str, resolved_at: &str) -> Option<f64> {
+/// Connectors that have nothing to add leave `custom_fields` empty; store
+/// those as an empty JSON object so the column always parses.
+fn custom_fields_json(custom_fields: &str) -> &str {
+    if custom_fields.trim().is_empty() {
+        "{}"
+    } else {
+        custom_fields
+    }
+}
+
+/// Column holding each ticket's resolution time under `mode`, filled in
+/// whenever the ticket is written.
+pub(crate) fn resolution_column(mode: DurationMode) -> &'static str {
+    match mode {
+        DurationMode::Business => "resolution_hours",
+        DurationMode::Calendar => "calendar_resolution_hours",
+    }
+}
+
+/// Business and calendar resolution hours to store for a ticket; `None`
+/// while it is open or when its timestamps don't parse.
+fn stored_resolution_hours(
+    created_at: &str,
+    resolved_at: Option<&str>,
+    schedule: &WorkSchedule,
+) -> (Option<f64>, Option<f64>) {
+    let hours = |mode| {
+        resolved_at
+            .and_then(|resolved_at| resolution_hours(created_at, resolved_at, mode, schedule))
+    };
+    (hours(DurationMode::Business), hours(DurationMode::Calendar))
+}
+
+/// Which rows `refresh_resolution_hours` recomputes.
+pub(crate) enum RefreshScope<'a> {
+    Ticket(&'a str),
+    Project(&'a str),
+    /// Resolved tickets still missing their hours.
+    Missing,
+}
+
+/// Recomputes the stored resolution hours of the rows of `table` in
+/// `scope`, with business hours following each project's schedule.
+pub(crate) fn refresh_resolution_hours(
+    conn: &Connection,
+    table: &str,
+    scope: RefreshScope,
+    schedules: &ProjectSchedules,
+) -> Result<(), AppError> {
+    let (condition, value) = match scope {
+        RefreshScope::Ticket(key) => ("jira_key = ?1", Some(key)),
+        RefreshScope::Project(project) => ("project_key = ?1", Some(project)),
+        RefreshScope::Missing => ("resolved_at IS NOT NULL AND resolution_hours IS NULL", None),
+    };
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT jira_key, created_at, resolved_at, project_key FROM {table} WHERE {condition}"
+        ))
+        .map_err(DbError::from)?;
+    let rows = stmt
+        .query_map(params_from_iter(value), |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    for (jira_key, created_at, resolved_at, project_key) in rows {
+        let (business_hours, calendar_hours) = stored_resolution_hours(
+            &created_at,
+            resolved_at.as_deref(),
+            schedules.get(&project_key),
+        );
+        conn.execute(
+            &format!(
+                "UPDATE {table} SET resolution_hours = ?2, calendar_resolution_hours = ?3 \
+                 WHERE jira_key = ?1"
+            ),
+            params![jira_key, business_hours, calendar_hours],
+        )
+        .map_err(DbError::from)?;
+    }
+    Ok(())
+}
+
+/// Hours from `created_at` to `resolved_at` under `mode`, business hours
+/// following `schedule`.
+pub(crate) fn resolution_hours(
+    created_at: &str,
+    resolved_at: &str,
+    mode: DurationMode,
+    schedule: &WorkSchedule,
+) -> Option<f64> {
     let created = DateTime::parse_from_rfc3339(created_at).ok()?.naive_utc();
     let resolved = DateTime::parse_from_rfc3339(resolved_at).ok()?.naive_utc();
-    business_hours_between(created, resolved, 9, 17).ok()
+    match mode {
+        DurationMode::Business => business_hours_between(created, resolved, schedule).ok(),
+        DurationMode::Calendar => {
+            (resolved >= created).then(|| (resolved - created).num_seconds() as f64 / 3600.0)
+        }
+    }
 }
 
-fn average(values: &[f64]) -> f64 {
+pub(crate) fn average(values: &[f64]) -> f64 {
     if values.is_empty() {
         return 0.0;
     }
@@ -330,18 +1555,26 @@ fn median(sorted_values: &[f64]) -> f64 {
     }
 }
 
+/// Percentile `p` (0–100) of already-sorted values, interpolating between
+/// neighbours the same way `median` does for even counts.
+pub(crate) fn percentile(sorted_values: &[f64], p: f64) -> f64 {
+    if sorted_values.is_empty() {
+        return 0.0;
+    }
+
+    let rank = p / 100.0 * (sorted_values.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted_values[lower] + (sorted_values[upper] - sorted_values[lower]) * (rank - lower as f64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::initialize_database;
+    use crate::db::test_support::{self, setup_db};
+    use crate::models::DateRange;
     use rusqlite::Connection;
 
-    fn setup_db() -> Connection {
-        let conn = Connection::open_in_memory().expect("in-memory db");
-        initialize_database(&conn).expect("schema initialized");
-        conn
-    }
-
     fn sample_ticket(
         key: &str,
         priority: &str,
@@ -349,20 +1582,13 @@ mod tests {
         resolved_at: Option<&str>,
     ) -> Ticket {
         Ticket {
-            id: 0,
-            jira_key: key.to_string(),
-            summary: format!("Summary {}", key),
             status: "Done".to_string(),
             priority: priority.to_string(),
-            issue_type: "Task".to_string(),
-            assignee: None,
-            reporter: None,
             created_at: created_at.to_string(),
             updated_at: created_at.to_string(),
             resolved_at: resolved_at.map(|value| value.to_string()),
-            labels: String::new(),
             project_key: "TEST".to_string(),
-            category: None,
+            ..test_support::ticket(key)
         }
     }
 
@@ -396,7 +1622,8 @@ mod tests {
         )
         .expect("insert TEST-3");
 
-        let entries = get_tickets_over_time(&conn).expect("timeline aggregations");
+        let entries =
+            get_tickets_over_time(&conn, &TicketFilter::default()).expect("timeline aggregations");
         let by_month = entries
             .into_iter()
             .map(|entry| (entry.date, (entry.created, entry.resolved)))
@@ -441,7 +1668,12 @@ mod tests {
         )
         .expect("insert TEST-12");
 
-        let by_priority = get_resolution_time_by_priority(&conn).expect("priority stats");
+        let by_priority = get_resolution_time_by_priority(
+            &conn,
+            &TicketFilter::default(),
+            DurationMode::Business,
+        )
+        .expect("priority stats");
         let high = by_priority
             .iter()
             .find(|entry| entry.name == "High")
@@ -451,11 +1683,719 @@ mod tests {
         assert!((high.median_hours - 6.0).abs() < 1e-9);
         assert_eq!(high.count, 2);
 
-        let summary = get_summary_stats(&conn).expect("summary stats");
+        let summary = get_summary_stats(&conn, &TicketFilter::default(), DurationMode::Business)
+            .expect("summary stats");
         assert_eq!(summary.total_tickets, 3);
         assert_eq!(summary.open_tickets, 0);
         assert_eq!(summary.resolved_tickets, 3);
         assert!((summary.avg_resolution_hours - (14.0 / 3.0)).abs() < 1e-9);
         assert!((summary.median_resolution_hours - 4.0).abs() < 1e-9);
+        assert!((summary.p75_resolution_hours - 6.0).abs() < 1e-9);
+        assert!((summary.p90_resolution_hours - 7.2).abs() < 1e-9);
+        assert!((summary.p95_resolution_hours - 7.6).abs() < 1e-9);
+        assert!((high.p90_hours - 7.6).abs() < 1e-9);
+
+        // Calendar mode counts the weekend TEST-12 spent open (Fri 16:00 to Mon 10:00)
+        let summary = get_summary_stats(&conn, &TicketFilter::default(), DurationMode::Calendar)
+            .expect("calendar summary stats");
+        assert!((summary.avg_resolution_hours - 26.0).abs() < 1e-9);
+        assert!((summary.median_resolution_hours - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tickets_by_customer_counts_open_and_resolution_per_account() {
+        let conn = setup_db();
+        for (key, customer, resolved_at) in [
+            ("TEST-30", Some("Acme"), Some("2025-01-06T11:00:00Z")),
+            ("TEST-31", Some("Acme"), Some("2025-01-06T13:00:00Z")),
+            ("TEST-32", Some("Acme"), None),
+            ("TEST-33", Some("Globex"), None),
+            ("TEST-34", None, None),
+        ] {
+            let ticket = Ticket {
+                customer: customer.map(str::to_string),
+                ..sample_ticket(key, "High", "2025-01-06T09:00:00Z", resolved_at)
+            };
+            upsert_ticket(&conn, &ticket).expect("insert ticket");
+        }
+
+        let customers =
+            get_tickets_by_customer(&conn, &TicketFilter::default(), DurationMode::Calendar)
+                .expect("customer stats");
+        let rows: Vec<(&str, u32, u32, f64)> = customers
+            .iter()
+            .map(|c| {
+                (
+                    c.customer.as_str(),
+                    c.total_tickets,
+                    c.open_tickets,
+                    c.avg_resolution_hours,
+                )
+            })
+            .collect();
+        assert_eq!(rows, vec![("Acme", 3, 1, 3.0), ("Globex", 1, 1, 0.0)]);
+    }
+
+    #[test]
+    fn affected_version_stats_count_each_version_a_ticket_affects() {
+        let conn = setup_db();
+        for (key, versions, resolved_at) in [
+            ("TEST-40", vec!["2.0"], Some("2025-01-06T13:00:00Z")),
+            ("TEST-41", vec!["2.0", "2.1"], None),
+            ("TEST-42", vec!["2.1"], Some("2025-01-06T11:00:00Z")),
+            ("TEST-43", vec!["2.1"], None),
+            ("TEST-44", vec![], None),
+        ] {
+            let ticket = Ticket {
+                affected_versions: versions.into_iter().map(str::to_string).collect(),
+                environment: Some("Chrome on macOS".to_string()),
+                ..sample_ticket(key, "High", "2025-01-06T09:00:00Z", resolved_at)
+            };
+            upsert_ticket(&conn, &ticket).expect("insert ticket");
+        }
+
+        let stats =
+            get_affected_version_stats(&conn, &TicketFilter::default(), DurationMode::Calendar)
+                .expect("affected version stats");
+        let rows: Vec<(&str, u32, u32, f64)> = stats
+            .iter()
+            .map(|v| {
+                (
+                    v.version.as_str(),
+                    v.total_tickets,
+                    v.open_tickets,
+                    v.avg_resolution_hours,
+                )
+            })
+            .collect();
+        assert_eq!(rows, vec![("2.1", 3, 2, 2.0), ("2.0", 2, 1, 4.0)]);
+
+        let stored = get_ticket_by_key(&conn, "TEST-41")
+            .expect("lookup")
+            .expect("stored ticket");
+        assert_eq!(stored.affected_versions, vec!["2.0", "2.1"]);
+        assert_eq!(stored.environment.as_deref(), Some("Chrome on macOS"));
+    }
+
+    #[test]
+    fn summary_stats_cover_escalated_tickets_separately() {
+        let conn = setup_db();
+        for (key, resolved_at, escalated) in [
+            ("TEST-20", Some("2025-01-06T11:00:00Z"), false),
+            ("TEST-21", Some("2025-01-06T19:00:00Z"), true),
+            ("TEST-22", Some("2025-01-07T09:00:00Z"), true),
+            ("TEST-23", None, true),
+        ] {
+            let ticket = Ticket {
+                escalated,
+                ..sample_ticket(key, "High", "2025-01-06T09:00:00Z", resolved_at)
+            };
+            upsert_ticket(&conn, &ticket).expect("insert ticket");
+        }
+
+        let summary = get_summary_stats(&conn, &TicketFilter::default(), DurationMode::Calendar)
+            .expect("summary stats");
+        assert_eq!(summary.escalated_tickets, 3);
+        assert!((summary.escalation_rate - 0.75).abs() < 1e-9);
+        assert!((summary.escalated_avg_resolution_hours - 17.0).abs() < 1e-9);
+        assert!((summary.escalated_median_resolution_hours - 17.0).abs() < 1e-9);
+        assert!((summary.avg_resolution_hours - 12.0).abs() < 1e-9);
+
+        let stored = get_tickets(&conn, &TicketFilter::default()).expect("tickets");
+        assert_eq!(stored.iter().filter(|t| t.escalated).count(), 3);
+    }
+
+    #[test]
+    fn source_metadata_keys_are_scoped_except_for_jira() {
+        assert_eq!(source_metadata_key("jira", "last_sync_at"), "last_sync_at");
+        assert_eq!(
+            source_metadata_key("gitlab", "last_sync_at"),
+            "gitlab:last_sync_at"
+        );
+
+        let conn = setup_db();
+        set_sync_metadata(&conn, "last_sync_at", "jira-ts").expect("set jira");
+        assert_eq!(
+            get_sync_metadata(&conn, &source_metadata_key("gitlab", "last_sync_at"))
+                .expect("get gitlab"),
+            None
+        );
+    }
+
+    #[test]
+    fn upsert_keeps_ticket_source() {
+        let conn = setup_db();
+        let mut ticket = sample_ticket("group/app#1", "High", "2025-01-06T09:00:00Z", None);
+        ticket.source = "gitlab".to_string();
+        upsert_ticket(&conn, &ticket).expect("insert gitlab ticket");
+
+        let tickets = get_tickets(&conn, &TicketFilter::default()).expect("tickets");
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets[0].source, "gitlab");
+        assert_eq!(tickets[0].custom_fields, "{}");
+    }
+
+    #[test]
+    fn upsert_skips_unchanged_tickets() {
+        let conn = setup_db();
+        let mut ticket = sample_ticket("OPS-1", "High", "2025-01-06T09:00:00Z", None);
+
+        assert_eq!(
+            upsert_ticket(&conn, &ticket).expect("insert"),
+            UpsertOutcome::Inserted
+        );
+        assert_eq!(
+            upsert_ticket(&conn, &ticket).expect("repeat"),
+            UpsertOutcome::Unchanged
+        );
+        ticket.components = vec!["API".to_string()];
+        assert_eq!(
+            upsert_ticket(&conn, &ticket).expect("change"),
+            UpsertOutcome::Updated
+        );
+
+        // A local edit forces the next sync to write Jira's value back
+        update_ticket_status(&conn, "OPS-1", "Closed", None).expect("local edit");
+        assert_eq!(
+            upsert_ticket(&conn, &ticket).expect("resync"),
+            UpsertOutcome::Updated
+        );
+        let stored = get_ticket_by_key(&conn, "OPS-1")
+            .expect("query")
+            .expect("ticket exists");
+        assert_eq!(stored.status, ticket.status);
+        assert_eq!(stored.components, vec!["API".to_string()]);
+    }
+
+    #[test]
+    fn update_ticket_status_writes_status_and_resolution() {
+        let conn = setup_db();
+        let ticket = sample_ticket("OPS-1", "High", "2025-01-06T09:00:00Z", None);
+        upsert_ticket(&conn, &ticket).expect("insert");
+
+        update_ticket_status(&conn, "OPS-1", "Closed", Some("2025-01-07T09:00:00Z"))
+            .expect("update");
+        let updated = get_ticket_by_key(&conn, "OPS-1")
+            .expect("query")
+            .expect("ticket exists");
+        assert_eq!(updated.status, "Closed");
+        assert_eq!(updated.resolved_at.as_deref(), Some("2025-01-07T09:00:00Z"));
+
+        assert!(get_ticket_by_key(&conn, "OPS-404")
+            .expect("query")
+            .is_none());
+    }
+
+    #[test]
+    fn resolution_hours_are_stored_with_the_ticket() {
+        let conn = setup_db();
+        let stored_hours = |conn: &Connection| -> (Option<f64>, Option<f64>) {
+            conn.query_row(
+                "SELECT resolution_hours, calendar_resolution_hours FROM tickets \
+                 WHERE jira_key = 'OPS-1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("stored hours")
+        };
+        upsert_ticket(
+            &conn,
+            &sample_ticket("OPS-1", "High", "2025-01-06T09:00:00Z", None),
+        )
+        .expect("insert");
+        assert_eq!(stored_hours(&conn), (None, None));
+
+        update_ticket_status(&conn, "OPS-1", "Closed", Some("2025-01-07T09:00:00Z"))
+            .expect("update");
+        assert_eq!(stored_hours(&conn), (Some(8.0), Some(24.0)));
+
+        // Rows written before the columns existed are backfilled
+        conn.execute(
+            "UPDATE tickets SET resolution_hours = NULL, calendar_resolution_hours = NULL",
+            [],
+        )
+        .expect("clear");
+        refresh_resolution_hours(
+            &conn,
+            "tickets",
+            RefreshScope::Missing,
+            &ProjectSchedules::default(),
+        )
+        .expect("refresh");
+        assert_eq!(stored_hours(&conn), (Some(8.0), Some(24.0)));
+
+        update_ticket_status(&conn, "OPS-1", "Open", None).expect("reopen");
+        assert_eq!(stored_hours(&conn), (None, None));
+    }
+
+    #[test]
+    fn filter_combines_sets_ranges_and_text() {
+        let conn = setup_db();
+        for (key, priority, created_at, resolved_at, summary) in [
+            ("OPS-1", "High", "2025-01-06T09:00:00Z", None, "VPN drops"),
+            ("OPS-2", "Low", "2025-01-07T09:00:00Z", None, "vpn slow"),
+            (
+                "OPS-3",
+                "High",
+                "2025-02-03T09:00:00Z",
+                Some("2025-02-04T09:00:00Z"),
+                "VPN down",
+            ),
+            (
+                "OPS-4",
+                "High",
+                "2025-01-08T09:00:00Z",
+                None,
+                "Printer 100% jammed",
+            ),
+        ] {
+            let mut ticket = sample_ticket(key, priority, created_at, resolved_at);
+            ticket.summary = summary.to_string();
+            upsert_ticket(&conn, &ticket).expect("insert");
+        }
+        let keys = |filter: &TicketFilter| -> Vec<String> {
+            let mut keys: Vec<String> = get_tickets(&conn, filter)
+                .expect("tickets")
+                .into_iter()
+                .map(|t| t.jira_key)
+                .collect();
+            keys.sort();
+            keys
+        };
+
+        let vpn = TicketFilter {
+            query: Some("vpn".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(keys(&vpn), vec!["OPS-1", "OPS-2", "OPS-3"]);
+        assert_eq!(
+            keys(&TicketFilter {
+                priorities: vec!["High".to_string()],
+                ..vpn.clone()
+            }),
+            vec!["OPS-1", "OPS-3"]
+        );
+        assert_eq!(
+            keys(&vpn.clone().created_in(DateRange {
+                from: Some("2025-01-07".to_string()),
+                to: Some("2025-02-01".to_string()),
+            })),
+            vec!["OPS-2"]
+        );
+        assert_eq!(
+            keys(&TicketFilter::default().resolved_in(DateRange::since("2025-02-01"))),
+            vec!["OPS-3"]
+        );
+        // LIKE wildcards in the query are matched literally
+        assert_eq!(
+            keys(&TicketFilter {
+                query: Some("100%".to_string()),
+                ..Default::default()
+            }),
+            vec!["OPS-4"]
+        );
+        assert_eq!(
+            count_tickets(&conn, &TicketFilter::default().with_projects(["TEST"])).expect("count"),
+            4
+        );
+        assert_eq!(
+            count_tickets(&conn, &TicketFilter::default().with_projects(["OTHER"])).expect("count"),
+            0
+        );
+    }
+
+    #[test]
+    fn projection_returns_only_requested_fields() {
+        let conn = setup_db();
+        let mut ticket = sample_ticket("OPS-1", "High", "2025-01-06T09:00:00Z", None);
+        ticket.components = vec!["API".to_string(), "Billing".to_string()];
+        ticket.story_points = Some(3.0);
+        upsert_ticket(&conn, &ticket).expect("insert");
+        let fields = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        let rows = get_tickets_projection(
+            &conn,
+            &fields(&[
+                "jira_key",
+                "status",
+                "components",
+                "story_points",
+                "assignee",
+            ]),
+            &TicketFilter::default(),
+        )
+        .expect("projection");
+        assert_eq!(
+            serde_json::Value::Object(rows[0].clone()),
+            serde_json::json!({
+                "jira_key": "OPS-1",
+                "status": ticket.status,
+                "components": ["API", "Billing"],
+                "story_points": 3.0,
+                "assignee": null,
+            })
+        );
+
+        assert!(
+            get_tickets_projection(&conn, &fields(&["password"]), &TicketFilter::default())
+                .is_err()
+        );
+        assert!(get_tickets_projection(&conn, &[], &TicketFilter::default()).is_err());
+    }
+
+    #[test]
+    fn ticket_pages_cover_every_ticket_once() {
+        let conn = setup_db();
+        for (key, created_at) in [
+            ("OPS-1", "2025-01-06T09:00:00Z"),
+            ("OPS-2", "2025-01-07T09:00:00Z"),
+            ("OPS-3", "2025-01-07T09:00:00Z"),
+            ("OPS-4", "2025-01-08T09:00:00Z"),
+            ("OPS-5", "2025-01-09T09:00:00Z"),
+        ] {
+            upsert_ticket(&conn, &sample_ticket(key, "High", created_at, None)).expect("insert");
+        }
+        let filter = TicketFilter::default();
+
+        let mut keys = Vec::new();
+        for offset in (0..6).step_by(2) {
+            let page = get_tickets_page(&conn, &filter, 2, offset).expect("page");
+            keys.extend(page.into_iter().map(|ticket| ticket.jira_key));
+        }
+        assert_eq!(keys, vec!["OPS-5", "OPS-4", "OPS-2", "OPS-3", "OPS-1"]);
+        assert!(get_tickets_page(&conn, &filter, 2, 6)
+            .expect("page")
+            .is_empty());
+    }
+
+    #[test]
+    fn stale_tickets_are_grouped_by_assignee() {
+        let conn = setup_db();
+        for (key, assignee, updated_at, resolved_at) in [
+            ("OPS-1", Some("Dana"), "2025-01-01T09:00:00.000+0000", None),
+            ("OPS-2", Some("Dana"), "2025-01-02T09:00:00Z", None),
+            ("OPS-3", None, "2025-01-03T09:00:00Z", None),
+            ("OPS-4", Some("Sam"), "2025-01-14T09:00:00Z", None),
+            (
+                "OPS-5",
+                Some("Sam"),
+                "2025-01-01T09:00:00Z",
+                Some("2025-01-01T10:00:00Z"),
+            ),
+        ] {
+            let mut ticket = sample_ticket(key, "High", "2024-12-01T09:00:00Z", resolved_at);
+            ticket.assignee = assignee.map(|a| a.to_string());
+            ticket.updated_at = updated_at.to_string();
+            upsert_ticket(&conn, &ticket).expect("insert");
+        }
+
+        let now = chrono::NaiveDate::from_ymd_opt(2025, 1, 15)
+            .and_then(|d| d.and_hms_opt(9, 0, 0))
+            .expect("valid datetime");
+        let groups = get_stale_tickets(&conn, 7, now).expect("stale tickets");
+
+        let summary: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|g| {
+                (
+                    g.assignee.as_str(),
+                    g.tickets.iter().map(|t| t.jira_key.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Dana", vec!["OPS-1", "OPS-2"]),
+                ("Unassigned", vec!["OPS-3"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn due_date_queries_split_overdue_and_upcoming() {
+        let conn = setup_db();
+        for (key, due_date, resolved_at) in [
+            ("OPS-1", Some("2025-01-10"), None),
+            ("OPS-2", Some("2025-01-15"), None),
+            ("OPS-3", Some("2025-01-22"), None),
+            ("OPS-4", Some("2025-01-23"), None),
+            ("OPS-5", Some("2025-01-01"), Some("2025-01-02T09:00:00Z")),
+            ("OPS-6", None, None),
+        ] {
+            let mut ticket = sample_ticket(key, "High", "2025-01-01T09:00:00Z", resolved_at);
+            ticket.due_date = due_date.map(|d| d.to_string());
+            upsert_ticket(&conn, &ticket).expect("insert");
+        }
+        let today = NaiveDate::from_ymd_opt(2025, 1, 15).expect("valid date");
+        let keys = |tickets: Vec<Ticket>| -> Vec<String> {
+            tickets.into_iter().map(|t| t.jira_key).collect()
+        };
+
+        let overdue = get_overdue_tickets(&conn, &TicketFilter::default(), today).expect("overdue");
+        assert_eq!(keys(overdue), vec!["OPS-1"]);
+
+        let due_soon = get_tickets_due_soon(&conn, &TicketFilter::default(), DUE_SOON_DAYS, today)
+            .expect("due soon");
+        assert_eq!(keys(due_soon), vec!["OPS-2", "OPS-3"]);
+    }
+
+    #[test]
+    fn components_round_trip_and_aggregate() {
+        let conn = setup_db();
+        for (key, components, resolved_at) in [
+            (
+                "OPS-1",
+                vec!["Platform", "API"],
+                Some("2025-01-06T11:00:00Z"),
+            ),
+            ("OPS-2", vec!["Platform"], Some("2025-01-06T13:00:00Z")),
+            ("OPS-3", vec![], None),
+        ] {
+            let mut ticket = sample_ticket(key, "High", "2025-01-06T09:00:00Z", resolved_at);
+            ticket.components = components.into_iter().map(str::to_string).collect();
+            upsert_ticket(&conn, &ticket).expect("insert");
+        }
+
+        let stored = get_ticket_by_key(&conn, "OPS-1")
+            .expect("lookup")
+            .expect("stored");
+        assert_eq!(stored.components, vec!["API", "Platform"]);
+
+        let counts: Vec<(String, u32)> =
+            get_count_by(&conn, &Dimension::Component, &TicketFilter::default())
+                .expect("counts")
+                .into_iter()
+                .map(|e| (e.name, e.count))
+                .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("Platform".to_string(), 2),
+                ("API".to_string(), 1),
+                ("No component".to_string(), 1),
+            ]
+        );
+
+        let times = get_resolution_time_by_component(
+            &conn,
+            &TicketFilter::default(),
+            DurationMode::Calendar,
+        )
+        .expect("resolution times");
+        let names: Vec<(&str, f64)> = times
+            .iter()
+            .map(|e| (e.name.as_str(), e.median_hours))
+            .collect();
+        assert_eq!(names, vec![("API", 2.0), ("Platform", 3.0)]);
+
+        // Re-syncing without a component drops it
+        let mut ticket = sample_ticket("OPS-1", "High", "2025-01-06T09:00:00Z", None);
+        ticket.components = vec!["API".to_string()];
+        upsert_ticket(&conn, &ticket).expect("update");
+        let stored = get_ticket_by_key(&conn, "OPS-1")
+            .expect("lookup")
+            .expect("stored");
+        assert_eq!(stored.components, vec!["API"]);
+    }
+
+    #[test]
+    fn age_histogram_buckets_open_tickets() {
+        let conn = setup_db();
+        for (key, priority, created_at, resolved_at) in [
+            ("OPS-1", "High", "2025-01-15T08:00:00Z", None),
+            ("OPS-2", "High", "2025-01-13T09:00:00.000+0000", None),
+            ("OPS-3", "Low", "2025-01-10T09:00:00Z", None),
+            ("OPS-4", "High", "2024-11-01T09:00:00Z", None),
+            (
+                "OPS-5",
+                "High",
+                "2025-01-14T09:00:00Z",
+                Some("2025-01-14T10:00:00Z"),
+            ),
+        ] {
+            let ticket = sample_ticket(key, priority, created_at, resolved_at);
+            upsert_ticket(&conn, &ticket).expect("insert");
+        }
+        // Merged into OPS-1, so no longer counted on its own
+        upsert_ticket(
+            &conn,
+            &sample_ticket("OPS-6", "High", "2025-01-15T08:00:00Z", None),
+        )
+        .expect("insert");
+        conn.execute(
+            "UPDATE tickets SET duplicate_of = 'OPS-1' WHERE jira_key = 'OPS-6'",
+            [],
+        )
+        .expect("merge");
+        let now = chrono::NaiveDate::from_ymd_opt(2025, 1, 15)
+            .and_then(|d| d.and_hms_opt(9, 0, 0))
+            .expect("valid datetime");
+
+        let counts = |priority| -> Vec<u32> {
+            get_open_ticket_age_histogram(&conn, priority, now)
+                .expect("histogram")
+                .iter()
+                .map(|entry| entry.count)
+                .collect()
+        };
+        assert_eq!(counts(None), vec![1, 1, 1, 0, 1]);
+        assert_eq!(counts(Some("High")), vec![1, 1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn rolling_ratio_covers_trailing_four_weeks() {
+        let mut tickets = Vec::new();
+        // Two created and one resolved in the four weeks before now
+        for (key, created_at, resolved_at) in [
+            ("OPS-1", "2025-01-20T09:00:00Z", None),
+            (
+                "OPS-2",
+                "2025-01-10T09:00:00.000+0000",
+                Some("2025-01-22T09:00:00Z"),
+            ),
+            // Created before the latest window, resolved inside the previous one only
+            (
+                "OPS-3",
+                "2024-12-01T09:00:00Z",
+                Some("2024-12-30T09:00:00Z"),
+            ),
+        ] {
+            tickets.push(sample_ticket(key, "High", created_at, resolved_at));
+        }
+        let today = chrono::NaiveDate::from_ymd_opt(2025, 1, 29).expect("valid date");
+
+        let conn = setup_db();
+        for ticket in &tickets {
+            upsert_ticket(&conn, ticket).expect("insert");
+        }
+        let counts =
+            get_weekly_created_resolved(&conn, &TicketFilter::default(), today, ratio_weeks(2))
+                .expect("weekly counts");
+        assert_eq!(counts.created, vec![0, 1, 1, 0, 0]);
+        assert_eq!(counts.resolved, vec![0, 1, 0, 0, 1]);
+        let trend = rolling_created_resolved(&counts, today, 2);
+
+        assert_eq!(trend.len(), 2);
+        assert_eq!(trend[1].week, "2025-W05");
+        assert_eq!((trend[1].created, trend[1].resolved), (2, 1));
+        assert_eq!(trend[1].ratio, Some(2.0));
+        assert_eq!(trend[0].week, "2025-W04");
+        assert_eq!((trend[0].created, trend[0].resolved), (2, 2));
+        assert_eq!(trend[0].ratio, Some(1.0));
+    }
+
+    #[test]
+    fn assignee_report_applies_minimum_samples() {
+        let conn = setup_db();
+        for (key, assignee, resolved_at) in [
+            ("OPS-1", Some("Dana"), "2025-01-06T11:00:00Z"),
+            ("OPS-2", Some("Dana"), "2025-01-06T13:00:00Z"),
+            ("OPS-3", Some("Sam"), "2025-01-06T10:00:00Z"),
+            ("OPS-4", Some("Sam"), "2025-01-06T10:00:00Z"),
+            ("OPS-5", Some("Lee"), "2025-01-06T10:00:00Z"),
+            ("OPS-6", None, "2025-01-06T10:00:00Z"),
+            ("OPS-7", None, "2025-01-06T10:00:00Z"),
+        ] {
+            let mut ticket = sample_ticket(key, "High", "2025-01-06T09:00:00Z", Some(resolved_at));
+            ticket.assignee = assignee.map(|a| a.to_string());
+            upsert_ticket(&conn, &ticket).expect("insert");
+        }
+
+        let report = get_resolution_time_by_assignee(
+            &conn,
+            &TicketFilter::default(),
+            DurationMode::Business,
+            2,
+        )
+        .expect("assignee report");
+
+        let names: Vec<&str> = report.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["Sam", "Dana"]);
+        assert!((report[1].avg_hours - 3.0).abs() < 1e-9);
+        assert_eq!(report[1].count, 2);
+    }
+
+    #[test]
+    fn project_stats_include_sla_compliance() {
+        let conn = setup_db();
+        for (key, project, priority, resolved_at) in [
+            // 2 business hours against a 4h Critical target
+            ("OPS-1", "OPS", "Critical", Some("2025-01-06T11:00:00Z")),
+            // 6 business hours: breached
+            ("OPS-2", "OPS", "Critical", Some("2025-01-06T15:00:00Z")),
+            ("OPS-3", "OPS", "High", None),
+            ("WEB-1", "WEB", "Low", Some("2025-01-06T10:00:00Z")),
+        ] {
+            let mut ticket = sample_ticket(key, priority, "2025-01-06T09:00:00Z", resolved_at);
+            ticket.project_key = project.to_string();
+            upsert_ticket(&conn, &ticket).expect("insert");
+        }
+        let targets = BTreeMap::from([("Critical".to_string(), 4.0)]);
+
+        let stats = get_stats_by_project(
+            &conn,
+            &TicketFilter::default(),
+            DurationMode::Business,
+            &targets,
+        )
+        .expect("project stats");
+
+        assert_eq!(stats.len(), 2);
+        let ops = &stats[0];
+        assert_eq!(ops.project_key, "OPS");
+        assert_eq!(
+            (ops.total_tickets, ops.open_tickets, ops.resolved_tickets),
+            (3, 1, 2)
+        );
+        assert!((ops.avg_resolution_hours - 4.0).abs() < 1e-9);
+        assert_eq!(ops.sla_compliance, Some(0.5));
+        assert_eq!(stats[1].project_key, "WEB");
+        assert_eq!(stats[1].sla_compliance, None);
+    }
+
+    #[test]
+    fn velocity_and_points_sum_story_points() {
+        let conn = setup_db();
+        let estimated = |key: &str, sprint: &str, points: f64, resolved_at: Option<&str>| Ticket {
+            assignee: Some("Dana".to_string()),
+            story_points: Some(points),
+            sprint: Some(sprint.to_string()),
+            ..sample_ticket(key, "High", "2025-01-06T09:00:00+00:00", resolved_at)
+        };
+        for ticket in [
+            estimated("OPS-1", "Sprint 1", 3.0, Some("2025-01-10T09:00:00+00:00")),
+            estimated("OPS-2", "Sprint 1", 5.0, None),
+            estimated("OPS-3", "Sprint 2", 8.0, Some("2025-01-24T09:00:00+00:00")),
+            sample_ticket("OPS-4", "High", "2025-01-06T09:00:00+00:00", None),
+        ] {
+            upsert_ticket(&conn, &ticket).expect("ticket inserted");
+        }
+
+        let velocity = get_velocity_by_sprint(&conn, &TicketFilter::default(), VELOCITY_SPRINTS)
+            .expect("velocity");
+        let sprints: Vec<(&str, f64, f64)> = velocity
+            .iter()
+            .map(|v| (v.sprint.as_str(), v.committed_points, v.completed_points))
+            .collect();
+        assert_eq!(
+            sprints,
+            vec![("Sprint 1", 8.0, 3.0), ("Sprint 2", 8.0, 8.0)]
+        );
+        assert_eq!(
+            get_velocity_by_sprint(&conn, &TicketFilter::default(), 1)
+                .expect("velocity")
+                .len(),
+            1
+        );
+
+        let points = get_points_by_assignee(&conn, &TicketFilter::default()).expect("points");
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].assignee, "Dana");
+        assert_eq!(
+            (points[0].open_points, points[0].completed_points),
+            (5.0, 11.0)
+        );
+        assert_eq!(points[0].tickets, 3);
     }
 }
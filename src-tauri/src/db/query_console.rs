@@ -0,0 +1,197 @@
+use super::dataset::sql_to_json;
+use crate::errors::{AppError, DbError};
+use crate::models::{QueryColumn, QueryColumnType, QueryResult};
+use rusqlite::types::{Type, Value};
+use rusqlite::{Connection, ErrorCode, Statement};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Statements the console accepts, by first keyword.
+const ALLOWED_STATEMENTS: [&str; 4] = ["SELECT", "WITH", "VALUES", "EXPLAIN"];
+
+/// Up to `max_rows` rows of `width` values, and whether more were left.
+fn collect_rows(
+    stmt: &mut Statement,
+    width: usize,
+    max_rows: usize,
+) -> rusqlite::Result<(Vec<Vec<Value>>, bool)> {
+    let mut values = Vec::new();
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        if values.len() == max_rows {
+            return Ok((values, true));
+        }
+        values.push(
+            (0..width)
+                .map(|i| row.get::<_, Value>(i))
+                .collect::<Result<_, _>>()?,
+        );
+    }
+    Ok((values, false))
+}
+
+/// Runs one read-only statement typed by the user on `conn`, which should
+/// come from `open_readonly_database`. Returns at most `max_rows` rows and
+/// stops the query once `timeout` has passed.
+pub fn run_readonly_query(
+    conn: &Connection,
+    sql: &str,
+    max_rows: usize,
+    timeout: Duration,
+) -> Result<QueryResult, AppError> {
+    let keyword = sql
+        .trim_start()
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    if !ALLOWED_STATEMENTS.contains(&keyword.as_str()) {
+        return Err(AppError::Config(format!(
+            "Only {} statements can be run",
+            ALLOWED_STATEMENTS.join(", ")
+        )));
+    }
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|e| AppError::Config(format!("Invalid query: {}", e)))?;
+    if !stmt.readonly() {
+        return Err(AppError::Config(
+            "Only read-only queries can be run".to_string(),
+        ));
+    }
+    let names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+
+    // Interrupts the query from another thread once the time is up
+    let interrupt = conn.get_interrupt_handle();
+    let (done, finished) = mpsc::channel::<()>();
+    let watchdog = std::thread::spawn(move || {
+        if finished.recv_timeout(timeout) == Err(mpsc::RecvTimeoutError::Timeout) {
+            interrupt.interrupt();
+        }
+    });
+
+    let started = Instant::now();
+    let collected = collect_rows(&mut stmt, names.len(), max_rows);
+    done.send(()).ok();
+    watchdog.join().ok();
+
+    let (values, truncated) = collected.map_err(|e| match e.sqlite_error_code() {
+        Some(ErrorCode::OperationInterrupted) => AppError::Config(format!(
+            "Query stopped after {} seconds",
+            timeout.as_secs_f64()
+        )),
+        _ => DbError::from(e).into(),
+    })?;
+
+    let columns = names
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| QueryColumn {
+            name,
+            column_type: values
+                .iter()
+                .map(|row| row[i].data_type())
+                .find(|t| *t != Type::Null)
+                .map_or(QueryColumnType::Null, |t| match t {
+                    Type::Integer => QueryColumnType::Integer,
+                    Type::Real => QueryColumnType::Real,
+                    Type::Text => QueryColumnType::Text,
+                    Type::Blob => QueryColumnType::Blob,
+                    Type::Null => QueryColumnType::Null,
+                }),
+        })
+        .collect();
+
+    Ok(QueryResult {
+        columns,
+        rows: values
+            .into_iter()
+            .map(|row| row.into_iter().map(sql_to_json).collect())
+            .collect(),
+        truncated,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{initialize_database, open_database, open_readonly_database, upsert_ticket};
+    use crate::models::Ticket;
+
+    #[test]
+    fn only_bounded_read_only_queries_run() {
+        let dir = std::env::temp_dir().join(format!("ticketdash-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let path = dir.join("tickets.db");
+        let path_str = path.to_str().expect("utf-8 path");
+        let conn = open_database(path_str, None).expect("db");
+        initialize_database(&conn).expect("schema initialized");
+        for key in ["OPS-1", "OPS-2"] {
+            let ticket = Ticket {
+                jira_key: key.to_string(),
+                summary: format!("Summary {}", key),
+                story_points: (key == "OPS-1").then_some(3.0),
+                ..Default::default()
+            };
+            upsert_ticket(&conn, &ticket).expect("ticket");
+        }
+        drop(conn);
+
+        let conn = open_readonly_database(path_str, None).expect("read-only db");
+        let timeout = Duration::from_secs(5);
+        let result = run_readonly_query(
+            &conn,
+            "  select jira_key, story_points, NULL AS nothing FROM tickets ORDER BY jira_key",
+            1,
+            timeout,
+        )
+        .expect("select");
+        assert_eq!(
+            result.columns,
+            vec![
+                QueryColumn {
+                    name: "jira_key".to_string(),
+                    column_type: QueryColumnType::Text,
+                },
+                QueryColumn {
+                    name: "story_points".to_string(),
+                    column_type: QueryColumnType::Real,
+                },
+                QueryColumn {
+                    name: "nothing".to_string(),
+                    column_type: QueryColumnType::Null,
+                },
+            ]
+        );
+        assert_eq!(
+            result.rows,
+            vec![vec![
+                serde_json::json!("OPS-1"),
+                serde_json::json!(3.0),
+                serde_json::Value::Null
+            ]]
+        );
+        assert!(result.truncated);
+
+        for sql in [
+            "DELETE FROM tickets",
+            "PRAGMA query_only = OFF",
+            "ATTACH DATABASE 'x.db' AS x",
+            "WITH gone AS (SELECT 1) DELETE FROM tickets",
+        ] {
+            assert!(
+                run_readonly_query(&conn, sql, 10, timeout).is_err(),
+                "{}",
+                sql
+            );
+        }
+
+        let endless = "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n) \
+                       SELECT COUNT(*) FROM n";
+        assert!(run_readonly_query(&conn, endless, 10, Duration::from_millis(50)).is_err());
+
+        drop(conn);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
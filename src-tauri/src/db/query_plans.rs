@@ -0,0 +1,162 @@
+use super::queries::{resolution_column, tickets_table, TICKET_COLUMNS};
+use crate::errors::{AppError, DbError};
+use crate::models::{DurationMode, QueryPlan, TicketFilter};
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection};
+use std::collections::HashMap;
+
+/// The heaviest queries behind the dashboard, in the shape `get_aggregations`
+/// and `get_tickets` run them. Each entry is (name, SQL, number of extra
+/// parameters bound ahead of the filter's).
+fn dashboard_queries(
+    filter: &TicketFilter,
+    mode: DurationMode,
+) -> Vec<(&'static str, String, usize)> {
+    let (clause, _) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let hours = resolution_column(mode);
+
+    vec![
+        (
+            "tickets_by_status",
+            format!(
+                "SELECT COALESCE(status, 'Uncategorized') AS name, COUNT(*) AS count \
+                 FROM {table} WHERE {clause} GROUP BY status ORDER BY count DESC"
+            ),
+            0,
+        ),
+        (
+            "tickets_created_by_month",
+            format!(
+                "SELECT strftime('%Y-%m', created_at) AS month, COUNT(*) FROM {table} \
+                 WHERE created_at IS NOT NULL AND {clause} GROUP BY month"
+            ),
+            0,
+        ),
+        (
+            "tickets_resolved_by_month",
+            format!(
+                "SELECT strftime('%Y-%m', resolved_at) AS month, COUNT(*) FROM {table} \
+                 WHERE resolved_at IS NOT NULL AND {clause} GROUP BY month"
+            ),
+            0,
+        ),
+        (
+            "resolution_time_by_priority",
+            format!(
+                "SELECT priority, {hours} FROM {table} \
+                 WHERE {hours} IS NOT NULL AND priority IS NOT NULL AND {clause} ORDER BY {hours}"
+            ),
+            0,
+        ),
+        (
+            "resolution_time_by_component",
+            format!(
+                "SELECT c.component, {hours} FROM {table} \
+                 JOIN ticket_components c ON c.ticket_key = tickets.jira_key \
+                 WHERE {hours} IS NOT NULL AND {clause} ORDER BY {hours}"
+            ),
+            0,
+        ),
+        (
+            "open_tickets",
+            format!("SELECT updated_at FROM {table} WHERE resolved_at IS NULL AND {clause}"),
+            0,
+        ),
+        (
+            "overdue_tickets",
+            format!(
+                "SELECT COUNT(*) FROM {table} \
+                 WHERE resolved_at IS NULL AND due_date < ? AND {clause}"
+            ),
+            1,
+        ),
+        (
+            "ticket_list",
+            format!("SELECT {TICKET_COLUMNS} FROM {table} WHERE {clause} ORDER BY created_at DESC"),
+            0,
+        ),
+    ]
+}
+
+/// Runs `EXPLAIN QUERY PLAN` over the main dashboard queries, so a slow
+/// dashboard on a large database can be traced to a missing index.
+pub fn explain_dashboard_queries(
+    conn: &Connection,
+    filter: &TicketFilter,
+    mode: DurationMode,
+) -> Result<Vec<QueryPlan>, AppError> {
+    let (_, values) = filter.to_sql_where();
+
+    dashboard_queries(filter, mode)
+        .into_iter()
+        .map(|(name, sql, extra_params)| {
+            let mut stmt = conn
+                .prepare(&format!("EXPLAIN QUERY PLAN {}", sql))
+                .map_err(DbError::from)?;
+            // Only the plan matters, so the extra parameters can stay NULL
+            let params =
+                std::iter::repeat_n(Value::Null, extra_params).chain(values.iter().cloned());
+            let steps = stmt
+                .query_map(params_from_iter(params), |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, i64>(1)?,
+                        row.get::<_, String>(3)?,
+                    ))
+                })
+                .map_err(DbError::from)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(DbError::from)?;
+
+            // Parents are always listed before their children
+            let mut depths: HashMap<i64, usize> = HashMap::new();
+            let plan = steps
+                .into_iter()
+                .map(|(id, parent, detail)| {
+                    let depth = depths.get(&parent).map_or(0, |depth| depth + 1);
+                    depths.insert(id, depth);
+                    format!("{}{}", "  ".repeat(depth), detail)
+                })
+                .collect();
+
+            Ok(QueryPlan {
+                name: name.to_string(),
+                sql,
+                plan,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+
+    #[test]
+    fn every_dashboard_query_has_a_plan() {
+        let conn = setup_db();
+        let filter = TicketFilter {
+            tags: vec!["vip".to_string()],
+            include_archived: true,
+            ..Default::default()
+        };
+
+        let plans =
+            explain_dashboard_queries(&conn, &filter, DurationMode::Business).expect("query plans");
+        assert_eq!(plans.len(), 8);
+        assert!(plans.iter().all(|plan| !plan.plan.is_empty()));
+
+        let by_status =
+            explain_dashboard_queries(&conn, &TicketFilter::default(), DurationMode::Calendar)
+                .expect("query plans")
+                .into_iter()
+                .find(|plan| plan.name == "tickets_by_status")
+                .expect("status plan");
+        assert!(by_status
+            .plan
+            .iter()
+            .any(|step| step.contains("idx_tickets_status")));
+    }
+}
@@ -0,0 +1,195 @@
+use super::queries::{map_ticket_row, TICKET_COLUMNS};
+use crate::errors::{AppError, DbError};
+use crate::models::{QuickSearchKind, QuickSearchResult, Ticket};
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// An FTS5 query matching every word of `text` as a prefix, with each word
+/// quoted so punctuation in it isn't read as query syntax.
+fn fts_query(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn select_tickets(
+    conn: &Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<Ticket>, AppError> {
+    let mut stmt = conn.prepare(sql).map_err(DbError::from)?;
+    let tickets = stmt
+        .query_map(params, map_ticket_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+    Ok(tickets)
+}
+
+/// Up to `limit` tickets for a cmd-K style launcher, best first: the ticket
+/// whose key is `text` (ignoring case), then full-text matches by relevance,
+/// then tickets whose personal notes match.
+/// Without any text, the most recently updated tickets.
+pub fn quick_search(
+    conn: &Connection,
+    text: &str,
+    limit: u32,
+) -> Result<Vec<QuickSearchResult>, AppError> {
+    let text = text.trim();
+    if text.is_empty() {
+        let recent = select_tickets(
+            conn,
+            &format!(
+                "SELECT {} FROM tickets ORDER BY updated_at DESC, jira_key LIMIT ?1",
+                TICKET_COLUMNS
+            ),
+            params![limit],
+        )?;
+        return Ok(recent
+            .into_iter()
+            .map(|ticket| QuickSearchResult {
+                kind: QuickSearchKind::Recent,
+                ticket,
+            })
+            .collect());
+    }
+
+    let mut results = Vec::new();
+    let exact = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM tickets WHERE jira_key = ?1 COLLATE NOCASE",
+                TICKET_COLUMNS
+            ),
+            params![text],
+            map_ticket_row,
+        )
+        .optional()
+        .map_err(DbError::from)?;
+    if let Some(ticket) = exact {
+        results.push(QuickSearchResult {
+            kind: QuickSearchKind::Key,
+            ticket,
+        });
+    }
+
+    let matches = select_tickets(
+        conn,
+        &format!(
+            "SELECT {} FROM tickets
+             JOIN (SELECT rowid AS fts_id, bm25(tickets_fts) AS fts_rank
+                   FROM tickets_fts WHERE tickets_fts MATCH ?1) ON id = fts_id
+             ORDER BY fts_rank, updated_at DESC
+             LIMIT ?2",
+            TICKET_COLUMNS
+        ),
+        params![fts_query(text), limit],
+    )?;
+    let note_matches = select_tickets(
+        conn,
+        &format!(
+            "SELECT {} FROM tickets
+             JOIN (SELECT ticket_key, MIN(note_rank) AS fts_rank
+                   FROM (SELECT rowid AS note_id, bm25(ticket_notes_fts) AS note_rank
+                         FROM ticket_notes_fts WHERE ticket_notes_fts MATCH ?1)
+                   JOIN ticket_notes ON id = note_id
+                   GROUP BY ticket_key) ON jira_key = ticket_key
+             ORDER BY fts_rank, updated_at DESC
+             LIMIT ?2",
+            TICKET_COLUMNS
+        ),
+        params![fts_query(text), limit],
+    )?;
+    let matches = matches
+        .into_iter()
+        .map(|ticket| (QuickSearchKind::Text, ticket))
+        .chain(
+            note_matches
+                .into_iter()
+                .map(|ticket| (QuickSearchKind::Note, ticket)),
+        );
+    for (kind, ticket) in matches {
+        if results.iter().any(|r| r.ticket.jira_key == ticket.jira_key) {
+            continue;
+        }
+        results.push(QuickSearchResult { kind, ticket });
+    }
+    results.truncate(limit as usize);
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::upsert_ticket;
+
+    fn ticket(key: &str, summary: &str, updated_at: &str) -> Ticket {
+        Ticket {
+            summary: summary.to_string(),
+            updated_at: updated_at.to_string(),
+            ..test_support::ticket(key)
+        }
+    }
+
+    fn hits(results: &[QuickSearchResult]) -> Vec<(QuickSearchKind, &str)> {
+        results
+            .iter()
+            .map(|r| (r.kind, r.ticket.jira_key.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn ranks_key_matches_before_text_matches() {
+        let conn = setup_db();
+        for t in [
+            ticket("OPS-12", "Printer offline", "2025-01-07T09:00:00Z"),
+            ticket("OPS-120", "VPN drops \"randomly\"", "2025-01-09T09:00:00Z"),
+            ticket("OPS-3", "VPN client update", "2025-01-08T09:00:00Z"),
+        ] {
+            upsert_ticket(&conn, &t).expect("ticket");
+        }
+
+        let results = quick_search(&conn, "ops-12", 10).expect("search");
+        assert_eq!(
+            hits(&results),
+            vec![
+                (QuickSearchKind::Key, "OPS-12"),
+                (QuickSearchKind::Text, "OPS-120")
+            ]
+        );
+
+        let results = quick_search(&conn, "vpn \"rand", 10).expect("search");
+        assert_eq!(hits(&results), vec![(QuickSearchKind::Text, "OPS-120")]);
+
+        // Notes are searched too, after the tickets' own text
+        crate::db::create_ticket_note(
+            &conn,
+            "OPS-3",
+            "Waiting on the vendor",
+            "2025-01-09T10:00:00Z",
+        )
+        .expect("note");
+        let results = quick_search(&conn, "vendor", 10).expect("search");
+        assert_eq!(hits(&results), vec![(QuickSearchKind::Note, "OPS-3")]);
+
+        // The index follows summary changes
+        upsert_ticket(
+            &conn,
+            &ticket("OPS-12", "VPN printer", "2025-01-10T09:00:00Z"),
+        )
+        .expect("ticket");
+        let results = quick_search(&conn, "printer", 10).expect("search");
+        assert_eq!(hits(&results), vec![(QuickSearchKind::Text, "OPS-12")]);
+
+        let results = quick_search(&conn, "  ", 2).expect("search");
+        assert_eq!(
+            hits(&results),
+            vec![
+                (QuickSearchKind::Recent, "OPS-12"),
+                (QuickSearchKind::Recent, "OPS-120")
+            ]
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use super::queries::{map_ticket_row, TICKET_COLUMNS};
+use super::ticket_links::BLOCKS_LINK_CONDITION;
+use crate::errors::{AppError, DbError};
+use crate::models::{CountEntry, ReleaseReadiness, TicketFilter};
+use crate::services::forecast;
+use crate::services::time_calc::parse_timestamp;
+use chrono::NaiveDateTime;
+use rusqlite::{params, params_from_iter, Connection};
+use std::collections::BTreeSet;
+
+/// Weeks of resolutions averaged into a release's throughput.
+const RELEASE_THROUGHPUT_WEEKS: i64 = 4;
+
+/// Fix versions with their ticket counts, by name.
+pub fn list_fix_versions(conn: &Connection) -> Result<Vec<CountEntry>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT v.version, COUNT(*) FROM ticket_fix_versions v
+             JOIN tickets ON tickets.jira_key = v.ticket_key
+             WHERE tickets.duplicate_of IS NULL
+             GROUP BY v.version ORDER BY v.version",
+        )
+        .map_err(DbError::from)?;
+    let versions = stmt
+        .query_map([], |row| {
+            Ok(CountEntry {
+                name: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(versions)
+}
+
+/// Readiness of `version`, or `None` when no stored ticket targets it.
+pub fn get_release_readiness(
+    conn: &Connection,
+    version: &str,
+    now: NaiveDateTime,
+) -> Result<Option<ReleaseReadiness>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM tickets WHERE duplicate_of IS NULL \
+             AND jira_key IN (SELECT ticket_key FROM ticket_fix_versions WHERE version = ?1) \
+             ORDER BY jira_key",
+            TICKET_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+    let tickets = stmt
+        .query_map(params![version], map_ticket_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+    if tickets.is_empty() {
+        return Ok(None);
+    }
+
+    let blocked = blocked_keys(conn)?;
+    let (open, done): (Vec<_>, Vec<_>) = tickets.into_iter().partition(|t| t.resolved_at.is_none());
+    let open_tickets = open.len() as u32;
+    let projects: BTreeSet<String> = open
+        .iter()
+        .chain(done.iter())
+        .map(|t| t.project_key.clone())
+        .collect();
+    let blockers = open
+        .into_iter()
+        .filter(|t| t.priority.eq_ignore_ascii_case("blocker") || blocked.contains(&t.jira_key))
+        .collect();
+
+    let throughput_per_week = project_throughput(conn, &projects, now)?;
+    let estimated_completion =
+        forecast::completion_date(f64::from(open_tickets), throughput_per_week, now);
+
+    Ok(Some(ReleaseReadiness {
+        version: version.to_string(),
+        total_tickets: open_tickets + done.len() as u32,
+        open_tickets,
+        done_tickets: done.len() as u32,
+        blockers,
+        throughput_per_week,
+        estimated_completion,
+    }))
+}
+
+/// Keys of tickets blocked by a ticket that isn't resolved (or isn't stored).
+fn blocked_keys(conn: &Connection) -> Result<BTreeSet<String>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT l.inward_key FROM ticket_links l \
+             LEFT JOIN tickets blocker ON blocker.jira_key = l.outward_key \
+             WHERE {} AND blocker.resolved_at IS NULL",
+            BLOCKS_LINK_CONDITION
+        ))
+        .map_err(DbError::from)?;
+    let keys = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(DbError::from)?
+        .collect::<Result<BTreeSet<String>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(keys)
+}
+
+/// Tickets resolved per week across `projects` over the trailing window.
+fn project_throughput(
+    conn: &Connection,
+    projects: &BTreeSet<String>,
+    now: NaiveDateTime,
+) -> Result<f64, AppError> {
+    let (clause, values) = TicketFilter::default()
+        .with_projects(projects.iter().cloned())
+        .to_sql_where();
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT resolved_at FROM tickets WHERE resolved_at IS NOT NULL AND {}",
+            clause
+        ))
+        .map_err(DbError::from)?;
+    let since = now - chrono::Duration::weeks(RELEASE_THROUGHPUT_WEEKS);
+
+    let mut resolved = 0;
+    for resolved_at in stmt
+        .query_map(params_from_iter(values), |row| row.get::<_, String>(0))
+        .map_err(DbError::from)?
+    {
+        let resolved_at = resolved_at.map_err(DbError::from)?;
+        if parse_timestamp(&resolved_at).is_some_and(|at| at >= since && at <= now) {
+            resolved += 1;
+        }
+    }
+
+    Ok(f64::from(resolved) / RELEASE_THROUGHPUT_WEEKS as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{replace_ticket_links, upsert_ticket};
+    use crate::models::{Ticket, TicketLink};
+    use chrono::NaiveDate;
+
+    fn ticket(
+        key: &str,
+        priority: &str,
+        version: Option<&str>,
+        resolved_at: Option<&str>,
+    ) -> Ticket {
+        Ticket {
+            priority: priority.to_string(),
+            created_at: "2025-01-01T09:00:00Z".to_string(),
+            updated_at: "2025-01-01T09:00:00Z".to_string(),
+            fix_versions: version.map(|v| vec![v.to_string()]).unwrap_or_default(),
+            ..test_support::ticket_resolved_at(key, resolved_at)
+        }
+    }
+
+    #[test]
+    fn readiness_counts_blockers_and_projects_completion() {
+        let conn = setup_db();
+        for t in [
+            ticket("OPS-1", "High", Some("2.0"), Some("2025-01-20T09:00:00Z")),
+            ticket("OPS-2", "Blocker", Some("2.0"), None),
+            ticket("OPS-3", "Medium", Some("2.0"), None),
+            ticket("OPS-4", "Medium", Some("2.0"), None),
+            // Outside the release but in its project: counts toward throughput
+            ticket("OPS-5", "Medium", None, Some("2025-01-22T09:00:00Z")),
+            ticket("OPS-6", "Medium", None, None),
+        ] {
+            upsert_ticket(&conn, &t).expect("ticket inserted");
+        }
+        replace_ticket_links(
+            &conn,
+            "OPS-6",
+            &[TicketLink {
+                outward_key: "OPS-6".to_string(),
+                inward_key: "OPS-3".to_string(),
+                link_type: "Blocks".to_string(),
+                outward_label: "blocks".to_string(),
+                inward_label: "is blocked by".to_string(),
+            }],
+        )
+        .expect("links");
+        let now = NaiveDate::from_ymd_opt(2025, 1, 27)
+            .and_then(|d| d.and_hms_opt(9, 0, 0))
+            .expect("valid datetime");
+
+        let readiness = get_release_readiness(&conn, "2.0", now)
+            .expect("readiness")
+            .expect("release exists");
+
+        assert_eq!(
+            (
+                readiness.total_tickets,
+                readiness.open_tickets,
+                readiness.done_tickets
+            ),
+            (4, 3, 1)
+        );
+        let blockers: Vec<&str> = readiness
+            .blockers
+            .iter()
+            .map(|t| t.jira_key.as_str())
+            .collect();
+        assert_eq!(blockers, vec!["OPS-2", "OPS-3"]);
+        // 2 resolved in 4 weeks -> 0.5/week -> 3 open take 6 weeks
+        assert_eq!(readiness.throughput_per_week, 0.5);
+        assert_eq!(
+            readiness.estimated_completion.as_deref(),
+            Some("2025-03-10")
+        );
+
+        assert!(get_release_readiness(&conn, "9.9", now)
+            .expect("readiness")
+            .is_none());
+        let versions = list_fix_versions(&conn).expect("versions");
+        assert_eq!((versions[0].name.as_str(), versions[0].count), ("2.0", 4));
+    }
+}
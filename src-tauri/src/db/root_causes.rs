@@ -0,0 +1,290 @@
+use super::queries::{average, resolution_column, tickets_table};
+use crate::errors::{AppError, DbError};
+use crate::models::{
+    DurationMode, RootCause, RootCauseBreakdown, RootCauseEntry, RootCauseInput, TicketFilter,
+};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use std::collections::BTreeMap;
+
+/// SQL expression giving a `tickets` row's root cause name, or NULL.
+pub(crate) const ROOT_CAUSE_EXPR: &str = "(SELECT r.name FROM ticket_root_causes c \
+     JOIN root_causes r ON r.id = c.root_cause_id WHERE c.ticket_key = tickets.jira_key)";
+
+fn map_root_cause_row(row: &rusqlite::Row) -> rusqlite::Result<RootCause> {
+    Ok(RootCause {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+    })
+}
+
+fn description(input: &RootCauseInput) -> Option<&str> {
+    input
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+}
+
+pub fn get_root_cause(conn: &Connection, id: i64) -> Result<Option<RootCause>, AppError> {
+    conn.query_row(
+        "SELECT id, name, description FROM root_causes WHERE id = ?1",
+        params![id],
+        map_root_cause_row,
+    )
+    .optional()
+    .map_err(|e| DbError::from(e).into())
+}
+
+/// The whole taxonomy, by name.
+pub fn list_root_causes(conn: &Connection) -> Result<Vec<RootCause>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT id, name, description FROM root_causes ORDER BY name COLLATE NOCASE")
+        .map_err(DbError::from)?;
+    let causes = stmt
+        .query_map([], map_root_cause_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+    Ok(causes)
+}
+
+pub fn create_root_cause(conn: &Connection, input: &RootCauseInput) -> Result<RootCause, AppError> {
+    conn.execute(
+        "INSERT INTO root_causes (name, description) VALUES (?1, ?2)",
+        params![input.name.trim(), description(input)],
+    )
+    .map_err(DbError::from)?;
+    get_root_cause(conn, conn.last_insert_rowid())?
+        .ok_or_else(|| AppError::Internal("Inserted root cause not found".to_string()))
+}
+
+/// Renames or redescribes a root cause; tickets keep pointing at it.
+/// `None` when it doesn't exist.
+pub fn update_root_cause(
+    conn: &Connection,
+    id: i64,
+    input: &RootCauseInput,
+) -> Result<Option<RootCause>, AppError> {
+    let updated = conn
+        .execute(
+            "UPDATE root_causes SET name = ?2, description = ?3 WHERE id = ?1",
+            params![id, input.name.trim(), description(input)],
+        )
+        .map_err(DbError::from)?;
+    if updated == 0 {
+        return Ok(None);
+    }
+    get_root_cause(conn, id)
+}
+
+/// Removes a root cause and clears it from every ticket it was set on.
+pub fn delete_root_cause(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    tx.execute(
+        "DELETE FROM ticket_root_causes WHERE root_cause_id = ?1",
+        params![id],
+    )
+    .map_err(DbError::from)?;
+    let deleted = tx
+        .execute("DELETE FROM root_causes WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    tx.commit().map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+pub fn get_ticket_root_cause(
+    conn: &Connection,
+    jira_key: &str,
+) -> Result<Option<RootCause>, AppError> {
+    conn.query_row(
+        "SELECT r.id, r.name, r.description FROM ticket_root_causes c
+         JOIN root_causes r ON r.id = c.root_cause_id WHERE c.ticket_key = ?1",
+        params![jira_key],
+        map_root_cause_row,
+    )
+    .optional()
+    .map_err(|e| DbError::from(e).into())
+}
+
+/// Sets a resolved ticket's root cause, replacing any earlier one, or
+/// clears it when `root_cause_id` is `None`.
+pub fn set_ticket_root_cause(
+    conn: &Connection,
+    jira_key: &str,
+    root_cause_id: Option<i64>,
+    now: &str,
+) -> Result<Option<RootCause>, AppError> {
+    let resolved: Option<bool> = conn
+        .query_row(
+            "SELECT resolved_at IS NOT NULL FROM tickets WHERE jira_key = ?1",
+            params![jira_key],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(DbError::from)?;
+    let Some(resolved) = resolved else {
+        return Err(AppError::Config(format!("Unknown ticket: {}", jira_key)));
+    };
+
+    let Some(root_cause_id) = root_cause_id else {
+        conn.execute(
+            "DELETE FROM ticket_root_causes WHERE ticket_key = ?1",
+            params![jira_key],
+        )
+        .map_err(DbError::from)?;
+        return Ok(None);
+    };
+    if !resolved {
+        return Err(AppError::Config(format!(
+            "Root causes can only be set on resolved tickets: {}",
+            jira_key
+        )));
+    }
+    let root_cause = get_root_cause(conn, root_cause_id)?
+        .ok_or_else(|| AppError::Config(format!("Unknown root cause: {}", root_cause_id)))?;
+    conn.execute(
+        "INSERT INTO ticket_root_causes (ticket_key, root_cause_id, set_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(ticket_key) DO UPDATE SET
+             root_cause_id = excluded.root_cause_id, set_at = excluded.set_at",
+        params![jira_key, root_cause_id, now],
+    )
+    .map_err(DbError::from)?;
+    Ok(Some(root_cause))
+}
+
+/// Root causes of `filter`'s resolved tickets with their share and average
+/// resolution time, most common first.
+pub fn get_root_cause_breakdown(
+    conn: &Connection,
+    filter: &TicketFilter,
+    mode: DurationMode,
+) -> Result<RootCauseBreakdown, AppError> {
+    let (clause, values) = filter.to_sql_where();
+    let table = tickets_table(filter);
+    let hours = resolution_column(mode);
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {ROOT_CAUSE_EXPR}, {hours} FROM {table} \
+             WHERE resolved_at IS NOT NULL AND {clause}"
+        ))
+        .map_err(DbError::from)?;
+    let rows = stmt
+        .query_map(params_from_iter(values), |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, Option<f64>>(1)?,
+            ))
+        })
+        .map_err(DbError::from)?;
+
+    let mut unclassified = 0u32;
+    let mut by_cause: BTreeMap<String, (u32, Vec<f64>)> = BTreeMap::new();
+    for row in rows {
+        match row.map_err(DbError::from)? {
+            (Some(cause), hours) => {
+                let (count, durations) = by_cause.entry(cause).or_default();
+                *count += 1;
+                durations.extend(hours);
+            }
+            (None, _) => unclassified += 1,
+        }
+    }
+
+    let classified: u32 = by_cause.values().map(|(count, _)| count).sum();
+    let mut causes: Vec<RootCauseEntry> = by_cause
+        .into_iter()
+        .map(|(root_cause, (count, durations))| RootCauseEntry {
+            root_cause,
+            count,
+            share: f64::from(count) / f64::from(classified),
+            avg_resolution_hours: average(&durations),
+        })
+        .collect();
+    causes.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.root_cause.cmp(&b.root_cause))
+    });
+
+    Ok(RootCauseBreakdown {
+        classified,
+        unclassified,
+        causes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support;
+    use crate::db::upsert_ticket;
+
+    const NOW: &str = "2025-01-08T09:00:00+00:00";
+
+    fn seeded_db() -> Connection {
+        let conn = test_support::setup_db();
+        for (key, resolved_at) in [
+            ("OPS-1", Some("2025-01-06T11:00:00Z")),
+            ("OPS-2", Some("2025-01-06T13:00:00Z")),
+            ("OPS-3", Some("2025-01-06T15:00:00Z")),
+            ("OPS-4", Some("2025-01-06T10:00:00Z")),
+            ("OPS-5", None),
+        ] {
+            upsert_ticket(&conn, &test_support::ticket_resolved_at(key, resolved_at))
+                .expect("ticket");
+        }
+        conn
+    }
+
+    fn input(name: &str) -> RootCauseInput {
+        RootCauseInput {
+            name: name.to_string(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn root_causes_are_set_on_resolved_tickets_only() {
+        let conn = seeded_db();
+        let config = create_root_cause(&conn, &input(" Config change ")).expect("create");
+        assert_eq!(config.name, "Config change");
+        assert!(create_root_cause(&conn, &input("config CHANGE")).is_err());
+
+        let set = set_ticket_root_cause(&conn, "OPS-1", Some(config.id), NOW).expect("set");
+        assert_eq!(set, Some(config.clone()));
+        assert_eq!(
+            get_ticket_root_cause(&conn, "OPS-1").expect("get"),
+            Some(config.clone())
+        );
+        assert!(set_ticket_root_cause(&conn, "OPS-5", Some(config.id), NOW).is_err());
+        assert!(set_ticket_root_cause(&conn, "OPS-9", Some(config.id), NOW).is_err());
+        assert!(set_ticket_root_cause(&conn, "OPS-2", Some(config.id + 1), NOW).is_err());
+
+        assert!(delete_root_cause(&conn, config.id).expect("delete"));
+        assert_eq!(get_ticket_root_cause(&conn, "OPS-1").expect("get"), None);
+    }
+
+    #[test]
+    fn breakdown_counts_resolved_tickets_per_root_cause() {
+        let conn = seeded_db();
+        let config = create_root_cause(&conn, &input("Config change")).expect("create");
+        let capacity = create_root_cause(&conn, &input("Capacity")).expect("create");
+        for (key, cause) in [("OPS-1", &config), ("OPS-2", &config), ("OPS-3", &capacity)] {
+            set_ticket_root_cause(&conn, key, Some(cause.id), NOW).expect("set");
+        }
+
+        let breakdown =
+            get_root_cause_breakdown(&conn, &TicketFilter::default(), DurationMode::Calendar)
+                .expect("breakdown");
+        assert_eq!(breakdown.classified, 3);
+        assert_eq!(breakdown.unclassified, 1);
+        let rows: Vec<(&str, u32, f64)> = breakdown
+            .causes
+            .iter()
+            .map(|c| (c.root_cause.as_str(), c.count, c.avg_resolution_hours))
+            .collect();
+        assert_eq!(rows, vec![("Config change", 2, 3.0), ("Capacity", 1, 6.0)]);
+        assert!((breakdown.causes[0].share - 2.0 / 3.0).abs() < 1e-9);
+    }
+}
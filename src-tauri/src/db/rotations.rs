@@ -0,0 +1,192 @@
+use super::queries::{average, get_tickets, resolution_hours};
+use super::support_calendars::project_schedules;
+use crate::errors::{AppError, DbError};
+use crate::models::{
+    DurationMode, PriorityLevel, Rotation, RotationInput, RotationStats, TicketFilter,
+};
+use crate::services::time_calc::parse_timestamp;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+const ROTATION_COLUMNS: &str = "id, person, start_at, end_at";
+
+fn map_rotation_row(row: &Row) -> rusqlite::Result<Rotation> {
+    Ok(Rotation {
+        id: row.get(0)?,
+        person: row.get(1)?,
+        start_at: row.get(2)?,
+        end_at: row.get(3)?,
+    })
+}
+
+pub fn create_rotation(conn: &Connection, input: &RotationInput) -> Result<Rotation, AppError> {
+    conn.execute(
+        "INSERT INTO rotations (person, start_at, end_at) VALUES (?1, ?2, ?3)",
+        params![input.person.trim(), input.start_at, input.end_at],
+    )
+    .map_err(DbError::from)?;
+
+    conn.query_row(
+        &format!("SELECT {} FROM rotations WHERE id = ?1", ROTATION_COLUMNS),
+        params![conn.last_insert_rowid()],
+        map_rotation_row,
+    )
+    .optional()
+    .map_err(DbError::from)?
+    .ok_or_else(|| AppError::Internal("Inserted rotation not found".to_string()))
+}
+
+/// Every rotation, earliest first.
+pub fn list_rotations(conn: &Connection) -> Result<Vec<Rotation>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM rotations ORDER BY start_at, id",
+            ROTATION_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+    let rotations = stmt
+        .query_map([], map_rotation_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(rotations)
+}
+
+pub fn delete_rotation(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute("DELETE FROM rotations WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+/// Each rotation with the `filter` tickets created during its window,
+/// earliest rotation first. Overlapping rotations (say a primary and a
+/// secondary) each count the tickets they share.
+pub fn get_triage_stats_by_rotation(
+    conn: &Connection,
+    filter: &TicketFilter,
+    mode: DurationMode,
+) -> Result<Vec<RotationStats>, AppError> {
+    let rotations = list_rotations(conn)?;
+    let tickets = get_tickets(conn, filter)?;
+    let schedules = project_schedules(conn)?;
+
+    Ok(rotations
+        .into_iter()
+        .filter_map(|rotation| {
+            let start = parse_timestamp(&rotation.start_at)?;
+            let end = parse_timestamp(&rotation.end_at)?;
+            let created: Vec<_> = tickets
+                .iter()
+                .filter(|t| {
+                    parse_timestamp(&t.created_at).is_some_and(|at| at >= start && at < end)
+                })
+                .collect();
+            let durations: Vec<f64> = created
+                .iter()
+                .filter_map(|t| {
+                    let schedule = schedules.get(&t.project_key);
+                    resolution_hours(&t.created_at, t.resolved_at.as_deref()?, mode, schedule)
+                })
+                .collect();
+            let resolved = created.iter().filter(|t| t.resolved_at.is_some()).count() as u32;
+            let days = (end - start).num_seconds() as f64 / 86_400.0;
+
+            Some(RotationStats {
+                created: created.len() as u32,
+                resolved,
+                open: created.len() as u32 - resolved,
+                critical: created
+                    .iter()
+                    .filter(|t| t.priority_level == Some(PriorityLevel::Critical))
+                    .count() as u32,
+                created_per_day: if days > 0.0 {
+                    created.len() as f64 / days
+                } else {
+                    0.0
+                },
+                avg_resolution_hours: (!durations.is_empty()).then(|| average(&durations)),
+                rotation,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::upsert_ticket;
+    use crate::models::Ticket;
+
+    fn ticket(key: &str, priority: &str, created_at: &str, resolved_at: Option<&str>) -> Ticket {
+        Ticket {
+            priority: priority.to_string(),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            ..test_support::ticket_resolved_at(key, resolved_at)
+        }
+    }
+
+    fn rotation(person: &str, start_at: &str, end_at: &str) -> RotationInput {
+        RotationInput {
+            person: person.to_string(),
+            start_at: start_at.to_string(),
+            end_at: end_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn attributes_tickets_to_the_rotation_they_were_created_in() {
+        let conn = setup_db();
+        for t in [
+            ticket(
+                "OPS-1",
+                "Critical",
+                "2025-01-06T10:00:00Z",
+                Some("2025-01-06T12:00:00Z"),
+            ),
+            ticket("OPS-2", "Low", "2025-01-08T10:00:00Z", None),
+            // Created the moment Sam's week starts
+            ticket(
+                "OPS-3",
+                "High",
+                "2025-01-13T09:00:00Z",
+                Some("2025-01-13T13:00:00Z"),
+            ),
+        ] {
+            upsert_ticket(&conn, &t).expect("ticket");
+        }
+        let sam = create_rotation(
+            &conn,
+            &rotation("Sam", "2025-01-13T09:00:00Z", "2025-01-27T09:00:00Z"),
+        )
+        .expect("rotation");
+        create_rotation(
+            &conn,
+            &rotation(" Dana ", "2025-01-06T09:00:00Z", "2025-01-13T09:00:00Z"),
+        )
+        .expect("rotation");
+
+        let stats =
+            get_triage_stats_by_rotation(&conn, &TicketFilter::default(), DurationMode::Business)
+                .expect("stats");
+        let people: Vec<&str> = stats.iter().map(|s| s.rotation.person.as_str()).collect();
+        assert_eq!(people, vec!["Dana", "Sam"]);
+
+        let dana = &stats[0];
+        assert_eq!((dana.created, dana.resolved, dana.open), (2, 1, 1));
+        assert_eq!(dana.critical, 1);
+        assert!((dana.created_per_day - 2.0 / 7.0).abs() < 1e-9);
+        assert_eq!(dana.avg_resolution_hours, Some(2.0));
+
+        let sam_stats = &stats[1];
+        assert_eq!((sam_stats.created, sam_stats.resolved), (1, 1));
+        assert!((sam_stats.created_per_day - 1.0 / 14.0).abs() < 1e-9);
+        assert_eq!(sam_stats.avg_resolution_hours, Some(4.0));
+
+        assert!(delete_rotation(&conn, sam.id).expect("delete"));
+        assert!(!delete_rotation(&conn, sam.id).expect("delete"));
+        assert_eq!(list_rotations(&conn).expect("rotations").len(), 1);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::errors::{AppError, DbError};
+use crate::models::{Granularity, SavedView, SavedViewInput, ViewSort};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+const VIEW_COLUMNS: &str =
+    "id, name, filter, sort_column, sort_descending, granularity, created_at, updated_at";
+
+fn map_view_row(row: &Row) -> rusqlite::Result<SavedView> {
+    let filter: String = row.get(2)?;
+    let sort_column: Option<String> = row.get(3)?;
+    let granularity: String = row.get(5)?;
+
+    Ok(SavedView {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        filter: serde_json::from_str(&filter)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(2, Type::Text, Box::new(e)))?,
+        sort: match sort_column {
+            Some(column) => Some(ViewSort {
+                column,
+                descending: row.get(4)?,
+            }),
+            None => None,
+        },
+        granularity: Granularity::parse(&granularity).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                5,
+                Type::Text,
+                format!("Unknown granularity: {}", granularity).into(),
+            )
+        })?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+fn filter_json(input: &SavedViewInput) -> Result<String, AppError> {
+    serde_json::to_string(&input.filter)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize view filter: {}", e)))
+}
+
+pub fn create_saved_view(conn: &Connection, input: &SavedViewInput) -> Result<SavedView, AppError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO saved_views
+             (name, filter, sort_column, sort_descending, granularity, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+        params![
+            input.name,
+            filter_json(input)?,
+            input.sort.as_ref().map(|s| s.column.as_str()),
+            input.sort.as_ref().is_some_and(|s| s.descending),
+            input.granularity.as_str(),
+            now,
+        ],
+    )
+    .map_err(DbError::from)?;
+
+    get_saved_view(conn, conn.last_insert_rowid())?
+        .ok_or_else(|| AppError::Internal("Inserted saved view not found".to_string()))
+}
+
+pub fn get_saved_view(conn: &Connection, id: i64) -> Result<Option<SavedView>, AppError> {
+    conn.query_row(
+        &format!("SELECT {} FROM saved_views WHERE id = ?1", VIEW_COLUMNS),
+        params![id],
+        map_view_row,
+    )
+    .optional()
+    .map_err(|e| DbError::from(e).into())
+}
+
+pub fn list_saved_views(conn: &Connection) -> Result<Vec<SavedView>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM saved_views ORDER BY name COLLATE NOCASE",
+            VIEW_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+
+    let views = stmt
+        .query_map([], map_view_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(views)
+}
+
+pub fn update_saved_view(
+    conn: &Connection,
+    id: i64,
+    input: &SavedViewInput,
+) -> Result<Option<SavedView>, AppError> {
+    conn.execute(
+        "UPDATE saved_views SET name = ?2, filter = ?3, sort_column = ?4, sort_descending = ?5,
+             granularity = ?6, updated_at = ?7
+         WHERE id = ?1",
+        params![
+            id,
+            input.name,
+            filter_json(input)?,
+            input.sort.as_ref().map(|s| s.column.as_str()),
+            input.sort.as_ref().is_some_and(|s| s.descending),
+            input.granularity.as_str(),
+            chrono::Utc::now().to_rfc3339(),
+        ],
+    )
+    .map_err(DbError::from)?;
+
+    get_saved_view(conn, id)
+}
+
+pub fn delete_saved_view(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute("DELETE FROM saved_views WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+    use crate::models::TicketFilter;
+
+    #[test]
+    fn saved_view_round_trips_filter_sort_and_granularity() {
+        let conn = setup_db();
+
+        let input = SavedViewInput {
+            name: "My Criticals".to_string(),
+            filter: TicketFilter {
+                tags: vec!["follow-up".to_string()],
+                ..Default::default()
+            },
+            sort: Some(ViewSort {
+                column: "created_at".to_string(),
+                descending: true,
+            }),
+            granularity: Granularity::Week,
+        };
+        let created = create_saved_view(&conn, &input).expect("create");
+        assert_eq!(created.filter.tags, vec!["follow-up".to_string()]);
+        assert_eq!(created.sort, input.sort);
+        assert_eq!(created.granularity, Granularity::Week);
+
+        let renamed = SavedViewInput {
+            name: "Team Backlog".to_string(),
+            sort: None,
+            ..input
+        };
+        let updated = update_saved_view(&conn, created.id, &renamed)
+            .expect("update")
+            .expect("view exists");
+        assert_eq!(updated.name, "Team Backlog");
+        assert_eq!(updated.sort, None);
+
+        assert_eq!(list_saved_views(&conn).expect("list").len(), 1);
+        assert!(delete_saved_view(&conn, created.id).expect("delete"));
+        assert!(list_saved_views(&conn).expect("list").is_empty());
+    }
+}
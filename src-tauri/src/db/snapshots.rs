@@ -0,0 +1,77 @@
+use crate::errors::{AppError, DbError};
+use crate::models::ReportSnapshot;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+fn map_snapshot_row(row: &Row) -> rusqlite::Result<ReportSnapshot> {
+    Ok(ReportSnapshot {
+        id: row.get(0)?,
+        period: row.get(1)?,
+        taken_at: row.get(2)?,
+    })
+}
+
+pub fn insert_report_snapshot(
+    conn: &Connection,
+    period: &str,
+    taken_at: &str,
+    data: &str,
+) -> Result<ReportSnapshot, AppError> {
+    conn.execute(
+        "INSERT INTO report_snapshots (period, taken_at, data) VALUES (?1, ?2, ?3)",
+        params![period, taken_at, data],
+    )
+    .map_err(DbError::from)?;
+
+    Ok(ReportSnapshot {
+        id: conn.last_insert_rowid(),
+        period: period.to_string(),
+        taken_at: taken_at.to_string(),
+    })
+}
+
+/// Snapshots newest first.
+pub fn list_report_snapshots(conn: &Connection) -> Result<Vec<ReportSnapshot>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, period, taken_at FROM report_snapshots ORDER BY taken_at DESC, id DESC",
+        )
+        .map_err(DbError::from)?;
+
+    let snapshots = stmt
+        .query_map([], map_snapshot_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(snapshots)
+}
+
+/// A snapshot and its stored JSON payload.
+pub fn get_report_snapshot(
+    conn: &Connection,
+    id: i64,
+) -> Result<Option<(ReportSnapshot, String)>, AppError> {
+    conn.query_row(
+        "SELECT id, period, taken_at, data FROM report_snapshots WHERE id = ?1",
+        params![id],
+        |row| Ok((map_snapshot_row(row)?, row.get::<_, String>(3)?)),
+    )
+    .optional()
+    .map_err(|e| DbError::from(e).into())
+}
+
+pub fn has_snapshot_for_period(conn: &Connection, period: &str) -> Result<bool, AppError> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM report_snapshots WHERE period = ?1)",
+        params![period],
+        |row| row.get(0),
+    )
+    .map_err(|e| DbError::from(e).into())
+}
+
+pub fn delete_report_snapshot(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute("DELETE FROM report_snapshots WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
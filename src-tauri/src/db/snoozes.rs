@@ -0,0 +1,191 @@
+use crate::errors::{AppError, DbError};
+use crate::models::{EndedSnooze, TicketSnooze};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Fixed-width UTC timestamps, so `until` compares correctly as text.
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Hides a ticket from default views until `until`, or until it changes
+/// first. Snoozing again replaces the previous snooze.
+pub fn snooze_ticket(
+    conn: &Connection,
+    jira_key: &str,
+    until: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<TicketSnooze, AppError> {
+    let snoozed = conn
+        .execute(
+            "INSERT INTO ticket_snoozes (ticket_key, until, snoozed_at, seen_updated_at)
+             SELECT jira_key, ?2, ?3, updated_at FROM tickets WHERE jira_key = ?1
+             ON CONFLICT(ticket_key) DO UPDATE SET until = excluded.until,
+                 snoozed_at = excluded.snoozed_at, seen_updated_at = excluded.seen_updated_at",
+            params![jira_key, timestamp(until), timestamp(now)],
+        )
+        .map_err(DbError::from)?;
+    if snoozed == 0 {
+        return Err(AppError::Config(format!("Unknown ticket: {}", jira_key)));
+    }
+
+    get_ticket_snooze(conn, jira_key)?
+        .ok_or_else(|| AppError::Internal("Stored snooze not found".to_string()))
+}
+
+pub fn unsnooze_ticket(conn: &Connection, jira_key: &str) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute(
+            "DELETE FROM ticket_snoozes WHERE ticket_key = ?1",
+            params![jira_key],
+        )
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+const SNOOZE_QUERY: &str = "SELECT s.ticket_key, COALESCE(t.summary, ''), s.until, s.snoozed_at
+     FROM ticket_snoozes s LEFT JOIN tickets t ON t.jira_key = s.ticket_key";
+
+fn map_snooze_row(row: &rusqlite::Row) -> rusqlite::Result<TicketSnooze> {
+    Ok(TicketSnooze {
+        ticket_key: row.get(0)?,
+        summary: row.get(1)?,
+        until: row.get(2)?,
+        snoozed_at: row.get(3)?,
+    })
+}
+
+pub fn get_ticket_snooze(
+    conn: &Connection,
+    jira_key: &str,
+) -> Result<Option<TicketSnooze>, AppError> {
+    conn.query_row(
+        &format!("{} WHERE s.ticket_key = ?1", SNOOZE_QUERY),
+        params![jira_key],
+        map_snooze_row,
+    )
+    .optional()
+    .map_err(|e| DbError::from(e).into())
+}
+
+/// Every snoozed ticket, soonest to wake first.
+pub fn list_snoozed_tickets(conn: &Connection) -> Result<Vec<TicketSnooze>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!("{} ORDER BY s.until, s.ticket_key", SNOOZE_QUERY))
+        .map_err(DbError::from)?;
+
+    let snoozes = stmt
+        .query_map([], map_snooze_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(snoozes)
+}
+
+/// Ends the snoozes that ran out by `now` or whose ticket was updated since
+/// it was snoozed, and returns them so the user can be told.
+pub fn end_due_snoozes(
+    conn: &Connection,
+    now: DateTime<Utc>,
+) -> Result<Vec<EndedSnooze>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT s.ticket_key, COALESCE(t.summary, ''),
+                    t.updated_at IS NOT s.seen_updated_at AS changed
+             FROM ticket_snoozes s LEFT JOIN tickets t ON t.jira_key = s.ticket_key
+             WHERE s.until <= ?1 OR t.updated_at IS NOT s.seen_updated_at
+             ORDER BY s.ticket_key",
+        )
+        .map_err(DbError::from)?;
+
+    let ended = stmt
+        .query_map(params![timestamp(now)], |row| {
+            Ok(EndedSnooze {
+                ticket_key: row.get(0)?,
+                summary: row.get(1)?,
+                changed: row.get(2)?,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    for snooze in &ended {
+        unsnooze_ticket(conn, &snooze.ticket_key)?;
+    }
+    Ok(ended)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{get_tickets, upsert_ticket};
+    use crate::models::{Ticket, TicketFilter};
+
+    fn ticket(key: &str, updated_at: &str) -> Ticket {
+        Ticket {
+            updated_at: updated_at.to_string(),
+            ..test_support::ticket(key)
+        }
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("valid timestamp")
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn snoozed_tickets_are_hidden_until_due_or_changed() {
+        let conn = setup_db();
+        for key in ["OPS-1", "OPS-2", "OPS-3"] {
+            upsert_ticket(&conn, &ticket(key, "2025-01-06T09:00:00Z")).expect("ticket");
+        }
+        let now = Utc::now();
+        let later = now + chrono::Duration::days(2);
+        snooze_ticket(&conn, "OPS-1", later, now).expect("snooze");
+        snooze_ticket(&conn, "OPS-2", later, now).expect("snooze");
+        assert!(snooze_ticket(&conn, "NOPE-1", later, now).is_err());
+
+        let keys = |filter: &TicketFilter| {
+            get_tickets(&conn, filter)
+                .expect("tickets")
+                .into_iter()
+                .map(|t| t.jira_key)
+                .collect::<Vec<_>>()
+        };
+        let listed = TicketFilter::default().without_snoozed();
+        assert_eq!(keys(&listed), vec!["OPS-3"]);
+        assert_eq!(keys(&TicketFilter::default()).len(), 3);
+        assert_eq!(list_snoozed_tickets(&conn).expect("snoozes").len(), 2);
+
+        // Nothing is due yet
+        assert!(end_due_snoozes(&conn, now).expect("end").is_empty());
+
+        // A sync updates OPS-2, which wakes it early
+        upsert_ticket(&conn, &ticket("OPS-2", "2025-01-07T09:00:00Z")).expect("ticket");
+        let ended = end_due_snoozes(&conn, now).expect("end");
+        assert_eq!(
+            ended,
+            vec![EndedSnooze {
+                ticket_key: "OPS-2".to_string(),
+                summary: "Summary OPS-2".to_string(),
+                changed: true,
+            }]
+        );
+
+        // OPS-1 wakes when its time comes
+        let ended = end_due_snoozes(&conn, later).expect("end");
+        assert_eq!(ended.len(), 1);
+        assert!(!ended[0].changed);
+        assert!(list_snoozed_tickets(&conn).expect("snoozes").is_empty());
+
+        // A snooze that ran out no longer hides the ticket, even before it is ended
+        snooze_ticket(&conn, "OPS-3", at("2025-01-01T00:00:00Z"), now).expect("snooze");
+        assert_eq!(keys(&listed).len(), 3);
+        assert!(unsnooze_ticket(&conn, "OPS-3").expect("unsnooze"));
+        assert!(!unsnooze_ticket(&conn, "OPS-3").expect("unsnooze"));
+    }
+}
@@ -0,0 +1,51 @@
+use crate::errors::{AppError, DbError};
+use crate::models::Sprint;
+use rusqlite::{params, Connection};
+
+/// Stores sprints seen during a sync, refreshing the state and dates of
+/// sprints already known.
+pub fn upsert_sprints(conn: &Connection, sprints: &[Sprint]) -> Result<(), AppError> {
+    let mut stmt = conn
+        .prepare_cached(
+            "INSERT INTO sprints (name, state, start_date, end_date) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(name) DO UPDATE SET
+                 state = excluded.state,
+                 start_date = excluded.start_date,
+                 end_date = excluded.end_date",
+        )
+        .map_err(DbError::from)?;
+    for sprint in sprints {
+        stmt.execute(params![
+            sprint.name,
+            sprint.state,
+            sprint.start_date,
+            sprint.end_date
+        ])
+        .map_err(DbError::from)?;
+    }
+    Ok(())
+}
+
+/// Every known sprint by start date; unscheduled sprints last.
+pub fn list_sprints(conn: &Connection) -> Result<Vec<Sprint>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT name, state, start_date, end_date FROM sprints
+             ORDER BY start_date IS NULL, start_date, name",
+        )
+        .map_err(DbError::from)?;
+    let sprints = stmt
+        .query_map([], |row| {
+            Ok(Sprint {
+                name: row.get(0)?,
+                state: row.get(1)?,
+                start_date: row.get(2)?,
+                end_date: row.get(3)?,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(sprints)
+}
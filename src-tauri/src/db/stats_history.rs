@@ -0,0 +1,113 @@
+use super::queries::get_summary_stats;
+use crate::errors::{AppError, DbError};
+use crate::models::{DurationMode, HistoryRange, StatsHistoryEntry, TicketFilter};
+use chrono::NaiveDateTime;
+use rusqlite::{params, Connection};
+
+/// Appends the current all-tickets `SummaryStats` to `stats_history`.
+pub fn record_stats_history(conn: &Connection, now: NaiveDateTime) -> Result<(), AppError> {
+    let stats = get_summary_stats(conn, &TicketFilter::default(), DurationMode::Business)?;
+    conn.execute(
+        "INSERT INTO stats_history (recorded_at, total_tickets, open_tickets, resolved_tickets,
+             stale_tickets, avg_resolution_hours, median_resolution_hours)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            now.and_utc().to_rfc3339(),
+            stats.total_tickets,
+            stats.open_tickets,
+            stats.resolved_tickets,
+            stats.stale_tickets,
+            stats.avg_resolution_hours,
+            stats.median_resolution_hours,
+        ],
+    )
+    .map_err(DbError::from)?;
+    Ok(())
+}
+
+/// History within `range` of `now`, oldest first. Syncs run many times a day,
+/// so only the last entry of each day is returned.
+pub fn get_stats_history(
+    conn: &Connection,
+    range: HistoryRange,
+    now: NaiveDateTime,
+) -> Result<Vec<StatsHistoryEntry>, AppError> {
+    let since = (now - chrono::Duration::days(range.days()))
+        .and_utc()
+        .to_rfc3339();
+    let mut stmt = conn
+        .prepare(
+            "SELECT recorded_at, total_tickets, open_tickets, resolved_tickets, stale_tickets,
+                 avg_resolution_hours, median_resolution_hours
+             FROM stats_history
+             WHERE id IN (
+                 SELECT MAX(id) FROM stats_history
+                 WHERE recorded_at >= ?1
+                 GROUP BY substr(recorded_at, 1, 10)
+             )
+             ORDER BY recorded_at",
+        )
+        .map_err(DbError::from)?;
+
+    let entries = stmt
+        .query_map(params![since], |row| {
+            Ok(StatsHistoryEntry {
+                recorded_at: row.get(0)?,
+                total_tickets: row.get(1)?,
+                open_tickets: row.get(2)?,
+                resolved_tickets: row.get(3)?,
+                stale_tickets: row.get(4)?,
+                avg_resolution_hours: row.get(5)?,
+                median_resolution_hours: row.get(6)?,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::upsert_ticket;
+    use crate::models::Ticket;
+    use chrono::NaiveDate;
+
+    fn at(month: u32, day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, month, day)
+            .and_then(|d| d.and_hms_opt(hour, 0, 0))
+            .expect("valid datetime")
+    }
+
+    #[test]
+    fn history_keeps_last_entry_per_day_within_range() {
+        let conn = setup_db();
+
+        record_stats_history(&conn, at(1, 2, 9)).expect("old entry");
+        record_stats_history(&conn, at(3, 10, 9)).expect("morning");
+        upsert_ticket(
+            &conn,
+            &Ticket {
+                created_at: "2025-03-10T10:00:00Z".to_string(),
+                updated_at: "2025-03-10T10:00:00Z".to_string(),
+                ..test_support::ticket("OPS-1")
+            },
+        )
+        .expect("ticket");
+        record_stats_history(&conn, at(3, 10, 17)).expect("evening");
+        record_stats_history(&conn, at(3, 11, 9)).expect("next day");
+
+        let history = get_stats_history(&conn, HistoryRange::Month, at(3, 12, 9)).expect("history");
+        assert_eq!(history.len(), 2);
+        assert!(history[0].recorded_at.starts_with("2025-03-10T17"));
+        assert_eq!(history[0].open_tickets, 1);
+        assert!(history[1].recorded_at.starts_with("2025-03-11"));
+
+        let history = get_stats_history(&conn, HistoryRange::Year, at(3, 12, 9)).expect("history");
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].total_tickets, 0);
+    }
+}
@@ -0,0 +1,359 @@
+use super::queries::{get_tickets, upsert_ticket, UpsertOutcome};
+use crate::errors::{AppError, DbError};
+use crate::models::{StatusCategory, StatusCategoryMapping, Ticket, TicketFilter};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub fn get_status_category_mappings(
+    conn: &Connection,
+) -> Result<Vec<StatusCategoryMapping>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT status, category FROM status_category_mappings ORDER BY status")
+        .map_err(DbError::from)?;
+    let mappings = stmt
+        .query_map([], |row| {
+            let category: String = row.get(1)?;
+            Ok(StatusCategoryMapping {
+                status: row.get(0)?,
+                category: StatusCategory::parse(&category).ok_or_else(|| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        1,
+                        Type::Text,
+                        format!("Unknown status category: {}", category).into(),
+                    )
+                })?,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+    Ok(mappings)
+}
+
+fn mapped_status_category(
+    conn: &Connection,
+    status: &str,
+) -> Result<Option<StatusCategory>, AppError> {
+    let category: Option<String> = conn
+        .prepare_cached("SELECT category FROM status_category_mappings WHERE status = ?1")
+        .and_then(|mut stmt| stmt.query_row(params![status], |row| row.get(0)).optional())
+        .map_err(DbError::from)?;
+    Ok(category.as_deref().and_then(StatusCategory::parse))
+}
+
+fn is_resolved_status(conn: &Connection, status: &str) -> Result<bool, AppError> {
+    conn.prepare_cached("SELECT 1 FROM resolved_statuses WHERE status = ?1")
+        .and_then(|mut stmt| stmt.exists(params![status]))
+        .map_err(|e| DbError::from(e).into())
+}
+
+/// When `ticket` entered its current status: the stored resolution date if
+/// it was already in that status, else the last journaled move into it,
+/// else its last update.
+fn effective_resolved_at(conn: &Connection, ticket: &Ticket) -> Result<String, AppError> {
+    let stored: Option<(String, Option<String>)> = conn
+        .query_row(
+            "SELECT status, resolved_at FROM tickets WHERE jira_key = ?1",
+            params![ticket.jira_key],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(DbError::from)?;
+    if let Some((status, Some(resolved_at))) = stored {
+        if status == ticket.status {
+            return Ok(resolved_at);
+        }
+    }
+
+    let status = serde_json::to_string(&ticket.status).unwrap_or_default();
+    let entered: Option<String> = conn
+        .query_row(
+            "SELECT changed_at FROM ticket_changes
+             WHERE ticket_key = ?1 AND field = 'status' AND new_value = ?2
+             ORDER BY id DESC LIMIT 1",
+            params![ticket.jira_key, status],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(DbError::from)?;
+    Ok(entered.unwrap_or_else(|| ticket.updated_at.clone()))
+}
+
+/// Settles `ticket`'s category, a local mapping for its status winning over
+/// what the source reported, and makes `resolved_at` agree with it: a ticket
+/// in a Done category or a configured resolved status is resolved, as of
+/// when it entered that status if the source sent no resolution date (see
+/// `effective_resolved_at`), and a ticket in any other known category is
+/// open. With no category known, `resolved_at` is left as the source sent it.
+/// Returns whether `resolved_at` was derived here rather than sent.
+pub(crate) fn apply_status_category(
+    conn: &Connection,
+    ticket: &mut Ticket,
+) -> Result<bool, AppError> {
+    if let Some(category) = mapped_status_category(conn, &ticket.status)? {
+        ticket.status_category = Some(category);
+    }
+    let resolved = ticket.status_category == Some(StatusCategory::Done)
+        || is_resolved_status(conn, &ticket.status)?;
+    if resolved && ticket.resolved_at.is_none() {
+        ticket.resolved_at = Some(effective_resolved_at(conn, ticket)?);
+        return Ok(true);
+    }
+    if !resolved && ticket.status_category.is_some() {
+        ticket.resolved_at = None;
+    }
+    Ok(false)
+}
+
+/// Whether the stored `jira_key`'s resolution date was derived from its
+/// status rather than sent by the source.
+fn resolution_derived(conn: &Connection, jira_key: &str) -> Result<bool, AppError> {
+    let derived: Option<bool> = conn
+        .query_row(
+            "SELECT resolution_derived FROM tickets WHERE jira_key = ?1",
+            params![jira_key],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(DbError::from)?;
+    Ok(derived.unwrap_or(false))
+}
+
+/// Maps every ticket in `status` to `category`, or removes the mapping when
+/// `category` is `None`, and rewrites the stored tickets in that status to
+/// match. Returns how many tickets changed. Without a mapping, tickets fall
+/// back to the source's category at the next sync.
+pub fn set_status_category_mapping(
+    conn: &Connection,
+    status: &str,
+    category: Option<StatusCategory>,
+) -> Result<u32, AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    match category {
+        Some(category) => tx.execute(
+            "INSERT INTO status_category_mappings (status, category) VALUES (?1, ?2)
+             ON CONFLICT(status) DO UPDATE SET category = excluded.category",
+            params![status, category.as_str()],
+        ),
+        None => tx.execute(
+            "DELETE FROM status_category_mappings WHERE status = ?1",
+            params![status],
+        ),
+    }
+    .map_err(DbError::from)?;
+
+    let filter = TicketFilter {
+        statuses: vec![status.to_string()],
+        ..Default::default()
+    }
+    .with_duplicates();
+    let mut changed = 0;
+    for mut ticket in get_tickets(&tx, &filter)? {
+        if category.is_none() {
+            ticket.status_category = None;
+        }
+        if upsert_ticket(&tx, &ticket)? == UpsertOutcome::Updated {
+            changed += 1;
+        }
+    }
+    tx.commit().map_err(DbError::from)?;
+    Ok(changed)
+}
+
+/// Statuses configured to count as resolved.
+pub fn get_resolved_statuses(conn: &Connection) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT status FROM resolved_statuses ORDER BY status")
+        .map_err(DbError::from)?;
+    let statuses = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(DbError::from)?;
+    Ok(statuses)
+}
+
+/// Replaces the statuses that count as resolved and rewrites the stored
+/// tickets in any status added or removed. Returns how many tickets changed.
+/// Tickets in a removed status lose a resolution date this setting derived,
+/// unless they are in a Done category; dates the source sent are kept.
+pub fn set_resolved_statuses(conn: &Connection, statuses: &[String]) -> Result<u32, AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    let previous = get_resolved_statuses(&tx)?;
+    tx.execute("DELETE FROM resolved_statuses", [])
+        .map_err(DbError::from)?;
+    for status in statuses {
+        tx.execute(
+            "INSERT OR IGNORE INTO resolved_statuses (status) VALUES (?1)",
+            params![status],
+        )
+        .map_err(DbError::from)?;
+    }
+
+    let removed: Vec<String> = previous
+        .iter()
+        .filter(|status| !statuses.contains(status))
+        .cloned()
+        .collect();
+    let added: Vec<String> = statuses
+        .iter()
+        .filter(|status| !previous.contains(status))
+        .cloned()
+        .collect();
+    let mut changed = 0;
+    for (touched, resolved) in [(removed, false), (added, true)] {
+        if touched.is_empty() {
+            continue;
+        }
+        let filter = TicketFilter {
+            statuses: touched,
+            ..Default::default()
+        }
+        .with_duplicates();
+        for mut ticket in get_tickets(&tx, &filter)? {
+            if !resolved
+                && ticket.status_category != Some(StatusCategory::Done)
+                && resolution_derived(&tx, &ticket.jira_key)?
+            {
+                ticket.resolved_at = None;
+            }
+            if upsert_ticket(&tx, &ticket)? == UpsertOutcome::Updated {
+                changed += 1;
+            }
+        }
+    }
+    tx.commit().map_err(DbError::from)?;
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{get_ticket_by_key, record_ticket_changes};
+
+    fn ticket(key: &str, status: &str, category: Option<StatusCategory>) -> Ticket {
+        Ticket {
+            status: status.to_string(),
+            status_category: category,
+            updated_at: "2025-01-08T17:00:00Z".to_string(),
+            ..test_support::ticket(key)
+        }
+    }
+
+    fn resolved_at(conn: &Connection, key: &str) -> Option<String> {
+        get_ticket_by_key(conn, key)
+            .expect("query")
+            .expect("ticket")
+            .resolved_at
+    }
+
+    #[test]
+    fn done_category_resolves_tickets_without_a_resolution_date() {
+        let conn = setup_db();
+
+        upsert_ticket(
+            &conn,
+            &ticket("OPS-1", "Shipped", Some(StatusCategory::Done)),
+        )
+        .expect("OPS-1");
+        upsert_ticket(&conn, &ticket("OPS-2", "Closed", None)).expect("OPS-2");
+        let mut reopened = ticket("OPS-3", "Reopened", Some(StatusCategory::Todo));
+        reopened.resolved_at = Some("2025-01-07T10:00:00Z".to_string());
+        upsert_ticket(&conn, &reopened).expect("OPS-3");
+
+        assert_eq!(
+            resolved_at(&conn, "OPS-1").as_deref(),
+            Some("2025-01-08T17:00:00Z")
+        );
+        assert_eq!(resolved_at(&conn, "OPS-2"), None);
+        assert_eq!(resolved_at(&conn, "OPS-3"), None);
+
+        // A local mapping covers sources that don't report a category
+        let changed =
+            set_status_category_mapping(&conn, "Closed", Some(StatusCategory::Done)).expect("map");
+        assert_eq!(changed, 1);
+        let closed = get_ticket_by_key(&conn, "OPS-2")
+            .expect("query")
+            .expect("ticket");
+        assert_eq!(closed.status_category, Some(StatusCategory::Done));
+        assert_eq!(closed.resolved_at.as_deref(), Some("2025-01-08T17:00:00Z"));
+        assert_eq!(
+            get_status_category_mappings(&conn).expect("mappings"),
+            vec![StatusCategoryMapping {
+                status: "Closed".to_string(),
+                category: StatusCategory::Done,
+            }]
+        );
+
+        set_status_category_mapping(&conn, "Closed", None).expect("unmap");
+        assert!(get_status_category_mappings(&conn)
+            .expect("mappings")
+            .is_empty());
+        let closed = get_ticket_by_key(&conn, "OPS-2")
+            .expect("query")
+            .expect("ticket");
+        assert_eq!(closed.status_category, None);
+    }
+
+    #[test]
+    fn resolved_statuses_resolve_tickets_as_of_entering_the_status() {
+        let conn = setup_db();
+
+        let open = ticket("OPS-1", "Open", None);
+        upsert_ticket(&conn, &open).expect("open");
+        let closed = ticket("OPS-1", "Closed", None);
+        upsert_ticket(&conn, &closed).expect("closed");
+        record_ticket_changes(&conn, &open, &closed, None, "2025-01-07T12:00:00Z")
+            .expect("journaled");
+        upsert_ticket(&conn, &ticket("OPS-2", "Closed", None)).expect("OPS-2");
+        let mut sent = ticket("OPS-3", "Closed", None);
+        sent.resolved_at = Some("2025-01-05T15:00:00Z".to_string());
+        upsert_ticket(&conn, &sent).expect("OPS-3");
+        upsert_ticket(&conn, &ticket("OPS-4", "Closed", None)).expect("OPS-4");
+        conn.execute(
+            "UPDATE tickets SET duplicate_of = 'OPS-2' WHERE jira_key = 'OPS-4'",
+            [],
+        )
+        .expect("merged");
+        assert_eq!(resolved_at(&conn, "OPS-1"), None);
+
+        let changed = set_resolved_statuses(&conn, &["Closed".to_string()]).expect("set");
+        assert_eq!(changed, 3);
+        assert_eq!(
+            get_resolved_statuses(&conn).expect("statuses"),
+            vec!["Closed".to_string()]
+        );
+        assert_eq!(
+            resolved_at(&conn, "OPS-1").as_deref(),
+            Some("2025-01-07T12:00:00Z")
+        );
+        assert_eq!(
+            resolved_at(&conn, "OPS-2").as_deref(),
+            Some("2025-01-08T17:00:00Z")
+        );
+
+        // Later updates in the same status keep the resolution date
+        let mut commented = ticket("OPS-1", "Closed", None);
+        commented.updated_at = "2025-01-10T09:00:00Z".to_string();
+        upsert_ticket(&conn, &commented).expect("commented");
+        assert_eq!(
+            resolved_at(&conn, "OPS-1").as_deref(),
+            Some("2025-01-07T12:00:00Z")
+        );
+
+        assert_eq!(
+            resolved_at(&conn, "OPS-4").as_deref(),
+            Some("2025-01-08T17:00:00Z")
+        );
+
+        // Only the dates the setting derived go away with it
+        set_resolved_statuses(&conn, &[]).expect("cleared");
+        assert_eq!(resolved_at(&conn, "OPS-1"), None);
+        assert_eq!(resolved_at(&conn, "OPS-4"), None);
+        assert_eq!(
+            resolved_at(&conn, "OPS-3").as_deref(),
+            Some("2025-01-05T15:00:00Z")
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use super::queries::{refresh_resolution_hours, RefreshScope};
+use crate::errors::{AppError, DbError};
+use crate::models::{SupportCalendar, SupportCalendarInput, WorkSchedule};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::collections::{BTreeSet, HashMap};
+
+/// The schedule every project's business hours follow, loaded once for a
+/// batch of tickets. Projects without a calendar get the default schedule.
+#[derive(Debug, Default)]
+pub(crate) struct ProjectSchedules {
+    by_project: HashMap<String, WorkSchedule>,
+    fallback: WorkSchedule,
+}
+
+impl ProjectSchedules {
+    pub(crate) fn get(&self, project_key: &str) -> &WorkSchedule {
+        self.by_project.get(project_key).unwrap_or(&self.fallback)
+    }
+}
+
+fn schedule_column(row: &Row, idx: usize) -> rusqlite::Result<WorkSchedule> {
+    let json: String = row.get(idx)?;
+    serde_json::from_str(&json)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, Box::new(e)))
+}
+
+pub(crate) fn project_schedules(conn: &Connection) -> Result<ProjectSchedules, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT p.project_key, c.schedule FROM project_calendars p
+             JOIN calendars c ON c.id = p.calendar_id",
+        )
+        .map_err(DbError::from)?;
+    let by_project = stmt
+        .query_map([], |row| Ok((row.get(0)?, schedule_column(row, 1)?)))
+        .map_err(DbError::from)?
+        .collect::<Result<HashMap<_, _>, _>>()
+        .map_err(DbError::from)?;
+    Ok(ProjectSchedules {
+        by_project,
+        fallback: WorkSchedule::default(),
+    })
+}
+
+/// The schedule of one project, for writing a single ticket.
+pub(crate) fn project_schedule(
+    conn: &Connection,
+    project_key: &str,
+) -> Result<WorkSchedule, AppError> {
+    let schedule = conn
+        .prepare_cached(
+            "SELECT c.schedule FROM project_calendars p
+             JOIN calendars c ON c.id = p.calendar_id
+             WHERE p.project_key = ?1",
+        )
+        .and_then(|mut stmt| {
+            stmt.query_row(params![project_key], |row| schedule_column(row, 0))
+                .optional()
+        })
+        .map_err(DbError::from)?;
+    Ok(schedule.unwrap_or_default())
+}
+
+fn calendar_projects(conn: &Connection, calendar_id: i64) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn
+        .prepare_cached(
+            "SELECT project_key FROM project_calendars WHERE calendar_id = ?1 ORDER BY project_key",
+        )
+        .map_err(DbError::from)?;
+    let projects = stmt
+        .query_map(params![calendar_id], |row| row.get(0))
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+    Ok(projects)
+}
+
+/// Assigns `projects` to the calendar, taking them off any other one, and
+/// returns every project whose schedule may have changed.
+fn replace_calendar_projects(
+    conn: &Connection,
+    calendar_id: i64,
+    projects: &[String],
+) -> Result<BTreeSet<String>, AppError> {
+    let mut touched: BTreeSet<String> = calendar_projects(conn, calendar_id)?.into_iter().collect();
+    conn.execute(
+        "DELETE FROM project_calendars WHERE calendar_id = ?1",
+        params![calendar_id],
+    )
+    .map_err(DbError::from)?;
+    let projects: BTreeSet<&str> = projects
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect();
+    for project in projects {
+        conn.execute(
+            "INSERT INTO project_calendars (project_key, calendar_id) VALUES (?1, ?2)
+             ON CONFLICT(project_key) DO UPDATE SET calendar_id = excluded.calendar_id",
+            params![project, calendar_id],
+        )
+        .map_err(DbError::from)?;
+        touched.insert(project.to_string());
+    }
+    Ok(touched)
+}
+
+/// Recomputes the stored business hours of every ticket in `projects`.
+fn refresh_projects(conn: &Connection, projects: &BTreeSet<String>) -> Result<(), AppError> {
+    let schedules = project_schedules(conn)?;
+    for project in projects {
+        for table in ["tickets", "tickets_archive"] {
+            refresh_resolution_hours(conn, table, RefreshScope::Project(project), &schedules)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn get_support_calendar(
+    conn: &Connection,
+    id: i64,
+) -> Result<Option<SupportCalendar>, AppError> {
+    let calendar = conn
+        .query_row(
+            "SELECT name, schedule FROM calendars WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get::<_, String>(0)?, schedule_column(row, 1)?)),
+        )
+        .optional()
+        .map_err(DbError::from)?;
+    match calendar {
+        Some((name, schedule)) => Ok(Some(SupportCalendar {
+            id,
+            name,
+            schedule,
+            projects: calendar_projects(conn, id)?,
+        })),
+        None => Ok(None),
+    }
+}
+
+/// Every calendar with its projects, by name.
+pub fn list_support_calendars(conn: &Connection) -> Result<Vec<SupportCalendar>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT id, name, schedule FROM calendars ORDER BY name COLLATE NOCASE")
+        .map_err(DbError::from)?;
+    let calendars = stmt
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, schedule_column(row, 2)?))
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<(i64, String, WorkSchedule)>, _>>()
+        .map_err(DbError::from)?;
+
+    calendars
+        .into_iter()
+        .map(|(id, name, schedule)| {
+            Ok(SupportCalendar {
+                id,
+                name,
+                schedule,
+                projects: calendar_projects(conn, id)?,
+            })
+        })
+        .collect()
+}
+
+fn schedule_json(schedule: &WorkSchedule) -> Result<String, AppError> {
+    serde_json::to_string(schedule)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize schedule: {}", e)))
+}
+
+pub fn create_support_calendar(
+    conn: &Connection,
+    input: &SupportCalendarInput,
+) -> Result<SupportCalendar, AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    tx.execute(
+        "INSERT INTO calendars (name, schedule) VALUES (?1, ?2)",
+        params![input.name.trim(), schedule_json(&input.schedule)?],
+    )
+    .map_err(DbError::from)?;
+    let id = tx.last_insert_rowid();
+    let touched = replace_calendar_projects(&tx, id, &input.projects)?;
+    refresh_projects(&tx, &touched)?;
+    tx.commit().map_err(DbError::from)?;
+
+    get_support_calendar(conn, id)?
+        .ok_or_else(|| AppError::Internal("Inserted calendar not found".to_string()))
+}
+
+/// Replaces the calendar's name, schedule and projects, and recomputes the
+/// stored resolution hours of the projects involved; `None` when it doesn't
+/// exist.
+pub fn update_support_calendar(
+    conn: &Connection,
+    id: i64,
+    input: &SupportCalendarInput,
+) -> Result<Option<SupportCalendar>, AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    let updated = tx
+        .execute(
+            "UPDATE calendars SET name = ?2, schedule = ?3 WHERE id = ?1",
+            params![id, input.name.trim(), schedule_json(&input.schedule)?],
+        )
+        .map_err(DbError::from)?;
+    if updated == 0 {
+        return Ok(None);
+    }
+    let touched = replace_calendar_projects(&tx, id, &input.projects)?;
+    refresh_projects(&tx, &touched)?;
+    tx.commit().map_err(DbError::from)?;
+
+    get_support_calendar(conn, id)
+}
+
+/// Deletes the calendar; its projects go back to the default schedule.
+pub fn delete_support_calendar(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    let touched = replace_calendar_projects(&tx, id, &[])?;
+    let deleted = tx
+        .execute("DELETE FROM calendars WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    refresh_projects(&tx, &touched)?;
+    tx.commit().map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::upsert_ticket;
+    use crate::models::Ticket;
+
+    fn stored_hours(conn: &Connection, key: &str) -> Option<f64> {
+        conn.query_row(
+            "SELECT resolution_hours FROM tickets WHERE jira_key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .expect("stored hours")
+    }
+
+    #[test]
+    fn project_calendars_decide_stored_business_hours() {
+        let conn = setup_db();
+        for (key, project) in [("EU-1", "EU"), ("US-1", "US")] {
+            let ticket = Ticket {
+                created_at: "2025-01-06T08:00:00Z".to_string(),
+                updated_at: "2025-01-06T12:00:00Z".to_string(),
+                project_key: project.to_string(),
+                ..test_support::ticket_resolved_at(key, Some("2025-01-06T12:00:00Z"))
+            };
+            upsert_ticket(&conn, &ticket).expect("ticket");
+        }
+        assert_eq!(stored_hours(&conn, "EU-1"), Some(3.0));
+
+        // 9–17 CET is 8–16 UTC
+        let calendar = create_support_calendar(
+            &conn,
+            &SupportCalendarInput {
+                name: "EU desk".to_string(),
+                schedule: WorkSchedule {
+                    utc_offset_minutes: 60,
+                    ..WorkSchedule::default()
+                },
+                projects: vec!["EU".to_string(), " ".to_string()],
+            },
+        )
+        .expect("calendar");
+        assert_eq!(calendar.projects, vec!["EU".to_string()]);
+        assert_eq!(stored_hours(&conn, "EU-1"), Some(4.0));
+        assert_eq!(stored_hours(&conn, "US-1"), Some(3.0));
+        assert_eq!(
+            project_schedules(&conn).expect("schedules").get("EU"),
+            &calendar.schedule
+        );
+
+        assert!(delete_support_calendar(&conn, calendar.id).expect("delete"));
+        assert_eq!(stored_hours(&conn, "EU-1"), Some(3.0));
+        assert!(list_support_calendars(&conn).expect("list").is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::errors::{AppError, DbError};
+use crate::models::{SyncItemFailure, SyncRun, SyncRunCounts, SyncRunStatus};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, Row};
+
+/// Older runs are pruned once this many are recorded.
+const SYNC_RUNS_KEPT: i64 = 500;
+
+/// Failures stored per run; the count is always recorded in full.
+const SYNC_RUN_FAILURES_KEPT: usize = 50;
+
+const SYNC_RUN_COLUMNS: &str = "id, source, started_at, finished_at, status, tickets_fetched, \
+     tickets_updated, tickets_failed, error, failures";
+
+fn map_sync_run_row(row: &Row) -> rusqlite::Result<SyncRun> {
+    let status: String = row.get(4)?;
+    let failures: String = row.get(9)?;
+
+    Ok(SyncRun {
+        id: row.get(0)?,
+        source: row.get(1)?,
+        started_at: row.get(2)?,
+        finished_at: row.get(3)?,
+        status: SyncRunStatus::parse(&status).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                4,
+                Type::Text,
+                format!("Unknown sync run status: {}", status).into(),
+            )
+        })?,
+        tickets_fetched: row.get(5)?,
+        tickets_updated: row.get(6)?,
+        tickets_failed: row.get(7)?,
+        error: row.get(8)?,
+        failures: serde_json::from_str(&failures)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(9, Type::Text, Box::new(e)))?,
+    })
+}
+
+pub fn start_sync_run(conn: &Connection, source: &str, started_at: &str) -> Result<i64, AppError> {
+    conn.execute(
+        "INSERT INTO sync_runs (source, started_at, status) VALUES (?1, ?2, ?3)",
+        params![source, started_at, SyncRunStatus::Running.as_str()],
+    )
+    .map_err(DbError::from)?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Closes a run as succeeded, or failed when `error` is set, and prunes
+/// the oldest runs. A run that skipped some items still succeeds.
+pub fn finish_sync_run(
+    conn: &Connection,
+    id: i64,
+    counts: SyncRunCounts,
+    failures: &[SyncItemFailure],
+    error: Option<&str>,
+    finished_at: &str,
+) -> Result<(), AppError> {
+    let status = match error {
+        Some(_) => SyncRunStatus::Failed,
+        None => SyncRunStatus::Succeeded,
+    };
+    let kept = &failures[..failures.len().min(SYNC_RUN_FAILURES_KEPT)];
+    let failures = serde_json::to_string(kept)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize sync failures: {}", e)))?;
+    conn.execute(
+        "UPDATE sync_runs SET finished_at = ?2, status = ?3, tickets_fetched = ?4,
+             tickets_updated = ?5, tickets_failed = ?6, error = ?7, failures = ?8
+         WHERE id = ?1",
+        params![
+            id,
+            finished_at,
+            status.as_str(),
+            counts.fetched,
+            counts.updated,
+            counts.failed,
+            error,
+            failures
+        ],
+    )
+    .map_err(DbError::from)?;
+
+    conn.execute(
+        "DELETE FROM sync_runs WHERE id <= (SELECT MAX(id) FROM sync_runs) - ?1",
+        params![SYNC_RUNS_KEPT],
+    )
+    .map_err(DbError::from)?;
+    Ok(())
+}
+
+/// The `limit` most recent runs, newest first.
+pub fn get_sync_history(conn: &Connection, limit: u32) -> Result<Vec<SyncRun>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM sync_runs ORDER BY id DESC LIMIT ?1",
+            SYNC_RUN_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+    let runs = stmt
+        .query_map(params![limit], map_sync_run_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+
+    #[test]
+    fn runs_are_listed_newest_first_with_outcome() {
+        let conn = setup_db();
+
+        let first = start_sync_run(&conn, "jira", "2025-01-06T09:00:00Z").expect("started");
+        finish_sync_run(
+            &conn,
+            first,
+            SyncRunCounts {
+                fetched: 12,
+                updated: 3,
+                failed: 1,
+            },
+            &[SyncItemFailure {
+                key: Some("OPS-7".to_string()),
+                error: "missing field `fields`".to_string(),
+            }],
+            None,
+            "2025-01-06T09:00:05Z",
+        )
+        .expect("finished");
+        let second = start_sync_run(&conn, "zendesk", "2025-01-06T10:00:00Z").expect("started");
+        finish_sync_run(
+            &conn,
+            second,
+            SyncRunCounts::default(),
+            &[],
+            Some("Authentication failed (401)"),
+            "2025-01-06T10:00:01Z",
+        )
+        .expect("finished");
+        start_sync_run(&conn, "jira", "2025-01-06T11:00:00Z").expect("started");
+
+        let runs = get_sync_history(&conn, 10).expect("history");
+        let statuses: Vec<SyncRunStatus> = runs.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                SyncRunStatus::Running,
+                SyncRunStatus::Failed,
+                SyncRunStatus::Succeeded
+            ]
+        );
+        assert_eq!(
+            runs[1].error.as_deref(),
+            Some("Authentication failed (401)")
+        );
+        assert_eq!(
+            (
+                runs[2].tickets_fetched,
+                runs[2].tickets_updated,
+                runs[2].tickets_failed
+            ),
+            (12, 3, 1)
+        );
+        assert_eq!(runs[2].failures[0].key.as_deref(), Some("OPS-7"));
+        assert!(runs[1].failures.is_empty());
+        assert_eq!(get_sync_history(&conn, 1).expect("history").len(), 1);
+    }
+}
@@ -0,0 +1,299 @@
+use super::queries::{average, get_tickets, percentile, resolution_hours};
+use super::support_calendars::project_schedules;
+use crate::errors::{AppError, DbError};
+use crate::models::{DurationMode, Team, TeamInput, TeamRollup, TicketFilter};
+use crate::services::time_calc::parse_timestamp;
+use chrono::NaiveDateTime;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Weeks of resolutions averaged into a team's throughput.
+const TEAM_THROUGHPUT_WEEKS: i64 = 4;
+
+fn team_members(conn: &Connection, team_id: i64) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn
+        .prepare_cached("SELECT assignee FROM team_members WHERE team_id = ?1 ORDER BY assignee")
+        .map_err(DbError::from)?;
+    let members = stmt
+        .query_map(params![team_id], |row| row.get(0))
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+    Ok(members)
+}
+
+fn replace_team_members(
+    conn: &Connection,
+    team_id: i64,
+    members: &[String],
+) -> Result<(), AppError> {
+    conn.execute(
+        "DELETE FROM team_members WHERE team_id = ?1",
+        params![team_id],
+    )
+    .map_err(DbError::from)?;
+    let members: BTreeSet<&str> = members
+        .iter()
+        .map(|m| m.trim())
+        .filter(|m| !m.is_empty())
+        .collect();
+    for member in members {
+        conn.execute(
+            "INSERT INTO team_members (team_id, assignee) VALUES (?1, ?2)",
+            params![team_id, member],
+        )
+        .map_err(DbError::from)?;
+    }
+    Ok(())
+}
+
+pub fn get_team(conn: &Connection, id: i64) -> Result<Option<Team>, AppError> {
+    let name: Option<String> = conn
+        .query_row("SELECT name FROM teams WHERE id = ?1", params![id], |row| {
+            row.get(0)
+        })
+        .optional()
+        .map_err(DbError::from)?;
+    match name {
+        Some(name) => Ok(Some(Team {
+            id,
+            name,
+            members: team_members(conn, id)?,
+        })),
+        None => Ok(None),
+    }
+}
+
+/// Every team with its members, by name.
+pub fn list_teams(conn: &Connection) -> Result<Vec<Team>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT id, name FROM teams ORDER BY name COLLATE NOCASE")
+        .map_err(DbError::from)?;
+    let teams = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<(i64, String)>, _>>()
+        .map_err(DbError::from)?;
+
+    teams
+        .into_iter()
+        .map(|(id, name)| {
+            Ok(Team {
+                id,
+                name,
+                members: team_members(conn, id)?,
+            })
+        })
+        .collect()
+}
+
+pub fn create_team(conn: &Connection, input: &TeamInput) -> Result<Team, AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    tx.execute(
+        "INSERT INTO teams (name) VALUES (?1)",
+        params![input.name.trim()],
+    )
+    .map_err(DbError::from)?;
+    let id = tx.last_insert_rowid();
+    replace_team_members(&tx, id, &input.members)?;
+    tx.commit().map_err(DbError::from)?;
+
+    get_team(conn, id)?.ok_or_else(|| AppError::Internal("Inserted team not found".to_string()))
+}
+
+/// Renames the team and replaces its members; `None` when it doesn't exist.
+pub fn update_team(
+    conn: &Connection,
+    id: i64,
+    input: &TeamInput,
+) -> Result<Option<Team>, AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    let updated = tx
+        .execute(
+            "UPDATE teams SET name = ?2 WHERE id = ?1",
+            params![id, input.name.trim()],
+        )
+        .map_err(DbError::from)?;
+    if updated == 0 {
+        return Ok(None);
+    }
+    replace_team_members(&tx, id, &input.members)?;
+    tx.commit().map_err(DbError::from)?;
+
+    get_team(conn, id)
+}
+
+pub fn delete_team(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    tx.execute("DELETE FROM team_members WHERE team_id = ?1", params![id])
+        .map_err(DbError::from)?;
+    let deleted = tx
+        .execute("DELETE FROM teams WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    tx.commit().map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+/// Workload, throughput and resolution time per team over `filter`'s
+/// tickets, by team name. Teams without any tickets are listed with zeros.
+pub fn get_team_rollups(
+    conn: &Connection,
+    filter: &TicketFilter,
+    mode: DurationMode,
+    now: NaiveDateTime,
+) -> Result<Vec<TeamRollup>, AppError> {
+    #[derive(Default)]
+    struct Tally {
+        open: u32,
+        resolved: u32,
+        recently_resolved: u32,
+        durations: Vec<f64>,
+    }
+
+    let mut tallies: BTreeMap<String, Tally> = BTreeMap::new();
+    let mut teams_of: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for team in list_teams(conn)? {
+        for member in &team.members {
+            teams_of
+                .entry(member.clone())
+                .or_default()
+                .push(team.name.clone());
+        }
+        tallies.entry(team.name).or_default();
+    }
+
+    let window_start = now - chrono::Duration::weeks(TEAM_THROUGHPUT_WEEKS);
+    let schedules = project_schedules(conn)?;
+    for ticket in get_tickets(conn, filter)? {
+        let Some(teams) = ticket.assignee.as_ref().and_then(|a| teams_of.get(a)) else {
+            continue;
+        };
+        for team in teams {
+            let tally = tallies.entry(team.clone()).or_default();
+            let Some(resolved_at) = ticket.resolved_at.as_deref() else {
+                tally.open += 1;
+                continue;
+            };
+            tally.resolved += 1;
+            if parse_timestamp(resolved_at).is_some_and(|at| at >= window_start && at <= now) {
+                tally.recently_resolved += 1;
+            }
+            let schedule = schedules.get(&ticket.project_key);
+            if let Some(hours) = resolution_hours(&ticket.created_at, resolved_at, mode, schedule) {
+                tally.durations.push(hours);
+            }
+        }
+    }
+
+    Ok(tallies
+        .into_iter()
+        .map(|(team, mut tally)| {
+            tally.durations.sort_by(f64::total_cmp);
+            let has_durations = !tally.durations.is_empty();
+            TeamRollup {
+                team,
+                open_tickets: tally.open,
+                resolved_tickets: tally.resolved,
+                throughput_per_week: f64::from(tally.recently_resolved)
+                    / TEAM_THROUGHPUT_WEEKS as f64,
+                avg_resolution_hours: has_durations.then(|| average(&tally.durations)),
+                p90_resolution_hours: has_durations.then(|| percentile(&tally.durations, 90.0)),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{aggregate, upsert_ticket};
+    use crate::models::{Dimension, Metric, Ticket};
+    use chrono::NaiveDate;
+
+    fn ticket(key: &str, assignee: &str, resolved_at: Option<&str>) -> Ticket {
+        Ticket {
+            assignee: Some(assignee.to_string()),
+            ..test_support::ticket_resolved_at(key, resolved_at)
+        }
+    }
+
+    fn team(name: &str, members: &[&str]) -> TeamInput {
+        TeamInput {
+            name: name.to_string(),
+            members: members.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn rolls_tickets_up_to_their_assignees_teams() {
+        let conn = setup_db();
+        for t in [
+            ticket("OPS-1", "ana", Some("2025-01-06T13:00:00Z")),
+            ticket("OPS-2", "ana", None),
+            ticket("OPS-3", "ben", Some("2025-01-06T11:00:00Z")),
+            ticket("OPS-4", "cy", None),
+        ] {
+            upsert_ticket(&conn, &t).expect("ticket");
+        }
+        let network = create_team(&conn, &team("Network", &["ana", " ben ", "ana"])).expect("team");
+        assert_eq!(network.members, vec!["ana", "ben"]);
+        let desk = create_team(&conn, &team("Desk", &["ben"])).expect("team");
+
+        let now = NaiveDate::from_ymd_opt(2025, 1, 10)
+            .and_then(|d| d.and_hms_opt(9, 0, 0))
+            .expect("valid datetime");
+        let rollups =
+            get_team_rollups(&conn, &TicketFilter::default(), DurationMode::Business, now)
+                .expect("rollups");
+        let rows: Vec<(&str, u32, u32, Option<f64>)> = rollups
+            .iter()
+            .map(|r| {
+                (
+                    r.team.as_str(),
+                    r.open_tickets,
+                    r.resolved_tickets,
+                    r.avg_resolution_hours,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![("Desk", 0, 1, Some(2.0)), ("Network", 1, 2, Some(3.0))]
+        );
+        assert_eq!(rollups[1].throughput_per_week, 0.5);
+
+        // The team dimension and filter work with the generic aggregations
+        let counts = aggregate(
+            &conn,
+            &Dimension::Team,
+            Metric::Count,
+            &TicketFilter::default(),
+            DurationMode::Business,
+        )
+        .expect("aggregate");
+        let counts: Vec<(&str, u32)> = counts.iter().map(|e| (e.name.as_str(), e.count)).collect();
+        assert_eq!(counts, vec![("Network", 3), ("Desk", 1), ("No team", 1)]);
+        let desk_only = TicketFilter {
+            teams: vec!["Desk".to_string()],
+            ..Default::default()
+        };
+        let keys: Vec<String> = get_tickets(&conn, &desk_only)
+            .expect("tickets")
+            .into_iter()
+            .map(|t| t.jira_key)
+            .collect();
+        assert_eq!(keys, vec!["OPS-3"]);
+
+        let renamed = update_team(&conn, desk.id, &team("Service Desk", &["cy"]))
+            .expect("update")
+            .expect("team exists");
+        assert_eq!(renamed.members, vec!["cy"]);
+        assert!(delete_team(&conn, network.id).expect("delete"));
+        assert_eq!(list_teams(&conn).expect("teams"), vec![renamed]);
+        assert_eq!(
+            update_team(&conn, network.id, &team("Gone", &[])).expect("update"),
+            None
+        );
+    }
+}
@@ -0,0 +1,43 @@
+//! Fixtures shared by the database and service tests.
+
+use super::initialize_database;
+use crate::models::Ticket;
+use rusqlite::Connection;
+
+/// A fresh in-memory database with the full schema.
+pub(crate) fn setup_db() -> Connection {
+    let conn = Connection::open_in_memory().expect("in-memory db");
+    initialize_database(&conn).expect("schema initialized");
+    conn
+}
+
+/// An open High-priority OPS task created 2025-01-06 09:00 UTC; tests
+/// override the fields they care about with struct update syntax.
+pub(crate) fn ticket(key: &str) -> Ticket {
+    Ticket {
+        jira_key: key.to_string(),
+        summary: format!("Summary {}", key),
+        status: "Open".to_string(),
+        priority: "High".to_string(),
+        issue_type: "Task".to_string(),
+        created_at: "2025-01-06T09:00:00Z".to_string(),
+        updated_at: "2025-01-06T09:00:00Z".to_string(),
+        project_key: "OPS".to_string(),
+        source: "jira".to_string(),
+        ..Default::default()
+    }
+}
+
+/// `ticket(key)` marked Done at `resolved_at`, or left open when `None`.
+pub(crate) fn ticket_resolved_at(key: &str, resolved_at: Option<&str>) -> Ticket {
+    Ticket {
+        status: if resolved_at.is_some() {
+            "Done"
+        } else {
+            "Open"
+        }
+        .to_string(),
+        resolved_at: resolved_at.map(str::to_string),
+        ..ticket(key)
+    }
+}
@@ -0,0 +1,204 @@
+use crate::errors::{AppError, DbError};
+use crate::models::{FieldChange, Ticket, TicketChange};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, Row};
+
+/// Changes on every update, so journaling it would only add noise.
+const UNJOURNALED_FIELDS: [&str; 1] = ["updated_at"];
+
+/// Fields of `incoming` that differ from `stored`, by field name.
+/// Every field is listed for a new ticket. The local row id is ignored.
+pub fn diff_tickets(stored: Option<&Ticket>, incoming: &Ticket) -> Vec<FieldChange> {
+    let to_fields = |ticket: &Ticket| match serde_json::to_value(ticket) {
+        Ok(serde_json::Value::Object(fields)) => fields,
+        _ => serde_json::Map::new(),
+    };
+    let before = stored.map(to_fields).unwrap_or_default();
+
+    to_fields(incoming)
+        .into_iter()
+        .filter(|(field, _)| field != "id")
+        .filter_map(|(field, after)| {
+            let before = before
+                .get(&field)
+                .cloned()
+                .unwrap_or(serde_json::Value::Null);
+            (stored.is_none() || before != after).then_some(FieldChange {
+                field,
+                before,
+                after,
+            })
+        })
+        .collect()
+}
+
+/// Journals every field that differs between two stored versions of a
+/// ticket. Returns how many changes were recorded.
+pub fn record_ticket_changes(
+    conn: &Connection,
+    before: &Ticket,
+    after: &Ticket,
+    sync_run_id: Option<i64>,
+    changed_at: &str,
+) -> Result<usize, AppError> {
+    let changes: Vec<FieldChange> = diff_tickets(Some(before), after)
+        .into_iter()
+        .filter(|change| !UNJOURNALED_FIELDS.contains(&change.field.as_str()))
+        .collect();
+
+    let mut stmt = conn
+        .prepare(
+            "INSERT INTO ticket_changes
+                 (ticket_key, field, old_value, new_value, changed_at, sync_run_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .map_err(DbError::from)?;
+    for change in &changes {
+        stmt.execute(params![
+            after.jira_key,
+            change.field,
+            change.before.to_string(),
+            change.after.to_string(),
+            changed_at,
+            sync_run_id
+        ])
+        .map_err(DbError::from)?;
+    }
+
+    Ok(changes.len())
+}
+
+fn map_ticket_change_row(row: &Row) -> rusqlite::Result<TicketChange> {
+    let json = |idx: usize| -> rusqlite::Result<serde_json::Value> {
+        let text: String = row.get(idx)?;
+        serde_json::from_str(&text)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, Box::new(e)))
+    };
+
+    Ok(TicketChange {
+        id: row.get(0)?,
+        ticket_key: row.get(1)?,
+        field: row.get(2)?,
+        old_value: json(3)?,
+        new_value: json(4)?,
+        changed_at: row.get(5)?,
+        sync_run_id: row.get(6)?,
+    })
+}
+
+/// Recorded field changes for `key`, newest first.
+pub fn get_ticket_change_log(conn: &Connection, key: &str) -> Result<Vec<TicketChange>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, ticket_key, field, old_value, new_value, changed_at, sync_run_id
+             FROM ticket_changes
+             WHERE ticket_key = ?1
+             ORDER BY id DESC",
+        )
+        .map_err(DbError::from)?;
+    let changes = stmt
+        .query_map(params![key], map_ticket_change_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(changes)
+}
+
+/// Every recorded change to `field`, oldest first within each ticket.
+pub fn get_field_changes(conn: &Connection, field: &str) -> Result<Vec<TicketChange>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, ticket_key, field, old_value, new_value, changed_at, sync_run_id
+             FROM ticket_changes
+             WHERE field = ?1
+             ORDER BY ticket_key, id",
+        )
+        .map_err(DbError::from)?;
+    let changes = stmt
+        .query_map(params![field], map_ticket_change_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+
+    fn ticket(status: &str) -> Ticket {
+        Ticket {
+            id: 7,
+            summary: "Printer on fire".to_string(),
+            status: status.to_string(),
+            updated_at: "2025-01-03T09:00:00Z".to_string(),
+            ..test_support::ticket("OPS-1")
+        }
+    }
+
+    #[test]
+    fn diff_lists_only_changed_fields_for_stored_tickets() {
+        let stored = ticket("Open");
+        let mut incoming = ticket("Done");
+        incoming.id = 0;
+        incoming.updated_at = "2025-01-04T09:00:00Z".to_string();
+
+        let fields: Vec<(String, serde_json::Value, serde_json::Value)> =
+            diff_tickets(Some(&stored), &incoming)
+                .into_iter()
+                .map(|c| (c.field, c.before, c.after))
+                .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("status".to_string(), "Open".into(), "Done".into()),
+                (
+                    "updated_at".to_string(),
+                    "2025-01-03T09:00:00Z".into(),
+                    "2025-01-04T09:00:00Z".into()
+                ),
+            ]
+        );
+        assert!(diff_tickets(Some(&stored), &ticket("Open")).is_empty());
+    }
+
+    #[test]
+    fn diff_lists_every_field_for_new_tickets() {
+        let changes = diff_tickets(None, &ticket("Open"));
+
+        assert!(changes.iter().all(|c| c.before.is_null()));
+        assert!(changes.iter().any(|c| c.field == "jira_key"));
+        assert!(!changes.iter().any(|c| c.field == "id"));
+    }
+
+    #[test]
+    fn change_log_records_old_and_new_values_newest_first() {
+        let conn = setup_db();
+
+        let open = ticket("Open");
+        let mut done = ticket("Done");
+        done.updated_at = "2025-01-04T09:00:00Z".to_string();
+        done.components = vec!["API".to_string()];
+        let recorded = record_ticket_changes(&conn, &open, &done, Some(3), "2025-01-04T09:00:05Z")
+            .expect("changes recorded");
+        assert_eq!(recorded, 2);
+        record_ticket_changes(&conn, &done, &ticket("Open"), None, "2025-01-05T09:00:00Z")
+            .expect("changes recorded");
+
+        let log = get_ticket_change_log(&conn, "OPS-1").expect("change log");
+        let fields: Vec<&str> = log.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["status", "components", "status", "components"]);
+        assert_eq!(log[2].old_value, serde_json::json!("Open"));
+        assert_eq!(log[2].new_value, serde_json::json!("Done"));
+        assert_eq!(log[2].sync_run_id, Some(3));
+        assert_eq!(log[3].new_value, serde_json::json!(["API"]));
+        assert_eq!(log[1].new_value, serde_json::json!([]));
+        assert_eq!(log[0].sync_run_id, None);
+        assert!(get_ticket_change_log(&conn, "OPS-2")
+            .expect("change log")
+            .is_empty());
+    }
+}
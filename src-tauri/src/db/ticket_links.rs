@@ -0,0 +1,142 @@
+use super::local_links::get_locally_linked_tickets;
+use crate::errors::{AppError, DbError};
+use crate::models::{LinkedTicket, TicketLink};
+use rusqlite::{params, Connection};
+
+/// Link types whose inward side is blocked by the outward side. Jira's
+/// default is named "Blocks"; some sites only keep the "blocks" wording.
+pub(crate) const BLOCKS_LINK_CONDITION: &str =
+    "(LOWER(link_type) = 'blocks' OR LOWER(outward_label) = 'blocks')";
+
+/// Replaces every stored link touching `key` with `links`. Jira reports a
+/// link on both issues, so the latest copy of either side is authoritative.
+pub fn replace_ticket_links(
+    conn: &Connection,
+    key: &str,
+    links: &[TicketLink],
+) -> Result<(), AppError> {
+    conn.execute(
+        "DELETE FROM ticket_links WHERE outward_key = ?1 OR inward_key = ?1",
+        params![key],
+    )
+    .map_err(DbError::from)?;
+
+    let mut stmt = conn
+        .prepare(
+            "INSERT OR REPLACE INTO ticket_links
+                 (outward_key, inward_key, link_type, outward_label, inward_label)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )
+        .map_err(DbError::from)?;
+    for link in links {
+        stmt.execute(params![
+            link.outward_key,
+            link.inward_key,
+            link.link_type,
+            link.outward_label,
+            link.inward_label,
+        ])
+        .map_err(DbError::from)?;
+    }
+    Ok(())
+}
+
+/// Tickets linked to `key` in either direction, in Jira or locally, ordered
+/// by link type then key.
+pub fn get_linked_tickets(conn: &Connection, key: &str) -> Result<Vec<LinkedTicket>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT other_key, link_type, relation, t.summary, t.status, t.resolved_at IS NOT NULL
+             FROM (
+                 SELECT inward_key AS other_key, link_type, outward_label AS relation
+                 FROM ticket_links WHERE outward_key = ?1
+                 UNION
+                 SELECT outward_key, link_type, inward_label
+                 FROM ticket_links WHERE inward_key = ?1
+             )
+             LEFT JOIN tickets t ON t.jira_key = other_key",
+        )
+        .map_err(DbError::from)?;
+    let linked = stmt
+        .query_map(params![key], |row| {
+            Ok(LinkedTicket {
+                jira_key: row.get(0)?,
+                link_type: row.get(1)?,
+                relation: row.get(2)?,
+                summary: row.get(3)?,
+                status: row.get(4)?,
+                resolved: row.get(5)?,
+                local: false,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    let mut linked: Vec<LinkedTicket> = linked
+        .into_iter()
+        .chain(get_locally_linked_tickets(conn, key)?)
+        .collect();
+    linked.sort_by(|a, b| {
+        a.link_type
+            .cmp(&b.link_type)
+            .then_with(|| a.jira_key.cmp(&b.jira_key))
+    });
+    Ok(linked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{get_summary_stats, upsert_ticket};
+    use crate::models::{DurationMode, Ticket, TicketFilter};
+
+    fn ticket(key: &str, resolved_at: Option<&str>) -> Ticket {
+        Ticket {
+            created_at: "2025-01-06T09:00:00+00:00".to_string(),
+            updated_at: "2025-01-06T09:00:00+00:00".to_string(),
+            ..test_support::ticket_resolved_at(key, resolved_at)
+        }
+    }
+
+    fn blocks(outward: &str, inward: &str) -> TicketLink {
+        TicketLink {
+            outward_key: outward.to_string(),
+            inward_key: inward.to_string(),
+            link_type: "Blocks".to_string(),
+            outward_label: "blocks".to_string(),
+            inward_label: "is blocked by".to_string(),
+        }
+    }
+
+    #[test]
+    fn links_read_from_both_sides_and_count_blocked_tickets() {
+        let conn = setup_db();
+        upsert_ticket(&conn, &ticket("OPS-1", None)).expect("ticket");
+        upsert_ticket(&conn, &ticket("OPS-2", None)).expect("ticket");
+        upsert_ticket(&conn, &ticket("OPS-3", None)).expect("ticket");
+        upsert_ticket(&conn, &ticket("OPS-4", Some("2025-01-07T09:00:00+00:00"))).expect("ticket");
+
+        // OPS-2 is blocked by OPS-1; OPS-3 only by the resolved OPS-4
+        replace_ticket_links(&conn, "OPS-1", &[blocks("OPS-1", "OPS-2")]).expect("links");
+        replace_ticket_links(&conn, "OPS-2", &[blocks("OPS-1", "OPS-2")]).expect("links");
+        replace_ticket_links(&conn, "OPS-4", &[blocks("OPS-4", "OPS-3")]).expect("links");
+
+        let linked = get_linked_tickets(&conn, "OPS-2").expect("linked");
+        assert_eq!(linked.len(), 1);
+        assert_eq!(linked[0].jira_key, "OPS-1");
+        assert_eq!(linked[0].relation, "is blocked by");
+        assert!(!linked[0].resolved);
+
+        let stats = get_summary_stats(&conn, &TicketFilter::default(), DurationMode::Business)
+            .expect("stats");
+        assert_eq!(stats.blocked_tickets, 1);
+
+        // The link is gone once either side stops reporting it
+        replace_ticket_links(&conn, "OPS-1", &[]).expect("links");
+        assert!(get_linked_tickets(&conn, "OPS-2")
+            .expect("linked")
+            .is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::errors::{AppError, DbError};
+use crate::models::TicketNote;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+const NOTE_COLUMNS: &str = "id, ticket_key, body, created_at, updated_at";
+
+fn map_note_row(row: &Row) -> rusqlite::Result<TicketNote> {
+    Ok(TicketNote {
+        id: row.get(0)?,
+        ticket_key: row.get(1)?,
+        body: row.get(2)?,
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+fn get_ticket_note(conn: &Connection, id: i64) -> Result<Option<TicketNote>, AppError> {
+    let note = conn
+        .query_row(
+            &format!("SELECT {} FROM ticket_notes WHERE id = ?1", NOTE_COLUMNS),
+            params![id],
+            map_note_row,
+        )
+        .optional()
+        .map_err(DbError::from)?;
+    Ok(note)
+}
+
+/// Notes on ticket `key`, oldest first.
+pub fn list_ticket_notes(conn: &Connection, key: &str) -> Result<Vec<TicketNote>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM ticket_notes WHERE ticket_key = ?1 ORDER BY created_at, id",
+            NOTE_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+    let notes = stmt
+        .query_map(params![key], map_note_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(notes)
+}
+
+pub fn create_ticket_note(
+    conn: &Connection,
+    key: &str,
+    body: &str,
+    now: &str,
+) -> Result<TicketNote, AppError> {
+    conn.execute(
+        "INSERT INTO ticket_notes (ticket_key, body, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?3)",
+        params![key, body, now],
+    )
+    .map_err(DbError::from)?;
+
+    get_ticket_note(conn, conn.last_insert_rowid())?
+        .ok_or_else(|| AppError::Internal("Inserted note not found".to_string()))
+}
+
+/// Replaces the note's body; `None` when it doesn't exist.
+pub fn update_ticket_note(
+    conn: &Connection,
+    id: i64,
+    body: &str,
+    now: &str,
+) -> Result<Option<TicketNote>, AppError> {
+    let updated = conn
+        .execute(
+            "UPDATE ticket_notes SET body = ?2, updated_at = ?3 WHERE id = ?1",
+            params![id, body, now],
+        )
+        .map_err(DbError::from)?;
+    if updated == 0 {
+        return Ok(None);
+    }
+    get_ticket_note(conn, id)
+}
+
+pub fn delete_ticket_note(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute("DELETE FROM ticket_notes WHERE id = ?1", params![id])
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+
+    #[test]
+    fn notes_are_edited_in_place() {
+        let conn = setup_db();
+
+        let first = create_ticket_note(&conn, "OPS-1", "Ask **Dana**", "2025-01-06T09:00:00Z")
+            .expect("note");
+        let second =
+            create_ticket_note(&conn, "OPS-1", "Vendor ticket #42", "2025-01-06T10:00:00Z")
+                .expect("note");
+        create_ticket_note(&conn, "OPS-2", "Unrelated", "2025-01-06T11:00:00Z").expect("note");
+
+        let edited = update_ticket_note(&conn, first.id, "Dana replied", "2025-01-07T09:00:00Z")
+            .expect("update")
+            .expect("note exists");
+        assert_eq!(edited.created_at, "2025-01-06T09:00:00Z");
+        assert_eq!(edited.updated_at, "2025-01-07T09:00:00Z");
+        assert_eq!(
+            list_ticket_notes(&conn, "OPS-1").expect("notes"),
+            vec![edited, second.clone()]
+        );
+
+        assert!(delete_ticket_note(&conn, second.id).expect("delete"));
+        assert!(!delete_ticket_note(&conn, second.id).expect("delete"));
+        assert_eq!(
+            update_ticket_note(&conn, second.id, "Gone", "2025-01-08T09:00:00Z").expect("update"),
+            None
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use super::queries::{map_ticket_row, TICKET_COLUMNS};
+use crate::errors::{AppError, DbError};
+use crate::models::Ticket;
+use rusqlite::{params, Connection};
+use std::collections::{HashMap, HashSet};
+
+/// Puts `keys` at the front of the personal queue in the given order. Other
+/// queued tickets keep their relative order behind them.
+pub fn reorder_tickets(conn: &Connection, keys: &[String]) -> Result<(), AppError> {
+    let mut seen = HashSet::new();
+    let mut order: Vec<String> = keys
+        .iter()
+        .filter(|key| seen.insert(key.as_str()))
+        .cloned()
+        .collect();
+    for key in ranked_keys(conn)? {
+        if !seen.contains(key.as_str()) {
+            order.push(key);
+        }
+    }
+
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    tx.execute("DELETE FROM ticket_rank", [])
+        .map_err(DbError::from)?;
+    for (position, key) in order.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO ticket_rank (ticket_key, position) VALUES (?1, ?2)",
+            params![key, position as i64],
+        )
+        .map_err(DbError::from)?;
+    }
+    tx.commit().map_err(DbError::from)?;
+    Ok(())
+}
+
+/// Takes ticket `key` out of the personal queue.
+pub fn remove_ticket_rank(conn: &Connection, key: &str) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute(
+            "DELETE FROM ticket_rank WHERE ticket_key = ?1",
+            params![key],
+        )
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+fn ranked_keys(conn: &Connection) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT ticket_key FROM ticket_rank ORDER BY position")
+        .map_err(DbError::from)?;
+    let keys = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+    Ok(keys)
+}
+
+/// Queue position by ticket key, for sorting other views by personal rank.
+pub(crate) fn ticket_positions(conn: &Connection) -> Result<HashMap<String, usize>, AppError> {
+    Ok(ranked_keys(conn)?
+        .into_iter()
+        .enumerate()
+        .map(|(position, key)| (key, position))
+        .collect())
+}
+
+/// The personal queue in order. Queued tickets no longer stored locally are
+/// skipped.
+pub fn get_ranked_tickets(conn: &Connection) -> Result<Vec<Ticket>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM tickets
+             JOIN (SELECT ticket_key, position FROM ticket_rank) ON jira_key = ticket_key
+             ORDER BY position",
+            TICKET_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+    let tickets = stmt
+        .query_map([], map_ticket_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(tickets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::upsert_ticket;
+
+    fn ticket(key: &str, summary: &str) -> Ticket {
+        Ticket {
+            summary: summary.to_string(),
+            ..test_support::ticket(key)
+        }
+    }
+
+    fn keys(conn: &Connection) -> Vec<String> {
+        get_ranked_tickets(conn)
+            .expect("queue")
+            .into_iter()
+            .map(|t| t.jira_key)
+            .collect()
+    }
+
+    #[test]
+    fn queue_order_survives_syncs() {
+        let conn = setup_db();
+        for key in ["OPS-1", "OPS-2", "OPS-3", "OPS-4"] {
+            upsert_ticket(&conn, &ticket(key, "Original")).expect("ticket");
+        }
+        let order = |keys: &[&str]| keys.iter().map(|k| k.to_string()).collect::<Vec<_>>();
+
+        reorder_tickets(&conn, &order(&["OPS-3", "OPS-1", "OPS-4"])).expect("reorder");
+        // Dragging OPS-4 to the top keeps the rest in place
+        reorder_tickets(&conn, &order(&["OPS-4", "OPS-4"])).expect("reorder");
+        assert_eq!(keys(&conn), order(&["OPS-4", "OPS-3", "OPS-1"]));
+
+        upsert_ticket(&conn, &ticket("OPS-1", "Updated by Jira")).expect("ticket");
+        assert_eq!(keys(&conn), order(&["OPS-4", "OPS-3", "OPS-1"]));
+
+        assert!(remove_ticket_rank(&conn, "OPS-3").expect("remove"));
+        assert_eq!(keys(&conn), order(&["OPS-4", "OPS-1"]));
+        assert_eq!(ticket_positions(&conn).expect("positions")["OPS-1"], 1);
+    }
+}
@@ -0,0 +1,90 @@
+use super::queries::{map_ticket_row, TICKET_COLUMNS};
+use crate::errors::{AppError, DbError};
+use crate::models::RecentlyViewed;
+use rusqlite::{params, Connection};
+
+/// Views kept per install; older ones are dropped as new tickets are viewed.
+const MAX_TICKET_VIEWS: u32 = 200;
+
+/// Records that ticket `key` was viewed at `viewed_at`.
+pub fn mark_viewed(conn: &Connection, key: &str, viewed_at: &str) -> Result<(), AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    tx.execute(
+        "INSERT INTO ticket_views (ticket_key, viewed_at) VALUES (?1, ?2)
+         ON CONFLICT(ticket_key) DO UPDATE SET viewed_at = excluded.viewed_at",
+        params![key, viewed_at],
+    )
+    .map_err(DbError::from)?;
+    tx.execute(
+        "DELETE FROM ticket_views WHERE ticket_key NOT IN (
+             SELECT ticket_key FROM ticket_views ORDER BY viewed_at DESC LIMIT ?1
+         )",
+        params![MAX_TICKET_VIEWS],
+    )
+    .map_err(DbError::from)?;
+    tx.commit().map_err(DbError::from)?;
+    Ok(())
+}
+
+/// The `limit` tickets viewed most recently, latest first. Views of tickets
+/// no longer stored locally are skipped.
+pub fn get_recently_viewed(conn: &Connection, limit: u32) -> Result<Vec<RecentlyViewed>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {}, viewed_at FROM tickets
+             JOIN (SELECT ticket_key, viewed_at FROM ticket_views) ON jira_key = ticket_key
+             ORDER BY viewed_at DESC
+             LIMIT ?1",
+            TICKET_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+    let column_count = stmt.column_count();
+    let views = stmt
+        .query_map(params![limit], |row| {
+            Ok(RecentlyViewed {
+                ticket: map_ticket_row(row)?,
+                viewed_at: row.get(column_count - 1)?,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(views)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{setup_db, ticket};
+    use crate::db::upsert_ticket;
+
+    #[test]
+    fn lists_latest_views_first() {
+        let conn = setup_db();
+        for key in ["OPS-1", "OPS-2", "OPS-3"] {
+            upsert_ticket(&conn, &ticket(key)).expect("ticket");
+        }
+
+        mark_viewed(&conn, "OPS-1", "2025-01-06T10:00:00Z").expect("view");
+        mark_viewed(&conn, "OPS-2", "2025-01-06T11:00:00Z").expect("view");
+        mark_viewed(&conn, "GONE-1", "2025-01-06T11:30:00Z").expect("view");
+        mark_viewed(&conn, "OPS-3", "2025-01-06T12:00:00Z").expect("view");
+        // Viewing again moves it to the top
+        mark_viewed(&conn, "OPS-1", "2025-01-06T13:00:00Z").expect("view");
+
+        let views = get_recently_viewed(&conn, 2).expect("views");
+        let keys: Vec<(&str, &str)> = views
+            .iter()
+            .map(|v| (v.ticket.jira_key.as_str(), v.viewed_at.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("OPS-1", "2025-01-06T13:00:00Z"),
+                ("OPS-3", "2025-01-06T12:00:00Z")
+            ]
+        );
+        assert_eq!(get_recently_viewed(&conn, 10).expect("views").len(), 3);
+    }
+}
@@ -0,0 +1,409 @@
+use crate::errors::{AppError, DbError};
+use crate::models::{
+    DateRange, FocusStats, FocusTicketStats, FocusTotals, TimeEntry, TimeReportPeriod,
+    TimeReportRow,
+};
+use crate::services::time_calc::parse_timestamp;
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::collections::BTreeMap;
+
+const TIME_ENTRY_COLUMNS: &str =
+    "id, ticket_key, started_at, ended_at, worklog_id, focus_minutes, interruptions";
+
+fn map_time_entry_row(row: &Row) -> rusqlite::Result<TimeEntry> {
+    Ok(TimeEntry {
+        id: row.get(0)?,
+        ticket_key: row.get(1)?,
+        started_at: row.get(2)?,
+        ended_at: row.get(3)?,
+        worklog_id: row.get(4)?,
+        focus_minutes: row.get(5)?,
+        interruptions: row.get(6)?,
+    })
+}
+
+pub fn get_time_entry(conn: &Connection, id: i64) -> Result<Option<TimeEntry>, AppError> {
+    let entry = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM time_entries WHERE id = ?1",
+                TIME_ENTRY_COLUMNS
+            ),
+            params![id],
+            map_time_entry_row,
+        )
+        .optional()
+        .map_err(DbError::from)?;
+    Ok(entry)
+}
+
+fn running_timer(conn: &Connection, key: &str) -> Result<Option<TimeEntry>, AppError> {
+    let entry = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM time_entries WHERE ticket_key = ?1 AND ended_at IS NULL",
+                TIME_ENTRY_COLUMNS
+            ),
+            params![key],
+            map_time_entry_row,
+        )
+        .optional()
+        .map_err(DbError::from)?;
+    Ok(entry)
+}
+
+/// Starts a timer on ticket `key` at `now`, or returns the one already running.
+pub fn start_timer(conn: &Connection, key: &str, now: &str) -> Result<TimeEntry, AppError> {
+    conn.execute(
+        "INSERT OR IGNORE INTO time_entries (ticket_key, started_at) VALUES (?1, ?2)",
+        params![key, now],
+    )
+    .map_err(DbError::from)?;
+
+    running_timer(conn, key)?
+        .ok_or_else(|| AppError::Internal("Started timer not found".to_string()))
+}
+
+/// Stops the timer running on ticket `key` at `now`; `None` when none runs.
+pub fn stop_timer(conn: &Connection, key: &str, now: &str) -> Result<Option<TimeEntry>, AppError> {
+    let Some(entry) = running_timer(conn, key)? else {
+        return Ok(None);
+    };
+    conn.execute(
+        "UPDATE time_entries SET ended_at = ?2 WHERE id = ?1",
+        params![entry.id, now],
+    )
+    .map_err(DbError::from)?;
+    get_time_entry(conn, entry.id)
+}
+
+/// Starts a focus session of `minutes` on ticket `key` at `now`. It runs
+/// like any timer and is stopped with `stop_timer`.
+pub fn start_focus_session(
+    conn: &Connection,
+    key: &str,
+    minutes: u32,
+    now: &str,
+) -> Result<TimeEntry, AppError> {
+    if running_timer(conn, key)?.is_some() {
+        return Err(AppError::Config(format!(
+            "A timer is already running on {}",
+            key
+        )));
+    }
+    conn.execute(
+        "INSERT INTO time_entries (ticket_key, started_at, focus_minutes) VALUES (?1, ?2, ?3)",
+        params![key, now, minutes],
+    )
+    .map_err(DbError::from)?;
+
+    get_time_entry(conn, conn.last_insert_rowid())?
+        .ok_or_else(|| AppError::Internal("Started focus session not found".to_string()))
+}
+
+/// Counts an interruption against the focus session running on ticket
+/// `key`; `None` when no focus session runs.
+pub fn record_interruption(conn: &Connection, key: &str) -> Result<Option<TimeEntry>, AppError> {
+    let Some(entry) = running_timer(conn, key)?.filter(|e| e.focus_minutes.is_some()) else {
+        return Ok(None);
+    };
+    conn.execute(
+        "UPDATE time_entries SET interruptions = interruptions + 1 WHERE id = ?1",
+        params![entry.id],
+    )
+    .map_err(DbError::from)?;
+    get_time_entry(conn, entry.id)
+}
+
+/// Timers still running, earliest first.
+pub fn get_running_timers(conn: &Connection) -> Result<Vec<TimeEntry>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM time_entries WHERE ended_at IS NULL ORDER BY started_at, id",
+            TIME_ENTRY_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+    let entries = stmt
+        .query_map([], map_time_entry_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(entries)
+}
+
+pub fn set_worklog_id(conn: &Connection, id: i64, worklog_id: &str) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE time_entries SET worklog_id = ?2 WHERE id = ?1",
+        params![id, worklog_id],
+    )
+    .map_err(DbError::from)?;
+    Ok(())
+}
+
+/// Seconds tracked per ticket and day (or week) from `from` up to but not
+/// including `to`, in UTC. Entries spanning midnight are split between the
+/// days they cover, and running timers count up to `now`.
+pub fn get_time_report(
+    conn: &Connection,
+    period: TimeReportPeriod,
+    from: NaiveDate,
+    to: NaiveDate,
+    now: NaiveDateTime,
+) -> Result<Vec<TimeReportRow>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT ticket_key, started_at, ended_at FROM time_entries")
+        .map_err(DbError::from)?;
+    let entries = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    let (Some(range_start), Some(range_end)) = (from.and_hms_opt(0, 0, 0), to.and_hms_opt(0, 0, 0))
+    else {
+        return Ok(Vec::new());
+    };
+    let mut totals: BTreeMap<(NaiveDate, String), i64> = BTreeMap::new();
+    for (ticket_key, started_at, ended_at) in entries {
+        let Some(start) = parse_timestamp(&started_at) else {
+            continue;
+        };
+        let end = match ended_at {
+            Some(ended_at) => match parse_timestamp(&ended_at) {
+                Some(end) => end,
+                None => continue,
+            },
+            None => now,
+        };
+        let mut cursor = start.max(range_start);
+        let end = end.min(range_end);
+        while cursor < end {
+            let day = cursor.date();
+            let next_day = day
+                .succ_opt()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .unwrap_or(end);
+            let slice_end = next_day.min(end);
+            let bucket = match period {
+                TimeReportPeriod::Day => day,
+                TimeReportPeriod::Week => {
+                    day - chrono::Duration::days(i64::from(day.weekday().num_days_from_monday()))
+                }
+            };
+            *totals.entry((bucket, ticket_key.clone())).or_default() +=
+                (slice_end - cursor).num_seconds();
+            cursor = slice_end;
+        }
+    }
+
+    Ok(totals
+        .into_iter()
+        .map(|((bucket, ticket_key), seconds)| TimeReportRow {
+            period_start: bucket.format("%Y-%m-%d").to_string(),
+            ticket_key,
+            seconds,
+        })
+        .collect())
+}
+
+/// A `DateRange` bound, either a bare date (midnight UTC) or a timestamp.
+fn parse_bound(value: &str) -> Option<NaiveDateTime> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .or_else(|| parse_timestamp(value))
+}
+
+/// Totals of the focus sessions started within `range`. Running sessions
+/// count up to `now`, and a session is completed once it has run for its
+/// planned length.
+pub fn get_focus_stats(
+    conn: &Connection,
+    range: &DateRange,
+    now: NaiveDateTime,
+) -> Result<FocusStats, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM time_entries WHERE focus_minutes IS NOT NULL",
+            TIME_ENTRY_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+    let sessions = stmt
+        .query_map([], map_time_entry_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    let from = range.from.as_deref().and_then(parse_bound);
+    let to = range.to.as_deref().and_then(parse_bound);
+    let mut totals = FocusTotals::default();
+    let mut by_ticket: BTreeMap<String, FocusTotals> = BTreeMap::new();
+    for session in sessions {
+        let Some(start) = parse_timestamp(&session.started_at) else {
+            continue;
+        };
+        if from.is_some_and(|from| start < from) || to.is_some_and(|to| start >= to) {
+            continue;
+        }
+        let end = session
+            .ended_at
+            .as_deref()
+            .and_then(parse_timestamp)
+            .unwrap_or(now);
+        let seconds = (end - start).num_seconds().max(0);
+        let completed = seconds >= i64::from(session.focus_minutes.unwrap_or(0)) * 60;
+
+        let ticket = by_ticket.entry(session.ticket_key).or_default();
+        for t in [&mut totals, ticket] {
+            t.sessions += 1;
+            t.completed += u32::from(completed);
+            t.interruptions += session.interruptions;
+            t.focus_seconds += seconds;
+        }
+    }
+
+    let mut tickets: Vec<FocusTicketStats> = by_ticket
+        .into_iter()
+        .map(|(ticket_key, totals)| FocusTicketStats { ticket_key, totals })
+        .collect();
+    tickets.sort_by(|a, b| b.totals.focus_seconds.cmp(&a.totals.focus_seconds));
+
+    Ok(FocusStats { totals, tickets })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, day).expect("valid date")
+    }
+
+    #[test]
+    fn reports_tracked_time_per_day_and_week() {
+        let conn = setup_db();
+
+        let first = start_timer(&conn, "OPS-1", "2025-01-06T09:00:00Z").expect("timer");
+        // Starting again keeps the running timer
+        assert_eq!(
+            start_timer(&conn, "OPS-1", "2025-01-06T09:30:00Z").expect("timer"),
+            first
+        );
+        stop_timer(&conn, "OPS-1", "2025-01-06T10:30:00Z").expect("stop");
+        // Runs past midnight into Tuesday
+        start_timer(&conn, "OPS-2", "2025-01-06T23:00:00Z").expect("timer");
+        stop_timer(&conn, "OPS-2", "2025-01-07T01:00:00Z").expect("stop");
+        assert_eq!(
+            stop_timer(&conn, "OPS-2", "2025-01-07T02:00:00Z").expect("stop"),
+            None
+        );
+        // Still running on the next Monday
+        start_timer(&conn, "OPS-1", "2025-01-13T09:00:00Z").expect("timer");
+        assert_eq!(get_running_timers(&conn).expect("timers").len(), 1);
+
+        let now = date(13).and_hms_opt(9, 15, 0).expect("valid datetime");
+        let rows: Vec<(String, String, i64)> =
+            get_time_report(&conn, TimeReportPeriod::Day, date(6), date(14), now)
+                .expect("report")
+                .into_iter()
+                .map(|r| (r.period_start, r.ticket_key, r.seconds))
+                .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("2025-01-06".to_string(), "OPS-1".to_string(), 5400),
+                ("2025-01-06".to_string(), "OPS-2".to_string(), 3600),
+                ("2025-01-07".to_string(), "OPS-2".to_string(), 3600),
+                ("2025-01-13".to_string(), "OPS-1".to_string(), 900),
+            ]
+        );
+
+        let weeks: Vec<(String, String, i64)> =
+            get_time_report(&conn, TimeReportPeriod::Week, date(6), date(13), now)
+                .expect("report")
+                .into_iter()
+                .map(|r| (r.period_start, r.ticket_key, r.seconds))
+                .collect();
+        assert_eq!(
+            weeks,
+            vec![
+                ("2025-01-06".to_string(), "OPS-1".to_string(), 5400),
+                ("2025-01-06".to_string(), "OPS-2".to_string(), 7200),
+            ]
+        );
+
+        set_worklog_id(&conn, first.id, "10042").expect("worklog");
+        assert_eq!(
+            get_time_entry(&conn, first.id)
+                .expect("entry")
+                .and_then(|e| e.worklog_id),
+            Some("10042".to_string())
+        );
+    }
+
+    #[test]
+    fn focus_stats_count_completed_sessions_and_interruptions() {
+        let conn = setup_db();
+
+        start_focus_session(&conn, "OPS-1", 25, "2025-01-06T09:00:00Z").expect("session");
+        assert!(start_focus_session(&conn, "OPS-1", 25, "2025-01-06T09:05:00Z").is_err());
+        record_interruption(&conn, "OPS-1").expect("interruption");
+        stop_timer(&conn, "OPS-1", "2025-01-06T09:25:00Z").expect("stop");
+        // Cut short after two interruptions
+        start_focus_session(&conn, "OPS-2", 25, "2025-01-06T10:00:00Z").expect("session");
+        record_interruption(&conn, "OPS-2").expect("interruption");
+        record_interruption(&conn, "OPS-2").expect("interruption");
+        stop_timer(&conn, "OPS-2", "2025-01-06T10:10:00Z").expect("stop");
+        // Plain timers are not focus sessions
+        start_timer(&conn, "OPS-3", "2025-01-06T11:00:00Z").expect("timer");
+        assert_eq!(
+            record_interruption(&conn, "OPS-3").expect("interruption"),
+            None
+        );
+        stop_timer(&conn, "OPS-3", "2025-01-06T12:00:00Z").expect("stop");
+        // Outside the range
+        start_focus_session(&conn, "OPS-1", 25, "2025-01-07T09:00:00Z").expect("session");
+
+        let now = date(7).and_hms_opt(9, 10, 0).expect("valid datetime");
+        let range = DateRange {
+            from: Some("2025-01-06".to_string()),
+            to: Some("2025-01-07".to_string()),
+        };
+        let stats = get_focus_stats(&conn, &range, now).expect("stats");
+        assert_eq!(
+            stats.totals,
+            FocusTotals {
+                sessions: 2,
+                completed: 1,
+                interruptions: 3,
+                focus_seconds: 35 * 60,
+            }
+        );
+        let tickets: Vec<(&str, u32, i64)> = stats
+            .tickets
+            .iter()
+            .map(|t| {
+                (
+                    t.ticket_key.as_str(),
+                    t.totals.completed,
+                    t.totals.focus_seconds,
+                )
+            })
+            .collect();
+        assert_eq!(tickets, vec![("OPS-1", 1, 1500), ("OPS-2", 0, 600)]);
+
+        // Without bounds the running session counts its elapsed time
+        let stats = get_focus_stats(&conn, &DateRange::default(), now).expect("stats");
+        assert_eq!((stats.totals.sessions, stats.totals.completed), (3, 1));
+        assert_eq!(stats.totals.focus_seconds, 45 * 60);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::errors::{AppError, DbError};
+use crate::models::User;
+use rusqlite::{params, Connection};
+
+/// Stores `users` from a directory sync, updating the display name, avatar
+/// and active flag of users already known. Users Jira no longer lists are
+/// kept, so older tickets still resolve to them. Returns how many were stored.
+pub fn upsert_users(conn: &Connection, users: &[User], synced_at: &str) -> Result<usize, AppError> {
+    let tx = conn.unchecked_transaction().map_err(DbError::from)?;
+    {
+        let mut stmt = tx
+            .prepare(
+                "INSERT INTO users (account_id, display_name, avatar_url, active, synced_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(account_id) DO UPDATE SET
+                     display_name = excluded.display_name,
+                     avatar_url = excluded.avatar_url,
+                     active = excluded.active,
+                     synced_at = excluded.synced_at",
+            )
+            .map_err(DbError::from)?;
+        for user in users {
+            stmt.execute(params![
+                user.account_id,
+                user.display_name,
+                user.avatar_url,
+                user.active,
+                synced_at
+            ])
+            .map_err(DbError::from)?;
+        }
+    }
+    tx.commit().map_err(DbError::from)?;
+    Ok(users.len())
+}
+
+/// Directory users by display name, inactive ones only when asked for.
+pub fn list_users(conn: &Connection, include_inactive: bool) -> Result<Vec<User>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT account_id, display_name, avatar_url, active FROM users
+             WHERE active = 1 OR ?1
+             ORDER BY display_name COLLATE NOCASE, account_id",
+        )
+        .map_err(DbError::from)?;
+    let users = stmt
+        .query_map(params![include_inactive], |row| {
+            Ok(User {
+                account_id: row.get(0)?,
+                display_name: row.get(1)?,
+                avatar_url: row.get(2)?,
+                active: row.get(3)?,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(users)
+}
+
+/// Keys of the projects tickets have been synced from Jira for.
+pub fn synced_jira_project_keys(conn: &Connection) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT DISTINCT project_key FROM tickets
+             WHERE source = 'jira' AND project_key != ''
+             ORDER BY project_key",
+        )
+        .map_err(DbError::from)?;
+    let keys = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+
+    fn user(account_id: &str, display_name: &str, active: bool) -> User {
+        User {
+            account_id: account_id.to_string(),
+            display_name: display_name.to_string(),
+            avatar_url: None,
+            active,
+        }
+    }
+
+    #[test]
+    fn resync_renames_users_without_changing_their_identity() {
+        let conn = setup_db();
+
+        upsert_users(
+            &conn,
+            &[user("acc-1", "Dana", true), user("acc-2", "Sam", false)],
+            "2025-01-06T09:00:00Z",
+        )
+        .expect("users");
+        upsert_users(
+            &conn,
+            &[user("acc-1", "Dana Scully", true)],
+            "2025-01-07T09:00:00Z",
+        )
+        .expect("users");
+
+        assert_eq!(
+            list_users(&conn, false).expect("users"),
+            vec![user("acc-1", "Dana Scully", true)]
+        );
+        assert_eq!(list_users(&conn, true).expect("users").len(), 2);
+    }
+}
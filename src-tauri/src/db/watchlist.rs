@@ -0,0 +1,217 @@
+use super::queries::{map_ticket_row, TICKET_COLUMNS};
+use crate::errors::{AppError, DbError};
+use crate::models::{FieldChange, Ticket, WatchlistChange};
+use rusqlite::{params, Connection};
+
+/// Columns compared between the live ticket and the acknowledged snapshot.
+const WATCHED_FIELDS: [(&str, &str); 3] = [
+    ("status", "seen_status"),
+    ("assignee", "seen_assignee"),
+    ("priority", "seen_priority"),
+];
+
+/// Pins a ticket, taking its current values as the baseline.
+pub fn watch_ticket(conn: &Connection, jira_key: &str) -> Result<(), AppError> {
+    let inserted = conn
+        .execute(
+            "INSERT INTO watched_tickets (ticket_key, pinned_at, seen_status, seen_assignee, seen_priority)
+             SELECT jira_key, ?2, status, assignee, priority FROM tickets WHERE jira_key = ?1
+             ON CONFLICT(ticket_key) DO NOTHING",
+            params![jira_key, chrono::Utc::now().to_rfc3339()],
+        )
+        .map_err(DbError::from)?;
+
+    if inserted == 0 && !is_watched(conn, jira_key)? {
+        return Err(AppError::Config(format!("Unknown ticket: {}", jira_key)));
+    }
+    Ok(())
+}
+
+pub fn unwatch_ticket(conn: &Connection, jira_key: &str) -> Result<bool, AppError> {
+    let deleted = conn
+        .execute(
+            "DELETE FROM watched_tickets WHERE ticket_key = ?1",
+            params![jira_key],
+        )
+        .map_err(DbError::from)?;
+    Ok(deleted > 0)
+}
+
+fn is_watched(conn: &Connection, jira_key: &str) -> Result<bool, AppError> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM watched_tickets WHERE ticket_key = ?1)",
+        params![jira_key],
+        |row| row.get(0),
+    )
+    .map_err(|e| DbError::from(e).into())
+}
+
+pub fn list_watched_tickets(conn: &Connection) -> Result<Vec<Ticket>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM tickets WHERE jira_key IN (SELECT ticket_key FROM watched_tickets) \
+             ORDER BY updated_at DESC",
+            TICKET_COLUMNS
+        ))
+        .map_err(DbError::from)?;
+
+    let tickets = stmt
+        .query_map([], map_ticket_row)
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(tickets)
+}
+
+fn changed_predicate() -> String {
+    WATCHED_FIELDS
+        .iter()
+        .map(|(live, seen)| format!("t.{} IS NOT w.{}", live, seen))
+        .collect::<Vec<_>>()
+        .join(" OR ")
+}
+
+/// Stamps `changed_at` on watched tickets that differ from their baseline.
+/// Called after every sync so the UI can tell when a change was first seen.
+pub fn mark_watchlist_changes(conn: &Connection) -> Result<usize, AppError> {
+    let marked = conn
+        .execute(
+            &format!(
+                "UPDATE watched_tickets SET changed_at = ?1
+                 WHERE changed_at IS NULL AND ticket_key IN (
+                     SELECT w.ticket_key FROM watched_tickets w
+                     JOIN tickets t ON t.jira_key = w.ticket_key
+                     WHERE {}
+                 )",
+                changed_predicate()
+            ),
+            params![chrono::Utc::now().to_rfc3339()],
+        )
+        .map_err(DbError::from)?;
+    Ok(marked)
+}
+
+pub fn get_watchlist_changes(conn: &Connection) -> Result<Vec<WatchlistChange>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT t.jira_key, t.summary, w.changed_at,
+                    t.status, w.seen_status, t.assignee, w.seen_assignee, t.priority, w.seen_priority
+             FROM watched_tickets w
+             JOIN tickets t ON t.jira_key = w.ticket_key
+             WHERE {}
+             ORDER BY w.changed_at DESC",
+            changed_predicate()
+        ))
+        .map_err(DbError::from)?;
+
+    let changes = stmt
+        .query_map([], |row| {
+            let mut changes = Vec::new();
+            for (idx, (field, _)) in WATCHED_FIELDS.iter().enumerate() {
+                let to: Option<String> = row.get(3 + idx * 2)?;
+                let from: Option<String> = row.get(4 + idx * 2)?;
+                if to != from {
+                    changes.push(FieldChange {
+                        field: field.to_string(),
+                        from,
+                        to,
+                    });
+                }
+            }
+
+            Ok(WatchlistChange {
+                jira_key: row.get(0)?,
+                summary: row.get(1)?,
+                changed_at: row.get(2)?,
+                changes,
+            })
+        })
+        .map_err(DbError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DbError::from)?;
+
+    Ok(changes)
+}
+
+/// Takes the ticket's current values as the new baseline, clearing its highlight.
+pub fn acknowledge_watchlist_change(conn: &Connection, jira_key: &str) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE watched_tickets SET
+             seen_status = (SELECT status FROM tickets WHERE jira_key = ?1),
+             seen_assignee = (SELECT assignee FROM tickets WHERE jira_key = ?1),
+             seen_priority = (SELECT priority FROM tickets WHERE jira_key = ?1),
+             changed_at = NULL
+         WHERE ticket_key = ?1",
+        params![jira_key],
+    )
+    .map_err(DbError::from)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support;
+    use crate::db::upsert_ticket;
+
+    fn ticket(key: &str, status: &str, assignee: Option<&str>) -> Ticket {
+        Ticket {
+            status: status.to_string(),
+            assignee: assignee.map(|a| a.to_string()),
+            ..test_support::ticket(key)
+        }
+    }
+
+    fn seeded_db() -> Connection {
+        let conn = test_support::setup_db();
+        upsert_ticket(&conn, &ticket("OPS-1", "Open", None)).expect("OPS-1");
+        upsert_ticket(&conn, &ticket("OPS-2", "Open", Some("Dana"))).expect("OPS-2");
+        conn
+    }
+
+    #[test]
+    fn changes_are_reported_until_acknowledged() {
+        let conn = seeded_db();
+        watch_ticket(&conn, "OPS-1").expect("watch");
+        watch_ticket(&conn, "OPS-2").expect("watch");
+        assert!(get_watchlist_changes(&conn).expect("changes").is_empty());
+
+        upsert_ticket(&conn, &ticket("OPS-1", "In Progress", Some("Dana"))).expect("update");
+        assert_eq!(mark_watchlist_changes(&conn).expect("mark"), 1);
+
+        let changes = get_watchlist_changes(&conn).expect("changes");
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].jira_key, "OPS-1");
+        assert!(changes[0].changed_at.is_some());
+        assert_eq!(
+            changes[0].changes,
+            vec![
+                FieldChange {
+                    field: "status".to_string(),
+                    from: Some("Open".to_string()),
+                    to: Some("In Progress".to_string()),
+                },
+                FieldChange {
+                    field: "assignee".to_string(),
+                    from: None,
+                    to: Some("Dana".to_string()),
+                },
+            ]
+        );
+
+        acknowledge_watchlist_change(&conn, "OPS-1").expect("ack");
+        assert!(get_watchlist_changes(&conn).expect("changes").is_empty());
+    }
+
+    #[test]
+    fn watching_unknown_ticket_fails() {
+        let conn = seeded_db();
+        assert!(watch_ticket(&conn, "OPS-404").is_err());
+
+        watch_ticket(&conn, "OPS-1").expect("watch");
+        watch_ticket(&conn, "OPS-1").expect("watching twice is a no-op");
+        assert_eq!(list_watched_tickets(&conn).expect("list").len(), 1);
+        assert!(unwatch_ticket(&conn, "OPS-1").expect("unwatch"));
+    }
+}
@@ -8,6 +8,12 @@ pub enum AppError {
     #[error("Jira API error: {0}")]
     JiraApi(#[from] JiraError),
 
+    #[error("Connector error: {0}")]
+    Connector(#[from] ConnectorError),
+
+    #[error("Email error: {0}")]
+    Email(String),
+
     #[error("Credential storage error: {0}")]
     Keyring(String),
 
@@ -60,6 +66,66 @@ pub enum JiraError {
     #[error("Failed to parse Jira response: {0}")]
     ParseError(String),
 
+    #[error("Jira rejected the request: {}", .0.summary())]
+    Validation(JiraValidationErrors),
+
+    #[error(
+        "Sync exceeded its {limit_secs}s deadline. Narrow the JQL or raise the limit in Settings."
+    )]
+    DeadlineExceeded { limit_secs: u64 },
+
     #[error("Not configured. Set Jira URL, email, and API token in Settings.")]
     NotConfigured,
 }
+
+/// Jira's `{"errorMessages": [...], "errors": {"field": "message"}}` body.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct JiraValidationErrors {
+    #[serde(rename = "errorMessages", default)]
+    pub messages: Vec<String>,
+    #[serde(rename = "errors", default)]
+    pub field_errors: std::collections::BTreeMap<String, String>,
+}
+
+impl JiraValidationErrors {
+    pub fn summary(&self) -> String {
+        self.messages
+            .iter()
+            .cloned()
+            .chain(
+                self.field_errors
+                    .iter()
+                    .map(|(field, message)| format!("{}: {}", field, message)),
+            )
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ConnectorError {
+    #[error("{0}: HTTP request failed: {1}")]
+    Http(&'static str, reqwest::Error),
+
+    #[error("{0}: authentication failed (401). Check the configured credentials.")]
+    Unauthorized(&'static str),
+
+    #[error("{source_id}: rate limited (429). Retry after {retry_after_secs}s.")]
+    RateLimited {
+        source_id: &'static str,
+        retry_after_secs: u64,
+    },
+
+    #[error("{source_id} returned {status}: {body}")]
+    ApiError {
+        source_id: &'static str,
+        status: u16,
+        body: String,
+    },
+
+    #[error("{0}: failed to parse response: {1}")]
+    ParseError(&'static str, String),
+
+    #[error("{0}: not configured. Set the URL and token in Settings.")]
+    NotConfigured(&'static str),
+}
@@ -0,0 +1,370 @@
+use serde_json::Value;
+
+/// Renders a Jira rich-text field as Markdown. Cloud (`/rest/api/3`) returns
+/// Atlassian Document Format; Server/Data Center returns plain strings,
+/// which are passed through unchanged. Unknown ADF nodes fall back to their
+/// children's text so nothing is silently dropped.
+pub fn to_markdown(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Object(_) => blocks(children(value)).join("\n\n"),
+        _ => String::new(),
+    }
+}
+
+fn children(node: &Value) -> &[Value] {
+    node.get("content")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn attr<'a>(node: &'a Value, name: &str) -> Option<&'a Value> {
+    node.get("attrs")?.get(name)
+}
+
+fn attr_str<'a>(node: &'a Value, name: &str) -> Option<&'a str> {
+    attr(node, name)?.as_str().filter(|s| !s.is_empty())
+}
+
+fn blocks(nodes: &[Value]) -> Vec<String> {
+    nodes
+        .iter()
+        .map(block)
+        .filter(|rendered| !rendered.trim().is_empty())
+        .collect()
+}
+
+fn block(node: &Value) -> String {
+    match node.get("type").and_then(Value::as_str).unwrap_or_default() {
+        "paragraph" => inline(children(node)),
+        "heading" => {
+            let level = attr(node, "level")
+                .and_then(Value::as_u64)
+                .unwrap_or(1)
+                .clamp(1, 6) as usize;
+            format!("{} {}", "#".repeat(level), inline(children(node)))
+        }
+        "bulletList" => list(node, None),
+        "orderedList" => {
+            let start = attr(node, "order").and_then(Value::as_u64).unwrap_or(1);
+            list(node, Some(start))
+        }
+        "codeBlock" => {
+            let language = attr_str(node, "language").unwrap_or_default();
+            format!("```{}\n{}\n```", language, plain_text(children(node)))
+        }
+        "blockquote" | "panel" => quote(&blocks(children(node)).join("\n\n")),
+        "rule" => "---".to_string(),
+        "table" => table(node),
+        // Embedded files have no useful text; attachments are listed separately
+        "mediaSingle" | "mediaGroup" | "media" => String::new(),
+        _ if node.get("text").is_some() => inline(std::slice::from_ref(node)),
+        _ => {
+            let nested = children(node);
+            if nested.iter().any(is_inline) {
+                inline(nested)
+            } else {
+                blocks(nested).join("\n\n")
+            }
+        }
+    }
+}
+
+fn is_inline(node: &Value) -> bool {
+    matches!(
+        node.get("type").and_then(Value::as_str),
+        Some("text" | "hardBreak" | "mention" | "emoji" | "inlineCard" | "date" | "status")
+    )
+}
+
+/// One line per item; continuation lines and nested lists are indented under
+/// the item's marker.
+fn list(node: &Value, ordered_start: Option<u64>) -> String {
+    children(node)
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let marker = match ordered_start {
+                Some(start) => format!("{}. ", start + idx as u64),
+                None => "- ".to_string(),
+            };
+            let body = blocks(children(item)).join("\n");
+            let indent = " ".repeat(marker.len());
+            body.lines()
+                .enumerate()
+                .map(|(line_idx, line)| match line_idx {
+                    0 => format!("{}{}", marker, line),
+                    _ if line.is_empty() => String::new(),
+                    _ => format!("{}{}", indent, line),
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn quote(text: &str) -> String {
+    text.lines()
+        .map(|line| match line {
+            "" => ">".to_string(),
+            _ => format!("> {}", line),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Pipe table with the first row as header. Cell content is flattened to a
+/// single line since Markdown tables can't hold blocks.
+fn table(node: &Value) -> String {
+    let rows: Vec<Vec<String>> = children(node)
+        .iter()
+        .map(|row| {
+            children(row)
+                .iter()
+                .map(|cell| {
+                    blocks(children(cell))
+                        .join(" ")
+                        .replace('\n', " ")
+                        .replace('|', "\\|")
+                })
+                .collect()
+        })
+        .collect();
+    let Some(columns) = rows.iter().map(Vec::len).max().filter(|&n| n > 0) else {
+        return String::new();
+    };
+
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+    for (idx, row) in rows.iter().enumerate() {
+        let mut cells = row.clone();
+        cells.resize(columns, String::new());
+        lines.push(format!("| {} |", cells.join(" | ")));
+        if idx == 0 {
+            lines.push(format!("|{}", " --- |".repeat(columns)));
+        }
+    }
+    lines.join("\n")
+}
+
+fn inline(nodes: &[Value]) -> String {
+    nodes.iter().map(inline_node).collect()
+}
+
+fn inline_node(node: &Value) -> String {
+    match node.get("type").and_then(Value::as_str).unwrap_or_default() {
+        "text" => marked_text(node),
+        "hardBreak" => "  \n".to_string(),
+        "mention" => {
+            let name = attr_str(node, "text")
+                .or_else(|| attr_str(node, "id"))
+                .unwrap_or("someone");
+            if name.starts_with('@') {
+                name.to_string()
+            } else {
+                format!("@{}", name)
+            }
+        }
+        "emoji" => attr_str(node, "text")
+            .or_else(|| attr_str(node, "shortName"))
+            .unwrap_or_default()
+            .to_string(),
+        "inlineCard" => attr_str(node, "url")
+            .map(|url| format!("<{}>", url))
+            .unwrap_or_default(),
+        "date" => attr(node, "timestamp")
+            .and_then(|ts| match ts {
+                Value::String(s) => s.parse::<i64>().ok(),
+                other => other.as_i64(),
+            })
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        "status" => attr_str(node, "text").unwrap_or_default().to_string(),
+        _ => inline(children(node)),
+    }
+}
+
+/// Applies text marks inside-out: code, then emphasis, then the link.
+fn marked_text(node: &Value) -> String {
+    let mut text = node
+        .get("text")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let marks = node
+        .get("marks")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let has = |mark: &str| {
+        marks
+            .iter()
+            .any(|m| m.get("type").and_then(Value::as_str) == Some(mark))
+    };
+
+    if text.is_empty() {
+        return text;
+    }
+    if has("code") {
+        text = format!("`{}`", text);
+    } else {
+        if has("strike") {
+            text = format!("~~{}~~", text);
+        }
+        if has("em") {
+            text = format!("_{}_", text);
+        }
+        if has("strong") {
+            text = format!("**{}**", text);
+        }
+    }
+    if let Some(href) = marks
+        .iter()
+        .find(|m| m.get("type").and_then(Value::as_str) == Some("link"))
+        .and_then(|m| attr_str(m, "href"))
+    {
+        text = format!("[{}]({})", text, href);
+    }
+    text
+}
+
+/// Raw text of a code block's children, marks ignored.
+fn plain_text(nodes: &[Value]) -> String {
+    nodes
+        .iter()
+        .map(|node| match node.get("text").and_then(Value::as_str) {
+            Some(text) => text.to_string(),
+            None => plain_text(children(node)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn doc(content: Value) -> Value {
+        json!({ "type": "doc", "version": 1, "content": content })
+    }
+
+    fn text(text: &str) -> Value {
+        json!({ "type": "text", "text": text })
+    }
+
+    fn paragraph(content: Value) -> Value {
+        json!({ "type": "paragraph", "content": content })
+    }
+
+    #[test]
+    fn renders_headings_and_marked_text() {
+        let value = doc(json!([
+            { "type": "heading", "attrs": { "level": 2 }, "content": [text("Impact")] },
+            paragraph(json!([
+                text("Login is "),
+                { "type": "text", "text": "down", "marks": [{ "type": "strong" }] },
+                text(" for "),
+                { "type": "text", "text": "sso.example.com", "marks": [{ "type": "code" }] },
+                text(", see "),
+                {
+                    "type": "text",
+                    "text": "runbook",
+                    "marks": [
+                        { "type": "em" },
+                        { "type": "link", "attrs": { "href": "https://wiki.example.com/rb" } }
+                    ]
+                }
+            ])),
+            { "type": "rule" }
+        ]));
+
+        assert_eq!(
+            to_markdown(&value),
+            "## Impact\n\nLogin is **down** for `sso.example.com`, see \
+             [_runbook_](https://wiki.example.com/rb)\n\n---"
+        );
+    }
+
+    #[test]
+    fn renders_nested_and_ordered_lists() {
+        let item = |content: Value| json!({ "type": "listItem", "content": content });
+        let value = doc(json!([
+            {
+                "type": "orderedList",
+                "attrs": { "order": 3 },
+                "content": [
+                    item(json!([paragraph(json!([text("Restart the pod")]))])),
+                    item(json!([
+                        paragraph(json!([text("Check logs")])),
+                        {
+                            "type": "bulletList",
+                            "content": [item(json!([paragraph(json!([text("api")]))]))]
+                        }
+                    ]))
+                ]
+            }
+        ]));
+
+        assert_eq!(
+            to_markdown(&value),
+            "3. Restart the pod\n4. Check logs\n   - api"
+        );
+    }
+
+    #[test]
+    fn renders_code_blocks_mentions_and_quotes() {
+        let value = doc(json!([
+            {
+                "type": "codeBlock",
+                "attrs": { "language": "bash" },
+                "content": [text("kubectl get pods\nkubectl logs api")]
+            },
+            paragraph(json!([
+                { "type": "mention", "attrs": { "id": "557058:abc", "text": "@Dana" } },
+                text(" please check"),
+                { "type": "hardBreak" },
+                { "type": "mention", "attrs": { "id": "5b10a2844c20165700ede21g" } }
+            ])),
+            {
+                "type": "panel",
+                "attrs": { "panelType": "warning" },
+                "content": [paragraph(json!([text("Customer facing")]))]
+            }
+        ]));
+
+        assert_eq!(
+            to_markdown(&value),
+            "```bash\nkubectl get pods\nkubectl logs api\n```\n\n\
+             @Dana please check  \n@5b10a2844c20165700ede21g\n\n\
+             > Customer facing"
+        );
+    }
+
+    #[test]
+    fn renders_tables_with_a_header_row() {
+        let cell =
+            |s: &str| json!({ "type": "tableCell", "content": [paragraph(json!([text(s)]))] });
+        let value = doc(json!([
+            {
+                "type": "table",
+                "content": [
+                    { "type": "tableRow", "content": [cell("Host"), cell("State")] },
+                    { "type": "tableRow", "content": [cell("db-1"), cell("up | degraded")] }
+                ]
+            }
+        ]));
+
+        assert_eq!(
+            to_markdown(&value),
+            "| Host | State |\n| --- | --- |\n| db-1 | up \\| degraded |"
+        );
+    }
+
+    #[test]
+    fn passes_server_plain_text_through() {
+        assert_eq!(to_markdown(&json!("h1. Wiki markup")), "h1. Wiki markup");
+        assert_eq!(to_markdown(&Value::Null), "");
+    }
+}
@@ -1,54 +1,235 @@
-use crate::errors::{AppError, JiraError};
-use crate::jira::types::JiraSearchResponse;
-use crate::models::Ticket;
+use crate::connectors::{SourceBatch, TicketSource};
+use crate::db::{account_cursor_key, account_ticket_key, source_metadata_key};
+use crate::errors::{AppError, JiraError, JiraValidationErrors};
+use crate::jira::adf;
+use crate::jira::fields::JiraFieldMapping;
+use crate::jira::limits::{JiraLimits, RateLimiter};
+use crate::jira::types::{
+    JiraCreatedIssue, JiraIssue, JiraIssueContent, JiraPagedSearchResponse, JiraSearchIssue,
+    JiraSearchResponse, JiraServerInfo, JiraTransitionsResponse, JiraUser, JiraWorklog,
+};
+use crate::models::{
+    Attachment, NewIssue, Sprint, StatusCategory, SyncItemFailure, Ticket, TicketComment,
+    TicketContent, TicketLink, TicketTransition, User,
+};
+use async_trait::async_trait;
 use base64::Engine;
-use chrono::DateTime;
+use chrono::{DateTime, NaiveDateTime};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::sync::OnceCell;
+
+pub const SOURCE_ID: &str = "jira";
+
+const PAGE_SIZE: u64 = 100;
+
+const SEARCH_FIELDS: [&str; 18] = [
+    "summary",
+    "status",
+    "priority",
+    "issuetype",
+    "assignee",
+    "reporter",
+    "created",
+    "updated",
+    "resolutiondate",
+    "duedate",
+    "labels",
+    "components",
+    "fixVersions",
+    "versions",
+    "environment",
+    "project",
+    "issuelinks",
+    "attachment",
+];
+
+/// Avatars and icons are small; anything bigger is refused.
+const MAX_IMAGE_BYTES: usize = 1024 * 1024;
+
+/// Result of `JiraClient::fetch_image`.
+pub enum ImageFetch {
+    /// The cached copy's ETag still matches.
+    NotModified,
+    Fetched {
+        bytes: Vec<u8>,
+        etag: Option<String>,
+        content_type: String,
+    },
+}
+
+/// Which REST API generation the site speaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JiraApiMode {
+    /// Jira Cloud: `/rest/api/3` with `nextPageToken` pagination.
+    Cloud,
+    /// Jira Server / Data Center: `/rest/api/2` with `startAt` pagination.
+    Server,
+}
+
+impl JiraApiMode {
+    fn from_deployment_type(deployment_type: Option<&str>) -> Self {
+        match deployment_type {
+            Some(t) if t.eq_ignore_ascii_case("cloud") => JiraApiMode::Cloud,
+            _ => JiraApiMode::Server,
+        }
+    }
+
+    fn api_path(self) -> &'static str {
+        match self {
+            JiraApiMode::Cloud => "rest/api/3",
+            JiraApiMode::Server => "rest/api/2",
+        }
+    }
+}
+
+/// How requests to Jira are authenticated.
+pub enum JiraAuth {
+    /// Atlassian account email plus API token (Jira Cloud).
+    Basic { email: String, token: String },
+    /// Personal Access Token (Jira Server / Data Center).
+    Bearer { token: String },
+}
+
+/// Persisted choice of auth strategy; the secret itself lives in the keychain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JiraAuthMethod {
+    #[default]
+    Basic,
+    Pat,
+}
+
+impl JiraAuthMethod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JiraAuthMethod::Basic => "basic",
+            JiraAuthMethod::Pat => "pat",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "basic" => Some(JiraAuthMethod::Basic),
+            "pat" => Some(JiraAuthMethod::Pat),
+            _ => None,
+        }
+    }
+}
+
+impl JiraAuth {
+    pub fn from_method(method: JiraAuthMethod, email: &str, token: &str) -> Self {
+        match method {
+            JiraAuthMethod::Basic => JiraAuth::Basic {
+                email: email.to_string(),
+                token: token.to_string(),
+            },
+            JiraAuthMethod::Pat => JiraAuth::Bearer {
+                token: token.to_string(),
+            },
+        }
+    }
+
+    fn header_value(&self) -> String {
+        match self {
+            JiraAuth::Basic { email, token } => {
+                let credentials = format!("{}:{}", email, token);
+                let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+                format!("Basic {}", encoded)
+            }
+            JiraAuth::Bearer { token } => format!("Bearer {}", token),
+        }
+    }
+}
 
 pub struct JiraClient {
-    base_url: String,
+    site_url: String,
     auth_header: String,
+    account_id: Option<i64>,
+    api_mode: OnceCell<JiraApiMode>,
+    limits: JiraLimits,
+    rate_limiter: RateLimiter,
+    field_mapping: JiraFieldMapping,
     client: reqwest::Client,
 }
 
 impl JiraClient {
-    pub fn new(jira_url: &str, email: &str, token: &str) -> Result<Self, AppError> {
-        let base_url = format!("{}/rest/api/3", jira_url.trim_end_matches('/'));
-        let auth_header = Self::create_auth_header(email, token);
-        let client = reqwest::Client::new();
+    pub fn new(jira_url: &str, auth: JiraAuth, client: reqwest::Client) -> Result<Self, AppError> {
+        let site_url = jira_url.trim_end_matches('/').to_string();
+        let auth_header = auth.header_value();
 
         Ok(JiraClient {
-            base_url,
+            site_url,
             auth_header,
+            account_id: None,
+            api_mode: OnceCell::new(),
+            limits: JiraLimits::default(),
+            rate_limiter: RateLimiter::per_second(JiraLimits::default().max_requests_per_sec),
+            field_mapping: JiraFieldMapping::default(),
             client,
         })
     }
 
-    fn create_auth_header(email: &str, token: &str) -> String {
-        let credentials = format!("{}:{}", email, token);
-        let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
-        format!("Basic {}", encoded)
+    pub fn with_limits(mut self, limits: JiraLimits) -> Self {
+        self.rate_limiter = RateLimiter::per_second(limits.max_requests_per_sec);
+        self.limits = limits;
+        self
     }
 
-    pub async fn fetch_tickets(&self, last_sync_ts: Option<&str>) -> Result<Vec<Ticket>, AppError> {
-        let mut all_tickets = Vec::new();
-        let mut next_page_token: Option<String> = None;
-        let jql = Self::build_jql(last_sync_ts);
+    pub fn with_field_mapping(mut self, field_mapping: JiraFieldMapping) -> Self {
+        self.field_mapping = field_mapping;
+        self
+    }
 
-        loop {
-            let response = self.search_jql(&jql, next_page_token.as_deref()).await?;
+    pub fn field_mapping(&self) -> &JiraFieldMapping {
+        &self.field_mapping
+    }
 
-            for issue in response.issues {
-                let ticket = Self::convert_issue_to_ticket(issue);
-                all_tickets.push(ticket);
-            }
+    /// Sends `request` once the rate budget allows, with auth and the
+    /// per-request timeout applied.
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, AppError> {
+        self.rate_limiter.acquire().await;
 
-            if response.next_page_token.is_none() {
-                break;
-            }
-            next_page_token = response.next_page_token;
-        }
+        let response = request
+            .header("Authorization", &self.auth_header)
+            .timeout(self.limits.request_timeout())
+            .send()
+            .await
+            .map_err(JiraError::from)?;
+        Ok(response)
+    }
+
+    /// Detects Cloud vs Server/Data Center from `serverInfo` on first use.
+    /// `/rest/api/2/serverInfo` exists on both, so it is safe to probe before
+    /// the mode is known.
+    async fn api_mode(&self) -> Result<JiraApiMode, AppError> {
+        self.api_mode
+            .get_or_try_init(|| async {
+                let response = self
+                    .send(
+                        self.client
+                            .get(format!("{}/rest/api/2/serverInfo", self.site_url)),
+                    )
+                    .await?;
 
-        Ok(all_tickets)
+                let info: JiraServerInfo = Self::parse_response(response).await?;
+                let mode = JiraApiMode::from_deployment_type(info.deployment_type.as_deref());
+                log::info!("Detected Jira API mode: {:?}", mode);
+                Ok::<_, AppError>(mode)
+            })
+            .await
+            .copied()
+    }
+
+    async fn api_url(&self, path: &str) -> Result<String, AppError> {
+        let mode = self.api_mode().await?;
+        Ok(format!("{}/{}/{}", self.site_url, mode.api_path(), path))
+    }
+
+    /// Tags synced tickets with `account_id` and gives the account its own cursor.
+    pub fn with_account(mut self, account_id: i64) -> Self {
+        self.account_id = Some(account_id);
+        self
     }
 
     fn build_jql(last_sync_ts: Option<&str>) -> String {
@@ -80,23 +261,8 @@ impl JiraClient {
             "jql".to_string(),
             serde_json::Value::String(jql.to_string()),
         );
-        body.insert("maxResults".to_string(), serde_json::Value::from(100_u64));
-        body.insert(
-            "fields".to_string(),
-            serde_json::Value::Array(vec![
-                serde_json::Value::String("summary".to_string()),
-                serde_json::Value::String("status".to_string()),
-                serde_json::Value::String("priority".to_string()),
-                serde_json::Value::String("issuetype".to_string()),
-                serde_json::Value::String("assignee".to_string()),
-                serde_json::Value::String("reporter".to_string()),
-                serde_json::Value::String("created".to_string()),
-                serde_json::Value::String("updated".to_string()),
-                serde_json::Value::String("resolutiondate".to_string()),
-                serde_json::Value::String("labels".to_string()),
-                serde_json::Value::String("project".to_string()),
-            ]),
-        );
+        body.insert("maxResults".to_string(), serde_json::Value::from(PAGE_SIZE));
+        body.insert("fields".to_string(), self.search_fields());
 
         if let Some(token) = next_page_token {
             body.insert(
@@ -105,25 +271,419 @@ impl JiraClient {
             );
         }
 
-        let url = format!("{}/search/jql", self.base_url);
+        let url = self.api_url("search/jql").await?;
         let response = self
-            .client
-            .post(&url)
-            .header("Authorization", &self.auth_header)
-            .header("Content-Type", "application/json")
-            .json(&serde_json::Value::Object(body))
-            .send()
+            .send(
+                self.client
+                    .post(&url)
+                    .header("Content-Type", "application/json")
+                    .json(&serde_json::Value::Object(body)),
+            )
+            .await?;
+
+        Self::parse_response(response).await
+    }
+
+    /// Server/Data Center search, paged by `startAt` offset.
+    async fn search_paged(
+        &self,
+        jql: &str,
+        start_at: u64,
+    ) -> Result<JiraPagedSearchResponse, AppError> {
+        let body = serde_json::json!({
+            "jql": jql,
+            "startAt": start_at,
+            "maxResults": PAGE_SIZE,
+            "fields": self.search_fields(),
+        });
+
+        let url = self.api_url("search").await?;
+        let response = self
+            .send(
+                self.client
+                    .post(&url)
+                    .header("Content-Type", "application/json")
+                    .json(&body),
+            )
+            .await?;
+
+        Self::parse_response(response).await
+    }
+
+    fn check_deadline(&self, deadline: std::time::Instant) -> Result<(), AppError> {
+        if std::time::Instant::now() >= deadline {
+            return Err(JiraError::DeadlineExceeded {
+                limit_secs: self.limits.sync_deadline_secs,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Standard fields plus the configured custom fields.
+    fn field_list(&self) -> Vec<&str> {
+        SEARCH_FIELDS
+            .iter()
+            .copied()
+            .chain(self.field_mapping.field_ids())
+            .collect()
+    }
+
+    fn search_fields(&self) -> serde_json::Value {
+        serde_json::Value::Array(
+            self.field_list()
+                .into_iter()
+                .map(|f| serde_json::Value::String(f.to_string()))
+                .collect(),
+        )
+    }
+
+    /// Users who can be assigned issues in any of `project_keys`, across
+    /// every page, each listed once.
+    pub async fn fetch_users(&self, project_keys: &[String]) -> Result<Vec<User>, AppError> {
+        if project_keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        let url = self.api_url("user/assignable/multiProjectSearch").await?;
+        let mut users: Vec<User> = Vec::new();
+        let mut start_at = 0;
+        loop {
+            let response = self
+                .send(self.client.get(&url).query(&[
+                    ("projectKeys", project_keys.join(",")),
+                    ("startAt", start_at.to_string()),
+                    ("maxResults", PAGE_SIZE.to_string()),
+                ]))
+                .await?;
+            let page: Vec<JiraUser> = Self::parse_response(response).await?;
+            let fetched = page.len() as u64;
+            for user in page.into_iter().filter_map(Self::convert_user) {
+                if !users.iter().any(|u| u.account_id == user.account_id) {
+                    users.push(user);
+                }
+            }
+            if fetched < PAGE_SIZE {
+                return Ok(users);
+            }
+            start_at += fetched;
+        }
+    }
+
+    /// `user` as a directory entry; `None` when Jira gave no identifier.
+    fn convert_user(user: JiraUser) -> Option<User> {
+        let account_id = user.account_id.or(user.name)?;
+        let mut avatar_urls = user.avatar_urls;
+        Some(User {
+            account_id,
+            display_name: user.display_name,
+            avatar_url: avatar_urls.remove("48x48"),
+            active: user.active,
+        })
+    }
+
+    /// Moves a page's issues into `items`, setting malformed ones aside.
+    fn collect_issues(
+        page: Vec<JiraSearchIssue>,
+        items: &mut Vec<JiraIssue>,
+        failures: &mut Vec<SyncItemFailure>,
+    ) {
+        for issue in page {
+            match issue.into_issue() {
+                Ok(issue) => items.push(issue),
+                Err(failure) => {
+                    log::warn!(
+                        "Skipping malformed Jira issue {}: {}",
+                        failure.key.as_deref().unwrap_or("(no key)"),
+                        failure.error
+                    );
+                    failures.push(failure);
+                }
+            }
+        }
+    }
+
+    /// Offset of the next `startAt` page, or `None` once `total` is reached.
+    /// An empty page also stops paging in case `total` over-reports.
+    fn next_start_at(page: &JiraPagedSearchResponse) -> Option<u64> {
+        let fetched = page.issues.len() as u64;
+        let next = page.start_at + fetched;
+        (fetched > 0 && next < page.total).then_some(next)
+    }
+
+    /// Available workflow transitions for `key`, in Jira's order.
+    pub async fn get_transitions(&self, key: &str) -> Result<Vec<TicketTransition>, AppError> {
+        let url = self.api_url(&format!("issue/{}/transitions", key)).await?;
+        let response = self.send(self.client.get(&url)).await?;
+        let parsed: JiraTransitionsResponse = Self::parse_response(response).await?;
+
+        Ok(parsed
+            .transitions
+            .into_iter()
+            .map(|t| TicketTransition {
+                id: t.id,
+                name: t.name,
+                resolves: t
+                    .to
+                    .status_category
+                    .is_some_and(|category| category.key == "done"),
+                to_status: t.to.name,
+            })
+            .collect())
+    }
+
+    pub async fn transition_issue(&self, key: &str, transition_id: &str) -> Result<(), AppError> {
+        let url = self.api_url(&format!("issue/{}/transitions", key)).await?;
+        let body = serde_json::json!({ "transition": { "id": transition_id } });
+        let response = self
+            .send(
+                self.client
+                    .post(&url)
+                    .header("Content-Type", "application/json")
+                    .json(&body),
+            )
+            .await?;
+
+        Self::check_response(response).await?;
+        Ok(())
+    }
+
+    /// Fetches a single issue with the same fields a sync pulls.
+    pub async fn get_issue(&self, key: &str) -> Result<JiraIssue, AppError> {
+        let url = self.api_url(&format!("issue/{}", key)).await?;
+        let response = self
+            .send(
+                self.client
+                    .get(&url)
+                    .query(&[("fields", self.field_list().join(","))]),
+            )
+            .await?;
+
+        Self::parse_response(response).await
+    }
+
+    /// Description and comments of `key`, rendered as Markdown.
+    pub async fn get_issue_content(&self, key: &str) -> Result<TicketContent, AppError> {
+        let url = self.api_url(&format!("issue/{}", key)).await?;
+        let response = self
+            .send(
+                self.client
+                    .get(&url)
+                    .query(&[("fields", "description,comment")]),
+            )
+            .await?;
+        let issue: JiraIssueContent = Self::parse_response(response).await?;
+
+        let description = adf::to_markdown(&issue.fields.description);
+        Ok(TicketContent {
+            jira_key: key.to_string(),
+            description: (!description.trim().is_empty()).then_some(description),
+            comments: issue
+                .fields
+                .comment
+                .map(|page| page.comments)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|c| TicketComment {
+                    author: c.author.map(|a| a.display_name),
+                    created_at: c.created,
+                    body: adf::to_markdown(&c.body),
+                })
+                .collect(),
+        })
+    }
+
+    /// Assigns `key` to `assignee` (an accountId on Cloud, a username on
+    /// Server/Data Center), or unassigns it when `None`.
+    pub async fn assign_issue(&self, key: &str, assignee: Option<&str>) -> Result<(), AppError> {
+        let url = self.api_url(&format!("issue/{}/assignee", key)).await?;
+        let body = match self.api_mode().await? {
+            JiraApiMode::Cloud => serde_json::json!({ "accountId": assignee }),
+            JiraApiMode::Server => serde_json::json!({ "name": assignee }),
+        };
+        let response = self
+            .send(
+                self.client
+                    .put(&url)
+                    .header("Content-Type", "application/json")
+                    .json(&body),
+            )
+            .await?;
+
+        Self::check_response(response).await?;
+        Ok(())
+    }
+
+    /// Creates an issue and returns its key.
+    pub async fn create_issue(&self, issue: &NewIssue) -> Result<String, AppError> {
+        let mode = self.api_mode().await?;
+        let url = self.api_url("issue").await?;
+
+        let mut fields = serde_json::json!({
+            "project": { "key": issue.project_key },
+            "issuetype": { "name": issue.issue_type },
+            "summary": issue.summary,
+            "labels": issue.labels,
+        });
+        if let Some(description) = issue
+            .description
+            .as_deref()
+            .filter(|d| !d.trim().is_empty())
+        {
+            fields["description"] = match mode {
+                JiraApiMode::Cloud => plain_text_to_adf(description),
+                JiraApiMode::Server => serde_json::Value::String(description.to_string()),
+            };
+        }
+        if let Some(priority) = issue.priority.as_deref().filter(|p| !p.is_empty()) {
+            fields["priority"] = serde_json::json!({ "name": priority });
+        }
+
+        let response = self
+            .send(
+                self.client
+                    .post(&url)
+                    .header("Content-Type", "application/json")
+                    .json(&serde_json::json!({ "fields": fields })),
+            )
+            .await?;
+
+        let created: JiraCreatedIssue = Self::parse_response(response).await?;
+        Ok(created.key)
+    }
+
+    pub async fn add_comment(&self, key: &str, body: &str) -> Result<(), AppError> {
+        let url = self.api_url(&format!("issue/{}/comment", key)).await?;
+        // v3 only accepts Atlassian Document Format; v2 takes plain text
+        let body = match self.api_mode().await? {
+            JiraApiMode::Cloud => serde_json::json!({ "body": plain_text_to_adf(body) }),
+            JiraApiMode::Server => serde_json::json!({ "body": body }),
+        };
+        let response = self
+            .send(
+                self.client
+                    .post(&url)
+                    .header("Content-Type", "application/json")
+                    .json(&body),
+            )
+            .await?;
+
+        Self::check_response(response).await?;
+        Ok(())
+    }
+
+    /// Logs `seconds` of work on issue `key`, started at `started` (UTC).
+    /// Returns the new worklog's ID.
+    pub async fn add_worklog(
+        &self,
+        key: &str,
+        started: NaiveDateTime,
+        seconds: i64,
+    ) -> Result<String, AppError> {
+        let url = self.api_url(&format!("issue/{}/worklog", key)).await?;
+        let response = self
+            .send(
+                self.client
+                    .post(&url)
+                    .header("Content-Type", "application/json")
+                    .json(&worklog_body(started, seconds)),
+            )
+            .await?;
+
+        let worklog: JiraWorklog = Self::parse_response(response).await?;
+        Ok(worklog.id)
+    }
+
+    /// Downloads an attachment's contents. Only URLs on this Jira site are
+    /// fetched, so credentials are never sent to another host.
+    pub async fn download_attachment(&self, content_url: &str) -> Result<Vec<u8>, AppError> {
+        if !content_url.starts_with(&format!("{}/", self.site_url)) {
+            return Err(AppError::Config(format!(
+                "Attachment URL {} is not on {}",
+                content_url, self.site_url
+            )));
+        }
+
+        let response = self.send(self.client.get(content_url)).await?;
+        let bytes = Self::check_response(response)
+            .await?
+            .bytes()
             .await
             .map_err(JiraError::from)?;
+        Ok(bytes.to_vec())
+    }
+
+    /// Downloads the image at `url` (an avatar or issue-type icon), or reports
+    /// that the copy tagged `etag` is still current. Only URLs on the Jira
+    /// site get the auth header; avatars served from elsewhere are public.
+    pub async fn fetch_image(&self, url: &str, etag: Option<&str>) -> Result<ImageFetch, AppError> {
+        let mut request = self.client.get(url);
+        if let Some(etag) = etag {
+            request = request.header("If-None-Match", etag);
+        }
+        let response = if url.starts_with(&format!("{}/", self.site_url)) {
+            self.send(request).await?
+        } else {
+            request
+                .timeout(self.limits.request_timeout())
+                .send()
+                .await
+                .map_err(JiraError::from)?
+        };
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(ImageFetch::NotModified);
+        }
 
+        let response = Self::check_response(response).await?;
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(reqwest::header::ETAG);
+        let content_type = header(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.split(';').next().map(|t| t.trim().to_ascii_lowercase()))
+            .unwrap_or_default();
+        if !content_type.starts_with("image/") {
+            return Err(JiraError::ParseError(format!(
+                "{} is not an image ({})",
+                url, content_type
+            ))
+            .into());
+        }
+        let bytes = response.bytes().await.map_err(JiraError::from)?;
+        if bytes.len() > MAX_IMAGE_BYTES {
+            return Err(JiraError::ParseError(format!(
+                "{} is larger than {} bytes",
+                url, MAX_IMAGE_BYTES
+            ))
+            .into());
+        }
+
+        Ok(ImageFetch::Fetched {
+            bytes: bytes.to_vec(),
+            etag,
+            content_type,
+        })
+    }
+
+    async fn parse_response<T: DeserializeOwned>(
+        response: reqwest::Response,
+    ) -> Result<T, AppError> {
+        Self::check_response(response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| JiraError::ParseError(e.to_string()).into())
+    }
+
+    /// Maps non-2xx responses onto `JiraError`, passing successful ones through.
+    async fn check_response(response: reqwest::Response) -> Result<reqwest::Response, AppError> {
         let status = response.status();
 
         if status.is_success() {
-            let search_response: JiraSearchResponse = response
-                .json()
-                .await
-                .map_err(|e| JiraError::ParseError(e.to_string()))?;
-            Ok(search_response)
+            Ok(response)
         } else if status.as_u16() == 401 {
             Err(JiraError::Unauthorized.into())
         } else if status.as_u16() == 429 {
@@ -149,6 +709,13 @@ impl JiraClient {
                 .text()
                 .await
                 .unwrap_or_else(|_| "Failed to read error response".to_string());
+
+            if status.as_u16() == 400 {
+                if let Some(errors) = parse_validation_errors(&body) {
+                    return Err(JiraError::Validation(errors).into());
+                }
+            }
+
             Err(JiraError::ApiError {
                 status: status.as_u16(),
                 body,
@@ -157,13 +724,27 @@ impl JiraClient {
         }
     }
 
-    fn convert_issue_to_ticket(issue: crate::jira::types::JiraIssue) -> Ticket {
+    pub(crate) fn convert_issue_to_ticket(issue: JiraIssue, fields: &JiraFieldMapping) -> Ticket {
+        let story_points = fields.story_points(&issue.fields.custom);
+        let sprint = fields.sprint(&issue.fields.custom);
+        let customer = fields.customer(&issue.fields.custom);
+        let environment = adf::to_markdown(&issue.fields.environment)
+            .trim()
+            .to_string();
+        let escalated = fields.escalated(&issue.fields.custom, &issue.fields.labels);
         Ticket {
             id: 0, // Will be set by database
             jira_key: issue.key,
             summary: issue.fields.summary,
             status: issue.fields.status.name,
+            status_category: issue
+                .fields
+                .status
+                .status_category
+                .as_ref()
+                .and_then(|category| StatusCategory::from_jira_key(&category.key)),
             priority: issue.fields.priority.name,
+            priority_level: None, // Set on write from the priority mappings
             issue_type: issue.fields.issuetype.name,
             assignee: issue.fields.assignee.map(|a| a.display_name),
             reporter: issue.fields.reporter.map(|r| r.display_name),
@@ -173,13 +754,490 @@ impl JiraClient {
             labels: issue.fields.labels.join(","),
             project_key: issue.fields.project.key,
             category: None, // Will be set by categorizer
+            category_confidence: None,
+            source: SOURCE_ID.to_string(),
+            custom_fields: String::new(),
+            account_id: None,
+            story_points,
+            sprint,
+            due_date: issue.fields.duedate,
+            customer,
+            environment: (!environment.is_empty()).then_some(environment),
+            escalated,
+            components: issue
+                .fields
+                .components
+                .into_iter()
+                .map(|c| c.name)
+                .collect(),
+            fix_versions: issue
+                .fields
+                .fix_versions
+                .into_iter()
+                .map(|v| v.name)
+                .collect(),
+            affected_versions: issue.fields.versions.into_iter().map(|v| v.name).collect(),
+        }
+    }
+
+    /// `fields.attachment` as stored attachment metadata.
+    pub(crate) fn convert_attachments(issue: &JiraIssue) -> Vec<Attachment> {
+        issue
+            .fields
+            .attachment
+            .iter()
+            .map(|a| Attachment {
+                id: a.id.clone(),
+                ticket_key: issue.key.clone(),
+                filename: a.filename.clone(),
+                size_bytes: a.size,
+                mime_type: a.mime_type.clone(),
+                author: a.author.as_ref().map(|author| author.display_name.clone()),
+                created_at: a.created.clone(),
+                content_url: a.content.clone(),
+            })
+            .collect()
+    }
+
+    /// `fields.issuelinks` as outward-first links.
+    pub(crate) fn convert_issue_links(issue: &JiraIssue) -> Vec<TicketLink> {
+        issue
+            .fields
+            .issuelinks
+            .iter()
+            .filter_map(|link| {
+                let (outward_key, inward_key) = match (&link.outward_issue, &link.inward_issue) {
+                    (Some(other), _) => (issue.key.clone(), other.key.clone()),
+                    (None, Some(other)) => (other.key.clone(), issue.key.clone()),
+                    (None, None) => return None,
+                };
+                Some(TicketLink {
+                    outward_key,
+                    inward_key,
+                    link_type: link.link_type.name.clone(),
+                    outward_label: link.link_type.outward.clone(),
+                    inward_label: link.link_type.inward.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reads Jira's field-validation body, ignoring 400s that carry nothing useful.
+fn parse_validation_errors(body: &str) -> Option<JiraValidationErrors> {
+    serde_json::from_str::<JiraValidationErrors>(body)
+        .ok()
+        .filter(|e| !e.messages.is_empty() || !e.field_errors.is_empty())
+}
+
+/// Wraps plain text in a minimal ADF document, one paragraph per blank-line
+/// separated block.
+fn plain_text_to_adf(text: &str) -> serde_json::Value {
+    let paragraphs: Vec<serde_json::Value> = text
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            serde_json::json!({
+                "type": "paragraph",
+                "content": [{ "type": "text", "text": p }],
+            })
+        })
+        .collect();
+
+    serde_json::json!({ "type": "doc", "version": 1, "content": paragraphs })
+}
+
+/// A worklog request body. Jira wants `started` with milliseconds and a
+/// numeric offset, and rejects worklogs shorter than a minute.
+fn worklog_body(started: NaiveDateTime, seconds: i64) -> serde_json::Value {
+    serde_json::json!({
+        "started": started.format("%Y-%m-%dT%H:%M:%S%.3f+0000").to_string(),
+        "timeSpentSeconds": seconds.max(60),
+    })
+}
+
+#[async_trait]
+impl TicketSource for JiraClient {
+    type Item = JiraIssue;
+
+    fn source_id(&self) -> &'static str {
+        SOURCE_ID
+    }
+
+    fn cursor_key(&self) -> String {
+        match self.account_id {
+            Some(account_id) => account_cursor_key(account_id),
+            None => source_metadata_key(SOURCE_ID, "last_sync_at"),
         }
     }
+
+    /// Every Jira cursor is a sync time, so an account never touches the
+    /// primary site's `last_sync_at`.
+    fn last_sync_key(&self) -> String {
+        self.cursor_key()
+    }
+
+    async fn authenticate(&self) -> Result<(), AppError> {
+        let response = self
+            .send(self.client.get(self.api_url("myself").await?))
+            .await?;
+
+        Self::parse_response::<serde_json::Value>(response).await?;
+        Ok(())
+    }
+
+    async fn fetch_incremental(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<SourceBatch<JiraIssue>, AppError> {
+        // Taken before fetching so updates made mid-sync are picked up next time
+        let sync_started_at = chrono::Utc::now().to_rfc3339();
+        let deadline = std::time::Instant::now() + self.limits.sync_deadline();
+        let mut items = Vec::new();
+        let mut failures = Vec::new();
+        let jql = Self::build_jql(cursor);
+
+        match self.api_mode().await? {
+            JiraApiMode::Cloud => {
+                let mut next_page_token: Option<String> = None;
+                loop {
+                    self.check_deadline(deadline)?;
+                    let response = self.search_jql(&jql, next_page_token.as_deref()).await?;
+                    Self::collect_issues(response.issues, &mut items, &mut failures);
+
+                    if response.next_page_token.is_none() {
+                        break;
+                    }
+                    next_page_token = response.next_page_token;
+                }
+            }
+            JiraApiMode::Server => {
+                let mut start_at = 0;
+                loop {
+                    self.check_deadline(deadline)?;
+                    let page = self.search_paged(&jql, start_at).await?;
+                    let next = Self::next_start_at(&page);
+                    Self::collect_issues(page.issues, &mut items, &mut failures);
+
+                    match next {
+                        Some(next) => start_at = next,
+                        None => break,
+                    }
+                }
+            }
+        }
+
+        Ok(SourceBatch {
+            items,
+            failures,
+            next_cursor: Some(sync_started_at),
+        })
+    }
+
+    fn map_to_ticket(&self, item: JiraIssue) -> Ticket {
+        let mut ticket = Self::convert_issue_to_ticket(item, &self.field_mapping);
+        if let Some(account_id) = self.account_id {
+            ticket.jira_key = account_ticket_key(account_id, &ticket.jira_key);
+        }
+        ticket.account_id = self.account_id;
+        ticket
+    }
+
+    fn map_links(&self, item: &JiraIssue) -> Option<Vec<TicketLink>> {
+        let mut links = Self::convert_issue_links(item);
+        // Linked issues live on the same site, so they share the account's keys
+        if let Some(account_id) = self.account_id {
+            for link in &mut links {
+                link.outward_key = account_ticket_key(account_id, &link.outward_key);
+                link.inward_key = account_ticket_key(account_id, &link.inward_key);
+            }
+        }
+        Some(links)
+    }
+
+    fn map_attachments(&self, item: &JiraIssue) -> Option<Vec<Attachment>> {
+        let mut attachments = Self::convert_attachments(item);
+        if let Some(account_id) = self.account_id {
+            for attachment in &mut attachments {
+                attachment.ticket_key = account_ticket_key(account_id, &attachment.ticket_key);
+            }
+        }
+        Some(attachments)
+    }
+
+    fn map_sprints(&self, item: &JiraIssue) -> Vec<Sprint> {
+        self.field_mapping.sprints(&item.fields.custom)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::JiraClient;
+    use super::{
+        parse_validation_errors, plain_text_to_adf, worklog_body, JiraApiMode, JiraAuth,
+        JiraAuthMethod, JiraClient,
+    };
+    use crate::connectors::TicketSource;
+    use crate::db::account_cursor_key;
+    use crate::jira::types::{JiraIssue, JiraPagedSearchResponse, JiraUser};
+    use crate::models::User;
+
+    fn paged(start_at: u64, issues: usize, total: u64) -> JiraPagedSearchResponse {
+        let issue = serde_json::json!({
+            "key": "OPS-1",
+            "fields": {
+                "summary": "s",
+                "status": { "name": "Open" },
+                "priority": { "name": "High" },
+                "issuetype": { "name": "Bug" },
+                "assignee": null,
+                "reporter": null,
+                "created": "2025-01-01T00:00:00.000+0000",
+                "updated": "2025-01-01T00:00:00.000+0000",
+                "resolutiondate": null,
+                "labels": [],
+                "project": { "key": "OPS" }
+            }
+        });
+        serde_json::from_value(serde_json::json!({
+            "startAt": start_at,
+            "maxResults": 100,
+            "total": total,
+            "issues": vec![issue; issues],
+        }))
+        .expect("paged response")
+    }
+
+    #[test]
+    fn auth_header_matches_strategy() {
+        let basic = JiraAuth::from_method(JiraAuthMethod::Basic, "me@example.com", "secret");
+        assert_eq!(basic.header_value(), "Basic bWVAZXhhbXBsZS5jb206c2VjcmV0");
+
+        let pat = JiraAuth::from_method(JiraAuthMethod::Pat, "ignored@example.com", "pat-123");
+        assert_eq!(pat.header_value(), "Bearer pat-123");
+    }
+
+    #[test]
+    fn validation_errors_are_structured() {
+        let errors = parse_validation_errors(
+            r#"{"errorMessages":[],"errors":{"summary":"You must specify a summary of the issue."}}"#,
+        )
+        .expect("validation errors");
+        assert_eq!(
+            errors.field_errors.get("summary").map(String::as_str),
+            Some("You must specify a summary of the issue.")
+        );
+        assert_eq!(
+            errors.summary(),
+            "summary: You must specify a summary of the issue."
+        );
+
+        assert!(parse_validation_errors(r#"{"errorMessages":[],"errors":{}}"#).is_none());
+        assert!(parse_validation_errors("<html>Bad Request</html>").is_none());
+    }
+
+    #[test]
+    fn plain_text_becomes_adf_paragraphs() {
+        let adf = plain_text_to_adf("First line\n\n\nSecond block\n");
+        assert_eq!(adf["type"], "doc");
+        let content = adf["content"].as_array().expect("content");
+        assert_eq!(content.len(), 2);
+        assert_eq!(content[0]["content"][0]["text"], "First line");
+        assert_eq!(content[1]["content"][0]["text"], "Second block");
+    }
+
+    #[test]
+    fn worklogs_use_jira_timestamps_and_whole_minutes() {
+        let started = chrono::NaiveDate::from_ymd_opt(2025, 1, 6)
+            .and_then(|d| d.and_hms_opt(9, 30, 0))
+            .expect("valid datetime");
+        let body = worklog_body(started, 25);
+        assert_eq!(body["started"], "2025-01-06T09:30:00.000+0000");
+        assert_eq!(body["timeSpentSeconds"], 60);
+        assert_eq!(worklog_body(started, 5400)["timeSpentSeconds"], 5400);
+    }
+
+    #[test]
+    fn users_are_identified_by_account_id_or_username() {
+        let users: Vec<JiraUser> = serde_json::from_value(serde_json::json!([
+            {
+                "accountId": "5b10a2844c20165700ede21g",
+                "displayName": "Dana Scully",
+                "active": false,
+                "avatarUrls": { "16x16": "https://a/16.png", "48x48": "https://a/48.png" }
+            },
+            { "name": "fmulder", "key": "JIRAUSER10100", "displayName": "Fox Mulder" },
+            { "displayName": "Anonymous" }
+        ]))
+        .expect("users");
+        let users: Vec<User> = users
+            .into_iter()
+            .filter_map(JiraClient::convert_user)
+            .collect();
+
+        assert_eq!(
+            users,
+            vec![
+                User {
+                    account_id: "5b10a2844c20165700ede21g".to_string(),
+                    display_name: "Dana Scully".to_string(),
+                    avatar_url: Some("https://a/48.png".to_string()),
+                    active: false,
+                },
+                User {
+                    account_id: "fmulder".to_string(),
+                    display_name: "Fox Mulder".to_string(),
+                    avatar_url: None,
+                    active: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn deployment_type_selects_api_mode() {
+        assert_eq!(
+            JiraApiMode::from_deployment_type(Some("Cloud")),
+            JiraApiMode::Cloud
+        );
+        assert_eq!(
+            JiraApiMode::from_deployment_type(Some("Server")),
+            JiraApiMode::Server
+        );
+        assert_eq!(
+            JiraApiMode::from_deployment_type(Some("DataCenter")),
+            JiraApiMode::Server
+        );
+        // Older Server releases omit deploymentType entirely
+        assert_eq!(JiraApiMode::from_deployment_type(None), JiraApiMode::Server);
+    }
+
+    #[test]
+    fn accounts_record_their_own_last_sync() {
+        let client = |url: &str| {
+            let auth = JiraAuth::from_method(JiraAuthMethod::Pat, "", "pat-123");
+            JiraClient::new(url, auth, reqwest::Client::new()).expect("client")
+        };
+        let primary = client("https://a.example.net");
+        let account = client("https://b.example.net").with_account(2);
+
+        assert_eq!(primary.last_sync_key(), "last_sync_at");
+        assert_eq!(account.last_sync_key(), account_cursor_key(2));
+        assert_eq!(account.last_sync_key(), account.cursor_key());
+    }
+
+    #[test]
+    fn issue_links_are_stored_outward_first() {
+        let issue: JiraIssue = serde_json::from_value(serde_json::json!({
+            "key": "OPS-2",
+            "fields": {
+                "summary": "s",
+                "status": { "name": "Open" },
+                "priority": { "name": "High" },
+                "issuetype": { "name": "Bug" },
+                "assignee": null,
+                "reporter": null,
+                "created": "2025-01-01T00:00:00.000+0000",
+                "updated": "2025-01-01T00:00:00.000+0000",
+                "resolutiondate": null,
+                "labels": [],
+                "project": { "key": "OPS" },
+                "issuelinks": [
+                    {
+                        "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+                        "inwardIssue": { "key": "OPS-1" }
+                    },
+                    {
+                        "type": { "name": "Relates", "inward": "relates to", "outward": "relates to" },
+                        "outwardIssue": { "key": "OPS-9" }
+                    }
+                ]
+            }
+        }))
+        .expect("issue");
+
+        let links = JiraClient::convert_issue_links(&issue);
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(
+            (links[0].outward_key.as_str(), links[0].inward_key.as_str()),
+            ("OPS-1", "OPS-2")
+        );
+        assert_eq!(links[0].link_type, "Blocks");
+        assert_eq!(
+            (links[1].outward_key.as_str(), links[1].inward_key.as_str()),
+            ("OPS-2", "OPS-9")
+        );
+    }
+
+    #[test]
+    fn attachments_keep_author_and_content_url() {
+        let issue: JiraIssue = serde_json::from_value(serde_json::json!({
+            "key": "OPS-3",
+            "fields": {
+                "summary": "s",
+                "status": { "name": "Open" },
+                "priority": { "name": "High" },
+                "issuetype": { "name": "Bug" },
+                "assignee": null,
+                "reporter": null,
+                "created": "2025-01-01T00:00:00.000+0000",
+                "updated": "2025-01-01T00:00:00.000+0000",
+                "resolutiondate": null,
+                "labels": [],
+                "project": { "key": "OPS" },
+                "attachment": [{
+                    "id": "10042",
+                    "filename": "trace.har",
+                    "size": 5120,
+                    "mimeType": "application/json",
+                    "author": { "displayName": "Dana" },
+                    "created": "2025-01-02T10:00:00.000+0000",
+                    "content": "https://jira.example.com/secure/attachment/10042/trace.har"
+                }]
+            }
+        }))
+        .expect("issue");
+
+        let attachments = JiraClient::convert_attachments(&issue);
+
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].ticket_key, "OPS-3");
+        assert_eq!(attachments[0].size_bytes, 5120);
+        assert_eq!(attachments[0].author.as_deref(), Some("Dana"));
+        assert_eq!(
+            attachments[0].content_url,
+            "https://jira.example.com/secure/attachment/10042/trace.har"
+        );
+    }
+
+    #[test]
+    fn next_start_at_advances_until_total() {
+        assert_eq!(JiraClient::next_start_at(&paged(0, 100, 250)), Some(100));
+        assert_eq!(JiraClient::next_start_at(&paged(200, 50, 250)), None);
+        assert_eq!(JiraClient::next_start_at(&paged(100, 0, 250)), None);
+    }
+
+    #[test]
+    fn malformed_issues_are_set_aside_without_failing_the_page() {
+        let mut page = paged(0, 2, 2);
+        let broken: JiraPagedSearchResponse = serde_json::from_value(serde_json::json!({
+            "startAt": 0,
+            "total": 2,
+            "issues": [{ "key": "OPS-9", "fields": { "summary": 42 } }],
+        }))
+        .expect("page with a malformed issue still parses");
+        page.issues.extend(broken.issues);
+
+        let mut items = Vec::new();
+        let mut failures = Vec::new();
+        JiraClient::collect_issues(page.issues, &mut items, &mut failures);
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].key.as_deref(), Some("OPS-9"));
+        assert!(failures[0].error.contains("invalid type"));
+    }
 
     #[test]
     fn build_jql_uses_incremental_query_for_valid_rfc3339() {
@@ -0,0 +1,264 @@
+use crate::models::Sprint;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Site-specific custom field ids (e.g. `customfield_10016`). Jira assigns
+/// these per instance, so estimates, sprints, customers and field-based
+/// escalation are only synced once set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JiraFieldMapping {
+    /// Story points / estimate field.
+    pub story_points: Option<String>,
+    /// Sprint field (Jira Software).
+    pub sprint: Option<String>,
+    /// Field flagging an escalated issue: a checkbox, select, or any field
+    /// that is set when the issue is escalated.
+    pub escalated: Option<String>,
+    /// Label marking an escalated issue, as an alternative (or in addition)
+    /// to `escalated`.
+    pub escalation_label: Option<String>,
+    /// Customer / account / organization field.
+    pub customer: Option<String>,
+}
+
+impl JiraFieldMapping {
+    /// Configured field ids, to request alongside the standard fields.
+    pub fn field_ids(&self) -> impl Iterator<Item = &str> {
+        [
+            &self.story_points,
+            &self.sprint,
+            &self.escalated,
+            &self.customer,
+        ]
+        .into_iter()
+        .filter_map(|id| id.as_deref().filter(|id| !id.is_empty()))
+    }
+
+    pub fn story_points(&self, fields: &HashMap<String, Value>) -> Option<f64> {
+        match fields.get(self.story_points.as_deref()?)? {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Whether the issue carries the escalation label or has the escalation
+    /// field set. Unchecked checkboxes, empty values, `false`, `0`, and "No"
+    /// don't count.
+    pub fn escalated(&self, fields: &HashMap<String, Value>, labels: &[String]) -> bool {
+        let labelled = self
+            .escalation_label
+            .as_deref()
+            .filter(|label| !label.is_empty())
+            .is_some_and(|label| labels.iter().any(|l| l.eq_ignore_ascii_case(label)));
+        labelled
+            || self
+                .escalated
+                .as_deref()
+                .and_then(|id| fields.get(id))
+                .is_some_and(is_set)
+    }
+
+    /// The issue's customer: a text field, a select option, or the first of
+    /// several organizations (Jira Service Management).
+    pub fn customer(&self, fields: &HashMap<String, Value>) -> Option<String> {
+        customer_name(fields.get(self.customer.as_deref()?)?)
+    }
+
+    /// Name of the last sprint the issue was in.
+    pub fn sprint(&self, fields: &HashMap<String, Value>) -> Option<String> {
+        self.sprints(fields).pop().map(|sprint| sprint.name)
+    }
+
+    /// Every sprint the issue was in, oldest first. Cloud returns sprint
+    /// objects; Server/Data Center returns `...Sprint@1a2b[id=1,...,name=Sprint 4,...]`
+    /// strings.
+    pub fn sprints(&self, fields: &HashMap<String, Value>) -> Vec<Sprint> {
+        let Some(sprints) = self
+            .sprint
+            .as_deref()
+            .and_then(|id| fields.get(id))
+            .and_then(Value::as_array)
+        else {
+            return Vec::new();
+        };
+        sprints
+            .iter()
+            .filter_map(|sprint| match sprint {
+                Value::Object(sprint) => {
+                    let text = |key: &str| sprint.get(key)?.as_str().map(str::to_string);
+                    Some(Sprint {
+                        name: text("name")?,
+                        state: text("state"),
+                        start_date: text("startDate"),
+                        end_date: text("endDate"),
+                    })
+                }
+                Value::String(sprint) => Some(Sprint {
+                    name: server_sprint_attribute(sprint, "name")?,
+                    state: server_sprint_attribute(sprint, "state")
+                        .map(|state| state.to_lowercase()),
+                    start_date: server_sprint_attribute(sprint, "startDate"),
+                    end_date: server_sprint_attribute(sprint, "endDate"),
+                }),
+                _ => None,
+            })
+            .filter(|sprint| !sprint.name.is_empty())
+            .collect()
+    }
+}
+
+/// Display name in a customer field value.
+fn customer_name(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim()).filter(|s| !s.is_empty()).map(str::to_string),
+        Value::Array(values) => values.iter().find_map(customer_name),
+        Value::Object(option) => ["value", "name"]
+            .iter()
+            .find_map(|key| option.get(*key).and_then(customer_name)),
+        _ => None,
+    }
+}
+
+/// Whether a flag-like field value means "yes".
+fn is_set(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(set) => *set,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => {
+            let s = s.trim();
+            !(s.is_empty()
+                || ["no", "false", "0"]
+                    .iter()
+                    .any(|no| s.eq_ignore_ascii_case(no)))
+        }
+        Value::Array(values) => values.iter().any(is_set),
+        // Select options arrive as `{ "value": "Yes", ... }`
+        Value::Object(option) => match option.get("value") {
+            Some(value) => is_set(value),
+            None => true,
+        },
+    }
+}
+
+/// `attribute`'s value in a Server sprint string, where unset values read
+/// `<null>`.
+fn server_sprint_attribute(sprint: &str, attribute: &str) -> Option<String> {
+    let marker = format!("{}=", attribute);
+    let start = sprint
+        .match_indices(&marker)
+        .map(|(at, _)| at)
+        .find(|&at| at == 0 || matches!(sprint.as_bytes()[at - 1], b'[' | b','))?
+        + marker.len();
+    let rest = &sprint[start..];
+    let end = rest.find([',', ']']).unwrap_or(rest.len());
+    Some(rest[..end].to_string()).filter(|value| value != "<null>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> JiraFieldMapping {
+        JiraFieldMapping {
+            story_points: Some("customfield_10016".to_string()),
+            sprint: Some("customfield_10020".to_string()),
+            escalated: Some("customfield_10050".to_string()),
+            escalation_label: Some("escalated".to_string()),
+            customer: Some("customfield_10060".to_string()),
+        }
+    }
+
+    #[test]
+    fn reads_cloud_and_server_sprint_formats() {
+        let cloud: HashMap<String, Value> = serde_json::from_value(serde_json::json!({
+            "customfield_10016": 5.0,
+            "customfield_10020": [
+                { "id": 1, "name": "Sprint 3", "state": "closed" },
+                { "id": 2, "name": "Sprint 4", "state": "active" }
+            ]
+        }))
+        .expect("fields");
+        assert_eq!(mapping().story_points(&cloud), Some(5.0));
+        assert_eq!(mapping().sprint(&cloud).as_deref(), Some("Sprint 4"));
+        assert_eq!(
+            mapping().sprints(&cloud)[0].state.as_deref(),
+            Some("closed")
+        );
+
+        let server: HashMap<String, Value> = serde_json::from_value(serde_json::json!({
+            "customfield_10016": "3",
+            "customfield_10020": [
+                "com.atlassian.greenhopper.service.sprint.Sprint@1f[id=7,rapidViewId=2,state=ACTIVE,name=Ops 12,startDate=2025-01-06]"
+            ]
+        }))
+        .expect("fields");
+        assert_eq!(mapping().story_points(&server), Some(3.0));
+        assert_eq!(mapping().sprint(&server).as_deref(), Some("Ops 12"));
+        assert_eq!(
+            mapping().sprints(&server),
+            vec![Sprint {
+                name: "Ops 12".to_string(),
+                state: Some("active".to_string()),
+                start_date: Some("2025-01-06".to_string()),
+                end_date: None,
+            }]
+        );
+
+        assert_eq!(JiraFieldMapping::default().story_points(&cloud), None);
+        assert_eq!(JiraFieldMapping::default().field_ids().count(), 0);
+    }
+
+    #[test]
+    fn escalation_comes_from_the_label_or_a_set_field() {
+        let fields = |value: Value| -> HashMap<String, Value> {
+            HashMap::from([("customfield_10050".to_string(), value)])
+        };
+        let no_labels: &[String] = &[];
+
+        assert!(mapping().escalated(
+            &HashMap::new(),
+            &["Customer".to_string(), "Escalated".to_string()]
+        ));
+        assert!(mapping().escalated(&fields(serde_json::json!([{ "value": "Yes" }])), no_labels));
+        assert!(mapping().escalated(&fields(serde_json::json!({ "value": "Tier 2" })), no_labels));
+        assert!(mapping().escalated(&fields(Value::Bool(true)), no_labels));
+        for unset in [
+            Value::Null,
+            serde_json::json!([]),
+            serde_json::json!({ "value": "No" }),
+            Value::String(String::new()),
+            Value::Bool(false),
+        ] {
+            assert!(!mapping().escalated(&fields(unset), no_labels));
+        }
+        assert!(!JiraFieldMapping::default()
+            .escalated(&fields(Value::Bool(true)), &["escalated".to_string()]));
+    }
+
+    #[test]
+    fn customer_reads_text_options_and_organizations() {
+        let customer = |value: Value| {
+            mapping().customer(&HashMap::from([("customfield_10060".to_string(), value)]))
+        };
+        assert_eq!(
+            customer(Value::String(" Acme ".to_string())),
+            Some("Acme".to_string())
+        );
+        assert_eq!(
+            customer(serde_json::json!({ "id": "10001", "value": "Globex" })),
+            Some("Globex".to_string())
+        );
+        assert_eq!(
+            customer(
+                serde_json::json!([{ "id": 3, "name": "Initech" }, { "id": 4, "name": "Hooli" }])
+            ),
+            Some("Initech".to_string())
+        );
+        assert_eq!(customer(serde_json::json!([])), None);
+        assert_eq!(customer(Value::Null), None);
+    }
+}
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Guard rails for a Jira sync so a runaway JQL can't hammer the API or hang.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JiraLimits {
+    /// Upper bound on any single HTTP request.
+    pub request_timeout_secs: u64,
+    /// Upper bound on fetching every page of one sync.
+    pub sync_deadline_secs: u64,
+    /// Client-side request budget; 0 disables throttling.
+    pub max_requests_per_sec: u32,
+}
+
+impl Default for JiraLimits {
+    fn default() -> Self {
+        JiraLimits {
+            request_timeout_secs: 30,
+            sync_deadline_secs: 600,
+            max_requests_per_sec: 10,
+        }
+    }
+}
+
+impl JiraLimits {
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_secs)
+    }
+
+    pub fn sync_deadline(&self) -> Duration {
+        Duration::from_secs(self.sync_deadline_secs)
+    }
+}
+
+/// Spaces requests evenly so at most `max_per_sec` start in any second.
+pub struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn per_second(max_per_sec: u32) -> Self {
+        let interval = if max_per_sec == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs(1) / max_per_sec
+        };
+
+        RateLimiter {
+            interval,
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Claims the next free slot and returns how long to wait for it.
+    fn reserve(&self, now: Instant) -> Duration {
+        let mut next_slot = self
+            .next_slot
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let slot = (*next_slot).max(now);
+        *next_slot = slot + self.interval;
+        slot - now
+    }
+
+    pub async fn acquire(&self) {
+        let wait = self.reserve(Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserve_spaces_requests_by_interval() {
+        let limiter = RateLimiter::per_second(10);
+        let now = Instant::now() + Duration::from_secs(1);
+
+        assert_eq!(limiter.reserve(now), Duration::ZERO);
+        assert_eq!(limiter.reserve(now), Duration::from_millis(100));
+        assert_eq!(limiter.reserve(now), Duration::from_millis(200));
+
+        // Idle time is not banked into a burst
+        let later = now + Duration::from_secs(5);
+        assert_eq!(limiter.reserve(later), Duration::ZERO);
+        assert_eq!(limiter.reserve(later), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn zero_budget_never_waits() {
+        let limiter = RateLimiter::per_second(0);
+        let now = Instant::now();
+        for _ in 0..5 {
+            assert_eq!(limiter.reserve(now), Duration::ZERO);
+        }
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let limits: JiraLimits =
+            serde_json::from_str(r#"{"max_requests_per_sec": 2}"#).expect("limits json");
+        assert_eq!(limits.max_requests_per_sec, 2);
+        assert_eq!(limits.request_timeout_secs, 30);
+        assert_eq!(limits.sync_deadline_secs, 600);
+    }
+}
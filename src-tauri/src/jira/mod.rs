@@ -1,4 +1,9 @@
+pub mod adf;
 pub mod client;
+pub mod fields;
+pub mod limits;
 pub mod types;
 
 pub use client::*;
+pub use fields::*;
+pub use limits::*;
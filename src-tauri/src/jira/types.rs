@@ -1,22 +1,145 @@
+use crate::models::SyncItemFailure;
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Deserialize)]
 pub struct JiraSearchResponse {
-    pub issues: Vec<JiraIssue>,
+    pub issues: Vec<JiraSearchIssue>,
     #[serde(rename = "nextPageToken")]
     pub next_page_token: Option<String>,
 }
 
+/// `/rest/api/2/search` response used by Jira Server and Data Center, which
+/// page with `startAt` offsets instead of `nextPageToken`.
+#[derive(Deserialize)]
+pub struct JiraPagedSearchResponse {
+    pub issues: Vec<JiraSearchIssue>,
+    #[serde(rename = "startAt")]
+    pub start_at: u64,
+    pub total: u64,
+}
+
+#[derive(Deserialize)]
+pub struct JiraServerInfo {
+    #[serde(rename = "deploymentType")]
+    pub deployment_type: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct JiraCreatedIssue {
+    pub key: String,
+}
+
+#[derive(Deserialize)]
+pub struct JiraWorklog {
+    pub id: String,
+}
+
+#[derive(Deserialize)]
+pub struct JiraTransitionsResponse {
+    pub transitions: Vec<JiraTransition>,
+}
+
+#[derive(Deserialize)]
+pub struct JiraTransition {
+    pub id: String,
+    pub name: String,
+    pub to: JiraStatus,
+}
+
+#[derive(Deserialize)]
+pub struct JiraStatus {
+    pub name: String,
+    #[serde(rename = "statusCategory")]
+    pub status_category: Option<KeyField>,
+}
+
+/// An entry of `user/assignable/multiProjectSearch`. Cloud identifies users
+/// by `accountId`; Server and Data Center by username (`name`).
+#[derive(Deserialize)]
+pub struct JiraUser {
+    #[serde(rename = "accountId")]
+    pub account_id: Option<String>,
+    pub name: Option<String>,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    #[serde(default = "default_active")]
+    pub active: bool,
+    /// Avatar URLs keyed by size, e.g. `48x48`.
+    #[serde(rename = "avatarUrls", default)]
+    pub avatar_urls: HashMap<String, String>,
+}
+
+fn default_active() -> bool {
+    true
+}
+
 #[derive(Deserialize)]
 pub struct JiraIssue {
     pub key: String,
     pub fields: JiraFields,
 }
 
+/// A search result. Issues that don't match `JiraIssue` are kept as raw
+/// JSON so one malformed issue doesn't fail its whole page.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum JiraSearchIssue {
+    Parsed(Box<JiraIssue>),
+    Malformed(serde_json::Value),
+}
+
+impl JiraSearchIssue {
+    /// The parsed issue, or the key and parse error of a malformed one.
+    pub fn into_issue(self) -> Result<JiraIssue, SyncItemFailure> {
+        match self {
+            JiraSearchIssue::Parsed(issue) => Ok(*issue),
+            JiraSearchIssue::Malformed(value) => {
+                let key = value
+                    .get("key")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::to_string);
+                // Parse again for the error the untagged match discarded
+                let error = match serde_json::from_value::<JiraIssue>(value) {
+                    Err(e) => e.to_string(),
+                    Ok(_) => "Issue could not be read".to_string(),
+                };
+                Err(SyncItemFailure { key, error })
+            }
+        }
+    }
+}
+
+/// Issue fetched for its rich-text content only.
+#[derive(Deserialize)]
+pub struct JiraIssueContent {
+    pub fields: JiraContentFields,
+}
+
+#[derive(Deserialize)]
+pub struct JiraContentFields {
+    /// ADF document on Cloud, plain string on Server/Data Center.
+    #[serde(default)]
+    pub description: serde_json::Value,
+    pub comment: Option<JiraCommentPage>,
+}
+
+#[derive(Deserialize)]
+pub struct JiraCommentPage {
+    pub comments: Vec<JiraComment>,
+}
+
+#[derive(Deserialize)]
+pub struct JiraComment {
+    pub author: Option<DisplayNameField>,
+    pub created: String,
+    pub body: serde_json::Value,
+}
+
 #[derive(Deserialize)]
 pub struct JiraFields {
     pub summary: String,
-    pub status: NameField,
+    pub status: JiraStatus,
     pub priority: NameField,
     pub issuetype: NameField,
     pub assignee: Option<DisplayNameField>,
@@ -24,8 +147,61 @@ pub struct JiraFields {
     pub created: String,
     pub updated: String,
     pub resolutiondate: Option<String>,
+    #[serde(default)]
+    pub duedate: Option<String>,
     pub labels: Vec<String>,
+    #[serde(default)]
+    pub components: Vec<NameField>,
+    #[serde(default, rename = "fixVersions")]
+    pub fix_versions: Vec<NameField>,
+    /// Affects versions.
+    #[serde(default)]
+    pub versions: Vec<NameField>,
+    /// Rich text on Cloud, a plain string on Server.
+    #[serde(default)]
+    pub environment: serde_json::Value,
     pub project: KeyField,
+    #[serde(default)]
+    pub issuelinks: Vec<JiraIssueLink>,
+    #[serde(default)]
+    pub attachment: Vec<JiraAttachment>,
+    /// Custom fields, read through `JiraFieldMapping`.
+    #[serde(flatten)]
+    pub custom: HashMap<String, serde_json::Value>,
+}
+
+/// One entry of `fields.issuelinks`. Exactly one of the two issues is set:
+/// `outwardIssue` reads "this issue <outward> that", `inwardIssue` reads
+/// "this issue <inward> that".
+#[derive(Deserialize)]
+pub struct JiraIssueLink {
+    #[serde(rename = "type")]
+    pub link_type: JiraIssueLinkType,
+    #[serde(rename = "inwardIssue")]
+    pub inward_issue: Option<KeyField>,
+    #[serde(rename = "outwardIssue")]
+    pub outward_issue: Option<KeyField>,
+}
+
+#[derive(Deserialize)]
+pub struct JiraIssueLinkType {
+    pub name: String,
+    pub inward: String,
+    pub outward: String,
+}
+
+#[derive(Deserialize)]
+pub struct JiraAttachment {
+    pub id: String,
+    pub filename: String,
+    #[serde(default)]
+    pub size: i64,
+    #[serde(rename = "mimeType")]
+    pub mime_type: Option<String>,
+    pub author: Option<DisplayNameField>,
+    pub created: Option<String>,
+    /// Download URL; needs the same auth as the REST API.
+    pub content: String,
 }
 
 #[derive(Deserialize)]
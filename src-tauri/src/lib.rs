@@ -1,4 +1,5 @@
 mod commands;
+mod connectors;
 mod db;
 mod errors;
 mod jira;
@@ -10,13 +11,23 @@ use db::DbPool;
 use std::path::PathBuf;
 use tauri::Manager;
 
+pub use models::{CustomMetric, DurationMode, Ticket};
+pub use services::metrics::{MetricProvider, MetricRegistry};
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
-#[allow(clippy::disallowed_methods)]
 pub fn run() {
+    run_with_metrics(MetricRegistry::default())
+}
+
+/// Runs the app with extra KPIs from `metrics`, for builds that add their
+/// own metrics without changing this crate.
+#[allow(clippy::disallowed_methods)]
+pub fn run_with_metrics(metrics: MetricRegistry) {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_store::Builder::new().build())
-        .setup(|app| {
+        .plugin(tauri_plugin_notification::init())
+        .setup(move |app| {
             // Initialize database
             let app_dir = app
                 .path()
@@ -26,29 +37,235 @@ pub fn run() {
             std::fs::create_dir_all(&app_dir)
                 .map_err(|e| format!("Failed to create app directory at {:?}: {}", app_dir, e))?;
 
-            let db_path: PathBuf = app_dir.join("tickets.db");
+            let db_path: PathBuf = app_dir.join(db::DATABASE_FILE);
             let db_path_str = db_path
                 .to_str()
                 .ok_or_else(|| format!("Invalid DB path: {:?}", db_path))?;
 
-            let db_pool = DbPool::new(db_path_str)
+            // Encrypted databases keep their key in the OS keychain
+            let db_key = commands::settings::get_database_key_internal().unwrap_or_else(|e| {
+                log::warn!("Reading database key failed: {}", e);
+                None
+            });
+            let db_pool = DbPool::new(db_path_str, db_key.as_deref())
                 .map_err(|e| format!("Failed to initialize database at {:?}: {}", db_path, e))?;
 
             app.manage(db_pool);
+            app.manage(services::cache::AggregationCache::new(metrics));
             app.manage(SyncLock(tokio::sync::Mutex::new(false)));
+            app.manage(WebhookState(std::sync::Mutex::new(None)));
+            app.manage(HttpApiState(std::sync::Mutex::new(None)));
+            app.manage(WallboardState(std::sync::Mutex::new(None)));
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             store_jira_token,
             delete_jira_token,
+            store_source_token,
+            delete_source_token,
             verify_jira_connection,
             save_jira_settings,
             load_jira_settings,
+            save_network_settings,
+            load_network_settings,
             trigger_sync,
             get_sync_status,
+            get_sync_history,
             get_dashboard_data,
+            aggregate,
+            get_pivot,
+            explain_queries,
+            run_readonly_query,
             get_all_tickets,
+            get_tickets_projection,
+            stream_tickets,
+            get_stale_tickets,
+            get_open_ticket_age_histogram,
+            get_stats_by_project,
+            get_status_dwell_violations,
+            list_rotations,
+            create_rotation,
+            delete_rotation,
+            get_triage_stats_by_rotation,
+            list_teams,
+            create_team,
+            update_team,
+            delete_team,
+            get_team_rollups,
+            list_support_calendars,
+            create_support_calendar,
+            update_support_calendar,
+            delete_support_calendar,
+            sync_users,
+            list_users,
+            get_icon,
+            open_in_jira,
+            copy_ticket_link,
+            get_stats_history,
+            get_inflow_anomalies,
+            get_trending_terms,
+            find_similar_tickets,
+            quick_search,
+            get_cohort_resolution,
+            mark_viewed,
+            get_recently_viewed,
+            list_ticket_notes,
+            create_ticket_note,
+            update_ticket_note,
+            delete_ticket_note,
+            start_timer,
+            stop_timer,
+            get_running_timers,
+            start_focus_session,
+            record_interruption,
+            get_focus_stats,
+            get_board_columns,
+            get_wip_limits,
+            set_wip_limit,
+            reorder_tickets,
+            get_ranked_tickets,
+            remove_ticket_rank,
+            get_calendar_entries,
+            schedule_work,
+            delete_scheduled_work,
+            export_ics,
+            get_time_report,
+            push_time_entry,
+            cluster_tickets,
+            get_status_category_mappings,
+            set_status_category_mapping,
+            get_resolved_statuses,
+            set_resolved_statuses,
+            get_priority_mappings,
+            set_priority_mapping,
+            get_linked_tickets,
+            get_ticket_change_log,
+            get_velocity_by_sprint,
+            get_points_by_assignee,
+            get_overdue_tickets,
+            get_tickets_due_soon,
+            list_fix_versions,
+            get_release_readiness,
+            get_ticket_attachments,
+            download_attachment,
+            trigger_gitlab_sync,
+            trigger_zendesk_sync,
+            trigger_servicenow_sync,
+            add_jira_account,
+            list_jira_accounts,
+            remove_jira_account,
+            trigger_account_sync,
+            start_webhook_listener,
+            stop_webhook_listener,
+            start_http_api,
+            stop_http_api,
+            get_ticket_transitions,
+            get_ticket_content,
+            get_pending_local_changes,
+            resolve_local_change,
+            get_outbox,
+            flush_outbox,
+            discard_outbox_item,
+            transition_ticket,
+            assign_ticket,
+            comment_on_ticket,
+            create_issue,
+            tag_tickets,
+            untag_tickets,
+            get_ticket_tags,
+            list_local_tags,
+            watch_ticket,
+            unwatch_ticket,
+            snooze_ticket,
+            unsnooze_ticket,
+            get_snoozed_tickets,
+            generate_standup,
+            generate_person_report,
+            export_person_report,
+            compare_periods,
+            list_metric_goals,
+            create_metric_goal,
+            update_metric_goal,
+            delete_metric_goal,
+            get_goal_progress,
+            simulate_capacity,
+            get_tickets_by_customer,
+            get_affected_version_stats,
+            list_root_causes,
+            create_root_cause,
+            update_root_cause,
+            delete_root_cause,
+            get_ticket_root_cause,
+            set_ticket_root_cause,
+            get_root_cause_breakdown,
+            link_tickets,
+            unlink_tickets,
+            get_open_follow_ups,
+            get_wallboard_payload,
+            start_wallboard,
+            stop_wallboard,
+            get_watchlist,
+            get_watchlist_changes,
+            acknowledge_watchlist_change,
+            save_alert_settings,
+            load_alert_settings,
+            send_slack_digest,
+            send_digest,
+            store_slack_webhook,
+            delete_slack_webhook,
+            save_smtp_settings,
+            load_smtp_settings,
+            save_retention_settings,
+            load_retention_settings,
+            save_categorizer_settings,
+            load_categorizer_settings,
+            store_categorizer_api_key,
+            delete_categorizer_api_key,
+            archive_tickets,
+            get_database_encryption_status,
+            encrypt_database,
+            store_smtp_password,
+            delete_smtp_password,
+            list_alert_rules,
+            create_alert_rule,
+            update_alert_rule,
+            delete_alert_rule,
+            list_outbound_webhooks,
+            create_outbound_webhook,
+            update_outbound_webhook,
+            delete_outbound_webhook,
+            get_webhook_signing_secret,
+            list_automations,
+            create_automation,
+            update_automation,
+            delete_automation,
+            get_automation_log,
+            save_automation_settings,
+            load_automation_settings,
+            list_saved_views,
+            create_saved_view,
+            update_saved_view,
+            delete_saved_view,
+            list_dashboards,
+            get_dashboard,
+            create_dashboard,
+            rename_dashboard,
+            duplicate_dashboard,
+            save_dashboard_layout,
+            delete_dashboard,
+            list_report_snapshots,
+            take_report_snapshot,
+            get_report_snapshot,
+            get_snapshot_diff,
+            delete_report_snapshot,
+            export_dataset,
+            import_dataset,
+            import_csv,
+            detect_duplicates,
+            list_duplicate_candidates,
+            merge_duplicate,
+            dismiss_duplicate,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,12 @@
+use crate::jira::JiraAuthMethod;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JiraAccount {
+    pub id: i64,
+    pub name: String,
+    pub base_url: String,
+    pub email: String,
+    pub auth_method: JiraAuthMethod,
+    pub created_at: String, // ISO 8601
+}
@@ -1,41 +1,224 @@
-use serde::Serialize;
+use super::Ticket;
+use serde::{Deserialize, Serialize};
 
-#[derive(Serialize)]
+/// How resolution durations are measured.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DurationMode {
+    /// Working hours only (9–17, weekdays).
+    #[default]
+    Business,
+    /// Raw elapsed hours, for teams running 24/7 support.
+    Calendar,
+}
+
+#[derive(Clone, Serialize)]
 pub struct AggregationResult {
     pub tickets_by_status: Vec<CountEntry>,
     pub tickets_by_priority: Vec<CountEntry>,
     pub tickets_by_category: Vec<CountEntry>,
+    pub tickets_by_local_tag: Vec<CountEntry>,
+    pub tickets_by_component: Vec<CountEntry>,
     pub tickets_over_time: Vec<TimeSeriesEntry>,
     pub resolution_time_by_priority: Vec<AvgEntry>,
+    pub resolution_time_by_assignee: Vec<AvgEntry>,
+    pub resolution_time_by_component: Vec<AvgEntry>,
+    pub created_resolved_ratio: Vec<RatioEntry>,
+    pub velocity_by_sprint: Vec<VelocityEntry>,
+    pub points_by_assignee: Vec<PointsEntry>,
+    pub tickets_by_customer: Vec<CustomerStats>,
+    pub tickets_by_affected_version: Vec<AffectedVersionStats>,
     pub summary: SummaryStats,
+    /// Values of the metrics registered through `MetricRegistry`.
+    pub custom_metrics: Vec<CustomMetric>,
 }
 
-#[derive(Serialize)]
+/// One plugin metric's value over the filtered tickets.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CustomMetric {
+    pub id: String,
+    pub label: String,
+    pub value: Option<f64>,
+}
+
+#[derive(Clone, Serialize)]
 pub struct CountEntry {
     pub name: String,
     pub count: u32,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct TimeSeriesEntry {
     pub date: String, // "2025-01" (month) or "2025-W03" (week)
     pub created: u32,
     pub resolved: u32,
 }
 
-#[derive(Serialize)]
+/// Tickets created and resolved in the 4 weeks ending on `week`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RatioEntry {
+    pub week: String, // ISO week, e.g. "2025-W03"
+    pub created: u32,
+    pub resolved: u32,
+    /// `created / resolved`; `None` when nothing was resolved.
+    pub ratio: Option<f64>,
+}
+
+/// Story points planned into a sprint and how many of them got resolved.
+/// Tickets without an estimate count toward `tickets` only.
+#[derive(Debug, Clone, Serialize)]
+pub struct VelocityEntry {
+    pub sprint: String,
+    pub committed_points: f64,
+    pub completed_points: f64,
+    pub tickets: u32,
+}
+
+/// Estimated work per assignee, for capacity planning.
+#[derive(Debug, Clone, Serialize)]
+pub struct PointsEntry {
+    pub assignee: String,
+    pub open_points: f64,
+    pub completed_points: f64,
+    pub tickets: u32,
+}
+
+#[derive(Clone, Serialize)]
 pub struct AvgEntry {
     pub name: String,
     pub avg_hours: f64,
     pub median_hours: f64,
+    pub p75_hours: f64,
+    pub p90_hours: f64,
+    pub p95_hours: f64,
     pub count: u32,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct SummaryStats {
     pub total_tickets: u32,
     pub open_tickets: u32,
     pub resolved_tickets: u32,
+    /// Open tickets not updated in `STALE_TICKET_DAYS` days.
+    pub stale_tickets: u32,
+    /// Open tickets with an unresolved "blocks" link pointing at them.
+    pub blocked_tickets: u32,
+    /// Open tickets past their due date.
+    pub overdue_tickets: u32,
     pub avg_resolution_hours: f64,
     pub median_resolution_hours: f64,
+    pub p75_resolution_hours: f64,
+    pub p90_resolution_hours: f64,
+    pub p95_resolution_hours: f64,
+    /// Tickets flagged escalated (see `JiraFieldMapping::escalated`).
+    pub escalated_tickets: u32,
+    /// Share of all tickets that were escalated, 0.0 to 1.0.
+    pub escalation_rate: f64,
+    pub escalated_avg_resolution_hours: f64,
+    pub escalated_median_resolution_hours: f64,
+}
+
+/// Stale open tickets belonging to one assignee, oldest update first.
+#[derive(Serialize)]
+pub struct StaleTicketGroup {
+    pub assignee: String,
+    pub tickets: Vec<Ticket>,
+}
+
+/// One project's row in the project comparison view.
+#[derive(Debug, Serialize)]
+pub struct ProjectStats {
+    pub project_key: String,
+    pub total_tickets: u32,
+    pub open_tickets: u32,
+    pub resolved_tickets: u32,
+    pub avg_resolution_hours: f64,
+    /// Fraction (0–1) of resolved tickets that met their SLA target.
+    pub sla_compliance: Option<f64>,
+}
+
+/// Volume and resolution time for one customer, from the configured
+/// customer field.
+#[derive(Debug, Clone, Serialize)]
+pub struct CustomerStats {
+    pub customer: String,
+    pub total_tickets: u32,
+    pub open_tickets: u32,
+    pub avg_resolution_hours: f64,
+}
+
+/// Tickets reporting a release among their affected versions: how many bugs
+/// it shipped with, how many are still open, and how long they took to fix.
+#[derive(Debug, Clone, Serialize)]
+pub struct AffectedVersionStats {
+    pub version: String,
+    pub total_tickets: u32,
+    pub open_tickets: u32,
+    pub avg_resolution_hours: f64,
+}
+
+/// `EXPLAIN QUERY PLAN` output for one of the dashboard queries.
+#[derive(Debug, Serialize)]
+pub struct QueryPlan {
+    pub name: String,
+    pub sql: String,
+    /// Plan steps, indented by nesting depth.
+    pub plan: Vec<String>,
+}
+
+/// What `aggregate` groups tickets by. Multi-valued fields (labels,
+/// components) count a ticket once under each of its values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dimension {
+    Status,
+    Priority,
+    Assignee,
+    Project,
+    Label,
+    Component,
+    Category,
+    /// Teams of the ticket's assignee (see `teams`).
+    Team,
+    /// The configured customer field.
+    Customer,
+    /// Jira "affects versions".
+    AffectedVersion,
+    /// Jira environment field.
+    Environment,
+    /// The locally set root cause (see `root_causes`).
+    RootCause,
+    /// ISO week (`2025-W02`) the ticket was created in.
+    CreatedWeek,
+    /// ISO week the ticket was resolved in; open tickets fall under "Unresolved".
+    ResolvedWeek,
+    /// A top-level key of the ticket's `custom_fields` object.
+    CustomField(String),
+}
+
+/// The number `aggregate` reports for each group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    Count,
+    AvgResolution,
+    P90Resolution,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AggregateEntry {
+    pub name: String,
+    pub value: f64,
+    /// Tickets behind `value`: all of them for `Count`, resolved ones for
+    /// the resolution metrics.
+    pub count: u32,
+}
+
+/// A two-dimensional `get_pivot` result: `values[r][c]` is the metric for
+/// `rows[r]` and `columns[c]`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PivotTable {
+    pub rows: Vec<String>,
+    pub columns: Vec<String>,
+    pub values: Vec<Vec<Option<f64>>>,
 }
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Built-in post-sync alerts, stored under the `alerts` settings key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertSettings {
+    pub enabled: bool,
+    /// Assignee display name treated as "me" for new-Critical alerts.
+    pub my_name: Option<String>,
+    /// Business-hour resolution targets keyed by priority name.
+    pub sla_targets_hours: BTreeMap<String, f64>,
+    /// Fraction of the target after which an open ticket counts as about to breach.
+    pub sla_warn_ratio: f64,
+    /// Business-hour limits on how long a ticket may stay in a status, keyed
+    /// by status name (e.g. "Waiting for Support").
+    pub status_dwell_limits_hours: BTreeMap<String, f64>,
+    /// Post a weekly digest to the configured Slack webhook.
+    pub slack_weekly_digest: bool,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        AlertSettings {
+            enabled: true,
+            my_name: None,
+            sla_targets_hours: BTreeMap::new(),
+            sla_warn_ratio: 0.8,
+            status_dwell_limits_hours: BTreeMap::new(),
+            slack_weekly_digest: false,
+        }
+    }
+}
+
+/// A notification ready to deliver.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Alert {
+    pub title: String,
+    pub body: String,
+    pub ticket_key: Option<String>,
+}
@@ -0,0 +1,173 @@
+use crate::models::Ticket;
+use serde::{Deserialize, Serialize};
+
+/// Which tickets a rule looks at. Unset fields match anything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TicketMatch {
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    pub assignee: Option<String>,
+    pub project_key: Option<String>,
+    /// Only consider unresolved tickets.
+    pub open_only: bool,
+}
+
+impl Default for TicketMatch {
+    fn default() -> Self {
+        TicketMatch {
+            status: None,
+            priority: None,
+            assignee: None,
+            project_key: None,
+            open_only: true,
+        }
+    }
+}
+
+impl TicketMatch {
+    pub fn matches(&self, ticket: &Ticket) -> bool {
+        let field_matches = |expected: &Option<String>, actual: Option<&str>| match expected {
+            Some(e) => actual.is_some_and(|a| a.eq_ignore_ascii_case(e)),
+            None => true,
+        };
+
+        (!self.open_only || ticket.resolved_at.is_none())
+            && field_matches(&self.status, Some(&ticket.status))
+            && field_matches(&self.priority, Some(&ticket.priority))
+            && field_matches(&self.assignee, ticket.assignee.as_deref())
+            && field_matches(&self.project_key, Some(&ticket.project_key))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Eq,
+}
+
+impl Comparison {
+    pub fn holds(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Gt => value > threshold,
+            Comparison::Gte => value >= threshold,
+            Comparison::Lt => value < threshold,
+            Comparison::Lte => value <= threshold,
+            Comparison::Eq => (value - threshold).abs() < f64::EPSILON,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Gt => ">",
+            Comparison::Gte => ">=",
+            Comparison::Lt => "<",
+            Comparison::Lte => "<=",
+            Comparison::Eq => "=",
+        }
+    }
+
+    /// The comparison written as `symbol` returns.
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        [
+            Comparison::Gt,
+            Comparison::Gte,
+            Comparison::Lt,
+            Comparison::Lte,
+            Comparison::Eq,
+        ]
+        .into_iter()
+        .find(|op| op.symbol() == symbol)
+    }
+}
+
+/// What makes a rule fire.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AlertCondition {
+    /// Number of matching tickets compared with `threshold`,
+    /// e.g. open Critical count > 5.
+    Count {
+        #[serde(default)]
+        tickets: TicketMatch,
+        op: Comparison,
+        threshold: f64,
+    },
+    /// Any matching ticket not updated for more than `days`.
+    Idle {
+        #[serde(default)]
+        tickets: TicketMatch,
+        days: f64,
+    },
+    /// Rolling 4-week created/resolved ratio above `threshold` for each of the
+    /// last `weeks` weeks, i.e. the backlog keeps growing.
+    BacklogGrowth { threshold: f64, weeks: u32 },
+    /// More tickets created today than usual: `z_threshold` standard
+    /// deviations above the daily mean of the previous four weeks.
+    InflowSpike { z_threshold: f64 },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertChannel {
+    Desktop,
+    Slack,
+}
+
+impl AlertChannel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AlertChannel::Desktop => "desktop",
+            AlertChannel::Slack => "slack",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "desktop" => Some(AlertChannel::Desktop),
+            "slack" => Some(AlertChannel::Slack),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertRule {
+    pub id: i64,
+    pub name: String,
+    pub condition: AlertCondition,
+    pub channel: AlertChannel,
+    /// Minimum gap between two firings of the same rule.
+    pub throttle_minutes: u32,
+    pub enabled: bool,
+    pub last_fired_at: Option<String>,
+}
+
+/// Fields the UI supplies when creating or updating a rule.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertRuleInput {
+    pub name: String,
+    pub condition: AlertCondition,
+    #[serde(default = "default_channel")]
+    pub channel: AlertChannel,
+    #[serde(default = "default_throttle_minutes")]
+    pub throttle_minutes: u32,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_channel() -> AlertChannel {
+    AlertChannel::Desktop
+}
+
+fn default_throttle_minutes() -> u32 {
+    60
+}
+
+fn default_enabled() -> bool {
+    true
+}
@@ -0,0 +1,15 @@
+use serde::Serialize;
+
+/// A day on which more tickets were created than the trailing baseline
+/// explains.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InflowAnomaly {
+    /// `YYYY-MM-DD`, UTC.
+    pub date: String,
+    pub created: u32,
+    /// Mean and standard deviation of daily created counts over the
+    /// baseline window before `date`.
+    pub baseline_mean: f64,
+    pub baseline_stddev: f64,
+    pub z_score: f64,
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// Metadata for a file attached to a ticket. The file itself is only
+/// fetched on demand through `download_attachment`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Attachment {
+    /// Source attachment id, e.g. Jira's "10042".
+    pub id: String,
+    pub ticket_key: String,
+    pub filename: String,
+    pub size_bytes: i64,
+    pub mime_type: Option<String>,
+    pub author: Option<String>,
+    pub created_at: Option<String>,
+    /// Authenticated URL of the file contents.
+    pub content_url: String,
+}
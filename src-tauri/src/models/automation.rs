@@ -0,0 +1,108 @@
+use crate::models::{AlertChannel, TicketFilter};
+use serde::{Deserialize, Serialize};
+
+/// What an automation does to each ticket that newly matches its filter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutomationAction {
+    AddTag {
+        tag: String,
+    },
+    Watch,
+    /// One notification per run listing the matched tickets.
+    Notify {
+        #[serde(default = "default_channel")]
+        channel: AlertChannel,
+    },
+    /// Queued in the outbox; needs Jira write-back enabled.
+    Comment {
+        body: String,
+    },
+    /// Queued in the outbox; needs Jira write-back enabled.
+    Transition {
+        transition_id: String,
+    },
+}
+
+fn default_channel() -> AlertChannel {
+    AlertChannel::Desktop
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Automation {
+    pub id: i64,
+    pub name: String,
+    pub filter: TicketFilter,
+    pub actions: Vec<AutomationAction>,
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+/// Fields the UI supplies when creating or updating an automation.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AutomationInput {
+    pub name: String,
+    pub filter: TicketFilter,
+    pub actions: Vec<AutomationAction>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AutomationOutcome {
+    /// Done locally (tag, watch) or handed to the notifier.
+    Applied,
+    /// Waiting in the outbox for the next flush.
+    Queued,
+    /// Not run, e.g. a Jira action while write-back is off.
+    Skipped,
+    Failed,
+}
+
+impl AutomationOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AutomationOutcome::Applied => "applied",
+            AutomationOutcome::Queued => "queued",
+            AutomationOutcome::Skipped => "skipped",
+            AutomationOutcome::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "applied" => Some(AutomationOutcome::Applied),
+            "queued" => Some(AutomationOutcome::Queued),
+            "skipped" => Some(AutomationOutcome::Skipped),
+            "failed" => Some(AutomationOutcome::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// One action an automation took (or tried to take) on one ticket.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AutomationLogEntry {
+    pub id: i64,
+    pub automation_id: i64,
+    /// Kept as it was at the time, so entries outlive renames and deletes.
+    pub automation_name: String,
+    pub ticket_key: String,
+    pub action: AutomationAction,
+    pub outcome: AutomationOutcome,
+    pub detail: Option<String>,
+    pub ran_at: String,
+}
+
+/// Automation settings, stored under the `automations` settings key.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutomationSettings {
+    /// Let automations comment on and transition tickets in Jira.
+    pub write_back: bool,
+}
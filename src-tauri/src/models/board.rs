@@ -0,0 +1,19 @@
+use super::{StatusCategory, Ticket};
+use serde::{Deserialize, Serialize};
+
+/// The most tickets a kanban column should hold.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WipLimit {
+    pub category: StatusCategory,
+    pub max_tickets: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardColumn {
+    pub category: StatusCategory,
+    /// Personal queue order first, then highest priority first.
+    pub tickets: Vec<Ticket>,
+    pub wip_limit: Option<u32>,
+    /// More tickets than `wip_limit` allows.
+    pub over_wip_limit: bool,
+}
@@ -0,0 +1,31 @@
+use serde::Serialize;
+
+/// A local plan to work on a ticket on a given day.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ScheduledWork {
+    pub id: i64,
+    pub ticket_key: String,
+    pub date: String, // YYYY-MM-DD
+    pub note: Option<String>,
+    pub created_at: String, // ISO 8601
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum CalendarEntryKind {
+    SprintStart,
+    Due,
+    Scheduled,
+    SprintEnd,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CalendarEntry {
+    pub date: String, // YYYY-MM-DD
+    pub kind: CalendarEntryKind,
+    /// `KEY: summary` for ticket entries, the sprint name for sprint ones.
+    pub title: String,
+    pub ticket_key: Option<String>,
+    /// Set on `scheduled` entries, for editing or removing them.
+    pub scheduled_work_id: Option<i64>,
+}
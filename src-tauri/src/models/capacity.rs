@@ -0,0 +1,38 @@
+use serde::Serialize;
+
+/// Projected open tickets at the end of a week.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BurndownPoint {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub open_tickets: f64,
+}
+
+/// The backlog's course with `extra_people` more (or, when negative, fewer)
+/// people resolving tickets.
+#[derive(Debug, Clone, Serialize)]
+pub struct CapacityScenario {
+    pub extra_people: i32,
+    pub people: u32,
+    pub throughput_per_week: f64,
+    /// Throughput minus inflow; the backlog only shrinks when positive.
+    pub net_per_week: f64,
+    pub burndown: Vec<BurndownPoint>,
+    /// Projected date (YYYY-MM-DD) the backlog is cleared; `None` when it
+    /// never shrinks.
+    pub clears_by: Option<String>,
+}
+
+/// Backlog burndown under a range of staffing levels, from recent history.
+#[derive(Debug, Clone, Serialize)]
+pub struct CapacitySimulation {
+    pub open_tickets: u32,
+    /// People who resolved at least one ticket in the history window.
+    pub active_people: u32,
+    pub throughput_per_person_per_week: f64,
+    pub inflow_per_week: f64,
+    /// Weeks of history the rates are averaged over.
+    pub history_weeks: u32,
+    /// One per staffing level from today's team to `extra_people`, in order.
+    pub scenarios: Vec<CapacityScenario>,
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// Which backend assigns categories during sync and import.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CategorizerKind {
+    /// Only the category rules sent from the frontend.
+    #[default]
+    Rules,
+    /// Rules first; tickets no rule matches are classified by comparing
+    /// their summary's embedding with each category's.
+    Embedding,
+}
+
+/// Categorization backend settings. Off (rules only) by default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategorizerSettings {
+    #[serde(default)]
+    pub backend: CategorizerKind,
+    /// OpenAI-compatible embeddings endpoint, e.g.
+    /// `http://localhost:11434/v1/embeddings` for a local Ollama model.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Cosine similarity below which the model's best guess is discarded.
+    #[serde(default = "default_min_confidence")]
+    pub min_confidence: f64,
+}
+
+fn default_min_confidence() -> f64 {
+    0.5
+}
+
+impl Default for CategorizerSettings {
+    fn default() -> Self {
+        Self {
+            backend: CategorizerKind::default(),
+            endpoint: None,
+            model: None,
+            min_confidence: default_min_confidence(),
+        }
+    }
+}
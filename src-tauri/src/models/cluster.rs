@@ -0,0 +1,14 @@
+use super::Ticket;
+use serde::Serialize;
+
+/// A group of tickets with similar summaries.
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketCluster {
+    /// The cluster's most common summary terms, joined for display.
+    pub label: String,
+    pub terms: Vec<String>,
+    pub size: u32,
+    /// The most central tickets, most central first.
+    pub representatives: Vec<Ticket>,
+    pub ticket_keys: Vec<String>,
+}
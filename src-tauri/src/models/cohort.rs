@@ -0,0 +1,19 @@
+use serde::Serialize;
+
+/// Tickets created in one month and how quickly they were resolved.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CohortRow {
+    /// Creation month, `YYYY-MM`.
+    pub cohort: String,
+    pub tickets: u32,
+    /// Fraction resolved within each of `CohortMatrix::window_days`, among
+    /// the tickets old enough for that window; `None` when none are.
+    pub resolved_within: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CohortMatrix {
+    pub window_days: Vec<u32>,
+    /// Oldest cohort first.
+    pub cohorts: Vec<CohortRow>,
+}
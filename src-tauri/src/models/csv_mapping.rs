@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+/// Which CSV columns (by header name) feed which ticket fields. Optional
+/// columns fall back to sensible defaults; unmapped columns are kept in the
+/// ticket's `custom_fields`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CsvMapping {
+    pub key: String,
+    pub summary: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub issue_type: Option<String>,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub reporter: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub resolved_at: Option<String>,
+    #[serde(default)]
+    pub labels: Option<String>,
+    #[serde(default)]
+    pub project_key: Option<String>,
+    #[serde(default)]
+    pub story_points: Option<String>,
+    #[serde(default)]
+    pub sprint: Option<String>,
+    /// Project key for every row when there is no project column.
+    #[serde(default)]
+    pub default_project: Option<String>,
+    /// Prepended to every key so imported keys can't collide with synced ones.
+    #[serde(default)]
+    pub key_prefix: Option<String>,
+    /// Value stored in `tickets.source`.
+    #[serde(default = "default_source")]
+    pub source: String,
+    /// chrono format for all date columns; detected per column when unset.
+    #[serde(default)]
+    pub date_format: Option<String>,
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+}
+
+fn default_source() -> String {
+    "csv".to_string()
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+/// A CSV row that couldn't be imported. `line` is 1-based and counts the header.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CsvRowError {
+    pub line: u64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CsvImportSummary {
+    pub imported: usize,
+    pub skipped: Vec<CsvRowError>,
+}
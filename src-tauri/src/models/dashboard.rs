@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+/// A named dashboard. `layout` is the frontend's widget layout, stored as-is.
+#[derive(Debug, Clone, Serialize)]
+pub struct Dashboard {
+    pub id: i64,
+    pub name: String,
+    pub layout: serde_json::Value,
+    pub created_at: String,
+    pub updated_at: String,
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Rows of one table, each keyed by column name.
+pub type TableRows = Vec<serde_json::Map<String, serde_json::Value>>;
+
+/// Portable dump of the local database and settings. Secrets stay in the OS
+/// keychain and are never included.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Dataset {
+    /// Layout of this file; bumped on incompatible changes.
+    pub format_version: u32,
+    /// Database schema the rows were exported from.
+    pub schema_version: i32,
+    pub exported_at: String,
+    pub tables: BTreeMap<String, TableRows>,
+    #[serde(default)]
+    pub settings: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Row counts per table written or read by an export/import.
+#[derive(Debug, Serialize)]
+pub struct DatasetSummary {
+    pub tables: BTreeMap<String, usize>,
+    pub settings: Vec<String>,
+}
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+/// How `copy_ticket_link` formats a link to a ticket.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkFormat {
+    /// The bare browse URL.
+    #[default]
+    Url,
+    /// `[KEY: summary](url)`
+    Markdown,
+    /// `<url|KEY: summary>`, Slack's mrkdwn link syntax.
+    Slack,
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Review state of a duplicate candidate.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateStatus {
+    Pending,
+    Merged,
+    Dismissed,
+}
+
+impl DuplicateStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DuplicateStatus::Pending => "pending",
+            DuplicateStatus::Merged => "merged",
+            DuplicateStatus::Dismissed => "dismissed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(DuplicateStatus::Pending),
+            "merged" => Some(DuplicateStatus::Merged),
+            "dismissed" => Some(DuplicateStatus::Dismissed),
+            _ => None,
+        }
+    }
+}
+
+/// Two tickets from different sources that look like the same work item.
+/// Summaries and sources are `None` once the ticket is no longer stored.
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateCandidate {
+    pub id: i64,
+    pub ticket_a: String,
+    pub summary_a: Option<String>,
+    pub source_a: Option<String>,
+    pub ticket_b: String,
+    pub summary_b: Option<String>,
+    pub source_b: Option<String>,
+    /// 0.0–1.0 similarity of the normalized summaries.
+    pub similarity: f64,
+    pub status: DuplicateStatus,
+    pub detected_at: String,
+    pub resolved_at: Option<String>,
+}
@@ -0,0 +1,217 @@
+use rusqlite::types::Value;
+use serde::{Deserialize, Serialize};
+
+/// Scope applied to ticket queries and aggregations. Every field is optional;
+/// an empty filter matches all tickets. Conditions on different fields are
+/// combined with AND; a list matches a ticket holding any of its values.
+/// Build one from `default()` with struct update syntax or the builder
+/// methods, and turn it into SQL with `to_sql_where`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TicketFilter {
+    /// Restrict to one Jira account (`jira_accounts.id`); `None` means all accounts.
+    #[serde(default)]
+    pub account_id: Option<i64>,
+    /// Local tags; a ticket matches if it carries any of them.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Also count tickets moved to `tickets_archive` by the retention policy.
+    #[serde(default)]
+    pub include_archived: bool,
+    /// Also match tickets merged into another as duplicates, for rewrites
+    /// that must reach every stored ticket. Never set by the UI.
+    #[serde(skip)]
+    pub include_duplicates: bool,
+    /// Leave out tickets snoozed with `snooze_ticket` until a later time.
+    /// Only the ticket list views set this; counts and rules include them.
+    #[serde(default)]
+    pub hide_snoozed: bool,
+    #[serde(default)]
+    pub statuses: Vec<String>,
+    #[serde(default)]
+    pub priorities: Vec<String>,
+    #[serde(default)]
+    pub assignees: Vec<String>,
+    #[serde(default)]
+    pub project_keys: Vec<String>,
+    /// Team names; a ticket matches if its assignee is on any of them.
+    #[serde(default)]
+    pub teams: Vec<String>,
+    #[serde(default)]
+    pub created: Option<DateRange>,
+    #[serde(default)]
+    pub resolved: Option<DateRange>,
+    /// Case-insensitive substring of the key or summary.
+    #[serde(default)]
+    pub query: Option<String>,
+}
+
+/// Bounds on an ISO 8601 timestamp column: `from` is inclusive, `to`
+/// exclusive. Either may be a bare date (`YYYY-MM-DD`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateRange {
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(default)]
+    pub to: Option<String>,
+}
+
+impl DateRange {
+    /// Everything from `from` onwards.
+    pub fn since(from: impl Into<String>) -> Self {
+        Self {
+            from: Some(from.into()),
+            to: None,
+        }
+    }
+}
+
+/// `column IN (?, ...)` for a non-empty `values`.
+fn push_in(conditions: &mut Vec<String>, params: &mut Vec<Value>, column: &str, values: &[String]) {
+    if values.is_empty() {
+        return;
+    }
+    let placeholders = vec!["?"; values.len()].join(", ");
+    conditions.push(format!("{} IN ({})", column, placeholders));
+    params.extend(values.iter().cloned().map(Value::Text));
+}
+
+fn push_range(
+    conditions: &mut Vec<String>,
+    params: &mut Vec<Value>,
+    column: &str,
+    range: Option<&DateRange>,
+) {
+    let Some(range) = range else {
+        return;
+    };
+    if let Some(from) = &range.from {
+        conditions.push(format!("{} >= ?", column));
+        params.push(Value::Text(from.clone()));
+    }
+    if let Some(to) = &range.to {
+        conditions.push(format!("{} < ?", column));
+        params.push(Value::Text(to.clone()));
+    }
+}
+
+impl TicketFilter {
+    pub fn with_projects(
+        mut self,
+        project_keys: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.project_keys = project_keys.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_duplicates(mut self) -> Self {
+        self.include_duplicates = true;
+        self
+    }
+
+    /// Hides snoozed tickets, for the lists a snooze is meant to declutter.
+    pub fn without_snoozed(mut self) -> Self {
+        self.hide_snoozed = true;
+        self
+    }
+
+    pub fn created_in(mut self, range: DateRange) -> Self {
+        self.created = Some(range);
+        self
+    }
+
+    pub fn resolved_in(mut self, range: DateRange) -> Self {
+        self.resolved = Some(range);
+        self
+    }
+
+    /// SQL predicate over the `tickets` columns (with anonymous `?`
+    /// placeholders) and the values to bind, in order. Always a valid
+    /// expression so callers can splice it after `WHERE`. Tickets merged
+    /// into another as duplicates only match with `include_duplicates`.
+    pub fn to_sql_where(&self) -> (String, Vec<Value>) {
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+
+        if !self.include_duplicates {
+            conditions.push("duplicate_of IS NULL".to_string());
+        }
+
+        if let Some(account_id) = self.account_id {
+            conditions.push("account_id = ?".to_string());
+            params.push(Value::Integer(account_id));
+        }
+
+        if self.hide_snoozed {
+            // Same fixed-width UTC format `until` is stored in
+            conditions.push(
+                "jira_key NOT IN (SELECT ticket_key FROM ticket_snoozes \
+                 WHERE until > strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))"
+                    .to_string(),
+            );
+        }
+
+        if !self.tags.is_empty() {
+            let placeholders = vec!["?"; self.tags.len()].join(", ");
+            conditions.push(format!(
+                "jira_key IN (SELECT ticket_key FROM local_tags WHERE tag IN ({}))",
+                placeholders
+            ));
+            params.extend(self.tags.iter().cloned().map(Value::Text));
+        }
+
+        push_in(&mut conditions, &mut params, "status", &self.statuses);
+        push_in(&mut conditions, &mut params, "priority", &self.priorities);
+        push_in(&mut conditions, &mut params, "assignee", &self.assignees);
+        push_in(
+            &mut conditions,
+            &mut params,
+            "project_key",
+            &self.project_keys,
+        );
+        if !self.teams.is_empty() {
+            let placeholders = vec!["?"; self.teams.len()].join(", ");
+            conditions.push(format!(
+                "assignee IN (SELECT m.assignee FROM team_members m \
+                 JOIN teams t ON t.id = m.team_id WHERE t.name IN ({}))",
+                placeholders
+            ));
+            params.extend(self.teams.iter().cloned().map(Value::Text));
+        }
+        push_range(
+            &mut conditions,
+            &mut params,
+            "created_at",
+            self.created.as_ref(),
+        );
+        push_range(
+            &mut conditions,
+            &mut params,
+            "resolved_at",
+            self.resolved.as_ref(),
+        );
+
+        if let Some(query) = self
+            .query
+            .as_deref()
+            .map(str::trim)
+            .filter(|q| !q.is_empty())
+        {
+            let pattern = format!(
+                "%{}%",
+                query
+                    .replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_")
+            );
+            conditions
+                .push("(jira_key LIKE ? ESCAPE '\\' OR summary LIKE ? ESCAPE '\\')".to_string());
+            params.push(Value::Text(pattern.clone()));
+            params.push(Value::Text(pattern));
+        }
+
+        if conditions.is_empty() {
+            return ("1".to_string(), params);
+        }
+        (conditions.join(" AND "), params)
+    }
+}
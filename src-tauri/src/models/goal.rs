@@ -0,0 +1,103 @@
+use crate::models::{Comparison, StatsHistoryEntry, SummaryStats};
+use serde::{Deserialize, Serialize};
+
+/// A summary stat a goal can target; each is recorded in the stats history,
+/// which the trend is fitted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalMetric {
+    OpenTickets,
+    StaleTickets,
+    ResolvedTickets,
+    AvgResolutionHours,
+    MedianResolutionHours,
+}
+
+impl GoalMetric {
+    pub const ALL: [GoalMetric; 5] = [
+        GoalMetric::OpenTickets,
+        GoalMetric::StaleTickets,
+        GoalMetric::ResolvedTickets,
+        GoalMetric::AvgResolutionHours,
+        GoalMetric::MedianResolutionHours,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GoalMetric::OpenTickets => "open_tickets",
+            GoalMetric::StaleTickets => "stale_tickets",
+            GoalMetric::ResolvedTickets => "resolved_tickets",
+            GoalMetric::AvgResolutionHours => "avg_resolution_hours",
+            GoalMetric::MedianResolutionHours => "median_resolution_hours",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|metric| metric.as_str() == value)
+    }
+
+    /// This metric's value in `stats`.
+    pub fn value(self, stats: &SummaryStats) -> f64 {
+        match self {
+            GoalMetric::OpenTickets => f64::from(stats.open_tickets),
+            GoalMetric::StaleTickets => f64::from(stats.stale_tickets),
+            GoalMetric::ResolvedTickets => f64::from(stats.resolved_tickets),
+            GoalMetric::AvgResolutionHours => stats.avg_resolution_hours,
+            GoalMetric::MedianResolutionHours => stats.median_resolution_hours,
+        }
+    }
+
+    /// This metric's value in a stats history entry.
+    pub fn recorded(self, entry: &StatsHistoryEntry) -> f64 {
+        match self {
+            GoalMetric::OpenTickets => f64::from(entry.open_tickets),
+            GoalMetric::StaleTickets => f64::from(entry.stale_tickets),
+            GoalMetric::ResolvedTickets => f64::from(entry.resolved_tickets),
+            GoalMetric::AvgResolutionHours => entry.avg_resolution_hours,
+            GoalMetric::MedianResolutionHours => entry.median_resolution_hours,
+        }
+    }
+}
+
+/// A target for a metric to reach by a deadline, e.g. median resolution
+/// `<` 8 hours by 2025-09-30.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricGoal {
+    pub id: i64,
+    pub metric: GoalMetric,
+    pub op: Comparison,
+    pub target: f64,
+    /// `YYYY-MM-DD`
+    pub deadline: String,
+    pub created_at: String,
+}
+
+/// Fields the UI supplies when creating or updating a goal.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetricGoalInput {
+    pub metric: GoalMetric,
+    pub op: Comparison,
+    pub target: f64,
+    pub deadline: String,
+}
+
+/// Where a goal stands now and where its trend is heading.
+#[derive(Debug, Clone, Serialize)]
+pub struct GoalProgress {
+    pub goal: MetricGoal,
+    pub current: f64,
+    /// `current` already satisfies the goal.
+    pub met: bool,
+    /// Change per week over the recent stats history; `None` with fewer
+    /// than two days of history.
+    pub trend_per_week: Option<f64>,
+    /// `current` extended along the trend to the deadline.
+    pub projected: Option<f64>,
+    /// The projection (or, without a trend, the current value) satisfies
+    /// the goal. After the deadline this is just `met`.
+    pub on_track: bool,
+    /// Days until the deadline; negative once it has passed.
+    pub days_left: i64,
+}
@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+/// Where the local HTTP API is listening and the token requests must carry.
+#[derive(Debug, Clone, Serialize)]
+pub struct HttpApiStatus {
+    pub port: u16,
+    pub token: String,
+}
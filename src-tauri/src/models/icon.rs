@@ -0,0 +1,23 @@
+use serde::Serialize;
+
+/// An avatar or issue-type icon kept on disk for offline use.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedIcon {
+    pub url: String,
+    /// Name of the image file inside the icon cache directory.
+    pub file_name: String,
+    pub content_type: String,
+    pub etag: Option<String>,
+    pub fetched_at: String,
+}
+
+/// An icon ready for an `<img>` tag, without the webview ever holding
+/// Jira credentials.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct IconImage {
+    /// `data:` URL of the image.
+    pub data_url: String,
+    /// True when Jira couldn't be reached and the cached copy was served
+    /// without revalidating.
+    pub stale: bool,
+}
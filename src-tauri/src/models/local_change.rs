@@ -0,0 +1,72 @@
+use super::Ticket;
+use serde::{Deserialize, Serialize};
+
+/// How a sync treats a field edited in the app that Jira hasn't confirmed yet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// The synced value replaces the local edit.
+    #[default]
+    ServerWins,
+    /// The local edit is kept until Jira reports the same value or it is
+    /// discarded.
+    LocalWins,
+    /// Like `LocalWins`, but a field Jira changed since the edit is flagged
+    /// for the user to resolve.
+    Prompt,
+}
+
+/// Ticket fields that can be edited locally ahead of Jira.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalField {
+    /// Status, together with the resolution date it implies.
+    Status,
+}
+
+impl LocalField {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LocalField::Status => "status",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "status" => Some(LocalField::Status),
+            _ => None,
+        }
+    }
+
+    pub fn value(self, ticket: &Ticket) -> Option<&str> {
+        match self {
+            LocalField::Status => Some(ticket.status.as_str()),
+        }
+    }
+
+    /// Copies this field from `from` onto `to`.
+    pub fn apply(self, from: &Ticket, to: &mut Ticket) {
+        match self {
+            LocalField::Status => {
+                to.status = from.status.clone();
+                to.status_category = from.status_category;
+                to.resolved_at = from.resolved_at.clone();
+            }
+        }
+    }
+}
+
+/// A local edit not yet confirmed by Jira. A ticket is dirty while it has any.
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalChange {
+    pub ticket_key: String,
+    pub field: LocalField,
+    /// Value currently stored locally.
+    pub local_value: Option<String>,
+    /// Jira's value when the edit was made.
+    pub base_value: Option<String>,
+    /// Newer Jira value that disagrees with the edit; only set under
+    /// `ConflictPolicy::Prompt`.
+    pub conflicting_value: Option<String>,
+    pub changed_at: String,
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// How a locally linked ticket relates to the one it points at, read
+/// source-first: "OPS-7 follow-up-of INC-3".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LocalLinkKind {
+    /// A task raised in the post-incident review of the target.
+    FollowUpOf,
+    /// The source was caused by the target, e.g. an incident by a change.
+    CausedBy,
+    RelatesTo,
+}
+
+impl LocalLinkKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LocalLinkKind::FollowUpOf => "follow-up-of",
+            LocalLinkKind::CausedBy => "caused-by",
+            LocalLinkKind::RelatesTo => "relates-to",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            LocalLinkKind::FollowUpOf,
+            LocalLinkKind::CausedBy,
+            LocalLinkKind::RelatesTo,
+        ]
+        .into_iter()
+        .find(|kind| kind.as_str() == value)
+    }
+
+    /// The relation as seen from the source ticket.
+    pub fn outward_label(self) -> &'static str {
+        match self {
+            LocalLinkKind::FollowUpOf => "is a follow-up of",
+            LocalLinkKind::CausedBy => "was caused by",
+            LocalLinkKind::RelatesTo => "relates to",
+        }
+    }
+
+    /// The relation as seen from the target ticket.
+    pub fn inward_label(self) -> &'static str {
+        match self {
+            LocalLinkKind::FollowUpOf => "has follow-up",
+            LocalLinkKind::CausedBy => "caused",
+            LocalLinkKind::RelatesTo => "relates to",
+        }
+    }
+}
+
+/// An open follow-up task of an incident.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FollowUp {
+    pub jira_key: String,
+    pub summary: String,
+    pub status: String,
+    pub assignee: Option<String>,
+}
+
+/// An incident whose follow-ups aren't all done yet.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct IncidentFollowUps {
+    pub incident_key: String,
+    /// `None` when the incident isn't stored locally.
+    pub incident_summary: Option<String>,
+    pub incident_resolved: bool,
+    /// Follow-ups linked to the incident, done or not.
+    pub total_follow_ups: u32,
+    pub open_follow_ups: Vec<FollowUp>,
+}
@@ -1,5 +1,127 @@
+pub mod account;
 pub mod aggregation;
+pub mod alert;
+pub mod alert_rule;
+pub mod anomaly;
+pub mod attachment;
+pub mod automation;
+pub mod board;
+pub mod calendar;
+pub mod capacity;
+pub mod categorizer;
+pub mod cluster;
+pub mod cohort;
+pub mod csv_mapping;
+pub mod dashboard;
+pub mod dataset;
+pub mod deep_link;
+pub mod duplicate;
+pub mod filter;
+pub mod goal;
+pub mod http_api;
+pub mod icon;
+pub mod local_change;
+pub mod local_link;
+pub mod network;
+pub mod new_issue;
+pub mod outbound_webhook;
+pub mod outbox;
+pub mod person_report;
+pub mod priority;
+pub mod query_console;
+pub mod quick_search;
+pub mod release;
+pub mod retention;
+pub mod root_cause;
+pub mod rotation;
+pub mod saved_view;
+pub mod similar;
+pub mod smtp;
+pub mod snapshot;
+pub mod snooze;
+pub mod sprint;
+pub mod standup;
+pub mod stats_history;
+pub mod status_category;
+pub mod status_dwell;
+pub mod support_calendar;
+pub mod sync_preview;
+pub mod sync_run;
+pub mod team;
 pub mod ticket;
+pub mod ticket_change;
+pub mod ticket_link;
+pub mod ticket_note;
+pub mod ticket_view;
+pub mod time_entry;
+pub mod transition;
+pub mod trending;
+pub mod user;
+pub mod wallboard;
+pub mod watchlist;
+pub mod webhook;
+pub mod work_schedule;
 
+pub use account::*;
 pub use aggregation::*;
+pub use alert::*;
+pub use alert_rule::*;
+pub use anomaly::*;
+pub use attachment::*;
+pub use automation::*;
+pub use board::*;
+pub use calendar::*;
+pub use capacity::*;
+pub use categorizer::*;
+pub use cluster::*;
+pub use cohort::*;
+pub use csv_mapping::*;
+pub use dashboard::*;
+pub use dataset::*;
+pub use deep_link::*;
+pub use duplicate::*;
+pub use filter::*;
+pub use goal::*;
+pub use http_api::*;
+pub use icon::*;
+pub use local_change::*;
+pub use local_link::*;
+pub use network::*;
+pub use new_issue::*;
+pub use outbound_webhook::*;
+pub use outbox::*;
+pub use person_report::*;
+pub use priority::*;
+pub use query_console::*;
+pub use quick_search::*;
+pub use release::*;
+pub use retention::*;
+pub use root_cause::*;
+pub use rotation::*;
+pub use saved_view::*;
+pub use similar::*;
+pub use smtp::*;
+pub use snapshot::*;
+pub use snooze::*;
+pub use sprint::*;
+pub use standup::*;
+pub use stats_history::*;
+pub use status_category::*;
+pub use status_dwell::*;
+pub use support_calendar::*;
+pub use sync_preview::*;
+pub use sync_run::*;
+pub use team::*;
 pub use ticket::*;
+pub use ticket_change::*;
+pub use ticket_link::*;
+pub use ticket_note::*;
+pub use ticket_view::*;
+pub use time_entry::*;
+pub use transition::*;
+pub use trending::*;
+pub use user::*;
+pub use wallboard::*;
+pub use watchlist::*;
+pub use webhook::*;
+pub use work_schedule::*;
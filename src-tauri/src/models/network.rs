@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Outbound HTTP settings for corporate networks.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkSettings {
+    /// Proxy for all HTTP(S) traffic, e.g. `http://proxy.corp:8080`.
+    #[serde(default)]
+    pub proxy_url: Option<String>,
+    /// PEM bundle trusted in addition to the built-in roots (for MITM proxies).
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    /// Development only: skips TLS certificate verification entirely.
+    #[serde(default)]
+    pub accept_invalid_certs: bool,
+}
@@ -0,0 +1,26 @@
+use crate::errors::JiraValidationErrors;
+use crate::models::Ticket;
+use serde::{Deserialize, Serialize};
+
+/// Fields for an issue created from the app.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewIssue {
+    pub project_key: String,
+    pub issue_type: String,
+    pub summary: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// Result of `create_issue`. Field-validation failures are returned as data
+/// so the form can highlight the offending fields.
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CreateIssueOutcome {
+    Created { ticket: Option<Ticket> },
+    Rejected { errors: JiraValidationErrors },
+}
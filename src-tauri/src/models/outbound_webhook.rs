@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// What an outbound webhook can subscribe to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// A built-in alert or an alert rule fired after a sync.
+    AlertFired,
+    /// A sync finished, successfully or not.
+    SyncCompleted,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct OutboundWebhook {
+    pub id: i64,
+    pub name: String,
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+/// Fields the UI supplies when creating or updating a webhook.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OutboundWebhookInput {
+    pub name: String,
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// A Jira write-back queued while Jira couldn't be reached.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutboxAction {
+    Comment {
+        body: String,
+    },
+    Transition {
+        transition_id: String,
+    },
+    /// `None` unassigns.
+    Assign {
+        assignee: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutboxStatus {
+    /// Waiting to be sent, possibly after a backoff.
+    Pending,
+    /// Jira rejected it; kept for the user to review or discard.
+    Failed,
+}
+
+impl OutboxStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OutboxStatus::Pending => "pending",
+            OutboxStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(OutboxStatus::Pending),
+            "failed" => Some(OutboxStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboxItem {
+    pub id: i64,
+    pub ticket_key: String,
+    pub action: OutboxAction,
+    pub status: OutboxStatus,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub last_attempt_at: Option<String>,
+    /// Earliest time an automatic flush retries this item.
+    pub next_attempt_at: Option<String>,
+}
+
+/// Result of one outbox flush.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct OutboxFlushSummary {
+    pub sent: u32,
+    pub failed: u32,
+    /// Still queued, because Jira is unreachable or the item is backing off.
+    pub pending: u32,
+}
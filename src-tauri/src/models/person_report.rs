@@ -0,0 +1,44 @@
+use crate::models::CountEntry;
+use serde::{Deserialize, Serialize};
+
+/// A ticket listed in a person report.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ReportTicket {
+    pub jira_key: String,
+    pub summary: String,
+    pub priority: String,
+    pub status: String,
+    /// Days since creation, for open tickets.
+    pub age_days: Option<i64>,
+    /// Hours to resolution, for resolved tickets.
+    pub resolution_hours: Option<f64>,
+}
+
+/// One team member's work over `from`..`to`, with the Markdown ready to share.
+#[derive(Debug, Clone, Serialize)]
+pub struct PersonReport {
+    pub assignee: String,
+    pub from: String,
+    pub to: String,
+    pub resolved_count: u32,
+    pub resolved_per_week: f64,
+    pub median_resolution_hours: f64,
+    pub avg_resolution_hours: f64,
+    pub open_tickets: u32,
+    /// Open tickets by priority, highest first.
+    pub load_by_priority: Vec<CountEntry>,
+    /// Oldest open tickets, oldest first.
+    pub aging: Vec<ReportTicket>,
+    /// Highest-priority resolutions in the range, slowest first within a priority.
+    pub notable: Vec<ReportTicket>,
+    pub markdown: String,
+}
+
+/// File format `export_person_report` writes.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    Pdf,
+}
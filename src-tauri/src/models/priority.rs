@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// Canonical priority scale that every source's priority names map onto,
+/// highest first.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PriorityLevel {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl PriorityLevel {
+    pub const ALL: [PriorityLevel; 4] = [
+        PriorityLevel::Critical,
+        PriorityLevel::High,
+        PriorityLevel::Medium,
+        PriorityLevel::Low,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PriorityLevel::Critical => "Critical",
+            PriorityLevel::High => "High",
+            PriorityLevel::Medium => "Medium",
+            PriorityLevel::Low => "Low",
+        }
+    }
+
+    /// The level named `value`, ignoring case.
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
+/// Maps a source priority name onto the canonical scale, in one project or
+/// (with no `project_key`) everywhere.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PriorityMapping {
+    pub priority: String,
+    #[serde(default)]
+    pub project_key: Option<String>,
+    pub level: PriorityLevel,
+}
@@ -0,0 +1,29 @@
+use serde::Serialize;
+
+/// SQLite storage class of a result column, taken from its first non-null
+/// value; `Null` when every value is null.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum QueryColumnType {
+    Integer,
+    Real,
+    Text,
+    Blob,
+    Null,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct QueryColumn {
+    pub name: String,
+    pub column_type: QueryColumnType,
+}
+
+/// Rows returned by `run_readonly_query`, each value in column order.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryResult {
+    pub columns: Vec<QueryColumn>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+    /// Whether rows past the limit were left out.
+    pub truncated: bool,
+    pub elapsed_ms: u64,
+}
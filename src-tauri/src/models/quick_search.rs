@@ -0,0 +1,22 @@
+use super::Ticket;
+use serde::Serialize;
+
+/// Why a ticket is in the quick-switcher results.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuickSearchKind {
+    /// The search text is the ticket's key.
+    Key,
+    /// The ticket's key, summary or labels contain the search words.
+    Text,
+    /// One of the user's notes on the ticket contains the search words.
+    Note,
+    /// Recently updated, listed while there is no search text.
+    Recent,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QuickSearchResult {
+    pub kind: QuickSearchKind,
+    pub ticket: Ticket,
+}
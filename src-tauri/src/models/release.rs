@@ -0,0 +1,19 @@
+use super::Ticket;
+use serde::Serialize;
+
+/// How close a Jira fix version is to done.
+#[derive(Debug, Serialize)]
+pub struct ReleaseReadiness {
+    pub version: String,
+    pub total_tickets: u32,
+    pub open_tickets: u32,
+    pub done_tickets: u32,
+    /// Open tickets in the release with Blocker priority or blocked by an
+    /// unresolved ticket.
+    pub blockers: Vec<Ticket>,
+    /// Tickets resolved per week across the release's projects, recently.
+    pub throughput_per_week: f64,
+    /// Projected date (YYYY-MM-DD) the open tickets are done at the current
+    /// throughput; `None` once nothing is open or nothing is being resolved.
+    pub estimated_completion: Option<String>,
+}
@@ -0,0 +1,28 @@
+use chrono::{Months, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// How long resolved tickets stay in the live `tickets` table.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetentionSettings {
+    /// Months after resolution before a ticket is archived; `None` keeps
+    /// every ticket live.
+    #[serde(default)]
+    pub resolved_retention_months: Option<u32>,
+}
+
+impl RetentionSettings {
+    /// Tickets resolved before this are due for archiving, if retention is on.
+    pub fn archive_cutoff(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.resolved_retention_months
+            .filter(|&months| months > 0)
+            .and_then(|months| now.checked_sub_months(Months::new(months)))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveSummary {
+    /// Tickets moved by this run.
+    pub archived: u32,
+    /// Tickets in the archive afterwards.
+    pub total_archived: u32,
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// One entry of the locally managed root-cause taxonomy.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RootCause {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Fields the UI supplies when creating or updating a root cause.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RootCauseInput {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Resolved tickets attributed to one root cause.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RootCauseEntry {
+    pub root_cause: String,
+    pub count: u32,
+    /// Fraction (0–1) of the classified tickets.
+    pub share: f64,
+    pub avg_resolution_hours: f64,
+}
+
+/// Root causes behind `filter`'s resolved tickets, for post-incident reviews.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RootCauseBreakdown {
+    pub classified: u32,
+    /// Resolved tickets without a root cause yet.
+    pub unclassified: u32,
+    /// Most common first.
+    pub causes: Vec<RootCauseEntry>,
+}
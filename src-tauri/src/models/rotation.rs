@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// A stretch of on-call / triage duty. `start_at` and `end_at` are RFC 3339
+/// timestamps; the window includes its start but not its end.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Rotation {
+    pub id: i64,
+    pub person: String,
+    pub start_at: String,
+    pub end_at: String,
+}
+
+/// Fields the UI supplies when adding a rotation.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RotationInput {
+    pub person: String,
+    pub start_at: String,
+    pub end_at: String,
+}
+
+/// Load on one rotation: the tickets created during its window.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RotationStats {
+    pub rotation: Rotation,
+    pub created: u32,
+    /// Of the tickets created, how many are resolved and still open.
+    pub resolved: u32,
+    pub open: u32,
+    /// Tickets created at Critical priority level.
+    pub critical: u32,
+    /// Tickets created per day of the window, for comparing rotations of
+    /// different lengths.
+    pub created_per_day: f64,
+    pub avg_resolution_hours: Option<f64>,
+}
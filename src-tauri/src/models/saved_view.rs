@@ -0,0 +1,60 @@
+use crate::models::TicketFilter;
+use serde::{Deserialize, Serialize};
+
+/// Time bucket for the created/resolved timeline.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    Week,
+    #[default]
+    Month,
+}
+
+impl Granularity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Granularity::Week => "week",
+            Granularity::Month => "month",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "week" => Some(Granularity::Week),
+            "month" => Some(Granularity::Month),
+            _ => None,
+        }
+    }
+}
+
+/// Ticket list sort order.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ViewSort {
+    pub column: String,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+/// A named dashboard configuration, e.g. "My Criticals".
+#[derive(Debug, Clone, Serialize)]
+pub struct SavedView {
+    pub id: i64,
+    pub name: String,
+    pub filter: TicketFilter,
+    pub sort: Option<ViewSort>,
+    pub granularity: Granularity,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Fields the UI supplies when creating or updating a view.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SavedViewInput {
+    pub name: String,
+    #[serde(default)]
+    pub filter: TicketFilter,
+    #[serde(default)]
+    pub sort: Option<ViewSort>,
+    #[serde(default)]
+    pub granularity: Granularity,
+}
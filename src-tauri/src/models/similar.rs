@@ -0,0 +1,10 @@
+use super::Ticket;
+use serde::Serialize;
+
+/// An earlier ticket whose summary resembles the one being triaged.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimilarTicket {
+    pub ticket: Ticket,
+    /// Trigram similarity of the two summaries, 0.0 to 1.0.
+    pub score: f64,
+}
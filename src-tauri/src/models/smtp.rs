@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// How the SMTP connection is secured.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Plain connection upgraded with STARTTLS (usually port 587).
+    #[default]
+    StartTls,
+    /// Implicit TLS (usually port 465).
+    Tls,
+    /// Unencrypted; only for local relays.
+    None,
+}
+
+/// Outgoing mail server for the email digest, stored under the `smtp` settings key.
+/// The password lives in the OS keychain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmtpSettings {
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+    /// Login name; `None` sends without authenticating.
+    pub username: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+impl Default for SmtpSettings {
+    fn default() -> Self {
+        SmtpSettings {
+            host: String::new(),
+            port: 587,
+            security: SmtpSecurity::StartTls,
+            username: None,
+            from: String::new(),
+            to: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use serde::Serialize;
+
+/// A stored dashboard snapshot, without its payload.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportSnapshot {
+    pub id: i64,
+    /// ISO week the snapshot belongs to, e.g. "2025-W03".
+    pub period: String,
+    pub taken_at: String,
+}
+
+/// How one number moved between two snapshots.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MetricDelta {
+    pub name: String,
+    pub from: f64,
+    pub to: f64,
+    pub change: f64,
+}
+
+/// Differences between two snapshots, `from` → `to`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotDiff {
+    pub from: ReportSnapshot,
+    pub to: ReportSnapshot,
+    pub summary: Vec<MetricDelta>,
+    pub by_status: Vec<MetricDelta>,
+    pub by_priority: Vec<MetricDelta>,
+    pub by_category: Vec<MetricDelta>,
+}
+
+/// A calendar span compared by `compare_periods`: `from` inclusive, `to`
+/// exclusive.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ReportPeriod {
+    /// As requested, e.g. "2025-Q1".
+    pub label: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// How the filtered tickets moved from period `a` to period `b`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PeriodComparison {
+    pub a: ReportPeriod,
+    pub b: ReportPeriod,
+    /// Created, resolved, median resolution hours, and (when SLA targets
+    /// apply to either period) SLA compliance as a fraction.
+    pub summary: Vec<MetricDelta>,
+    /// Tickets created per category, for the top categories of either period.
+    pub top_categories: Vec<MetricDelta>,
+}
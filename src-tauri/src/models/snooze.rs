@@ -0,0 +1,19 @@
+use serde::Serialize;
+
+/// A ticket hidden from default views until `until`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TicketSnooze {
+    pub ticket_key: String,
+    pub summary: String,
+    pub until: String,
+    pub snoozed_at: String,
+}
+
+/// A snooze that ended, either on time or because the ticket changed.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct EndedSnooze {
+    pub ticket_key: String,
+    pub summary: String,
+    /// The ticket was updated while snoozed, before `until`.
+    pub changed: bool,
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+/// A Jira Software sprint as seen on synced issues.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct Sprint {
+    pub name: String,
+    /// `active`, `closed` or `future`.
+    pub state: Option<String>,
+    pub start_date: Option<String>, // ISO 8601
+    pub end_date: Option<String>,   // ISO 8601
+}
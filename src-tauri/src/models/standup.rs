@@ -0,0 +1,23 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StandupItem {
+    pub jira_key: String,
+    pub summary: String,
+    pub status: String,
+    /// Why a blocker is listed, e.g. "blocked by OPS-9".
+    pub note: Option<String>,
+}
+
+/// One person's standup for `date`, with the Markdown ready to paste.
+#[derive(Debug, Clone, Serialize)]
+pub struct Standup {
+    pub date: String,
+    pub assignee: String,
+    /// Start of the "yesterday" window: the previous working day.
+    pub since: String,
+    pub resolved: Vec<StandupItem>,
+    pub in_progress: Vec<StandupItem>,
+    pub blockers: Vec<StandupItem>,
+    pub markdown: String,
+}
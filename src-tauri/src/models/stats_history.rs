@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// One point in the summary stats history.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsHistoryEntry {
+    pub recorded_at: String,
+    pub total_tickets: u32,
+    pub open_tickets: u32,
+    pub resolved_tickets: u32,
+    pub stale_tickets: u32,
+    pub avg_resolution_hours: f64,
+    pub median_resolution_hours: f64,
+}
+
+/// How far back `get_stats_history` looks.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryRange {
+    Month,
+    Quarter,
+    #[default]
+    Year,
+}
+
+impl HistoryRange {
+    pub fn days(self) -> i64 {
+        match self {
+            HistoryRange::Month => 30,
+            HistoryRange::Quarter => 91,
+            HistoryRange::Year => 365,
+        }
+    }
+}
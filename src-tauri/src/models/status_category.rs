@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+/// Workflow-independent stage of a status, as in Jira's `statusCategory`.
+/// When known it decides whether a ticket counts as resolved.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusCategory {
+    Todo,
+    InProgress,
+    Done,
+}
+
+impl StatusCategory {
+    /// Every category in workflow order.
+    pub const ALL: [StatusCategory; 3] = [
+        StatusCategory::Todo,
+        StatusCategory::InProgress,
+        StatusCategory::Done,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StatusCategory::Todo => "todo",
+            StatusCategory::InProgress => "in_progress",
+            StatusCategory::Done => "done",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "todo" => Some(StatusCategory::Todo),
+            "in_progress" => Some(StatusCategory::InProgress),
+            "done" => Some(StatusCategory::Done),
+            _ => None,
+        }
+    }
+
+    /// Jira's `statusCategory.key`; `undefined` has no category.
+    pub fn from_jira_key(key: &str) -> Option<Self> {
+        match key {
+            "new" => Some(StatusCategory::Todo),
+            "indeterminate" => Some(StatusCategory::InProgress),
+            "done" => Some(StatusCategory::Done),
+            _ => None,
+        }
+    }
+}
+
+/// A local override of the category for every ticket in `status`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StatusCategoryMapping {
+    pub status: String,
+    pub category: StatusCategory,
+}
@@ -0,0 +1,16 @@
+use serde::Serialize;
+
+/// A stretch a ticket spent in one status for longer than the status's
+/// dwell limit allows.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusDwellViolation {
+    pub jira_key: String,
+    pub summary: String,
+    pub status: String,
+    pub entered_at: String,
+    /// `None` while the ticket is still in `status`.
+    pub left_at: Option<String>,
+    /// Business hours spent in `status` (so far, for an ongoing stretch).
+    pub dwell_hours: f64,
+    pub limit_hours: f64,
+}
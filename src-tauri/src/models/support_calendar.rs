@@ -0,0 +1,24 @@
+use super::WorkSchedule;
+use serde::{Deserialize, Serialize};
+
+/// Working hours shared by a set of projects, e.g. an EU and a US support
+/// team. Resolution and SLA hours of a project's tickets follow its
+/// calendar; projects without one use 9–17 UTC on weekdays.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SupportCalendar {
+    pub id: i64,
+    pub name: String,
+    pub schedule: WorkSchedule,
+    /// Project keys assigned to this calendar, sorted.
+    pub projects: Vec<String>,
+}
+
+/// Fields the UI supplies when creating or updating a calendar. Listing a
+/// project moves it off any other calendar.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SupportCalendarInput {
+    pub name: String,
+    pub schedule: WorkSchedule,
+    #[serde(default)]
+    pub projects: Vec<String>,
+}
@@ -0,0 +1,39 @@
+use crate::models::SyncItemFailure;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewAction {
+    Insert,
+    Update,
+}
+
+/// One ticket field a sync would change.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    /// Stored value; `null` for tickets that would be inserted.
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketPreview {
+    pub jira_key: String,
+    pub summary: String,
+    pub action: PreviewAction,
+    pub changes: Vec<FieldChange>,
+}
+
+/// What a sync would write, computed without writing anything.
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncPreview {
+    pub source: String,
+    pub fetched: u32,
+    pub inserts: u32,
+    pub updates: u32,
+    pub unchanged: u32,
+    pub failures: Vec<SyncItemFailure>,
+    /// The first few inserts and updates, with their field changes.
+    pub samples: Vec<TicketPreview>,
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncRunStatus {
+    /// Still in progress, or the app quit before it finished.
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl SyncRunStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SyncRunStatus::Running => "running",
+            SyncRunStatus::Succeeded => "succeeded",
+            SyncRunStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "running" => Some(SyncRunStatus::Running),
+            "succeeded" => Some(SyncRunStatus::Succeeded),
+            "failed" => Some(SyncRunStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// Ticket counts for one sync run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct SyncRunCounts {
+    /// Items returned by the source.
+    pub fetched: u32,
+    /// Tickets that were new or changed since they were last stored.
+    pub updated: u32,
+    /// Items that couldn't be stored.
+    pub failed: u32,
+}
+
+/// An item the source returned that couldn't be synced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncItemFailure {
+    /// Ticket key, when the item had a readable one.
+    pub key: Option<String>,
+    pub error: String,
+}
+
+/// One recorded sync of a source, for diagnosing flaky syncs.
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncRun {
+    pub id: i64,
+    pub source: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub status: SyncRunStatus,
+    pub tickets_fetched: u32,
+    pub tickets_updated: u32,
+    pub tickets_failed: u32,
+    /// Why the run failed, when it did.
+    pub error: Option<String>,
+    /// Items skipped during the run. Only the first few are stored;
+    /// `tickets_failed` has the full count.
+    pub failures: Vec<SyncItemFailure>,
+}
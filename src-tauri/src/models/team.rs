@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// A named group of assignees, for rollups Jira's own fields can't give.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Team {
+    pub id: i64,
+    pub name: String,
+    /// Assignee display names, sorted.
+    pub members: Vec<String>,
+}
+
+/// Fields the UI supplies when creating or updating a team.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TeamInput {
+    pub name: String,
+    #[serde(default)]
+    pub members: Vec<String>,
+}
+
+/// One team's row in the team comparison view. Tickets count toward every
+/// team their assignee belongs to.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TeamRollup {
+    pub team: String,
+    /// Open tickets assigned to the team's members.
+    pub open_tickets: u32,
+    pub resolved_tickets: u32,
+    /// Tickets resolved per week over the trailing window.
+    pub throughput_per_week: f64,
+    pub avg_resolution_hours: Option<f64>,
+    pub p90_resolution_hours: Option<f64>,
+}
@@ -1,12 +1,15 @@
+use super::{PriorityLevel, StatusCategory};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Ticket {
     pub id: i64,
     pub jira_key: String,
     pub summary: String,
     pub status: String,
+    pub status_category: Option<StatusCategory>, // from Jira or a local mapping
     pub priority: String,
+    pub priority_level: Option<PriorityLevel>, // `priority` on the canonical scale
     pub issue_type: String,
     pub assignee: Option<String>,
     pub reporter: Option<String>,
@@ -15,5 +18,41 @@ pub struct Ticket {
     pub resolved_at: Option<String>, // ISO 8601
     pub labels: String,              // comma-separated
     pub project_key: String,
-    pub category: Option<String>, // computed locally
+    pub category: Option<String>,         // computed locally
+    pub category_confidence: Option<f64>, // 0.0-1.0; 1.0 for a rule match
+    pub source: String,                   // "jira", "gitlab", ...
+    pub custom_fields: String,            // JSON object of source-specific fields
+    pub account_id: Option<i64>,          // jira_accounts.id when synced through an account
+    pub story_points: Option<f64>,        // from the configured estimate field
+    pub sprint: Option<String>,           // most recent sprint the ticket was in
+    pub due_date: Option<String>,         // YYYY-MM-DD
+    pub customer: Option<String>,         // from the configured customer field
+    pub environment: Option<String>,      // Jira environment field, as Markdown
+    #[serde(default)]
+    pub escalated: bool, // from the configured escalation label or field
+    #[serde(default)]
+    pub components: Vec<String>, // stored in ticket_components
+    #[serde(default)]
+    pub fix_versions: Vec<String>, // stored in ticket_fix_versions
+    #[serde(default)]
+    pub affected_versions: Vec<String>, // stored in ticket_affected_versions
+}
+
+impl Ticket {
+    /// The canonical priority if known, else the source's; SLA targets are
+    /// keyed by this.
+    pub fn sla_priority(&self) -> &str {
+        self.priority_level
+            .map_or(self.priority.as_str(), PriorityLevel::as_str)
+    }
+}
+
+/// One chunk of a `stream_tickets` response.
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketChunk {
+    /// Position of the chunk's first ticket in the full result.
+    pub offset: u32,
+    pub tickets: Vec<Ticket>,
+    /// Set on the last chunk.
+    pub done: bool,
 }
@@ -0,0 +1,14 @@
+use serde::Serialize;
+
+/// One field change seen when a newer version of a ticket was stored.
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketChange {
+    pub id: i64,
+    pub ticket_key: String,
+    pub field: String,
+    pub old_value: serde_json::Value,
+    pub new_value: serde_json::Value,
+    pub changed_at: String,
+    /// The sync run that stored the change; `None` for webhook and manual refreshes.
+    pub sync_run_id: Option<i64>,
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// A directed link between two tickets, stored outward-first: `outward_key`
+/// `outward_label` `inward_key`, e.g. "OPS-1 blocks OPS-2".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TicketLink {
+    pub outward_key: String,
+    pub inward_key: String,
+    /// Link type name, e.g. "Blocks", "Duplicate", "Relates".
+    pub link_type: String,
+    /// e.g. "blocks"
+    pub outward_label: String,
+    /// e.g. "is blocked by"
+    pub inward_label: String,
+}
+
+/// A ticket linked to the one being viewed. `relation` reads from the viewed
+/// ticket's side ("is blocked by OPS-2"); summary and status are `None` when
+/// the other ticket isn't stored locally.
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkedTicket {
+    pub jira_key: String,
+    pub link_type: String,
+    pub relation: String,
+    pub summary: Option<String>,
+    pub status: Option<String>,
+    pub resolved: bool,
+    /// Made with `link_tickets` rather than synced from Jira.
+    pub local: bool,
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+/// A private markdown note on a ticket, kept locally and never synced.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TicketNote {
+    pub id: i64,
+    pub ticket_key: String,
+    pub body: String,
+    pub created_at: String, // ISO 8601
+    pub updated_at: String, // ISO 8601
+}
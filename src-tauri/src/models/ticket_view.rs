@@ -0,0 +1,8 @@
+use super::Ticket;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentlyViewed {
+    pub ticket: Ticket,
+    pub viewed_at: String, // ISO 8601
+}
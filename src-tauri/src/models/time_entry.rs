@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// One run of a ticket's timer.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TimeEntry {
+    pub id: i64,
+    pub ticket_key: String,
+    pub started_at: String,       // ISO 8601
+    pub ended_at: Option<String>, // None while the timer runs
+    /// Set once the entry has been pushed to Jira as a worklog.
+    pub worklog_id: Option<String>,
+    /// Planned length when the entry is a focus session.
+    pub focus_minutes: Option<u32>,
+    /// Interruptions recorded during a focus session.
+    pub interruptions: u32,
+}
+
+/// Bucket size for the time report.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeReportPeriod {
+    #[default]
+    Day,
+    Week,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TimeReportRow {
+    /// First day of the bucket, `YYYY-MM-DD`; weeks start on Monday.
+    pub period_start: String,
+    pub ticket_key: String,
+    pub seconds: i64,
+}
+
+/// Focus sessions for one ticket, or overall.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct FocusTotals {
+    pub sessions: u32,
+    /// Sessions that ran for their full planned length.
+    pub completed: u32,
+    pub interruptions: u32,
+    pub focus_seconds: i64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FocusTicketStats {
+    pub ticket_key: String,
+    #[serde(flatten)]
+    pub totals: FocusTotals,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FocusStats {
+    #[serde(flatten)]
+    pub totals: FocusTotals,
+    /// Per ticket, most focused time first.
+    pub tickets: Vec<FocusTicketStats>,
+}
@@ -0,0 +1,27 @@
+use serde::Serialize;
+
+/// A workflow transition available on a ticket.
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketTransition {
+    pub id: String,
+    pub name: String,
+    pub to_status: String,
+    /// Target status is in Jira's "done" category, so the ticket counts as resolved.
+    pub resolves: bool,
+}
+
+/// A ticket's description and comments, fetched live and rendered as Markdown.
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketContent {
+    pub jira_key: String,
+    pub description: Option<String>,
+    /// Oldest first, as Jira returns them.
+    pub comments: Vec<TicketComment>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TicketComment {
+    pub author: Option<String>,
+    pub created_at: String,
+    pub body: String,
+}
@@ -0,0 +1,13 @@
+use serde::Serialize;
+
+/// A word showing up in recent ticket summaries far more than it used to.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrendingTerm {
+    pub term: String,
+    /// Tickets in the range whose summary contains the term.
+    pub recent_count: u32,
+    /// Tickets created before the range whose summary contains it.
+    pub previous_count: u32,
+    /// Recent count weighted by how rare the term was before the range.
+    pub score: f64,
+}
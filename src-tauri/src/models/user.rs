@@ -0,0 +1,13 @@
+use serde::Serialize;
+
+/// A Jira user from the directory synced by `sync_users`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct User {
+    /// `accountId` on Jira Cloud, the username on Server / Data Center;
+    /// what `assign_ticket` expects. Stays the same when the display name
+    /// changes.
+    pub account_id: String,
+    pub display_name: String,
+    pub avatar_url: Option<String>,
+    pub active: bool,
+}
@@ -0,0 +1,47 @@
+use serde::Serialize;
+
+/// The big numbers at the top of the wallboard.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct WallboardKpis {
+    pub open_tickets: u32,
+    /// Open tickets at the Critical priority level.
+    pub open_criticals: u32,
+    pub created_today: u32,
+    pub resolved_today: u32,
+    /// Open tickets past their SLA target.
+    pub sla_breached: u32,
+}
+
+/// An open ticket's time left on its business-hour SLA target; negative
+/// once breached.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SlaCountdown {
+    pub jira_key: String,
+    pub summary: String,
+    pub priority: String,
+    pub assignee: Option<String>,
+    pub target_hours: f64,
+    pub remaining_hours: f64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct WallboardTicket {
+    pub jira_key: String,
+    pub summary: String,
+    pub status: String,
+    pub assignee: Option<String>,
+    pub created_at: String,
+}
+
+/// Everything a TV wallboard shows, in one call.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct WallboardPayload {
+    /// Name of the saved view the board is scoped to, if any.
+    pub view_name: Option<String>,
+    pub generated_at: String,
+    pub kpis: WallboardKpis,
+    /// Soonest to breach first; already breached tickets lead.
+    pub sla_countdowns: Vec<SlaCountdown>,
+    /// Open Critical tickets, newest first.
+    pub newest_criticals: Vec<WallboardTicket>,
+}
@@ -0,0 +1,19 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// A watched ticket whose status, assignee, or priority moved since the user
+/// last acknowledged it.
+#[derive(Debug, Serialize)]
+pub struct WatchlistChange {
+    pub jira_key: String,
+    pub summary: String,
+    /// When a sync first noticed the change.
+    pub changed_at: Option<String>,
+    pub changes: Vec<FieldChange>,
+}
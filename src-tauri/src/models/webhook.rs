@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+/// Where the Jira webhook listener is bound and the secret its URL must carry.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookListenerStatus {
+    pub port: u16,
+    pub secret: String,
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Working time on one day, in minutes after midnight. `end_minute` may be
+/// 1440 for coverage running until midnight.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkWindow {
+    pub start_minute: u32,
+    pub end_minute: u32,
+}
+
+impl WorkWindow {
+    pub const fn hours(start_hour: u32, end_hour: u32) -> Self {
+        Self {
+            start_minute: start_hour * 60,
+            end_minute: end_hour * 60,
+        }
+    }
+}
+
+/// Working windows per weekday, Monday first; `None` marks a day off.
+/// Business-hour durations only count time inside these windows.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkSchedule {
+    pub days: [Option<WorkWindow>; 7],
+    /// How far the windows' local time is ahead of UTC, e.g. 60 for CET.
+    #[serde(default)]
+    pub utc_offset_minutes: i32,
+}
+
+impl Default for WorkSchedule {
+    /// 9–17, Monday to Friday.
+    fn default() -> Self {
+        let weekday = Some(WorkWindow::hours(9, 17));
+        Self {
+            days: [weekday, weekday, weekday, weekday, weekday, None, None],
+            utc_offset_minutes: 0,
+        }
+    }
+}
@@ -0,0 +1,781 @@
+use crate::db::support_calendars::{project_schedules, ProjectSchedules};
+use crate::db::{
+    end_due_snoozes, get_sync_metadata, get_tickets, get_watchlist_changes,
+    get_weekly_created_resolved, list_alert_rules, ratio_weeks, record_alert_once,
+    rolling_created_resolved, set_alert_rule_fired, set_sync_metadata,
+};
+use crate::errors::{AppError, DbError};
+use crate::models::{
+    Alert, AlertChannel, AlertCondition, AlertRule, AlertSettings, EndedSnooze, PriorityLevel,
+    Ticket, TicketFilter, WebhookEvent, WorkSchedule,
+};
+use crate::services::anomaly::{inflow_anomalies, BASELINE_DAYS};
+use crate::services::notify::digest::{build_weekly_digest, WeeklyDigest};
+use crate::services::notify::slack::SlackNotifier;
+use crate::services::notify::webhook_out;
+use crate::services::status_dwell::status_dwell_violations;
+use crate::services::time_calc::{business_hours_between, parse_timestamp};
+use chrono::{DateTime, NaiveDateTime};
+use rusqlite::Connection;
+use std::sync::{Arc, Mutex};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_store::StoreExt;
+
+/// Evaluates the built-in alerts against the local database. Every alert is
+/// keyed in `alert_log`, so re-running after the next sync only yields new ones.
+pub fn evaluate_alerts(
+    conn: &Connection,
+    settings: &AlertSettings,
+    now: NaiveDateTime,
+) -> Result<Vec<Alert>, AppError> {
+    let mut alerts = Vec::new();
+
+    for change in get_watchlist_changes(conn)? {
+        let alert_key = format!(
+            "watch:{}:{}",
+            change.jira_key,
+            change.changed_at.as_deref().unwrap_or_default()
+        );
+        if record_alert_once(conn, &alert_key)? {
+            let body = change
+                .changes
+                .iter()
+                .map(|c| {
+                    format!(
+                        "{}: {} → {}",
+                        c.field,
+                        c.from.as_deref().unwrap_or("none"),
+                        c.to.as_deref().unwrap_or("none")
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            alerts.push(Alert {
+                title: format!("Watched ticket {} changed", change.jira_key),
+                body,
+                ticket_key: Some(change.jira_key),
+            });
+        }
+    }
+
+    let open_tickets: Vec<Ticket> = get_tickets(conn, &TicketFilter::default())?
+        .into_iter()
+        .filter(|t| t.resolved_at.is_none())
+        .collect();
+
+    if let Some(me) = settings.my_name.as_deref().filter(|n| !n.is_empty()) {
+        for ticket in open_tickets.iter().filter(|t| {
+            t.priority_level == Some(PriorityLevel::Critical) && t.assignee.as_deref() == Some(me)
+        }) {
+            if record_alert_once(conn, &format!("critical:{}", ticket.jira_key))? {
+                alerts.push(Alert {
+                    title: format!("Critical ticket assigned: {}", ticket.jira_key),
+                    body: ticket.summary.clone(),
+                    ticket_key: Some(ticket.jira_key.clone()),
+                });
+            }
+        }
+    }
+
+    let schedules = project_schedules(conn)?;
+    for ticket in &open_tickets {
+        let Some((elapsed, target)) = sla_progress(ticket, settings, &schedules, now) else {
+            continue;
+        };
+
+        let (kind, title) = if elapsed >= target {
+            ("sla-breached", "SLA breached")
+        } else if elapsed >= target * settings.sla_warn_ratio {
+            ("sla-warning", "SLA about to breach")
+        } else {
+            continue;
+        };
+
+        if record_alert_once(conn, &format!("{}:{}", kind, ticket.jira_key))? {
+            alerts.push(Alert {
+                title: format!("{}: {}", title, ticket.jira_key),
+                body: format!(
+                    "{} ({}) open {:.1}h of {:.0}h target",
+                    ticket.summary, ticket.priority, elapsed, target
+                ),
+                ticket_key: Some(ticket.jira_key.clone()),
+            });
+        }
+    }
+
+    let ongoing = status_dwell_violations(
+        conn,
+        &TicketFilter::default(),
+        &settings.status_dwell_limits_hours,
+        now,
+    )?
+    .into_iter()
+    .filter(|v| v.left_at.is_none());
+    for violation in ongoing {
+        let alert_key = format!(
+            "dwell:{}:{}:{}",
+            violation.jira_key, violation.status, violation.entered_at
+        );
+        if record_alert_once(conn, &alert_key)? {
+            alerts.push(Alert {
+                title: format!("Too long in {}: {}", violation.status, violation.jira_key),
+                body: format!(
+                    "{} in {} for {:.1}h of {:.0}h allowed",
+                    violation.summary,
+                    violation.status,
+                    violation.dwell_hours,
+                    violation.limit_hours
+                ),
+                ticket_key: Some(violation.jira_key),
+            });
+        }
+    }
+
+    Ok(alerts)
+}
+
+/// A user-defined rule that fired, with where to deliver it.
+#[derive(Debug)]
+pub struct RuleHit {
+    pub rule_id: i64,
+    pub channel: AlertChannel,
+    pub alert: Alert,
+}
+
+/// Evaluates enabled `alert_rules` against the local tickets. Rules that fire
+/// are stamped with `last_fired_at` and stay quiet for their throttle window.
+pub fn evaluate_alert_rules(
+    conn: &Connection,
+    now: NaiveDateTime,
+) -> Result<Vec<RuleHit>, AppError> {
+    let rules: Vec<AlertRule> = list_alert_rules(conn)?
+        .into_iter()
+        .filter(|r| r.enabled && !is_throttled(r, now))
+        .collect();
+    if rules.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let tickets = get_tickets(conn, &TicketFilter::default())?;
+    let fired_at = now.and_utc().to_rfc3339();
+    let mut hits = Vec::new();
+
+    for rule in rules {
+        let Some(body) = rule_body(conn, &rule.condition, &tickets, now)? else {
+            continue;
+        };
+
+        set_alert_rule_fired(conn, rule.id, &fired_at)?;
+        hits.push(RuleHit {
+            rule_id: rule.id,
+            channel: rule.channel,
+            alert: Alert {
+                title: rule.name,
+                body,
+                ticket_key: None,
+            },
+        });
+    }
+
+    Ok(hits)
+}
+
+fn is_throttled(rule: &AlertRule, now: NaiveDateTime) -> bool {
+    rule.last_fired_at
+        .as_deref()
+        .and_then(parse_timestamp)
+        .is_some_and(|last| now - last < chrono::Duration::minutes(rule.throttle_minutes.into()))
+}
+
+/// Notification body when `condition` holds, `None` otherwise.
+fn rule_body(
+    conn: &Connection,
+    condition: &AlertCondition,
+    tickets: &[Ticket],
+    now: NaiveDateTime,
+) -> Result<Option<String>, AppError> {
+    let body = match condition {
+        AlertCondition::Count {
+            tickets: ticket_match,
+            op,
+            threshold,
+        } => {
+            let count = tickets.iter().filter(|t| ticket_match.matches(t)).count() as f64;
+            op.holds(count, *threshold)
+                .then(|| format!("{} matching tickets ({} {})", count, op.symbol(), threshold))
+        }
+        AlertCondition::Idle {
+            tickets: ticket_match,
+            days,
+        } => {
+            let idle: Vec<&str> = tickets
+                .iter()
+                .filter(|t| ticket_match.matches(t))
+                .filter(|t| {
+                    parse_timestamp(&t.updated_at).is_some_and(|updated| {
+                        (now - updated).num_minutes() as f64 / (24.0 * 60.0) > *days
+                    })
+                })
+                .map(|t| t.jira_key.as_str())
+                .collect();
+
+            if idle.is_empty() {
+                return Ok(None);
+            }
+            let preview = idle.iter().take(5).copied().collect::<Vec<_>>().join(", ");
+            let more = if idle.len() > 5 {
+                format!(" and {} more", idle.len() - 5)
+            } else {
+                String::new()
+            };
+            Some(format!(
+                "{} tickets idle for more than {} days: {}{}",
+                idle.len(),
+                days,
+                preview,
+                more
+            ))
+        }
+        AlertCondition::BacklogGrowth { threshold, weeks } => {
+            let points = *weeks as usize;
+            let today = now.date();
+            // Counted in SQL, the same way as the dashboard's ratio trend
+            let counts = get_weekly_created_resolved(
+                conn,
+                &TicketFilter::default(),
+                today,
+                ratio_weeks(points),
+            )?;
+            let trend = rolling_created_resolved(&counts, today, points);
+            let Some(latest) = trend.last() else {
+                return Ok(None);
+            };
+            let sustained = trend.iter().all(|entry| match entry.ratio {
+                Some(ratio) => ratio > *threshold,
+                // Nothing resolved: growing as long as anything was created
+                None => entry.created > 0,
+            });
+            if !sustained {
+                return Ok(None);
+            }
+            Some(format!(
+                "Created/resolved ratio above {} for {} weeks ({} created, {} resolved in the last 4 weeks)",
+                threshold, weeks, latest.created, latest.resolved
+            ))
+        }
+        AlertCondition::InflowSpike { z_threshold } => {
+            let Some(spike) = inflow_anomalies(tickets, now, 1, BASELINE_DAYS, *z_threshold).pop()
+            else {
+                return Ok(None);
+            };
+            Some(format!(
+                "{} tickets created today, against {:.1} a day over the last {} days (z = {:.1})",
+                spike.created, spike.baseline_mean, BASELINE_DAYS, spike.z_score
+            ))
+        }
+    };
+    Ok(body)
+}
+
+/// An open ticket that has used up its SLA target.
+#[derive(Debug, Clone)]
+pub struct SlaBreach {
+    pub jira_key: String,
+    pub summary: String,
+    pub priority: String,
+    pub elapsed_hours: f64,
+    pub target_hours: f64,
+}
+
+/// Open tickets past their business-hour SLA target, longest overdue first.
+pub fn sla_breaches(
+    conn: &Connection,
+    settings: &AlertSettings,
+    now: NaiveDateTime,
+) -> Result<Vec<SlaBreach>, AppError> {
+    let schedules = project_schedules(conn)?;
+    let mut breaches: Vec<SlaBreach> = get_tickets(conn, &TicketFilter::default())?
+        .into_iter()
+        .filter(|t| t.resolved_at.is_none())
+        .filter_map(|t| {
+            let (elapsed, target) = sla_progress(&t, settings, &schedules, now)?;
+            (elapsed >= target).then(|| SlaBreach {
+                jira_key: t.jira_key,
+                summary: t.summary,
+                priority: t.priority,
+                elapsed_hours: elapsed,
+                target_hours: target,
+            })
+        })
+        .collect();
+    breaches.sort_by(|a, b| {
+        (b.elapsed_hours - b.target_hours).total_cmp(&(a.elapsed_hours - a.target_hours))
+    });
+    Ok(breaches)
+}
+
+/// Business hours elapsed, on the ticket's project calendar, and the target
+/// for a ticket whose priority level (or raw priority, when unmapped) has one.
+pub(crate) fn sla_progress(
+    ticket: &Ticket,
+    settings: &AlertSettings,
+    schedules: &ProjectSchedules,
+    now: NaiveDateTime,
+) -> Option<(f64, f64)> {
+    let target = *settings.sla_targets_hours.get(ticket.sla_priority())?;
+    let elapsed =
+        business_hours_since(&ticket.created_at, schedules.get(&ticket.project_key), now)?;
+    Some((elapsed, target))
+}
+
+fn business_hours_since(
+    created_at: &str,
+    schedule: &WorkSchedule,
+    now: NaiveDateTime,
+) -> Option<f64> {
+    let created = DateTime::parse_from_rfc3339(created_at).ok()?.naive_utc();
+    business_hours_between(created, now, schedule).ok()
+}
+
+pub fn load_alert_settings(app_handle: &tauri::AppHandle) -> Result<AlertSettings, AppError> {
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+
+    match store.get("alerts") {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|e| AppError::Config(format!("Failed to parse settings: {}", e))),
+        None => Ok(AlertSettings::default()),
+    }
+}
+
+fn snooze_alert(snooze: EndedSnooze) -> Alert {
+    let title = if snooze.changed {
+        format!("{} changed while snoozed", snooze.ticket_key)
+    } else {
+        format!("{} is back from snooze", snooze.ticket_key)
+    };
+    Alert {
+        title,
+        body: snooze.summary,
+        ticket_key: Some(snooze.ticket_key),
+    }
+}
+
+/// Shows each alert as a native desktop notification.
+pub fn deliver_desktop(app_handle: &tauri::AppHandle, alerts: &[Alert]) {
+    for alert in alerts {
+        if let Err(e) = app_handle
+            .notification()
+            .builder()
+            .title(&alert.title)
+            .body(&alert.body)
+            .show()
+        {
+            log::warn!("Failed to show notification '{}': {}", alert.title, e);
+        }
+    }
+}
+
+/// Evaluates the built-in alerts and user rules after a sync has been
+/// persisted, and delivers whatever fired.
+pub async fn run_post_sync_alerts(
+    db_pool: Arc<Mutex<Connection>>,
+    app_handle: &tauri::AppHandle,
+) -> Result<usize, AppError> {
+    let settings = load_alert_settings(app_handle)?;
+
+    let slack_digest = settings.slack_weekly_digest;
+
+    let (alerts, hits, digest) = tauri::async_runtime::spawn_blocking({
+        let db_pool = db_pool.clone();
+        move || {
+            let conn = db_pool.lock().map_err(|_| DbError::LockFailed)?;
+            let now = chrono::Utc::now().naive_utc();
+            let mut alerts = if settings.enabled {
+                evaluate_alerts(&conn, &settings, now)?
+            } else {
+                Vec::new()
+            };
+            // Snoozes were asked for explicitly, so they wake even with alerts off
+            alerts.extend(
+                end_due_snoozes(&conn, now.and_utc())?
+                    .into_iter()
+                    .map(snooze_alert),
+            );
+            let hits = evaluate_alert_rules(&conn, now)?;
+            let digest = if slack_digest && weekly_digest_due(&conn, now)? {
+                Some(build_weekly_digest(&conn, now)?)
+            } else {
+                None
+            };
+            Ok::<_, AppError>((alerts, hits, digest))
+        }
+    })
+    .await
+    .map_err(|_| AppError::Internal("Task join failed".to_string()))??;
+
+    deliver_desktop(app_handle, &alerts);
+
+    let needs_slack = digest.is_some() || hits.iter().any(|hit| hit.channel == AlertChannel::Slack);
+    let slack = if needs_slack {
+        slack_notifier(app_handle)?
+    } else {
+        None
+    };
+
+    for hit in &hits {
+        log::info!("Alert rule {} fired", hit.rule_id);
+        match hit.channel {
+            AlertChannel::Desktop => deliver_desktop(app_handle, std::slice::from_ref(&hit.alert)),
+            AlertChannel::Slack => match &slack {
+                Some(slack) => {
+                    if let Err(e) = slack.post_alert(&hit.alert).await {
+                        log::warn!("Slack alert for rule {} failed: {}", hit.rule_id, e);
+                    }
+                }
+                None => log::warn!(
+                    "Alert rule {} targets Slack but no webhook is configured",
+                    hit.rule_id
+                ),
+            },
+        }
+    }
+
+    let fired = alerts
+        .iter()
+        .map(|alert| (None, alert))
+        .chain(hits.iter().map(|hit| (Some(hit.rule_id), &hit.alert)))
+        .map(|(rule_id, alert)| {
+            serde_json::json!({
+                "rule_id": rule_id,
+                "title": alert.title,
+                "body": alert.body,
+                "ticket_key": alert.ticket_key,
+            })
+        })
+        .collect();
+    if let Err(e) =
+        webhook_out::dispatch(db_pool.clone(), app_handle, WebhookEvent::AlertFired, fired).await
+    {
+        log::warn!("Alert webhooks failed: {}", e);
+    }
+
+    if let (Some(digest), Some(slack)) = (digest, &slack) {
+        slack.post_digest(&digest).await?;
+        tauri::async_runtime::spawn_blocking(move || {
+            let conn = db_pool.lock().map_err(|_| DbError::LockFailed)?;
+            set_sync_metadata(
+                &conn,
+                SLACK_DIGEST_KEY,
+                &chrono::Utc::now()
+                    .naive_utc()
+                    .format(DIGEST_TS_FORMAT)
+                    .to_string(),
+            )
+        })
+        .await
+        .map_err(|_| AppError::Internal("Task join failed".to_string()))??;
+    }
+
+    Ok(alerts.len() + hits.len())
+}
+
+const SLACK_DIGEST_KEY: &str = "slack:last_digest_at";
+const DIGEST_TS_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// True when no Slack digest has been posted in the last seven days.
+fn weekly_digest_due(conn: &Connection, now: NaiveDateTime) -> Result<bool, AppError> {
+    let last = get_sync_metadata(conn, SLACK_DIGEST_KEY)?
+        .and_then(|value| NaiveDateTime::parse_from_str(&value, DIGEST_TS_FORMAT).ok());
+    Ok(match last {
+        Some(last) => now - last >= chrono::Duration::days(7),
+        None => true,
+    })
+}
+
+/// Slack client for the stored webhook, or `None` when Slack isn't set up.
+pub(crate) fn slack_notifier(
+    app_handle: &tauri::AppHandle,
+) -> Result<Option<SlackNotifier>, AppError> {
+    match crate::commands::settings::get_slack_webhook_internal()? {
+        Some(url) => Ok(Some(SlackNotifier::new(
+            &url,
+            crate::commands::settings::http_client(app_handle)?,
+        )?)),
+        None => Ok(None),
+    }
+}
+
+/// Builds this week's digest and posts it to Slack right away.
+pub async fn send_slack_digest(
+    db_pool: Arc<Mutex<Connection>>,
+    app_handle: &tauri::AppHandle,
+) -> Result<WeeklyDigest, AppError> {
+    let slack = slack_notifier(app_handle)?
+        .ok_or_else(|| AppError::Config("Slack webhook is not configured".to_string()))?;
+    let digest = tauri::async_runtime::spawn_blocking(move || {
+        let conn = db_pool.lock().map_err(|_| DbError::LockFailed)?;
+        build_weekly_digest(&conn, chrono::Utc::now().naive_utc())
+    })
+    .await
+    .map_err(|_| AppError::Internal("Task join failed".to_string()))??;
+
+    slack.post_digest(&digest).await?;
+    Ok(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{create_alert_rule, get_alert_rule, upsert_ticket, watch_ticket};
+    use crate::models::{AlertRuleInput, Comparison, TicketMatch};
+    use chrono::NaiveDate;
+
+    fn open_ticket(key: &str, priority: &str, assignee: Option<&str>) -> Ticket {
+        Ticket {
+            priority: priority.to_string(),
+            assignee: assignee.map(|a| a.to_string()),
+            ..test_support::ticket(key)
+        }
+    }
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 1, day)
+            .and_then(|d| d.and_hms_opt(hour, 0, 0))
+            .expect("valid datetime")
+    }
+
+    #[test]
+    fn critical_assigned_to_me_fires_once() {
+        let conn = setup_db();
+        upsert_ticket(&conn, &open_ticket("OPS-1", "Critical", Some("Dana"))).expect("OPS-1");
+        upsert_ticket(&conn, &open_ticket("OPS-2", "Critical", Some("Sam"))).expect("OPS-2");
+        let settings = AlertSettings {
+            my_name: Some("Dana".to_string()),
+            ..Default::default()
+        };
+
+        let alerts = evaluate_alerts(&conn, &settings, at(6, 10)).expect("alerts");
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].ticket_key.as_deref(), Some("OPS-1"));
+
+        assert!(evaluate_alerts(&conn, &settings, at(6, 11))
+            .expect("alerts")
+            .is_empty());
+    }
+
+    #[test]
+    fn sla_warns_then_breaches() {
+        let conn = setup_db();
+        upsert_ticket(&conn, &open_ticket("OPS-1", "High", None)).expect("OPS-1");
+        let mut settings = AlertSettings::default();
+        settings.sla_targets_hours.insert("High".to_string(), 10.0);
+
+        // 4 business hours in: below the 80% warning threshold
+        assert!(evaluate_alerts(&conn, &settings, at(6, 13))
+            .expect("alerts")
+            .is_empty());
+
+        // 8 business hours in: warning
+        let alerts = evaluate_alerts(&conn, &settings, at(6, 17)).expect("alerts");
+        assert_eq!(alerts.len(), 1);
+        assert!(alerts[0].title.starts_with("SLA about to breach"));
+
+        // 11 business hours in: breach
+        let alerts = evaluate_alerts(&conn, &settings, at(7, 12)).expect("alerts");
+        assert_eq!(alerts.len(), 1);
+        assert!(alerts[0].title.starts_with("SLA breached"));
+
+        assert!(sla_breaches(&conn, &settings, at(6, 17))
+            .expect("breaches")
+            .is_empty());
+        let breaches = sla_breaches(&conn, &settings, at(7, 12)).expect("breaches");
+        assert_eq!(breaches.len(), 1);
+        assert_eq!(breaches[0].jira_key, "OPS-1");
+    }
+
+    #[test]
+    fn status_dwell_limit_fires_once_per_stretch() {
+        let conn = setup_db();
+        let mut waiting = open_ticket("OPS-1", "Low", None);
+        waiting.status = "Waiting for Support".to_string();
+        upsert_ticket(&conn, &waiting).expect("OPS-1");
+        let mut settings = AlertSettings::default();
+        settings
+            .status_dwell_limits_hours
+            .insert("Waiting for Support".to_string(), 16.0);
+
+        assert!(evaluate_alerts(&conn, &settings, at(7, 17))
+            .expect("alerts")
+            .is_empty());
+        let alerts = evaluate_alerts(&conn, &settings, at(8, 10)).expect("alerts");
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].title, "Too long in Waiting for Support: OPS-1");
+        assert!(evaluate_alerts(&conn, &settings, at(9, 10))
+            .expect("alerts")
+            .is_empty());
+    }
+
+    #[test]
+    fn watched_change_fires_once_per_change() {
+        let conn = setup_db();
+        upsert_ticket(&conn, &open_ticket("OPS-1", "High", None)).expect("OPS-1");
+        watch_ticket(&conn, "OPS-1").expect("watch");
+
+        let mut updated = open_ticket("OPS-1", "High", None);
+        updated.status = "In Progress".to_string();
+        upsert_ticket(&conn, &updated).expect("update");
+        crate::db::mark_watchlist_changes(&conn).expect("mark");
+
+        let settings = AlertSettings::default();
+        let alerts = evaluate_alerts(&conn, &settings, at(6, 10)).expect("alerts");
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].body, "status: Open → In Progress");
+        assert!(evaluate_alerts(&conn, &settings, at(6, 11))
+            .expect("alerts")
+            .is_empty());
+    }
+
+    fn rule(name: &str, condition: AlertCondition) -> AlertRuleInput {
+        AlertRuleInput {
+            name: name.to_string(),
+            condition,
+            channel: AlertChannel::Desktop,
+            throttle_minutes: 60,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn count_rule_fires_and_throttles() {
+        let conn = setup_db();
+        for key in ["OPS-1", "OPS-2", "OPS-3"] {
+            upsert_ticket(&conn, &open_ticket(key, "Critical", None)).expect("ticket");
+        }
+        let created = create_alert_rule(
+            &conn,
+            &rule(
+                "Too many criticals",
+                AlertCondition::Count {
+                    tickets: TicketMatch {
+                        priority: Some("critical".to_string()),
+                        ..Default::default()
+                    },
+                    op: Comparison::Gt,
+                    threshold: 2.0,
+                },
+            ),
+        )
+        .expect("rule");
+
+        let hits = evaluate_alert_rules(&conn, at(6, 10)).expect("hits");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].alert.title, "Too many criticals");
+        assert_eq!(hits[0].alert.body, "3 matching tickets (> 2)");
+
+        // Inside the throttle window
+        assert!(evaluate_alert_rules(&conn, at(6, 10))
+            .expect("hits")
+            .is_empty());
+        assert!(get_alert_rule(&conn, created.id)
+            .expect("rule")
+            .and_then(|r| r.last_fired_at)
+            .is_some());
+
+        // After the window
+        assert_eq!(
+            evaluate_alert_rules(&conn, at(6, 12)).expect("hits").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn idle_rule_lists_stale_tickets() {
+        let conn = setup_db();
+        upsert_ticket(&conn, &open_ticket("OPS-1", "High", None)).expect("OPS-1");
+        create_alert_rule(
+            &conn,
+            &rule(
+                "Idle tickets",
+                AlertCondition::Idle {
+                    tickets: TicketMatch::default(),
+                    days: 7.0,
+                },
+            ),
+        )
+        .expect("rule");
+
+        assert!(evaluate_alert_rules(&conn, at(10, 9))
+            .expect("hits")
+            .is_empty());
+
+        let hits = evaluate_alert_rules(&conn, at(14, 9)).expect("hits");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(
+            hits[0].alert.body,
+            "1 tickets idle for more than 7 days: OPS-1"
+        );
+    }
+
+    #[test]
+    fn inflow_spike_rule_fires_on_an_unusual_day() {
+        let conn = setup_db();
+        // Five tickets on Jan 6 against an empty four-week baseline
+        for key in ["OPS-1", "OPS-2", "OPS-3", "OPS-4", "OPS-5"] {
+            upsert_ticket(&conn, &open_ticket(key, "High", None)).expect("ticket");
+        }
+        create_alert_rule(
+            &conn,
+            &rule(
+                "Inflow spike",
+                AlertCondition::InflowSpike { z_threshold: 3.0 },
+            ),
+        )
+        .expect("rule");
+
+        assert!(evaluate_alert_rules(&conn, at(7, 9))
+            .expect("hits")
+            .is_empty());
+
+        let hits = evaluate_alert_rules(&conn, at(6, 18)).expect("hits");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(
+            hits[0].alert.body,
+            "5 tickets created today, against 0.0 a day over the last 28 days (z = 5.0)"
+        );
+    }
+
+    #[test]
+    fn backlog_growth_rule_needs_sustained_ratio() {
+        let conn = setup_db();
+        // Three open tickets created on Jan 6 and nothing resolved
+        for key in ["OPS-1", "OPS-2", "OPS-3"] {
+            upsert_ticket(&conn, &open_ticket(key, "High", None)).expect("ticket");
+        }
+        create_alert_rule(
+            &conn,
+            &rule(
+                "Backlog growing",
+                AlertCondition::BacklogGrowth {
+                    threshold: 1.5,
+                    weeks: 2,
+                },
+            ),
+        )
+        .expect("rule");
+
+        // The window ending a week earlier predates every ticket
+        assert!(evaluate_alert_rules(&conn, at(8, 9))
+            .expect("hits")
+            .is_empty());
+
+        let hits = evaluate_alert_rules(&conn, at(15, 9)).expect("hits");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(
+            hits[0].alert.body,
+            "Created/resolved ratio above 1.5 for 2 weeks (3 created, 0 resolved in the last 4 weeks)"
+        );
+    }
+}
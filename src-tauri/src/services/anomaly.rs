@@ -0,0 +1,116 @@
+use crate::models::{InflowAnomaly, Ticket};
+use crate::services::time_calc::parse_timestamp;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use std::collections::HashMap;
+
+/// Days of history each day's inflow is compared against.
+pub const BASELINE_DAYS: u32 = 28;
+
+/// Standard deviations above the baseline mean that count as a spike.
+pub const DEFAULT_Z_THRESHOLD: f64 = 3.0;
+
+/// Floor on the baseline spread, so a flat baseline (e.g. no tickets at all)
+/// doesn't turn one extra ticket into an infinite z-score.
+const MIN_STDDEV: f64 = 1.0;
+
+/// Tickets created on each of the `days` days ending at `last_day`, oldest first.
+fn daily_created_counts(
+    tickets: &[Ticket],
+    last_day: NaiveDate,
+    days: u32,
+) -> Vec<(NaiveDate, u32)> {
+    let first_day = last_day - Duration::days(i64::from(days) - 1);
+    let mut counts: HashMap<NaiveDate, u32> = HashMap::new();
+    for created in tickets
+        .iter()
+        .filter_map(|t| parse_timestamp(&t.created_at))
+    {
+        let day = created.date();
+        if day >= first_day && day <= last_day {
+            *counts.entry(day).or_default() += 1;
+        }
+    }
+
+    first_day
+        .iter_days()
+        .take(days as usize)
+        .map(|day| (day, counts.get(&day).copied().unwrap_or_default()))
+        .collect()
+}
+
+/// Days among the `days` ending at `now` on which the created count sits
+/// more than `z_threshold` standard deviations above the mean of the
+/// `baseline_days` before it, oldest first. Only spikes are reported, not dips.
+pub fn inflow_anomalies(
+    tickets: &[Ticket],
+    now: NaiveDateTime,
+    days: u32,
+    baseline_days: u32,
+    z_threshold: f64,
+) -> Vec<InflowAnomaly> {
+    let baseline_days = baseline_days.max(1) as usize;
+    let series = daily_created_counts(tickets, now.date(), days + baseline_days as u32);
+
+    series
+        .windows(baseline_days + 1)
+        .filter_map(|window| {
+            let (baseline, current) = window.split_at(baseline_days);
+            let (date, created) = current[0];
+            let counts: Vec<f64> = baseline.iter().map(|(_, c)| f64::from(*c)).collect();
+            let mean = counts.iter().sum::<f64>() / counts.len() as f64;
+            let variance =
+                counts.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / counts.len() as f64;
+            let stddev = variance.sqrt();
+            let z_score = (f64::from(created) - mean) / stddev.max(MIN_STDDEV);
+
+            (z_score > z_threshold).then(|| InflowAnomaly {
+                date: date.format("%Y-%m-%d").to_string(),
+                created,
+                baseline_mean: mean,
+                baseline_stddev: stddev,
+                z_score,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn created_on(day: NaiveDate, count: usize) -> Vec<Ticket> {
+        (0..count)
+            .map(|n| Ticket {
+                jira_key: format!("OPS-{}-{}", day, n),
+                created_at: format!("{}T10:00:00Z", day),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flags_days_well_above_the_trailing_baseline() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 31).expect("valid date");
+        let now = today.and_hms_opt(18, 0, 0).expect("valid time");
+        let mut tickets = Vec::new();
+        // One ticket a day for the baseline, then a small bump and a spike
+        for offset in 3..=40 {
+            tickets.extend(created_on(today - Duration::days(offset), 1));
+        }
+        tickets.extend(created_on(today - Duration::days(2), 3));
+        tickets.extend(created_on(today - Duration::days(1), 1));
+        tickets.extend(created_on(today, 6));
+
+        let anomalies = inflow_anomalies(&tickets, now, 7, BASELINE_DAYS, DEFAULT_Z_THRESHOLD);
+        let flagged: Vec<(&str, u32)> = anomalies
+            .iter()
+            .map(|a| (a.date.as_str(), a.created))
+            .collect();
+        // The bump of 3 is 2 (floored) deviations out; the spike of 6 is ~4.9
+        assert_eq!(flagged, vec![("2025-03-31", 6)]);
+        assert!((anomalies[0].baseline_mean - 30.0 / 28.0).abs() < 1e-9);
+        assert!((anomalies[0].z_score - (6.0 - 30.0 / 28.0)).abs() < 1e-9);
+
+        assert!(inflow_anomalies(&tickets, now, 7, BASELINE_DAYS, 5.0).is_empty());
+    }
+}
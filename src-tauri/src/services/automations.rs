@@ -0,0 +1,291 @@
+use crate::db::{
+    enqueue_outbox, get_automation_matches, get_tickets, list_automations, record_automation_run,
+    set_automation_matches, tag_tickets, watch_ticket, DbPool,
+};
+use crate::errors::AppError;
+use crate::jira::client::SOURCE_ID as JIRA_SOURCE_ID;
+use crate::models::{
+    Alert, AlertChannel, AutomationAction, AutomationOutcome, AutomationSettings, OutboxAction,
+    Ticket,
+};
+use crate::services::alerts;
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use std::sync::{Arc, Mutex};
+use tauri_plugin_store::StoreExt;
+
+/// Ticket keys listed in a notification before the rest are summarized.
+const NOTICE_KEYS: usize = 10;
+
+/// A notification an automation asked for, delivered once the run is stored.
+#[derive(Debug)]
+pub struct AutomationNotice {
+    pub automation_id: i64,
+    pub channel: AlertChannel,
+    pub alert: Alert,
+}
+
+/// Runs every enabled automation against the tickets that newly match its
+/// filter, i.e. that didn't match on the previous run. Every action taken is
+/// logged in `automation_log`. Jira actions are queued in the outbox when
+/// `write_back` is on and skipped otherwise.
+pub fn run_automations(
+    conn: &Connection,
+    write_back: bool,
+    now: DateTime<Utc>,
+) -> Result<Vec<AutomationNotice>, AppError> {
+    let ran_at = now.to_rfc3339();
+    let mut notices = Vec::new();
+
+    for automation in list_automations(conn)?.into_iter().filter(|a| a.enabled) {
+        let tickets = get_tickets(conn, &automation.filter)?;
+        let previous = get_automation_matches(conn, automation.id)?;
+        let new: Vec<&Ticket> = tickets
+            .iter()
+            .filter(|ticket| !previous.contains(&ticket.jira_key))
+            .collect();
+
+        for ticket in &new {
+            for action in &automation.actions {
+                let (outcome, detail) = apply_action(conn, ticket, action, write_back, now)
+                    .unwrap_or_else(|e| (AutomationOutcome::Failed, Some(e.to_string())));
+                record_automation_run(
+                    conn,
+                    &automation,
+                    &ticket.jira_key,
+                    action,
+                    outcome,
+                    detail.as_deref(),
+                    &ran_at,
+                )?;
+            }
+        }
+
+        if !new.is_empty() {
+            for action in &automation.actions {
+                if let AutomationAction::Notify { channel } = action {
+                    notices.push(AutomationNotice {
+                        automation_id: automation.id,
+                        channel: *channel,
+                        alert: notice_alert(&automation.name, &new),
+                    });
+                }
+            }
+        }
+
+        let keys: Vec<String> = tickets.into_iter().map(|ticket| ticket.jira_key).collect();
+        set_automation_matches(conn, automation.id, &keys)?;
+    }
+
+    Ok(notices)
+}
+
+fn apply_action(
+    conn: &Connection,
+    ticket: &Ticket,
+    action: &AutomationAction,
+    write_back: bool,
+    now: DateTime<Utc>,
+) -> Result<(AutomationOutcome, Option<String>), AppError> {
+    let jira_action = match action {
+        AutomationAction::AddTag { tag } => {
+            tag_tickets(conn, std::slice::from_ref(&ticket.jira_key), tag)?;
+            return Ok((AutomationOutcome::Applied, None));
+        }
+        AutomationAction::Watch => {
+            watch_ticket(conn, &ticket.jira_key)?;
+            return Ok((AutomationOutcome::Applied, None));
+        }
+        // Delivered once per run rather than per ticket
+        AutomationAction::Notify { .. } => return Ok((AutomationOutcome::Applied, None)),
+        AutomationAction::Comment { body } => OutboxAction::Comment { body: body.clone() },
+        AutomationAction::Transition { transition_id } => OutboxAction::Transition {
+            transition_id: transition_id.clone(),
+        },
+    };
+
+    if !write_back {
+        return Ok((
+            AutomationOutcome::Skipped,
+            Some("Jira write-back is disabled".to_string()),
+        ));
+    }
+    if ticket.source != JIRA_SOURCE_ID {
+        return Ok((
+            AutomationOutcome::Skipped,
+            Some(format!("{} tickets can't be written back", ticket.source)),
+        ));
+    }
+    let id = enqueue_outbox(conn, &ticket.jira_key, &jira_action, now)?;
+    Ok((
+        AutomationOutcome::Queued,
+        Some(format!("Outbox item {}", id)),
+    ))
+}
+
+fn notice_alert(name: &str, tickets: &[&Ticket]) -> Alert {
+    let mut lines: Vec<String> = tickets
+        .iter()
+        .take(NOTICE_KEYS)
+        .map(|ticket| format!("{}: {}", ticket.jira_key, ticket.summary))
+        .collect();
+    if tickets.len() > NOTICE_KEYS {
+        lines.push(format!("and {} more", tickets.len() - NOTICE_KEYS));
+    }
+
+    Alert {
+        title: name.to_string(),
+        body: lines.join("\n"),
+        ticket_key: match tickets {
+            [ticket] => Some(ticket.jira_key.clone()),
+            _ => None,
+        },
+    }
+}
+
+pub fn load_automation_settings(
+    app_handle: &tauri::AppHandle,
+) -> Result<AutomationSettings, AppError> {
+    let store = app_handle
+        .store("settings.json")
+        .map_err(|e| AppError::Config(format!("Failed to access store: {}", e)))?;
+
+    match store.get("automations") {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|e| AppError::Config(format!("Failed to parse settings: {}", e))),
+        None => Ok(AutomationSettings::default()),
+    }
+}
+
+/// Runs the automations after a sync has been persisted and delivers the
+/// notifications they asked for. Returns how many were delivered.
+pub async fn run_post_sync_automations(
+    db_pool: Arc<Mutex<Connection>>,
+    app_handle: &tauri::AppHandle,
+) -> Result<usize, AppError> {
+    let write_back = load_automation_settings(app_handle)?.write_back;
+    let notices = DbPool(db_pool)
+        .run(move |conn| run_automations(conn, write_back, Utc::now()))
+        .await?;
+
+    let slack = if notices
+        .iter()
+        .any(|notice| notice.channel == AlertChannel::Slack)
+    {
+        alerts::slack_notifier(app_handle)?
+    } else {
+        None
+    };
+
+    for notice in &notices {
+        match notice.channel {
+            AlertChannel::Desktop => {
+                alerts::deliver_desktop(app_handle, std::slice::from_ref(&notice.alert))
+            }
+            AlertChannel::Slack => match &slack {
+                Some(slack) => {
+                    if let Err(e) = slack.post_alert(&notice.alert).await {
+                        log::warn!(
+                            "Slack notice for automation {} failed: {}",
+                            notice.automation_id,
+                            e
+                        );
+                    }
+                }
+                None => log::warn!(
+                    "Automation {} notifies Slack but no webhook is configured",
+                    notice.automation_id
+                ),
+            },
+        }
+    }
+
+    Ok(notices.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{
+        create_automation, get_automation_log, get_outbox, get_ticket_tags, list_watched_tickets,
+        upsert_ticket,
+    };
+    use crate::models::{AutomationInput, TicketFilter};
+
+    fn ticket(key: &str, priority: &str) -> Ticket {
+        Ticket {
+            priority: priority.to_string(),
+            ..test_support::ticket(key)
+        }
+    }
+
+    #[test]
+    fn automations_act_once_per_newly_matching_ticket() {
+        let conn = setup_db();
+        upsert_ticket(&conn, &ticket("OPS-1", "Critical")).expect("ticket");
+        upsert_ticket(&conn, &ticket("OPS-2", "Low")).expect("ticket");
+        create_automation(
+            &conn,
+            &AutomationInput {
+                name: "Escalate criticals".to_string(),
+                filter: TicketFilter {
+                    priorities: vec!["Critical".to_string()],
+                    ..Default::default()
+                },
+                actions: vec![
+                    AutomationAction::AddTag {
+                        tag: "escalated".to_string(),
+                    },
+                    AutomationAction::Watch,
+                    AutomationAction::Notify {
+                        channel: AlertChannel::Desktop,
+                    },
+                    AutomationAction::Comment {
+                        body: "Escalated to on-call".to_string(),
+                    },
+                ],
+                enabled: true,
+            },
+        )
+        .expect("automation");
+        let now = Utc::now();
+
+        let notices = run_automations(&conn, false, now).expect("run");
+        assert_eq!(notices.len(), 1);
+        assert_eq!(notices[0].alert.ticket_key.as_deref(), Some("OPS-1"));
+        assert_eq!(
+            get_ticket_tags(&conn, "OPS-1").expect("tags"),
+            vec!["escalated"]
+        );
+        assert!(get_ticket_tags(&conn, "OPS-2").expect("tags").is_empty());
+        assert_eq!(list_watched_tickets(&conn).expect("watched").len(), 1);
+        let outcomes = |conn: &Connection| {
+            get_automation_log(conn, 100)
+                .expect("log")
+                .into_iter()
+                .map(|entry| (entry.ticket_key, entry.outcome))
+                .collect::<Vec<_>>()
+        };
+        let log = outcomes(&conn);
+        assert_eq!(log.len(), 4);
+        // Newest first, so the comment is at the top
+        assert_eq!(log[0], ("OPS-1".to_string(), AutomationOutcome::Skipped));
+        assert!(get_outbox(&conn).expect("outbox").is_empty());
+
+        // Still matching, so nothing happens again
+        assert!(run_automations(&conn, true, now).expect("run").is_empty());
+        assert_eq!(outcomes(&conn).len(), 4);
+
+        // A newly matching ticket is acted on, with write-back on this time
+        upsert_ticket(&conn, &ticket("OPS-2", "Critical")).expect("ticket");
+        let notices = run_automations(&conn, true, now).expect("run");
+        assert_eq!(notices[0].alert.ticket_key.as_deref(), Some("OPS-2"));
+        let log = outcomes(&conn);
+        assert_eq!(log.len(), 8);
+        assert_eq!(log[0], ("OPS-2".to_string(), AutomationOutcome::Queued));
+        let outbox = get_outbox(&conn).expect("outbox");
+        assert_eq!(outbox.len(), 1);
+        assert_eq!(outbox[0].ticket_key, "OPS-2");
+    }
+}
@@ -0,0 +1,163 @@
+use crate::db::{get_aggregations, get_tickets};
+use crate::errors::{AppError, DbError};
+use crate::models::{AggregationResult, DurationMode, TicketFilter};
+use crate::services::metrics::MetricRegistry;
+use rusqlite::Connection;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Aggregations include "now"-relative figures (stale, overdue), so even an
+/// unchanged database is recomputed after this long.
+const MAX_AGE: Duration = Duration::from_secs(5 * 60);
+
+/// Distinct filters kept at once; the oldest entry is evicted beyond this.
+const MAX_ENTRIES: usize = 32;
+
+struct CachedAggregation {
+    /// `total_changes()` of the connection when the result was computed.
+    changes: i64,
+    computed_at: Instant,
+    result: AggregationResult,
+}
+
+/// Memoized dashboard aggregations, keyed by a hash of the filter and
+/// duration mode. Entries are only served while the connection has seen no
+/// writes since they were computed, so a sync or local edit invalidates
+/// them without every write path having to know about the cache. Results
+/// include the registered plugin metrics.
+#[derive(Clone, Default)]
+pub struct AggregationCache {
+    entries: Arc<Mutex<HashMap<u64, CachedAggregation>>>,
+    metrics: MetricRegistry,
+}
+
+fn cache_key(filter: &TicketFilter, mode: DurationMode) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(&(filter, mode))
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Rows inserted, updated or deleted through `conn` since it was opened.
+fn total_changes(conn: &Connection) -> Result<i64, AppError> {
+    Ok(conn
+        .query_row("SELECT total_changes()", [], |row| row.get(0))
+        .map_err(DbError::from)?)
+}
+
+impl AggregationCache {
+    pub fn new(metrics: MetricRegistry) -> Self {
+        AggregationCache {
+            entries: Arc::default(),
+            metrics,
+        }
+    }
+
+    /// Aggregations for `filter`, from the cache when nothing has been
+    /// written since they were computed.
+    pub fn get_or_compute(
+        &self,
+        conn: &Connection,
+        filter: &TicketFilter,
+        mode: DurationMode,
+    ) -> Result<AggregationResult, AppError> {
+        let key = cache_key(filter, mode);
+        let changes = total_changes(conn)?;
+
+        let mut entries = self.entries.lock().map_err(|_| DbError::LockFailed)?;
+        if let Some(cached) = entries.get(&key) {
+            if cached.changes == changes && cached.computed_at.elapsed() < MAX_AGE {
+                return Ok(cached.result.clone());
+            }
+        }
+
+        let mut result = get_aggregations(conn, filter, mode)?;
+        if !self.metrics.is_empty() {
+            result.custom_metrics = self.metrics.compute(&get_tickets(conn, filter)?, mode);
+        }
+        entries.retain(|_, cached| cached.changes == changes);
+        if entries.len() >= MAX_ENTRIES {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, cached)| cached.computed_at)
+                .map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            key,
+            CachedAggregation {
+                changes,
+                computed_at: Instant::now(),
+                result: result.clone(),
+            },
+        );
+        Ok(result)
+    }
+
+    /// Drops every entry. Needed when the connection is replaced, since the
+    /// new one starts counting changes from zero again.
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{setup_db, ticket};
+    use crate::db::upsert_ticket;
+
+    #[test]
+    fn cached_result_is_reused_until_the_database_changes() {
+        let conn = setup_db();
+        upsert_ticket(&conn, &ticket("OPS-1")).expect("ticket");
+        let cache = AggregationCache::default();
+        let filter = TicketFilter::default();
+        let total = |cache: &AggregationCache| {
+            cache
+                .get_or_compute(&conn, &filter, DurationMode::Calendar)
+                .expect("aggregations")
+                .summary
+                .total_tickets
+        };
+
+        assert_eq!(total(&cache), 1);
+        // Written behind the cache's back, but total_changes() still moves
+        conn.execute("DELETE FROM tickets", []).expect("delete");
+        assert_eq!(total(&cache), 0);
+
+        upsert_ticket(&conn, &ticket("OPS-2")).expect("ticket");
+        assert_eq!(total(&cache), 1);
+        assert_eq!(cache.entries.lock().expect("cache lock").len(), 1);
+    }
+
+    #[test]
+    fn filters_and_modes_are_cached_separately() {
+        let filter = TicketFilter::default();
+        let scoped = TicketFilter {
+            tags: vec!["vip".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            cache_key(&filter, DurationMode::Calendar),
+            cache_key(&filter.clone(), DurationMode::Calendar)
+        );
+        assert_ne!(
+            cache_key(&filter, DurationMode::Calendar),
+            cache_key(&scoped, DurationMode::Calendar)
+        );
+        assert_ne!(
+            cache_key(&filter, DurationMode::Calendar),
+            cache_key(&filter, DurationMode::Business)
+        );
+    }
+}
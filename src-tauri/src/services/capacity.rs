@@ -0,0 +1,181 @@
+use crate::db::get_tickets;
+use crate::errors::AppError;
+use crate::models::{CapacityScenario, CapacitySimulation, TicketFilter};
+use crate::services::forecast;
+use crate::services::time_calc::parse_timestamp;
+use chrono::{Duration, NaiveDateTime};
+use rusqlite::Connection;
+use std::collections::BTreeSet;
+
+/// Weeks of history the throughput and inflow rates are averaged over.
+const CAPACITY_HISTORY_WEEKS: u32 = 8;
+
+/// Projects `filter`'s open backlog over the next `weeks` weeks for every
+/// staffing level from today's team to `extra_people` more (or fewer, when
+/// negative). Each person resolves the team's recent average per person, and
+/// tickets keep arriving at the recent inflow rate.
+pub fn simulate_capacity(
+    conn: &Connection,
+    filter: &TicketFilter,
+    extra_people: i32,
+    weeks: u32,
+    now: NaiveDateTime,
+) -> Result<CapacitySimulation, AppError> {
+    let window_start = now - Duration::weeks(i64::from(CAPACITY_HISTORY_WEEKS));
+    let in_window =
+        |at: &str| parse_timestamp(at).is_some_and(|at| at >= window_start && at <= now);
+
+    let mut open_tickets = 0u32;
+    let mut created = 0u32;
+    let mut resolved = 0u32;
+    let mut people = BTreeSet::new();
+    for ticket in get_tickets(conn, filter)? {
+        if in_window(&ticket.created_at) {
+            created += 1;
+        }
+        match ticket.resolved_at.as_deref() {
+            None => open_tickets += 1,
+            Some(resolved_at) if in_window(resolved_at) => {
+                resolved += 1;
+                if let Some(assignee) = ticket.assignee {
+                    people.insert(assignee);
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    let history_weeks = f64::from(CAPACITY_HISTORY_WEEKS);
+    let active_people = people.len() as u32;
+    let per_person = if active_people == 0 {
+        0.0
+    } else {
+        f64::from(resolved) / history_weeks / f64::from(active_people)
+    };
+    let inflow = f64::from(created) / history_weeks;
+
+    let scenarios = (0..=extra_people.unsigned_abs())
+        .map(|step| {
+            let extra = step as i32 * extra_people.signum();
+            let people = (i64::from(active_people) + i64::from(extra)).max(0) as u32;
+            let throughput = per_person * f64::from(people);
+            CapacityScenario {
+                extra_people: extra,
+                people,
+                throughput_per_week: throughput,
+                net_per_week: throughput - inflow,
+                burndown: forecast::burndown(
+                    f64::from(open_tickets),
+                    inflow,
+                    throughput,
+                    weeks,
+                    now,
+                ),
+                clears_by: forecast::completion_date(
+                    f64::from(open_tickets),
+                    throughput - inflow,
+                    now,
+                ),
+            }
+        })
+        .collect();
+
+    Ok(CapacitySimulation {
+        open_tickets,
+        active_people,
+        throughput_per_person_per_week: per_person,
+        inflow_per_week: inflow,
+        history_weeks: CAPACITY_HISTORY_WEEKS,
+        scenarios,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::upsert_ticket;
+    use crate::models::Ticket;
+    use chrono::NaiveDate;
+
+    fn ticket(key: &str, assignee: &str, created_at: &str, resolved_at: Option<&str>) -> Ticket {
+        Ticket {
+            assignee: Some(assignee.to_string()),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            ..test_support::ticket_resolved_at(key, resolved_at)
+        }
+    }
+
+    #[test]
+    fn extra_people_burn_the_backlog_down_faster() {
+        let conn = setup_db();
+        let old = "2024-10-01T09:00:00Z";
+        let mut tickets = Vec::new();
+        // Two people resolve 16 tickets in the 8-week window: 1 per person-week
+        for n in 0..16 {
+            let assignee = if n % 2 == 0 { "Dana" } else { "Sam" };
+            tickets.push(ticket(
+                &format!("OPS-{}", n),
+                assignee,
+                old,
+                Some("2025-02-10T09:00:00Z"),
+            ));
+        }
+        // 8 tickets created in the window, 1 a week; 10 open in total
+        for n in 16..26 {
+            let created_at = if n < 24 { "2025-02-10T09:00:00Z" } else { old };
+            tickets.push(ticket(&format!("OPS-{}", n), "Dana", created_at, None));
+        }
+        // Resolved before the window
+        tickets.push(ticket("OPS-99", "Lee", old, Some("2024-11-01T09:00:00Z")));
+        for t in &tickets {
+            upsert_ticket(&conn, t).expect("ticket");
+        }
+
+        let now = NaiveDate::from_ymd_opt(2025, 3, 3)
+            .and_then(|d| d.and_hms_opt(9, 0, 0))
+            .expect("valid datetime");
+        let simulation =
+            simulate_capacity(&conn, &TicketFilter::default(), 2, 3, now).expect("simulation");
+
+        assert_eq!(simulation.open_tickets, 10);
+        assert_eq!(simulation.active_people, 2);
+        assert_eq!(simulation.throughput_per_person_per_week, 1.0);
+        assert_eq!(simulation.inflow_per_week, 1.0);
+
+        let summary: Vec<(i32, u32, f64, Option<&str>)> = simulation
+            .scenarios
+            .iter()
+            .map(|s| {
+                (
+                    s.extra_people,
+                    s.people,
+                    s.net_per_week,
+                    s.clears_by.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, 2, 1.0, Some("2025-05-12")),
+                (1, 3, 2.0, Some("2025-04-07")),
+                (2, 4, 3.0, Some("2025-03-27")),
+            ]
+        );
+        let open: Vec<f64> = simulation.scenarios[2]
+            .burndown
+            .iter()
+            .map(|point| point.open_tickets)
+            .collect();
+        assert_eq!(open, vec![7.0, 4.0, 1.0]);
+        assert_eq!(simulation.scenarios[2].burndown[0].date, "2025-03-10");
+
+        let fewer =
+            simulate_capacity(&conn, &TicketFilter::default(), -3, 1, now).expect("simulation");
+        let people: Vec<u32> = fewer.scenarios.iter().map(|s| s.people).collect();
+        assert_eq!(people, vec![2, 1, 0, 0]);
+        assert_eq!(fewer.scenarios[3].clears_by, None);
+    }
+}
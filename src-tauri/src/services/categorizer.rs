@@ -1,4 +1,5 @@
 use crate::models::Ticket;
+use async_trait::async_trait;
 use regex::Regex;
 use serde::Deserialize;
 
@@ -39,6 +40,61 @@ pub fn categorize_ticket(ticket: &Ticket, rules: &[CategoryRule]) -> Option<Stri
     None
 }
 
+/// A category chosen for a ticket and how sure the backend is of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryMatch {
+    pub category: String,
+    /// 0.0 to 1.0; rule matches are always 1.0.
+    pub confidence: f64,
+}
+
+/// Assigns categories to freshly fetched or imported tickets. Backends
+/// mustn't fail a sync: one that can't reach its model falls back to
+/// whatever it can decide locally.
+#[async_trait]
+pub trait CategorizerBackend: Send + Sync {
+    /// One entry per ticket, in order; `None` leaves a ticket uncategorized.
+    async fn categorize(&self, tickets: &[Ticket]) -> Vec<Option<CategoryMatch>>;
+}
+
+/// The default backend: the first matching category rule wins.
+pub struct RulesCategorizer {
+    rules: Vec<CategoryRule>,
+}
+
+impl RulesCategorizer {
+    pub fn new(rules: Vec<CategoryRule>) -> Self {
+        Self { rules }
+    }
+}
+
+#[async_trait]
+impl CategorizerBackend for RulesCategorizer {
+    async fn categorize(&self, tickets: &[Ticket]) -> Vec<Option<CategoryMatch>> {
+        tickets
+            .iter()
+            .map(|ticket| rule_match(ticket, &self.rules))
+            .collect()
+    }
+}
+
+/// `categorize_ticket` as a full-confidence match.
+pub fn rule_match(ticket: &Ticket, rules: &[CategoryRule]) -> Option<CategoryMatch> {
+    categorize_ticket(ticket, rules).map(|category| CategoryMatch {
+        category,
+        confidence: 1.0,
+    })
+}
+
+/// Runs `backend` over `tickets` and stores the category and confidence on each.
+pub async fn apply_categories(backend: &dyn CategorizerBackend, tickets: &mut [Ticket]) {
+    let matches = backend.categorize(tickets).await;
+    for (ticket, category) in tickets.iter_mut().zip(matches) {
+        ticket.category_confidence = category.as_ref().map(|c| c.confidence);
+        ticket.category = category.map(|c| c.category);
+    }
+}
+
 fn eval_condition(ticket: &Ticket, cond: &RuleCondition) -> bool {
     let field_value = match cond.field.as_str() {
         "summary" => &ticket.summary,
@@ -83,7 +139,9 @@ mod tests {
             jira_key: "TEST-1".to_string(),
             summary: "User forgot password, needs reset".to_string(),
             status: "Open".to_string(),
+            status_category: None,
             priority: "Medium".to_string(),
+            priority_level: None,
             issue_type: "Task".to_string(),
             assignee: None,
             reporter: None,
@@ -93,6 +151,19 @@ mod tests {
             labels: String::new(),
             project_key: "TEST".to_string(),
             category: None,
+            category_confidence: None,
+            source: "jira".to_string(),
+            custom_fields: String::new(),
+            account_id: None,
+            story_points: None,
+            sprint: None,
+            due_date: None,
+            customer: None,
+            environment: None,
+            escalated: false,
+            components: Vec::new(),
+            fix_versions: Vec::new(),
+            affected_versions: Vec::new(),
         };
 
         let rules = vec![CategoryRule {
@@ -117,7 +188,9 @@ mod tests {
             jira_key: "TEST-1".to_string(),
             summary: "Weird thing".to_string(),
             status: "Open".to_string(),
+            status_category: None,
             priority: "Medium".to_string(),
+            priority_level: None,
             issue_type: "Task".to_string(),
             assignee: None,
             reporter: None,
@@ -127,6 +200,19 @@ mod tests {
             labels: String::new(),
             project_key: "TEST".to_string(),
             category: None,
+            category_confidence: None,
+            source: "jira".to_string(),
+            custom_fields: String::new(),
+            account_id: None,
+            story_points: None,
+            sprint: None,
+            due_date: None,
+            customer: None,
+            environment: None,
+            escalated: false,
+            components: Vec::new(),
+            fix_versions: Vec::new(),
+            affected_versions: Vec::new(),
         };
 
         let rules = vec![CategoryRule {
@@ -143,4 +229,38 @@ mod tests {
         let result = categorize_ticket(&ticket, &rules);
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn rules_backend_stores_full_confidence_matches() {
+        let mut tickets = vec![
+            Ticket {
+                jira_key: "TEST-1".to_string(),
+                summary: "VPN down".to_string(),
+                ..Default::default()
+            },
+            Ticket {
+                jira_key: "TEST-2".to_string(),
+                summary: "Printer jammed".to_string(),
+                category: Some("Stale".to_string()),
+                category_confidence: Some(0.7),
+                ..Default::default()
+            },
+        ];
+        let backend = RulesCategorizer::new(vec![CategoryRule {
+            name: "VPN".to_string(),
+            conditions: vec![RuleCondition {
+                field: "summary".to_string(),
+                operator: "contains".to_string(),
+                value: "vpn".to_string(),
+                case_sensitive: false,
+            }],
+            match_mode: MatchMode::Any,
+        }]);
+
+        tauri::async_runtime::block_on(apply_categories(&backend, &mut tickets));
+        assert_eq!(tickets[0].category.as_deref(), Some("VPN"));
+        assert_eq!(tickets[0].category_confidence, Some(1.0));
+        assert_eq!(tickets[1].category, None);
+        assert_eq!(tickets[1].category_confidence, None);
+    }
 }
@@ -0,0 +1,225 @@
+use crate::models::{Ticket, TicketCluster};
+use crate::services::dedupe::normalize_summary;
+use crate::services::similar::{trigram_similarity, trigrams};
+use crate::services::trending::summary_terms;
+use std::collections::HashMap;
+
+/// Assignment passes before giving up on the medoids settling.
+const MAX_ITERATIONS: usize = 20;
+
+/// Tickets returned per cluster as examples.
+const REPRESENTATIVES: usize = 3;
+
+/// Terms in a cluster's label.
+const LABEL_TERMS: usize = 3;
+
+/// The ticket in `candidates` most similar to all the others, as a medoid.
+fn most_central(candidates: &[usize], similarity: &[Vec<f64>]) -> usize {
+    candidates
+        .iter()
+        .copied()
+        .max_by(|&a, &b| {
+            let total = |i: usize| candidates.iter().map(|&j| similarity[i][j]).sum::<f64>();
+            // Prefer the earlier ticket on ties so results are stable
+            total(a).total_cmp(&total(b)).then(b.cmp(&a))
+        })
+        .unwrap_or_default()
+}
+
+/// Starting medoids: the most central ticket, then repeatedly the ticket
+/// least like any medoid chosen so far.
+fn initial_medoids(similarity: &[Vec<f64>], k: usize) -> Vec<usize> {
+    let all: Vec<usize> = (0..similarity.len()).collect();
+    let mut medoids = vec![most_central(&all, similarity)];
+    while medoids.len() < k {
+        let next = all
+            .iter()
+            .copied()
+            .filter(|i| !medoids.contains(i))
+            .min_by(|&a, &b| {
+                let closest = |i: usize| {
+                    medoids
+                        .iter()
+                        .map(|&m| similarity[i][m])
+                        .fold(f64::MIN, f64::max)
+                };
+                closest(a).total_cmp(&closest(b)).then(a.cmp(&b))
+            });
+        match next {
+            Some(next) => medoids.push(next),
+            None => break,
+        }
+    }
+    medoids
+}
+
+/// Position in `medoids` of the medoid each ticket is most similar to.
+fn assign(similarity: &[Vec<f64>], medoids: &[usize]) -> Vec<usize> {
+    (0..similarity.len())
+        .map(|i| {
+            (0..medoids.len())
+                .max_by(|&a, &b| {
+                    similarity[i][medoids[a]]
+                        .total_cmp(&similarity[i][medoids[b]])
+                        .then(b.cmp(&a))
+                })
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Groups `tickets` into at most `k` clusters of similar summaries with
+/// k-medoids over character-trigram similarity, largest cluster first.
+/// Each cluster is labelled with the terms most of its tickets share.
+pub fn cluster_tickets(tickets: Vec<Ticket>, k: usize) -> Vec<TicketCluster> {
+    if tickets.is_empty() || k == 0 {
+        return Vec::new();
+    }
+    let grams: Vec<_> = tickets
+        .iter()
+        .map(|t| trigrams(&normalize_summary(&t.summary)))
+        .collect();
+    let similarity: Vec<Vec<f64>> = grams
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            grams
+                .iter()
+                .enumerate()
+                .map(|(j, b)| {
+                    if i == j {
+                        1.0
+                    } else {
+                        trigram_similarity(a, b)
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut medoids = initial_medoids(&similarity, k.min(tickets.len()));
+    let mut assignment = assign(&similarity, &medoids);
+    for _ in 0..MAX_ITERATIONS {
+        let updated: Vec<usize> = (0..medoids.len())
+            .map(|cluster| {
+                let members: Vec<usize> = (0..tickets.len())
+                    .filter(|&i| assignment[i] == cluster)
+                    .collect();
+                if members.is_empty() {
+                    medoids[cluster]
+                } else {
+                    most_central(&members, &similarity)
+                }
+            })
+            .collect();
+        if updated == medoids {
+            break;
+        }
+        medoids = updated;
+        assignment = assign(&similarity, &medoids);
+    }
+
+    let mut clusters: Vec<TicketCluster> = medoids
+        .iter()
+        .enumerate()
+        .filter_map(|(cluster, &medoid)| {
+            let mut members: Vec<usize> = (0..tickets.len())
+                .filter(|&i| assignment[i] == cluster)
+                .collect();
+            if members.is_empty() {
+                return None;
+            }
+            members.sort_by(|&a, &b| {
+                similarity[medoid][b]
+                    .total_cmp(&similarity[medoid][a])
+                    .then(a.cmp(&b))
+            });
+
+            let mut term_counts: HashMap<String, usize> = HashMap::new();
+            for &i in &members {
+                for term in summary_terms(&tickets[i].summary) {
+                    *term_counts.entry(term).or_default() += 1;
+                }
+            }
+            let mut terms: Vec<(String, usize)> = term_counts.into_iter().collect();
+            terms.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            let terms: Vec<String> = terms
+                .into_iter()
+                .take(LABEL_TERMS)
+                .map(|(term, _)| term)
+                .collect();
+
+            Some(TicketCluster {
+                label: terms.join(", "),
+                terms,
+                size: members.len() as u32,
+                representatives: members
+                    .iter()
+                    .take(REPRESENTATIVES)
+                    .map(|&i| tickets[i].clone())
+                    .collect(),
+                ticket_keys: members
+                    .iter()
+                    .map(|&i| tickets[i].jira_key.clone())
+                    .collect(),
+            })
+        })
+        .collect();
+
+    clusters.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.label.cmp(&b.label)));
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(key: &str, summary: &str) -> Ticket {
+        Ticket {
+            jira_key: key.to_string(),
+            summary: summary.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn groups_tickets_about_the_same_problem() {
+        let tickets = vec![
+            ticket("OPS-1", "VPN disconnects every hour"),
+            ticket("OPS-2", "Printer out of toner"),
+            ticket("OPS-3", "VPN keeps disconnecting"),
+            ticket("OPS-4", "Outlook password reset"),
+            ticket("OPS-5", "VPN disconnects after sleep"),
+            ticket("OPS-6", "Printer toner empty on floor 3"),
+            ticket("OPS-7", "Password reset for Outlook mobile"),
+        ];
+
+        let clusters = cluster_tickets(tickets.clone(), 3);
+        let mut groups: Vec<Vec<&str>> = clusters
+            .iter()
+            .map(|c| {
+                let mut keys: Vec<&str> = c.ticket_keys.iter().map(String::as_str).collect();
+                keys.sort();
+                keys
+            })
+            .collect();
+        groups.sort();
+        assert_eq!(
+            groups,
+            vec![
+                vec!["OPS-1", "OPS-3", "OPS-5"],
+                vec!["OPS-2", "OPS-6"],
+                vec!["OPS-4", "OPS-7"],
+            ]
+        );
+
+        let vpn = &clusters[0];
+        assert_eq!(vpn.size, 3);
+        assert_eq!(vpn.terms[0], "vpn");
+        assert_eq!(vpn.representatives.len(), 3);
+
+        assert_eq!(cluster_tickets(tickets.clone(), 1)[0].size, 7);
+        assert_eq!(cluster_tickets(tickets, 20).len(), 7);
+        assert!(cluster_tickets(Vec::new(), 3).is_empty());
+    }
+}
@@ -0,0 +1,374 @@
+use crate::errors::AppError;
+use crate::models::{CsvMapping, CsvRowError, Ticket};
+use crate::services::time_calc::parse_timestamp;
+use chrono::{NaiveDate, NaiveDateTime};
+use csv::StringRecord;
+use std::io::Read;
+
+/// Date-time layouts tried, in order, when no `date_format` is configured.
+/// Month-first comes before day-first, so a column is only read as day-first
+/// when one of its values rules month-first out (e.g. `25/01/2025`).
+const DATETIME_FORMATS: [&str; 9] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%m/%d/%Y %H:%M:%S",
+    "%m/%d/%Y %H:%M",
+    "%d/%m/%Y %H:%M:%S",
+    "%d/%m/%Y %H:%M",
+    "%d.%m.%Y %H:%M:%S",
+    "%d.%m.%Y %H:%M",
+];
+const DATE_FORMATS: [&str; 6] = [
+    "%Y-%m-%d",
+    "%m/%d/%Y",
+    "%d/%m/%Y",
+    "%d.%m.%Y",
+    "%d %b %Y",
+    "%b %d, %Y",
+];
+
+/// How the values of one date column are parsed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DateFormat {
+    /// RFC 3339 or Jira-style `+0000` offsets.
+    Offset,
+    /// Naive date-time, taken as UTC.
+    DateTime(&'static str),
+    /// Date only, taken as midnight UTC.
+    Date(&'static str),
+    /// The mapping's explicit `date_format`.
+    Custom,
+}
+
+/// Header positions of the mapped columns.
+struct Columns {
+    key: usize,
+    summary: usize,
+    created_at: usize,
+    status: Option<usize>,
+    priority: Option<usize>,
+    issue_type: Option<usize>,
+    assignee: Option<usize>,
+    reporter: Option<usize>,
+    updated_at: Option<usize>,
+    resolved_at: Option<usize>,
+    labels: Option<usize>,
+    project_key: Option<usize>,
+    story_points: Option<usize>,
+    sprint: Option<usize>,
+}
+
+impl Columns {
+    fn contains(&self, idx: usize) -> bool {
+        [self.key, self.summary, self.created_at].contains(&idx)
+            || [
+                self.status,
+                self.priority,
+                self.issue_type,
+                self.assignee,
+                self.reporter,
+                self.updated_at,
+                self.resolved_at,
+                self.labels,
+                self.project_key,
+                self.story_points,
+                self.sprint,
+            ]
+            .contains(&Some(idx))
+    }
+}
+
+/// Detected formats of the date columns, in `Columns` order.
+struct DateFormats {
+    created_at: DateFormat,
+    updated_at: Option<DateFormat>,
+    resolved_at: Option<DateFormat>,
+}
+
+/// Tickets read from a CSV file, plus the rows that were skipped and why.
+pub struct ParsedCsv {
+    pub tickets: Vec<Ticket>,
+    pub skipped: Vec<CsvRowError>,
+}
+
+/// Reads tickets from CSV according to `mapping`. Header problems and
+/// undetectable date columns fail the whole import; bad rows are skipped.
+pub fn parse_csv<R: Read>(reader: R, mapping: &CsvMapping) -> Result<ParsedCsv, AppError> {
+    if !mapping.delimiter.is_ascii() {
+        return Err(AppError::Config(
+            "CSV delimiter must be an ASCII character".to_string(),
+        ));
+    }
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(mapping.delimiter as u8)
+        .flexible(true)
+        .from_reader(reader);
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| AppError::Config(format!("Failed to read CSV header: {}", e)))?
+        .iter()
+        .map(|h| h.trim().to_string())
+        .collect();
+    let column = |name: &str| -> Result<usize, AppError> {
+        headers
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| AppError::Config(format!("CSV has no column named '{}'", name)))
+    };
+    let optional = |name: &Option<String>| name.as_deref().map(column).transpose();
+
+    let columns = Columns {
+        key: column(&mapping.key)?,
+        summary: column(&mapping.summary)?,
+        created_at: column(&mapping.created_at)?,
+        status: optional(&mapping.status)?,
+        priority: optional(&mapping.priority)?,
+        issue_type: optional(&mapping.issue_type)?,
+        assignee: optional(&mapping.assignee)?,
+        reporter: optional(&mapping.reporter)?,
+        updated_at: optional(&mapping.updated_at)?,
+        resolved_at: optional(&mapping.resolved_at)?,
+        labels: optional(&mapping.labels)?,
+        project_key: optional(&mapping.project_key)?,
+        story_points: optional(&mapping.story_points)?,
+        sprint: optional(&mapping.sprint)?,
+    };
+
+    let records = reader
+        .records()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Config(format!("Failed to read CSV: {}", e)))?;
+
+    let format_for = |col: usize| -> Result<DateFormat, AppError> {
+        if mapping.date_format.is_some() {
+            return Ok(DateFormat::Custom);
+        }
+        let values: Vec<&str> = records
+            .iter()
+            .filter_map(|r| r.get(col))
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .collect();
+        detect_date_format(&values).ok_or_else(|| {
+            AppError::Config(format!(
+                "Couldn't detect the date format of column '{}'; set date_format",
+                headers[col]
+            ))
+        })
+    };
+    let formats = DateFormats {
+        created_at: format_for(columns.created_at)?,
+        updated_at: columns.updated_at.map(format_for).transpose()?,
+        resolved_at: columns.resolved_at.map(format_for).transpose()?,
+    };
+
+    let mut tickets = Vec::with_capacity(records.len());
+    let mut skipped = Vec::new();
+    for record in &records {
+        match map_row(record, &headers, &columns, &formats, mapping) {
+            Ok(ticket) => tickets.push(ticket),
+            Err(message) => skipped.push(CsvRowError {
+                line: record.position().map_or(0, |p| p.line()),
+                message,
+            }),
+        }
+    }
+
+    Ok(ParsedCsv { tickets, skipped })
+}
+
+fn map_row(
+    record: &StringRecord,
+    headers: &[String],
+    columns: &Columns,
+    formats: &DateFormats,
+    mapping: &CsvMapping,
+) -> Result<Ticket, String> {
+    let field = |col: usize| record.get(col).map(str::trim).unwrap_or_default();
+    let optional =
+        |col: Option<usize>| col.map(field).filter(|v| !v.is_empty()).map(str::to_string);
+    let date = |col: usize, format: DateFormat| -> Result<Option<String>, String> {
+        let value = field(col);
+        if value.is_empty() {
+            return Ok(None);
+        }
+        parse_date(value, format, mapping.date_format.as_deref())
+            .map(|dt| Some(dt.and_utc().to_rfc3339()))
+            .ok_or_else(|| format!("Invalid date in '{}': {}", headers[col], value))
+    };
+    let optional_date = |col: Option<usize>, format: Option<DateFormat>| match (col, format) {
+        (Some(col), Some(format)) => date(col, format),
+        _ => Ok(None),
+    };
+
+    let key = field(columns.key);
+    if key.is_empty() {
+        return Err(format!("Missing '{}'", headers[columns.key]));
+    }
+    let created_at = date(columns.created_at, formats.created_at)?
+        .ok_or_else(|| format!("Missing '{}'", headers[columns.created_at]))?;
+    let updated_at = optional_date(columns.updated_at, formats.updated_at)?;
+    let resolved_at = optional_date(columns.resolved_at, formats.resolved_at)?;
+    let story_points = optional(columns.story_points)
+        .map(|value| {
+            value
+                .parse::<f64>()
+                .map_err(|_| format!("Invalid number in story points: {}", value))
+        })
+        .transpose()?;
+
+    // Keep whatever the mapping doesn't cover so it isn't lost
+    let custom_fields: serde_json::Map<String, serde_json::Value> = headers
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !columns.contains(*idx))
+        .filter_map(|(idx, header)| {
+            let value = field(idx);
+            (!value.is_empty()).then(|| (header.clone(), value.into()))
+        })
+        .collect();
+
+    let status = optional(columns.status).unwrap_or_else(|| {
+        if resolved_at.is_some() {
+            "Done"
+        } else {
+            "Open"
+        }
+        .to_string()
+    });
+
+    Ok(Ticket {
+        jira_key: format!("{}{}", mapping.key_prefix.as_deref().unwrap_or(""), key),
+        summary: field(columns.summary).to_string(),
+        status,
+        priority: optional(columns.priority).unwrap_or_else(|| "Medium".to_string()),
+        issue_type: optional(columns.issue_type).unwrap_or_else(|| "Task".to_string()),
+        assignee: optional(columns.assignee),
+        reporter: optional(columns.reporter),
+        updated_at: updated_at.unwrap_or_else(|| created_at.clone()),
+        created_at,
+        resolved_at,
+        labels: optional(columns.labels).unwrap_or_default(),
+        project_key: optional(columns.project_key)
+            .or_else(|| mapping.default_project.clone())
+            .unwrap_or_else(|| "CSV".to_string()),
+        source: mapping.source.clone(),
+        custom_fields: serde_json::Value::Object(custom_fields).to_string(),
+        story_points,
+        sprint: optional(columns.sprint),
+        ..Default::default()
+    })
+}
+
+/// First known format that parses every value of a column.
+fn detect_date_format(values: &[&str]) -> Option<DateFormat> {
+    std::iter::once(DateFormat::Offset)
+        .chain(DATETIME_FORMATS.iter().map(|f| DateFormat::DateTime(f)))
+        .chain(DATE_FORMATS.iter().map(|f| DateFormat::Date(f)))
+        .find(|format| {
+            values
+                .iter()
+                .all(|v| parse_date(v, *format, None).is_some())
+        })
+}
+
+fn parse_date(value: &str, format: DateFormat, custom: Option<&str>) -> Option<NaiveDateTime> {
+    let naive = |f: &str| {
+        NaiveDateTime::parse_from_str(value, f).ok().or_else(|| {
+            NaiveDate::parse_from_str(value, f)
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+    };
+    match format {
+        DateFormat::Offset => parse_timestamp(value),
+        DateFormat::DateTime(f) => NaiveDateTime::parse_from_str(value, f).ok(),
+        DateFormat::Date(f) => NaiveDate::parse_from_str(value, f)
+            .ok()
+            .and_then(|d| d.and_hms_opt(0, 0, 0)),
+        DateFormat::Custom => {
+            let f = custom?;
+            chrono::DateTime::parse_from_str(value, f)
+                .ok()
+                .map(|dt| dt.naive_utc())
+                .or_else(|| naive(f))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> CsvMapping {
+        serde_json::from_value(serde_json::json!({
+            "key": "ID",
+            "summary": "Title",
+            "created_at": "Opened",
+            "resolved_at": "Closed",
+            "assignee": "Owner",
+            "key_prefix": "HD-",
+        }))
+        .expect("valid mapping")
+    }
+
+    #[test]
+    fn maps_columns_and_detects_day_first_dates() {
+        let csv = "ID,Title,Opened,Closed,Owner,Team\n\
+                   1,VPN down,03/01/2025 09:30,25/01/2025 10:00,alice,Network\n\
+                   2,New laptop,15/01/2025 14:00,,,\n";
+
+        let parsed = parse_csv(csv.as_bytes(), &mapping()).expect("parses");
+
+        assert!(parsed.skipped.is_empty());
+        assert_eq!(parsed.tickets.len(), 2);
+        let first = &parsed.tickets[0];
+        assert_eq!(first.jira_key, "HD-1");
+        assert_eq!(first.created_at, "2025-01-03T09:30:00+00:00");
+        assert_eq!(first.updated_at, first.created_at);
+        assert_eq!(
+            first.resolved_at.as_deref(),
+            Some("2025-01-25T10:00:00+00:00")
+        );
+        assert_eq!(first.status, "Done");
+        assert_eq!(first.assignee.as_deref(), Some("alice"));
+        assert_eq!(first.source, "csv");
+        assert_eq!(first.custom_fields, r#"{"Team":"Network"}"#);
+
+        let second = &parsed.tickets[1];
+        assert_eq!(second.status, "Open");
+        assert_eq!(second.assignee, None);
+        assert_eq!(second.custom_fields, "{}");
+    }
+
+    #[test]
+    fn skips_rows_missing_required_values() {
+        let csv = "ID,Title,Opened,Closed,Owner\n\
+                   ,No key,2025-01-03,,\n\
+                   7,Fine,2025-01-04,,\n";
+
+        let parsed = parse_csv(csv.as_bytes(), &mapping()).expect("parses");
+
+        assert_eq!(parsed.tickets.len(), 1);
+        assert_eq!(parsed.tickets[0].created_at, "2025-01-04T00:00:00+00:00");
+        assert_eq!(
+            parsed.skipped,
+            vec![CsvRowError {
+                line: 2,
+                message: "Missing 'ID'".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_columns() {
+        let csv = "Key,Title,Opened\n1,x,2025-01-03\n";
+
+        assert!(matches!(
+            parse_csv(csv.as_bytes(), &mapping()),
+            Err(AppError::Config(_))
+        ));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::db::{get_dedupe_tickets, insert_duplicate_candidate, DedupeTicket};
+use crate::errors::AppError;
+use crate::services::time_calc::parse_timestamp;
+use chrono::NaiveDateTime;
+use rusqlite::Connection;
+
+/// Summaries at least this similar are flagged as duplicate candidates.
+pub const DUPLICATE_THRESHOLD: f64 = 0.85;
+
+/// Only tickets created within this many days of each other are compared;
+/// the same work item filed in two trackers is filed at about the same time.
+const DUPLICATE_WINDOW_DAYS: i64 = 14;
+
+/// Words that carry no meaning in a ticket summary.
+const STOP_WORDS: [&str; 12] = [
+    "a", "an", "the", "and", "or", "of", "to", "in", "on", "for", "is", "re",
+];
+
+/// Lowercases, drops punctuation and filler words, and collapses whitespace,
+/// so `"Re: VPN is down!"` and `"vpn down"` compare equal.
+pub fn normalize_summary(summary: &str) -> String {
+    summary
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty() && !STOP_WORDS.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Sørensen–Dice coefficient over character bigrams of two normalized
+/// summaries: 1.0 for identical text, 0.0 for nothing in common.
+pub fn similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return if a.is_empty() { 0.0 } else { 1.0 };
+    }
+    let a = bigrams(a);
+    let b = bigrams(b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    // Both sorted: count the multiset intersection in one pass
+    let (mut i, mut j, mut shared) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+        }
+    }
+    2.0 * shared as f64 / (a.len() + b.len()) as f64
+}
+
+fn bigrams(text: &str) -> Vec<(char, char)> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut pairs: Vec<(char, char)> = chars.windows(2).map(|w| (w[0], w[1])).collect();
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Pairs of tickets from different sources whose summaries reach `threshold`.
+/// Expects `tickets` ordered by `created_at`.
+pub fn find_duplicates(tickets: &[DedupeTicket], threshold: f64) -> Vec<(usize, usize, f64)> {
+    let prepared: Vec<(String, Option<NaiveDateTime>)> = tickets
+        .iter()
+        .map(|t| {
+            (
+                normalize_summary(&t.summary),
+                parse_timestamp(&t.created_at),
+            )
+        })
+        .collect();
+    let window = chrono::Duration::days(DUPLICATE_WINDOW_DAYS);
+
+    let mut pairs = Vec::new();
+    for (i, ticket) in tickets.iter().enumerate() {
+        let (summary, created) = &prepared[i];
+        for (j, other) in tickets.iter().enumerate().skip(i + 1) {
+            let (other_summary, other_created) = &prepared[j];
+            if let (Some(created), Some(other_created)) = (created, other_created) {
+                if *other_created - *created > window {
+                    break;
+                }
+            }
+            if ticket.source == other.source {
+                continue;
+            }
+            let score = similarity(summary, other_summary);
+            if score >= threshold {
+                pairs.push((i, j, score));
+            }
+        }
+    }
+    pairs
+}
+
+/// Flags new duplicate candidates among the stored tickets. Returns how many
+/// pairs were added; pairs flagged before (including dismissed ones) are kept.
+pub fn detect_duplicates(
+    conn: &Connection,
+    threshold: f64,
+    now: NaiveDateTime,
+) -> Result<usize, AppError> {
+    let tickets = get_dedupe_tickets(conn)?;
+    let detected_at = now.and_utc().to_rfc3339();
+
+    let mut added = 0;
+    for (i, j, score) in find_duplicates(&tickets, threshold) {
+        if insert_duplicate_candidate(
+            conn,
+            &tickets[i].jira_key,
+            &tickets[j].jira_key,
+            score,
+            &detected_at,
+        )? {
+            added += 1;
+        }
+    }
+    Ok(added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{get_summary_stats, list_duplicate_candidates, merge_duplicate, upsert_ticket};
+    use crate::models::{DuplicateStatus, DurationMode, Ticket, TicketFilter};
+    use chrono::NaiveDate;
+
+    fn ticket(key: &str, summary: &str, source: &str, created_at: &str) -> Ticket {
+        Ticket {
+            summary: summary.to_string(),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            source: source.to_string(),
+            ..test_support::ticket(key)
+        }
+    }
+
+    #[test]
+    fn normalizes_and_scores_summaries() {
+        assert_eq!(normalize_summary("Re: VPN is DOWN!"), "vpn down");
+        assert_eq!(similarity("vpn down", "vpn down"), 1.0);
+        assert!(similarity("printer jammed floor 3", "printer jammed floor 2") > 0.85);
+        assert!(similarity("vpn down", "new laptop request") < 0.3);
+    }
+
+    #[test]
+    fn flags_cross_source_pairs_and_merge_hides_duplicate() {
+        let conn = setup_db();
+        for t in [
+            ticket("OPS-1", "VPN is down", "jira", "2025-01-06T09:00:00+00:00"),
+            ticket(
+                "ZD-9",
+                "Re: VPN down!",
+                "zendesk",
+                "2025-01-06T10:00:00+00:00",
+            ),
+            ticket("OPS-2", "VPN down", "jira", "2025-01-07T09:00:00+00:00"),
+            ticket("ZD-10", "VPN down", "zendesk", "2025-03-01T09:00:00+00:00"),
+        ] {
+            upsert_ticket(&conn, &t).expect("ticket inserted");
+        }
+        let now = NaiveDate::from_ymd_opt(2025, 3, 2)
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .expect("valid datetime");
+
+        // Same-source and out-of-window pairs are not compared
+        assert_eq!(
+            detect_duplicates(&conn, DUPLICATE_THRESHOLD, now).expect("detect"),
+            2
+        );
+        assert_eq!(
+            detect_duplicates(&conn, DUPLICATE_THRESHOLD, now).expect("detect"),
+            0
+        );
+
+        let pending =
+            list_duplicate_candidates(&conn, Some(DuplicateStatus::Pending)).expect("candidates");
+        let pair = pending
+            .iter()
+            .find(|c| c.ticket_a == "OPS-1")
+            .expect("OPS-1 flagged");
+        assert_eq!(pair.ticket_b, "ZD-9");
+        assert_eq!(pair.source_b.as_deref(), Some("zendesk"));
+
+        let merged = merge_duplicate(&conn, pair.id, "OPS-1")
+            .expect("merge")
+            .expect("pending candidate");
+        assert_eq!(merged.status, DuplicateStatus::Merged);
+        assert!(merge_duplicate(&conn, pair.id, "OPS-1")
+            .expect("merge")
+            .is_none());
+
+        let stats = get_summary_stats(&conn, &TicketFilter::default(), DurationMode::Business)
+            .expect("stats");
+        assert_eq!(stats.total_tickets, 3);
+    }
+}
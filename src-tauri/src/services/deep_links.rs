@@ -0,0 +1,56 @@
+use crate::models::LinkFormat;
+
+/// The page for issue `key` on the Jira site at `base_url`.
+pub fn browse_url(base_url: &str, key: &str) -> String {
+    format!("{}/browse/{}", base_url.trim_end_matches('/'), key)
+}
+
+/// A link to `url` labelled with the ticket's key and summary, escaped so
+/// the summary can't break out of the link syntax.
+pub fn format_ticket_link(url: &str, key: &str, summary: &str, format: LinkFormat) -> String {
+    match format {
+        LinkFormat::Url => url.to_string(),
+        LinkFormat::Markdown => {
+            let mut label = String::with_capacity(key.len() + summary.len() + 2);
+            for c in format!("{}: {}", key, summary).chars() {
+                if matches!(c, '\\' | '[' | ']') {
+                    label.push('\\');
+                }
+                label.push(c);
+            }
+            format!("[{}]({})", label, url.replace(')', "%29"))
+        }
+        LinkFormat::Slack => {
+            let label = format!("{}: {}", key, summary)
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            format!("<{}|{}>", url, label)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_links_per_destination() {
+        let url = browse_url("https://example.atlassian.net/", "OPS-7");
+        assert_eq!(url, "https://example.atlassian.net/browse/OPS-7");
+
+        let summary = "[VPN] drops <5 min> & reconnects";
+        assert_eq!(
+            format_ticket_link(&url, "OPS-7", summary, LinkFormat::Url),
+            url
+        );
+        assert_eq!(
+            format_ticket_link(&url, "OPS-7", summary, LinkFormat::Markdown),
+            "[OPS-7: \\[VPN\\] drops <5 min> & reconnects](https://example.atlassian.net/browse/OPS-7)"
+        );
+        assert_eq!(
+            format_ticket_link(&url, "OPS-7", summary, LinkFormat::Slack),
+            "<https://example.atlassian.net/browse/OPS-7|OPS-7: [VPN] drops &lt;5 min&gt; &amp; reconnects>"
+        );
+    }
+}
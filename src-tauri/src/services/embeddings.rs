@@ -0,0 +1,229 @@
+use crate::errors::{AppError, ConnectorError};
+use crate::models::Ticket;
+use crate::services::categorizer::{rule_match, CategorizerBackend, CategoryMatch, CategoryRule};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+pub const SOURCE_ID: &str = "embeddings";
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f64>,
+    #[serde(default)]
+    index: Option<usize>,
+}
+
+/// Categorizes with rules first, then classifies what's left by comparing
+/// summary embeddings from an OpenAI-compatible endpoint (a local model
+/// server or a hosted API) with embeddings of the category rules.
+pub struct EmbeddingCategorizer {
+    endpoint: String,
+    model: String,
+    api_key: Option<String>,
+    rules: Vec<CategoryRule>,
+    min_confidence: f64,
+    client: reqwest::Client,
+}
+
+impl EmbeddingCategorizer {
+    pub fn new(
+        endpoint: &str,
+        model: &str,
+        api_key: Option<String>,
+        rules: Vec<CategoryRule>,
+        min_confidence: f64,
+        client: reqwest::Client,
+    ) -> Self {
+        EmbeddingCategorizer {
+            endpoint: endpoint.to_string(),
+            model: model.to_string(),
+            api_key,
+            rules,
+            min_confidence,
+            client,
+        }
+    }
+
+    async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f64>>, AppError> {
+        let mut request = self.client.post(&self.endpoint).json(&serde_json::json!({
+            "model": self.model,
+            "input": inputs,
+        }));
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ConnectorError::Http(SOURCE_ID, e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ConnectorError::ApiError {
+                source_id: SOURCE_ID,
+                status: status.as_u16(),
+                body,
+            }
+            .into());
+        }
+
+        let mut body: EmbeddingResponse = response
+            .json()
+            .await
+            .map_err(|e| ConnectorError::ParseError(SOURCE_ID, e.to_string()))?;
+        if body.data.len() != inputs.len() {
+            return Err(ConnectorError::ParseError(
+                SOURCE_ID,
+                format!(
+                    "expected {} embeddings, got {}",
+                    inputs.len(),
+                    body.data.len()
+                ),
+            )
+            .into());
+        }
+        body.data.sort_by_key(|d| d.index);
+        Ok(body.data.into_iter().map(|d| d.embedding).collect())
+    }
+
+    /// Model guesses for `tickets`, which no rule matched.
+    async fn classify(&self, tickets: &[&Ticket]) -> Result<Vec<Option<CategoryMatch>>, AppError> {
+        let labels: Vec<String> = self.rules.iter().map(category_label).collect();
+        let inputs: Vec<String> = labels
+            .into_iter()
+            .chain(tickets.iter().map(|t| t.summary.clone()))
+            .collect();
+        let mut embeddings = self.embed(&inputs).await?;
+        let ticket_embeddings = embeddings.split_off(self.rules.len());
+
+        Ok(ticket_embeddings
+            .iter()
+            .map(|embedding| {
+                best_category(embedding, &embeddings, self.min_confidence).map(
+                    |(idx, confidence)| CategoryMatch {
+                        category: self.rules[idx].name.clone(),
+                        confidence,
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl CategorizerBackend for EmbeddingCategorizer {
+    async fn categorize(&self, tickets: &[Ticket]) -> Vec<Option<CategoryMatch>> {
+        let mut matches: Vec<Option<CategoryMatch>> = tickets
+            .iter()
+            .map(|ticket| rule_match(ticket, &self.rules))
+            .collect();
+        let unmatched: Vec<usize> = (0..tickets.len())
+            .filter(|&idx| matches[idx].is_none())
+            .collect();
+        if unmatched.is_empty() || self.rules.is_empty() {
+            return matches;
+        }
+
+        let pending: Vec<&Ticket> = unmatched.iter().map(|&idx| &tickets[idx]).collect();
+        match self.classify(&pending).await {
+            Ok(guesses) => {
+                for (idx, guess) in unmatched.into_iter().zip(guesses) {
+                    matches[idx] = guess;
+                }
+            }
+            Err(e) => log::warn!("Embedding categorizer failed, using rules only: {}", e),
+        }
+        matches
+    }
+}
+
+/// Text embedded to stand for a category: its name and the values its
+/// rule looks for, e.g. "Password: password, reset".
+fn category_label(rule: &CategoryRule) -> String {
+    let values: Vec<&str> = rule
+        .conditions
+        .iter()
+        .filter(|c| c.operator != "regex")
+        .map(|c| c.value.as_str())
+        .collect();
+    if values.is_empty() {
+        rule.name.clone()
+    } else {
+        format!("{}: {}", rule.name, values.join(", "))
+    }
+}
+
+fn cosine_similarity(a: &[f64], b: &[f64]) -> f64 {
+    let dot: f64 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f64]| v.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let denominator = norm(a) * norm(b);
+    if denominator == 0.0 {
+        0.0
+    } else {
+        dot / denominator
+    }
+}
+
+/// Index and similarity of the category closest to `ticket`, if it reaches
+/// `min_confidence`.
+fn best_category(
+    ticket: &[f64],
+    categories: &[Vec<f64>],
+    min_confidence: f64,
+) -> Option<(usize, f64)> {
+    categories
+        .iter()
+        .enumerate()
+        .map(|(idx, category)| (idx, cosine_similarity(ticket, category).clamp(0.0, 1.0)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .filter(|(_, similarity)| *similarity >= min_confidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::categorizer::{MatchMode, RuleCondition};
+
+    #[test]
+    fn picks_the_closest_category_above_the_threshold() {
+        let categories = vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0]];
+
+        let (idx, confidence) =
+            best_category(&[0.2, 0.9, 0.1], &categories, 0.5).expect("close to the second");
+        assert_eq!(idx, 1);
+        assert!((confidence - 0.9 / (0.86f64).sqrt()).abs() < 1e-9);
+
+        // Equally far from both, and not close to either
+        assert_eq!(best_category(&[0.0, 0.0, 1.0], &categories, 0.5), None);
+        assert_eq!(
+            best_category(&[0.0, 0.0, 0.0], &categories, 0.0).map(|m| m.1),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn category_labels_list_the_values_rules_look_for() {
+        let condition = |operator: &str, value: &str| RuleCondition {
+            field: "summary".to_string(),
+            operator: operator.to_string(),
+            value: value.to_string(),
+            case_sensitive: false,
+        };
+        let rule = CategoryRule {
+            name: "Password".to_string(),
+            conditions: vec![
+                condition("contains", "password"),
+                condition("regex", "^reset.*"),
+                condition("contains", "locked out"),
+            ],
+            match_mode: MatchMode::Any,
+        };
+        assert_eq!(category_label(&rule), "Password: password, locked out");
+    }
+}
@@ -0,0 +1,38 @@
+//! Straight-line backlog projections from weekly rates, shared by release
+//! readiness and the capacity simulator.
+
+use crate::models::BurndownPoint;
+use chrono::{Duration, NaiveDateTime};
+
+/// Date (YYYY-MM-DD) `open` tickets are done when the backlog shrinks by
+/// `burn_per_week`; `None` once nothing is open or it isn't shrinking.
+pub fn completion_date(open: f64, burn_per_week: f64, now: NaiveDateTime) -> Option<String> {
+    if open <= 0.0 || burn_per_week <= 0.0 {
+        return None;
+    }
+    let days = (open / burn_per_week * 7.0).ceil() as i64;
+    Some((now + Duration::days(days)).format("%Y-%m-%d").to_string())
+}
+
+/// Open tickets at the end of each of the next `weeks` weeks, starting from
+/// `open`, with `inflow_per_week` arriving and `throughput_per_week` resolved.
+pub fn burndown(
+    open: f64,
+    inflow_per_week: f64,
+    throughput_per_week: f64,
+    weeks: u32,
+    now: NaiveDateTime,
+) -> Vec<BurndownPoint> {
+    let mut remaining = open;
+    (1..=weeks)
+        .map(|week| {
+            remaining = (remaining + inflow_per_week - throughput_per_week).max(0.0);
+            BurndownPoint {
+                date: (now + Duration::weeks(i64::from(week)))
+                    .format("%Y-%m-%d")
+                    .to_string(),
+                open_tickets: remaining,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,151 @@
+use crate::db::{get_stats_history, get_summary_stats, list_metric_goals};
+use crate::errors::AppError;
+use crate::models::{DurationMode, GoalProgress, HistoryRange, TicketFilter};
+use crate::services::time_calc::parse_timestamp;
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+
+/// Least-squares slope of `points` (days, value), in value per day; `None`
+/// without two distinct times.
+fn daily_trend(points: &[(f64, f64)]) -> Option<f64> {
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (covariance, variance) = points.iter().fold((0.0, 0.0), |(cov, var), (x, y)| {
+        (
+            cov + (x - mean_x) * (y - mean_y),
+            var + (x - mean_x).powi(2),
+        )
+    });
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/// Every goal with the metric's current value over all tickets (business
+/// hours, like the stats history) and a straight-line projection to its
+/// deadline from the last month of history.
+pub fn get_goal_progress(
+    conn: &Connection,
+    now: NaiveDateTime,
+) -> Result<Vec<GoalProgress>, AppError> {
+    let goals = list_metric_goals(conn)?;
+    if goals.is_empty() {
+        return Ok(Vec::new());
+    }
+    let stats = get_summary_stats(conn, &TicketFilter::default(), DurationMode::Business)?;
+    let history: Vec<_> = get_stats_history(conn, HistoryRange::Month, now)?
+        .into_iter()
+        .filter_map(|entry| Some((parse_timestamp(&entry.recorded_at)?, entry)))
+        .collect();
+
+    goals
+        .into_iter()
+        .map(|goal| {
+            let deadline = NaiveDate::parse_from_str(&goal.deadline, "%Y-%m-%d").map_err(|_| {
+                AppError::Internal(format!("Invalid goal deadline: {}", goal.deadline))
+            })?;
+            let days_left = (deadline - now.date()).num_days();
+            let current = goal.metric.value(&stats);
+            let met = goal.op.holds(current, goal.target);
+
+            let points: Vec<(f64, f64)> = history
+                .iter()
+                .map(|(recorded_at, entry)| {
+                    let days = (*recorded_at - history[0].0).num_seconds() as f64 / 86_400.0;
+                    (days, goal.metric.recorded(entry))
+                })
+                .collect();
+            let trend = daily_trend(&points);
+            let projected = trend.map(|per_day| current + per_day * days_left.max(0) as f64);
+            let on_track = if days_left < 0 {
+                met
+            } else {
+                goal.op.holds(projected.unwrap_or(current), goal.target)
+            };
+
+            Ok(GoalProgress {
+                current,
+                met,
+                trend_per_week: trend.map(|per_day| per_day * 7.0),
+                projected,
+                on_track,
+                days_left,
+                goal,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{create_metric_goal, upsert_ticket};
+    use crate::models::{Comparison, GoalMetric, MetricGoalInput, Ticket};
+    use rusqlite::params;
+
+    #[test]
+    fn progress_projects_the_recent_trend_to_the_deadline() {
+        let conn = setup_db();
+        for key in ["OPS-1", "OPS-2"] {
+            let ticket = Ticket {
+                created_at: "2025-06-01T09:00:00Z".to_string(),
+                updated_at: "2025-06-01T09:00:00Z".to_string(),
+                ..test_support::ticket(key)
+            };
+            upsert_ticket(&conn, &ticket).expect("ticket");
+        }
+        // Open tickets falling by one a week
+        for (recorded_at, open) in [
+            ("2025-06-01T09:00:00+00:00", 4),
+            ("2025-06-08T09:00:00+00:00", 3),
+            ("2025-06-15T09:00:00+00:00", 2),
+        ] {
+            conn.execute(
+                "INSERT INTO stats_history (recorded_at, total_tickets, open_tickets,
+                     resolved_tickets, stale_tickets, avg_resolution_hours,
+                     median_resolution_hours)
+                 VALUES (?1, ?2, ?2, 0, 0, 0, 0)",
+                params![recorded_at, open],
+            )
+            .expect("history");
+        }
+        let goal = |op, target, deadline: &str| MetricGoalInput {
+            metric: GoalMetric::OpenTickets,
+            op,
+            target,
+            deadline: deadline.to_string(),
+        };
+        let reachable =
+            create_metric_goal(&conn, &goal(Comparison::Lte, 0.5, "2025-06-29")).expect("goal");
+        let missed =
+            create_metric_goal(&conn, &goal(Comparison::Lt, 1.0, "2025-06-14")).expect("goal");
+
+        let now = NaiveDate::from_ymd_opt(2025, 6, 15)
+            .and_then(|d| d.and_hms_opt(12, 0, 0))
+            .expect("valid datetime");
+        let progress = get_goal_progress(&conn, now).expect("progress");
+        let of = |id: i64| {
+            progress
+                .iter()
+                .find(|p| p.goal.id == id)
+                .expect("goal listed")
+        };
+
+        let reachable = of(reachable.id);
+        assert_eq!(reachable.current, 2.0);
+        assert!(!reachable.met);
+        assert_eq!(reachable.days_left, 14);
+        let trend = reachable.trend_per_week.expect("trend");
+        assert!((trend + 1.0).abs() < 1e-9);
+        assert!(reachable.projected.expect("projection").abs() < 1e-9);
+        assert!(reachable.on_track);
+
+        let missed = of(missed.id);
+        assert_eq!(missed.days_left, -1);
+        assert_eq!(missed.projected, Some(2.0));
+        assert!(!missed.on_track);
+    }
+}
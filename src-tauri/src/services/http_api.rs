@@ -0,0 +1,362 @@
+use crate::db::{get_summary_stats, get_tickets, get_tickets_page};
+use crate::errors::{AppError, DbError};
+use crate::models::{DateRange, DurationMode, TicketFilter};
+use crate::services::cache::AggregationCache;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// Decodes `%XX` escapes and `+` in a query string component.
+pub(crate) fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (escaped, bytes[i]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                i += 2;
+            }
+            (None, b'+') => decoded.push(b' '),
+            (None, byte) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn query_pairs(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect()
+}
+
+/// Builds the filter and duration mode from query parameters. List filters
+/// repeat (`project=OPS&project=WEB`); dates bound `created` and `resolved`.
+fn filter_from_query(pairs: &[(String, String)]) -> Result<(TicketFilter, DurationMode), String> {
+    let mut filter = TicketFilter::default();
+    let mut mode = DurationMode::default();
+    let mut created = DateRange::default();
+    let mut resolved = DateRange::default();
+    for (name, value) in pairs {
+        let value = value.clone();
+        match name.as_str() {
+            "project" => filter.project_keys.push(value),
+            "status" => filter.statuses.push(value),
+            "priority" => filter.priorities.push(value),
+            "assignee" => filter.assignees.push(value),
+            "team" => filter.teams.push(value),
+            "tag" => filter.tags.push(value),
+            "q" => filter.query = Some(value),
+            "created_from" => created.from = Some(value),
+            "created_to" => created.to = Some(value),
+            "resolved_from" => resolved.from = Some(value),
+            "resolved_to" => resolved.to = Some(value),
+            "include_archived" => filter.include_archived = value == "true",
+            "hide_snoozed" => filter.hide_snoozed = value == "true",
+            "account" => {
+                let id = value
+                    .parse()
+                    .map_err(|_| format!("Invalid account id: {}", value))?;
+                filter.account_id = Some(id);
+            }
+            "mode" => {
+                mode = match value.as_str() {
+                    "business" => DurationMode::Business,
+                    "calendar" => DurationMode::Calendar,
+                    _ => return Err(format!("Unknown duration mode: {}", value)),
+                }
+            }
+            // Read by the endpoints themselves
+            "token" | "limit" | "offset" => {}
+            _ => return Err(format!("Unknown parameter: {}", name)),
+        }
+    }
+    if created != DateRange::default() {
+        filter.created = Some(created);
+    }
+    if resolved != DateRange::default() {
+        filter.resolved = Some(resolved);
+    }
+    Ok((filter, mode))
+}
+
+/// Compares the whole token whatever the first mismatch, so response times
+/// don't reveal how much of a guess was right.
+pub(crate) fn tokens_match(provided: &str, token: &str) -> bool {
+    provided.len() == token.len()
+        && provided
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// The token from an `Authorization: Bearer` header or a `token` query
+/// parameter, for clients that can't set headers.
+fn authorized(bearer: Option<&str>, pairs: &[(String, String)], token: &str) -> bool {
+    let provided = bearer.or_else(|| {
+        pairs
+            .iter()
+            .find(|(name, _)| name == "token")
+            .map(|(_, value)| value.as_str())
+    });
+    provided.is_some_and(|provided| tokens_match(provided, token))
+}
+
+/// A non-negative whole-number query parameter such as `limit`.
+fn count_param(pairs: &[(String, String)], name: &str) -> Result<Option<u32>, String> {
+    match pairs.iter().find(|(n, _)| n == name) {
+        Some((_, value)) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("Invalid {}", name)),
+        None => Ok(None),
+    }
+}
+
+fn json<T: Serialize>(value: &T) -> Result<(u16, String), AppError> {
+    serde_json::to_string(value)
+        .map(|body| (200, body))
+        .map_err(|e| AppError::Internal(format!("Failed to serialize response: {}", e)))
+}
+
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+/// Answers one authorized GET request with a status and JSON body.
+fn route(
+    conn: &Connection,
+    cache: &AggregationCache,
+    path: &str,
+    pairs: &[(String, String)],
+) -> Result<(u16, String), AppError> {
+    let (filter, mode) = match filter_from_query(pairs) {
+        Ok(parsed) => parsed,
+        Err(message) => return Ok((400, error_body(&message))),
+    };
+    match path {
+        "/api/summary" => json(&get_summary_stats(conn, &filter, mode)?),
+        "/api/aggregations" => json(&cache.get_or_compute(conn, &filter, mode)?),
+        "/api/tickets" => {
+            let (limit, offset) = match (count_param(pairs, "limit"), count_param(pairs, "offset"))
+            {
+                (Ok(limit), Ok(offset)) => (limit, offset),
+                (Err(message), _) | (_, Err(message)) => return Ok((400, error_body(&message))),
+            };
+            // Paged in SQL, so a small limit doesn't load the whole table
+            let tickets = match (limit, offset) {
+                (None, None) => get_tickets(conn, &filter)?,
+                (limit, offset) => get_tickets_page(
+                    conn,
+                    &filter,
+                    limit.unwrap_or(u32::MAX),
+                    offset.unwrap_or(0),
+                )?,
+            };
+            json(&tickets)
+        }
+        _ => Ok((404, error_body("Not found"))),
+    }
+}
+
+/// Read-only JSON API over the local database for dashboards and scripts,
+/// e.g. Grafana. Only binds to 127.0.0.1, and every request needs the token.
+pub struct HttpApiServer {
+    server: Arc<tiny_http::Server>,
+    port: u16,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl HttpApiServer {
+    pub fn start(
+        port: u16,
+        token: String,
+        db_pool: Arc<Mutex<Connection>>,
+        cache: AggregationCache,
+    ) -> Result<Self, AppError> {
+        let server = tiny_http::Server::http(("127.0.0.1", port)).map_err(|e| {
+            AppError::Config(format!("Failed to start HTTP API on {}: {}", port, e))
+        })?;
+        let port = server
+            .server_addr()
+            .to_ip()
+            .map(|addr| addr.port())
+            .unwrap_or(port);
+        let server = Arc::new(server);
+
+        let worker = server.clone();
+        let thread = std::thread::spawn(move || {
+            for request in worker.incoming_requests() {
+                handle_request(request, &token, &db_pool, &cache);
+            }
+        });
+
+        log::info!("HTTP API started on 127.0.0.1:{}", port);
+        Ok(HttpApiServer {
+            server,
+            port,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl Drop for HttpApiServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+        log::info!("HTTP API on port {} stopped", self.port);
+    }
+}
+
+fn handle_request(
+    request: tiny_http::Request,
+    token: &str,
+    db_pool: &Mutex<Connection>,
+    cache: &AggregationCache,
+) {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let pairs = query_pairs(query);
+    let bearer = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"))
+        .and_then(|header| header.value.as_str().strip_prefix("Bearer "));
+
+    let (status, body) = if *request.method() != tiny_http::Method::Get {
+        (405, error_body("Only GET is supported"))
+    } else if !authorized(bearer, &pairs, token) {
+        (401, error_body("Missing or wrong token"))
+    } else {
+        let result = db_pool
+            .lock()
+            .map_err(|_| AppError::from(DbError::LockFailed))
+            .and_then(|conn| route(&conn, cache, path, &pairs));
+        result.unwrap_or_else(|e| {
+            log::error!("HTTP API request {} failed: {}", path, e);
+            (500, error_body("Internal error"))
+        })
+    };
+
+    let mut response = tiny_http::Response::from_string(body).with_status_code(status);
+    if let Ok(header) = tiny_http::Header::from_bytes("Content-Type", "application/json") {
+        response.add_header(header);
+    }
+    request.respond(response).ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::upsert_ticket;
+    use crate::models::Ticket;
+
+    fn pairs(query: &str) -> Vec<(String, String)> {
+        query_pairs(query)
+    }
+
+    #[test]
+    fn query_parameters_become_a_filter() {
+        assert_eq!(percent_decode("In%20Progress+now%2"), "In Progress now%2");
+
+        let (filter, mode) = filter_from_query(&pairs(
+            "project=OPS&project=WEB&status=In+Progress&created_from=2025-01-01&mode=calendar",
+        ))
+        .expect("filter");
+        assert_eq!(filter.project_keys, vec!["OPS", "WEB"]);
+        assert_eq!(filter.statuses, vec!["In Progress"]);
+        assert_eq!(
+            filter.created,
+            Some(DateRange {
+                from: Some("2025-01-01".to_string()),
+                to: None,
+            })
+        );
+        assert_eq!(filter.resolved, None);
+        assert_eq!(mode, DurationMode::Calendar);
+
+        assert!(filter_from_query(&pairs("mode=fast")).is_err());
+        assert!(filter_from_query(&pairs("DROP=TABLE")).is_err());
+    }
+
+    #[test]
+    fn requests_need_the_token() {
+        assert!(authorized(Some("s3cret"), &[], "s3cret"));
+        assert!(authorized(None, &pairs("token=s3cret"), "s3cret"));
+        assert!(!authorized(None, &[], "s3cret"));
+        assert!(!authorized(Some("s3cre"), &[], "s3cret"));
+        assert!(!authorized(None, &pairs("token=s3creT"), "s3cret"));
+    }
+
+    #[test]
+    fn endpoints_serve_filtered_json() {
+        let conn = setup_db();
+        for (key, project, created_at) in [
+            ("OPS-1", "OPS", "2025-01-08T09:00:00Z"),
+            ("OPS-2", "OPS", "2025-01-07T09:00:00Z"),
+            ("WEB-1", "WEB", "2025-01-06T09:00:00Z"),
+        ] {
+            let ticket = Ticket {
+                project_key: project.to_string(),
+                created_at: created_at.to_string(),
+                ..test_support::ticket(key)
+            };
+            upsert_ticket(&conn, &ticket).expect("ticket");
+        }
+        let cache = AggregationCache::default();
+
+        let (status, body) =
+            route(&conn, &cache, "/api/tickets", &pairs("project=OPS&limit=1")).expect("tickets");
+        assert_eq!(status, 200);
+        let tickets: serde_json::Value = serde_json::from_str(&body).expect("json");
+        assert_eq!(tickets.as_array().map(Vec::len), Some(1));
+        assert_eq!(tickets[0]["jira_key"], "OPS-1");
+
+        let (_, body) = route(
+            &conn,
+            &cache,
+            "/api/tickets",
+            &pairs("project=OPS&limit=5&offset=1"),
+        )
+        .expect("second page");
+        let tickets: serde_json::Value = serde_json::from_str(&body).expect("json");
+        assert_eq!(tickets.as_array().map(Vec::len), Some(1));
+        assert_eq!(tickets[0]["jira_key"], "OPS-2");
+
+        let (status, body) =
+            route(&conn, &cache, "/api/summary", &pairs("project=OPS")).expect("summary");
+        assert_eq!(status, 200);
+        let summary: serde_json::Value = serde_json::from_str(&body).expect("json");
+        assert_eq!(summary["total_tickets"], 2);
+
+        let (status, _) = route(&conn, &cache, "/api/aggregations", &[]).expect("aggregations");
+        assert_eq!(status, 200);
+        let (status, _) = route(&conn, &cache, "/api/tickets", &pairs("limit=x")).expect("limit");
+        assert_eq!(status, 400);
+        let (status, _) =
+            route(&conn, &cache, "/api/tickets", &pairs("offset=-1")).expect("offset");
+        assert_eq!(status, 400);
+        let (status, _) = route(&conn, &cache, "/api/other", &[]).expect("other");
+        assert_eq!(status, 404);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::errors::AppError;
+use crate::models::NetworkSettings;
+
+/// Builds the `reqwest` client every connector uses, applying proxy and
+/// certificate settings.
+pub fn build_http_client(settings: &NetworkSettings) -> Result<reqwest::Client, AppError> {
+    let mut builder = reqwest::Client::builder();
+
+    if let Some(proxy_url) = non_empty(&settings.proxy_url) {
+        let proxy = reqwest::Proxy::all(proxy_url)
+            .map_err(|e| AppError::Config(format!("Invalid proxy URL '{}': {}", proxy_url, e)))?;
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(path) = non_empty(&settings.ca_cert_path) {
+        let pem = std::fs::read(path)
+            .map_err(|e| AppError::Config(format!("Failed to read CA bundle '{}': {}", path, e)))?;
+        let certificates = reqwest::Certificate::from_pem_bundle(&pem)
+            .map_err(|e| AppError::Config(format!("Invalid CA bundle '{}': {}", path, e)))?;
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    if settings.accept_invalid_certs {
+        log::warn!("TLS certificate verification is disabled");
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    builder
+        .build()
+        .map_err(|e| AppError::Config(format!("Failed to build HTTP client: {}", e)))
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_settings_build_a_client() {
+        assert!(build_http_client(&NetworkSettings::default()).is_ok());
+    }
+
+    #[test]
+    fn blank_values_are_ignored() {
+        let settings = NetworkSettings {
+            proxy_url: Some("  ".to_string()),
+            ca_cert_path: Some(String::new()),
+            accept_invalid_certs: false,
+        };
+        assert!(build_http_client(&settings).is_ok());
+    }
+
+    #[test]
+    fn missing_ca_bundle_is_a_config_error() {
+        let settings = NetworkSettings {
+            ca_cert_path: Some("/nonexistent/corp-ca.pem".to_string()),
+            ..Default::default()
+        };
+        let err = build_http_client(&settings).expect_err("missing bundle");
+        assert!(matches!(err, AppError::Config(_)));
+    }
+}
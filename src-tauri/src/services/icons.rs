@@ -0,0 +1,139 @@
+use crate::db::{get_cached_icon, save_cached_icon, DbPool};
+use crate::errors::AppError;
+use crate::jira::{ImageFetch, JiraClient};
+use crate::models::{CachedIcon, IconImage};
+use crate::services::hashing::fnv1a;
+use base64::Engine;
+use std::path::Path;
+
+/// File name for the image cached from `url`: a stable hash of the URL, so
+/// nothing in it comes from the server, plus an extension for the type.
+fn icon_file_name(url: &str, content_type: &str) -> String {
+    let hash = fnv1a(url.as_bytes());
+    let extension = match content_type {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/svg+xml" => "svg",
+        "image/webp" => "webp",
+        _ => "img",
+    };
+    format!("{:016x}.{}", hash, extension)
+}
+
+fn data_url(content_type: &str, bytes: &[u8]) -> String {
+    format!(
+        "data:{};base64,{}",
+        content_type,
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    )
+}
+
+/// The image at `url`, served from `cache_dir` when the cached copy is still
+/// current (by ETag) or Jira can't be reached, and downloaded otherwise.
+pub async fn load_icon(
+    client: Option<&JiraClient>,
+    db: &DbPool,
+    cache_dir: &Path,
+    url: &str,
+) -> Result<IconImage, AppError> {
+    let lookup_url = url.to_string();
+    let cached = db
+        .run(move |conn| get_cached_icon(conn, &lookup_url))
+        .await?
+        .and_then(|icon| {
+            let bytes = std::fs::read(cache_dir.join(&icon.file_name)).ok()?;
+            Some((icon, bytes))
+        });
+
+    let fetched = match client {
+        Some(client) => {
+            let etag = cached.as_ref().and_then(|(icon, _)| icon.etag.as_deref());
+            client.fetch_image(url, etag).await
+        }
+        None => Err(AppError::Config(
+            "Jira is not configured; only cached icons are available".to_string(),
+        )),
+    };
+
+    match (fetched, cached) {
+        (Ok(ImageFetch::NotModified), Some((icon, bytes))) => Ok(IconImage {
+            data_url: data_url(&icon.content_type, &bytes),
+            stale: false,
+        }),
+        // Only sent a conditional request when there was a cached copy
+        (Ok(ImageFetch::NotModified), None) => Err(AppError::Internal(format!(
+            "{} answered 304 to an unconditional request",
+            url
+        ))),
+        (
+            Ok(ImageFetch::Fetched {
+                bytes,
+                etag,
+                content_type,
+            }),
+            _,
+        ) => store_icon(db, cache_dir, url, bytes, etag, content_type).await,
+        (Err(e), Some((icon, bytes))) => {
+            log::warn!("Serving cached icon for {}: {}", url, e);
+            Ok(IconImage {
+                data_url: data_url(&icon.content_type, &bytes),
+                stale: true,
+            })
+        }
+        (Err(e), None) => Err(e),
+    }
+}
+
+async fn store_icon(
+    db: &DbPool,
+    cache_dir: &Path,
+    url: &str,
+    bytes: Vec<u8>,
+    etag: Option<String>,
+    content_type: String,
+) -> Result<IconImage, AppError> {
+    let icon = CachedIcon {
+        url: url.to_string(),
+        file_name: icon_file_name(url, &content_type),
+        content_type,
+        etag,
+        fetched_at: chrono::Utc::now().to_rfc3339(),
+    };
+    std::fs::create_dir_all(cache_dir).map_err(|e| {
+        AppError::Config(format!("Failed to create {}: {}", cache_dir.display(), e))
+    })?;
+    let path = cache_dir.join(&icon.file_name);
+    std::fs::write(&path, &bytes)
+        .map_err(|e| AppError::Config(format!("Failed to write {}: {}", path.display(), e)))?;
+
+    let image = IconImage {
+        data_url: data_url(&icon.content_type, &bytes),
+        stale: false,
+    };
+    db.run(move |conn| save_cached_icon(conn, &icon)).await?;
+    Ok(image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_file_names_come_from_the_url_only() {
+        let name = icon_file_name("https://example.atlassian.net/avatar/1", "image/png");
+        assert!(name.ends_with(".png"));
+        assert_eq!(
+            name,
+            icon_file_name("https://example.atlassian.net/avatar/1", "image/png")
+        );
+        assert_ne!(
+            name,
+            icon_file_name("https://example.atlassian.net/avatar/2", "image/png")
+        );
+        assert!(icon_file_name("https://x/../../etc/passwd", "text/html").ends_with(".img"));
+        assert!(!icon_file_name("https://x/../../etc/passwd", "image/png").contains('/'));
+
+        assert_eq!(data_url("image/gif", b"GIF"), "data:image/gif;base64,R0lG");
+    }
+}
@@ -0,0 +1,142 @@
+use crate::models::{Sprint, Ticket};
+use chrono::{NaiveDate, NaiveDateTime};
+
+/// Escapes a TEXT value per RFC 5545.
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line to at most 75 octets per line, continuing with a
+/// leading space, without splitting UTF-8 characters.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded
+}
+
+/// A UID part made of characters safe in any calendar app.
+fn uid_part(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+fn date_of(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
+
+/// An iCalendar file with an all-day event for each ticket's due date and
+/// each sprint's start and end. Returns the file and its event count.
+pub fn build_calendar(
+    tickets: &[Ticket],
+    sprints: &[Sprint],
+    now: NaiveDateTime,
+) -> (String, usize) {
+    let mut events: Vec<(String, NaiveDate, String)> = Vec::new();
+    for ticket in tickets {
+        if let Some(due) = ticket.due_date.as_deref().and_then(date_of) {
+            events.push((
+                format!("due-{}", uid_part(&ticket.jira_key)),
+                due,
+                format!("Due: {} {}", ticket.jira_key, ticket.summary),
+            ));
+        }
+    }
+    for sprint in sprints {
+        let boundaries = [
+            ("start", "starts", sprint.start_date.as_deref()),
+            ("end", "ends", sprint.end_date.as_deref()),
+        ];
+        for (uid, verb, value) in boundaries {
+            if let Some(date) = value.and_then(date_of) {
+                events.push((
+                    format!("sprint-{}-{}", uid, uid_part(&sprint.name)),
+                    date,
+                    format!("{} {}", sprint.name, verb),
+                ));
+            }
+        }
+    }
+
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//TicketDash//Deadlines//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    for (uid, date, summary) in &events {
+        let end = date.succ_opt().unwrap_or(*date);
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}@ticketdash", uid),
+            format!("DTSTAMP:{}", stamp),
+            format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+            format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")),
+            format!("SUMMARY:{}", escape_text(summary)),
+            "TRANSP:TRANSPARENT".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut calendar = String::new();
+    for line in lines {
+        calendar.push_str(&fold_line(&line));
+        calendar.push_str("\r\n");
+    }
+    (calendar, events.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_all_day_events_for_due_dates_and_sprints() {
+        let ticket = Ticket {
+            jira_key: "OPS-7".to_string(),
+            summary: "Renew certs; VPN, mail".to_string(),
+            due_date: Some("2025-02-14".to_string()),
+            ..Default::default()
+        };
+        let sprint = Sprint {
+            name: "Ops 2".to_string(),
+            state: Some("active".to_string()),
+            start_date: Some("2025-02-10T09:00:00.000Z".to_string()),
+            end_date: None,
+        };
+        let now = NaiveDate::from_ymd_opt(2025, 2, 1)
+            .and_then(|d| d.and_hms_opt(8, 0, 0))
+            .expect("valid datetime");
+
+        let (ics, events) = build_calendar(&[ticket], &[sprint], now);
+        assert_eq!(events, 2);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains(
+            "UID:due-OPS-7@ticketdash\r\nDTSTAMP:20250201T080000Z\r\n\
+             DTSTART;VALUE=DATE:20250214\r\nDTEND;VALUE=DATE:20250215\r\n\
+             SUMMARY:Due: OPS-7 Renew certs\\; VPN\\, mail\r\n"
+        ));
+        assert!(ics.contains("UID:sprint-start-Ops-2@ticketdash\r\n"));
+        assert!(ics.contains("SUMMARY:Ops 2 starts\r\n"));
+
+        let long = fold_line(&format!("SUMMARY:{}", "é".repeat(60)));
+        assert!(long.split("\r\n").all(|line| line.len() <= 75));
+    }
+}
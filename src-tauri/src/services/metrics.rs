@@ -0,0 +1,150 @@
+use crate::models::{CustomMetric, DurationMode, Ticket};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+
+/// A KPI computed over the filtered tickets and shown next to the built-in
+/// aggregations in `AggregationResult::custom_metrics`. Implement it in your
+/// own crate and pass the providers to `run_with_metrics`, so proprietary
+/// metrics don't need a fork.
+pub trait MetricProvider: Send + Sync {
+    /// Stable identifier the UI keys the metric by, e.g. `reopen_rate`.
+    fn id(&self) -> &str;
+    /// Name shown next to the value.
+    fn label(&self) -> &str;
+    /// The metric over `tickets` (already filtered), or `None` when it
+    /// doesn't apply, e.g. a ratio over no tickets. Resolution times should
+    /// follow `mode`.
+    fn compute(&self, tickets: &[Ticket], mode: DurationMode) -> Option<f64>;
+}
+
+struct FnMetric<F> {
+    id: String,
+    label: String,
+    compute: F,
+}
+
+impl<F> MetricProvider for FnMetric<F>
+where
+    F: Fn(&[Ticket], DurationMode) -> Option<f64> + Send + Sync,
+{
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn compute(&self, tickets: &[Ticket], mode: DurationMode) -> Option<f64> {
+        (self.compute)(tickets, mode)
+    }
+}
+
+/// The metric providers registered at startup.
+#[derive(Clone, Default)]
+pub struct MetricRegistry(Arc<Vec<Arc<dyn MetricProvider>>>);
+
+impl MetricRegistry {
+    pub fn with(mut self, provider: impl MetricProvider + 'static) -> Self {
+        Arc::make_mut(&mut self.0).push(Arc::new(provider));
+        self
+    }
+
+    /// Registers a closure as a metric, for KPIs that don't need a type.
+    pub fn with_fn<F>(self, id: &str, label: &str, compute: F) -> Self
+    where
+        F: Fn(&[Ticket], DurationMode) -> Option<f64> + Send + Sync + 'static,
+    {
+        self.with(FnMetric {
+            id: id.to_string(),
+            label: label.to_string(),
+            compute,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Every registered metric over `tickets`. A provider that panics is
+    /// logged and reported without a value rather than failing the dashboard.
+    pub fn compute(&self, tickets: &[Ticket], mode: DurationMode) -> Vec<CustomMetric> {
+        self.0
+            .iter()
+            .map(|provider| {
+                let value = catch_unwind(AssertUnwindSafe(|| provider.compute(tickets, mode)))
+                    .unwrap_or_else(|_| {
+                        log::error!("Metric provider {} panicked", provider.id());
+                        None
+                    });
+                CustomMetric {
+                    id: provider.id().to_string(),
+                    label: provider.label().to_string(),
+                    value,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TicketCount;
+
+    impl MetricProvider for TicketCount {
+        fn id(&self) -> &str {
+            "ticket_count"
+        }
+
+        fn label(&self) -> &str {
+            "Tickets"
+        }
+
+        fn compute(&self, tickets: &[Ticket], _mode: DurationMode) -> Option<f64> {
+            Some(tickets.len() as f64)
+        }
+    }
+
+    #[test]
+    fn registered_metrics_are_computed_in_order() {
+        let registry = MetricRegistry::default()
+            .with(TicketCount)
+            .with_fn("unassigned_share", "Unassigned", |tickets, _| {
+                let unassigned = tickets.iter().filter(|t| t.assignee.is_none()).count();
+                (!tickets.is_empty()).then(|| unassigned as f64 / tickets.len() as f64)
+            })
+            .with_fn("broken", "Broken", |_, _| panic!("bad plugin"));
+        let tickets = vec![
+            Ticket {
+                assignee: Some("Dana".to_string()),
+                ..Default::default()
+            },
+            Ticket::default(),
+        ];
+
+        let metrics = registry.compute(&tickets, DurationMode::Business);
+        assert_eq!(
+            metrics,
+            vec![
+                CustomMetric {
+                    id: "ticket_count".to_string(),
+                    label: "Tickets".to_string(),
+                    value: Some(2.0),
+                },
+                CustomMetric {
+                    id: "unassigned_share".to_string(),
+                    label: "Unassigned".to_string(),
+                    value: Some(0.5),
+                },
+                CustomMetric {
+                    id: "broken".to_string(),
+                    label: "Broken".to_string(),
+                    value: None,
+                },
+            ]
+        );
+        assert_eq!(registry.compute(&[], DurationMode::Business)[1].value, None);
+    }
+}
@@ -1,6 +1,37 @@
+pub mod alerts;
+pub mod anomaly;
+pub mod automations;
+pub mod cache;
+pub mod capacity;
 pub mod categorizer;
+pub mod clustering;
+pub mod csv_import;
+pub mod dedupe;
+pub mod deep_links;
+pub mod embeddings;
+pub mod forecast;
+pub mod goals;
+pub mod hashing;
+pub mod http_api;
+pub mod http_client;
+pub mod icons;
+pub mod ics;
+pub mod metrics;
+pub mod notify;
+pub mod outbox;
+pub mod pdf;
+pub mod period_comparison;
+pub mod person_report;
 #[cfg(test)]
 pub mod scheduler;
+pub mod similar;
+pub mod snapshots;
+pub mod source_sync;
+pub mod standup;
+pub mod status_dwell;
 pub mod time_calc;
+pub mod trending;
+pub mod wallboard;
+pub mod webhook;
 
 pub use categorizer::*;
@@ -0,0 +1,134 @@
+use crate::db::{count_tickets, get_aggregations};
+use crate::errors::AppError;
+use crate::models::{CountEntry, DateRange, DurationMode, SummaryStats, TicketFilter};
+use chrono::NaiveDateTime;
+use rusqlite::Connection;
+
+/// Weekly summary shared by the Slack and email digests.
+pub struct WeeklyDigest {
+    pub period_start: NaiveDateTime,
+    pub period_end: NaiveDateTime,
+    pub created_this_week: u32,
+    pub resolved_this_week: u32,
+    pub summary: SummaryStats,
+    pub top_statuses: Vec<CountEntry>,
+    pub top_priorities: Vec<CountEntry>,
+}
+
+pub fn build_weekly_digest(
+    conn: &Connection,
+    now: NaiveDateTime,
+) -> Result<WeeklyDigest, AppError> {
+    let period_start = now - chrono::Duration::days(7);
+    let since = period_start.and_utc().to_rfc3339();
+
+    let created_this_week = count_tickets(
+        conn,
+        &TicketFilter::default().created_in(DateRange::since(&since)),
+    )?;
+    let resolved_this_week = count_tickets(
+        conn,
+        &TicketFilter::default().resolved_in(DateRange::since(since)),
+    )?;
+
+    let aggregations = get_aggregations(conn, &TicketFilter::default(), DurationMode::Business)?;
+
+    Ok(WeeklyDigest {
+        period_start,
+        period_end: now,
+        created_this_week,
+        resolved_this_week,
+        summary: aggregations.summary,
+        top_statuses: aggregations.tickets_by_status.into_iter().take(5).collect(),
+        top_priorities: aggregations
+            .tickets_by_priority
+            .into_iter()
+            .take(5)
+            .collect(),
+    })
+}
+
+impl WeeklyDigest {
+    pub fn title(&self) -> String {
+        format!(
+            "TicketDash weekly digest ({} – {})",
+            self.period_start.format("%b %-d"),
+            self.period_end.format("%b %-d")
+        )
+    }
+
+    /// Plain-text body; Slack renders the `*bold*` markers, email shows them as-is.
+    pub fn render_text(&self) -> String {
+        let mut lines = vec![
+            format!(
+                "*This week:* {} created, {} resolved",
+                self.created_this_week, self.resolved_this_week
+            ),
+            format!(
+                "*Overall:* {} open / {} total, median resolution {:.1}h (avg {:.1}h)",
+                self.summary.open_tickets,
+                self.summary.total_tickets,
+                self.summary.median_resolution_hours,
+                self.summary.avg_resolution_hours
+            ),
+        ];
+
+        for (label, entries) in [
+            ("By status", &self.top_statuses),
+            ("By priority", &self.top_priorities),
+        ] {
+            if !entries.is_empty() {
+                let counts = entries
+                    .iter()
+                    .map(|e| format!("{} {}", e.name, e.count))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                lines.push(format!("*{}:* {}", label, counts));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::upsert_ticket;
+    use crate::models::Ticket;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn digest_counts_this_weeks_activity() {
+        let conn = setup_db();
+        for (key, created, resolved) in [
+            (
+                "OPS-1",
+                "2024-12-01T09:00:00+00:00",
+                Some("2025-01-08T09:00:00+00:00"),
+            ),
+            ("OPS-2", "2025-01-07T09:00:00+00:00", None),
+        ] {
+            let ticket = Ticket {
+                created_at: created.to_string(),
+                updated_at: created.to_string(),
+                ..test_support::ticket_resolved_at(key, resolved)
+            };
+            upsert_ticket(&conn, &ticket).expect("ticket");
+        }
+
+        let now = NaiveDate::from_ymd_opt(2025, 1, 10)
+            .and_then(|d| d.and_hms_opt(9, 0, 0))
+            .expect("valid datetime");
+        let digest = build_weekly_digest(&conn, now).expect("digest");
+
+        assert_eq!(digest.created_this_week, 1);
+        assert_eq!(digest.resolved_this_week, 1);
+        assert_eq!(digest.summary.open_tickets, 1);
+        assert_eq!(digest.title(), "TicketDash weekly digest (Jan 3 – Jan 10)");
+        assert!(digest
+            .render_text()
+            .starts_with("*This week:* 1 created, 1 resolved"));
+    }
+}
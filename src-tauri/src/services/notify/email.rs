@@ -0,0 +1,193 @@
+use crate::errors::AppError;
+use crate::models::{SmtpSecurity, SmtpSettings};
+use crate::services::alerts::SlaBreach;
+use crate::services::notify::digest::WeeklyDigest;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::Serialize;
+
+/// A rendered digest email, returned as-is by dry runs.
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestEmail {
+    pub subject: String,
+    pub body: String,
+}
+
+pub fn render_digest_email(digest: &WeeklyDigest, breaches: &[SlaBreach]) -> DigestEmail {
+    // The shared digest text carries Slack `*bold*` markers
+    let mut body = digest.render_text().replace('*', "");
+    body.push_str("\n\n");
+
+    if breaches.is_empty() {
+        body.push_str("No open tickets are past their SLA target.");
+    } else {
+        body.push_str(&format!("SLA breaches ({}):", breaches.len()));
+        for breach in breaches {
+            body.push_str(&format!(
+                "\n- {} [{}] {} ({:.1}h of {:.0}h target)",
+                breach.jira_key,
+                breach.priority,
+                breach.summary,
+                breach.elapsed_hours,
+                breach.target_hours
+            ));
+        }
+    }
+
+    DigestEmail {
+        subject: digest.title(),
+        body,
+    }
+}
+
+/// Sends digest emails through the configured SMTP server.
+pub struct EmailSender {
+    from: Mailbox,
+    to: Vec<Mailbox>,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl EmailSender {
+    pub fn new(settings: &SmtpSettings, password: Option<String>) -> Result<Self, AppError> {
+        if settings.host.trim().is_empty() {
+            return Err(AppError::Config("SMTP host is not configured".to_string()));
+        }
+        if settings.to.is_empty() {
+            return Err(AppError::Config(
+                "Digest email needs at least one recipient".to_string(),
+            ));
+        }
+
+        let from = parse_mailbox(&settings.from)?;
+        let to = settings
+            .to
+            .iter()
+            .map(|address| parse_mailbox(address))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut builder = match settings.security {
+            SmtpSecurity::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&settings.host)
+                    .map_err(|e| AppError::Email(e.to_string()))?
+            }
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&settings.host)
+                .map_err(|e| AppError::Email(e.to_string()))?,
+            SmtpSecurity::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&settings.host)
+            }
+        }
+        .port(settings.port);
+
+        if let Some(username) = settings.username.as_deref().filter(|u| !u.is_empty()) {
+            builder = builder.credentials(Credentials::new(
+                username.to_string(),
+                password.unwrap_or_default(),
+            ));
+        }
+
+        Ok(EmailSender {
+            from,
+            to,
+            transport: builder.build(),
+        })
+    }
+
+    pub async fn send(&self, email: &DigestEmail) -> Result<(), AppError> {
+        let mut builder = Message::builder()
+            .from(self.from.clone())
+            .subject(email.subject.as_str())
+            .header(ContentType::TEXT_PLAIN);
+        for recipient in &self.to {
+            builder = builder.to(recipient.clone());
+        }
+        let message = builder
+            .body(email.body.clone())
+            .map_err(|e| AppError::Email(e.to_string()))?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| AppError::Email(e.to_string()))?;
+        Ok(())
+    }
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox, AppError> {
+    address
+        .parse()
+        .map_err(|_| AppError::Config(format!("Invalid email address: {}", address)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SummaryStats;
+    use chrono::NaiveDate;
+
+    fn digest() -> WeeklyDigest {
+        let period_end = NaiveDate::from_ymd_opt(2025, 1, 10)
+            .and_then(|d| d.and_hms_opt(9, 0, 0))
+            .expect("valid datetime");
+        WeeklyDigest {
+            period_start: period_end - chrono::Duration::days(7),
+            period_end,
+            created_this_week: 3,
+            resolved_this_week: 2,
+            summary: SummaryStats {
+                total_tickets: 10,
+                open_tickets: 4,
+                resolved_tickets: 6,
+                stale_tickets: 1,
+                blocked_tickets: 0,
+                overdue_tickets: 0,
+                avg_resolution_hours: 12.0,
+                median_resolution_hours: 8.0,
+                p75_resolution_hours: 16.0,
+                p90_resolution_hours: 30.0,
+                p95_resolution_hours: 40.0,
+                escalated_tickets: 0,
+                escalation_rate: 0.0,
+                escalated_avg_resolution_hours: 0.0,
+                escalated_median_resolution_hours: 0.0,
+            },
+            top_statuses: Vec::new(),
+            top_priorities: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn renders_breaches_without_slack_markup() {
+        let breaches = vec![SlaBreach {
+            jira_key: "OPS-7".to_string(),
+            summary: "Printer on fire".to_string(),
+            priority: "Critical".to_string(),
+            elapsed_hours: 6.5,
+            target_hours: 4.0,
+        }];
+
+        let email = render_digest_email(&digest(), &breaches);
+
+        assert_eq!(email.subject, "TicketDash weekly digest (Jan 3 – Jan 10)");
+        assert!(email.body.starts_with("This week: 3 created, 2 resolved"));
+        assert!(!email.body.contains('*'));
+        assert!(email
+            .body
+            .contains("SLA breaches (1):\n- OPS-7 [Critical] Printer on fire (6.5h of 4h target)"));
+    }
+
+    #[test]
+    fn rejects_settings_without_recipients() {
+        let settings = SmtpSettings {
+            host: "smtp.example.com".to_string(),
+            from: "dash@example.com".to_string(),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            EmailSender::new(&settings, None),
+            Err(AppError::Config(_))
+        ));
+    }
+}
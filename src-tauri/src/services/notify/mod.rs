@@ -0,0 +1,4 @@
+pub mod digest;
+pub mod email;
+pub mod slack;
+pub mod webhook_out;
@@ -0,0 +1,96 @@
+use crate::errors::{AppError, ConnectorError};
+use crate::models::Alert;
+use crate::services::notify::digest::WeeklyDigest;
+
+pub const SOURCE_ID: &str = "slack";
+
+/// Posts messages to a Slack incoming webhook.
+pub struct SlackNotifier {
+    webhook_url: String,
+    client: reqwest::Client,
+}
+
+impl SlackNotifier {
+    pub fn new(webhook_url: &str, client: reqwest::Client) -> Result<Self, AppError> {
+        if !webhook_url.starts_with("https://hooks.slack.com/") {
+            return Err(AppError::Config(
+                "Slack webhook URL must start with https://hooks.slack.com/".to_string(),
+            ));
+        }
+
+        Ok(SlackNotifier {
+            webhook_url: webhook_url.to_string(),
+            client,
+        })
+    }
+
+    pub async fn post_alert(&self, alert: &Alert) -> Result<(), AppError> {
+        self.post(&alert_message(alert)).await
+    }
+
+    pub async fn post_digest(&self, digest: &WeeklyDigest) -> Result<(), AppError> {
+        self.post(&format!("*{}*\n{}", digest.title(), digest.render_text()))
+            .await
+    }
+
+    async fn post(&self, text: &str) -> Result<(), AppError> {
+        let response = self
+            .client
+            .post(&self.webhook_url)
+            .json(&serde_json::json!({ "text": text }))
+            .send()
+            .await
+            .map_err(|e| ConnectorError::Http(SOURCE_ID, e))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            let body = response.text().await.unwrap_or_default();
+            Err(ConnectorError::ApiError {
+                source_id: SOURCE_ID,
+                status: status.as_u16(),
+                body,
+            }
+            .into())
+        }
+    }
+}
+
+fn alert_message(alert: &Alert) -> String {
+    match &alert.ticket_key {
+        Some(key) => format!(
+            ":rotating_light: *{}* ({})\n{}",
+            alert.title, key, alert.body
+        ),
+        None => format!(":rotating_light: *{}*\n{}", alert.title, alert.body),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_non_slack_urls() {
+        assert!(SlackNotifier::new("https://example.com/hook", reqwest::Client::new()).is_err());
+        assert!(SlackNotifier::new(
+            "https://hooks.slack.com/services/T000/B000/XXXX",
+            reqwest::Client::new()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn alert_message_includes_ticket_key() {
+        let alert = Alert {
+            title: "SLA breached: OPS-1".to_string(),
+            body: "VPN drops".to_string(),
+            ticket_key: Some("OPS-1".to_string()),
+        };
+        assert_eq!(
+            alert_message(&alert),
+            ":rotating_light: *SLA breached: OPS-1* (OPS-1)\nVPN drops"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use crate::db::{outbound_webhooks_for, DbPool};
+use crate::errors::{AppError, ConnectorError};
+use crate::models::{OutboundWebhook, WebhookEvent};
+use hmac::{Hmac, Mac};
+use rusqlite::Connection;
+use serde::Serialize;
+use sha2::Sha256;
+use std::sync::{Arc, Mutex};
+
+pub const SOURCE_ID: &str = "outbound_webhook";
+
+/// `sha256=<hex>` HMAC of `<timestamp>.<body>` with the signing secret.
+pub const SIGNATURE_HEADER: &str = "X-TicketDash-Signature";
+/// Unix seconds when the request was signed, so receivers can drop replays.
+pub const TIMESTAMP_HEADER: &str = "X-TicketDash-Timestamp";
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    event: WebhookEvent,
+    sent_at: String,
+    data: &'a serde_json::Value,
+}
+
+fn hmac_sha256_hex(secret: &str, message: &str) -> Result<String, AppError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| AppError::Internal(format!("Invalid signing key: {}", e)))?;
+    mac.update(message.as_bytes());
+    Ok(mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+fn signature(secret: &str, timestamp: i64, body: &str) -> Result<String, AppError> {
+    Ok(format!(
+        "sha256={}",
+        hmac_sha256_hex(secret, &format!("{}.{}", timestamp, body))?
+    ))
+}
+
+/// Posts signed JSON payloads to outbound webhooks.
+pub struct WebhookSender {
+    client: reqwest::Client,
+    secret: String,
+}
+
+impl WebhookSender {
+    pub fn new(client: reqwest::Client, secret: String) -> Self {
+        WebhookSender { client, secret }
+    }
+
+    pub async fn send(
+        &self,
+        webhook: &OutboundWebhook,
+        event: WebhookEvent,
+        data: &serde_json::Value,
+    ) -> Result<(), AppError> {
+        let now = chrono::Utc::now();
+        let body = serde_json::to_string(&Payload {
+            event,
+            sent_at: now.to_rfc3339(),
+            data,
+        })
+        .map_err(|e| AppError::Internal(format!("Failed to serialize webhook payload: {}", e)))?;
+        let timestamp = now.timestamp();
+
+        let response = self
+            .client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, signature(&self.secret, timestamp, &body)?)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| ConnectorError::Http(SOURCE_ID, e))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            let body = response.text().await.unwrap_or_default();
+            Err(ConnectorError::ApiError {
+                source_id: SOURCE_ID,
+                status: status.as_u16(),
+                body,
+            }
+            .into())
+        }
+    }
+}
+
+/// Posts each of `items` to every enabled webhook subscribed to `event`.
+/// A receiver that is down is logged rather than failing the caller.
+pub async fn dispatch(
+    db_pool: Arc<Mutex<Connection>>,
+    app_handle: &tauri::AppHandle,
+    event: WebhookEvent,
+    items: Vec<serde_json::Value>,
+) -> Result<(), AppError> {
+    if items.is_empty() {
+        return Ok(());
+    }
+    let webhooks = DbPool(db_pool)
+        .run(move |conn| outbound_webhooks_for(conn, event))
+        .await?;
+    if webhooks.is_empty() {
+        return Ok(());
+    }
+
+    let sender = WebhookSender::new(
+        crate::commands::settings::http_client(app_handle)?,
+        crate::commands::settings::webhook_signing_secret(false)?,
+    );
+    for webhook in &webhooks {
+        for data in &items {
+            if let Err(e) = sender.send(webhook, event, data).await {
+                log::warn!("Webhook '{}' delivery failed: {}", webhook.name, e);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payloads_are_signed_with_hmac_sha256() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            hmac_sha256_hex("Jefe", "what do ya want for nothing?").expect("hmac"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        let body = r#"{"event":"sync_completed"}"#;
+        let signed = signature("s3cret", 1736154000, body).expect("signature");
+        assert_eq!(
+            signed,
+            format!(
+                "sha256={}",
+                hmac_sha256_hex("s3cret", &format!("1736154000.{}", body)).expect("hmac")
+            )
+        );
+        assert_ne!(
+            signed,
+            signature("s3cret", 1736154001, body).expect("signature")
+        );
+        assert_ne!(
+            signed,
+            signature("other", 1736154000, body).expect("signature")
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use crate::db::{
+    clear_local_change, delete_outbox_item, get_pending_outbox, jira_issue_key, outbox_item_due,
+    record_outbox_attempt, ticket_key_account, DbPool,
+};
+use crate::errors::{AppError, JiraError};
+use crate::jira::JiraClient;
+use crate::models::{LocalField, OutboxAction, OutboxFlushSummary, OutboxStatus};
+use chrono::Utc;
+
+/// Longest wait between automatic retries of an unsent item.
+const MAX_RETRY_MINUTES: i64 = 60;
+
+/// Jira couldn't be reached at all, as opposed to rejecting the request.
+pub fn is_offline_error(error: &AppError) -> bool {
+    matches!(error, AppError::JiraApi(JiraError::Http(_)))
+}
+
+/// Backoff after `attempts` failed sends: 1, 2, 4, ... minutes, capped.
+fn retry_delay(attempts: u32) -> chrono::Duration {
+    chrono::Duration::minutes((1_i64 << attempts.min(6)).min(MAX_RETRY_MINUTES))
+}
+
+/// Sends the write-backs queued for tickets of `account_id` (`None` for the
+/// primary site, which `client` must talk to) in the order they were made.
+/// Stops at the first item Jira can't be reached for, or (unless `force`)
+/// that is still backing off, so later actions never overtake earlier ones.
+/// Items Jira rejects are marked failed and skipped.
+pub async fn flush_outbox(
+    client: &JiraClient,
+    db: &DbPool,
+    account_id: Option<i64>,
+    force: bool,
+) -> Result<OutboxFlushSummary, AppError> {
+    let items: Vec<_> = db
+        .run(get_pending_outbox)
+        .await?
+        .into_iter()
+        .filter(|item| ticket_key_account(&item.ticket_key) == account_id)
+        .collect();
+    let mut summary = OutboxFlushSummary::default();
+
+    for (idx, item) in items.iter().enumerate() {
+        let now = Utc::now();
+        if !force && !outbox_item_due(item, now) {
+            summary.pending = (items.len() - idx) as u32;
+            break;
+        }
+
+        let issue_key = jira_issue_key(&item.ticket_key);
+        let result = match &item.action {
+            OutboxAction::Comment { body } => client.add_comment(issue_key, body).await,
+            OutboxAction::Transition { transition_id } => {
+                client.transition_issue(issue_key, transition_id).await
+            }
+            OutboxAction::Assign { assignee } => {
+                client.assign_issue(issue_key, assignee.as_deref()).await
+            }
+        };
+
+        let id = item.id;
+        let key = item.ticket_key.clone();
+        let is_transition = matches!(item.action, OutboxAction::Transition { .. });
+        match result {
+            Ok(()) => {
+                db.run(move |conn| {
+                    delete_outbox_item(conn, id)?;
+                    if is_transition {
+                        clear_local_change(conn, &key, LocalField::Status)?;
+                    }
+                    Ok(())
+                })
+                .await?;
+                summary.sent += 1;
+            }
+            Err(e) if is_offline_error(&e) => {
+                let next = now + retry_delay(item.attempts);
+                let error = e.to_string();
+                db.run(move |conn| {
+                    record_outbox_attempt(conn, id, OutboxStatus::Pending, &error, now, Some(next))
+                })
+                .await?;
+                summary.pending = (items.len() - idx) as u32;
+                break;
+            }
+            Err(e) => {
+                log::warn!("Jira rejected queued {:?} for {}: {}", item.action, key, e);
+                let error = e.to_string();
+                db.run(move |conn| {
+                    record_outbox_attempt(conn, id, OutboxStatus::Failed, &error, now, None)?;
+                    // Let the next sync restore Jira's status
+                    if is_transition {
+                        clear_local_change(conn, &key, LocalField::Status)?;
+                    }
+                    Ok(())
+                })
+                .await?;
+                summary.failed += 1;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+    use crate::db::{account_ticket_key, enqueue_outbox, get_outbox, insert_jira_account};
+    use crate::jira::{JiraAuth, JiraAuthMethod};
+    use std::sync::{Arc, Mutex};
+
+    /// A Server/Data Center Jira on a local port that accepts every write and
+    /// records it as "METHOD path".
+    fn fake_jira() -> (String, Arc<Mutex<Vec<String>>>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").expect("bind fake Jira");
+        let url = format!("http://{}", server.server_addr());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        std::thread::spawn(move || {
+            for request in server.incoming_requests() {
+                let response = if request.url().ends_with("/serverInfo") {
+                    tiny_http::Response::from_string(r#"{"deploymentType":"Server"}"#)
+                } else {
+                    recorded.lock().expect("requests lock").push(format!(
+                        "{} {}",
+                        request.method(),
+                        request.url()
+                    ));
+                    tiny_http::Response::from_string("")
+                };
+                let _ = request.respond(response);
+            }
+        });
+        (url, requests)
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        let minutes: Vec<i64> = [0, 1, 2, 5, 6, 40]
+            .into_iter()
+            .map(|attempts| retry_delay(attempts).num_minutes())
+            .collect();
+        assert_eq!(minutes, vec![1, 2, 4, 32, 60, 60]);
+    }
+
+    #[test]
+    fn account_items_go_to_their_own_site_without_the_key_suffix() {
+        let conn = setup_db();
+        let account = insert_jira_account(
+            &conn,
+            "EU",
+            "https://eu.example.net",
+            "a@x.io",
+            JiraAuthMethod::Pat,
+        )
+        .expect("insert account");
+        let account_key = account_ticket_key(account.id, "OPS-1");
+        let now = Utc::now();
+        for action in [
+            OutboxAction::Transition {
+                transition_id: "31".to_string(),
+            },
+            OutboxAction::Comment {
+                body: "Fixed".to_string(),
+            },
+        ] {
+            enqueue_outbox(&conn, &account_key, &action, now).expect("queued");
+        }
+        enqueue_outbox(
+            &conn,
+            "OPS-1",
+            &OutboxAction::Comment {
+                body: "Primary".to_string(),
+            },
+            now,
+        )
+        .expect("queued");
+        let db = DbPool(Arc::new(Mutex::new(conn)));
+
+        let (url, requests) = fake_jira();
+        let auth = JiraAuth::Bearer {
+            token: "pat".to_string(),
+        };
+        let client = JiraClient::new(&url, auth, reqwest::Client::new())
+            .expect("client")
+            .with_account(account.id);
+        let summary =
+            tauri::async_runtime::block_on(flush_outbox(&client, &db, Some(account.id), true))
+                .expect("flushed");
+
+        assert_eq!(summary.sent, 2);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            *requests.lock().expect("requests lock"),
+            vec![
+                "POST /rest/api/2/issue/OPS-1/transitions".to_string(),
+                "POST /rest/api/2/issue/OPS-1/comment".to_string(),
+            ]
+        );
+        // The primary site's item waits for the primary client
+        let conn = db.0.lock().expect("db lock");
+        let left = get_outbox(&conn).expect("outbox");
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].ticket_key, "OPS-1");
+    }
+}
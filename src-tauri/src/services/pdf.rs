@@ -0,0 +1,250 @@
+//! Minimal PDF 1.4 writer for text reports: A4 pages of left-aligned lines
+//! in the standard Helvetica fonts, so no font files need embedding.
+
+const PAGE_WIDTH: u32 = 595;
+const PAGE_HEIGHT: u32 = 842;
+const MARGIN: u32 = 50;
+/// Characters per line at body size before wrapping; Helvetica averages
+/// about half an em per character.
+const WRAP_COLUMNS: usize = 95;
+
+struct Line {
+    text: Vec<u8>,
+    bold: bool,
+    size: u32,
+}
+
+impl Line {
+    fn leading(&self) -> u32 {
+        self.size + self.size / 2
+    }
+}
+
+/// Encodes `text` as WinAnsi, the encoding of the standard fonts. Characters
+/// it can't represent become `?`.
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '€' => 0x80,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            c if (c as u32) < 0x80 || (0xa0..=0xff).contains(&(c as u32)) => c as u8,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// Escapes a PDF literal string.
+fn escape(text: &[u8]) -> Vec<u8> {
+    let mut escaped = Vec::with_capacity(text.len());
+    for &byte in text {
+        if matches!(byte, b'\\' | b'(' | b')') {
+            escaped.push(b'\\');
+        }
+        escaped.push(byte);
+    }
+    escaped
+}
+
+/// Splits `text` at spaces into lines of at most `columns` characters;
+/// longer words are cut.
+fn wrap(text: &str, columns: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split(' ') {
+        let mut word = word.to_string();
+        while word.chars().count() > columns {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            let cut: String = word.chars().take(columns).collect();
+            word = word.chars().skip(columns).collect();
+            lines.push(cut);
+        }
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > columns {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+    lines.push(current);
+    lines
+}
+
+/// Turns report Markdown into styled lines: `#` and `##` headings are bold
+/// and larger, `**` markers are dropped, and long lines wrap.
+fn markdown_lines(markdown: &str) -> Vec<Line> {
+    let mut lines = Vec::new();
+    for source in markdown.lines() {
+        let (text, bold, size) = if let Some(heading) = source.strip_prefix("# ") {
+            (heading, true, 16)
+        } else if let Some(heading) = source.strip_prefix("## ") {
+            (heading, true, 12)
+        } else {
+            (source, false, 10)
+        };
+        let text = text.replace("**", "");
+        let columns = WRAP_COLUMNS * 10 / size as usize;
+        for wrapped in wrap(&text, columns) {
+            lines.push(Line {
+                text: win_ansi(&wrapped),
+                bold,
+                size,
+            });
+        }
+    }
+    lines
+}
+
+/// Content streams for each page.
+fn paginate(lines: &[Line]) -> Vec<Vec<u8>> {
+    let mut pages = Vec::new();
+    let mut content = Vec::new();
+    let mut y = PAGE_HEIGHT - MARGIN;
+    for line in lines {
+        if y < MARGIN + line.leading() && !content.is_empty() {
+            pages.push(std::mem::take(&mut content));
+            y = PAGE_HEIGHT - MARGIN;
+        }
+        y -= line.leading();
+        if line.text.is_empty() {
+            continue;
+        }
+        let font = if line.bold { "F2" } else { "F1" };
+        content.extend_from_slice(
+            format!("BT /{} {} Tf {} {} Td (", font, line.size, MARGIN, y).as_bytes(),
+        );
+        content.extend(escape(&line.text));
+        content.extend_from_slice(b") Tj ET\n");
+    }
+    pages.push(content);
+    pages
+}
+
+/// Renders report Markdown as a PDF document.
+pub fn markdown_to_pdf(markdown: &str) -> Vec<u8> {
+    let pages = paginate(&markdown_lines(markdown));
+
+    // 1 catalog, 2 page tree, 3-4 fonts, then a page and its content per page
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| 5 + 2 * i).collect();
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{} 0 R", id))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+    ];
+    for (page_id, content) in page_ids.iter().zip(pages) {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                page_id + 1
+            )
+            .into_bytes(),
+        );
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend(content);
+        stream.extend_from_slice(b"endstream");
+        objects.push(stream);
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (idx, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", idx + 1).as_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+    let xref = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+    }
+
+    #[test]
+    fn long_reports_span_pages_with_a_valid_xref() {
+        let mut markdown = "# Report – Dana (Q1)\n\n".to_string();
+        for i in 0..120 {
+            markdown.push_str(&format!("- OPS-{} fix the (flaky) VPN\n", i));
+        }
+        let pdf = markdown_to_pdf(&markdown);
+
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        assert!(contains(&pdf, b"/Count 3 >>"));
+        assert!(contains(&pdf, b"(Report \x96 Dana \\(Q1\\)) Tj"));
+        assert!(contains(&pdf, b"(- OPS-7 fix the \\(flaky\\) VPN) Tj"));
+
+        // Every xref entry points at the start of its object
+        let tail = String::from_utf8_lossy(&pdf[pdf.len() - 40..]).into_owned();
+        let xref_start: usize = tail
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|rest| rest.lines().next())
+            .and_then(|offset| offset.parse().ok())
+            .expect("startxref");
+        let xref = String::from_utf8_lossy(&pdf[xref_start..]).into_owned();
+        assert!(xref.starts_with("xref\n0 11\n"));
+        let entries: Vec<&str> = xref
+            .lines()
+            .skip(3)
+            .take_while(|line| line.ends_with(" n "))
+            .collect();
+        assert_eq!(entries.len(), 10);
+        for (idx, entry) in entries.iter().enumerate() {
+            let offset: usize = entry[..10].parse().expect("offset");
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", idx + 1).as_bytes()));
+        }
+    }
+
+    #[test]
+    fn lines_wrap_at_spaces() {
+        assert_eq!(wrap("aaa bbb ccc", 7), vec!["aaa bbb", "ccc"]);
+        assert_eq!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("", 4), vec![""]);
+    }
+}
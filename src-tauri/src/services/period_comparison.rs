@@ -0,0 +1,288 @@
+use crate::db::queries::resolution_hours;
+use crate::db::support_calendars::project_schedules;
+use crate::db::{get_count_by, get_summary_stats, get_tickets};
+use crate::errors::AppError;
+use crate::models::{
+    DateRange, Dimension, DurationMode, MetricDelta, PeriodComparison, ReportPeriod, TicketFilter,
+};
+use crate::services::snapshots::{diff_values, sort_by_change};
+use chrono::{Months, NaiveDate};
+use rusqlite::Connection;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Categories taken from each period for `top_categories`.
+const TOP_CATEGORIES: usize = 5;
+
+/// Reads `YYYY`, `YYYY-Qn` or `YYYY-MM` as the span it names.
+pub fn parse_period(label: &str) -> Result<ReportPeriod, AppError> {
+    let invalid = || {
+        AppError::Config(format!(
+            "Invalid period (expected YYYY, YYYY-Qn or YYYY-MM): {}",
+            label
+        ))
+    };
+    let label = label.trim();
+    let (year, rest) = label.split_once('-').unwrap_or((label, ""));
+    let year: i32 = year.parse().map_err(|_| invalid())?;
+    let (month, months) = if rest.is_empty() {
+        (1, 12)
+    } else if let Some(quarter) = rest.strip_prefix(['Q', 'q']) {
+        match quarter.parse::<u32>() {
+            Ok(quarter @ 1..=4) => (quarter * 3 - 2, 3),
+            _ => return Err(invalid()),
+        }
+    } else {
+        match rest.parse::<u32>() {
+            Ok(month @ 1..=12) if rest.len() == 2 => (month, 1),
+            _ => return Err(invalid()),
+        }
+    };
+    let from = NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(invalid)?;
+    let to = from
+        .checked_add_months(Months::new(months))
+        .ok_or_else(invalid)?;
+    Ok(ReportPeriod {
+        label: label.to_string(),
+        from: from.to_string(),
+        to: to.to_string(),
+    })
+}
+
+struct PeriodMetrics {
+    created: f64,
+    resolved: f64,
+    median_resolution_hours: f64,
+    sla_compliance: Option<f64>,
+    categories: BTreeMap<String, f64>,
+    top_categories: Vec<String>,
+}
+
+fn period_metrics(
+    conn: &Connection,
+    filter: &TicketFilter,
+    period: &ReportPeriod,
+    mode: DurationMode,
+    sla_targets_hours: &BTreeMap<String, f64>,
+) -> Result<PeriodMetrics, AppError> {
+    let range = DateRange {
+        from: Some(period.from.clone()),
+        to: Some(period.to.clone()),
+    };
+    let created = filter.clone().created_in(range.clone());
+    let resolved = filter.clone().resolved_in(range);
+    let resolved_stats = get_summary_stats(conn, &resolved, mode)?;
+
+    // Same rule as the per-project SLA compliance: business hours against
+    // the target for the ticket's priority
+    let schedules = project_schedules(conn)?;
+    let (mut checked, mut met) = (0u32, 0u32);
+    for ticket in get_tickets(conn, &resolved)? {
+        let Some(target) = sla_targets_hours.get(ticket.sla_priority()) else {
+            continue;
+        };
+        let Some(hours) = ticket.resolved_at.as_deref().and_then(|resolved_at| {
+            resolution_hours(
+                &ticket.created_at,
+                resolved_at,
+                DurationMode::Business,
+                schedules.get(&ticket.project_key),
+            )
+        }) else {
+            continue;
+        };
+        checked += 1;
+        if hours <= *target {
+            met += 1;
+        }
+    }
+
+    let categories = get_count_by(conn, &Dimension::Category, &created)?;
+    Ok(PeriodMetrics {
+        created: categories.iter().map(|entry| f64::from(entry.count)).sum(),
+        resolved: f64::from(resolved_stats.resolved_tickets),
+        median_resolution_hours: resolved_stats.median_resolution_hours,
+        sla_compliance: (checked > 0).then(|| f64::from(met) / f64::from(checked)),
+        top_categories: categories
+            .iter()
+            .take(TOP_CATEGORIES)
+            .map(|entry| entry.name.clone())
+            .collect(),
+        categories: categories
+            .into_iter()
+            .map(|entry| (entry.name, f64::from(entry.count)))
+            .collect(),
+    })
+}
+
+/// Compares `filter`'s tickets between periods `a` and `b`: tickets created
+/// and resolved, median resolution time, SLA compliance, and created tickets
+/// in the top categories, biggest movers first. The filter's own created and
+/// resolved ranges are replaced by each period.
+pub fn compare_periods(
+    conn: &Connection,
+    a: ReportPeriod,
+    b: ReportPeriod,
+    filter: &TicketFilter,
+    mode: DurationMode,
+    sla_targets_hours: &BTreeMap<String, f64>,
+) -> Result<PeriodComparison, AppError> {
+    let filter = TicketFilter {
+        created: None,
+        resolved: None,
+        ..filter.clone()
+    };
+    let before = period_metrics(conn, &filter, &a, mode, sla_targets_hours)?;
+    let after = period_metrics(conn, &filter, &b, mode, sla_targets_hours)?;
+
+    let delta = |name: &str, from: f64, to: f64| MetricDelta {
+        name: name.to_string(),
+        from,
+        to,
+        change: to - from,
+    };
+    let mut summary = vec![
+        delta("created", before.created, after.created),
+        delta("resolved", before.resolved, after.resolved),
+        delta(
+            "median_resolution_hours",
+            before.median_resolution_hours,
+            after.median_resolution_hours,
+        ),
+    ];
+    if let (Some(from), Some(to)) = (before.sla_compliance, after.sla_compliance) {
+        summary.push(delta("sla_compliance", from, to));
+    }
+
+    let top: BTreeSet<&String> = before
+        .top_categories
+        .iter()
+        .chain(&after.top_categories)
+        .collect();
+    let pick = |counts: &BTreeMap<String, f64>| -> BTreeMap<String, f64> {
+        counts
+            .iter()
+            .filter(|(name, _)| top.contains(name))
+            .map(|(name, count)| (name.clone(), *count))
+            .collect()
+    };
+    let mut top_categories = diff_values(pick(&before.categories), pick(&after.categories));
+    sort_by_change(&mut top_categories);
+
+    Ok(PeriodComparison {
+        a,
+        b,
+        summary,
+        top_categories,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::upsert_ticket;
+    use crate::models::Ticket;
+
+    fn ticket(key: &str, category: &str, created_at: &str, resolved_at: Option<&str>) -> Ticket {
+        Ticket {
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            category: Some(category.to_string()),
+            ..test_support::ticket_resolved_at(key, resolved_at)
+        }
+    }
+
+    #[test]
+    fn periods_parse_as_years_quarters_and_months() {
+        let span = |label: &str| {
+            parse_period(label)
+                .map(|period| (period.from, period.to))
+                .ok()
+        };
+        assert_eq!(
+            span("2025"),
+            Some(("2025-01-01".to_string(), "2026-01-01".to_string()))
+        );
+        assert_eq!(
+            span("2024-q4"),
+            Some(("2024-10-01".to_string(), "2025-01-01".to_string()))
+        );
+        assert_eq!(
+            span("2025-02"),
+            Some(("2025-02-01".to_string(), "2025-03-01".to_string()))
+        );
+        assert_eq!(span("2025-Q5"), None);
+        assert_eq!(span("2025-2"), None);
+        assert_eq!(span("Q1"), None);
+    }
+
+    #[test]
+    fn quarters_compare_counts_resolution_sla_and_categories() {
+        let conn = setup_db();
+        for ticket in [
+            // Q1: two network tickets, resolved in 2h and 4h
+            ticket(
+                "OPS-1",
+                "Network",
+                "2025-01-06T09:00:00Z",
+                Some("2025-01-06T11:00:00Z"),
+            ),
+            ticket(
+                "OPS-2",
+                "Network",
+                "2025-02-03T09:00:00Z",
+                Some("2025-02-03T13:00:00Z"),
+            ),
+            // Q2: one network and two access tickets, one resolved late
+            ticket(
+                "OPS-3",
+                "Network",
+                "2025-04-07T09:00:00Z",
+                Some("2025-04-07T10:00:00Z"),
+            ),
+            ticket(
+                "OPS-4",
+                "Access",
+                "2025-04-08T09:00:00Z",
+                Some("2025-04-08T16:00:00Z"),
+            ),
+            ticket("OPS-5", "Access", "2025-05-05T09:00:00Z", None),
+        ] {
+            upsert_ticket(&conn, &ticket).expect("ticket");
+        }
+
+        let targets = BTreeMap::from([("High".to_string(), 5.0)]);
+        let comparison = compare_periods(
+            &conn,
+            parse_period("2025-Q1").expect("period"),
+            parse_period("2025-Q2").expect("period"),
+            &TicketFilter::default(),
+            DurationMode::Calendar,
+            &targets,
+        )
+        .expect("comparison");
+        let rows = |deltas: &[MetricDelta]| -> Vec<(String, f64, f64)> {
+            deltas
+                .iter()
+                .map(|d| (d.name.clone(), d.from, d.to))
+                .collect()
+        };
+
+        assert_eq!(
+            rows(&comparison.summary),
+            vec![
+                ("created".to_string(), 2.0, 3.0),
+                ("resolved".to_string(), 2.0, 2.0),
+                ("median_resolution_hours".to_string(), 3.0, 4.0),
+                ("sla_compliance".to_string(), 1.0, 0.5),
+            ]
+        );
+        assert_eq!(
+            rows(&comparison.top_categories),
+            vec![
+                ("Access".to_string(), 0.0, 2.0),
+                ("Network".to_string(), 2.0, 1.0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use crate::db::queries::resolution_hours;
+use crate::db::support_calendars::project_schedules;
+use crate::db::{get_summary_stats, get_tickets};
+use crate::errors::AppError;
+use crate::models::{
+    CountEntry, DateRange, DurationMode, PersonReport, PriorityLevel, ReportTicket, Ticket,
+    TicketFilter,
+};
+use crate::services::time_calc::parse_timestamp;
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+use std::collections::BTreeMap;
+
+/// Open tickets listed under "Aging".
+const AGING_TICKETS: usize = 10;
+/// Resolutions listed under "Notable resolutions".
+const NOTABLE_TICKETS: usize = 5;
+
+/// Position of `priority` on the canonical scale; names off the scale sort last.
+fn priority_rank(priority: &str) -> usize {
+    PriorityLevel::parse(priority).map_or(PriorityLevel::ALL.len(), |level| level as usize)
+}
+
+fn report_ticket(ticket: &Ticket) -> ReportTicket {
+    ReportTicket {
+        jira_key: ticket.jira_key.clone(),
+        summary: ticket.summary.clone(),
+        priority: ticket.sla_priority().to_string(),
+        status: ticket.status.clone(),
+        age_days: None,
+        resolution_hours: None,
+    }
+}
+
+/// Summarizes `assignee`'s work from `from` up to (not including) `to`:
+/// throughput and resolution times, their current open load, the oldest of
+/// it, and the most important tickets they resolved.
+pub fn build_person_report(
+    conn: &Connection,
+    assignee: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+    mode: DurationMode,
+    now: NaiveDateTime,
+) -> Result<PersonReport, AppError> {
+    let theirs = TicketFilter {
+        assignees: vec![assignee.to_string()],
+        ..Default::default()
+    };
+    let in_range = theirs.clone().resolved_in(DateRange {
+        from: Some(from.to_string()),
+        to: Some(to.to_string()),
+    });
+    let throughput = get_summary_stats(conn, &in_range, mode)?;
+    let weeks = ((to - from).num_days() as f64 / 7.0).max(1.0);
+
+    let open: Vec<Ticket> = get_tickets(conn, &theirs)?
+        .into_iter()
+        .filter(|ticket| ticket.resolved_at.is_none())
+        .collect();
+    let mut load: BTreeMap<(usize, String), u32> = BTreeMap::new();
+    for ticket in &open {
+        let priority = ticket.sla_priority();
+        *load
+            .entry((priority_rank(priority), priority.to_string()))
+            .or_default() += 1;
+    }
+
+    let mut aging: Vec<ReportTicket> = open
+        .iter()
+        .filter_map(|ticket| {
+            let created = parse_timestamp(&ticket.created_at)?;
+            Some(ReportTicket {
+                age_days: Some((now - created).num_days()),
+                ..report_ticket(ticket)
+            })
+        })
+        .collect();
+    aging.sort_by(|a, b| b.age_days.cmp(&a.age_days));
+    aging.truncate(AGING_TICKETS);
+
+    let schedules = project_schedules(conn)?;
+    let mut notable: Vec<ReportTicket> = get_tickets(conn, &in_range)?
+        .iter()
+        .map(|ticket| ReportTicket {
+            resolution_hours: ticket.resolved_at.as_deref().and_then(|resolved_at| {
+                resolution_hours(
+                    &ticket.created_at,
+                    resolved_at,
+                    mode,
+                    schedules.get(&ticket.project_key),
+                )
+            }),
+            ..report_ticket(ticket)
+        })
+        .collect();
+    notable.sort_by(|a, b| {
+        priority_rank(&a.priority)
+            .cmp(&priority_rank(&b.priority))
+            .then(
+                b.resolution_hours
+                    .unwrap_or(0.0)
+                    .total_cmp(&a.resolution_hours.unwrap_or(0.0)),
+            )
+    });
+    notable.truncate(NOTABLE_TICKETS);
+
+    let mut report = PersonReport {
+        assignee: assignee.to_string(),
+        from: from.to_string(),
+        to: to.to_string(),
+        resolved_count: throughput.resolved_tickets,
+        resolved_per_week: throughput.resolved_tickets as f64 / weeks,
+        median_resolution_hours: throughput.median_resolution_hours,
+        avg_resolution_hours: throughput.avg_resolution_hours,
+        open_tickets: open.len() as u32,
+        load_by_priority: load
+            .into_iter()
+            .map(|((_, name), count)| CountEntry { name, count })
+            .collect(),
+        aging,
+        notable,
+        markdown: String::new(),
+    };
+    report.markdown = render_markdown(&report, to.pred_opt().unwrap_or(to));
+    Ok(report)
+}
+
+/// Markdown with `#` headings, which `pdf::markdown_to_pdf` also understands.
+fn render_markdown(report: &PersonReport, last_day: NaiveDate) -> String {
+    let tickets = |items: &[ReportTicket], detail: &dyn Fn(&ReportTicket) -> String| {
+        if items.is_empty() {
+            return vec!["- None".to_string()];
+        }
+        items
+            .iter()
+            .map(|item| format!("- {} {} ({})", item.jira_key, item.summary, detail(item)))
+            .collect()
+    };
+
+    let mut lines = vec![
+        format!("# {}: {} to {}", report.assignee, report.from, last_day),
+        String::new(),
+        "## Throughput".to_string(),
+        format!(
+            "- Resolved: {} ({:.1} per week)",
+            report.resolved_count, report.resolved_per_week
+        ),
+        format!(
+            "- Median resolution: {:.1}h",
+            report.median_resolution_hours
+        ),
+        format!("- Average resolution: {:.1}h", report.avg_resolution_hours),
+        String::new(),
+        "## Current load".to_string(),
+        format!("- Open tickets: {}", report.open_tickets),
+    ];
+    lines.extend(
+        report
+            .load_by_priority
+            .iter()
+            .map(|entry| format!("- {}: {}", entry.name, entry.count)),
+    );
+    lines.push(String::new());
+    lines.push("## Aging".to_string());
+    lines.extend(tickets(&report.aging, &|item| {
+        format!("{}, {} days", item.status, item.age_days.unwrap_or(0))
+    }));
+    lines.push(String::new());
+    lines.push("## Notable resolutions".to_string());
+    lines.extend(tickets(
+        &report.notable,
+        &|item| match item.resolution_hours {
+            Some(hours) => format!("{}, {:.1}h", item.priority, hours),
+            None => item.priority.clone(),
+        },
+    ));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::upsert_ticket;
+
+    fn ticket(key: &str, priority: &str, created_at: &str, resolved_at: Option<&str>) -> Ticket {
+        Ticket {
+            priority: priority.to_string(),
+            assignee: Some("Dana".to_string()),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            ..test_support::ticket_resolved_at(key, resolved_at)
+        }
+    }
+
+    #[test]
+    fn report_covers_throughput_load_and_notable_work() {
+        let conn = setup_db();
+        for ticket in [
+            ticket(
+                "OPS-1",
+                "Low",
+                "2025-01-06T09:00:00Z",
+                Some("2025-01-06T10:00:00Z"),
+            ),
+            ticket(
+                "OPS-2",
+                "Critical",
+                "2025-01-07T09:00:00Z",
+                Some("2025-01-07T11:00:00Z"),
+            ),
+            ticket(
+                "OPS-3",
+                "Critical",
+                "2025-01-08T09:00:00Z",
+                Some("2025-01-08T15:00:00Z"),
+            ),
+            // Resolved before the range
+            ticket(
+                "OPS-4",
+                "High",
+                "2024-12-01T09:00:00Z",
+                Some("2024-12-02T09:00:00Z"),
+            ),
+            ticket("OPS-5", "High", "2024-12-20T09:00:00Z", None),
+            ticket("OPS-6", "Low", "2025-01-10T09:00:00Z", None),
+            ticket("OPS-7", "High", "2025-01-12T09:00:00Z", None),
+            Ticket {
+                assignee: Some("Sam".to_string()),
+                ..ticket("OPS-9", "High", "2024-11-01T09:00:00Z", None)
+            },
+        ] {
+            upsert_ticket(&conn, &ticket).expect("ticket");
+        }
+
+        let from = NaiveDate::from_ymd_opt(2025, 1, 1).expect("valid date");
+        let to = NaiveDate::from_ymd_opt(2025, 1, 15).expect("valid date");
+        let now = to.and_hms_opt(9, 0, 0).expect("valid datetime");
+        let report = build_person_report(&conn, "Dana", from, to, DurationMode::Calendar, now)
+            .expect("report");
+        let keys = |items: &[ReportTicket]| -> Vec<String> {
+            items.iter().map(|item| item.jira_key.clone()).collect()
+        };
+
+        assert_eq!(report.resolved_count, 3);
+        assert_eq!(report.resolved_per_week, 1.5);
+        assert_eq!(report.median_resolution_hours, 2.0);
+        assert_eq!(report.open_tickets, 3);
+        let load: Vec<(String, u32)> = report
+            .load_by_priority
+            .iter()
+            .map(|entry| (entry.name.clone(), entry.count))
+            .collect();
+        assert_eq!(load, vec![("High".to_string(), 2), ("Low".to_string(), 1)]);
+        assert_eq!(keys(&report.aging), vec!["OPS-5", "OPS-6", "OPS-7"]);
+        assert_eq!(report.aging[0].age_days, Some(26));
+        assert_eq!(keys(&report.notable), vec!["OPS-3", "OPS-2", "OPS-1"]);
+        assert!(report.markdown.starts_with(
+            "# Dana: 2025-01-01 to 2025-01-14\n\n## Throughput\n- Resolved: 3 (1.5 per week)"
+        ));
+        assert!(report
+            .markdown
+            .ends_with("- OPS-1 Summary OPS-1 (Low, 1.0h)"));
+    }
+}
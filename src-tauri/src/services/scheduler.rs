@@ -51,8 +51,14 @@ impl SyncScheduler {
                 app_handle.emit("background-sync-started", ()).ok();
 
                 // Perform sync (call the sync logic without the lock check)
-                match perform_background_sync(&db_pool, &jira_url, &email, &category_rules_json)
-                    .await
+                match perform_background_sync(
+                    &db_pool,
+                    &jira_url,
+                    &email,
+                    &category_rules_json,
+                    &app_handle,
+                )
+                .await
                 {
                     Ok(count) => {
                         log::info!("Background sync completed: {} tickets", count);
@@ -79,61 +85,35 @@ async fn perform_background_sync(
     jira_url: &str,
     email: &str,
     category_rules_json: &str,
+    app_handle: &tauri::AppHandle,
 ) -> Result<usize, AppError> {
-    // Get token
     let token = crate::commands::settings::get_jira_token_internal().await?;
-
-    // Parse category rules
-    let rules_wrapper: crate::commands::sync::CategoryRulesWrapper =
-        serde_json::from_str(category_rules_json)
-            .map_err(|e| AppError::Config(format!("Failed to parse category rules: {}", e)))?;
-    let category_rules = rules_wrapper.category_rules;
-
-    // Create Jira client
-    let client = crate::jira::JiraClient::new(jira_url, email, &token)?;
-
-    // Get last sync timestamp
-    let db_clone = db_pool.clone();
-    let last_sync_ts = tauri::async_runtime::spawn_blocking(move || {
-        let conn = db_clone
-            .lock()
-            .map_err(|_| AppError::Internal("Lock failed".to_string()))?;
-        crate::db::get_sync_metadata(&conn, "last_sync_at")
-    })
-    .await
-    .map_err(|_| AppError::Internal("Task join failed".to_string()))??;
-
-    // Fetch tickets from Jira
-    let mut tickets = client.fetch_tickets(last_sync_ts.as_deref()).await?;
-
-    // Categorize tickets
-    for ticket in &mut tickets {
-        ticket.category = crate::services::categorize_ticket(ticket, &category_rules);
-    }
-
-    let synced_count = tickets.len();
-
-    // Store in database
-    let db_clone = db_pool.clone();
-    tauri::async_runtime::spawn_blocking(move || {
-        let conn = db_clone
-            .lock()
-            .map_err(|_| AppError::Internal("Lock failed".to_string()))?;
-
-        for ticket in &tickets {
-            crate::db::upsert_ticket(&conn, ticket)?;
+    let category_rules = crate::commands::sync::parse_category_rules(category_rules_json)?;
+    let auth = crate::jira::JiraAuth::Basic {
+        email: email.to_string(),
+        token,
+    };
+    let client = crate::commands::settings::jira_client(app_handle, jira_url, auth)?;
+
+    // Connectivity is back if the flush gets through; queued writes go first
+    let db = crate::db::DbPool(db_pool.clone());
+    match crate::services::outbox::flush_outbox(&client, &db, None, false).await {
+        Ok(summary) if summary.sent > 0 => {
+            log::info!("Flushed {} queued write-backs", summary.sent);
         }
+        Ok(_) => {}
+        Err(e) => log::warn!("Outbox flush failed: {}", e),
+    }
 
-        // Update last sync timestamp
-        let now = chrono::Utc::now().to_rfc3339();
-        crate::db::set_sync_metadata(&conn, "last_sync_at", &now)?;
-
-        Ok::<(), AppError>(())
-    })
-    .await
-    .map_err(|_| AppError::Internal("Task join failed".to_string()))??;
+    let outcome = crate::services::source_sync::sync_source(
+        &client,
+        db_pool.clone(),
+        &category_rules,
+        app_handle,
+    )
+    .await?;
 
-    Ok(synced_count)
+    Ok(outcome.synced)
 }
 
 #[cfg(test)]
@@ -0,0 +1,105 @@
+use crate::models::{SimilarTicket, Ticket};
+use crate::services::dedupe::normalize_summary;
+use std::collections::HashSet;
+
+/// Summaries less similar than this aren't worth suggesting.
+const MIN_SIMILARITY: f64 = 0.2;
+
+/// Character trigrams of each word, padded with spaces so short words and
+/// word boundaries still contribute ("vpn" -> "  v", " vp", "vpn", "pn ").
+pub fn trigrams(normalized: &str) -> HashSet<[char; 3]> {
+    let mut grams = HashSet::new();
+    for word in normalized.split(' ').filter(|word| !word.is_empty()) {
+        let chars: Vec<char> = ["  ", word, " "].concat().chars().collect();
+        grams.extend(chars.windows(3).map(|w| [w[0], w[1], w[2]]));
+    }
+    grams
+}
+
+/// Dice coefficient over two trigram sets.
+pub fn trigram_similarity(a: &HashSet<[char; 3]>, b: &HashSet<[char; 3]>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let shared = a.intersection(b).count();
+    2.0 * shared as f64 / (a.len() + b.len()) as f64
+}
+
+/// Up to `limit` tickets from `candidates` whose summaries resemble
+/// `target`'s, most similar first, newest first among equals. Unlike the
+/// duplicate detector this looks across all time and sources: the point is
+/// to find earlier incidents and how they were resolved.
+pub fn similar_tickets(
+    target: &Ticket,
+    candidates: Vec<Ticket>,
+    limit: usize,
+) -> Vec<SimilarTicket> {
+    let target_grams = trigrams(&normalize_summary(&target.summary));
+
+    let mut similar: Vec<SimilarTicket> = candidates
+        .into_iter()
+        .filter(|ticket| ticket.jira_key != target.jira_key)
+        .filter_map(|ticket| {
+            let score = trigram_similarity(
+                &target_grams,
+                &trigrams(&normalize_summary(&ticket.summary)),
+            );
+            (score >= MIN_SIMILARITY).then_some(SimilarTicket { ticket, score })
+        })
+        .collect();
+
+    similar.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| b.ticket.created_at.cmp(&a.ticket.created_at))
+    });
+    similar.truncate(limit);
+    similar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(key: &str, summary: &str, created_at: &str) -> Ticket {
+        Ticket {
+            jira_key: key.to_string(),
+            summary: summary.to_string(),
+            created_at: created_at.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ranks_prior_incidents_by_summary_similarity() {
+        let target = ticket(
+            "OPS-9",
+            "VPN disconnects every hour",
+            "2025-03-01T09:00:00Z",
+        );
+        let candidates = vec![
+            target.clone(),
+            ticket("OPS-1", "VPN keeps disconnecting", "2025-01-05T09:00:00Z"),
+            ticket("OPS-2", "Printer out of toner", "2025-01-06T09:00:00Z"),
+            ticket(
+                "OPS-3",
+                "vpn disconnects every hour!",
+                "2025-02-01T09:00:00Z",
+            ),
+            ticket(
+                "OPS-4",
+                "Re: VPN disconnects every hour",
+                "2025-02-10T09:00:00Z",
+            ),
+        ];
+
+        let similar = similar_tickets(&target, candidates, 10);
+        let keys: Vec<&str> = similar.iter().map(|s| s.ticket.jira_key.as_str()).collect();
+        // Exact matches first (newest first), then the near miss; never itself
+        assert_eq!(keys, vec!["OPS-4", "OPS-3", "OPS-1"]);
+        assert!((similar[0].score - 1.0).abs() < 1e-9);
+        assert!(similar[2].score < 1.0 && similar[2].score >= MIN_SIMILARITY);
+
+        assert_eq!(similar_tickets(&target, Vec::new(), 10).len(), 0);
+    }
+}
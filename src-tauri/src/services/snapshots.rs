@@ -0,0 +1,205 @@
+use crate::db::{
+    get_aggregations, get_report_snapshot, has_snapshot_for_period, insert_report_snapshot,
+};
+use crate::errors::AppError;
+use crate::models::{DurationMode, MetricDelta, ReportSnapshot, SnapshotDiff, TicketFilter};
+use chrono::NaiveDateTime;
+use rusqlite::Connection;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Freezes the current all-tickets dashboard into `report_snapshots`.
+pub fn take_snapshot(conn: &Connection, now: NaiveDateTime) -> Result<ReportSnapshot, AppError> {
+    let aggregations = get_aggregations(conn, &TicketFilter::default(), DurationMode::Business)?;
+    let data = serde_json::to_string(&aggregations)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize snapshot: {}", e)))?;
+
+    insert_report_snapshot(
+        conn,
+        &now.format("%G-W%V").to_string(),
+        &now.and_utc().to_rfc3339(),
+        &data,
+    )
+}
+
+/// Takes this ISO week's snapshot unless one already exists. Runs after every
+/// sync, so the first sync of each week records it.
+pub fn take_weekly_snapshot_if_due(
+    conn: &Connection,
+    now: NaiveDateTime,
+) -> Result<Option<ReportSnapshot>, AppError> {
+    if has_snapshot_for_period(conn, &now.format("%G-W%V").to_string())? {
+        return Ok(None);
+    }
+    take_snapshot(conn, now).map(Some)
+}
+
+/// The parts of a stored `AggregationResult` that are compared. Everything is
+/// optional so snapshots taken by older versions still load.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct SnapshotData {
+    summary: BTreeMap<String, serde_json::Value>,
+    tickets_by_status: Vec<SnapshotCount>,
+    tickets_by_priority: Vec<SnapshotCount>,
+    tickets_by_category: Vec<SnapshotCount>,
+}
+
+#[derive(Deserialize)]
+struct SnapshotCount {
+    name: String,
+    count: f64,
+}
+
+/// Compares snapshot `from_id` with `to_id`; `None` if either is missing.
+pub fn get_snapshot_diff(
+    conn: &Connection,
+    from_id: i64,
+    to_id: i64,
+) -> Result<Option<SnapshotDiff>, AppError> {
+    let (Some((from, from_data)), Some((to, to_data))) = (
+        get_report_snapshot(conn, from_id)?,
+        get_report_snapshot(conn, to_id)?,
+    ) else {
+        return Ok(None);
+    };
+    let from_data = parse_snapshot(&from_data)?;
+    let to_data = parse_snapshot(&to_data)?;
+
+    let numeric = |summary: &BTreeMap<String, serde_json::Value>| -> BTreeMap<String, f64> {
+        summary
+            .iter()
+            .filter_map(|(name, value)| value.as_f64().map(|v| (name.clone(), v)))
+            .collect()
+    };
+
+    Ok(Some(SnapshotDiff {
+        from,
+        to,
+        summary: diff_values(numeric(&from_data.summary), numeric(&to_data.summary)),
+        by_status: diff_counts(&from_data.tickets_by_status, &to_data.tickets_by_status),
+        by_priority: diff_counts(&from_data.tickets_by_priority, &to_data.tickets_by_priority),
+        by_category: diff_counts(&from_data.tickets_by_category, &to_data.tickets_by_category),
+    }))
+}
+
+fn parse_snapshot(data: &str) -> Result<SnapshotData, AppError> {
+    serde_json::from_str(data)
+        .map_err(|e| AppError::Internal(format!("Failed to parse snapshot: {}", e)))
+}
+
+fn diff_counts(from: &[SnapshotCount], to: &[SnapshotCount]) -> Vec<MetricDelta> {
+    let as_map = |entries: &[SnapshotCount]| -> BTreeMap<String, f64> {
+        entries.iter().map(|e| (e.name.clone(), e.count)).collect()
+    };
+    let mut deltas = diff_values(as_map(from), as_map(to));
+    sort_by_change(&mut deltas);
+    deltas
+}
+
+/// Biggest movers first, ties by name.
+pub(crate) fn sort_by_change(deltas: &mut [MetricDelta]) {
+    deltas.sort_by(|a, b| {
+        b.change
+            .abs()
+            .total_cmp(&a.change.abs())
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// One delta per name in either side; a name missing on one side counts as 0.
+pub(crate) fn diff_values(
+    from: BTreeMap<String, f64>,
+    to: BTreeMap<String, f64>,
+) -> Vec<MetricDelta> {
+    let mut names: Vec<&String> = from.keys().chain(to.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let before = from.get(name).copied().unwrap_or(0.0);
+            let after = to.get(name).copied().unwrap_or(0.0);
+            MetricDelta {
+                name: name.clone(),
+                from: before,
+                to: after,
+                change: after - before,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{list_report_snapshots, upsert_ticket};
+    use crate::models::Ticket;
+    use chrono::NaiveDate;
+
+    fn ticket(key: &str, status: &str) -> Ticket {
+        Ticket {
+            status: status.to_string(),
+            ..test_support::ticket(key)
+        }
+    }
+
+    fn at(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 1, day)
+            .and_then(|d| d.and_hms_opt(9, 0, 0))
+            .expect("valid datetime")
+    }
+
+    #[test]
+    fn weekly_snapshot_is_taken_once_per_week_and_diffs() {
+        let conn = setup_db();
+        upsert_ticket(&conn, &ticket("OPS-1", "Open")).expect("OPS-1");
+
+        let first = take_weekly_snapshot_if_due(&conn, at(6))
+            .expect("snapshot")
+            .expect("first of the week");
+        assert_eq!(first.period, "2025-W02");
+        assert!(take_weekly_snapshot_if_due(&conn, at(8))
+            .expect("snapshot")
+            .is_none());
+
+        upsert_ticket(&conn, &ticket("OPS-2", "Open")).expect("OPS-2");
+        upsert_ticket(&conn, &ticket("OPS-3", "In Progress")).expect("OPS-3");
+        let second = take_weekly_snapshot_if_due(&conn, at(13))
+            .expect("snapshot")
+            .expect("first of the next week");
+        assert_eq!(list_report_snapshots(&conn).expect("list").len(), 2);
+
+        let diff = get_snapshot_diff(&conn, first.id, second.id)
+            .expect("diff")
+            .expect("both snapshots exist");
+        let total = diff
+            .summary
+            .iter()
+            .find(|d| d.name == "total_tickets")
+            .expect("total_tickets delta");
+        assert_eq!((total.from, total.to, total.change), (1.0, 3.0, 2.0));
+        assert_eq!(
+            diff.by_status,
+            vec![
+                MetricDelta {
+                    name: "In Progress".to_string(),
+                    from: 0.0,
+                    to: 1.0,
+                    change: 1.0,
+                },
+                MetricDelta {
+                    name: "Open".to_string(),
+                    from: 1.0,
+                    to: 2.0,
+                    change: 1.0,
+                },
+            ]
+        );
+        assert!(get_snapshot_diff(&conn, first.id, 404)
+            .expect("diff")
+            .is_none());
+    }
+}
@@ -0,0 +1,315 @@
+use crate::connectors::TicketSource;
+use crate::db::{
+    archive_resolved_tickets, diff_tickets, finish_sync_run, get_sync_metadata, get_ticket_by_key,
+    mark_watchlist_changes, record_stats_history, replace_attachments, replace_ticket_links,
+    set_sync_metadata, start_sync_run, upsert_sprints, upsert_synced_ticket, DbPool, UpsertCounts,
+};
+use crate::errors::{AppError, DbError};
+use crate::models::{
+    PreviewAction, SyncItemFailure, SyncPreview, SyncRunCounts, Ticket, TicketPreview, WebhookEvent,
+};
+use crate::services::categorizer::{apply_categories, CategorizerBackend, CategoryRule};
+use crate::services::notify::webhook_out;
+use crate::services::{alerts, automations};
+use crate::services::{dedupe, snapshots};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tauri::Emitter;
+
+#[derive(Serialize, Clone)]
+pub(crate) struct SyncProgress {
+    pub(crate) phase: String,
+    pub(crate) current: usize,
+    pub(crate) total: Option<usize>,
+}
+
+pub struct SourceSyncOutcome {
+    pub source_id: &'static str,
+    pub synced: usize,
+    pub counts: SyncRunCounts,
+    /// How the fetched tickets were stored.
+    pub upserts: UpsertCounts,
+    /// Items the source returned that were skipped.
+    pub failures: Vec<SyncItemFailure>,
+}
+
+/// Runs one incremental sync of `source` into the tickets table. The new
+/// cursor is only stored once every ticket has been written, so a failed
+/// sync is retried from the same point next time. Items the source couldn't
+/// read are skipped rather than failing the sync. Every run is recorded in
+/// `sync_runs`.
+pub async fn sync_source<S: TicketSource>(
+    source: &S,
+    db_pool: Arc<Mutex<Connection>>,
+    category_rules: &[CategoryRule],
+    app_handle: &tauri::AppHandle,
+) -> Result<SourceSyncOutcome, AppError> {
+    let source_id = source.source_id();
+    let pool = DbPool(db_pool.clone());
+    let run_id = pool
+        .run(move |conn| start_sync_run(conn, source_id, &chrono::Utc::now().to_rfc3339()))
+        .await
+        .inspect_err(|e| log::warn!("Recording sync run failed: {}", e))
+        .ok();
+
+    let result = run_sync(source, db_pool.clone(), category_rules, run_id, app_handle).await;
+    let (counts, failures, error) = match &result {
+        Ok(outcome) => (outcome.counts, outcome.failures.clone(), None),
+        Err(e) => (SyncRunCounts::default(), Vec::new(), Some(e.to_string())),
+    };
+
+    let completed = serde_json::json!({
+        "source_id": source_id,
+        "counts": counts,
+        "error": error,
+    });
+    if let Some(run_id) = run_id {
+        let finished = pool
+            .run(move |conn| {
+                finish_sync_run(
+                    conn,
+                    run_id,
+                    counts,
+                    &failures,
+                    error.as_deref(),
+                    &chrono::Utc::now().to_rfc3339(),
+                )
+            })
+            .await;
+        if let Err(e) = finished {
+            log::warn!("Recording sync run failed: {}", e);
+        }
+    }
+
+    if let Err(e) = webhook_out::dispatch(
+        db_pool,
+        app_handle,
+        WebhookEvent::SyncCompleted,
+        vec![completed],
+    )
+    .await
+    {
+        log::warn!("Sync webhooks failed: {}", e);
+    }
+
+    result
+}
+
+async fn run_sync<S: TicketSource>(
+    source: &S,
+    db_pool: Arc<Mutex<Connection>>,
+    category_rules: &[CategoryRule],
+    run_id: Option<i64>,
+    app_handle: &tauri::AppHandle,
+) -> Result<SourceSyncOutcome, AppError> {
+    let source_id = source.source_id();
+    let cursor_key = source.cursor_key();
+    let last_sync_key = source.last_sync_key();
+
+    source.authenticate().await?;
+
+    let db_clone = db_pool.clone();
+    let key = cursor_key.clone();
+    let cursor = tauri::async_runtime::spawn_blocking(move || {
+        let conn = db_clone.lock().map_err(|_| DbError::LockFailed)?;
+        get_sync_metadata(&conn, &key)
+    })
+    .await
+    .map_err(|_| AppError::Internal("Task join failed".to_string()))??;
+
+    emit_progress(app_handle, "fetching", 0, None);
+
+    let batch = source.fetch_incremental(cursor.as_deref()).await?;
+    let total_count = batch.items.len();
+    let failures = batch.failures;
+
+    emit_progress(app_handle, "categorizing", 0, Some(total_count));
+
+    let categorizer = crate::commands::settings::categorizer_backend(app_handle, category_rules)?;
+    let mut tickets = Vec::with_capacity(total_count);
+    let mut links = Vec::new();
+    let mut attachments = Vec::new();
+    let mut sprints = Vec::new();
+    for (idx, item) in batch.items.into_iter().enumerate() {
+        let item_links = source.map_links(&item);
+        let item_attachments = source.map_attachments(&item);
+        sprints.extend(source.map_sprints(&item));
+        let ticket = source.map_to_ticket(item);
+        if let Some(item_links) = item_links {
+            links.push((ticket.jira_key.clone(), item_links));
+        }
+        if let Some(item_attachments) = item_attachments {
+            attachments.push((ticket.jira_key.clone(), item_attachments));
+        }
+        tickets.push(ticket);
+
+        // Emit progress every 10 tickets
+        if idx % 10 == 0 {
+            emit_progress(app_handle, "categorizing", idx, Some(total_count));
+        }
+    }
+
+    apply_categories(categorizer.as_ref(), &mut tickets).await;
+
+    emit_progress(app_handle, "saving", 0, Some(total_count));
+
+    let next_cursor = batch.next_cursor;
+    let conflict_policy = crate::commands::settings::conflict_policy(app_handle)?;
+    let retention = crate::commands::settings::load_retention_settings_internal(app_handle)?;
+    let db_alerts = db_pool.clone();
+    let db_automations = db_pool.clone();
+    let upserts = tauri::async_runtime::spawn_blocking(move || {
+        let conn = db_pool.lock().map_err(|_| DbError::LockFailed)?;
+
+        let mut upserts = UpsertCounts::default();
+        for ticket in &tickets {
+            upserts.record(upsert_synced_ticket(
+                &conn,
+                ticket,
+                conflict_policy,
+                run_id,
+            )?);
+        }
+        for (key, ticket_links) in &links {
+            replace_ticket_links(&conn, key, ticket_links)?;
+        }
+        for (key, ticket_attachments) in &attachments {
+            replace_attachments(&conn, key, ticket_attachments)?;
+        }
+        upsert_sprints(&conn, &sprints)?;
+        mark_watchlist_changes(&conn)?;
+
+        if let Some(next_cursor) = &next_cursor {
+            set_sync_metadata(&conn, &cursor_key, next_cursor)?;
+        }
+        // Timestamp-cursored sources already advanced their last sync time above
+        if cursor_key != last_sync_key {
+            let now = chrono::Utc::now().to_rfc3339();
+            set_sync_metadata(&conn, &last_sync_key, &now)?;
+        }
+
+        // Reporting side effects shouldn't fail an otherwise good sync
+        let now = chrono::Utc::now().naive_utc();
+        if let Err(e) = record_stats_history(&conn, now) {
+            log::warn!("Recording stats history failed: {}", e);
+        }
+        if let Err(e) = snapshots::take_weekly_snapshot_if_due(&conn, now) {
+            log::warn!("Weekly report snapshot failed: {}", e);
+        }
+        if let Err(e) = dedupe::detect_duplicates(&conn, dedupe::DUPLICATE_THRESHOLD, now) {
+            log::warn!("Duplicate detection failed: {}", e);
+        }
+        if let Some(cutoff) = retention.archive_cutoff(now) {
+            if let Err(e) = archive_resolved_tickets(&conn, cutoff) {
+                log::warn!("Archiving resolved tickets failed: {}", e);
+            }
+        }
+
+        Ok::<UpsertCounts, AppError>(upserts)
+    })
+    .await
+    .map_err(|_| AppError::Internal("Task join failed".to_string()))??;
+
+    if let Err(e) = alerts::run_post_sync_alerts(db_alerts, app_handle).await {
+        log::warn!("Post-sync alerts failed: {}", e);
+    }
+    if let Err(e) = automations::run_post_sync_automations(db_automations, app_handle).await {
+        log::warn!("Post-sync automations failed: {}", e);
+    }
+
+    Ok(SourceSyncOutcome {
+        source_id,
+        synced: total_count,
+        counts: SyncRunCounts {
+            fetched: (total_count + failures.len()) as u32,
+            updated: upserts.inserted + upserts.updated,
+            failed: failures.len() as u32,
+        },
+        upserts,
+        failures,
+    })
+}
+
+/// Inserts and updates returned with their field changes by `preview_source`.
+const PREVIEW_SAMPLES: usize = 20;
+
+/// Fetches and maps `source` exactly like `sync_source`, then compares the
+/// result with the stored tickets instead of writing it. Nothing is stored,
+/// including the cursor and the sync run. Unconfirmed local edits are
+/// compared as stored, so the conflict policy isn't applied.
+pub async fn preview_source<S: TicketSource>(
+    source: &S,
+    db: &DbPool,
+    categorizer: &dyn CategorizerBackend,
+) -> Result<SyncPreview, AppError> {
+    source.authenticate().await?;
+
+    let cursor_key = source.cursor_key();
+    let cursor = db
+        .run(move |conn| get_sync_metadata(conn, &cursor_key))
+        .await?;
+    let batch = source.fetch_incremental(cursor.as_deref()).await?;
+
+    let mut tickets: Vec<Ticket> = batch
+        .items
+        .into_iter()
+        .map(|item| source.map_to_ticket(item))
+        .collect();
+    apply_categories(categorizer, &mut tickets).await;
+
+    let source_id = source.source_id();
+    let failures = batch.failures;
+    db.run(move |conn| {
+        let mut preview = SyncPreview {
+            source: source_id.to_string(),
+            fetched: (tickets.len() + failures.len()) as u32,
+            inserts: 0,
+            updates: 0,
+            unchanged: 0,
+            failures,
+            samples: Vec::new(),
+        };
+
+        for ticket in tickets {
+            let stored = get_ticket_by_key(conn, &ticket.jira_key)?;
+            let changes = diff_tickets(stored.as_ref(), &ticket);
+            let action = match stored {
+                None => PreviewAction::Insert,
+                Some(_) if changes.is_empty() => {
+                    preview.unchanged += 1;
+                    continue;
+                }
+                Some(_) => PreviewAction::Update,
+            };
+            match action {
+                PreviewAction::Insert => preview.inserts += 1,
+                PreviewAction::Update => preview.updates += 1,
+            }
+            if preview.samples.len() < PREVIEW_SAMPLES {
+                preview.samples.push(TicketPreview {
+                    jira_key: ticket.jira_key,
+                    summary: ticket.summary,
+                    action,
+                    changes,
+                });
+            }
+        }
+
+        Ok(preview)
+    })
+    .await
+}
+
+fn emit_progress(app_handle: &tauri::AppHandle, phase: &str, current: usize, total: Option<usize>) {
+    app_handle
+        .emit(
+            "sync-progress",
+            SyncProgress {
+                phase: phase.to_string(),
+                current,
+                total,
+            },
+        )
+        .ok();
+}
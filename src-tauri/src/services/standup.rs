@@ -0,0 +1,217 @@
+use crate::db::{get_linked_tickets, get_tickets, STALE_TICKET_DAYS};
+use crate::errors::AppError;
+use crate::models::{
+    DateRange, Standup, StandupItem, StatusCategory, Ticket, TicketFilter, WorkSchedule,
+};
+use crate::services::time_calc::parse_timestamp;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+
+/// The last working day before `date` under the default schedule, so a
+/// Monday standup covers Friday and the weekend.
+fn previous_working_day(date: NaiveDate) -> NaiveDate {
+    let schedule = WorkSchedule::default();
+    let mut day = date - Duration::days(1);
+    for _ in 0..7 {
+        if schedule.days[day.weekday().num_days_from_monday() as usize].is_some() {
+            break;
+        }
+        day -= Duration::days(1);
+    }
+    day
+}
+
+fn item(ticket: &Ticket, note: Option<String>) -> StandupItem {
+    StandupItem {
+        jira_key: ticket.jira_key.clone(),
+        summary: ticket.summary.clone(),
+        status: ticket.status.clone(),
+        note,
+    }
+}
+
+fn is_in_progress(ticket: &Ticket) -> bool {
+    match ticket.status_category {
+        Some(category) => category == StatusCategory::InProgress,
+        None => ticket.status.eq_ignore_ascii_case("In Progress"),
+    }
+}
+
+/// Why an open ticket is stuck: an unresolved ticket blocking it, or no
+/// update in `STALE_TICKET_DAYS`.
+fn blocker_note(
+    conn: &Connection,
+    ticket: &Ticket,
+    now: NaiveDateTime,
+) -> Result<Option<String>, AppError> {
+    let blocked_by: Vec<String> = get_linked_tickets(conn, &ticket.jira_key)?
+        .into_iter()
+        .filter(|linked| !linked.resolved && linked.relation.eq_ignore_ascii_case("is blocked by"))
+        .map(|linked| linked.jira_key)
+        .collect();
+    if !blocked_by.is_empty() {
+        return Ok(Some(format!("blocked by {}", blocked_by.join(", "))));
+    }
+
+    let idle_days = parse_timestamp(&ticket.updated_at)
+        .map(|updated| (now - updated).num_days())
+        .unwrap_or(0);
+    Ok((idle_days >= i64::from(STALE_TICKET_DAYS))
+        .then(|| format!("no update for {} days", idle_days)))
+}
+
+/// Compiles `assignee`'s standup for `date`: what they resolved since the
+/// previous working day, what they have in progress, and what is blocked.
+pub fn build_standup(
+    conn: &Connection,
+    assignee: &str,
+    date: NaiveDate,
+    now: NaiveDateTime,
+) -> Result<Standup, AppError> {
+    let since = previous_working_day(date);
+    let mine = TicketFilter {
+        assignees: vec![assignee.to_string()],
+        ..Default::default()
+    };
+
+    let resolved = get_tickets(
+        conn,
+        &mine.clone().resolved_in(DateRange {
+            from: Some(since.to_string()),
+            to: Some(date.to_string()),
+        }),
+    )?
+    .iter()
+    .map(|ticket| item(ticket, None))
+    .collect();
+
+    let mut in_progress = Vec::new();
+    let mut blockers = Vec::new();
+    for ticket in get_tickets(conn, &mine)?
+        .iter()
+        .filter(|ticket| ticket.resolved_at.is_none())
+    {
+        if let Some(note) = blocker_note(conn, ticket, now)? {
+            blockers.push(item(ticket, Some(note)));
+        } else if is_in_progress(ticket) {
+            in_progress.push(item(ticket, None));
+        }
+    }
+
+    let mut standup = Standup {
+        date: date.to_string(),
+        assignee: assignee.to_string(),
+        since: since.to_string(),
+        resolved,
+        in_progress,
+        blockers,
+        markdown: String::new(),
+    };
+    standup.markdown = render_markdown(&standup, since);
+    Ok(standup)
+}
+
+/// Slack-flavoured Markdown: `*bold*` headings and `-` bullets.
+fn render_markdown(standup: &Standup, since: NaiveDate) -> String {
+    let section = |title: String, items: &[StandupItem]| {
+        let mut lines = vec![title];
+        if items.is_empty() {
+            lines.push("- Nothing".to_string());
+        }
+        for item in items {
+            lines.push(match &item.note {
+                Some(note) => format!("- {} {} ({})", item.jira_key, item.summary, note),
+                None => format!("- {} {} ({})", item.jira_key, item.summary, item.status),
+            });
+        }
+        lines.join("\n")
+    };
+
+    [
+        section(
+            format!("*Since {}*", since.format("%a %b %-d")),
+            &standup.resolved,
+        ),
+        section("*Today*".to_string(), &standup.in_progress),
+        section("*Blockers*".to_string(), &standup.blockers),
+    ]
+    .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{replace_ticket_links, upsert_ticket};
+    use crate::models::TicketLink;
+
+    fn ticket(key: &str, status: &str, updated_at: &str, resolved_at: Option<&str>) -> Ticket {
+        Ticket {
+            status: status.to_string(),
+            assignee: Some("Dana".to_string()),
+            created_at: "2025-01-01T09:00:00Z".to_string(),
+            updated_at: updated_at.to_string(),
+            resolved_at: resolved_at.map(str::to_string),
+            ..test_support::ticket(key)
+        }
+    }
+
+    #[test]
+    fn monday_standup_covers_the_weekend() {
+        let conn = setup_db();
+        let recent = "2025-01-05T09:00:00Z";
+        for ticket in [
+            // Resolved on Friday and last Thursday
+            ticket("OPS-1", "Done", recent, Some("2025-01-03T15:00:00Z")),
+            ticket("OPS-2", "Done", recent, Some("2025-01-02T15:00:00Z")),
+            ticket("OPS-3", "In Progress", recent, None),
+            ticket("OPS-4", "In Progress", recent, None),
+            ticket("OPS-5", "Open", "2024-12-20T09:00:00Z", None),
+            ticket("OPS-6", "Open", recent, None),
+            Ticket {
+                assignee: Some("Sam".to_string()),
+                ..ticket("OPS-9", "Open", recent, None)
+            },
+        ] {
+            upsert_ticket(&conn, &ticket).expect("ticket");
+        }
+        replace_ticket_links(
+            &conn,
+            "OPS-9",
+            &[TicketLink {
+                outward_key: "OPS-9".to_string(),
+                inward_key: "OPS-4".to_string(),
+                link_type: "Blocks".to_string(),
+                outward_label: "blocks".to_string(),
+                inward_label: "is blocked by".to_string(),
+            }],
+        )
+        .expect("links");
+
+        let monday = NaiveDate::from_ymd_opt(2025, 1, 6).expect("valid date");
+        let now = monday.and_hms_opt(9, 0, 0).expect("valid datetime");
+        let standup = build_standup(&conn, "Dana", monday, now).expect("standup");
+        let keys = |items: &[StandupItem]| {
+            let mut keys: Vec<String> = items.iter().map(|item| item.jira_key.clone()).collect();
+            keys.sort();
+            keys
+        };
+        let note = |key: &str| {
+            standup
+                .blockers
+                .iter()
+                .find(|item| item.jira_key == key)
+                .and_then(|item| item.note.clone())
+        };
+
+        assert_eq!(standup.since, "2025-01-03");
+        assert_eq!(keys(&standup.resolved), vec!["OPS-1"]);
+        assert_eq!(keys(&standup.in_progress), vec!["OPS-3"]);
+        assert_eq!(keys(&standup.blockers), vec!["OPS-4", "OPS-5"]);
+        assert_eq!(note("OPS-4").as_deref(), Some("blocked by OPS-9"));
+        assert_eq!(note("OPS-5").as_deref(), Some("no update for 17 days"));
+        assert!(standup
+            .markdown
+            .starts_with("*Since Fri Jan 3*\n- OPS-1 Summary OPS-1 (Done)\n\n*Today*"));
+    }
+}
@@ -0,0 +1,190 @@
+use crate::db::support_calendars::project_schedules;
+use crate::db::{get_field_changes, get_tickets};
+use crate::errors::AppError;
+use crate::models::{StatusDwellViolation, Ticket, TicketChange, TicketFilter, WorkSchedule};
+use crate::services::time_calc::{business_hours_between, parse_timestamp};
+use chrono::NaiveDateTime;
+use rusqlite::Connection;
+use std::collections::{BTreeMap, HashMap};
+
+/// Stretches `ticket` overstayed a limited status, rebuilt from its journaled
+/// status `changes` (oldest first). A change is dated by the sync that saw
+/// it, so stretches are only as precise as the sync interval. The last
+/// stretch of an open ticket runs until `now`; a resolved ticket's is skipped.
+/// Dwell is counted in the business hours of the ticket's project `schedule`.
+fn ticket_violations(
+    ticket: &Ticket,
+    changes: &[&TicketChange],
+    limits: &BTreeMap<String, f64>,
+    schedule: &WorkSchedule,
+    now: NaiveDateTime,
+) -> Vec<StatusDwellViolation> {
+    let status_of = |value: &serde_json::Value| value.as_str().unwrap_or_default().to_string();
+    let mut status = changes
+        .first()
+        .map_or_else(|| ticket.status.clone(), |c| status_of(&c.old_value));
+    let mut entered_at = ticket.created_at.clone();
+    let mut stretches: Vec<(String, String, Option<String>)> = Vec::new();
+    for change in changes {
+        let next = status_of(&change.new_value);
+        stretches.push((
+            std::mem::replace(&mut status, next),
+            std::mem::replace(&mut entered_at, change.changed_at.clone()),
+            Some(change.changed_at.clone()),
+        ));
+    }
+    if ticket.resolved_at.is_none() {
+        stretches.push((status, entered_at, None));
+    }
+
+    stretches
+        .into_iter()
+        .filter_map(|(status, entered_at, left_at)| {
+            let limit = *limits.get(&status)?;
+            let start = parse_timestamp(&entered_at)?;
+            let end = match left_at.as_deref() {
+                Some(left_at) => parse_timestamp(left_at)?,
+                None => now,
+            };
+            let dwell = business_hours_between(start, end, schedule).ok()?;
+            (dwell > limit).then(|| StatusDwellViolation {
+                jira_key: ticket.jira_key.clone(),
+                summary: ticket.summary.clone(),
+                status,
+                entered_at,
+                left_at,
+                dwell_hours: dwell,
+                limit_hours: limit,
+            })
+        })
+        .collect()
+}
+
+/// Times `filter`'s tickets stayed in a status longer than its business-hour
+/// limit in `limits`, past or ongoing, furthest over the limit first.
+pub fn status_dwell_violations(
+    conn: &Connection,
+    filter: &TicketFilter,
+    limits: &BTreeMap<String, f64>,
+    now: NaiveDateTime,
+) -> Result<Vec<StatusDwellViolation>, AppError> {
+    if limits.is_empty() {
+        return Ok(Vec::new());
+    }
+    let schedules = project_schedules(conn)?;
+    let changes = get_field_changes(conn, "status")?;
+    let mut changes_by_key: HashMap<&str, Vec<&TicketChange>> = HashMap::new();
+    for change in &changes {
+        changes_by_key
+            .entry(change.ticket_key.as_str())
+            .or_default()
+            .push(change);
+    }
+
+    let mut violations: Vec<StatusDwellViolation> = get_tickets(conn, filter)?
+        .iter()
+        .flat_map(|ticket| {
+            let changes = changes_by_key
+                .get(ticket.jira_key.as_str())
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            ticket_violations(
+                ticket,
+                changes,
+                limits,
+                schedules.get(&ticket.project_key),
+                now,
+            )
+        })
+        .collect();
+    violations.sort_by(|a, b| {
+        (b.dwell_hours - b.limit_hours)
+            .total_cmp(&(a.dwell_hours - a.limit_hours))
+            .then_with(|| a.jira_key.cmp(&b.jira_key))
+    });
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::{create_support_calendar, record_ticket_changes, upsert_ticket};
+    use crate::models::{SupportCalendarInput, WorkWindow};
+    use chrono::NaiveDate;
+
+    fn ticket(key: &str, status: &str) -> Ticket {
+        Ticket {
+            status: status.to_string(),
+            ..test_support::ticket(key)
+        }
+    }
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 1, day)
+            .and_then(|d| d.and_hms_opt(hour, 0, 0))
+            .expect("valid datetime")
+    }
+
+    #[test]
+    fn finds_past_and_ongoing_overstays() {
+        let conn = setup_db();
+
+        // OPS-1 waited three business days before moving on
+        let waiting = ticket("OPS-1", "Waiting for Support");
+        let mut moved = waiting.clone();
+        moved.status = "In Progress".to_string();
+        upsert_ticket(&conn, &moved).expect("OPS-1");
+        record_ticket_changes(&conn, &waiting, &moved, None, "2025-01-09T09:00:00Z")
+            .expect("change");
+        // OPS-2 is still waiting; OPS-3 has waited one business day
+        upsert_ticket(&conn, &ticket("OPS-2", "Waiting for Support")).expect("OPS-2");
+        let mut recent = ticket("OPS-3", "Waiting for Support");
+        recent.created_at = "2025-01-09T09:00:00Z".to_string();
+        upsert_ticket(&conn, &recent).expect("OPS-3");
+        // EU-1 waits like OPS-2, but its project's desk never closes
+        let eu = Ticket {
+            project_key: "EU".to_string(),
+            ..ticket("EU-1", "Waiting for Support")
+        };
+        upsert_ticket(&conn, &eu).expect("EU-1");
+        create_support_calendar(
+            &conn,
+            &SupportCalendarInput {
+                name: "EU desk".to_string(),
+                schedule: WorkSchedule {
+                    days: [Some(WorkWindow::hours(0, 24)); 7],
+                    utc_offset_minutes: 0,
+                },
+                projects: vec!["EU".to_string()],
+            },
+        )
+        .expect("calendar");
+
+        let limits = BTreeMap::from([("Waiting for Support".to_string(), 16.0)]);
+        let violations =
+            status_dwell_violations(&conn, &TicketFilter::default(), &limits, at(10, 9))
+                .expect("violations");
+
+        let found: Vec<(&str, Option<&str>, f64)> = violations
+            .iter()
+            .map(|v| (v.jira_key.as_str(), v.left_at.as_deref(), v.dwell_hours))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("EU-1", None, 96.0),
+                ("OPS-2", None, 32.0),
+                ("OPS-1", Some("2025-01-09T09:00:00Z"), 24.0),
+            ]
+        );
+        assert!(status_dwell_violations(
+            &conn,
+            &TicketFilter::default(),
+            &BTreeMap::new(),
+            at(10, 9)
+        )
+        .expect("violations")
+        .is_empty());
+    }
+}
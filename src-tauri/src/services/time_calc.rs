@@ -1,58 +1,76 @@
 use crate::errors::AppError;
-use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime, Weekday};
+use crate::models::WorkSchedule;
+use chrono::{Datelike, Duration, NaiveDateTime, Timelike};
 
-pub fn business_hours_between(
-    start: NaiveDateTime,
-    end: NaiveDateTime,
-    work_start_hour: u32,
-    work_end_hour: u32,
-) -> Result<f64, AppError> {
-    // Validate work hours are in valid range (0-23)
-    if work_start_hour > 23 || work_end_hour > 23 {
-        return Err(AppError::Internal(format!(
-            "Invalid work hours: start={}, end={} (must be 0-23)",
-            work_start_hour, work_end_hour
-        )));
+const MINUTES_PER_DAY: u32 = 24 * 60;
+const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+
+/// Checks that every window runs forward within its day and the offset is
+/// a real UTC offset.
+pub fn validate_schedule(schedule: &WorkSchedule) -> Result<(), AppError> {
+    for window in schedule.days.iter().flatten() {
+        if window.start_minute >= window.end_minute || window.end_minute > MINUTES_PER_DAY {
+            return Err(AppError::Config(format!(
+                "Invalid work window: start={}, end={} (minutes after midnight, 0-{})",
+                window.start_minute, window.end_minute, MINUTES_PER_DAY
+            )));
+        }
     }
 
-    if work_start_hour >= work_end_hour {
-        return Err(AppError::Internal(format!(
-            "Work start hour ({}) must be less than work end hour ({})",
-            work_start_hour, work_end_hour
+    validate_utc_offset(schedule.utc_offset_minutes)
+}
+
+/// Checks that `minutes` is a real UTC offset (at most ±14 hours).
+pub fn validate_utc_offset(minutes: i32) -> Result<(), AppError> {
+    if !(-MAX_UTC_OFFSET_MINUTES..=MAX_UTC_OFFSET_MINUTES).contains(&minutes) {
+        return Err(AppError::Config(format!(
+            "Invalid UTC offset: {} minutes",
+            minutes
         )));
     }
+    Ok(())
+}
+
+/// Hours between `start` and `end` (naive UTC) that fall inside
+/// `schedule`'s working windows. Each day is counted in whole minutes.
+pub fn business_hours_between(
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    schedule: &WorkSchedule,
+) -> Result<f64, AppError> {
+    validate_schedule(schedule)?;
 
     if end <= start {
         return Ok(0.0);
     }
 
-    let work_start = NaiveTime::from_hms_opt(work_start_hour, 0, 0).ok_or_else(|| {
-        AppError::Internal(format!("Invalid work start hour: {}", work_start_hour))
-    })?;
-    let work_end = NaiveTime::from_hms_opt(work_end_hour, 0, 0)
-        .ok_or_else(|| AppError::Internal(format!("Invalid work end hour: {}", work_end_hour)))?;
+    // Windows are in the schedule's local time
+    let offset = Duration::minutes(i64::from(schedule.utc_offset_minutes));
+    let (start, end) = (start + offset, end + offset);
+    let seconds_into_day = |at: NaiveDateTime| i64::from(at.time().num_seconds_from_midnight());
     let mut total_minutes: i64 = 0;
 
     let mut current_date = start.date();
     let end_date = end.date();
 
     while current_date <= end_date {
-        let weekday = current_date.weekday();
-        if weekday != Weekday::Sat && weekday != Weekday::Sun {
+        let weekday = current_date.weekday().num_days_from_monday() as usize;
+        if let Some(window) = schedule.days[weekday] {
+            let work_start = i64::from(window.start_minute) * 60;
+            let work_end = i64::from(window.end_minute) * 60;
             let day_start = if current_date == start.date() {
-                start.time().max(work_start)
+                seconds_into_day(start).max(work_start)
             } else {
                 work_start
             };
             let day_end = if current_date == end_date {
-                end.time().min(work_end)
+                seconds_into_day(end).min(work_end)
             } else {
                 work_end
             };
 
             if day_end > day_start {
-                let diff = day_end - day_start;
-                total_minutes += diff.num_minutes();
+                total_minutes += (day_end - day_start) / 60;
             }
         }
         current_date += Duration::days(1);
@@ -61,11 +79,34 @@ pub fn business_hours_between(
     Ok(total_minutes as f64 / 60.0)
 }
 
+/// Parses a stored ticket timestamp to naive UTC. Accepts RFC 3339 and the
+/// `+0000`-style offsets Jira returns.
+pub fn parse_timestamp(value: &str) -> Option<NaiveDateTime> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .or_else(|_| chrono::DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+        .map(|dt| dt.naive_utc())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::WorkWindow;
     use chrono::NaiveDate;
 
+    #[test]
+    fn parse_timestamp_accepts_jira_offsets() {
+        let expected = NaiveDate::from_ymd_opt(2025, 1, 6)
+            .and_then(|d| d.and_hms_opt(8, 0, 0))
+            .expect("valid datetime");
+        assert_eq!(parse_timestamp("2025-01-06T08:00:00Z"), Some(expected));
+        assert_eq!(
+            parse_timestamp("2025-01-06T09:00:00.000+0100"),
+            Some(expected)
+        );
+        assert_eq!(parse_timestamp("yesterday"), None);
+    }
+
     #[test]
     fn test_business_hours_same_day() {
         let start = NaiveDate::from_ymd_opt(2025, 1, 6)
@@ -76,7 +117,7 @@ mod tests {
             .unwrap()
             .and_hms_opt(15, 0, 0)
             .unwrap();
-        let hours = business_hours_between(start, end, 9, 17).unwrap();
+        let hours = business_hours_between(start, end, &WorkSchedule::default()).unwrap();
         assert_eq!(hours, 5.0);
     }
 
@@ -91,7 +132,7 @@ mod tests {
             .unwrap()
             .and_hms_opt(10, 30, 0)
             .unwrap();
-        let hours = business_hours_between(start, end, 9, 17).unwrap();
+        let hours = business_hours_between(start, end, &WorkSchedule::default()).unwrap();
         // Monday: 16:00->17:00 = 1h
         // Tuesday: 09:00->17:00 = 8h
         // Wednesday: 09:00->10:30 = 1.5h
@@ -110,7 +151,7 @@ mod tests {
             .unwrap()
             .and_hms_opt(10, 0, 0)
             .unwrap();
-        let hours = business_hours_between(start, end, 9, 17).unwrap();
+        let hours = business_hours_between(start, end, &WorkSchedule::default()).unwrap();
         // Friday: 16:00->17:00 = 1h
         // Sat/Sun: 0h (excluded)
         // Monday: 09:00->10:00 = 1h
@@ -128,7 +169,7 @@ mod tests {
             .unwrap()
             .and_hms_opt(10, 0, 0)
             .unwrap();
-        let hours = business_hours_between(start, end, 9, 17).unwrap();
+        let hours = business_hours_between(start, end, &WorkSchedule::default()).unwrap();
         assert_eq!(hours, 0.0);
     }
 
@@ -143,10 +184,127 @@ mod tests {
             .and_hms_opt(15, 0, 0)
             .unwrap();
 
-        // Test invalid hour > 23
-        assert!(business_hours_between(start, end, 25, 17).is_err());
+        let mut schedule = WorkSchedule::default();
+        schedule.days[0] = Some(WorkWindow {
+            start_minute: 9 * 60,
+            end_minute: 25 * 60,
+        });
+        assert!(business_hours_between(start, end, &schedule).is_err());
+
+        schedule.days[0] = Some(WorkWindow::hours(17, 9));
+        assert!(business_hours_between(start, end, &schedule).is_err());
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 1, day)
+            .and_then(|d| d.and_hms_opt(hour, minute, 0))
+            .expect("valid datetime")
+    }
+
+    #[test]
+    fn schedules_cover_weekend_half_days_and_round_the_clock_weekdays() {
+        // Saturday 9:00–13:30 on top of the usual weekdays
+        let mut half_saturday = WorkSchedule::default();
+        half_saturday.days[5] = Some(WorkWindow {
+            start_minute: 9 * 60,
+            end_minute: 13 * 60 + 30,
+        });
+        // Friday 16:00 to Monday 10:00: 1h + 4.5h + 1h
+        let hours =
+            business_hours_between(at(10, 16, 0), at(13, 10, 0), &half_saturday).expect("hours");
+        assert_eq!(hours, 6.5);
+
+        // 24/5: Friday 22:00 to Monday 02:00 counts up to midnight only
+        let always = Some(WorkWindow::hours(0, 24));
+        let round_the_clock = WorkSchedule {
+            days: [always, always, always, always, always, None, None],
+            utc_offset_minutes: 0,
+        };
+        let hours =
+            business_hours_between(at(10, 22, 0), at(13, 2, 0), &round_the_clock).expect("hours");
+        assert_eq!(hours, 4.0);
+
+        // Windows in CET: 08:00–16:00 UTC on weekdays
+        let cet = WorkSchedule {
+            utc_offset_minutes: 60,
+            ..WorkSchedule::default()
+        };
+        let hours = business_hours_between(at(6, 7, 0), at(6, 16, 30), &cet).expect("hours");
+        assert_eq!(hours, 8.0);
+    }
+
+    /// Deterministic xorshift, so property failures reproduce.
+    struct Cases(u64);
+
+    impl Cases {
+        fn below(&mut self, bound: u32) -> u32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % u64::from(bound)) as u32
+        }
+
+        fn schedule(&mut self) -> WorkSchedule {
+            let mut schedule = WorkSchedule {
+                days: [None; 7],
+                utc_offset_minutes: self.below(25) as i32 * 60 - 12 * 60,
+            };
+            for day in schedule.days.iter_mut() {
+                if self.below(4) > 0 {
+                    let start_minute = self.below(MINUTES_PER_DAY);
+                    let end_minute = start_minute + 1 + self.below(MINUTES_PER_DAY - start_minute);
+                    *day = Some(WorkWindow {
+                        start_minute,
+                        end_minute,
+                    });
+                }
+            }
+            schedule
+        }
+
+        fn instant(&mut self) -> NaiveDateTime {
+            at(1, 0, 0) + Duration::minutes(i64::from(self.below(21 * MINUTES_PER_DAY)))
+        }
+    }
 
-        // Test start >= end
-        assert!(business_hours_between(start, end, 17, 9).is_err());
+    #[test]
+    fn business_hours_properties_hold_for_generated_schedules() {
+        let mut cases = Cases(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..500 {
+            let schedule = cases.schedule();
+            let mut instants = [cases.instant(), cases.instant(), cases.instant()];
+            instants.sort();
+            let [a, b, c] = instants;
+            let hours = |from, to| business_hours_between(from, to, &schedule).expect("hours");
+
+            // Never negative and never more than the elapsed time
+            let total = hours(a, c);
+            assert!(total >= 0.0);
+            assert!(total <= (c - a).num_minutes() as f64 / 60.0);
+            // Splitting an interval doesn't change its total
+            assert!((hours(a, b) + hours(b, c) - total).abs() < 1e-9);
+            // A whole week counts every window once
+            let week: u32 = schedule
+                .days
+                .iter()
+                .flatten()
+                .map(|window| window.end_minute - window.start_minute)
+                .sum();
+            assert_eq!(hours(a, a + Duration::days(7)), f64::from(week) / 60.0);
+        }
+
+        // Round-the-clock coverage is plain elapsed time
+        let always = WorkSchedule {
+            days: [Some(WorkWindow::hours(0, 24)); 7],
+            utc_offset_minutes: 0,
+        };
+        for _ in 0..100 {
+            let (from, to) = (cases.instant(), cases.instant());
+            let elapsed = (to - from).num_minutes().max(0) as f64 / 60.0;
+            assert_eq!(
+                business_hours_between(from, to, &always).expect("hours"),
+                elapsed
+            );
+        }
     }
 }
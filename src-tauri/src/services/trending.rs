@@ -0,0 +1,155 @@
+use crate::models::{Ticket, TrendingTerm};
+use crate::services::dedupe::normalize_summary;
+use crate::services::time_calc::parse_timestamp;
+use chrono::{Duration, NaiveDateTime};
+use std::collections::{BTreeSet, HashMap};
+
+/// A term must appear in at least this many recent tickets to trend.
+const MIN_RECENT_COUNT: u32 = 2;
+
+/// Words too generic in a ticket summary to say anything about a topic.
+const GENERIC_WORDS: [&str; 16] = [
+    "not", "with", "from", "when", "after", "can", "cannot", "unable", "please", "request",
+    "issue", "error", "working", "does", "new", "need",
+];
+
+/// Distinct terms in a summary: normalized words of three or more letters
+/// that aren't bare numbers or generic filler.
+pub fn summary_terms(summary: &str) -> BTreeSet<String> {
+    normalize_summary(summary)
+        .split(' ')
+        .filter(|word| word.chars().count() >= 3)
+        .filter(|word| !word.chars().all(|c| c.is_ascii_digit()))
+        .filter(|word| !GENERIC_WORDS.contains(word))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Terms from the summaries of tickets created in the `days` before `now`,
+/// ranked by TF-IDF: a term's recent ticket count times the log inverse of
+/// how many earlier tickets mentioned it. Words that were always common
+/// score low; a new product name or a sudden run of "timeout"s scores high.
+pub fn trending_terms(
+    tickets: &[Ticket],
+    now: NaiveDateTime,
+    days: u32,
+    limit: usize,
+) -> Vec<TrendingTerm> {
+    let since = now - Duration::days(i64::from(days));
+    // term -> (recent tickets, earlier tickets)
+    let mut counts: HashMap<String, (u32, u32)> = HashMap::new();
+    let mut previous_total = 0u32;
+
+    for ticket in tickets {
+        let Some(created) = parse_timestamp(&ticket.created_at) else {
+            continue;
+        };
+        if created > now {
+            continue;
+        }
+        let recent = created >= since;
+        if !recent {
+            previous_total += 1;
+        }
+        for term in summary_terms(&ticket.summary) {
+            let entry = counts.entry(term).or_default();
+            if recent {
+                entry.0 += 1;
+            } else {
+                entry.1 += 1;
+            }
+        }
+    }
+
+    let mut terms: Vec<TrendingTerm> = counts
+        .into_iter()
+        .filter(|(_, (recent, _))| *recent >= MIN_RECENT_COUNT)
+        .map(|(term, (recent_count, previous_count))| {
+            let idf =
+                ((f64::from(previous_total) + 1.0) / (f64::from(previous_count) + 1.0)).ln() + 1.0;
+            TrendingTerm {
+                term,
+                recent_count,
+                previous_count,
+                score: f64::from(recent_count) * idf,
+            }
+        })
+        .collect();
+
+    terms.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.term.cmp(&b.term))
+    });
+    terms.truncate(limit);
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn ticket(key: &str, summary: &str, created_at: &str) -> Ticket {
+        Ticket {
+            jira_key: key.to_string(),
+            summary: summary.to_string(),
+            created_at: created_at.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ranks_terms_new_to_the_range_above_perennial_ones() {
+        let now = NaiveDate::from_ymd_opt(2025, 3, 31)
+            .and_then(|d| d.and_hms_opt(12, 0, 0))
+            .expect("valid timestamp");
+        let tickets = vec![
+            // Before the range: printer tickets are routine
+            ticket("OPS-1", "Printer jammed", "2025-01-10T09:00:00Z"),
+            ticket("OPS-2", "Printer offline", "2025-01-20T09:00:00Z"),
+            ticket("OPS-3", "Printer out of toner", "2025-02-03T09:00:00Z"),
+            ticket("OPS-4", "VPN slow from home", "2025-02-14T09:00:00Z"),
+            // In the range
+            ticket("OPS-5", "Login timeout on portal", "2025-03-25T09:00:00Z"),
+            ticket(
+                "OPS-6",
+                "Portal login timeout again",
+                "2025-03-27T09:00:00Z",
+            ),
+            ticket("OPS-7", "Timeout after login", "2025-03-30T09:00:00Z"),
+            ticket("OPS-8", "Printer offline", "2025-03-28T09:00:00Z"),
+            ticket("OPS-9", "Printer jammed", "2025-03-29T09:00:00Z"),
+            // Created after `now` (clock skew); ignored
+            ticket("OPS-10", "Printer exploded", "2025-04-02T09:00:00Z"),
+        ];
+
+        let terms = trending_terms(&tickets, now, 14, 10);
+        let ranked: Vec<(&str, u32, u32)> = terms
+            .iter()
+            .map(|t| (t.term.as_str(), t.recent_count, t.previous_count))
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![
+                ("login", 3, 0),
+                ("timeout", 3, 0),
+                ("portal", 2, 0),
+                ("printer", 2, 3),
+            ]
+        );
+        // 3 × (ln(5 / 1) + 1)
+        assert!((terms[0].score - 3.0 * (5.0f64.ln() + 1.0)).abs() < 1e-9);
+
+        assert_eq!(trending_terms(&tickets, now, 14, 1).len(), 1);
+    }
+
+    #[test]
+    fn summary_terms_skip_numbers_and_filler() {
+        let terms = summary_terms("Re: Error 503 on the SSO login page, please fix");
+        assert_eq!(
+            terms.into_iter().collect::<Vec<_>>(),
+            vec!["fix", "login", "page", "sso"]
+        );
+    }
+}
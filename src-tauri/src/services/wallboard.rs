@@ -0,0 +1,270 @@
+use crate::db::support_calendars::project_schedules;
+use crate::db::{get_saved_view, get_tickets, DbPool};
+use crate::errors::AppError;
+use crate::models::{
+    AlertSettings, PriorityLevel, SlaCountdown, TicketFilter, WallboardKpis, WallboardPayload,
+    WallboardTicket,
+};
+use crate::services::alerts::{load_alert_settings, sla_progress};
+use crate::services::time_calc::parse_timestamp;
+use chrono::NaiveDateTime;
+use rusqlite::Connection;
+use tauri::Emitter;
+use tokio::time::{interval, Duration};
+
+/// Rows per wallboard list; a TV can't show more legibly.
+const WALLBOARD_LIST_LIMIT: usize = 10;
+
+/// Builds the wallboard for `filter`'s tickets. "Today" is the calendar day
+/// at `utc_offset_minutes` from UTC, i.e. the viewer's local day.
+pub fn build_wallboard(
+    conn: &Connection,
+    filter: &TicketFilter,
+    view_name: Option<String>,
+    settings: &AlertSettings,
+    now: NaiveDateTime,
+    utc_offset_minutes: i32,
+) -> Result<WallboardPayload, AppError> {
+    let schedules = project_schedules(conn)?;
+    let offset = chrono::Duration::minutes(i64::from(utc_offset_minutes));
+    let today = (now + offset).date();
+    let is_today = |at: &str| parse_timestamp(at).is_some_and(|at| (at + offset).date() == today);
+
+    let mut kpis = WallboardKpis::default();
+    let mut sla_countdowns = Vec::new();
+    let mut criticals = Vec::new();
+    for ticket in get_tickets(conn, filter)? {
+        if is_today(&ticket.created_at) {
+            kpis.created_today += 1;
+        }
+        if let Some(resolved_at) = ticket.resolved_at.as_deref() {
+            if is_today(resolved_at) {
+                kpis.resolved_today += 1;
+            }
+            continue;
+        }
+
+        kpis.open_tickets += 1;
+        if let Some((elapsed, target)) = sla_progress(&ticket, settings, &schedules, now) {
+            if elapsed >= target {
+                kpis.sla_breached += 1;
+            }
+            sla_countdowns.push(SlaCountdown {
+                jira_key: ticket.jira_key.clone(),
+                summary: ticket.summary.clone(),
+                priority: ticket.priority.clone(),
+                assignee: ticket.assignee.clone(),
+                target_hours: target,
+                remaining_hours: target - elapsed,
+            });
+        }
+        if ticket.priority_level == Some(PriorityLevel::Critical) {
+            kpis.open_criticals += 1;
+            criticals.push(WallboardTicket {
+                jira_key: ticket.jira_key,
+                summary: ticket.summary,
+                status: ticket.status,
+                assignee: ticket.assignee,
+                created_at: ticket.created_at,
+            });
+        }
+    }
+
+    sla_countdowns.sort_by(|a, b| {
+        a.remaining_hours
+            .total_cmp(&b.remaining_hours)
+            .then_with(|| a.jira_key.cmp(&b.jira_key))
+    });
+    sla_countdowns.truncate(WALLBOARD_LIST_LIMIT);
+    criticals.sort_by_key(|t| std::cmp::Reverse(parse_timestamp(&t.created_at)));
+    criticals.truncate(WALLBOARD_LIST_LIMIT);
+
+    Ok(WallboardPayload {
+        view_name,
+        generated_at: now.and_utc().to_rfc3339(),
+        kpis,
+        sla_countdowns,
+        newest_criticals: criticals,
+    })
+}
+
+/// The wallboard scoped to saved view `view_id`, or to all tickets.
+pub fn load_wallboard(
+    conn: &Connection,
+    view_id: Option<i64>,
+    settings: &AlertSettings,
+    now: NaiveDateTime,
+    utc_offset_minutes: i32,
+) -> Result<WallboardPayload, AppError> {
+    let (filter, view_name) = match view_id {
+        Some(id) => {
+            let view = get_saved_view(conn, id)?
+                .ok_or_else(|| AppError::Config(format!("Unknown saved view: {}", id)))?;
+            (view.filter, Some(view.name))
+        }
+        None => (TicketFilter::default(), None),
+    };
+    build_wallboard(conn, &filter, view_name, settings, now, utc_offset_minutes)
+}
+
+/// Background task emitting `wallboard-updated` with a fresh payload every
+/// interval; stops when dropped.
+pub struct WallboardTicker {
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+impl WallboardTicker {
+    pub fn start(
+        db: DbPool,
+        view_id: Option<i64>,
+        utc_offset_minutes: i32,
+        interval_seconds: u64,
+        app_handle: tauri::AppHandle,
+    ) -> Self {
+        let task = tauri::async_runtime::spawn(async move {
+            let mut ticker = interval(Duration::from_secs(interval_seconds));
+            loop {
+                ticker.tick().await;
+                // Settings are re-read so SLA target edits show up on the next tick
+                let now = chrono::Utc::now().naive_utc();
+                let payload = match load_alert_settings(&app_handle) {
+                    Ok(settings) => {
+                        db.run(move |conn| {
+                            load_wallboard(conn, view_id, &settings, now, utc_offset_minutes)
+                        })
+                        .await
+                    }
+                    Err(e) => Err(e),
+                };
+                match payload {
+                    Ok(payload) => {
+                        app_handle.emit("wallboard-updated", payload).ok();
+                    }
+                    Err(e) => {
+                        log::error!("Wallboard refresh failed: {}", e);
+                        app_handle.emit("wallboard-error", e.to_string()).ok();
+                    }
+                }
+            }
+        });
+        log::info!("Wallboard refreshing every {}s", interval_seconds);
+        WallboardTicker { task }
+    }
+}
+
+impl Drop for WallboardTicker {
+    fn drop(&mut self) {
+        self.task.abort();
+        log::info!("Wallboard ticker stopped");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{self, setup_db};
+    use crate::db::upsert_ticket;
+    use crate::models::Ticket;
+    use chrono::NaiveDate;
+    use std::collections::BTreeMap;
+
+    fn ticket(
+        key: &str,
+        level: PriorityLevel,
+        created_at: &str,
+        resolved_at: Option<&str>,
+    ) -> Ticket {
+        Ticket {
+            priority: level.as_str().to_string(),
+            priority_level: Some(level),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            ..test_support::ticket_resolved_at(key, resolved_at)
+        }
+    }
+
+    #[test]
+    fn wallboard_counts_today_and_orders_countdowns_and_criticals() {
+        let conn = setup_db();
+        // Wednesday; the default calendar is 9-17 on weekdays
+        for t in [
+            ticket(
+                "OPS-1",
+                PriorityLevel::Critical,
+                "2025-01-06T09:00:00Z",
+                None,
+            ),
+            ticket(
+                "OPS-2",
+                PriorityLevel::Critical,
+                "2025-01-08T10:00:00Z",
+                None,
+            ),
+            ticket("OPS-3", PriorityLevel::High, "2025-01-08T09:00:00Z", None),
+            ticket(
+                "OPS-4",
+                PriorityLevel::Critical,
+                "2025-01-07T09:00:00Z",
+                Some("2025-01-08T11:00:00Z"),
+            ),
+        ] {
+            upsert_ticket(&conn, &t).expect("ticket");
+        }
+        let settings = AlertSettings {
+            sla_targets_hours: BTreeMap::from([
+                ("Critical".to_string(), 4.0),
+                ("High".to_string(), 16.0),
+            ]),
+            ..Default::default()
+        };
+        let now = NaiveDate::from_ymd_opt(2025, 1, 8)
+            .and_then(|d| d.and_hms_opt(12, 0, 0))
+            .expect("valid datetime");
+
+        let board = build_wallboard(&conn, &TicketFilter::default(), None, &settings, now, 0)
+            .expect("wallboard");
+        assert_eq!(
+            board.kpis,
+            WallboardKpis {
+                open_tickets: 3,
+                open_criticals: 2,
+                created_today: 2,
+                resolved_today: 1,
+                sla_breached: 1,
+            }
+        );
+        let countdowns: Vec<(&str, f64)> = board
+            .sla_countdowns
+            .iter()
+            .map(|c| (c.jira_key.as_str(), c.remaining_hours))
+            .collect();
+        assert_eq!(
+            countdowns,
+            vec![("OPS-1", -15.0), ("OPS-2", 2.0), ("OPS-3", 13.0)]
+        );
+        let criticals: Vec<&str> = board
+            .newest_criticals
+            .iter()
+            .map(|t| t.jira_key.as_str())
+            .collect();
+        assert_eq!(criticals, vec!["OPS-2", "OPS-1"]);
+
+        // 12:00 UTC is 01:00 on Jan 9 in Auckland: OPS-4 was resolved just after
+        // local midnight, while OPS-2 and OPS-3 were created the day before
+        let auckland = build_wallboard(
+            &conn,
+            &TicketFilter::default(),
+            None,
+            &settings,
+            now,
+            13 * 60,
+        )
+        .expect("wallboard");
+        assert_eq!(
+            (auckland.kpis.created_today, auckland.kpis.resolved_today),
+            (0, 1)
+        );
+
+        assert!(load_wallboard(&conn, Some(42), &settings, now, 0).is_err());
+    }
+}
@@ -0,0 +1,444 @@
+use crate::db::{
+    account_ticket_key, delete_ticket, list_jira_accounts, replace_attachments,
+    replace_ticket_links, upsert_synced_ticket,
+};
+use crate::errors::{AppError, DbError};
+use crate::jira::types::JiraIssue;
+use crate::jira::{JiraClient, JiraFieldMapping};
+use crate::models::ConflictPolicy;
+use crate::services::categorizer::{rule_match, CategoryRule};
+use crate::services::http_api::{percent_decode, tokens_match};
+use rusqlite::Connection;
+use serde::Deserialize;
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use tauri::Emitter;
+
+pub const WEBHOOK_PATH: &str = "/webhook/jira";
+
+/// Jira issue payloads can carry large descriptions and changelogs, but
+/// anything past this is not a webhook we want to buffer.
+const MAX_BODY_BYTES: u64 = 5 * 1024 * 1024;
+
+#[derive(Deserialize)]
+struct WebhookPayload {
+    #[serde(rename = "webhookEvent")]
+    webhook_event: String,
+    issue: Option<serde_json::Value>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum WebhookOutcome {
+    Upserted(String),
+    Deleted(String),
+    Ignored,
+}
+
+/// The added Jira account whose site sent `issue`, matched on the issue's
+/// `self` URL. `None` means the primary site, whose tickets keep bare keys.
+fn issue_account(conn: &Connection, issue: &serde_json::Value) -> Result<Option<i64>, AppError> {
+    let Some(self_url) = issue.get("self").and_then(|s| s.as_str()) else {
+        return Ok(None);
+    };
+    let account = list_jira_accounts(conn)?.into_iter().find(|account| {
+        let base = account.base_url.trim_end_matches('/');
+        self_url
+            .get(..base.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(base))
+            && self_url[base.len()..].starts_with('/')
+    });
+    Ok(account.map(|account| account.id))
+}
+
+/// Applies one Jira webhook body to the tickets table. Issues from an added
+/// account are stored under that account's keys, as its sync stores them.
+pub fn apply_event(
+    conn: &Connection,
+    body: &[u8],
+    category_rules: &[CategoryRule],
+    fields: &JiraFieldMapping,
+    conflict_policy: ConflictPolicy,
+) -> Result<WebhookOutcome, AppError> {
+    let payload: WebhookPayload = serde_json::from_slice(body)
+        .map_err(|e| AppError::Config(format!("Invalid webhook payload: {}", e)))?;
+
+    let Some(issue) = payload.issue else {
+        return Ok(WebhookOutcome::Ignored);
+    };
+    let account_id = issue_account(conn, &issue)?;
+    let keyed = |key: &str| match account_id {
+        Some(account_id) => account_ticket_key(account_id, key),
+        None => key.to_string(),
+    };
+
+    match payload.webhook_event.as_str() {
+        "jira:issue_created" | "jira:issue_updated" => {
+            let has_links = issue.pointer("/fields/issuelinks").is_some();
+            let has_attachments = issue.pointer("/fields/attachment").is_some();
+            let issue: JiraIssue = serde_json::from_value(issue)
+                .map_err(|e| AppError::Config(format!("Invalid webhook issue: {}", e)))?;
+            let mut links = JiraClient::convert_issue_links(&issue);
+            let mut attachments = JiraClient::convert_attachments(&issue);
+            let mut ticket = JiraClient::convert_issue_to_ticket(issue, fields);
+            ticket.jira_key = keyed(&ticket.jira_key);
+            ticket.account_id = account_id;
+            for link in &mut links {
+                link.outward_key = keyed(&link.outward_key);
+                link.inward_key = keyed(&link.inward_key);
+            }
+            for attachment in &mut attachments {
+                attachment.ticket_key = keyed(&attachment.ticket_key);
+            }
+            // Events are applied one at a time under the lock, so only the rules run here
+            let category = rule_match(&ticket, category_rules);
+            ticket.category_confidence = category.as_ref().map(|c| c.confidence);
+            ticket.category = category.map(|c| c.category);
+            upsert_synced_ticket(conn, &ticket, conflict_policy, None)?;
+            // Payloads trimmed of issuelinks or attachments mustn't wipe what's stored
+            if has_links {
+                replace_ticket_links(conn, &ticket.jira_key, &links)?;
+            }
+            if has_attachments {
+                replace_attachments(conn, &ticket.jira_key, &attachments)?;
+            }
+            Ok(WebhookOutcome::Upserted(ticket.jira_key))
+        }
+        "jira:issue_deleted" => {
+            let key = issue
+                .get("key")
+                .and_then(|k| k.as_str())
+                .map(keyed)
+                .ok_or_else(|| AppError::Config("Webhook issue has no key".to_string()))?;
+            delete_ticket(conn, &key)?;
+            replace_ticket_links(conn, &key, &[])?;
+            Ok(WebhookOutcome::Deleted(key))
+        }
+        _ => Ok(WebhookOutcome::Ignored),
+    }
+}
+
+/// Checks the `secret` query parameter on the request URL. Configure the
+/// Jira webhook URL as `http://127.0.0.1:<port>/webhook/jira?secret=<secret>`.
+/// The secret is always required, so a web page can't post events to the
+/// listener from the browser.
+fn authorize(url: &str, secret: &str) -> Result<(), u16> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    if path != WEBHOOK_PATH {
+        return Err(404);
+    }
+
+    let provided = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| percent_decode(name) == "secret")
+        .map(|(_, value)| percent_decode(value));
+
+    if provided.is_some_and(|provided| tokens_match(&provided, secret)) {
+        Ok(())
+    } else {
+        Err(401)
+    }
+}
+
+/// Local HTTP listener receiving Jira issue webhooks. Only binds to
+/// 127.0.0.1; reaching it from Jira Cloud needs a relay or tunnel.
+pub struct WebhookListener {
+    server: Arc<tiny_http::Server>,
+    port: u16,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WebhookListener {
+    pub fn start(
+        port: u16,
+        secret: String,
+        db_pool: Arc<Mutex<Connection>>,
+        category_rules: Vec<CategoryRule>,
+        fields: JiraFieldMapping,
+        app_handle: tauri::AppHandle,
+    ) -> Result<Self, AppError> {
+        let server = tiny_http::Server::http(("127.0.0.1", port)).map_err(|e| {
+            AppError::Config(format!(
+                "Failed to start webhook listener on {}: {}",
+                port, e
+            ))
+        })?;
+        let port = server
+            .server_addr()
+            .to_ip()
+            .map(|addr| addr.port())
+            .unwrap_or(port);
+        let server = Arc::new(server);
+
+        let worker = server.clone();
+        let thread = std::thread::spawn(move || {
+            for request in worker.incoming_requests() {
+                handle_request(
+                    request,
+                    &secret,
+                    &db_pool,
+                    &category_rules,
+                    &fields,
+                    &app_handle,
+                );
+            }
+        });
+
+        log::info!("Webhook listener started on 127.0.0.1:{}", port);
+        Ok(WebhookListener {
+            server,
+            port,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl Drop for WebhookListener {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+        log::info!("Webhook listener on port {} stopped", self.port);
+    }
+}
+
+fn handle_request(
+    mut request: tiny_http::Request,
+    secret: &str,
+    db_pool: &Mutex<Connection>,
+    category_rules: &[CategoryRule],
+    fields: &JiraFieldMapping,
+    app_handle: &tauri::AppHandle,
+) {
+    let status = if *request.method() != tiny_http::Method::Post {
+        405
+    } else if let Err(status) = authorize(request.url(), secret) {
+        status
+    } else {
+        let mut body = Vec::new();
+        match request
+            .as_reader()
+            .take(MAX_BODY_BYTES)
+            .read_to_end(&mut body)
+        {
+            Ok(_) => match apply_locked(db_pool, &body, category_rules, fields, app_handle) {
+                Ok(outcome) => {
+                    if let WebhookOutcome::Upserted(key) | WebhookOutcome::Deleted(key) = &outcome {
+                        app_handle.emit("ticket-updated", key).ok();
+                    }
+                    204
+                }
+                Err(AppError::Config(message)) => {
+                    log::warn!("Rejected webhook: {}", message);
+                    400
+                }
+                Err(e) => {
+                    log::error!("Failed to apply webhook: {}", e);
+                    500
+                }
+            },
+            Err(e) => {
+                log::warn!("Failed to read webhook body: {}", e);
+                400
+            }
+        }
+    };
+
+    request.respond(tiny_http::Response::empty(status)).ok();
+}
+
+fn apply_locked(
+    db_pool: &Mutex<Connection>,
+    body: &[u8],
+    category_rules: &[CategoryRule],
+    fields: &JiraFieldMapping,
+    app_handle: &tauri::AppHandle,
+) -> Result<WebhookOutcome, AppError> {
+    // Read per event so a policy change applies without restarting the listener
+    let conflict_policy = crate::commands::settings::conflict_policy(app_handle)?;
+    let conn = db_pool.lock().map_err(|_| DbError::LockFailed)?;
+    apply_event(&conn, body, category_rules, fields, conflict_policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::setup_db;
+    use crate::db::{get_tickets, insert_jira_account};
+    use crate::jira::JiraAuthMethod;
+    use crate::models::TicketFilter;
+
+    fn issue_event(event: &str, status: &str) -> Vec<u8> {
+        site_issue_event("https://acme.atlassian.net", event, status)
+    }
+
+    fn site_issue_event(site: &str, event: &str, status: &str) -> Vec<u8> {
+        serde_json::json!({
+            "webhookEvent": event,
+            "issue": {
+                "self": format!("{}/rest/api/2/issue/10007", site),
+                "key": "OPS-7",
+                "fields": {
+                    "summary": "VPN drops",
+                    "status": { "name": status },
+                    "priority": { "name": "High" },
+                    "issuetype": { "name": "Bug" },
+                    "assignee": { "displayName": "Dana" },
+                    "reporter": null,
+                    "created": "2025-01-06T09:00:00.000+0000",
+                    "updated": "2025-01-06T10:00:00.000+0000",
+                    "resolutiondate": null,
+                    "labels": ["network"],
+                    "project": { "key": "OPS" }
+                }
+            }
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    #[test]
+    fn update_event_upserts_ticket() {
+        let conn = setup_db();
+
+        let created = apply_event(
+            &conn,
+            &issue_event("jira:issue_created", "Open"),
+            &[],
+            &JiraFieldMapping::default(),
+            ConflictPolicy::default(),
+        )
+        .expect("created");
+        assert_eq!(created, WebhookOutcome::Upserted("OPS-7".to_string()));
+
+        apply_event(
+            &conn,
+            &issue_event("jira:issue_updated", "In Progress"),
+            &[],
+            &JiraFieldMapping::default(),
+            ConflictPolicy::default(),
+        )
+        .expect("updated");
+
+        let tickets = get_tickets(&conn, &TicketFilter::default()).expect("tickets");
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets[0].status, "In Progress");
+        assert_eq!(tickets[0].source, "jira");
+    }
+
+    #[test]
+    fn delete_event_removes_ticket() {
+        let conn = setup_db();
+        apply_event(
+            &conn,
+            &issue_event("jira:issue_created", "Open"),
+            &[],
+            &JiraFieldMapping::default(),
+            ConflictPolicy::default(),
+        )
+        .expect("created");
+
+        let deleted = apply_event(
+            &conn,
+            &issue_event("jira:issue_deleted", "Open"),
+            &[],
+            &JiraFieldMapping::default(),
+            ConflictPolicy::default(),
+        )
+        .expect("deleted");
+        assert_eq!(deleted, WebhookOutcome::Deleted("OPS-7".to_string()));
+        assert!(get_tickets(&conn, &TicketFilter::default())
+            .expect("tickets")
+            .is_empty());
+    }
+
+    #[test]
+    fn account_events_use_the_account_ticket_key() {
+        let conn = setup_db();
+        let account = insert_jira_account(
+            &conn,
+            "EU",
+            "https://eu.example.net/",
+            "ops@example.net",
+            JiraAuthMethod::Basic,
+        )
+        .expect("account");
+        let account_key = account_ticket_key(account.id, "OPS-7");
+        let apply = |event: &str, site: &str| {
+            apply_event(
+                &conn,
+                &site_issue_event(site, event, "Open"),
+                &[],
+                &JiraFieldMapping::default(),
+                ConflictPolicy::default(),
+            )
+            .expect("applied")
+        };
+
+        assert_eq!(
+            apply("jira:issue_created", "https://eu.example.net"),
+            WebhookOutcome::Upserted(account_key.clone())
+        );
+        // The primary site's OPS-7 is a different issue
+        assert_eq!(
+            apply("jira:issue_created", "https://acme.atlassian.net"),
+            WebhookOutcome::Upserted("OPS-7".to_string())
+        );
+        let tickets = get_tickets(&conn, &TicketFilter::default()).expect("tickets");
+        let stored = tickets
+            .iter()
+            .find(|t| t.jira_key == account_key)
+            .expect("account ticket");
+        assert_eq!(stored.account_id, Some(account.id));
+        assert_eq!(tickets.len(), 2);
+
+        assert_eq!(
+            apply("jira:issue_deleted", "https://eu.example.net"),
+            WebhookOutcome::Deleted(account_key)
+        );
+        let tickets = get_tickets(&conn, &TicketFilter::default()).expect("tickets");
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets[0].jira_key, "OPS-7");
+    }
+
+    #[test]
+    fn unrelated_events_are_ignored() {
+        let conn = setup_db();
+        let body = br#"{"webhookEvent": "comment_created", "comment": {}}"#;
+        assert_eq!(
+            apply_event(
+                &conn,
+                body,
+                &[],
+                &JiraFieldMapping::default(),
+                ConflictPolicy::default()
+            )
+            .expect("ignored"),
+            WebhookOutcome::Ignored
+        );
+        assert!(apply_event(
+            &conn,
+            b"not json",
+            &[],
+            &JiraFieldMapping::default(),
+            ConflictPolicy::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn authorize_checks_path_and_secret() {
+        assert_eq!(authorize("/other?secret=s3cret", "s3cret"), Err(404));
+        assert_eq!(authorize("/webhook/jira?secret=s3cret", "s3cret"), Ok(()));
+        assert_eq!(authorize("/webhook/jira?secret=s3%2Bc", "s3+c"), Ok(()));
+        assert_eq!(authorize("/webhook/jira?secret=nope", "s3cret"), Err(401));
+        assert_eq!(authorize("/webhook/jira?secret=", "s3cret"), Err(401));
+        assert_eq!(authorize("/webhook/jira", "s3cret"), Err(401));
+    }
+}
@@ -6,13 +6,17 @@ import { PriorityChart } from './PriorityChart';
 import { CategoryChart } from './CategoryChart';
 import { TimelineChart } from './TimelineChart';
 import { ResolutionTimeChart } from './ResolutionTimeChart';
+import { DurationMode } from '../../types/aggregation';
 
 export function DashboardView() {
-  const { aggregations, isLoadingAggregations, fetchAggregations } = useAppStore((s) => ({
-    aggregations: s.aggregations,
-    isLoadingAggregations: s.isLoadingAggregations,
-    fetchAggregations: s.fetchAggregations,
-  }));
+  const { aggregations, isLoadingAggregations, fetchAggregations, durationMode, setDurationMode } =
+    useAppStore((s) => ({
+      aggregations: s.aggregations,
+      isLoadingAggregations: s.isLoadingAggregations,
+      fetchAggregations: s.fetchAggregations,
+      durationMode: s.durationMode,
+      setDurationMode: s.setDurationMode,
+    }));
 
   useEffect(() => {
     fetchAggregations();
@@ -49,12 +53,22 @@ export function DashboardView() {
     <div>
       <div className="flex justify-between items-center mb-6 no-print">
         <h2 className="text-2xl font-bold">Dashboard</h2>
-        <button
-          onClick={() => window.print()}
-          className="px-4 py-2 bg-[--color-primary] text-white rounded hover:opacity-80"
-        >
-          Export PDF
-        </button>
+        <div className="flex gap-2">
+          <select
+            value={durationMode}
+            onChange={(e) => setDurationMode(e.target.value as DurationMode)}
+            className="px-3 py-2 bg-[--color-surface] border border-gray-700 rounded text-[--color-text]"
+          >
+            <option value="business">Business hours</option>
+            <option value="calendar">Calendar hours</option>
+          </select>
+          <button
+            onClick={() => window.print()}
+            className="px-4 py-2 bg-[--color-primary] text-white rounded hover:opacity-80"
+          >
+            Export PDF
+          </button>
+        </div>
       </div>
 
       <SummaryCards stats={aggregations.summary} />
@@ -6,7 +6,7 @@ interface SummaryCardsProps {
 
 export function SummaryCards({ stats }: SummaryCardsProps) {
   return (
-    <div className="grid grid-cols-4 gap-4 mb-8">
+    <div className="grid grid-cols-6 gap-4 mb-8">
       <div className="bg-[--color-surface-alt] p-4 rounded">
         <div className="text-[--color-text-muted] text-sm">Total Tickets</div>
         <div className="text-2xl font-bold">{stats.total_tickets}</div>
@@ -19,6 +19,14 @@ export function SummaryCards({ stats }: SummaryCardsProps) {
         <div className="text-[--color-text-muted] text-sm">Resolved</div>
         <div className="text-2xl font-bold text-[--color-success]">{stats.resolved_tickets}</div>
       </div>
+      <div className="bg-[--color-surface-alt] p-4 rounded">
+        <div className="text-[--color-text-muted] text-sm">Stale (7d+)</div>
+        <div className="text-2xl font-bold text-[--color-warning]">{stats.stale_tickets}</div>
+      </div>
+      <div className="bg-[--color-surface-alt] p-4 rounded">
+        <div className="text-[--color-text-muted] text-sm">Blocked</div>
+        <div className="text-2xl font-bold text-[--color-danger]">{stats.blocked_tickets}</div>
+      </div>
       <div className="bg-[--color-surface-alt] p-4 rounded">
         <div className="text-[--color-text-muted] text-sm">Avg Resolution</div>
         <div className="text-2xl font-bold">
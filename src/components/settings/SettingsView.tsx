@@ -2,9 +2,12 @@ import { useState, useEffect, useCallback } from 'react';
 import { useAppStore } from '../../stores/useAppStore';
 import { invokeCommand } from '../../hooks/useTauriInvoke';
 
+type JiraAuthMethod = 'basic' | 'pat';
+
 interface JiraSettings {
   jira_url: string;
   email: string;
+  auth_method: JiraAuthMethod;
 }
 
 export function SettingsView() {
@@ -15,6 +18,7 @@ export function SettingsView() {
 
   const [jiraUrl, setJiraUrl] = useState('');
   const [email, setEmail] = useState('');
+  const [authMethod, setAuthMethod] = useState<JiraAuthMethod>('basic');
   const [token, setToken] = useState('');
   const [saveStatus, setSaveStatus] = useState<string | null>(null);
 
@@ -24,6 +28,7 @@ export function SettingsView() {
       if (settings) {
         setJiraUrl(settings.jira_url);
         setEmail(settings.email);
+        setAuthMethod(settings.auth_method ?? 'basic');
       }
     } catch (error) {
       console.error('Failed to load settings:', error);
@@ -42,6 +47,7 @@ export function SettingsView() {
       await invokeCommand('save_jira_settings', {
         jiraUrl,
         email,
+        authMethod,
       });
 
       // Save token to keyring
@@ -78,6 +84,18 @@ export function SettingsView() {
           />
         </div>
 
+        <div className="mb-4">
+          <label className="block text-sm text-[--color-text-muted] mb-2">Authentication</label>
+          <select
+            value={authMethod}
+            onChange={(e) => setAuthMethod(e.target.value as JiraAuthMethod)}
+            className="w-full px-3 py-2 bg-[--color-surface] border border-gray-700 rounded text-[--color-text]"
+          >
+            <option value="basic">Email + API token (Jira Cloud)</option>
+            <option value="pat">Personal Access Token (Server / Data Center)</option>
+          </select>
+        </div>
+
         <div className="mb-4">
           <label className="block text-sm text-[--color-text-muted] mb-2">Email</label>
           <input
@@ -1,5 +1,27 @@
-import { invoke } from '@tauri-apps/api/core';
+import { Channel, invoke } from '@tauri-apps/api/core';
+import type { Ticket, TicketChunk, TicketFilter } from '../types/ticket';
 
 export async function invokeCommand<T>(command: string, args?: Record<string, unknown>): Promise<T> {
   return await invoke<T>(command, args);
 }
+
+/**
+ * Loads tickets through `stream_tickets`, calling `onChunk` as each page
+ * arrives. Resolves with the total once the last chunk has been handled.
+ */
+export function streamTickets(
+  onChunk: (tickets: Ticket[]) => void,
+  filter?: TicketFilter,
+  chunkSize?: number
+): Promise<number> {
+  return new Promise((resolve, reject) => {
+    const channel = new Channel<TicketChunk>();
+    channel.onmessage = (chunk) => {
+      onChunk(chunk.tickets);
+      if (chunk.done) {
+        resolve(chunk.offset + chunk.tickets.length);
+      }
+    };
+    invoke<number>('stream_tickets', { filter, chunkSize, onChunk: channel }).catch(reject);
+  });
+}
@@ -1,5 +1,5 @@
 import { Ticket } from '../types/ticket';
-import { AggregationResult } from '../types/aggregation';
+import { AggregationResult, DurationMode } from '../types/aggregation';
 
 export interface FilterState {
   dateRange: { start: string; end: string } | null;
@@ -17,9 +17,11 @@ export interface TicketSlice {
   tickets: Ticket[];
   aggregations: AggregationResult | null;
   isLoadingAggregations: boolean;
+  durationMode: DurationMode;
   error: string | null;
   fetchTickets: () => Promise<void>;
   fetchAggregations: () => Promise<void>;
+  setDurationMode: (mode: DurationMode) => Promise<void>;
 }
 
 export interface SyncProgress {
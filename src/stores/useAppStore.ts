@@ -13,6 +13,7 @@ export const useAppStore = create<AppStore>((set, get) => ({
   tickets: [],
   aggregations: null,
   isLoadingAggregations: false,
+  durationMode: 'business',
   error: null,
   fetchTickets: async () => {
     try {
@@ -25,12 +26,18 @@ export const useAppStore = create<AppStore>((set, get) => ({
   fetchAggregations: async () => {
     set({ isLoadingAggregations: true });
     try {
-      const aggregations = await invokeCommand<AggregationResult>('get_dashboard_data');
+      const aggregations = await invokeCommand<AggregationResult>('get_dashboard_data', {
+        durationMode: get().durationMode,
+      });
       set({ aggregations, isLoadingAggregations: false, error: null });
     } catch (error) {
       set({ isLoadingAggregations: false, error: String(error) });
     }
   },
+  setDurationMode: async (mode) => {
+    set({ durationMode: mode });
+    await get().fetchAggregations();
+  },
 
   // Sync
   syncStatus: 'idle',
@@ -41,9 +48,11 @@ export const useAppStore = create<AppStore>((set, get) => ({
     set({ syncStatus: 'syncing', syncError: null, syncProgress: null });
     try {
       // Load settings from store
-      const settings = await invokeCommand<{ jira_url: string; email: string } | null>(
-        'load_jira_settings',
-      );
+      const settings = await invokeCommand<{
+        jira_url: string;
+        email: string;
+        auth_method?: 'basic' | 'pat';
+      } | null>('load_jira_settings');
 
       if (!settings) {
         throw new Error('No Jira settings found. Please configure in Settings.');
@@ -59,6 +68,7 @@ export const useAppStore = create<AppStore>((set, get) => ({
         {
           jiraUrl: settings.jira_url,
           email: settings.email,
+          authMethod: settings.auth_method ?? 'basic',
           categoryRulesJson: categoryRules,
         },
       );
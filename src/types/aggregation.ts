@@ -1,10 +1,25 @@
+import { Comparison } from './alerts';
+import { Ticket } from './ticket';
+
+export type DurationMode = 'business' | 'calendar';
+
 export interface AggregationResult {
   tickets_by_status: CountEntry[];
   tickets_by_priority: CountEntry[];
   tickets_by_category: CountEntry[];
+  tickets_by_local_tag: CountEntry[];
+  tickets_by_component: CountEntry[];
   tickets_over_time: TimeSeriesEntry[];
   resolution_time_by_priority: AvgEntry[];
+  resolution_time_by_assignee: AvgEntry[];
+  resolution_time_by_component: AvgEntry[];
+  created_resolved_ratio: RatioEntry[];
+  velocity_by_sprint: VelocityEntry[];
+  points_by_assignee: PointsEntry[];
+  tickets_by_customer: CustomerStats[];
+  tickets_by_affected_version: AffectedVersionStats[];
   summary: SummaryStats;
+  custom_metrics: CustomMetric[];
 }
 
 export interface CountEntry {
@@ -18,10 +33,20 @@ export interface TimeSeriesEntry {
   resolved: number;
 }
 
+export interface RatioEntry {
+  week: string;
+  created: number;
+  resolved: number;
+  ratio: number | null;
+}
+
 export interface AvgEntry {
   name: string;
   avg_hours: number;
   median_hours: number;
+  p75_hours: number;
+  p90_hours: number;
+  p95_hours: number;
   count: number;
 }
 
@@ -29,6 +54,178 @@ export interface SummaryStats {
   total_tickets: number;
   open_tickets: number;
   resolved_tickets: number;
+  stale_tickets: number;
+  blocked_tickets: number;
+  overdue_tickets: number;
   avg_resolution_hours: number;
   median_resolution_hours: number;
+  p75_resolution_hours: number;
+  p90_resolution_hours: number;
+  p95_resolution_hours: number;
+  escalated_tickets: number;
+  escalation_rate: number;
+  escalated_avg_resolution_hours: number;
+  escalated_median_resolution_hours: number;
+}
+
+export interface StaleTicketGroup {
+  assignee: string;
+  tickets: Ticket[];
+}
+
+export interface VelocityEntry {
+  sprint: string;
+  committed_points: number;
+  completed_points: number;
+  tickets: number;
+}
+
+export interface PointsEntry {
+  assignee: string;
+  open_points: number;
+  completed_points: number;
+  tickets: number;
+}
+
+export interface ProjectStats {
+  project_key: string;
+  total_tickets: number;
+  open_tickets: number;
+  resolved_tickets: number;
+  avg_resolution_hours: number;
+  sla_compliance: number | null;
 }
+
+export interface CustomerStats {
+  customer: string;
+  total_tickets: number;
+  open_tickets: number;
+  avg_resolution_hours: number;
+}
+
+export interface AffectedVersionStats {
+  version: string;
+  total_tickets: number;
+  open_tickets: number;
+  avg_resolution_hours: number;
+}
+
+export interface Dashboard {
+  id: number;
+  name: string;
+  layout: unknown;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface ReportSnapshot {
+  id: number;
+  period: string;
+  taken_at: string;
+}
+
+export interface MetricDelta {
+  name: string;
+  from: number;
+  to: number;
+  change: number;
+}
+
+export interface SnapshotDiff {
+  from: ReportSnapshot;
+  to: ReportSnapshot;
+  summary: MetricDelta[];
+  by_status: MetricDelta[];
+  by_priority: MetricDelta[];
+  by_category: MetricDelta[];
+}
+
+export interface ReportPeriod {
+  label: string;
+  from: string;
+  to: string;
+}
+
+export interface PeriodComparison {
+  a: ReportPeriod;
+  b: ReportPeriod;
+  summary: MetricDelta[];
+  top_categories: MetricDelta[];
+}
+
+export type HistoryRange = 'month' | 'quarter' | 'year';
+
+export interface StatsHistoryEntry {
+  recorded_at: string;
+  total_tickets: number;
+  open_tickets: number;
+  resolved_tickets: number;
+  stale_tickets: number;
+  avg_resolution_hours: number;
+  median_resolution_hours: number;
+}
+
+export type GoalMetric =
+  | 'open_tickets'
+  | 'stale_tickets'
+  | 'resolved_tickets'
+  | 'avg_resolution_hours'
+  | 'median_resolution_hours';
+
+export interface MetricGoal {
+  id: number;
+  metric: GoalMetric;
+  op: Comparison;
+  target: number;
+  deadline: string;
+  created_at: string;
+}
+
+export interface MetricGoalInput {
+  metric: GoalMetric;
+  op: Comparison;
+  target: number;
+  deadline: string;
+}
+
+export interface GoalProgress {
+  goal: MetricGoal;
+  current: number;
+  met: boolean;
+  trend_per_week: number | null;
+  projected: number | null;
+  on_track: boolean;
+  days_left: number;
+}
+
+export interface CustomMetric {
+  id: string;
+  label: string;
+  value: number | null;
+}
+
+export interface ReportTicket {
+  jira_key: string;
+  summary: string;
+  priority: string;
+  status: string;
+  age_days: number | null;
+  resolution_hours: number | null;
+}
+
+export interface PersonReport {
+  assignee: string;
+  from: string;
+  to: string;
+  resolved_count: number;
+  resolved_per_week: number;
+  median_resolution_hours: number;
+  avg_resolution_hours: number;
+  open_tickets: number;
+  load_by_priority: CountEntry[];
+  aging: ReportTicket[];
+  notable: ReportTicket[];
+  markdown: string;
+}
+
+export type ReportFormat = 'markdown' | 'pdf';
@@ -0,0 +1,111 @@
+import { TicketFilter } from './ticket';
+
+export interface TicketMatch {
+  status?: string | null;
+  priority?: string | null;
+  assignee?: string | null;
+  project_key?: string | null;
+  open_only?: boolean;
+}
+
+export type Comparison = 'gt' | 'gte' | 'lt' | 'lte' | 'eq';
+
+export type AlertCondition =
+  | { kind: 'count'; tickets?: TicketMatch; op: Comparison; threshold: number }
+  | { kind: 'idle'; tickets?: TicketMatch; days: number }
+  | { kind: 'backlog_growth'; threshold: number; weeks: number }
+  | { kind: 'inflow_spike'; z_threshold: number };
+
+export type AlertChannel = 'desktop' | 'slack';
+
+export interface AlertRule {
+  id: number;
+  name: string;
+  condition: AlertCondition;
+  channel: AlertChannel;
+  throttle_minutes: number;
+  enabled: boolean;
+  last_fired_at: string | null;
+}
+
+export interface AlertRuleInput {
+  name: string;
+  condition: AlertCondition;
+  channel?: AlertChannel;
+  throttle_minutes?: number;
+  enabled?: boolean;
+}
+
+export type AutomationAction =
+  | { type: 'add_tag'; tag: string }
+  | { type: 'watch' }
+  | { type: 'notify'; channel?: AlertChannel }
+  | { type: 'comment'; body: string }
+  | { type: 'transition'; transition_id: string };
+
+export interface Automation {
+  id: number;
+  name: string;
+  filter: TicketFilter;
+  actions: AutomationAction[];
+  enabled: boolean;
+  created_at: string;
+}
+
+export interface AutomationInput {
+  name: string;
+  filter: TicketFilter;
+  actions: AutomationAction[];
+  enabled?: boolean;
+}
+
+export type AutomationOutcome = 'applied' | 'queued' | 'skipped' | 'failed';
+
+export interface AutomationLogEntry {
+  id: number;
+  automation_id: number;
+  automation_name: string;
+  ticket_key: string;
+  action: AutomationAction;
+  outcome: AutomationOutcome;
+  detail: string | null;
+  ran_at: string;
+}
+
+export interface AutomationSettings {
+  write_back: boolean;
+}
+
+export type WebhookEvent = 'alert_fired' | 'sync_completed';
+
+export interface OutboundWebhook {
+  id: number;
+  name: string;
+  url: string;
+  events: WebhookEvent[];
+  enabled: boolean;
+  created_at: string;
+}
+
+export interface OutboundWebhookInput {
+  name: string;
+  url: string;
+  events: WebhookEvent[];
+  enabled?: boolean;
+}
+
+export type SmtpSecurity = 'starttls' | 'tls' | 'none';
+
+export interface SmtpSettings {
+  host: string;
+  port: number;
+  security: SmtpSecurity;
+  username: string | null;
+  from: string;
+  to: string[];
+}
+
+export interface DigestEmail {
+  subject: string;
+  body: string;
+}
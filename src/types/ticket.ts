@@ -3,7 +3,9 @@ export interface Ticket {
   jira_key: string;
   summary: string;
   status: string;
+  status_category: StatusCategory | null;
   priority: string;
+  priority_level: PriorityLevel | null;
   issue_type: string;
   assignee: string | null;
   reporter: string | null;
@@ -13,4 +15,714 @@ export interface Ticket {
   labels: string;
   project_key: string;
   category: string | null;
+  category_confidence: number | null;
+  source: string;
+  custom_fields: string;
+  account_id: number | null;
+  story_points: number | null;
+  sprint: string | null;
+  due_date: string | null;
+  customer: string | null;
+  environment: string | null;
+  escalated: boolean;
+  components: string[];
+  fix_versions: string[];
+  affected_versions: string[];
+}
+
+export interface TicketTransition {
+  id: string;
+  name: string;
+  to_status: string;
+  resolves: boolean;
+}
+
+export interface NewIssue {
+  project_key: string;
+  issue_type: string;
+  summary: string;
+  description?: string | null;
+  priority?: string | null;
+  labels?: string[];
+}
+
+export interface JiraValidationErrors {
+  errorMessages: string[];
+  errors: Record<string, string>;
+}
+
+export type CreateIssueOutcome =
+  | { status: 'created'; ticket: Ticket | null }
+  | { status: 'rejected'; errors: JiraValidationErrors };
+
+/** ISO 8601 bounds; `from` is inclusive, `to` exclusive. */
+export interface DateRange {
+  from?: string | null;
+  to?: string | null;
+}
+
+export interface TicketFilter {
+  account_id?: number | null;
+  tags?: string[];
+  include_archived?: boolean;
+  hide_snoozed?: boolean;
+  statuses?: string[];
+  priorities?: string[];
+  assignees?: string[];
+  project_keys?: string[];
+  teams?: string[];
+  created?: DateRange | null;
+  resolved?: DateRange | null;
+  query?: string | null;
+}
+
+export interface FieldChange {
+  field: string;
+  from: string | null;
+  to: string | null;
+}
+
+export interface WatchlistChange {
+  jira_key: string;
+  summary: string;
+  changed_at: string | null;
+  changes: FieldChange[];
+}
+
+export interface TicketSnooze {
+  ticket_key: string;
+  summary: string;
+  until: string;
+  snoozed_at: string;
+}
+
+export interface StandupItem {
+  jira_key: string;
+  summary: string;
+  status: string;
+  note: string | null;
+}
+
+export interface Standup {
+  date: string;
+  assignee: string;
+  since: string;
+  resolved: StandupItem[];
+  in_progress: StandupItem[];
+  blockers: StandupItem[];
+  markdown: string;
+}
+
+export type Granularity = 'week' | 'month';
+
+export interface ViewSort {
+  column: string;
+  descending?: boolean;
+}
+
+export interface SavedView {
+  id: number;
+  name: string;
+  filter: TicketFilter;
+  sort: ViewSort | null;
+  granularity: Granularity;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface SavedViewInput {
+  name: string;
+  filter?: TicketFilter;
+  sort?: ViewSort | null;
+  granularity?: Granularity;
+}
+
+export interface DatasetSummary {
+  tables: Record<string, number>;
+  settings: string[];
+}
+
+export interface CsvMapping {
+  key: string;
+  summary: string;
+  created_at: string;
+  status?: string | null;
+  priority?: string | null;
+  issue_type?: string | null;
+  assignee?: string | null;
+  reporter?: string | null;
+  updated_at?: string | null;
+  resolved_at?: string | null;
+  labels?: string | null;
+  project_key?: string | null;
+  story_points?: string | null;
+  sprint?: string | null;
+  default_project?: string | null;
+  key_prefix?: string | null;
+  source?: string;
+  date_format?: string | null;
+  delimiter?: string;
+}
+
+export interface CsvRowError {
+  line: number;
+  message: string;
+}
+
+export interface CsvImportSummary {
+  imported: number;
+  skipped: CsvRowError[];
+}
+
+export type DuplicateStatus = 'pending' | 'merged' | 'dismissed';
+
+export interface DuplicateCandidate {
+  id: number;
+  ticket_a: string;
+  summary_a: string | null;
+  source_a: string | null;
+  ticket_b: string;
+  summary_b: string | null;
+  source_b: string | null;
+  similarity: number;
+  status: DuplicateStatus;
+  detected_at: string;
+  resolved_at: string | null;
+}
+
+export interface LinkedTicket {
+  jira_key: string;
+  link_type: string;
+  relation: string;
+  summary: string | null;
+  status: string | null;
+  resolved: boolean;
+  local: boolean;
+}
+
+export type LocalLinkKind = 'follow-up-of' | 'caused-by' | 'relates-to';
+
+export interface FollowUp {
+  jira_key: string;
+  summary: string;
+  status: string;
+  assignee: string | null;
+}
+
+export interface IncidentFollowUps {
+  incident_key: string;
+  incident_summary: string | null;
+  incident_resolved: boolean;
+  total_follow_ups: number;
+  open_follow_ups: FollowUp[];
+}
+
+export interface ReleaseReadiness {
+  version: string;
+  total_tickets: number;
+  open_tickets: number;
+  done_tickets: number;
+  blockers: Ticket[];
+  throughput_per_week: number;
+  estimated_completion: string | null;
+}
+
+export interface BurndownPoint {
+  date: string;
+  open_tickets: number;
+}
+
+export interface CapacityScenario {
+  extra_people: number;
+  people: number;
+  throughput_per_week: number;
+  net_per_week: number;
+  burndown: BurndownPoint[];
+  clears_by: string | null;
+}
+
+export interface CapacitySimulation {
+  open_tickets: number;
+  active_people: number;
+  throughput_per_person_per_week: number;
+  inflow_per_week: number;
+  history_weeks: number;
+  scenarios: CapacityScenario[];
+}
+
+export interface Attachment {
+  id: string;
+  ticket_key: string;
+  filename: string;
+  size_bytes: number;
+  mime_type: string | null;
+  author: string | null;
+  created_at: string | null;
+  content_url: string;
+}
+
+export interface TicketComment {
+  author: string | null;
+  created_at: string;
+  body: string;
+}
+
+export interface TicketContent {
+  jira_key: string;
+  description: string | null;
+  comments: TicketComment[];
+}
+
+export type ConflictPolicy = 'server_wins' | 'local_wins' | 'prompt';
+
+export type LocalField = 'status';
+
+export interface LocalChange {
+  ticket_key: string;
+  field: LocalField;
+  local_value: string | null;
+  base_value: string | null;
+  conflicting_value: string | null;
+  changed_at: string;
+}
+
+export type OutboxAction =
+  | { type: 'comment'; body: string }
+  | { type: 'transition'; transition_id: string }
+  | { type: 'assign'; assignee: string | null };
+
+export interface OutboxItem {
+  id: number;
+  ticket_key: string;
+  action: OutboxAction;
+  status: 'pending' | 'failed';
+  attempts: number;
+  last_error: string | null;
+  created_at: string;
+  last_attempt_at: string | null;
+  next_attempt_at: string | null;
+}
+
+export interface OutboxFlushSummary {
+  sent: number;
+  failed: number;
+  pending: number;
+}
+
+export interface SyncItemFailure {
+  key: string | null;
+  error: string;
+}
+
+export interface SyncRun {
+  id: number;
+  source: string;
+  started_at: string;
+  finished_at: string | null;
+  status: 'running' | 'succeeded' | 'failed';
+  tickets_fetched: number;
+  tickets_updated: number;
+  tickets_failed: number;
+  error: string | null;
+  failures: SyncItemFailure[];
+}
+
+export interface FieldChange {
+  field: string;
+  before: unknown;
+  after: unknown;
+}
+
+export interface TicketPreview {
+  jira_key: string;
+  summary: string;
+  action: 'insert' | 'update';
+  changes: FieldChange[];
+}
+
+export interface SyncPreview {
+  source: string;
+  fetched: number;
+  inserts: number;
+  updates: number;
+  unchanged: number;
+  failures: SyncItemFailure[];
+  samples: TicketPreview[];
+}
+
+export interface TicketChange {
+  id: number;
+  ticket_key: string;
+  field: string;
+  old_value: unknown;
+  new_value: unknown;
+  changed_at: string;
+  sync_run_id: number | null;
+}
+
+export interface RetentionSettings {
+  resolved_retention_months: number | null;
+}
+
+export interface ArchiveSummary {
+  archived: number;
+  total_archived: number;
+}
+
+export interface QueryPlan {
+  name: string;
+  sql: string;
+  plan: string[];
+}
+
+export interface TicketChunk {
+  offset: number;
+  tickets: Ticket[];
+  done: boolean;
+}
+
+/** A ticket reduced to the fields passed to `get_tickets_projection`. */
+export type TicketProjection<K extends keyof Ticket> = Pick<Ticket, K>;
+
+export type Dimension =
+  | 'status'
+  | 'priority'
+  | 'assignee'
+  | 'project'
+  | 'label'
+  | 'component'
+  | 'category'
+  | 'team'
+  | 'customer'
+  | 'affected_version'
+  | 'environment'
+  | 'root_cause'
+  | 'created_week'
+  | 'resolved_week'
+  | { custom_field: string };
+
+export type Metric = 'count' | 'avg_resolution' | 'p90_resolution';
+
+export interface AggregateEntry {
+  name: string;
+  value: number;
+  count: number;
+}
+
+export interface PivotTable {
+  rows: string[];
+  columns: string[];
+  /** `values[r][c]` is the metric for `rows[r]` × `columns[c]`. */
+  values: (number | null)[][];
+}
+
+export interface InflowAnomaly {
+  date: string;
+  created: number;
+  baseline_mean: number;
+  baseline_stddev: number;
+  z_score: number;
+}
+
+export interface TrendingTerm {
+  term: string;
+  recent_count: number;
+  previous_count: number;
+  score: number;
+}
+
+export interface SimilarTicket {
+  ticket: Ticket;
+  score: number;
+}
+
+export type CategorizerKind = 'rules' | 'embedding';
+
+export interface CategorizerSettings {
+  backend: CategorizerKind;
+  endpoint: string | null;
+  model: string | null;
+  min_confidence: number;
+}
+
+export interface TicketCluster {
+  label: string;
+  terms: string[];
+  size: number;
+  representatives: Ticket[];
+  ticket_keys: string[];
+}
+
+export type StatusCategory = 'todo' | 'in_progress' | 'done';
+
+export interface StatusCategoryMapping {
+  status: string;
+  category: StatusCategory;
+}
+
+export type PriorityLevel = 'Critical' | 'High' | 'Medium' | 'Low';
+
+export interface PriorityMapping {
+  priority: string;
+  project_key: string | null;
+  level: PriorityLevel;
+}
+
+export interface StatusDwellViolation {
+  jira_key: string;
+  summary: string;
+  status: string;
+  entered_at: string;
+  left_at: string | null;
+  dwell_hours: number;
+  limit_hours: number;
+}
+
+export interface Rotation {
+  id: number;
+  person: string;
+  start_at: string;
+  end_at: string;
+}
+
+export interface RotationStats {
+  rotation: Rotation;
+  created: number;
+  resolved: number;
+  open: number;
+  critical: number;
+  created_per_day: number;
+  avg_resolution_hours: number | null;
+}
+
+export interface Team {
+  id: number;
+  name: string;
+  members: string[];
+}
+
+export interface TeamInput {
+  name: string;
+  members: string[];
+}
+
+export interface WorkWindow {
+  start_minute: number;
+  end_minute: number;
+}
+
+export interface WorkSchedule {
+  /** Monday first; null marks a day off */
+  days: (WorkWindow | null)[];
+  utc_offset_minutes: number;
+}
+
+export interface SupportCalendar {
+  id: number;
+  name: string;
+  schedule: WorkSchedule;
+  projects: string[];
+}
+
+export interface SupportCalendarInput {
+  name: string;
+  schedule: WorkSchedule;
+  projects: string[];
+}
+
+export interface TeamRollup {
+  team: string;
+  open_tickets: number;
+  resolved_tickets: number;
+  throughput_per_week: number;
+  avg_resolution_hours: number | null;
+  p90_resolution_hours: number | null;
+}
+
+export interface RootCause {
+  id: number;
+  name: string;
+  description: string | null;
+}
+
+export interface RootCauseInput {
+  name: string;
+  description?: string | null;
+}
+
+export interface RootCauseEntry {
+  root_cause: string;
+  count: number;
+  share: number;
+  avg_resolution_hours: number;
+}
+
+export interface RootCauseBreakdown {
+  classified: number;
+  unclassified: number;
+  causes: RootCauseEntry[];
+}
+
+export interface User {
+  account_id: string;
+  display_name: string;
+  avatar_url: string | null;
+  active: boolean;
+}
+
+export interface IconImage {
+  data_url: string;
+  stale: boolean;
+}
+
+export type LinkFormat = 'url' | 'markdown' | 'slack';
+
+export type QuickSearchKind = 'key' | 'text' | 'note' | 'recent';
+
+export interface QuickSearchResult {
+  kind: QuickSearchKind;
+  ticket: Ticket;
+}
+
+export interface RecentlyViewed {
+  ticket: Ticket;
+  viewed_at: string;
+}
+
+export interface TicketNote {
+  id: number;
+  ticket_key: string;
+  body: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface TimeEntry {
+  id: number;
+  ticket_key: string;
+  started_at: string;
+  ended_at: string | null;
+  worklog_id: string | null;
+  focus_minutes: number | null;
+  interruptions: number;
+}
+
+export type TimeReportPeriod = 'day' | 'week';
+
+export interface TimeReportRow {
+  period_start: string;
+  ticket_key: string;
+  seconds: number;
+}
+
+export interface FocusTotals {
+  sessions: number;
+  completed: number;
+  interruptions: number;
+  focus_seconds: number;
+}
+
+export interface FocusTicketStats extends FocusTotals {
+  ticket_key: string;
+}
+
+export interface FocusStats extends FocusTotals {
+  tickets: FocusTicketStats[];
+}
+
+export interface WipLimit {
+  category: StatusCategory;
+  max_tickets: number;
+}
+
+export interface BoardColumn {
+  category: StatusCategory;
+  tickets: Ticket[];
+  wip_limit: number | null;
+  over_wip_limit: boolean;
+}
+
+export interface ScheduledWork {
+  id: number;
+  ticket_key: string;
+  date: string;
+  note: string | null;
+  created_at: string;
+}
+
+export type CalendarEntryKind = 'sprint_start' | 'due' | 'scheduled' | 'sprint_end';
+
+export interface CalendarEntry {
+  date: string;
+  kind: CalendarEntryKind;
+  title: string;
+  ticket_key: string | null;
+  scheduled_work_id: number | null;
+}
+
+export interface CohortRow {
+  cohort: string;
+  tickets: number;
+  /** Parallel to `CohortMatrix.window_days`. */
+  resolved_within: (number | null)[];
+}
+
+export interface CohortMatrix {
+  window_days: number[];
+  cohorts: CohortRow[];
+}
+
+export interface HttpApiStatus {
+  port: number;
+  token: string;
+}
+
+export type QueryColumnType = 'integer' | 'real' | 'text' | 'blob' | 'null';
+
+export interface QueryColumn {
+  name: string;
+  column_type: QueryColumnType;
+}
+
+export interface QueryResult {
+  columns: QueryColumn[];
+  rows: unknown[][];
+  truncated: boolean;
+  elapsed_ms: number;
+}
+
+export interface WallboardKpis {
+  open_tickets: number;
+  open_criticals: number;
+  /** "Today" is the local day at the `utc_offset_minutes` passed, UTC by default. */
+  created_today: number;
+  resolved_today: number;
+  sla_breached: number;
+}
+
+export interface SlaCountdown {
+  jira_key: string;
+  summary: string;
+  priority: string;
+  assignee: string | null;
+  target_hours: number;
+  /** Business hours left; negative once breached. */
+  remaining_hours: number;
+}
+
+export interface WallboardTicket {
+  jira_key: string;
+  summary: string;
+  status: string;
+  assignee: string | null;
+  created_at: string;
+}
+
+/** Payload of `get_wallboard_payload` and `wallboard-updated` events. */
+export interface WallboardPayload {
+  view_name: string | null;
+  generated_at: string;
+  kpis: WallboardKpis;
+  sla_countdowns: SlaCountdown[];
+  newest_criticals: WallboardTicket[];
 }